@@ -2,6 +2,7 @@ use core::traits::IndexView;
 
 use core::box::BoxTrait;
 use core::gas::withdraw_gas;
+use core::iter::Iterator;
 use core::option::OptionTrait;
 use core::serde::Serde;
 
@@ -14,6 +15,12 @@ extern fn array_pop_front<T>(ref arr: Array<T>) -> Option<Box<T>> nopanic;
 extern fn array_pop_front_consume<T>(arr: Array<T>) -> Option<(Array<T>, Box<T>)> nopanic;
 extern fn array_snapshot_pop_front<T>(ref arr: @Array<T>) -> Option<Box<@T>> nopanic;
 extern fn array_snapshot_pop_back<T>(ref arr: @Array<T>) -> Option<Box<@T>> nopanic;
+extern fn array_snapshot_multi_pop_front<T>(
+    ref arr: @Array<T>, count: usize
+) -> Option<@Array<T>> implicits(RangeCheck) nopanic;
+extern fn array_snapshot_multi_pop_back<T>(
+    ref arr: @Array<T>, count: usize
+) -> Option<@Array<T>> implicits(RangeCheck) nopanic;
 #[panic_with('Index out of bounds', array_at)]
 extern fn array_get<T>(
     arr: @Array<T>, index: usize
@@ -22,6 +29,13 @@ extern fn array_slice<T>(
     arr: @Array<T>, start: usize, length: usize
 ) -> Option<@Array<T>> implicits(RangeCheck) nopanic;
 extern fn array_len<T>(arr: @Array<T>) -> usize nopanic;
+extern fn array_append_span<T>(
+    ref arr: Array<T>, span: @Array<T>
+) -> Option<()> implicits(RangeCheck, GasBuiltin) nopanic;
+#[panic_with('Out of gas', array_concat_spans)]
+extern fn array_concat<T>(
+    lhs: @Array<T>, rhs: @Array<T>
+) -> Option<Array<T>> implicits(RangeCheck, GasBuiltin) nopanic;
 
 #[generate_trait]
 pub impl ArrayImpl<T> of ArrayTrait<T> {
@@ -42,6 +56,14 @@ pub impl ArrayImpl<T> of ArrayTrait<T> {
             Option::None => {}
         };
     }
+    /// Appends all the elements of `span` to the array, copying them in a single loop.
+    /// Much cheaper than `append_span` for copyable elements.
+    fn extend<+Copy<T>, +Drop<T>>(ref self: Array<T>, span: Span<T>) {
+        match array_append_span(ref self, span.snapshot) {
+            Option::Some(()) => {},
+            Option::None => core::panic_with_felt252('Out of gas'),
+        }
+    }
     #[inline(always)]
     fn pop_front(ref self: Array<T>) -> Option<T> nopanic {
         match array_pop_front(ref self) {
@@ -90,6 +112,13 @@ impl ArrayIndex<T> of IndexView<Array<T>, usize, @T> {
     }
 }
 
+impl ArrayIterator<T> of Iterator<Array<T>, T> {
+    #[inline(always)]
+    fn next(ref self: Array<T>) -> Option<T> {
+        self.pop_front()
+    }
+}
+
 impl ArraySerde<T, +Serde<T>, +Drop<T>> of Serde<Array<T>> {
     fn serialize(self: @Array<T>, ref output: Array<felt252>) {
         self.len().serialize(ref output);
@@ -166,6 +195,26 @@ pub impl SpanImpl<T> of SpanTrait<T> {
         }
     }
     #[inline(always)]
+    fn multi_pop_front(ref self: Span<T>, count: usize) -> Option<Span<T>> {
+        let mut snapshot = self.snapshot;
+        let popped = array_snapshot_multi_pop_front(ref snapshot, count);
+        self = Span { snapshot };
+        match popped {
+            Option::Some(snapshot) => Option::Some(Span { snapshot }),
+            Option::None => Option::None,
+        }
+    }
+    #[inline(always)]
+    fn multi_pop_back(ref self: Span<T>, count: usize) -> Option<Span<T>> {
+        let mut snapshot = self.snapshot;
+        let popped = array_snapshot_multi_pop_back(ref snapshot, count);
+        self = Span { snapshot };
+        match popped {
+            Option::Some(snapshot) => Option::Some(Span { snapshot }),
+            Option::None => Option::None,
+        }
+    }
+    #[inline(always)]
     fn get(self: Span<T>, index: usize) -> Option<Box<@T>> {
         array_get(self.snapshot, index)
     }
@@ -185,6 +234,10 @@ pub impl SpanImpl<T> of SpanTrait<T> {
     fn is_empty(self: Span<T>) -> bool {
         self.len() == 0_usize
     }
+    /// Returns a new array with the elements of `self` followed by the elements of `other`.
+    fn concat<+Copy<T>>(self: Span<T>, other: Span<T>) -> Array<T> {
+        array_concat_spans(self.snapshot, other.snapshot)
+    }
 }
 
 pub impl SpanIndex<T> of IndexView<Span<T>, usize, @T> {
@@ -194,6 +247,13 @@ pub impl SpanIndex<T> of IndexView<Span<T>, usize, @T> {
     }
 }
 
+impl SpanIterator<T> of Iterator<Span<T>, @T> {
+    #[inline(always)]
+    fn next(ref self: Span<T>) -> Option<@T> {
+        self.pop_front()
+    }
+}
+
 // TODO(spapini): Remove TDrop. It is necessary to get rid of response in case of panic.
 impl ArrayTCloneImpl<T, +Clone<T>, +Drop<T>> of Clone<Array<T>> {
     fn clone(self: @Array<T>) -> Array<T> {
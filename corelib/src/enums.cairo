@@ -0,0 +1,36 @@
+//! Conversions between the values of C-like enums - enums whose variants have no data - and the
+//! discriminants of their variants.
+//!
+//! The discriminant of a variant is its explicit discriminant if it has one, and otherwise the
+//! discriminant of the previous variant plus one, starting from 0:
+//! ```
+//! enum Status {
+//!     Ok = 200,
+//!     Created,
+//!     NotFound = 404,
+//! }
+//! ```
+
+/// The conversions of a C-like enum to and from the discriminants of its variants.
+/// Implemented by the compiler for every C-like enum - generic code converting the values of a
+/// generic type should require it:
+/// ```
+/// fn status_code<T, +CLikeEnum<T>>(status: T) -> felt252 {
+///     CLikeEnum::to_felt252(status)
+/// }
+/// ```
+pub trait CLikeEnum<T> {
+    /// Returns the discriminant of the variant of the value.
+    fn to_felt252(self: T) -> felt252 nopanic;
+    /// Returns the value whose variant has the given discriminant, or `None` if no variant has it.
+    fn try_from_felt252(value: felt252) -> Option<T> nopanic;
+}
+
+/// Returns the discriminant of the variant of a value of a C-like enum.
+/// The type must be known to be a C-like enum - generic code should use [CLikeEnum] instead.
+pub extern fn enum_to_felt252<T>(value: T) -> felt252 nopanic;
+
+/// Returns the value of a C-like enum whose variant has the given discriminant, or `None` if no
+/// variant of the enum has it.
+/// The type must be known to be a C-like enum - generic code should use [CLikeEnum] instead.
+pub extern fn enum_try_from_felt252<T>(value: felt252) -> Option<T> nopanic;
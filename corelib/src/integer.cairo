@@ -1839,6 +1839,53 @@ enum SignedIntegerResult<T> {
 }
 impl SignedIntegerResultDrop<T, +Drop<T>> of Drop<SignedIntegerResult<T>>;
 
+/// Returns the quotient and the remainder of a signed division, rounding toward zero, so that the
+/// remainder has the sign of the dividend. Returns `None` if the quotient overflows, which happens
+/// only when the minimal value is divided by -1.
+/// Used for `i128`, as the smaller signed integers have a `div_rem` libfunc.
+fn signed_div_rem<
+    T,
+    +Copy<T>,
+    +Drop<T>,
+    +Into<T, felt252>,
+    +TryInto<felt252, T>,
+    +PartialOrd<T>,
+    +core::num::traits::Zero<T>
+>(
+    lhs: T, rhs: NonZero<T>
+) -> Option<(T, T)> {
+    let rhs: T = rhs.into();
+    let zero = core::num::traits::Zero::zero();
+    let lhs_is_negative = lhs < zero;
+    let rhs_is_negative = rhs < zero;
+    let lhs_abs = signed_abs_as_u128(lhs.into(), lhs_is_negative);
+    let rhs_abs = signed_abs_as_u128(rhs.into(), rhs_is_negative);
+    let (q, r) = u128_safe_divmod(lhs_abs, rhs_abs.try_into().unwrap());
+    let q: felt252 = q.into();
+    let r: felt252 = r.into();
+    let q = if lhs_is_negative == rhs_is_negative {
+        q
+    } else {
+        -q
+    };
+    let r = if lhs_is_negative {
+        -r
+    } else {
+        r
+    };
+    Option::Some((q.try_into()?, r.try_into()?))
+}
+
+/// Returns the absolute value of a signed integer, given as a felt252 along with its sign.
+fn signed_abs_as_u128(value: felt252, is_negative: bool) -> u128 {
+    let abs = if is_negative {
+        -value
+    } else {
+        value
+    };
+    abs.try_into().unwrap()
+}
+
 #[derive(Copy, Drop)]
 pub extern type i8;
 impl NumericLiterali8 of NumericLiteral<i8>;
@@ -1918,6 +1965,60 @@ impl I8MulEq of MulEq<i8> {
     }
 }
 
+#[panic_with('i8 is 0', i8_as_non_zero)]
+fn i8_try_as_non_zero(a: i8) -> Option<NonZero<i8>> nopanic {
+    match i8_is_zero(a) {
+        IsZeroResult::Zero => Option::None,
+        IsZeroResult::NonZero(x) => Option::Some(x),
+    }
+}
+
+impl I8TryIntoNonZero of TryInto<i8, NonZero<i8>> {
+    fn try_into(self: i8) -> Option<NonZero<i8>> {
+        Option::Some(i8_as_non_zero(self))
+    }
+}
+
+/// Returns the quotient and the remainder of the division, rounding toward zero, or `None` if the
+/// quotient overflows - when dividing the minimal value by -1.
+pub extern fn i8_div_rem(
+    lhs: i8, rhs: NonZero<i8>
+) -> Option<(i8, i8)> implicits(RangeCheck) nopanic;
+
+impl I8Div of Div<i8> {
+    fn div(lhs: i8, rhs: i8) -> i8 {
+        let (q, _r) = i8_div_rem(lhs, rhs.try_into().expect('Division by 0'))
+            .expect('i8_div Overflow');
+        q
+    }
+}
+impl I8DivEq of DivEq<i8> {
+    #[inline(always)]
+    fn div_eq(ref self: i8, other: i8) {
+        self = Div::div(self, other);
+    }
+}
+
+impl I8Rem of Rem<i8> {
+    fn rem(lhs: i8, rhs: i8) -> i8 {
+        let (_q, r) = i8_div_rem(lhs, rhs.try_into().expect('Division by 0'))
+            .expect('i8_rem Overflow');
+        r
+    }
+}
+impl I8RemEq of RemEq<i8> {
+    #[inline(always)]
+    fn rem_eq(ref self: i8, other: i8) {
+        self = Rem::rem(self, other);
+    }
+}
+
+impl I8DivRem of DivRem<i8> {
+    fn div_rem(lhs: i8, rhs: NonZero<i8>) -> (i8, i8) {
+        i8_div_rem(lhs, rhs).expect('i8_div Overflow')
+    }
+}
+
 /// If `lhs` >= `rhs` returns `Ok(lhs - rhs)` else returns `Err(2**8 + lhs - rhs)`.
 pub extern fn i8_diff(lhs: i8, rhs: i8) -> Result<u8, u8> implicits(RangeCheck) nopanic;
 impl I8PartialOrd of PartialOrd<i8> {
@@ -2024,6 +2125,60 @@ impl I16MulEq of MulEq<i16> {
     }
 }
 
+#[panic_with('i16 is 0', i16_as_non_zero)]
+fn i16_try_as_non_zero(a: i16) -> Option<NonZero<i16>> nopanic {
+    match i16_is_zero(a) {
+        IsZeroResult::Zero => Option::None,
+        IsZeroResult::NonZero(x) => Option::Some(x),
+    }
+}
+
+impl I16TryIntoNonZero of TryInto<i16, NonZero<i16>> {
+    fn try_into(self: i16) -> Option<NonZero<i16>> {
+        Option::Some(i16_as_non_zero(self))
+    }
+}
+
+/// Returns the quotient and the remainder of the division, rounding toward zero, or `None` if the
+/// quotient overflows - when dividing the minimal value by -1.
+pub extern fn i16_div_rem(
+    lhs: i16, rhs: NonZero<i16>
+) -> Option<(i16, i16)> implicits(RangeCheck) nopanic;
+
+impl I16Div of Div<i16> {
+    fn div(lhs: i16, rhs: i16) -> i16 {
+        let (q, _r) = i16_div_rem(lhs, rhs.try_into().expect('Division by 0'))
+            .expect('i16_div Overflow');
+        q
+    }
+}
+impl I16DivEq of DivEq<i16> {
+    #[inline(always)]
+    fn div_eq(ref self: i16, other: i16) {
+        self = Div::div(self, other);
+    }
+}
+
+impl I16Rem of Rem<i16> {
+    fn rem(lhs: i16, rhs: i16) -> i16 {
+        let (_q, r) = i16_div_rem(lhs, rhs.try_into().expect('Division by 0'))
+            .expect('i16_rem Overflow');
+        r
+    }
+}
+impl I16RemEq of RemEq<i16> {
+    #[inline(always)]
+    fn rem_eq(ref self: i16, other: i16) {
+        self = Rem::rem(self, other);
+    }
+}
+
+impl I16DivRem of DivRem<i16> {
+    fn div_rem(lhs: i16, rhs: NonZero<i16>) -> (i16, i16) {
+        i16_div_rem(lhs, rhs).expect('i16_div Overflow')
+    }
+}
+
 /// If `lhs` >= `rhs` returns `Ok(lhs - rhs)` else returns `Err(2**16 + lhs - rhs)`.
 pub extern fn i16_diff(lhs: i16, rhs: i16) -> Result<u16, u16> implicits(RangeCheck) nopanic;
 impl I16PartialOrd of PartialOrd<i16> {
@@ -2130,6 +2285,60 @@ impl I32MulEq of MulEq<i32> {
     }
 }
 
+#[panic_with('i32 is 0', i32_as_non_zero)]
+fn i32_try_as_non_zero(a: i32) -> Option<NonZero<i32>> nopanic {
+    match i32_is_zero(a) {
+        IsZeroResult::Zero => Option::None,
+        IsZeroResult::NonZero(x) => Option::Some(x),
+    }
+}
+
+impl I32TryIntoNonZero of TryInto<i32, NonZero<i32>> {
+    fn try_into(self: i32) -> Option<NonZero<i32>> {
+        Option::Some(i32_as_non_zero(self))
+    }
+}
+
+/// Returns the quotient and the remainder of the division, rounding toward zero, or `None` if the
+/// quotient overflows - when dividing the minimal value by -1.
+pub extern fn i32_div_rem(
+    lhs: i32, rhs: NonZero<i32>
+) -> Option<(i32, i32)> implicits(RangeCheck) nopanic;
+
+impl I32Div of Div<i32> {
+    fn div(lhs: i32, rhs: i32) -> i32 {
+        let (q, _r) = i32_div_rem(lhs, rhs.try_into().expect('Division by 0'))
+            .expect('i32_div Overflow');
+        q
+    }
+}
+impl I32DivEq of DivEq<i32> {
+    #[inline(always)]
+    fn div_eq(ref self: i32, other: i32) {
+        self = Div::div(self, other);
+    }
+}
+
+impl I32Rem of Rem<i32> {
+    fn rem(lhs: i32, rhs: i32) -> i32 {
+        let (_q, r) = i32_div_rem(lhs, rhs.try_into().expect('Division by 0'))
+            .expect('i32_rem Overflow');
+        r
+    }
+}
+impl I32RemEq of RemEq<i32> {
+    #[inline(always)]
+    fn rem_eq(ref self: i32, other: i32) {
+        self = Rem::rem(self, other);
+    }
+}
+
+impl I32DivRem of DivRem<i32> {
+    fn div_rem(lhs: i32, rhs: NonZero<i32>) -> (i32, i32) {
+        i32_div_rem(lhs, rhs).expect('i32_div Overflow')
+    }
+}
+
 /// If `lhs` >= `rhs` returns `Ok(lhs - rhs)` else returns `Err(2**32 + lhs - rhs)`.
 pub extern fn i32_diff(lhs: i32, rhs: i32) -> Result<u32, u32> implicits(RangeCheck) nopanic;
 impl I32PartialOrd of PartialOrd<i32> {
@@ -2236,6 +2445,60 @@ impl I64MulEq of MulEq<i64> {
     }
 }
 
+#[panic_with('i64 is 0', i64_as_non_zero)]
+fn i64_try_as_non_zero(a: i64) -> Option<NonZero<i64>> nopanic {
+    match i64_is_zero(a) {
+        IsZeroResult::Zero => Option::None,
+        IsZeroResult::NonZero(x) => Option::Some(x),
+    }
+}
+
+impl I64TryIntoNonZero of TryInto<i64, NonZero<i64>> {
+    fn try_into(self: i64) -> Option<NonZero<i64>> {
+        Option::Some(i64_as_non_zero(self))
+    }
+}
+
+/// Returns the quotient and the remainder of the division, rounding toward zero, or `None` if the
+/// quotient overflows - when dividing the minimal value by -1.
+pub extern fn i64_div_rem(
+    lhs: i64, rhs: NonZero<i64>
+) -> Option<(i64, i64)> implicits(RangeCheck) nopanic;
+
+impl I64Div of Div<i64> {
+    fn div(lhs: i64, rhs: i64) -> i64 {
+        let (q, _r) = i64_div_rem(lhs, rhs.try_into().expect('Division by 0'))
+            .expect('i64_div Overflow');
+        q
+    }
+}
+impl I64DivEq of DivEq<i64> {
+    #[inline(always)]
+    fn div_eq(ref self: i64, other: i64) {
+        self = Div::div(self, other);
+    }
+}
+
+impl I64Rem of Rem<i64> {
+    fn rem(lhs: i64, rhs: i64) -> i64 {
+        let (_q, r) = i64_div_rem(lhs, rhs.try_into().expect('Division by 0'))
+            .expect('i64_rem Overflow');
+        r
+    }
+}
+impl I64RemEq of RemEq<i64> {
+    #[inline(always)]
+    fn rem_eq(ref self: i64, other: i64) {
+        self = Rem::rem(self, other);
+    }
+}
+
+impl I64DivRem of DivRem<i64> {
+    fn div_rem(lhs: i64, rhs: NonZero<i64>) -> (i64, i64) {
+        i64_div_rem(lhs, rhs).expect('i64_div Overflow')
+    }
+}
+
 /// If `lhs` >= `rhs` returns `Ok(lhs - rhs)` else returns `Err(2**64 + lhs - rhs)`.
 pub extern fn i64_diff(lhs: i64, rhs: i64) -> Result<u64, u64> implicits(RangeCheck) nopanic;
 impl I64PartialOrd of PartialOrd<i64> {
@@ -2355,6 +2618,54 @@ impl I128MulEq of MulEq<i128> {
     }
 }
 
+#[panic_with('i128 is 0', i128_as_non_zero)]
+fn i128_try_as_non_zero(a: i128) -> Option<NonZero<i128>> nopanic {
+    match i128_is_zero(a) {
+        IsZeroResult::Zero => Option::None,
+        IsZeroResult::NonZero(x) => Option::Some(x),
+    }
+}
+
+impl I128TryIntoNonZero of TryInto<i128, NonZero<i128>> {
+    fn try_into(self: i128) -> Option<NonZero<i128>> {
+        Option::Some(i128_as_non_zero(self))
+    }
+}
+
+impl I128Div of Div<i128> {
+    fn div(lhs: i128, rhs: i128) -> i128 {
+        let (q, _r) = signed_div_rem(lhs, rhs.try_into().expect('Division by 0'))
+            .expect('i128_div Overflow');
+        q
+    }
+}
+impl I128DivEq of DivEq<i128> {
+    #[inline(always)]
+    fn div_eq(ref self: i128, other: i128) {
+        self = Div::div(self, other);
+    }
+}
+
+impl I128Rem of Rem<i128> {
+    fn rem(lhs: i128, rhs: i128) -> i128 {
+        let (_q, r) = signed_div_rem(lhs, rhs.try_into().expect('Division by 0'))
+            .expect('i128_rem Overflow');
+        r
+    }
+}
+impl I128RemEq of RemEq<i128> {
+    #[inline(always)]
+    fn rem_eq(ref self: i128, other: i128) {
+        self = Rem::rem(self, other);
+    }
+}
+
+impl I128DivRem of DivRem<i128> {
+    fn div_rem(lhs: i128, rhs: NonZero<i128>) -> (i128, i128) {
+        signed_div_rem(lhs, rhs).expect('i128_div Overflow')
+    }
+}
+
 /// If `lhs` >= `rhs` returns `Ok(lhs - rhs)` else returns `Err(2**128 + lhs - rhs)`.
 pub extern fn i128_diff(lhs: i128, rhs: i128) -> Result<u128, u128> implicits(RangeCheck) nopanic;
 impl I128PartialOrd of PartialOrd<i128> {
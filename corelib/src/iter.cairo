@@ -0,0 +1,39 @@
+use core::num::traits::One;
+
+/// A type whose values can be iterated over, by a `for` loop - `for x in iterator { ... }`.
+pub trait Iterator<T, Item> {
+    /// Advances the iterator and returns the next item, or `Option::None` when it is exhausted.
+    fn next(ref self: T) -> Option<Item>;
+}
+
+/// The half-open range of values `start..end`, including `start` and excluding `end`.
+#[derive(Copy, Drop, PartialEq)]
+pub struct Range<T> {
+    pub start: T,
+    pub end: T,
+}
+
+/// The `..` operator - `start..end` creates a [Range] from `start` to `end`.
+pub trait RangeOp<T> {
+    fn range(start: T, end: T) -> Range<T>;
+}
+
+impl RangeOpImpl<T> of RangeOp<T> {
+    fn range(start: T, end: T) -> Range<T> {
+        Range { start, end }
+    }
+}
+
+impl RangeIterator<
+    T, +Copy<T>, +Drop<T>, +PartialOrd<T>, +Add<T>, +One<T>
+> of Iterator<Range<T>, T> {
+    fn next(ref self: Range<T>) -> Option<T> {
+        let value = self.start;
+        if value < self.end {
+            self.start = value + One::one();
+            Option::Some(value)
+        } else {
+            Option::None
+        }
+    }
+}
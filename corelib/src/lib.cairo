@@ -171,6 +171,25 @@ impl Felt252Neg of Neg<felt252> {
 
 pub extern fn felt252_div(lhs: felt252, rhs: NonZero<felt252>) -> felt252 nopanic;
 
+/// Returns the multiplicative inverse of `a` in the field.
+pub extern fn felt252_inverse(a: NonZero<felt252>) -> NonZero<felt252> nopanic;
+
+/// Returns the multiplicative inverse of `a` in the field, or `Option::None` if `a` is zero.
+pub fn felt252_checked_inverse(a: felt252) -> Option<NonZero<felt252>> nopanic {
+    match felt252_is_zero(a) {
+        zeroable::IsZeroResult::Zero => Option::None,
+        zeroable::IsZeroResult::NonZero(a) => Option::Some(felt252_inverse(a)),
+    }
+}
+
+/// Returns `lhs / rhs` in the field, or `Option::None` if `rhs` is zero.
+pub fn felt252_checked_div(lhs: felt252, rhs: felt252) -> Option<felt252> nopanic {
+    match felt252_is_zero(rhs) {
+        zeroable::IsZeroResult::Zero => Option::None,
+        zeroable::IsZeroResult::NonZero(rhs) => Option::Some(felt252_div(lhs, rhs)),
+    }
+}
+
 impl Felt252PartialEq of PartialEq<felt252> {
     #[inline(always)]
     fn eq(lhs: @felt252, rhs: @felt252) -> bool {
@@ -243,10 +262,18 @@ use result::{Result, ResultTrait};
 pub mod option;
 use option::{Option, OptionTrait};
 
+// C-like enums.
+pub mod enums;
+use enums::{CLikeEnum, enum_to_felt252, enum_try_from_felt252};
+
 // Clone.
 pub mod clone;
 use clone::Clone;
 
+// Iterators.
+pub mod iter;
+use iter::{Iterator, Range, RangeOp};
+
 // EC.
 pub mod ec;
 use ec::{EcOp, EcPoint, EcState};
@@ -6,10 +6,12 @@ mod bytes31_test;
 mod cmp_test;
 mod dict_test;
 mod ec_test;
+mod enum_test;
 mod felt_test;
 mod fmt_test;
 mod hash_test;
 mod integer_test;
+mod iter_test;
 mod keccak_test;
 mod num_test;
 mod math_test;
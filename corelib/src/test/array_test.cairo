@@ -73,6 +73,35 @@ fn test_slice_out_of_bound_2() {
     array![10, 11, 12].span().slice(0, 4);
 }
 
+#[test]
+fn test_span_multi_pop_front() {
+    let mut span = array![10, 11, 12, 13].span();
+    let popped = span.multi_pop_front(3).unwrap();
+    assert_eq(@popped.len(), @3, 'Unexpected popped length.');
+    assert_eq(popped[0], @10, 'Unexpected element');
+    assert_eq(popped[2], @12, 'Unexpected element');
+    assert_eq(@span.len(), @1, 'Unexpected span length.');
+    assert_eq(span.at(0), @13, 'Unexpected element');
+    assert(span.multi_pop_front(2).is_none(), 'Popped too many elements');
+    assert_eq(@span.len(), @1, 'Unexpected span length.');
+    assert_eq(@span.multi_pop_front(0).unwrap().len(), @0, 'Unexpected popped length.');
+}
+
+#[test]
+fn test_span_multi_pop_back() {
+    let mut span = array![10, 11, 12, 13].span();
+    let popped = span.multi_pop_back(3).unwrap();
+    assert_eq(@popped.len(), @3, 'Unexpected popped length.');
+    assert_eq(popped[0], @11, 'Unexpected element');
+    assert_eq(popped[2], @13, 'Unexpected element');
+    assert_eq(@span.len(), @1, 'Unexpected span length.');
+    assert_eq(span.at(0), @10, 'Unexpected element');
+    assert(span.multi_pop_back(2).is_none(), 'Popped too many elements');
+    assert_eq(@span.len(), @1, 'Unexpected span length.');
+    assert_eq(span.multi_pop_back(1).unwrap()[0], @10, 'Unexpected element');
+    assert_eq(@span.len(), @0, 'Unexpected span length.');
+}
+
 #[test]
 fn test_equality() {
     let arr1 = array![];
@@ -116,3 +145,32 @@ fn test_append_span() {
     assert_eq(arr[4], @11, 'Unexpected element');
     assert_eq(arr[5], @12, 'Unexpected element');
 }
+
+#[test]
+fn test_extend() {
+    let mut arr = array![10, 11, 12];
+    arr.extend(arr.span());
+    arr.extend(array![].span());
+    assert(arr.len() == 6, 'Unexpected length');
+    assert_eq(arr[3], @10, 'Unexpected element');
+    assert_eq(arr[4], @11, 'Unexpected element');
+    assert_eq(arr[5], @12, 'Unexpected element');
+}
+
+#[test]
+fn test_extend_multi_cell_elements() {
+    let mut arr = array![(1_u256, 'a'), (2_u256, 'b')];
+    arr.extend(array![(3_u256, 'c')].span());
+    assert(arr.len() == 3, 'Unexpected length');
+    assert(*arr[0] == (1_u256, 'a'), 'Unexpected element');
+    assert(*arr[2] == (3_u256, 'c'), 'Unexpected element');
+}
+
+#[test]
+fn test_concat() {
+    let lhs = array![10, 11];
+    let rhs = array![12, 13, 14];
+    assert(lhs.span().concat(rhs.span()) == array![10, 11, 12, 13, 14], 'Unexpected concat');
+    assert(array![].span().concat(rhs.span()) == rhs, 'Unexpected empty lhs concat');
+    assert(lhs.span().concat(array![].span()) == lhs, 'Unexpected empty rhs concat');
+}
@@ -0,0 +1,53 @@
+use core::enums::{CLikeEnum, enum_to_felt252, enum_try_from_felt252};
+use core::test::test_utils::assert_eq;
+
+#[derive(Copy, Drop, PartialEq)]
+enum Status {
+    Ok = 200,
+    Created,
+    NotFound = 404,
+    Unknown = 0,
+}
+
+#[derive(Copy, Drop, PartialEq)]
+enum Single {
+    Only = 7,
+}
+
+#[test]
+fn test_enum_to_felt252() {
+    assert_eq(@enum_to_felt252(Status::Ok), @200, 'Ok != 200');
+    assert_eq(@enum_to_felt252(Status::Created), @201, 'Created != 201');
+    assert_eq(@enum_to_felt252(Status::NotFound), @404, 'NotFound != 404');
+    assert_eq(@enum_to_felt252(Status::Unknown), @0, 'Unknown != 0');
+    assert_eq(@enum_to_felt252(Single::Only), @7, 'Only != 7');
+}
+
+#[test]
+fn test_enum_try_from_felt252() {
+    assert(enum_try_from_felt252(200) == Option::Some(Status::Ok), '200 != Ok');
+    assert(enum_try_from_felt252(201) == Option::Some(Status::Created), '201 != Created');
+    assert(enum_try_from_felt252(404) == Option::Some(Status::NotFound), '404 != NotFound');
+    assert(enum_try_from_felt252(0) == Option::Some(Status::Unknown), '0 != Unknown');
+    assert(enum_try_from_felt252::<Status>(202).is_none(), '202 is a status');
+    assert(enum_try_from_felt252(7) == Option::Some(Single::Only), '7 != Only');
+    assert(enum_try_from_felt252::<Single>(0).is_none(), '0 is a single');
+}
+
+fn generic_to_felt252<T, +CLikeEnum<T>>(value: T) -> felt252 {
+    CLikeEnum::to_felt252(value)
+}
+
+fn generic_try_from_felt252<T, +CLikeEnum<T>>(value: felt252) -> Option<T> {
+    CLikeEnum::try_from_felt252(value)
+}
+
+#[test]
+fn test_c_like_enum() {
+    assert_eq(@generic_to_felt252(Status::Created), @201, 'Created != 201');
+    assert_eq(@generic_to_felt252(true), @1, 'true != 1');
+    assert_eq(@Status::NotFound.to_felt252(), @404, 'NotFound != 404');
+    assert(generic_try_from_felt252(404) == Option::Some(Status::NotFound), '404 != NotFound');
+    assert(generic_try_from_felt252::<Status>(202).is_none(), '202 is a status');
+    assert(generic_try_from_felt252(0) == Option::Some(false), '0 != false');
+}
@@ -1,4 +1,5 @@
 use core::test::test_utils::{assert_eq, assert_ne};
+use core::{felt252_checked_div, felt252_checked_inverse};
 
 #[test]
 fn test_felt252_operators() {
@@ -17,3 +18,21 @@ fn test_felt252_clone() {
     let felt252_clone = felt252_snap.clone();
     assert_eq(@felt252_clone, @2, 'felt252_clone == 2');
 }
+
+#[test]
+fn test_felt252_checked_inverse() {
+    assert(felt252_checked_inverse(0).is_none(), 'inverse of 0 is None');
+    let inverse: felt252 = felt252_checked_inverse(1).unwrap().into();
+    assert_eq(@inverse, @1, '1 / 1 == 1');
+    let inverse: felt252 = felt252_checked_inverse(2).unwrap().into();
+    assert_eq(@(inverse * 2), @1, '(1 / 2) * 2 == 1');
+    let inverse: felt252 = felt252_checked_inverse(-1).unwrap().into();
+    assert_eq(@inverse, @-1, '1 / -1 == -1');
+}
+
+#[test]
+fn test_felt252_checked_div() {
+    assert(felt252_checked_div(5, 0).is_none(), '5 / 0 is None');
+    assert_eq(@felt252_checked_div(18, 6).unwrap(), @3, '18 / 6 == 3');
+    assert_eq(@(felt252_checked_div(1, 3).unwrap() * 3), @1, '(1 / 3) * 3 == 1');
+}
@@ -1145,6 +1145,15 @@ fn test_i8_operators() {
     assert_eq(@(2_i8 * -4_i8), @-8_i8, '2 * -4 == -8');
     assert_eq(@(-1_i8 * -3_i8), @3_i8, '-1 * -3 == 3');
     assert_eq(@(-2_i8 * -4_i8), @8_i8, '-2 * -4 == 8');
+    assert_eq(@(7_i8 / 2_i8), @3_i8, '7 / 2 == 3');
+    assert_eq(@(-7_i8 / 2_i8), @-3_i8, '-7 / 2 == -3');
+    assert_eq(@(7_i8 / -2_i8), @-3_i8, '7 / -2 == -3');
+    assert_eq(@(-7_i8 / -2_i8), @3_i8, '-7 / -2 == 3');
+    assert_eq(@(7_i8 % 2_i8), @1_i8, '7 % 2 == 1');
+    assert_eq(@(-7_i8 % 2_i8), @-1_i8, '-7 % 2 == -1');
+    assert_eq(@(7_i8 % -2_i8), @1_i8, '7 % -2 == 1');
+    assert_eq(@(-7_i8 % -2_i8), @-1_i8, '-7 % -2 == -1');
+    assert_eq(@DivRem::div_rem(-9_i8, 4_i8.try_into().unwrap()), @(-2_i8, -1_i8), '-9 divrem 4');
     assert_lt(1_i8, 4_i8, '1 < 4');
     assert_le(1_i8, 4_i8, '1 <= 4');
     assert(!(4_i8 < 4_i8), '!(4 < 4)');
@@ -1156,6 +1165,18 @@ fn test_i8_operators() {
     assert_ge(3_i8, 3_i8, '3 >= 3');
 }
 
+#[test]
+#[should_panic(expected: ('i8_div Overflow',))]
+fn test_i8_div_overflow() {
+    -0x80_i8 / -1_i8;
+}
+
+#[test]
+#[should_panic(expected: ('i8 is 0',))]
+fn test_i8_div_by_zero() {
+    1_i8 / 0_i8;
+}
+
 #[test]
 #[should_panic(expected: ('i8_sub Underflow',))]
 fn test_i8_sub_underflow_1() {
@@ -1248,6 +1269,17 @@ fn test_i16_operators() {
     assert_eq(@(2_i16 * -4_i16), @-8_i16, '2 * -4 == -8');
     assert_eq(@(-1_i16 * -3_i16), @3_i16, '-1 * -3 == 3');
     assert_eq(@(-2_i16 * -4_i16), @8_i16, '-2 * -4 == 8');
+    assert_eq(@(7_i16 / 2_i16), @3_i16, '7 / 2 == 3');
+    assert_eq(@(-7_i16 / 2_i16), @-3_i16, '-7 / 2 == -3');
+    assert_eq(@(7_i16 / -2_i16), @-3_i16, '7 / -2 == -3');
+    assert_eq(@(-7_i16 / -2_i16), @3_i16, '-7 / -2 == 3');
+    assert_eq(@(7_i16 % 2_i16), @1_i16, '7 % 2 == 1');
+    assert_eq(@(-7_i16 % 2_i16), @-1_i16, '-7 % 2 == -1');
+    assert_eq(@(7_i16 % -2_i16), @1_i16, '7 % -2 == 1');
+    assert_eq(@(-7_i16 % -2_i16), @-1_i16, '-7 % -2 == -1');
+    assert_eq(
+        @DivRem::div_rem(-9_i16, 4_i16.try_into().unwrap()), @(-2_i16, -1_i16), '-9 divrem 4'
+    );
     assert_lt(1_i16, 4_i16, '1 < 4');
     assert_le(1_i16, 4_i16, '1 <= 4');
     assert(!(4_i16 < 4_i16), '!(4 < 4)');
@@ -1351,6 +1383,17 @@ fn test_i32_operators() {
     assert_eq(@(2_i32 * -4_i32), @-8_i32, '2 * -4 == -8');
     assert_eq(@(-1_i32 * -3_i32), @3_i32, '-1 * -3 == 3');
     assert_eq(@(-2_i32 * -4_i32), @8_i32, '-2 * -4 == 8');
+    assert_eq(@(7_i32 / 2_i32), @3_i32, '7 / 2 == 3');
+    assert_eq(@(-7_i32 / 2_i32), @-3_i32, '-7 / 2 == -3');
+    assert_eq(@(7_i32 / -2_i32), @-3_i32, '7 / -2 == -3');
+    assert_eq(@(-7_i32 / -2_i32), @3_i32, '-7 / -2 == 3');
+    assert_eq(@(7_i32 % 2_i32), @1_i32, '7 % 2 == 1');
+    assert_eq(@(-7_i32 % 2_i32), @-1_i32, '-7 % 2 == -1');
+    assert_eq(@(7_i32 % -2_i32), @1_i32, '7 % -2 == 1');
+    assert_eq(@(-7_i32 % -2_i32), @-1_i32, '-7 % -2 == -1');
+    assert_eq(
+        @DivRem::div_rem(-9_i32, 4_i32.try_into().unwrap()), @(-2_i32, -1_i32), '-9 divrem 4'
+    );
     assert_lt(1_i32, 4_i32, '1 < 4');
     assert_le(1_i32, 4_i32, '1 <= 4');
     assert(!(4_i32 < 4_i32), '!(4 < 4)');
@@ -1462,6 +1505,17 @@ fn test_i64_operators() {
     assert_eq(@(2_i64 * -4_i64), @-8_i64, '2 * -4 == -8');
     assert_eq(@(-1_i64 * -3_i64), @3_i64, '-1 * -3 == 3');
     assert_eq(@(-2_i64 * -4_i64), @8_i64, '-2 * -4 == 8');
+    assert_eq(@(7_i64 / 2_i64), @3_i64, '7 / 2 == 3');
+    assert_eq(@(-7_i64 / 2_i64), @-3_i64, '-7 / 2 == -3');
+    assert_eq(@(7_i64 / -2_i64), @-3_i64, '7 / -2 == -3');
+    assert_eq(@(-7_i64 / -2_i64), @3_i64, '-7 / -2 == 3');
+    assert_eq(@(7_i64 % 2_i64), @1_i64, '7 % 2 == 1');
+    assert_eq(@(-7_i64 % 2_i64), @-1_i64, '-7 % 2 == -1');
+    assert_eq(@(7_i64 % -2_i64), @1_i64, '7 % -2 == 1');
+    assert_eq(@(-7_i64 % -2_i64), @-1_i64, '-7 % -2 == -1');
+    assert_eq(
+        @DivRem::div_rem(-9_i64, 4_i64.try_into().unwrap()), @(-2_i64, -1_i64), '-9 divrem 4'
+    );
     assert_lt(1_i64, 4_i64, '1 < 4');
     assert_le(1_i64, 4_i64, '1 <= 4');
     assert(!(4_i64 < 4_i64), '!(4 < 4)');
@@ -1579,6 +1633,17 @@ fn test_i128_operators() {
         @-0x80000000000000000000000000000000_i128,
         'failed MIN_I128 as mul result'
     );
+    assert_eq(@(7_i128 / 2_i128), @3_i128, '7 / 2 == 3');
+    assert_eq(@(-7_i128 / 2_i128), @-3_i128, '-7 / 2 == -3');
+    assert_eq(@(7_i128 / -2_i128), @-3_i128, '7 / -2 == -3');
+    assert_eq(@(-7_i128 / -2_i128), @3_i128, '-7 / -2 == 3');
+    assert_eq(@(7_i128 % 2_i128), @1_i128, '7 % 2 == 1');
+    assert_eq(@(-7_i128 % 2_i128), @-1_i128, '-7 % 2 == -1');
+    assert_eq(@(7_i128 % -2_i128), @1_i128, '7 % -2 == 1');
+    assert_eq(@(-7_i128 % -2_i128), @-1_i128, '-7 % -2 == -1');
+    assert_eq(
+        @DivRem::div_rem(-9_i128, 4_i128.try_into().unwrap()), @(-2_i128, -1_i128), '-9 divrem 4'
+    );
     assert_lt(1_i128, 4_i128, '1 < 4');
     assert_le(1_i128, 4_i128, '1 <= 4');
     assert(!(4_i128 < 4_i128), '!(4 < 4)');
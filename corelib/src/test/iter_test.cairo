@@ -0,0 +1,52 @@
+use core::test::test_utils::assert_eq;
+
+#[test]
+fn test_for_over_span() {
+    let mut sum = 0;
+    for value in array![1, 2, 3].span() {
+        sum += *value;
+    };
+    assert_eq(@sum, @6, 'sum != 6');
+}
+
+#[test]
+fn test_for_over_array() {
+    let mut sum = 0;
+    for (a, b) in array![(1, 2), (3, 4)] {
+        sum += a * b;
+    };
+    assert_eq(@sum, @14, 'sum != 14');
+}
+
+#[test]
+fn test_for_over_range() {
+    let mut sum: u32 = 0;
+    for i in 0..5_u32 {
+        if i == 2 {
+            continue;
+        }
+        sum += i;
+    };
+    assert_eq(@sum, @8, 'sum != 8');
+}
+
+#[test]
+fn test_for_over_empty_range() {
+    let mut count: u8 = 0;
+    for _ in 3..3_u8 {
+        count += 1;
+    };
+    assert_eq(@count, @0, 'count != 0');
+}
+
+#[test]
+fn test_for_break() {
+    let mut last = 0;
+    for value in array![1, 2, 3, 4].span() {
+        if *value == 3 {
+            break;
+        }
+        last = *value;
+    };
+    assert_eq(@last, @2, 'last != 2');
+}
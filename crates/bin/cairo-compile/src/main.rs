@@ -1,9 +1,14 @@
 use std::fs;
+use std::io::{self, Write};
 use std::path::PathBuf;
 
 use anyhow::Context;
-use cairo_lang_compiler::project::check_compiler_path;
-use cairo_lang_compiler::{compile_cairo_project_at_path, CompilerConfig};
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::metadata::compilation_metadata;
+use cairo_lang_compiler::project::{check_compiler_path, setup_project};
+use cairo_lang_compiler::{compile_prepared_db, CompilerConfig};
+use cairo_lang_filesystem::flag::OptimizationLevel;
+use cairo_lang_sierra::binary::encode_program;
 use cairo_lang_utils::logging::init_logging;
 use clap::Parser;
 
@@ -22,6 +27,23 @@ struct Args {
     /// Replaces sierra ids with human-readable ones.
     #[arg(short, long, default_value_t = false)]
     replace_ids: bool,
+    /// Removes the `dup`, `drop` and `rename` invocations that cancel out from the Sierra code.
+    #[arg(long, default_value_t = false)]
+    eliminate_redundant_dup_drop: bool,
+    /// The file to write the compilation metadata to, as JSON (e.g. `metadata.json`).
+    #[arg(long)]
+    metadata: Option<PathBuf>,
+    /// The optimization level - 0 for no optimizations, 1 for inlining only functions marked for
+    /// inlining, 2 for the default optimizations, and 3 for applying the local optimizations as
+    /// well.
+    #[arg(short = 'O', long, default_value = "2")]
+    optimization_level: OptimizationLevel,
+    /// Writes the program in the binary Sierra format instead of the textual one.
+    #[arg(long, default_value_t = false)]
+    binary: bool,
+    /// Writes the program with comments heading its sections and the code of each function.
+    #[arg(long, default_value_t = false, conflicts_with = "binary")]
+    pretty: bool,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -33,16 +55,45 @@ fn main() -> anyhow::Result<()> {
     // Check if args.path is a file or a directory.
     check_compiler_path(args.single_file, &args.path)?;
 
-    let sierra_program = compile_cairo_project_at_path(
-        &args.path,
-        CompilerConfig { replace_ids: args.replace_ids, ..CompilerConfig::default() },
+    let mut db = RootDatabase::builder()
+        .detect_corelib()
+        .with_optimization_level(args.optimization_level)
+        .build()?;
+    let main_crate_ids = setup_project(&mut db, &args.path)?;
+    let sierra_program = compile_prepared_db(
+        &mut db,
+        main_crate_ids.clone(),
+        CompilerConfig {
+            replace_ids: args.replace_ids,
+            eliminate_redundant_dup_drop: args.eliminate_redundant_dup_drop,
+            ..CompilerConfig::default()
+        },
     )?;
+    let sierra_code = if args.binary {
+        encode_program(&sierra_program)
+    } else if args.pretty {
+        sierra_program.pretty().to_string().into_bytes()
+    } else {
+        sierra_program.to_string().into_bytes()
+    };
 
-    match args.output {
-        Some(path) => {
-            fs::write(path, format!("{sierra_program}")).context("Failed to write output.")?
+    match &args.output {
+        Some(path) => fs::write(path, &sierra_code).context("Failed to write output.")?,
+        None if args.binary => {
+            io::stdout().write_all(&sierra_code).context("Failed to write output.")?
         }
-        None => println!("{sierra_program}"),
+        None => println!("{}", String::from_utf8_lossy(&sierra_code)),
+    }
+
+    if let Some(metadata_path) = args.metadata {
+        let artifacts: Vec<(String, &[u8])> =
+            args.output.into_iter().map(|path| (path, sierra_code.as_slice())).collect();
+        let metadata = compilation_metadata(&db, &main_crate_ids, &sierra_program, &artifacts);
+        fs::write(
+            metadata_path,
+            serde_json::to_string_pretty(&metadata).context("Failed to serialize metadata.")?,
+        )
+        .context("Failed to write metadata.")?;
     }
 
     Ok(())
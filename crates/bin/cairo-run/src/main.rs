@@ -1,18 +1,26 @@
 //! Compiles and runs a Cairo program.
 
+use std::fs;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Ok};
+use cairo_felt::Felt252;
 use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
 use cairo_lang_compiler::project::{check_compiler_path, setup_project};
 use cairo_lang_diagnostics::ToOption;
+use cairo_lang_filesystem::flag::OptimizationLevel;
 use cairo_lang_runner::short_string::as_cairo_short_string;
-use cairo_lang_runner::{SierraCasmRunner, StarknetState};
+use cairo_lang_runner::value_format::user_type_member_names;
+use cairo_lang_runner::{Arg, FunctionResources, RunResultValue, SierraCasmRunner, StarknetState};
+use cairo_lang_sierra::ProgramParser;
 use cairo_lang_sierra_generator::db::SierraGenGroup;
 use cairo_lang_sierra_generator::replace_ids::{DebugReplacer, SierraIdReplacer};
 use cairo_lang_starknet::contract::get_contracts_info;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use clap::Parser;
+use itertools::Itertools;
+use num_bigint::BigInt;
 
 /// Command line args parser.
 /// Exits with 0/1 if the input is formatted correctly/incorrectly.
@@ -20,7 +28,8 @@ use clap::Parser;
 #[clap(version, verbatim_doc_comment)]
 struct Args {
     /// The file to compile and run.
-    path: PathBuf,
+    #[arg(required_unless_present = "diff_resources")]
+    path: Option<PathBuf>,
     /// Whether path is a single file.
     #[arg(short, long)]
     single_file: bool,
@@ -30,20 +39,42 @@ struct Args {
     /// Whether to print the memory.
     #[arg(long, default_value_t = false)]
     print_full_memory: bool,
+    /// The optimization level - 0 for no optimizations, 1 for inlining only functions marked for
+    /// inlining, 2 for the default optimizations, and 3 for applying the local optimizations as
+    /// well.
+    #[arg(short = 'O', long, default_value = "2")]
+    optimization_level: OptimizationLevel,
+    /// The suffix of the name of the function to run.
+    #[arg(long, default_value = "::main")]
+    function: String,
+    /// The arguments of the function to run, separated by commas.
+    #[arg(long, value_delimiter = ',', allow_hyphen_values = true)]
+    args: Vec<BigInt>,
+    /// Instead of compiling and running `path`, runs the function in two compiled Sierra programs,
+    /// before and after a change, and prints the difference in the resources used by each function.
+    #[arg(long, num_args = 2, value_names = ["BEFORE", "AFTER"], conflicts_with = "path")]
+    diff_resources: Vec<PathBuf>,
 }
 
 fn main() -> anyhow::Result<()> {
     let args = Args::parse();
+    if let [before, after] = &args.diff_resources[..] {
+        return print_resources_diff(&args, before, after);
+    }
+    let path = args.path.as_ref().with_context(|| "Missing the path to run.")?;
 
     // Check if args.path is a file or a directory.
-    check_compiler_path(args.single_file, &args.path)?;
+    check_compiler_path(args.single_file, path)?;
 
-    let db = &mut RootDatabase::builder().detect_corelib().build()?;
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_optimization_level(args.optimization_level)
+        .build()?;
 
-    let main_crate_ids = setup_project(db, Path::new(&args.path))?;
+    let main_crate_ids = setup_project(db, Path::new(path))?;
 
     if DiagnosticsReporter::stderr().check(db) {
-        anyhow::bail!("failed to compile: {}", args.path.display());
+        anyhow::bail!("failed to compile: {}", path.display());
     }
 
     let sierra_program = db
@@ -63,17 +94,26 @@ fn main() -> anyhow::Result<()> {
         contracts_info,
     )
     .with_context(|| "Failed setting up runner.")?;
+    let function = runner.find_function(&args.function)?;
     let result = runner
         .run_function_with_starknet_context(
-            runner.find_function("::main")?,
-            &[],
+            function,
+            &run_args(&args),
             args.available_gas,
             StarknetState::default(),
         )
         .with_context(|| "Failed to run the function.")?;
     match result.value {
         cairo_lang_runner::RunResultValue::Success(values) => {
-            println!("Run completed successfully, returning {values:?}")
+            match runner.format_return_value(
+                function,
+                &values,
+                &result.memory,
+                &user_type_member_names(db),
+            ) {
+                Some(formatted) => println!("Run completed successfully, returning {formatted}"),
+                None => println!("Run completed successfully, returning {values:?}"),
+            }
         }
         cairo_lang_runner::RunResultValue::Panic(values) => {
             print!("Run panicked with [");
@@ -101,3 +141,90 @@ fn main() -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+/// Returns the arguments of the function to run.
+fn run_args(args: &Args) -> Vec<Arg> {
+    args.args.iter().map(|value| Arg::Value(Felt252::from(value.clone()))).collect()
+}
+
+/// Runs the function in two compiled Sierra programs, and prints the difference in the resources
+/// used by each function whose resources changed.
+fn print_resources_diff(args: &Args, before: &Path, after: &Path) -> anyhow::Result<()> {
+    let (before_value, before_resources) = run_with_resources(args, before)?;
+    let (after_value, after_resources) = run_with_resources(args, after)?;
+    if before_value != after_value {
+        println!(
+            "Warning: the runs returned different values - {before_value:?} and {after_value:?}."
+        );
+    }
+    let mut unchanged = 0;
+    for name in before_resources.keys().chain(after_resources.keys()).unique() {
+        let (before, after) = match (before_resources.get(name), after_resources.get(name)) {
+            (Some(before), Some(after)) if before == after => {
+                unchanged += 1;
+                continue;
+            }
+            (Some(before), Some(after)) => {
+                println!("{name}:");
+                (before.clone(), after.clone())
+            }
+            (Some(before), None) => {
+                println!("{name} (removed):");
+                (before.clone(), FunctionResources::default())
+            }
+            (None, after) => {
+                println!("{name} (added):");
+                (FunctionResources::default(), after.cloned().unwrap_or_default())
+            }
+        };
+        print_resource_diff("calls", before.calls, after.calls);
+        print_resource_diff("steps", before.steps, after.steps);
+        print_resource_diff("memory cells", before.memory_cells, after.memory_cells);
+        for builtin in before.builtins.keys().chain(after.builtins.keys()).unique() {
+            print_resource_diff(
+                builtin,
+                before.builtins.get(builtin).copied().unwrap_or_default(),
+                after.builtins.get(builtin).copied().unwrap_or_default(),
+            );
+        }
+    }
+    println!("Functions with unchanged resources: {unchanged}.");
+    Ok(())
+}
+
+/// Prints the values of a resource before and after a change, and the difference between them.
+fn print_resource_diff(name: &str, before: usize, after: usize) {
+    let diff = after as i128 - before as i128;
+    println!("    {name}: {before} -> {after} ({diff:+})");
+}
+
+/// Runs the function in a compiled Sierra program, and returns its result along with the resources
+/// used by each function called in the run.
+fn run_with_resources(
+    args: &Args,
+    path: &Path,
+) -> anyhow::Result<(RunResultValue, OrderedHashMap<String, FunctionResources>)> {
+    let sierra_code = fs::read_to_string(path)
+        .with_context(|| format!("Could not read file: {}", path.display()))?;
+    let Result::Ok(sierra_program) = ProgramParser::new().parse(&sierra_code) else {
+        anyhow::bail!("Failed to parse sierra program: {}", path.display());
+    };
+    if args.available_gas.is_none() && sierra_program.requires_gas_counter() {
+        anyhow::bail!("Program requires gas counter, please provide `--available-gas` argument.");
+    }
+    let runner = SierraCasmRunner::new(
+        sierra_program,
+        if args.available_gas.is_some() { Some(Default::default()) } else { None },
+        Default::default(),
+    )
+    .with_context(|| "Failed setting up runner.")?;
+    let (result, resources) = runner
+        .run_function_with_resources(
+            runner.find_function(&args.function)?,
+            &run_args(args),
+            args.available_gas,
+            StarknetState::default(),
+        )
+        .with_context(|| format!("Failed to run the function in: {}", path.display()))?;
+    Ok((result.value, resources))
+}
@@ -29,6 +29,10 @@ struct Args {
     /// Should we add the starknet plugin to run the tests.
     #[arg(long, default_value_t = false)]
     starknet: bool,
+    /// The directory to write the Starknet state diff and emitted events of each ran test to, as
+    /// JSON artifacts matching the devnet formats.
+    #[arg(long)]
+    state_artifacts_dir: Option<PathBuf>,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -41,6 +45,7 @@ fn main() -> anyhow::Result<()> {
         filter: args.filter,
         ignored: args.ignored,
         include_ignored: args.include_ignored,
+        state_artifacts_dir: args.state_artifacts_dir,
     };
 
     let runner = TestRunner::new(&args.path, args.starknet, config)?;
@@ -1,11 +1,50 @@
 use std::fs;
 
 use anyhow::Context;
+use cairo_felt::Felt252;
+use cairo_lang_casm::hints::Hint;
+use cairo_lang_sierra::binary::decode_program;
+use cairo_lang_sierra::program::Program;
 use cairo_lang_sierra::ProgramParser;
+use cairo_lang_sierra_to_casm::compiler::{
+    compile_with_config, AssembledCairoProgram, CairoProgram, CairoProgramDebugInfo,
+    InstructionDebugInfo, SierraToCasmConfig,
+};
 use cairo_lang_sierra_to_casm::metadata::calc_metadata;
+use cairo_lang_starknet::felt252_serde::sierra_from_felt252s;
+use cairo_lang_utils::bigint::BigUintAsHex;
 use cairo_lang_utils::logging::init_logging;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use indoc::indoc;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use serde::Serialize;
+
+#[cfg(test)]
+#[path = "main_test.rs"]
+mod test;
+
+/// The format of the input Sierra program.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum InputFormat {
+    /// The textual Sierra representation.
+    Text,
+    /// The JSON serialization of the Sierra program.
+    Json,
+    /// The felt252 encoding used by declared contract classes, as a JSON list of hex values.
+    Felt252s,
+    /// The binary encoding of the Sierra program.
+    Binary,
+}
+
+/// The format of the output CASM program.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum OutputFormat {
+    /// The textual CASM representation, including the hints.
+    Text,
+    /// A JSON object with the assembled bytecode and the hints keyed by their bytecode offset.
+    Json,
+}
 
 /// Command line args parser.
 /// Exits with 0/1 if the input is formatted correctly/incorrectly.
@@ -15,30 +54,129 @@ struct Args {
     /// The file to compile
     file: String,
     output: String,
+    /// The format of the input file.
+    #[arg(long, value_enum, default_value_t = InputFormat::Text)]
+    input_format: InputFormat,
+    /// The format of the output file.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    output_format: OutputFormat,
+    /// Add the bytecode offset of each Sierra statement, and the Sierra statement of each
+    /// instruction, to the output (JSON output only).
+    #[arg(long, default_value_t = false)]
+    debug_info: bool,
+    /// Interleave the CASM with the Sierra statements it was compiled from and their gas costs
+    /// (text output only).
+    #[arg(long, default_value_t = false)]
+    annotated: bool,
+    /// Apply the peephole optimizations to the compiled CASM - the only optimization pass run on a
+    /// Sierra program.
+    #[arg(long, default_value_t = false)]
+    peephole_optimizations: bool,
 }
 
-fn main() -> anyhow::Result<()> {
-    init_logging(log::LevelFilter::Off);
-    log::info!("Starting Sierra compilation.");
+/// The JSON representation of a compiled CASM program.
+#[derive(Serialize)]
+struct CasmProgramJson {
+    bytecode: Vec<BigUintAsHex>,
+    hints: Vec<(usize, Vec<Hint>)>,
+    /// The bytecode offset of each Sierra statement.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sierra_statement_offsets: Option<Vec<usize>>,
+    /// The bytecode offset and Sierra statement of each instruction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    instructions_debug_info: Option<Vec<InstructionDebugInfo>>,
+}
+impl CasmProgramJson {
+    fn new(cairo_program: CairoProgram, debug_info: bool) -> Self {
+        let prime = BigInt::from(Felt252::prime());
+        let AssembledCairoProgram { bytecode, hints } = cairo_program.assemble();
+        let CairoProgramDebugInfo { sierra_statement_info, instruction_info } =
+            cairo_program.debug_info;
+        let bytecode = bytecode
+            .iter()
+            .map(|big_int| BigUintAsHex { value: big_int.mod_floor(&prime).to_biguint().unwrap() })
+            .collect();
+        let sierra_statement_offsets = debug_info.then(|| {
+            sierra_statement_info.iter().map(|statement_info| statement_info.code_offset).collect()
+        });
+        let instructions_debug_info = debug_info.then_some(instruction_info);
+        Self { bytecode, hints, sierra_statement_offsets, instructions_debug_info }
+    }
+}
 
-    let args = Args::parse();
+/// Parses the Sierra program from the content of the input file.
+fn parse_program(input: &[u8], input_format: InputFormat) -> anyhow::Result<Program> {
+    let sierra_code =
+        || std::str::from_utf8(input).with_context(|| "Input file is not valid UTF-8.");
+    match input_format {
+        InputFormat::Text => {
+            let Ok(program) = ProgramParser::new().parse(sierra_code()?) else {
+                anyhow::bail!(indoc! {"
+                    Failed to parse sierra program.
+                    Note: StarkNet contracts should be compiled with `starknet-sierra-compile`."
+                })
+            };
+            Ok(program)
+        }
+        InputFormat::Json => {
+            serde_json::from_str(sierra_code()?).with_context(|| "Failed to parse sierra program.")
+        }
+        InputFormat::Felt252s => {
+            let felts: Vec<BigUintAsHex> = serde_json::from_str(sierra_code()?)
+                .with_context(|| "Failed to parse sierra program felts.")?;
+            let (_sierra_version_id, _compiler_version_id, program) = sierra_from_felt252s(&felts)
+                .with_context(|| "Failed to decode sierra program felts.")?;
+            Ok(program)
+        }
+        InputFormat::Binary => {
+            decode_program(input).with_context(|| "Failed to decode sierra program.")
+        }
+    }
+}
 
-    let sierra_code = fs::read_to_string(args.file).with_context(|| "Could not read file!")?;
-    let Ok(program) = ProgramParser::new().parse(&sierra_code) else {
-        anyhow::bail!(indoc! {"
-            Failed to parse sierra program.
-            Note: StarkNet contracts should be compiled with `starknet-sierra-compile`."
-        })
-    };
+/// Compiles the content of the input file, returning the content of the output file.
+fn compile_input(input: &[u8], args: &Args) -> anyhow::Result<String> {
+    match args.output_format {
+        OutputFormat::Text => anyhow::ensure!(
+            !args.debug_info,
+            "`--debug-info` is only supported with `--output-format json`."
+        ),
+        OutputFormat::Json => anyhow::ensure!(
+            !args.annotated,
+            "`--annotated` is only supported with `--output-format text`."
+        ),
+    }
+    let program = parse_program(input, args.input_format)?;
 
-    let gas_usage_check = true;
-    let cairo_program = cairo_lang_sierra_to_casm::compiler::compile(
+    let cairo_program = compile_with_config(
         &program,
         &calc_metadata(&program, Default::default())
             .with_context(|| "Failed calculating Sierra variables.")?,
-        gas_usage_check,
+        &SierraToCasmConfig {
+            gas_usage_check: true,
+            peephole_optimizations: args.peephole_optimizations,
+            ..Default::default()
+        },
     )
     .with_context(|| "Compilation failed.")?;
 
-    fs::write(args.output, format!("{cairo_program}")).with_context(|| "Failed to write output.")
+    Ok(match args.output_format {
+        OutputFormat::Text if args.annotated => cairo_program.annotated(&program).to_string(),
+        OutputFormat::Text => format!("{cairo_program}"),
+        OutputFormat::Json => {
+            serde_json::to_string_pretty(&CasmProgramJson::new(cairo_program, args.debug_info))
+                .with_context(|| "Casm program serialization failed.")?
+        }
+    })
+}
+
+fn main() -> anyhow::Result<()> {
+    init_logging(log::LevelFilter::Off);
+    log::info!("Starting Sierra compilation.");
+
+    let args = Args::parse();
+
+    let input = fs::read(&args.file).with_context(|| "Could not read file!")?;
+    let res = compile_input(&input, &args)?;
+    fs::write(&args.output, res).with_context(|| "Failed to write output.")
 }
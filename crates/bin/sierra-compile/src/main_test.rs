@@ -0,0 +1,98 @@
+use cairo_lang_sierra::binary::encode_program;
+use cairo_lang_sierra::ProgramParser;
+use cairo_lang_starknet::compiler_version::{
+    current_compiler_version_id, current_sierra_version_id,
+};
+use cairo_lang_starknet::felt252_serde::sierra_to_felt252s;
+use clap::Parser;
+use indoc::indoc;
+
+use super::{compile_input, Args};
+
+/// A program with numeric ids, as in declared contract classes, so it can be encoded as felt252s.
+const SIERRA: &str = indoc! {"
+    type [0] = felt252;
+    type [1] = NonZero<[0]>;
+
+    libfunc [0] = branch_align;
+    libfunc [1] = felt252_is_zero;
+    libfunc [2] = drop<[0]>;
+    libfunc [3] = drop<[1]>;
+    libfunc [4] = felt252_add;
+    libfunc [5] = store_temp<[0]>;
+
+    [1]([0]) { fallthrough() 5([0]) };
+    [0]() -> ();
+    [2]([2]) -> ();
+    [5]([1]) -> ([1]);
+    return([1]);
+    [0]() -> ();
+    [3]([0]) -> ();
+    [4]([1], [2]) -> ([1]);
+    [5]([1]) -> ([1]);
+    return([1]);
+
+    [0]@0([0]: [0], [1]: [0], [2]: [0]) -> ([0]);
+"};
+
+/// Parses the args of a sierra-compile invocation with the given flags.
+fn args(flags: &[&str]) -> Args {
+    Args::try_parse_from(["sierra-compile", "input", "output"].iter().chain(flags)).unwrap()
+}
+
+#[test]
+fn test_input_formats() {
+    let program = ProgramParser::new().parse(SIERRA).unwrap();
+    let expected = compile_input(SIERRA.as_bytes(), &args(&[])).unwrap();
+    assert!(expected.contains("jmp rel"), "Unexpected CASM:\n{expected}");
+
+    let json = serde_json::to_string(&program).unwrap();
+    assert_eq!(
+        compile_input(json.as_bytes(), &args(&["--input-format", "json"])).unwrap(),
+        expected
+    );
+    let binary = encode_program(&program);
+    assert_eq!(compile_input(&binary, &args(&["--input-format", "binary"])).unwrap(), expected);
+    let felts =
+        sierra_to_felt252s(current_sierra_version_id(), current_compiler_version_id(), &program)
+            .unwrap();
+    let felts = serde_json::to_string(&felts).unwrap();
+    assert_eq!(
+        compile_input(felts.as_bytes(), &args(&["--input-format", "felt252s"])).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn test_json_output() {
+    let output =
+        compile_input(SIERRA.as_bytes(), &args(&["--output-format", "json", "--debug-info"]))
+            .unwrap();
+    let output: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert!(!output["bytecode"].as_array().unwrap().is_empty());
+    let program = ProgramParser::new().parse(SIERRA).unwrap();
+    // The offsets include the end of the program.
+    assert_eq!(
+        output["sierra_statement_offsets"].as_array().unwrap().len(),
+        program.statements.len() + 1
+    );
+    assert!(output["instructions_debug_info"].is_array());
+
+    let output = compile_input(SIERRA.as_bytes(), &args(&["--output-format", "json"])).unwrap();
+    let output: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert!(output.get("sierra_statement_offsets").is_none());
+}
+
+#[test]
+fn test_unsupported_flag_combinations() {
+    assert_eq!(
+        compile_input(SIERRA.as_bytes(), &args(&["--debug-info"])).unwrap_err().to_string(),
+        "`--debug-info` is only supported with `--output-format json`."
+    );
+    assert_eq!(
+        compile_input(SIERRA.as_bytes(), &args(&["--output-format", "json", "--annotated"]))
+            .unwrap_err()
+            .to_string(),
+        "`--annotated` is only supported with `--output-format text`."
+    );
+}
@@ -26,6 +26,9 @@ struct Args {
     /// Replaces sierra ids with human-readable ones.
     #[arg(short, long, default_value_t = false)]
     replace_ids: bool,
+    /// Removes the `dup`, `drop` and `rename` invocations that cancel out from the Sierra code.
+    #[arg(long, default_value_t = false)]
+    eliminate_redundant_dup_drop: bool,
     /// The allowed libfuncs list to use (default: most recent audited list).
     #[arg(long)]
     allowed_libfuncs_list_name: Option<String>,
@@ -46,7 +49,11 @@ fn main() -> anyhow::Result<()> {
     let res = starknet_compile(
         args.path,
         args.contract_path,
-        Some(CompilerConfig { replace_ids: args.replace_ids, ..CompilerConfig::default() }),
+        Some(CompilerConfig {
+            replace_ids: args.replace_ids,
+            eliminate_redundant_dup_drop: args.eliminate_redundant_dup_drop,
+            ..CompilerConfig::default()
+        }),
         Some(list_selector),
     )?;
     match args.output {
@@ -0,0 +1,57 @@
+//! Lists the external functions of Starknet contracts that are neither declared by an interface
+//! trait nor used by any test of the project.
+
+use std::path::PathBuf;
+
+use anyhow::bail;
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_compiler::project::{check_compiler_path, setup_project};
+use cairo_lang_defs::ids::TopLevelLanguageElementId;
+use cairo_lang_filesystem::cfg::{Cfg, CfgSet};
+use cairo_lang_starknet::contract::find_contracts;
+use cairo_lang_starknet::starknet_plugin_suite;
+use cairo_lang_starknet::unused_externals::find_unused_external_functions;
+use cairo_lang_test_plugin::test_plugin_suite;
+use clap::Parser;
+
+/// Command line args parser.
+/// Exits with 0/1 if no/some unused external functions were found.
+#[derive(Parser, Debug)]
+#[clap(version, verbatim_doc_comment)]
+struct Args {
+    /// The crate to analyze.
+    path: PathBuf,
+    /// Whether path is a single file.
+    #[arg(short, long)]
+    single_file: bool,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    // Check if args.path is a file or a directory.
+    check_compiler_path(args.single_file, &args.path)?;
+
+    // The tests are compiled as well, as they are the users of the external functions.
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_cfg(CfgSet::from_iter([Cfg::name("test")]))
+        .with_plugin_suite(starknet_plugin_suite())
+        .with_plugin_suite(test_plugin_suite())
+        .build()?;
+    let main_crate_ids = setup_project(db, &args.path)?;
+    if DiagnosticsReporter::stderr().with_crates(&main_crate_ids).check(db) {
+        bail!("failed to compile: {}", args.path.display());
+    }
+
+    let contracts = find_contracts(db, &main_crate_ids);
+    let unused_functions = find_unused_external_functions(db, &contracts);
+    for function_id in &unused_functions {
+        println!("{}", function_id.full_path(db));
+    }
+    if !unused_functions.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
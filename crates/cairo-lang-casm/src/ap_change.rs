@@ -23,6 +23,33 @@ impl Display for ApChange {
     }
 }
 
+impl ApChange {
+    /// Returns the ap change of running code with the ap change `self`, followed by code with the
+    /// ap change `other`. Fails if the total ap change overflows.
+    pub fn checked_add(self, other: ApChange) -> Result<ApChange, ApChangeError> {
+        match (self, other) {
+            (ApChange::Known(a), ApChange::Known(b)) => {
+                a.checked_add(b).map(ApChange::Known).ok_or(ApChangeError::OffsetOverflow)
+            }
+            _ => Ok(ApChange::Unknown),
+        }
+    }
+
+    /// Returns the ap change at a point reached by branches with the ap changes `self` and
+    /// `other`, which is only known if both are known and equal.
+    pub fn merge(self, other: ApChange) -> ApChange {
+        if self == other { self } else { ApChange::Unknown }
+    }
+
+    /// Returns the ap change of running a sequence of code segments with the given ap changes.
+    /// Fails if the total ap change overflows.
+    pub fn checked_sum(
+        ap_changes: impl IntoIterator<Item = ApChange>,
+    ) -> Result<ApChange, ApChangeError> {
+        ap_changes.into_iter().try_fold(ApChange::Known(0), ApChange::checked_add)
+    }
+}
+
 #[derive(Debug, Error, Eq, PartialEq)]
 pub enum ApChangeError {
     #[error("Unknown ap change")]
@@ -34,3 +34,40 @@ fn test_overflow() {
         Err(ApChangeError::OffsetOverflow)
     );
 }
+
+#[test]
+fn test_ap_change_arithmetic() {
+    assert_eq!(ApChange::Known(2).checked_add(ApChange::Known(3)), Ok(ApChange::Known(5)));
+    assert_eq!(ApChange::Known(2).checked_add(ApChange::Unknown), Ok(ApChange::Unknown));
+    assert_eq!(ApChange::Unknown.checked_add(ApChange::Known(3)), Ok(ApChange::Unknown));
+    assert_eq!(
+        ApChange::Known(usize::MAX).checked_add(ApChange::Known(1)),
+        Err(ApChangeError::OffsetOverflow)
+    );
+
+    assert_eq!(ApChange::Known(2).merge(ApChange::Known(2)), ApChange::Known(2));
+    assert_eq!(ApChange::Known(2).merge(ApChange::Known(3)), ApChange::Unknown);
+    assert_eq!(ApChange::Unknown.merge(ApChange::Unknown), ApChange::Unknown);
+
+    assert_eq!(ApChange::checked_sum([]), Ok(ApChange::Known(0)));
+    assert_eq!(
+        ApChange::checked_sum([ApChange::Known(1), ApChange::Known(2), ApChange::Known(3)]),
+        Ok(ApChange::Known(6))
+    );
+    assert_eq!(
+        ApChange::checked_sum([ApChange::Known(1), ApChange::Unknown, ApChange::Known(3)]),
+        Ok(ApChange::Unknown)
+    );
+}
+
+#[test]
+fn test_composed_ap_change_application() {
+    let operand = CellRef { register: Register::AP, offset: 3 };
+    let ap_change = ApChange::checked_sum([ApChange::Known(1), ApChange::Known(4)]).unwrap();
+    assert_eq!(
+        operand.apply_ap_change(ap_change),
+        Ok(CellRef { register: Register::AP, offset: -2 })
+    );
+    let ap_change = ApChange::Known(1).merge(ApChange::Known(4));
+    assert_eq!(operand.apply_ap_change(ap_change), Err(ApChangeError::UnknownApChange));
+}
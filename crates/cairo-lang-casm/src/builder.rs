@@ -14,7 +14,9 @@ use crate::instructions::{
     AddApInstruction, AssertEqInstruction, CallInstruction, Instruction, InstructionBody,
     JnzInstruction, JumpInstruction, RetInstruction,
 };
-use crate::operand::{BinOpOperand, CellRef, DerefOrImmediate, Operation, Register, ResOperand};
+use crate::operand::{
+    prime, BinOpOperand, CellRef, DerefOrImmediate, Operation, Register, ResOperand,
+};
 
 #[cfg(test)]
 #[path = "builder_test.rs"]
@@ -225,6 +227,11 @@ impl CasmBuilder {
         var
     }
 
+    /// Adds a variable holding the constant `value`, as an immediate - see [immediate_value].
+    pub fn add_const(&mut self, value: impl Into<BigInt>) -> Var {
+        self.add_var(CellExpression::Immediate(immediate_value(value.into())))
+    }
+
     /// Allocates a new variable in memory, either local (FP-based) or temp (AP-based).
     pub fn alloc_var(&mut self, local_var: bool) -> Var {
         let var = self.add_var(CellExpression::Deref(CellRef {
@@ -451,10 +458,12 @@ impl CasmBuilder {
 
     /// Rescoping the values, while ignoring all vars not stated in `vars` and giving the vars on
     /// the left side the values of the vars on the right side.
-    pub fn rescope<const VAR_COUNT: usize>(&mut self, vars: [(Var, Var); VAR_COUNT]) {
+    pub fn rescope(&mut self, vars: impl IntoIterator<Item = (Var, Var)>) {
         self.main_state.validate_finality();
-        let values =
-            vars.map(|(new_var, value_var)| (new_var, self.main_state.get_adjusted(value_var)));
+        let values: Vec<_> = vars
+            .into_iter()
+            .map(|(new_var, value_var)| (new_var, self.main_state.get_adjusted(value_var)))
+            .collect();
         self.main_state.ap_change = 0;
         self.main_state.allocated = 0;
         self.main_state.vars.clear();
@@ -640,6 +649,15 @@ impl Default for CasmBuilder {
     }
 }
 
+/// Returns `value` as an immediate value. Values in the range `(-PRIME, PRIME)` are kept as is,
+/// and other values are reduced into it, keeping their sign.
+///
+/// Values are not replaced by their negation modulo the prime, even when it is smaller, as the
+/// hints using the immediates compare them as integers.
+pub fn immediate_value(value: BigInt) -> BigInt {
+    value % prime()
+}
+
 #[macro_export]
 macro_rules! casm_build_extend {
     ($builder:ident,) => {};
@@ -656,7 +674,9 @@ macro_rules! casm_build_extend {
         $crate::casm_build_extend!($builder, $($tok)*)
     };
     ($builder:ident, const $imm:ident = $value:expr; $($tok:tt)*) => {
-        let $imm = $builder.add_var($crate::cell_expression::CellExpression::Immediate(($value).into()));
+        // Values are commonly written in parentheses, which are no longer needed as an argument.
+        #[allow(unused_parens)]
+        let $imm = $builder.add_const($value);
         $crate::casm_build_extend!($builder, $($tok)*)
     };
     ($builder:ident, assert $dst:ident = $res:ident; $($tok:tt)*) => {
@@ -1,10 +1,12 @@
 use indoc::indoc;
 use itertools::join;
+use num_bigint::BigInt;
 use pretty_assertions::assert_eq;
 
 use super::CasmBuilder;
 use crate::builder::CasmBuildResult;
 use crate::cell_expression::CellExpression;
+use crate::operand::prime;
 use crate::{casm_build_extend, res};
 
 #[test]
@@ -352,3 +354,23 @@ fn test_fail() {
         "}
     );
 }
+
+#[test]
+fn test_consts() {
+    let mut builder = CasmBuilder::default();
+    casm_build_extend! {builder,
+        const u128_bound = BigInt::from(2).pow(128);
+        const minus_one = -1;
+        const prime_plus_one = prime() + 1;
+        const minus_prime_minus_one = -prime() - 1;
+    };
+    let CasmBuildResult { branches: [(state, _)], .. } = builder.build(["Fallthrough"]);
+    for (var, value) in [
+        (u128_bound, BigInt::from(2).pow(128)),
+        (minus_one, BigInt::from(-1)),
+        (prime_plus_one, BigInt::from(1)),
+        (minus_prime_minus_one, BigInt::from(-1)),
+    ] {
+        assert_eq!(state.get_adjusted(var), CellExpression::Immediate(value));
+    }
+}
@@ -0,0 +1,111 @@
+use num_bigint::BigInt;
+use thiserror::Error;
+
+use crate::assembler::{ApUpdate, InstructionRepr, Op1Addr, Opcode, PcUpdate, Res};
+use crate::encoder::DecodingError;
+use crate::instructions::{
+    AddApInstruction, AssertEqInstruction, CallInstruction, Instruction, InstructionBody,
+    JnzInstruction, JumpInstruction, RetInstruction,
+};
+use crate::operand::{BinOpOperand, CellRef, DerefOrImmediate, Operation, Register, ResOperand};
+
+#[cfg(test)]
+#[path = "disassembler_test.rs"]
+mod test;
+
+/// An error in disassembling bytecode.
+#[derive(Debug, Error, Eq, PartialEq)]
+#[error("Failed to disassemble the instruction at offset {offset}: {error}")]
+pub struct DisassemblyError {
+    /// The offset of the instruction within the bytecode.
+    pub offset: usize,
+    pub error: DecodingError,
+}
+
+/// Disassembles bytecode into instructions. The inverse of assembling and encoding the
+/// instructions.
+///
+/// Immediate values are taken as is. Bytecode whose values were reduced modulo the field prime,
+/// such as the bytecode of a contract class, has its negative immediates as large positive values.
+pub fn disassemble(bytecode: &[BigInt]) -> Result<Vec<Instruction>, DisassemblyError> {
+    let mut instructions = vec![];
+    let mut offset = 0;
+    while offset < bytecode.len() {
+        let instruction = InstructionRepr::decode(&bytecode[offset..])
+            .and_then(|repr| repr.disassemble())
+            .map_err(|error| DisassemblyError { offset, error })?;
+        offset += instruction.body.op_size();
+        instructions.push(instruction);
+    }
+    Ok(instructions)
+}
+
+impl InstructionRepr {
+    /// Returns the instruction assembled into this representation. The inverse of
+    /// [Instruction::assemble].
+    pub fn disassemble(&self) -> Result<Instruction, DecodingError> {
+        let relative = self.pc_update == PcUpdate::JumpRel;
+        let body = match (&self.opcode, &self.pc_update, &self.ap_update) {
+            (Opcode::Ret, PcUpdate::Jump, ApUpdate::Regular) => {
+                InstructionBody::Ret(RetInstruction {})
+            }
+            (Opcode::AssertEq, PcUpdate::Regular, ApUpdate::Regular | ApUpdate::Add1) => {
+                InstructionBody::AssertEq(AssertEqInstruction {
+                    a: CellRef { register: self.dst_register, offset: self.off0 },
+                    b: self.res_operand()?,
+                })
+            }
+            (Opcode::Call, PcUpdate::Jump | PcUpdate::JumpRel, ApUpdate::Add2) => {
+                InstructionBody::Call(CallInstruction { target: self.op1_operand()?, relative })
+            }
+            (Opcode::Nop, PcUpdate::Regular, ApUpdate::Add) => {
+                InstructionBody::AddAp(AddApInstruction { operand: self.res_operand()? })
+            }
+            (
+                Opcode::Nop,
+                PcUpdate::Jump | PcUpdate::JumpRel,
+                ApUpdate::Regular | ApUpdate::Add1,
+            ) => InstructionBody::Jump(JumpInstruction { target: self.op1_operand()?, relative }),
+            (Opcode::Nop, PcUpdate::Jnz, ApUpdate::Regular | ApUpdate::Add1) => {
+                InstructionBody::Jnz(JnzInstruction {
+                    jump_offset: self.op1_operand()?,
+                    condition: CellRef { register: self.dst_register, offset: self.off0 },
+                })
+            }
+            _ => return Err(DecodingError::UnsupportedInstruction),
+        };
+        let instruction = Instruction::new(body, self.ap_update == ApUpdate::Add1);
+        // The fields not used by the instruction have fixed values in the assembled
+        // representation, so an instruction that is not assembled back to this representation is
+        // not a valid casm instruction.
+        if instruction.assemble() != *self {
+            return Err(DecodingError::UnsupportedInstruction);
+        }
+        Ok(instruction)
+    }
+
+    /// Returns the op1 operand of the instruction, as the target of a jump or a call.
+    fn op1_operand(&self) -> Result<DerefOrImmediate, DecodingError> {
+        Ok(match self.op1_addr {
+            Op1Addr::Imm => self.imm.clone().ok_or(DecodingError::UnsupportedInstruction)?.into(),
+            Op1Addr::AP => CellRef { register: Register::AP, offset: self.off2 }.into(),
+            Op1Addr::FP => CellRef { register: Register::FP, offset: self.off2 }.into(),
+            Op1Addr::Op0 => return Err(DecodingError::UnsupportedInstruction),
+        })
+    }
+
+    /// Returns the res operand of the instruction.
+    fn res_operand(&self) -> Result<ResOperand, DecodingError> {
+        let op0 = CellRef { register: self.op0_register, offset: self.off1 };
+        let op = match self.res {
+            Res::Op1 if self.op1_addr == Op1Addr::Op0 => {
+                return Ok(ResOperand::DoubleDeref(op0, self.off2));
+            }
+            Res::Op1 => return Ok(self.op1_operand()?.into()),
+            Res::Add => Operation::Add,
+            Res::Mul => Operation::Mul,
+            Res::Unconstrained => return Err(DecodingError::UnsupportedInstruction),
+        };
+        Ok(ResOperand::BinOp(BinOpOperand { op, a: op0, b: self.op1_operand()? }))
+    }
+}
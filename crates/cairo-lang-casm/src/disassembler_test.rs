@@ -0,0 +1,75 @@
+use num_bigint::BigInt;
+use pretty_assertions::assert_eq;
+use test_case::test_case;
+
+use super::{disassemble, DisassemblyError};
+use crate::casm;
+use crate::encoder::DecodingError;
+use crate::inline::CasmContext;
+
+#[test_case(casm! {
+    [ap + 0] = 1, ap++;
+    [ap + 0] = 13, ap++;
+    call rel 3;
+    ret;
+    jmp rel 5 if [fp + -3] != 0;
+    [ap + 0] = [fp + -5], ap++;
+    jmp rel 8;
+    [ap + 0] = [fp + -5] + [fp + -4], ap++;
+    [fp + -3] = [ap + 0] + 1, ap++;
+    call rel (-9);
+    ret;
+}; "fib")]
+#[test_case(casm! {
+    [fp + 2] = [[ap + -1] + 3];
+    [ap + 0] = [fp + -3] * [ap + -2];
+    [ap + 0] = [ap + -1] * 5, ap++;
+    ap += 7;
+    ap += [fp + -4];
+    jmp abs 3;
+    jmp abs [ap + -1], ap++;
+    jmp rel 4, ap++;
+    call abs [fp + -3];
+    jmp rel [ap + -2] if [ap + -1] != 0, ap++;
+}; "operand kinds")]
+fn test_disassemble_round_trip(casm: CasmContext) {
+    let bytecode: Vec<BigInt> =
+        casm.instructions.iter().flat_map(|inst| inst.assemble().encode()).collect();
+    assert_eq!(disassemble(&bytecode), Ok(casm.instructions));
+}
+
+#[test_case(vec![-1], DecodingError::InvalidInstructionWord(BigInt::from(-1));
+            "negative word")]
+#[test_case(vec![0x8000000000000000u64 as i128],
+            DecodingError::InvalidInstructionWord(BigInt::from(0x8000000000000000u64));
+            "reserved bit")]
+#[test_case(vec![0x401c7fff7fff7fff],
+            DecodingError::InvalidFlags { encoding: 0x401c7fff7fff7fff, field: "op1 address" };
+            "multiple op1 addresses")]
+#[test_case(vec![0x5000800080008000],
+            DecodingError::InvalidFlags { encoding: 0x5000800080008000, field: "opcode" };
+            "multiple opcodes")]
+#[test_case(vec![0x480680017fff8000], DecodingError::MissingImmediate(0x480680017fff8000);
+            "missing immediate")]
+#[test_case(vec![0x800080008000], DecodingError::UnsupportedInstruction; "nop")]
+#[test_case(vec![0x208b7fff7fff7ffd], DecodingError::UnsupportedInstruction;
+            "non canonical ret")]
+fn test_disassemble_errors(bytecode: Vec<i128>, error: DecodingError) {
+    let bytecode: Vec<BigInt> = bytecode.into_iter().map(BigInt::from).collect();
+    assert_eq!(disassemble(&bytecode), Err(DisassemblyError { offset: 0, error }));
+}
+
+#[test]
+fn test_disassemble_error_offset() {
+    let bytecode: Vec<BigInt> = [0x480680017fff8000u64, 1, 0x208b7fff7fff7ffe, 0x480680017fff8000]
+        .into_iter()
+        .map(BigInt::from)
+        .collect();
+    assert_eq!(
+        disassemble(&bytecode),
+        Err(DisassemblyError {
+            offset: 3,
+            error: DecodingError::MissingImmediate(0x480680017fff8000)
+        })
+    );
+}
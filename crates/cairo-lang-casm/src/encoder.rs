@@ -1,4 +1,6 @@
 use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use thiserror::Error;
 
 use crate::assembler::{ApUpdate, FpUpdate, InstructionRepr, Op1Addr, Opcode, PcUpdate, Res};
 use crate::operand::Register;
@@ -25,6 +27,21 @@ const OPCODE_CALL_BIT: i32 = 12;
 const OPCODE_RET_BIT: i32 = 13;
 const OPCODE_ASSERT_EQ_BIT: i32 = 14;
 
+/// An error in decoding an instruction.
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum DecodingError {
+    #[error("Missing instruction word.")]
+    MissingInstructionWord,
+    #[error("{0} is not a valid instruction word.")]
+    InvalidInstructionWord(BigInt),
+    #[error("Instruction word {encoding:#x} has invalid {field} flags.")]
+    InvalidFlags { encoding: u64, field: &'static str },
+    #[error("Instruction word {0:#x} is missing its immediate value.")]
+    MissingImmediate(u64),
+    #[error("The instruction has no casm representation.")]
+    UnsupportedInstruction,
+}
+
 impl InstructionRepr {
     pub fn encode(&self) -> Vec<BigInt> {
         // Convert the offsets from possibly negative numbers in the range [-2^15, 2^15)
@@ -117,4 +134,87 @@ impl InstructionRepr {
             vec![bigint_encoding]
         }
     }
+
+    /// Decodes the instruction at the start of `bytecode`, which consists of its instruction word
+    /// and its immediate value, if it has one. The inverse of [Self::encode].
+    pub fn decode(bytecode: &[BigInt]) -> Result<Self, DecodingError> {
+        let word = bytecode.first().ok_or(DecodingError::MissingInstructionWord)?;
+        // The highest bit of the 64-bit word is reserved, and must be zero.
+        let encoding = word
+            .to_u64()
+            .filter(|encoding| encoding >> 63 == 0)
+            .ok_or_else(|| DecodingError::InvalidInstructionWord(word.clone()))?;
+        let offset = |idx: u32| {
+            let offset_enc = (encoding >> (idx * OFFSET_BITS)) & ((1 << OFFSET_BITS) - 1);
+            (offset_enc as i32 - (1 << (OFFSET_BITS - 1))) as i16
+        };
+        let flags = encoding >> (3 * OFFSET_BITS);
+        let flag = |bit: i32| flags & (1 << bit) != 0;
+        let register = |bit: i32| if flag(bit) { Register::FP } else { Register::AP };
+        let invalid_flags = |field| DecodingError::InvalidFlags { encoding, field };
+
+        let op1_addr = match (flag(OP1_IMM_BIT), flag(OP1_FP_BIT), flag(OP1_AP_BIT)) {
+            (false, false, false) => Op1Addr::Op0,
+            (true, false, false) => Op1Addr::Imm,
+            (false, true, false) => Op1Addr::FP,
+            (false, false, true) => Op1Addr::AP,
+            _ => return Err(invalid_flags("op1 address")),
+        };
+        let pc_update = match (flag(PC_JUMP_ABS_BIT), flag(PC_JUMP_REL_BIT), flag(PC_JNZ_BIT)) {
+            (false, false, false) => PcUpdate::Regular,
+            (true, false, false) => PcUpdate::Jump,
+            (false, true, false) => PcUpdate::JumpRel,
+            (false, false, true) => PcUpdate::Jnz,
+            _ => return Err(invalid_flags("pc update")),
+        };
+        let res = match (flag(RES_ADD_BIT), flag(RES_MUL_BIT), &pc_update) {
+            (false, false, PcUpdate::Jnz) => Res::Unconstrained,
+            (false, false, _) => Res::Op1,
+            (true, false, pc_update) if *pc_update != PcUpdate::Jnz => Res::Add,
+            (false, true, pc_update) if *pc_update != PcUpdate::Jnz => Res::Mul,
+            _ => return Err(invalid_flags("res")),
+        };
+        let opcode_flags =
+            (flag(OPCODE_CALL_BIT), flag(OPCODE_RET_BIT), flag(OPCODE_ASSERT_EQ_BIT));
+        let opcode = match opcode_flags {
+            (false, false, false) => Opcode::Nop,
+            (true, false, false) => Opcode::Call,
+            (false, true, false) => Opcode::Ret,
+            (false, false, true) => Opcode::AssertEq,
+            _ => return Err(invalid_flags("opcode")),
+        };
+        let ap_update = match (flag(AP_ADD_BIT), flag(AP_ADD1_BIT), &opcode) {
+            (false, false, Opcode::Call) => ApUpdate::Add2,
+            (false, false, _) => ApUpdate::Regular,
+            (true, false, opcode) if *opcode != Opcode::Call => ApUpdate::Add,
+            (false, true, opcode) if *opcode != Opcode::Call => ApUpdate::Add1,
+            _ => return Err(invalid_flags("ap update")),
+        };
+        let fp_update = match opcode {
+            Opcode::Nop | Opcode::AssertEq => FpUpdate::Regular,
+            Opcode::Call => FpUpdate::ApPlus2,
+            Opcode::Ret => FpUpdate::Dst,
+        };
+        let imm = match op1_addr {
+            Op1Addr::Imm => {
+                Some(bytecode.get(1).ok_or(DecodingError::MissingImmediate(encoding))?.clone())
+            }
+            _ => None,
+        };
+
+        Ok(Self {
+            off0: offset(0),
+            off1: offset(1),
+            off2: offset(2),
+            imm,
+            dst_register: register(DST_REG_BIT),
+            op0_register: register(OP0_REG_BIT),
+            op1_addr,
+            res,
+            pc_update,
+            ap_update,
+            fp_update,
+            opcode,
+        })
+    }
 }
@@ -2,6 +2,7 @@ use num_bigint::BigInt;
 use pretty_assertions::assert_eq;
 use test_case::test_case;
 
+use crate::assembler::InstructionRepr;
 use crate::casm;
 use crate::inline::CasmContext;
 
@@ -108,3 +109,18 @@ fn test_encode_multiple(casm: CasmContext, expected: Vec<i128>) {
         casm.instructions.iter().flat_map(|inst| inst.assemble().encode()).collect();
     assert_eq!(enc, exp);
 }
+
+#[test_case(casm!(jmp abs 3;), 0x8780017fff7fff, Some(3); "jmp abs 3;")]
+#[test_case(casm!(call rel (-5);), 0x1104800180018000, Some(-5); "call rel (-5);")]
+#[test_case(casm!(ret;), 0x208b7fff7fff7ffe, None; "ret;")]
+#[test_case(
+    casm!([ap + 0] = [fp + -5], ap++;),
+    0x480a7ffb7fff8000,
+    None;
+    "[ap + 0] = [fp + -5], ap++;"
+)]
+fn test_decode(mut casm: CasmContext, encoding: u64, immediate: Option<i16>) {
+    let mut bytecode = vec![BigInt::from(encoding)];
+    bytecode.extend(immediate.map(BigInt::from));
+    assert_eq!(InstructionRepr::decode(&bytecode), Ok(casm.instructions.remove(0).assemble()));
+}
@@ -0,0 +1,380 @@
+use std::collections::HashMap;
+
+use num_bigint::BigInt;
+use num_traits::{Signed, Zero};
+use thiserror::Error;
+
+use crate::cell_expression::{CellExpression, CellOperator};
+use crate::hints::{CoreHint, CoreHintBase, Hint};
+use crate::instructions::{Instruction, InstructionBody};
+use crate::operand::{
+    prime, BinOpOperand, CellRef, DerefOrImmediate, Operation, Register, ResOperand,
+};
+
+#[cfg(test)]
+#[path = "executor_test.rs"]
+mod test;
+
+/// The number of address bits within a segment. Segment `i` starts at address `i << SEGMENT_BITS`.
+const SEGMENT_BITS: u32 = 32;
+
+/// Returns the given value reduced to the range `[0, PRIME)`.
+fn reduce(value: BigInt) -> BigInt {
+    let value = value % prime();
+    if value.is_negative() { value + prime() } else { value }
+}
+
+/// Returns the given field element as a signed value in the range `(-PRIME / 2, PRIME / 2)`.
+fn to_signed(value: &BigInt) -> BigInt {
+    if value > &(prime() >> 1) { value - prime() } else { value.clone() }
+}
+
+/// Returns the inverse of the given non-zero field element.
+fn inverse(value: &BigInt) -> BigInt {
+    value.modpow(&(prime() - 2), prime())
+}
+
+/// An error in executing casm instructions.
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum ExecutionError {
+    #[error("Memory cell {0:#x} is unknown.")]
+    UnknownMemoryCell(usize),
+    #[error("Memory cell {address:#x} holds {existing}, and cannot be set to {value}.")]
+    InconsistentMemory { address: usize, existing: BigInt, value: BigInt },
+    #[error("{0} is not a valid address.")]
+    InvalidAddress(BigInt),
+    #[error("There is no instruction at pc {0}.")]
+    InvalidPc(BigInt),
+    #[error("Cannot deduce the unknown operands of `{0}`.")]
+    CannotDeduceOperands(String),
+    #[error("Division by zero.")]
+    DivisionByZero,
+    #[error("Unsupported hint: {0:?}.")]
+    UnsupportedHint(Box<Hint>),
+}
+
+/// A write-once memory of field elements.
+#[derive(Clone, Debug, Default)]
+pub struct Memory {
+    cells: HashMap<usize, BigInt>,
+}
+impl Memory {
+    /// Returns the value of the cell at the given address, if it is known.
+    pub fn get(&self, address: usize) -> Option<&BigInt> {
+        self.cells.get(&address)
+    }
+
+    /// Sets the value of the cell at the given address, reduced to a field element. Fails if the
+    /// cell already holds a different value.
+    pub fn insert(&mut self, address: usize, value: BigInt) -> Result<(), ExecutionError> {
+        let value = reduce(value);
+        match self.cells.get(&address) {
+            Some(existing) if *existing != value => Err(ExecutionError::InconsistentMemory {
+                address,
+                existing: existing.clone(),
+                value,
+            }),
+            Some(_) => Ok(()),
+            None => {
+                self.cells.insert(address, value);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A minimal casm executor, for running instructions on concrete values in tests.
+///
+/// Memory is flat, and pointers are plain addresses: segment `i` starts at address
+/// `i << SEGMENT_BITS`, and the execution segment is the first segment. The code is not stored
+/// in memory, and `pc` is the offset of the current instruction within the code.
+#[derive(Clone, Debug)]
+pub struct Vm {
+    pub memory: Memory,
+    pub ap: usize,
+    pub fp: usize,
+    pub pc: usize,
+    /// The number of segments allocated so far, including the reserved segment 0.
+    n_segments: usize,
+}
+impl Default for Vm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Vm {
+    /// Returns a new executor, with `ap` and `fp` at the start of the execution segment.
+    pub fn new() -> Self {
+        let mut vm = Self { memory: Memory::default(), ap: 0, fp: 0, pc: 0, n_segments: 1 };
+        vm.ap = vm.add_segment();
+        vm.fp = vm.ap;
+        vm
+    }
+
+    /// Allocates a new memory segment and returns its start address.
+    pub fn add_segment(&mut self) -> usize {
+        let start = self.n_segments << SEGMENT_BITS;
+        self.n_segments += 1;
+        start
+    }
+
+    /// Writes the given value at `ap`, and increments `ap`.
+    pub fn push(&mut self, value: impl Into<BigInt>) -> Result<(), ExecutionError> {
+        self.memory.insert(self.ap, value.into())?;
+        self.ap += 1;
+        Ok(())
+    }
+
+    /// Returns the address of the given cell.
+    pub fn cell_address(&self, cell: &CellRef) -> Result<usize, ExecutionError> {
+        let base = match cell.register {
+            Register::AP => self.ap,
+            Register::FP => self.fp,
+        };
+        add_offset(base, &cell.offset.into())
+    }
+
+    /// Returns the value of the given cell.
+    pub fn get_cell(&self, cell: &CellRef) -> Result<BigInt, ExecutionError> {
+        let address = self.cell_address(cell)?;
+        self.memory.get(address).cloned().ok_or(ExecutionError::UnknownMemoryCell(address))
+    }
+
+    /// Sets the value of the given cell.
+    pub fn insert_cell(&mut self, cell: &CellRef, value: BigInt) -> Result<(), ExecutionError> {
+        self.memory.insert(self.cell_address(cell)?, value)
+    }
+
+    /// Returns the value of the given res operand.
+    pub fn get_res(&self, operand: &ResOperand) -> Result<BigInt, ExecutionError> {
+        self.get_cell_expression(&CellExpression::from_res_operand(operand.clone()))
+    }
+
+    /// Returns the value of the given cell expression.
+    pub fn get_cell_expression(&self, expr: &CellExpression) -> Result<BigInt, ExecutionError> {
+        Ok(match expr {
+            CellExpression::Deref(cell) => self.get_cell(cell)?,
+            CellExpression::DoubleDeref(cell, offset) => {
+                let address = add_offset(to_address(&self.get_cell(cell)?)?, &(*offset).into())?;
+                self.memory
+                    .get(address)
+                    .cloned()
+                    .ok_or(ExecutionError::UnknownMemoryCell(address))?
+            }
+            CellExpression::Immediate(value) => reduce(value.clone()),
+            CellExpression::BinOp { op, a, b } => {
+                let a = self.get_cell(a)?;
+                let b = self.get_deref_or_immediate(b)?;
+                match op {
+                    CellOperator::Add => reduce(a + b),
+                    CellOperator::Sub => reduce(a - b),
+                    CellOperator::Mul => reduce(a * b),
+                    CellOperator::Div if b.is_zero() => return Err(ExecutionError::DivisionByZero),
+                    CellOperator::Div => reduce(a * inverse(&b)),
+                }
+            }
+        })
+    }
+
+    /// Returns the value of the given deref or immediate operand.
+    fn get_deref_or_immediate(&self, operand: &DerefOrImmediate) -> Result<BigInt, ExecutionError> {
+        match operand {
+            DerefOrImmediate::Deref(cell) => self.get_cell(cell),
+            DerefOrImmediate::Immediate(value) => Ok(reduce(value.value.clone())),
+        }
+    }
+
+    /// Runs the given instructions from the current `pc`, until `pc` reaches the end of the code,
+    /// or jumps beyond it.
+    ///
+    /// `hint_handler` is called on the hints of each instruction before it is executed.
+    /// [Vm::execute_hint] may be used as a handler that supports only some of the core hints.
+    pub fn run(
+        &mut self,
+        instructions: &[Instruction],
+        hint_handler: &mut impl FnMut(&mut Vm, &Hint) -> Result<(), ExecutionError>,
+    ) -> Result<(), ExecutionError> {
+        let mut code = HashMap::new();
+        let mut code_size = 0;
+        for instruction in instructions {
+            code.insert(code_size, instruction);
+            code_size += instruction.body.op_size();
+        }
+        while self.pc < code_size {
+            let instruction =
+                code.get(&self.pc).ok_or_else(|| ExecutionError::InvalidPc(self.pc.into()))?;
+            for hint in &instruction.hints {
+                hint_handler(self, hint)?;
+            }
+            self.step(instruction)?;
+        }
+        Ok(())
+    }
+
+    /// Executes a single instruction, ignoring its hints.
+    pub fn step(&mut self, instruction: &Instruction) -> Result<(), ExecutionError> {
+        let next_pc = self.pc + instruction.body.op_size();
+        match &instruction.body {
+            InstructionBody::AssertEq(insn) => {
+                if !self.assert_eq(&insn.a, &insn.b)? {
+                    return Err(ExecutionError::CannotDeduceOperands(instruction.to_string()));
+                }
+                self.pc = next_pc;
+            }
+            InstructionBody::Jump(insn) => {
+                let target = self.get_deref_or_immediate(&insn.target)?;
+                self.pc = self.jump_target(target, insn.relative)?;
+            }
+            InstructionBody::Jnz(insn) => {
+                self.pc = if self.get_cell(&insn.condition)?.is_zero() {
+                    next_pc
+                } else {
+                    self.jump_target(self.get_deref_or_immediate(&insn.jump_offset)?, true)?
+                };
+            }
+            InstructionBody::Call(insn) => {
+                let target = self.get_deref_or_immediate(&insn.target)?;
+                self.memory.insert(self.ap, self.fp.into())?;
+                self.memory.insert(self.ap + 1, next_pc.into())?;
+                self.pc = self.jump_target(target, insn.relative)?;
+                self.ap += 2;
+                self.fp = self.ap;
+            }
+            InstructionBody::Ret(_) => {
+                self.pc =
+                    to_address(&self.get_cell(&CellRef { register: Register::FP, offset: -1 })?)?;
+                self.fp =
+                    to_address(&self.get_cell(&CellRef { register: Register::FP, offset: -2 })?)?;
+            }
+            InstructionBody::AddAp(insn) => {
+                self.ap = add_offset(self.ap, &to_signed(&self.get_res(&insn.operand)?))?;
+                self.pc = next_pc;
+            }
+        }
+        if instruction.inc_ap {
+            self.ap += 1;
+        }
+        Ok(())
+    }
+
+    /// Returns the pc after jumping to the given target.
+    fn jump_target(&self, target: BigInt, relative: bool) -> Result<usize, ExecutionError> {
+        let target = if relative { self.pc + to_signed(&target) } else { target };
+        usize::try_from(&target).map_err(|_| ExecutionError::InvalidPc(target))
+    }
+
+    /// Asserts that `dst` equals `res`, deducing the value of at most one unknown cell of them.
+    /// Returns false if there is more than one unknown cell.
+    fn assert_eq(&mut self, dst: &CellRef, res: &ResOperand) -> Result<bool, ExecutionError> {
+        let dst_address = self.cell_address(dst)?;
+        let dst_value = self.memory.get(dst_address).cloned();
+        // The address and the value of the cell within `res` which is deduced from `dst`.
+        let (address, value) = match res {
+            ResOperand::Deref(cell) => (self.cell_address(cell)?, dst_value),
+            ResOperand::DoubleDeref(cell, offset) => {
+                let address = add_offset(to_address(&self.get_cell(cell)?)?, &(*offset).into())?;
+                (address, dst_value)
+            }
+            ResOperand::Immediate(value) => {
+                self.memory.insert(dst_address, value.value.clone())?;
+                return Ok(true);
+            }
+            ResOperand::BinOp(BinOpOperand { op, a, b }) => {
+                let a_address = self.cell_address(a)?;
+                let a_value = self.memory.get(a_address).cloned();
+                let (b_address, b_value) = match b {
+                    DerefOrImmediate::Deref(cell) => {
+                        let address = self.cell_address(cell)?;
+                        (Some(address), self.memory.get(address).cloned())
+                    }
+                    DerefOrImmediate::Immediate(value) => (None, Some(reduce(value.value.clone()))),
+                };
+                match (a_value, b_value, dst_value) {
+                    (Some(a_value), Some(b_value), _) => {
+                        let value = match op {
+                            Operation::Add => a_value + b_value,
+                            Operation::Mul => a_value * b_value,
+                        };
+                        self.memory.insert(dst_address, value)?;
+                        return Ok(true);
+                    }
+                    (None, Some(known), Some(dst_value)) => {
+                        (a_address, Some(solve(op, dst_value, &known)?))
+                    }
+                    (Some(known), None, Some(dst_value)) => {
+                        (b_address.unwrap(), Some(solve(op, dst_value, &known)?))
+                    }
+                    _ => return Ok(false),
+                }
+            }
+        };
+        match (self.memory.get(address).cloned(), value) {
+            (Some(res_value), _) => self.memory.insert(dst_address, res_value)?,
+            (None, Some(value)) => self.memory.insert(address, value)?,
+            (None, None) => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Executes the given hint, if it is one of the supported core hints: `AllocSegment`,
+    /// `TestLessThan`, `TestLessThanOrEqual`, `WideMul128`, `DivMod` and `SquareRoot`.
+    pub fn execute_hint(&mut self, hint: &Hint) -> Result<(), ExecutionError> {
+        let unsupported = || ExecutionError::UnsupportedHint(Box::new(hint.clone()));
+        let Hint::Core(CoreHintBase::Core(hint)) = hint else {
+            return Err(unsupported());
+        };
+        match hint {
+            CoreHint::AllocSegment { dst } => {
+                let segment = self.add_segment();
+                self.insert_cell(dst, segment.into())?;
+            }
+            CoreHint::TestLessThan { lhs, rhs, dst } => {
+                let value = self.get_res(lhs)? < self.get_res(rhs)?;
+                self.insert_cell(dst, usize::from(value).into())?;
+            }
+            CoreHint::TestLessThanOrEqual { lhs, rhs, dst } => {
+                let value = self.get_res(lhs)? <= self.get_res(rhs)?;
+                self.insert_cell(dst, usize::from(value).into())?;
+            }
+            CoreHint::WideMul128 { lhs, rhs, high, low } => {
+                let product = self.get_res(lhs)? * self.get_res(rhs)?;
+                self.insert_cell(high, &product >> 128)?;
+                self.insert_cell(low, product & BigInt::from(u128::MAX))?;
+            }
+            CoreHint::DivMod { lhs, rhs, quotient, remainder } => {
+                let lhs = self.get_res(lhs)?;
+                let rhs = self.get_res(rhs)?;
+                if rhs.is_zero() {
+                    return Err(ExecutionError::DivisionByZero);
+                }
+                self.insert_cell(quotient, &lhs / &rhs)?;
+                self.insert_cell(remainder, lhs % rhs)?;
+            }
+            CoreHint::SquareRoot { value, dst } => {
+                let value = self.get_res(value)?;
+                self.insert_cell(dst, value.sqrt())?;
+            }
+            _ => return Err(unsupported()),
+        }
+        Ok(())
+    }
+}
+
+/// Returns the value of the operand of `op` which is not `known`, given the result of `op`.
+fn solve(op: &Operation, result: BigInt, known: &BigInt) -> Result<BigInt, ExecutionError> {
+    match op {
+        Operation::Add => Ok(reduce(result - known)),
+        Operation::Mul if known.is_zero() => Err(ExecutionError::DivisionByZero),
+        Operation::Mul => Ok(reduce(result * inverse(known))),
+    }
+}
+
+/// Returns the given value as an address.
+fn to_address(value: &BigInt) -> Result<usize, ExecutionError> {
+    usize::try_from(value).map_err(|_| ExecutionError::InvalidAddress(value.clone()))
+}
+
+/// Returns the address at the given offset from `address`.
+fn add_offset(address: usize, offset: &BigInt) -> Result<usize, ExecutionError> {
+    to_address(&(address + offset))
+}
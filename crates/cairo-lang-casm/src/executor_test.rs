@@ -0,0 +1,134 @@
+use num_bigint::BigInt;
+use pretty_assertions::assert_eq;
+use test_log::test;
+
+use super::{ExecutionError, Vm};
+use crate::casm;
+use crate::hints::{CoreHint, Hint};
+use crate::operand::{prime, CellRef, Register};
+
+/// Returns the value of `[ap + offset]`.
+fn ap_value(vm: &Vm, offset: i16) -> BigInt {
+    vm.get_cell(&CellRef { register: Register::AP, offset }).unwrap()
+}
+
+#[test]
+fn test_loop() {
+    let ctx = casm! {
+        [ap] = 1, ap++;
+        [ap] = 1, ap++;
+        [ap] = 10, ap++;
+        body:
+        [ap] = [ap - 2], ap++;
+        [ap] = [ap - 4] + [ap - 3], ap++;
+        // Deduces the decremented counter.
+        [ap - 3] = [ap] + 1, ap++;
+        jmp body if [ap - 1] != 0;
+    };
+    let mut vm = Vm::new();
+    let initial_ap = vm.ap;
+    vm.run(&ctx.instructions, &mut Vm::execute_hint).unwrap();
+    assert_eq!(vm.ap, initial_ap + 33);
+    assert_eq!(ap_value(&vm, -2), BigInt::from(144));
+}
+
+#[test]
+fn test_call_and_ret() {
+    let ctx = casm! {
+        [ap] = 5, ap++;
+        call square;
+        jmp end;
+        square:
+        [ap] = [fp - 3] * [fp - 3], ap++;
+        ret;
+        end:
+    };
+    let mut vm = Vm::new();
+    let initial_fp = vm.fp;
+    vm.run(&ctx.instructions, &mut Vm::execute_hint).unwrap();
+    assert_eq!(vm.fp, initial_fp);
+    assert_eq!(ap_value(&vm, -1), BigInt::from(25));
+}
+
+#[test]
+fn test_deductions() {
+    let ctx = casm! {
+        [fp] = [ap] * 3, ap++;
+        [fp + 1] = [[fp + 2] + 1];
+        [ap] = (-1), ap++;
+    };
+    let mut vm = Vm::new();
+    let segment = vm.add_segment();
+    vm.push(7).unwrap();
+    vm.push(8).unwrap();
+    vm.push(segment).unwrap();
+    vm.run(&ctx.instructions, &mut Vm::execute_hint).unwrap();
+    assert_eq!((ap_value(&vm, -2) * 3) % prime(), BigInt::from(7));
+    assert_eq!(vm.memory.get(segment + 1), Some(&BigInt::from(8)));
+    assert_eq!(ap_value(&vm, -1), prime() - 1);
+}
+
+#[test]
+fn test_hints() {
+    let ctx = casm! {
+        %{ memory[ap + 0] = memory[fp + 0] < memory[fp + 1] %}
+        jmp a_is_smaller if [ap] != 0, ap++;
+        [ap] = [fp + 1], ap++;
+        jmp end;
+        a_is_smaller:
+        [ap] = [fp], ap++;
+        end:
+        %{ memory[ap + 0] = segments.add() %}
+        ap += 1;
+    };
+    for (a, b, min) in [(3, 5, 3), (5, 3, 3), (4, 4, 4)] {
+        let mut vm = Vm::new();
+        vm.push(a).unwrap();
+        vm.push(b).unwrap();
+        vm.run(&ctx.instructions, &mut Vm::execute_hint).unwrap();
+        assert_eq!(ap_value(&vm, -2), BigInt::from(min));
+        assert!(vm.memory.get(vm.ap - 1).is_some());
+    }
+}
+
+#[test]
+fn test_hint_handler() {
+    let ctx = casm! {
+        %{ memory[ap + 0] = segments.add() %}
+        [ap] = [ap] + 0, ap++;
+    };
+    let mut vm = Vm::new();
+    let mut n_hints = 0;
+    vm.run(&ctx.instructions, &mut |vm: &mut Vm, hint: &Hint| {
+        n_hints += 1;
+        assert_eq!(
+            hint,
+            &CoreHint::AllocSegment { dst: CellRef { register: Register::AP, offset: 0 } }.into()
+        );
+        vm.insert_cell(&CellRef { register: Register::AP, offset: 0 }, 17.into())
+    })
+    .unwrap();
+    assert_eq!(n_hints, 1);
+    assert_eq!(ap_value(&vm, -1), BigInt::from(17));
+}
+
+#[test]
+fn test_errors() {
+    let run = |ctx: crate::inline::CasmContext| {
+        Vm::new().run(&ctx.instructions, &mut Vm::execute_hint).unwrap_err()
+    };
+    assert_eq!(
+        run(casm! { [ap] = 1; [ap] = 2; }),
+        ExecutionError::InconsistentMemory {
+            address: 1 << 32,
+            existing: BigInt::from(1),
+            value: BigInt::from(2)
+        }
+    );
+    assert_eq!(
+        run(casm! { [ap] = [ap + 1] + [ap + 2]; }),
+        ExecutionError::CannotDeduceOperands("[ap + 0] = [ap + 1] + [ap + 2]".into())
+    );
+    assert_eq!(run(casm! { jmp rel -2; }), ExecutionError::InvalidPc(BigInt::from(-2)));
+    assert_eq!(run(casm! { jmp abs [ap]; }), ExecutionError::UnknownMemoryCell(1 << 32));
+}
@@ -253,6 +253,10 @@ pub enum CoreHint {
         t_or_k0: CellRef,
         t_or_k1: CellRef,
     },
+    /// Computes the multiplicative inverse of `val` in the field.
+    /// `val` must not be zero.
+    #[codec(index = 28)]
+    FieldInverse { val: ResOperand, inverse: CellRef },
 }
 
 /// Represents a deprecated hint which is kept for backward compatibility of previously deployed
@@ -754,6 +758,18 @@ impl PythonicHint for CoreHint {
                     "
                 )
             }
+            CoreHint::FieldInverse { val, inverse } => {
+                formatdoc!(
+                    "
+
+                        from starkware.crypto.signature.signature import FIELD_PRIME
+                        from starkware.python.math_utils import div_mod
+
+                        memory{inverse} = div_mod(1, {}, FIELD_PRIME)
+                    ",
+                    ResOperandAsIntegerFormatter(val)
+                )
+            }
         }
     }
 }
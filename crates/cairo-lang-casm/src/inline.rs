@@ -1,5 +1,10 @@
+use std::collections::HashMap;
+
 use crate::hints::Hint;
-use crate::instructions::Instruction;
+use crate::instructions::{
+    CallInstruction, Instruction, InstructionBody, JnzInstruction, JumpInstruction,
+};
+use crate::operand::DerefOrImmediate;
 
 #[cfg(test)]
 #[path = "inline_test.rs"]
@@ -11,6 +16,7 @@ macro_rules! casm {
         {
             let mut ctx = $crate::inline::CasmContext::default();
             $crate::casm_extend!(ctx, $($tok)*);
+            ctx.resolve_labels();
             ctx
         }
     }
@@ -29,24 +35,24 @@ macro_rules! casm_extend {
         $crate::append_instruction!($ctx, body $(,$ap++)?);
         $crate::casm_extend!($ctx, $($tok)*)
     };
-    ($ctx:ident, call rel $target:tt $(,$ap:ident++)? ; $($tok:tt)*) => {
+    ($ctx:ident, call rel $target:tt ; $($tok:tt)*) => {
         let body = $crate::instructions::InstructionBody::Call(
             $crate::instructions::CallInstruction {
                 target: $crate::deref_or_immediate!($target),
                 relative: true,
             }
         );
-        $crate::append_instruction!($ctx, body $(,$ap++)?);
+        $crate::append_instruction!($ctx, body);
         $crate::casm_extend!($ctx, $($tok)*)
     };
-    ($ctx:ident, call abs $target:tt $(,$ap:ident++)? ; $($tok:tt)*) => {
+    ($ctx:ident, call abs $target:tt ; $($tok:tt)*) => {
         let body = $crate::instructions::InstructionBody::Call(
             $crate::instructions::CallInstruction {
                 target: $crate::deref_or_immediate!($target),
                 relative: false,
             }
         );
-        $crate::append_instruction!($ctx, body $(,$ap++)?);
+        $crate::append_instruction!($ctx, body);
         $crate::casm_extend!($ctx, $($tok)*)
     };
     ($ctx:ident, jmp rel $target:expr $(,$ap:ident++)? ; $($tok:tt)*) => {
@@ -79,6 +85,17 @@ macro_rules! casm_extend {
         $crate::append_instruction!($ctx, body $(,$ap++)?);
         $crate::casm_extend!($ctx, $($tok)*)
     };
+    ($ctx:ident, jmp $label:ident if $cond:tt != 0 $(,$ap:ident++)? ; $($tok:tt)*) => {
+        $crate::add_label_jump!($ctx, $label);
+        let body = $crate::instructions::InstructionBody::Jnz(
+            $crate::instructions::JnzInstruction {
+                jump_offset: $crate::deref_or_immediate!(0),
+                condition: $crate::deref!($cond),
+            }
+        );
+        $crate::append_instruction!($ctx, body $(,$ap++)?);
+        $crate::casm_extend!($ctx, $($tok)*)
+    };
     ($ctx:ident, jmp $target:tt if $cond:tt != 0 $(,$ap:ident++)? ; $($tok:tt)*) => {
         let body = $crate::instructions::InstructionBody::Jnz(
             $crate::instructions::JnzInstruction {
@@ -89,20 +106,67 @@ macro_rules! casm_extend {
         $crate::append_instruction!($ctx, body $(,$ap++)?);
         $crate::casm_extend!($ctx, $($tok)*)
     };
-    ($ctx:ident, ap += $operand:tt $(,$ap:ident++)? ; $($tok:tt)*) => {
+    ($ctx:ident, jmp $label:ident $(,$ap:ident++)? ; $($tok:tt)*) => {
+        $crate::add_label_jump!($ctx, $label);
+        let body = $crate::instructions::InstructionBody::Jump(
+            $crate::instructions::JumpInstruction {
+                target: $crate::deref_or_immediate!(0),
+                relative: true,
+            }
+        );
+        $crate::append_instruction!($ctx, body $(,$ap++)?);
+        $crate::casm_extend!($ctx, $($tok)*)
+    };
+    ($ctx:ident, call $label:ident ; $($tok:tt)*) => {
+        $crate::add_label_jump!($ctx, $label);
+        let body = $crate::instructions::InstructionBody::Call(
+            $crate::instructions::CallInstruction {
+                target: $crate::deref_or_immediate!(0),
+                relative: true,
+            }
+        );
+        $crate::append_instruction!($ctx, body);
+        $crate::casm_extend!($ctx, $($tok)*)
+    };
+    ($ctx:ident, $label:ident : $($tok:tt)*) => {
+        assert!(
+            $ctx.labels.insert(stringify!($label), $ctx.current_code_offset).is_none(),
+            "Label `{}` is defined more than once.",
+            stringify!($label),
+        );
+        $crate::casm_extend!($ctx, $($tok)*)
+    };
+    ($ctx:ident, const $name:ident = $value:expr ; $($tok:tt)*) => {
+        #[allow(non_snake_case)]
+        let $name = $value;
+        $crate::casm_extend!($ctx, $($tok)*)
+    };
+    ($ctx:ident, ap += $operand:tt ; $($tok:tt)*) => {
         let body = $crate::instructions::InstructionBody::AddAp(
             $crate::instructions::AddApInstruction { operand: $crate::res!($operand) }
         );
-        $crate::append_instruction!($ctx, body $(,$ap++)?);
+        $crate::append_instruction!($ctx, body);
         $crate::casm_extend!($ctx, $($tok)*)
     };
-    ($ctx:ident, ret $(,$ap:ident++)? ; $($tok:tt)*) => {
+    ($ctx:ident, ret ; $($tok:tt)*) => {
         let body = $crate::instructions::InstructionBody::Ret(
             $crate::instructions::RetInstruction {}
         );
-        $crate::append_instruction!($ctx, body $(,$ap++)?);
+        $crate::append_instruction!($ctx, body);
         $crate::casm_extend!($ctx, $($tok)*)
     };
+    ($ctx:ident, call $kind:ident $target:tt, ap++ ; $($tok:tt)*) => {
+        compile_error!("A call instruction cannot have an ap++.");
+    };
+    ($ctx:ident, call $label:ident, ap++ ; $($tok:tt)*) => {
+        compile_error!("A call instruction cannot have an ap++.");
+    };
+    ($ctx:ident, ap += $operand:tt, ap++ ; $($tok:tt)*) => {
+        compile_error!("An add_ap instruction cannot have an ap++.");
+    };
+    ($ctx:ident, ret, ap++ ; $($tok:tt)*) => {
+        compile_error!("A ret instruction cannot have an ap++.");
+    };
     ($ctx:ident, %{ memory $dst:tt = segments . add ( ) %} $($tok:tt)*) => {
         $ctx.current_hints.push($crate::hints::CoreHint::AllocSegment{dst: $crate::deref!($dst)}.into());
         $crate::casm_extend!($ctx, $($tok)*)
@@ -226,6 +290,17 @@ macro_rules! append_instruction {
     };
 }
 
+#[macro_export]
+macro_rules! add_label_jump {
+    ($ctx:ident, $label:ident) => {
+        $ctx.label_jumps.push($crate::inline::LabelJump {
+            instruction_idx: $ctx.instructions.len(),
+            code_offset: $ctx.current_code_offset,
+            label: stringify!($label),
+        });
+    };
+}
+
 #[macro_export]
 macro_rules! is_inc_ap {
     () => {
@@ -242,26 +317,71 @@ pub struct CasmContext {
     pub current_code_offset: usize,
     pub current_hints: Vec<Hint>,
     pub instructions: Vec<Instruction>,
-    // TODO(spapini): Branches.
+    /// The code offsets of the labels defined so far.
+    pub labels: HashMap<&'static str, usize>,
+    /// The jumps and calls to labels, which are yet to be resolved.
+    pub label_jumps: Vec<LabelJump>,
     // TODO(spapini): Relocations.
 }
+impl CasmContext {
+    /// Sets the targets of the jumps and calls to labels to the relative offsets of the labels.
+    /// Called at the end of `casm!`, and should be called after extending a context with jumps to
+    /// labels using `casm_extend!`.
+    pub fn resolve_labels(&mut self) {
+        let label_jumps = std::mem::take(&mut self.label_jumps);
+        for LabelJump { instruction_idx, code_offset, label } in label_jumps {
+            let Some(label_offset) = self.labels.get(label) else {
+                panic!("Label `{label}` is not defined.");
+            };
+            let target = match &mut self.instructions[instruction_idx].body {
+                InstructionBody::Jump(JumpInstruction { target, .. })
+                | InstructionBody::Call(CallInstruction { target, .. })
+                | InstructionBody::Jnz(JnzInstruction { jump_offset: target, .. }) => target,
+                _ => unreachable!("Only jumps and calls target labels."),
+            };
+            *target = DerefOrImmediate::from(*label_offset as i128 - code_offset as i128);
+        }
+    }
+}
+
+/// A jump or a call to a label.
+pub struct LabelJump {
+    /// The index of the instruction of the jump.
+    pub instruction_idx: usize,
+    /// The code offset of the jump.
+    pub code_offset: usize,
+    /// The name of the label.
+    pub label: &'static str,
+}
 
 #[macro_export]
 macro_rules! deref {
-    ([ap + $offset:expr]) => {
-        $crate::operand::CellRef { register: $crate::reg!(ap), offset: $offset }
+    ([ap + $($offset:tt)+]) => {
+        $crate::operand::CellRef {
+            register: $crate::reg!(ap),
+            offset: $crate::cell_offset!($($offset)+),
+        }
     };
-    ([fp + $offset:expr]) => {
-        $crate::operand::CellRef { register: $crate::reg!(fp), offset: $offset }
+    ([fp + $($offset:tt)+]) => {
+        $crate::operand::CellRef {
+            register: $crate::reg!(fp),
+            offset: $crate::cell_offset!($($offset)+),
+        }
     };
     ([& $var:ident + $offset:expr]) => {
         $crate::operand::CellRef { register: $var.register, offset: $var.offset + $offset }
     };
-    ([ap - $offset:expr]) => {
-        $crate::operand::CellRef { register: $crate::reg!(ap), offset: -$offset }
+    ([ap - $($offset:tt)+]) => {
+        $crate::operand::CellRef {
+            register: $crate::reg!(ap),
+            offset: $crate::cell_offset!(@neg $($offset)+),
+        }
     };
-    ([fp - $offset:expr]) => {
-        $crate::operand::CellRef { register: $crate::reg!(fp), offset: -$offset }
+    ([fp - $($offset:tt)+]) => {
+        $crate::operand::CellRef {
+            register: $crate::reg!(fp),
+            offset: $crate::cell_offset!(@neg $($offset)+),
+        }
     };
     ([& $var:ident - $offset:expr]) => {
         $crate::operand::CellRef { register: $var.register, offset: $var.offset - $offset }
@@ -280,6 +400,42 @@ macro_rules! deref {
     };
 }
 
+/// Returns an offset of a cell. Literal offsets are validated at compile time to be in the range
+/// of the offsets of an instruction.
+#[macro_export]
+macro_rules! cell_offset {
+    (@neg $offset:literal) => {{
+        const OFFSET: i16 = $crate::inline::checked_offset(-$offset);
+        OFFSET
+    }};
+    (@neg $($offset:tt)+) => {
+        -($($offset)+)
+    };
+    // A `-` is matched before matching a literal, as a literal may start with a `-`.
+    (- $offset:tt) => {
+        $crate::cell_offset!(@neg $offset)
+    };
+    (- $($offset:tt)+) => {
+        -$($offset)+
+    };
+    ($offset:literal) => {{
+        const OFFSET: i16 = $crate::inline::checked_offset($offset);
+        OFFSET
+    }};
+    ($($offset:tt)+) => {
+        $($offset)+
+    };
+}
+
+/// Returns the given offset as the offset of a cell, panicking if it is out of range.
+pub const fn checked_offset(offset: i128) -> i16 {
+    assert!(
+        offset >= i16::MIN as i128 && offset <= i16::MAX as i128,
+        "Cell offsets must be in the range [-2^15, 2^15)."
+    );
+    offset as i16
+}
+
 #[macro_export]
 macro_rules! reg {
     (ap) => {
@@ -292,8 +448,8 @@ macro_rules! reg {
 
 #[macro_export]
 macro_rules! deref_or_immediate {
-    ([$a:ident $($op:tt $offset:expr)?]) => {
-        $crate::operand::DerefOrImmediate::Deref($crate::deref!([$a $($op $offset)?]))
+    ([$a:ident $($offset:tt)*]) => {
+        $crate::operand::DerefOrImmediate::Deref($crate::deref!([$a $($offset)*]))
     };
     ($a:expr) => {
         $crate::operand::DerefOrImmediate::from($a)
@@ -316,23 +472,35 @@ macro_rules! res {
             b: $crate::deref_or_immediate!($b),
         })
     };
-    ([[ap $($op:tt $offset:expr)?]]) => {
-        $crate::operand::ResOperand::DoubleDeref($crate::deref!([ap $($op $offset)?]), 0)
-    };
-    ([[ap $($op:tt $offset:expr)?] + $outer:expr]) => {
-        $crate::operand::ResOperand::DoubleDeref($crate::deref!([ap $($op $offset)?]), $outer)
-    };
-    ([[ap $($op:tt $offset:expr)?] - $outer:expr]) => {
-        $crate::operand::ResOperand::DoubleDeref($crate::deref!([ap $($op $offset)?]), -$outer)
-    };
-    ([[fp $($op:tt $offset:expr)?]]) => {
-        $crate::operand::ResOperand::DoubleDeref($crate::deref!([fp $($op $offset)?]), 0)
-    };
-    ([[fp $($op:tt $offset:expr)?] + $outer:expr]) => {
-        $crate::operand::ResOperand::DoubleDeref($crate::deref!([fp $($op $offset)?]), $outer)
-    };
-    ([[fp $($op:tt $offset:expr)?] - $outer:expr]) => {
-        $crate::operand::ResOperand::DoubleDeref($crate::deref!([fp $($op $offset)?]), -$outer)
+    ([[ap $($inner:tt)*]]) => {
+        $crate::operand::ResOperand::DoubleDeref($crate::deref!([ap $($inner)*]), 0)
+    };
+    ([[ap $($inner:tt)*] + $($outer:tt)+]) => {
+        $crate::operand::ResOperand::DoubleDeref(
+            $crate::deref!([ap $($inner)*]),
+            $crate::cell_offset!($($outer)+),
+        )
+    };
+    ([[ap $($inner:tt)*] - $($outer:tt)+]) => {
+        $crate::operand::ResOperand::DoubleDeref(
+            $crate::deref!([ap $($inner)*]),
+            $crate::cell_offset!(@neg $($outer)+),
+        )
+    };
+    ([[fp $($inner:tt)*]]) => {
+        $crate::operand::ResOperand::DoubleDeref($crate::deref!([fp $($inner)*]), 0)
+    };
+    ([[fp $($inner:tt)*] + $($outer:tt)+]) => {
+        $crate::operand::ResOperand::DoubleDeref(
+            $crate::deref!([fp $($inner)*]),
+            $crate::cell_offset!($($outer)+),
+        )
+    };
+    ([[fp $($inner:tt)*] - $($outer:tt)+]) => {
+        $crate::operand::ResOperand::DoubleDeref(
+            $crate::deref!([fp $($inner)*]),
+            $crate::cell_offset!(@neg $($outer)+),
+        )
     };
     ([[&$a:expr]]) => {
         $crate::operand::ResOperand::DoubleDeref($a, 0)
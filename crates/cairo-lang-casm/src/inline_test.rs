@@ -36,8 +36,8 @@ fn test_assert() {
         %{ (memory[ap + 0], memory[ap + 1]) = divmod(memory[ap + 9], memory[fp + 9]) %}
         %{ (memory[ap + 0], memory[ap + 1]) = divmod(50, memory[fp + 9]) %}
         %{ (memory[ap + 0], memory[ap + 1]) = divmod(memory[ap + 9], 2) %}
-        call abs 5, ap++;
-        call rel y, ap++;
+        call abs 5;
+        call rel y;
         ret;
     };
 
@@ -68,8 +68,83 @@ fn test_assert() {
             %{ (memory[ap + 0], memory[ap + 1]) = divmod(memory[ap + 9], memory[fp + 9]) %}
             %{ (memory[ap + 0], memory[ap + 1]) = divmod(50, memory[fp + 9]) %}
             %{ (memory[ap + 0], memory[ap + 1]) = divmod(memory[ap + 9], 2) %}
-            call abs 5, ap++
-            call rel [fp + 5], ap++
+            call abs 5
+            call rel [fp + 5]
             ret"}
     );
 }
+
+#[test]
+fn test_labels_and_consts() {
+    let ctx = casm! {
+        const ONE = 1;
+        const STEP = -ONE;
+        [ap + 0] = ONE, ap++;
+        call fib;
+        ret;
+        fib:
+        jmp rec if [fp + -3] != 0;
+        [ap + 0] = [fp + -5], ap++;
+        jmp end;
+        rec:
+        [ap + 0] = [fp + -5] + [fp + -4], ap++;
+        [fp + -3] = [ap + 0] + STEP, ap++;
+        call fib;
+        end:
+        ret;
+    };
+
+    let code = join(ctx.instructions.iter().map(Instruction::to_string), "\n");
+    assert_eq!(
+        code,
+        indoc! {"
+            [ap + 0] = 1, ap++
+            call rel 3
+            ret
+            jmp rel 5 if [fp + -3] != 0
+            [ap + 0] = [fp + -5], ap++
+            jmp rel 7
+            [ap + 0] = [fp + -5] + [fp + -4], ap++
+            [fp + -3] = [ap + 0] + -1, ap++
+            call rel -8
+            ret"}
+    );
+}
+
+#[test]
+#[should_panic(expected = "Label `missing` is not defined.")]
+fn test_undefined_label() {
+    casm! {
+        jmp missing;
+    };
+}
+
+#[test]
+#[should_panic(expected = "Label `twice` is defined more than once.")]
+fn test_label_defined_twice() {
+    casm! {
+        twice:
+        ret;
+        twice:
+        ret;
+    };
+}
+
+#[test]
+fn test_offsets() {
+    let x = 3;
+    let ctx = casm! {
+        [ap - x + 1] = [[fp + -x] + x - 1];
+        [fp + 32767] = [[ap - 32768] - x + 1];
+        [ap + -x + 1] = [fp - 2 * x];
+    };
+
+    let code = join(ctx.instructions.iter().map(Instruction::to_string), "\n");
+    assert_eq!(
+        code,
+        indoc! {"
+            [ap + -4] = [[fp + -3] + 2]
+            [fp + 32767] = [[ap + -32768] + -4]
+            [ap + -2] = [fp + -6]"}
+    );
+}
@@ -4,7 +4,9 @@ pub mod ap_change;
 pub mod assembler;
 pub mod builder;
 pub mod cell_expression;
+pub mod disassembler;
 pub mod encoder;
+pub mod executor;
 pub mod hints;
 pub mod inline;
 pub mod instructions;
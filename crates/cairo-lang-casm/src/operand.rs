@@ -1,6 +1,9 @@
 use std::fmt::Display;
+use std::sync::OnceLock;
 
 use cairo_lang_utils::bigint::BigIntAsHex;
+use num_bigint::BigInt;
+use num_traits::One;
 use parity_scale_codec_derive::{Decode, Encode};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -9,6 +12,12 @@ use serde::{Deserialize, Serialize};
 #[path = "operand_test.rs"]
 mod test;
 
+/// Returns the prime of the field of the Cairo VM, `2^251 + 17 * 2^192 + 1`.
+pub fn prime() -> &'static BigInt {
+    static PRIME: OnceLock<BigInt> = OnceLock::new();
+    PRIME.get_or_init(|| (BigInt::one() << 251) + (BigInt::from(17) << 192) + 1)
+}
+
 #[derive(
     Copy, Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize, Encode, Decode, JsonSchema,
 )]
@@ -9,7 +9,8 @@ use cairo_lang_filesystem::db::{
     FilesGroup, FilesGroupEx, CORELIB_CRATE_NAME,
 };
 use cairo_lang_filesystem::detect::detect_corelib;
-use cairo_lang_filesystem::ids::CrateLongId;
+use cairo_lang_filesystem::flag::{Flag, OptimizationLevel};
+use cairo_lang_filesystem::ids::{CrateLongId, FlagId};
 use cairo_lang_lowering::db::{LoweringDatabase, LoweringGroup};
 use cairo_lang_parser::db::ParserDatabase;
 use cairo_lang_project::ProjectConfig;
@@ -81,6 +82,9 @@ pub struct RootDatabaseBuilder {
     detect_corelib: bool,
     project_config: Option<Box<ProjectConfig>>,
     cfg_set: Option<CfgSet>,
+    optimization_level: Option<OptimizationLevel>,
+    eliminate_dead_storage_writes: bool,
+    local_optimizations: bool,
 }
 
 impl RootDatabaseBuilder {
@@ -90,6 +94,9 @@ impl RootDatabaseBuilder {
             detect_corelib: false,
             project_config: None,
             cfg_set: None,
+            optimization_level: None,
+            eliminate_dead_storage_writes: false,
+            local_optimizations: false,
         }
     }
 
@@ -118,6 +125,21 @@ impl RootDatabaseBuilder {
         self
     }
 
+    pub fn with_optimization_level(&mut self, optimization_level: OptimizationLevel) -> &mut Self {
+        self.optimization_level = Some(optimization_level);
+        self
+    }
+
+    pub fn with_dead_storage_writes_elimination(&mut self) -> &mut Self {
+        self.eliminate_dead_storage_writes = true;
+        self
+    }
+
+    pub fn with_local_optimizations(&mut self) -> &mut Self {
+        self.local_optimizations = true;
+        self
+    }
+
     pub fn build(&mut self) -> Result<RootDatabase> {
         // NOTE: Order of operations matters here!
         //   Errors if something is not OK are very subtle, mostly this results in missing
@@ -133,6 +155,21 @@ impl RootDatabaseBuilder {
             db.use_cfg(cfg_set);
         }
 
+        if let Some(optimization_level) = self.optimization_level {
+            let flag_id = FlagId::new(&db, "optimization_level");
+            db.set_flag(flag_id, Some(Arc::new(Flag::OptimizationLevel(optimization_level))));
+        }
+
+        if self.eliminate_dead_storage_writes {
+            let flag_id = FlagId::new(&db, "eliminate_dead_storage_writes");
+            db.set_flag(flag_id, Some(Arc::new(Flag::EliminateDeadStorageWrites(true))));
+        }
+
+        if self.local_optimizations {
+            let flag_id = FlagId::new(&db, "local_optimizations");
+            db.set_flag(flag_id, Some(Arc::new(Flag::LocalOptimizations(true))));
+        }
+
         if self.detect_corelib {
             let path =
                 detect_corelib().ok_or_else(|| anyhow!("Failed to find development corelib."))?;
@@ -8,6 +8,7 @@ use std::sync::Arc;
 use ::cairo_lang_diagnostics::ToOption;
 use anyhow::{Context, Result};
 use cairo_lang_filesystem::ids::CrateId;
+use cairo_lang_sierra::dup_drop::eliminate_redundant_dup_drop;
 use cairo_lang_sierra::program::Program;
 use cairo_lang_sierra_generator::db::SierraGenGroup;
 use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
@@ -18,6 +19,7 @@ use crate::project::{get_main_crate_ids_from_project, setup_project, ProjectConf
 
 pub mod db;
 pub mod diagnostics;
+pub mod metadata;
 pub mod project;
 
 /// Configuration for the compiler.
@@ -30,6 +32,9 @@ pub struct CompilerConfig<'c> {
     /// The name of the allowed libfuncs list to use in compilation.
     /// If None the default list of audited libfuncs will be used.
     pub allowed_libfuncs_list_name: Option<String>,
+
+    /// Removes the `dup`, `drop` and `rename` invocations that cancel out from the Sierra code.
+    pub eliminate_redundant_dup_drop: bool,
 }
 
 /// The default compiler configuration.
@@ -39,6 +44,7 @@ impl Default for CompilerConfig<'static> {
             diagnostics_reporter: DiagnosticsReporter::default(),
             replace_ids: false,
             allowed_libfuncs_list_name: None,
+            eliminate_redundant_dup_drop: false,
         }
     }
 }
@@ -107,6 +113,9 @@ pub fn compile_prepared_db(
     // Try to move the program out of the Arc if it is not cached in Salsa, or just clone it.
     let mut sierra_program = Arc::try_unwrap(sierra_program).unwrap_or_else(|arc| (*arc).clone());
 
+    if compiler_config.eliminate_redundant_dup_drop {
+        sierra_program = eliminate_redundant_dup_drop(&sierra_program);
+    }
     if compiler_config.replace_ids {
         sierra_program = replace_sierra_ids_in_program(db, &sierra_program);
     }
@@ -0,0 +1,165 @@
+//! Machine-readable metadata of a compilation, for build caching and attestation tooling.
+
+use std::path::PathBuf;
+
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_filesystem::cfg::CfgSet;
+use cairo_lang_filesystem::db::{Edition, FilesGroup};
+use cairo_lang_filesystem::ids::{CrateId, CrateLongId, Directory};
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_sierra::program::Program;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::db::RootDatabase;
+
+#[cfg(test)]
+#[path = "metadata_test.rs"]
+mod test;
+
+/// The metadata of a compilation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompilationMetadata {
+    /// The version of the compiler.
+    pub compiler_version: String,
+    /// The crates of the project.
+    pub crates: Vec<CrateMetadata>,
+    /// The names of the compiled crates.
+    pub main_crates: Vec<String>,
+    /// The enabled cfg options.
+    pub cfg_set: CfgSet,
+    /// The enabled plugins.
+    pub plugins: PluginsMetadata,
+    /// The hashes of the functions of the compiled Sierra program.
+    pub functions: Vec<FunctionMetadata>,
+    /// The digests of the emitted artifacts.
+    pub artifacts: Vec<ArtifactMetadata>,
+}
+
+/// The metadata of a crate.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrateMetadata {
+    pub name: String,
+    /// The root directory of the crate, if it is on the file system.
+    pub root: Option<PathBuf>,
+    pub edition: Edition,
+    /// The names of the other crates visible from the crate, i.e. its direct and transitive
+    /// dependencies.
+    pub dependencies: Vec<String>,
+}
+
+/// The plugins enabled in a compilation, by kind.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginsMetadata {
+    pub macro_plugins: Vec<String>,
+    pub inline_macro_plugins: Vec<String>,
+    pub analyzer_plugins: Vec<String>,
+}
+
+/// The hash of a function of a Sierra program.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionMetadata {
+    pub name: String,
+    /// The SHA-256 of the signature and the statements of the function, with the ids replaced by
+    /// their names and the statement indices relative to the entry point of the function.
+    pub sha256: String,
+}
+
+/// The digest of an emitted artifact.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactMetadata {
+    pub path: String,
+    pub sha256: String,
+}
+
+/// Collects the metadata of the compilation of `program` from the crates `main_crate_ids` in `db`.
+/// `artifacts` are the paths and contents of the artifacts emitted by the compilation.
+pub fn compilation_metadata(
+    db: &RootDatabase,
+    main_crate_ids: &[CrateId],
+    program: &Program,
+    artifacts: &[(String, &[u8])],
+) -> CompilationMetadata {
+    CompilationMetadata {
+        compiler_version: env!("CARGO_PKG_VERSION").into(),
+        crates: db.crates().into_iter().map(|crate_id| crate_metadata(db, crate_id)).collect(),
+        main_crates: main_crate_ids.iter().map(|crate_id| crate_name(db, *crate_id)).collect(),
+        cfg_set: (*db.cfg_set()).clone(),
+        plugins: PluginsMetadata {
+            macro_plugins: db.macro_plugins().iter().map(|plugin| plugin.name().into()).collect(),
+            inline_macro_plugins: db.inline_macro_plugins().keys().cloned().collect(),
+            analyzer_plugins: db
+                .analyzer_plugins()
+                .iter()
+                .map(|plugin| plugin.name().into())
+                .collect(),
+        },
+        // The ids are replaced so the hashes do not depend on the interning order of the ids.
+        functions: functions_metadata(&replace_sierra_ids_in_program(db, program)),
+        artifacts: artifacts
+            .iter()
+            .map(|(path, content)| ArtifactMetadata { path: path.clone(), sha256: sha256(content) })
+            .collect(),
+    }
+}
+
+/// Returns the metadata of a crate.
+fn crate_metadata(db: &RootDatabase, crate_id: CrateId) -> CrateMetadata {
+    let config = db.crate_config(crate_id);
+    let root = config.as_ref().and_then(|config| match &config.root {
+        Directory::Real(path) => Some(path.clone()),
+        Directory::Virtual { .. } => None,
+    });
+    let dependencies = db
+        .visible_crates(crate_id)
+        .iter()
+        .filter(|dependency| **dependency != crate_id)
+        .map(|dependency| crate_name(db, *dependency))
+        .collect();
+    CrateMetadata {
+        name: crate_name(db, crate_id),
+        root,
+        edition: config.map(|config| config.edition).unwrap_or_default(),
+        dependencies,
+    }
+}
+
+/// Returns the name of a crate.
+fn crate_name(db: &RootDatabase, crate_id: CrateId) -> String {
+    match db.lookup_intern_crate(crate_id) {
+        CrateLongId::Real(name) | CrateLongId::Virtual { name, .. } => name.into(),
+    }
+}
+
+/// Returns the hashes of the functions of the program.
+fn functions_metadata(program: &Program) -> Vec<FunctionMetadata> {
+    let functions = program.funcs.iter().sorted_by_key(|function| function.entry_point.0);
+    let entry_points = functions
+        .clone()
+        .skip(1)
+        .map(|function| function.entry_point.0)
+        .chain([program.statements.len()]);
+    functions
+        .zip(entry_points)
+        .map(|(function, end)| {
+            let start = function.entry_point.0;
+            let mut code = format!(
+                "{}({}) -> ({})\n",
+                function.id,
+                function.signature.param_types.iter().join(", "),
+                function.signature.ret_types.iter().join(", ")
+            );
+            for statement in &program.statements[start..end] {
+                code += &format!("{}\n", statement.clone().map(|idx| idx.0 - start));
+            }
+            FunctionMetadata { name: function.id.to_string(), sha256: sha256(code.as_bytes()) }
+        })
+        .collect()
+}
+
+/// Returns the hex encoded SHA-256 of the data.
+fn sha256(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use cairo_lang_filesystem::db::Edition;
+use cairo_lang_sierra::program::Program;
+
+use crate::db::RootDatabase;
+use crate::metadata::{compilation_metadata, CompilationMetadata};
+use crate::project::setup_project;
+use crate::{compile_prepared_db, CompilerConfig};
+
+/// Compiles `examples/fib.cairo` in a new database, and returns the program and its metadata.
+fn compile_fib(replace_ids: bool) -> (Program, CompilationMetadata) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../examples/fib.cairo");
+    let mut db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let main_crate_ids = setup_project(&mut db, &path).unwrap();
+    let compiler_config = CompilerConfig { replace_ids, ..CompilerConfig::default() };
+    let program = compile_prepared_db(&mut db, main_crate_ids.clone(), compiler_config).unwrap();
+    let sierra = program.to_string();
+    let metadata = compilation_metadata(
+        &db,
+        &main_crate_ids,
+        &program,
+        &[("fib.sierra".into(), sierra.as_bytes())],
+    );
+    (program, metadata)
+}
+
+#[test]
+fn test_compilation_metadata() {
+    let (_, metadata) = compile_fib(true);
+
+    assert_eq!(metadata.compiler_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(metadata.main_crates, vec!["fib".to_string()]);
+    let fib_crate = metadata.crates.iter().find(|crate_metadata| crate_metadata.name == "fib");
+    let fib_crate = fib_crate.unwrap();
+    assert_eq!(fib_crate.edition, Edition::default());
+    assert_eq!(fib_crate.dependencies, vec!["core".to_string()]);
+    assert!(metadata.functions.iter().any(|function| function.name == "fib::fib::fib"));
+    assert!(metadata
+        .plugins
+        .macro_plugins
+        .iter()
+        .any(|plugin| plugin == "cairo_lang_plugins::plugins::derive::DerivePlugin"));
+    assert_eq!(metadata.artifacts.len(), 1);
+    assert_eq!(metadata.artifacts[0].sha256.len(), 64);
+}
+
+#[test]
+fn test_compilation_metadata_is_stable() {
+    let (program, metadata) = compile_fib(true);
+    let (other_program, other_metadata) = compile_fib(false);
+    // The compilations emit different Sierra, as only one of them replaces the ids.
+    assert_ne!(program.to_string(), other_program.to_string());
+    assert_ne!(metadata.artifacts, other_metadata.artifacts);
+    assert_eq!(metadata.functions, other_metadata.functions);
+    assert_eq!(metadata.plugins, other_metadata.plugins);
+    assert_eq!(metadata.crates, other_metadata.crates);
+}
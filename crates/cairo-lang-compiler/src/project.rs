@@ -76,7 +76,10 @@ pub fn update_crate_roots_from_project_config(db: &mut dyn SemanticGroup, config
             path = PathBuf::from(&config.base_path).join(path);
         }
         let root = Directory::Real(path);
-        db.set_crate_config(crate_id, Some(CrateConfiguration { root, edition }));
+        db.set_crate_config(
+            crate_id,
+            Some(CrateConfiguration::default_for_root(root).with_edition(edition)),
+        );
     }
 }
 
@@ -7,6 +7,7 @@ use cairo_lang_filesystem::ids::{CrateId, Directory, FileId, FileKind, FileLongI
 use cairo_lang_parser::db::ParserGroup;
 use cairo_lang_syntax::attribute::consts::{
     FMT_SKIP_ATTR, IMPLICIT_PRECEDENCE_ATTR, INLINE_ATTR, MUST_USE_ATTR, STARKNET_INTERFACE_ATTR,
+    STORAGE_WRITE_ATTR,
 };
 use cairo_lang_syntax::node::ast::MaybeModuleBody;
 use cairo_lang_syntax::node::db::SyntaxGroup;
@@ -185,6 +186,7 @@ fn allowed_attributes(db: &dyn DefsGroup) -> Arc<OrderedHashSet<String>> {
         INLINE_ATTR.into(),
         MUST_USE_ATTR.into(),
         IMPLICIT_PRECEDENCE_ATTR.into(),
+        STORAGE_WRITE_ATTR.into(),
         FMT_SKIP_ATTR.into(),
         // TODO(orizi): Remove this once `starknet` is removed from corelib.
         STARKNET_INTERFACE_ATTR.into(),
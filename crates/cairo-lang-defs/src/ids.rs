@@ -394,6 +394,7 @@ define_language_element_id_as_enum! {
     pub enum FunctionWithBodyId {
         Free(FreeFunctionId),
         Impl(ImplFunctionId),
+        Trait(TraitFunctionId),
     }
 }
 impl FunctionWithBodyId {
@@ -401,6 +402,7 @@ impl FunctionWithBodyId {
         match self {
             FunctionWithBodyId::Free(free_function) => free_function.name(db),
             FunctionWithBodyId::Impl(impl_function) => impl_function.name(db),
+            FunctionWithBodyId::Trait(trait_function) => trait_function.name(db),
         }
     }
 }
@@ -414,6 +416,9 @@ impl TopLevelLanguageElementId for FunctionWithBodyId {
             FunctionWithBodyId::Impl(impl_function_id) => {
                 db.lookup_intern_impl_function(*impl_function_id).name(db)
             }
+            FunctionWithBodyId::Trait(trait_function_id) => {
+                db.lookup_intern_trait_function(*trait_function_id).name(db)
+            }
         }
     }
 }
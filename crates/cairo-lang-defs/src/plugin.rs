@@ -89,6 +89,18 @@ pub trait MacroPlugin: std::fmt::Debug + Sync + Send {
     /// Note: They may not cause a diagnostic if some other plugin declares such attribute, but
     /// plugin writers should not rely on that.
     fn declared_attributes(&self) -> Vec<String>;
+
+    /// Traits this plugin derives for types with the `#[derive(...)]` attribute.
+    /// Used for completing derived traits - the plugin handles derives whether they are declared
+    /// here or not.
+    fn declared_derives(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// A name identifying the plugin, stable across compilations.
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
 }
 
 /// Result of plugin code generation.
@@ -24,11 +24,25 @@ pub struct CrateConfiguration {
     pub root: Directory,
     /// The cairo edition of the crate.
     pub edition: Edition,
+    /// The crates the crate directly depends on, or None if its dependencies are not declared, in
+    /// which case all the crates of the project are available to it. The dependencies of these
+    /// crates are not included, even though their items may reach the crate through them.
+    pub dependencies: Option<Vec<CrateId>>,
 }
 impl CrateConfiguration {
     /// Returns a new configuration.
     pub fn default_for_root(root: Directory) -> Self {
-        Self { root, edition: Edition::default() }
+        Self { root, edition: Edition::default(), dependencies: None }
+    }
+
+    /// Returns the configuration with the given edition.
+    pub fn with_edition(self, edition: Edition) -> Self {
+        Self { edition, ..self }
+    }
+
+    /// Returns the configuration with the given direct dependencies.
+    pub fn with_dependencies(self, dependencies: Vec<CrateId>) -> Self {
+        Self { dependencies: Some(dependencies), ..self }
     }
 }
 
@@ -1,7 +1,51 @@
+use std::str::FromStr;
+
 /// A compilation flag.
 #[derive(PartialEq, Eq, Debug)]
 pub enum Flag {
     /// Whether automatically add `withdraw_gas` calls in code cycles.
     /// Default is true - automatically add.
     AddWithdrawGas(bool),
+    /// Which optimization passes to apply.
+    /// Default is [OptimizationLevel::O2] - apply all passes.
+    OptimizationLevel(OptimizationLevel),
+    /// Whether to remove storage writes that are overwritten before the storage can be read.
+    /// Default is false - keep all the writes.
+    EliminateDeadStorageWrites(bool),
+    /// Whether to apply the local optimization passes - constant folding, peephole optimizations
+    /// and common subexpression elimination.
+    /// Default is to apply them only from [OptimizationLevel::O3].
+    LocalOptimizations(bool),
+}
+
+/// The level of optimization of the compilation, enabling or disabling the optimization passes as
+/// a group.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OptimizationLevel {
+    /// No optimizations - only inlining of functions that must be inlined, and no reordering of
+    /// statements, keeping the generated code close to the source code, for compilation speed and
+    /// debuggability.
+    O0,
+    /// Inlines only functions explicitly marked for inlining, and reorders statements.
+    O1,
+    /// Applies all the default optimizations, including heuristic inlining.
+    #[default]
+    O2,
+    /// Applies all the optimizations of [OptimizationLevel::O2], and the local optimization passes
+    /// - constant folding, peephole optimizations and common subexpression elimination.
+    O3,
+}
+
+impl FromStr for OptimizationLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "0" => Ok(Self::O0),
+            "1" => Ok(Self::O1),
+            "2" => Ok(Self::O2),
+            "3" => Ok(Self::O3),
+            _ => Err(format!("Invalid optimization level `{s}`, expected 0, 1, 2 or 3.")),
+        }
+    }
 }
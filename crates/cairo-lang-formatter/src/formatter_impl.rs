@@ -7,6 +7,7 @@ use cairo_lang_syntax as syntax;
 use cairo_lang_syntax::attribute::consts::FMT_SKIP_ATTR;
 use cairo_lang_syntax::node::db::SyntaxGroup;
 use cairo_lang_syntax::node::{ast, SyntaxNode, Terminal, TypedSyntaxNode};
+use cairo_lang_utils::ensure_sufficient_stack;
 use itertools::Itertools;
 use smol_str::SmolStr;
 use syntax::node::ast::MaybeModuleBody;
@@ -671,7 +672,9 @@ impl<'a> FormatterImpl<'a> {
             if child.width(self.db) == TextWidth::default() {
                 continue;
             }
-            self.format_node(child, no_space_after && i == n_children - 1);
+            ensure_sufficient_stack(|| {
+                self.format_node(child, no_space_after && i == n_children - 1)
+            });
             if let BreakLinePointsPositions::List { properties, breaking_frequency } =
                 &internal_break_line_points_positions
             {
@@ -56,6 +56,11 @@ impl SyntaxNodeFormat for SyntaxNode {
             {
                 true
             }
+            SyntaxKind::TokenDotDot
+                if grandparent_kind(db, self) == Some(SyntaxKind::ExprBinary) =>
+            {
+                true
+            }
             SyntaxKind::TokenLT | SyntaxKind::TokenGT
                 if matches!(
                     grandparent_kind(db, self),
@@ -74,10 +79,10 @@ impl SyntaxNodeFormat for SyntaxNode {
 
     fn force_no_space_after(&self, db: &dyn SyntaxGroup) -> bool {
         match self.kind(db) {
+            SyntaxKind::TokenAt => grandparent_kind(db, self) != Some(SyntaxKind::PatternBinding),
             SyntaxKind::TokenDot
             | SyntaxKind::TokenNot
             | SyntaxKind::TokenBitNot
-            | SyntaxKind::TokenAt
             | SyntaxKind::TokenColonColon
             | SyntaxKind::TokenLParen
             | SyntaxKind::TokenLBrack
@@ -114,6 +119,11 @@ impl SyntaxNodeFormat for SyntaxNode {
             SyntaxKind::TokenMinus | SyntaxKind::TokenMul => {
                 matches!(grandparent_kind(db, self), Some(SyntaxKind::ExprUnary))
             }
+            SyntaxKind::TokenDotDot
+                if grandparent_kind(db, self) == Some(SyntaxKind::ExprBinary) =>
+            {
+                true
+            }
             SyntaxKind::TokenPlus
                 if grandparent_kind(db, self) == Some(SyntaxKind::GenericParamImplAnonymous) =>
             {
@@ -237,6 +247,25 @@ impl SyntaxNodeFormat for SyntaxNode {
                 SyntaxKind::ElseClause => Some(3),
                 _ => None,
             },
+            Some(SyntaxKind::ExprWhile | SyntaxKind::ExprFor) => match self.kind(db) {
+                SyntaxKind::ExprBlock => Some(1),
+                SyntaxKind::ExprBinary
+                | SyntaxKind::ExprErrorPropagate
+                | SyntaxKind::ExprFieldInitShorthand
+                | SyntaxKind::ExprFunctionCall
+                | SyntaxKind::ExprIf
+                | SyntaxKind::ExprList
+                | SyntaxKind::ExprMatch
+                | SyntaxKind::ExprMissing
+                | SyntaxKind::ExprParenthesized
+                | SyntaxKind::ExprPath
+                | SyntaxKind::ExprStructCtorCall
+                | SyntaxKind::ExprListParenthesized
+                | SyntaxKind::ArgListBraced
+                | SyntaxKind::ArgListBracketed
+                | SyntaxKind::ExprUnary => Some(2),
+                _ => None,
+            },
             Some(SyntaxKind::ExprMatch) => match self.kind(db) {
                 SyntaxKind::MatchArms => Some(1),
                 SyntaxKind::ExprBinary
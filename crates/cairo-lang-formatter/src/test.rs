@@ -60,3 +60,24 @@ fn format_and_compare_file(unformatted_filename: &str, expected_filename: &str,
         fs::read_to_string(expected_filename).expect("Expected file does not exists.");
     assert_eq!(formatted_file, expected_file);
 }
+
+#[test]
+fn format_deeply_nested_expr() {
+    let db_val = SimpleParserDatabase::default();
+    let nesting = 1000;
+    let code = format!("fn foo() -> felt252 {{ {}0{} }}", "(".repeat(nesting), ")".repeat(nesting));
+    // The too deeply nested part of the expression is kept as skipped tokens.
+    let formatted = crate::format_string(&db_val, code.clone());
+    let without_whitespace = |s: &str| s.split_whitespace().collect::<String>();
+    assert_eq!(without_whitespace(&formatted), without_whitespace(&code));
+}
+
+#[test]
+fn format_long_flat_expr() {
+    let db_val = SimpleParserDatabase::default();
+    let length = 10000;
+    let code = format!("fn foo() -> felt252 {{ {} }}", vec!["0"; length].join(" + "));
+    let formatted = crate::format_string(&db_val, code.clone());
+    let without_whitespace = |s: &str| s.split_whitespace().collect::<String>();
+    assert_eq!(without_whitespace(&formatted), without_whitespace(&code));
+}
@@ -46,3 +46,13 @@ fn fib(a: Box::<felt252>, b: Box::<felt252>, n: Box::<felt252>) -> Box::<felt252
         },
     }
 }
+
+fn bindings(x: Option<felt252>) {
+    let t@( a ,_ )=(1,2);
+    match x { v@Option::Some(y) => {}, Option::None => {}, }
+}
+
+fn loops(values: Span<felt252>, n: u32) {
+    for  value  in values { sum+=*value; };
+    for i in 0 .. n {}
+}
@@ -0,0 +1,177 @@
+//! An on-disk cache of the results of the analysis of the project files, so that they are shown
+//! right away when the server restarts, while the project is analyzed again.
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use cairo_lang_defs::ids::ModuleId;
+use cairo_lang_filesystem::ids::{CrateId, FileId, FileLongId};
+use cairo_lang_semantic::db::SemanticGroup;
+use itertools::Itertools;
+use lsp::Diagnostic;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+#[cfg(test)]
+#[path = "analysis_cache_test.rs"]
+mod test;
+
+/// The environment variable holding the path of the analysis cache file.
+pub const ANALYSIS_CACHE_ENV_VAR: &str = "CAIRO_LSP_ANALYSIS_CACHE";
+
+/// The results of the analysis of the project files, by their paths.
+pub struct AnalysisCache {
+    path: PathBuf,
+    files: HashMap<PathBuf, CachedFile>,
+    /// Whether the cache changed since it was loaded or last saved.
+    dirty: bool,
+}
+
+/// The format of the cache file.
+#[derive(Default, Serialize, Deserialize)]
+struct CacheContent {
+    /// The version of the server that wrote the cache. Caches of other versions are discarded, as
+    /// the analysis may differ.
+    version: String,
+    files: HashMap<PathBuf, CachedFile>,
+}
+
+/// The results of the analysis of a file, valid as long as the inputs of the analysis of its crate
+/// are unchanged.
+#[derive(Clone, Serialize, Deserialize)]
+struct CachedFile {
+    /// The hash of the inputs of the analysis of the crate of the file, see [crate_inputs_hash].
+    inputs_hash: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl AnalysisCache {
+    /// Loads the cache at the path in the `CAIRO_LSP_ANALYSIS_CACHE` environment variable, if set.
+    /// A missing or invalid cache file results in an empty cache.
+    pub fn from_env() -> Option<Self> {
+        let path = std::env::var(ANALYSIS_CACHE_ENV_VAR).ok().filter(|path| !path.is_empty())?;
+        Some(Self::load(PathBuf::from(path)))
+    }
+
+    /// Loads the cache at the given path.
+    pub fn load(path: PathBuf) -> Self {
+        let files = match fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str::<CacheContent>(&content) {
+                Ok(content) if content.version == env!("CARGO_PKG_VERSION") => content.files,
+                Ok(_) => Default::default(),
+                Err(err) => {
+                    warn!("Ignoring invalid analysis cache `{}`: {err}.", path.display());
+                    Default::default()
+                }
+            },
+            Err(_) => Default::default(),
+        };
+        Self { path, files, dirty: false }
+    }
+
+    /// Writes the cache to its file, if it changed.
+    pub fn save(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        let content = CacheContent {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            files: self.files.clone(),
+        };
+        let result = self
+            .path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&self.path, serde_json::to_string(&content).unwrap()));
+        match result {
+            Ok(()) => self.dirty = false,
+            Err(err) => {
+                warn!("Failed to write the analysis cache `{}`: {err}.", self.path.display())
+            }
+        }
+    }
+
+    /// Returns the cached diagnostics of a file, if the inputs of the analysis of its crate did not
+    /// change since they were cached.
+    pub fn diagnostics(&self, path: &Path, inputs_hash: &str) -> Option<&Vec<Diagnostic>> {
+        let cached = self.files.get(path)?;
+        (cached.inputs_hash == inputs_hash).then_some(&cached.diagnostics)
+    }
+
+    /// Returns the paths of the files in the cache.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.files.keys().cloned().collect()
+    }
+
+    /// Caches the diagnostics of a file, computed with the given inputs of the analysis of its
+    /// crate. Files without diagnostics are not kept in the cache.
+    pub fn insert(&mut self, path: PathBuf, inputs_hash: String, diagnostics: Vec<Diagnostic>) {
+        if diagnostics.is_empty() {
+            self.dirty |= self.files.remove(&path).is_some();
+            return;
+        }
+        self.files.insert(path, CachedFile { inputs_hash, diagnostics });
+        self.dirty = true;
+    }
+}
+
+/// Returns the hash of the inputs of the analysis of the crate of a file, see [crate_inputs_hash].
+/// The hashes of the crates are memoized in `crate_hashes`, as computing them reads all the files
+/// of the crates.
+pub fn file_inputs_hash(
+    db: &(dyn SemanticGroup + 'static),
+    file_id: FileId,
+    crate_hashes: &mut HashMap<CrateId, String>,
+) -> Option<String> {
+    let module_id = *db.file_modules(file_id).ok()?.first()?;
+    let crate_id = module_id.owning_crate(db.upcast());
+    Some(crate_hashes.entry(crate_id).or_insert_with(|| crate_inputs_hash(db, crate_id)).clone())
+}
+
+/// Returns the hash of the inputs of the analysis of a crate: the configurations and the files of
+/// the crate and of the crates visible to it, the compilation flags and the cfg set.
+/// The diagnostics of the files of the crate are determined by these inputs, so they may only be
+/// reused while the hash is unchanged.
+pub fn crate_inputs_hash(db: &(dyn SemanticGroup + 'static), crate_id: CrateId) -> String {
+    let mut hasher = Sha256::new();
+    let mut update = |value: &str| {
+        // Prefix each value with its length, so that different sequences of values differ.
+        hasher.update(value.len().to_le_bytes());
+        hasher.update(value.as_bytes());
+    };
+    update(&format!("{:?}", db.cfg_set()));
+    for (flag_id, flag) in db.flags().iter() {
+        update(&format!("{:?}: {flag:?}", db.lookup_intern_flag(*flag_id)));
+    }
+    for crate_id in db.visible_crates(crate_id).iter() {
+        update(&format!("{:?}", db.lookup_intern_crate(*crate_id)));
+        match db.crate_config(*crate_id) {
+            Some(config) => {
+                update(&format!("{:?} {:?}", config.root, config.edition));
+                // The ids of the dependencies are interned, so they differ between sessions.
+                let dependencies = config.dependencies.map(|dependencies| {
+                    dependencies.into_iter().map(|id| db.lookup_intern_crate(id)).collect_vec()
+                });
+                update(&format!("{dependencies:?}"));
+            }
+            None => update("None"),
+        }
+        for module_id in db.crate_modules(*crate_id).iter() {
+            for file_id in module_files(db, *module_id) {
+                // Virtual files are generated from the files on disk, so they are not inputs.
+                let FileLongId::OnDisk(path) = db.lookup_intern_file(file_id) else {
+                    continue;
+                };
+                update(&path.to_string_lossy());
+                update(db.file_content(file_id).as_deref().map_or("", String::as_str));
+            }
+        }
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Returns the files of a module, or none if they can not be computed.
+fn module_files(db: &dyn SemanticGroup, module_id: ModuleId) -> Vec<FileId> {
+    db.module_files(module_id).map_or_else(|_| vec![], |files| files.to_vec())
+}
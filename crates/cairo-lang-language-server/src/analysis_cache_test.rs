@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_filesystem::db::{CrateConfiguration, FilesGroup, FilesGroupEx};
+use cairo_lang_filesystem::ids::{CrateLongId, Directory, FileId};
+use lsp::Diagnostic;
+
+use super::{file_inputs_hash, AnalysisCache};
+
+/// The root directory of the crate of [setup]. It does not exist, the content of its files is
+/// given by overrides.
+const ROOT: &str = "/analysis_cache_test";
+
+/// Returns a database with a crate whose `lib.cairo` declares the module `a`, with the given
+/// content, along with the id of `lib.cairo`.
+fn setup(a_content: &str) -> (RootDatabase, FileId) {
+    let mut db = RootDatabase::builder().detect_corelib().build().unwrap();
+    let crate_id = db.intern_crate(CrateLongId::Real("test".into()));
+    db.set_crate_config(
+        crate_id,
+        Some(CrateConfiguration::default_for_root(Directory::Real(PathBuf::from(ROOT)))),
+    );
+    let lib_file = FileId::new(&db, PathBuf::from(ROOT).join("lib.cairo"));
+    let a_file = FileId::new(&db, PathBuf::from(ROOT).join("a.cairo"));
+    db.override_file_content(lib_file, Some(Arc::new("mod a;\n".into())));
+    db.override_file_content(a_file, Some(Arc::new(a_content.into())));
+    (db, lib_file)
+}
+
+/// Returns the hash of the inputs of the analysis of `lib.cairo` in the database of [setup].
+fn lib_inputs_hash(a_content: &str) -> String {
+    let (db, lib_file) = setup(a_content);
+    file_inputs_hash(&db, lib_file, &mut HashMap::new()).unwrap()
+}
+
+#[test]
+fn test_inputs_hash_depends_on_other_crate_files() {
+    let hash = lib_inputs_hash("fn foo() {}");
+    assert_eq!(lib_inputs_hash("fn foo() {}"), hash);
+    // The diagnostics of `lib.cairo` may depend on `a.cairo`, although it did not change.
+    assert_ne!(lib_inputs_hash("fn foo() -> felt252 { 1 }"), hash);
+}
+
+#[test]
+fn test_cached_diagnostics_require_same_inputs() {
+    let path = PathBuf::from(ROOT).join("lib.cairo");
+    let mut cache = AnalysisCache::load(PathBuf::from(ROOT).join("cache.json"));
+    assert_eq!(cache.diagnostics(&path, "hash"), None);
+    cache.insert(path.clone(), "hash".into(), vec![Diagnostic::default()]);
+    assert_eq!(cache.diagnostics(&path, "hash"), Some(&vec![Diagnostic::default()]));
+    assert_eq!(cache.diagnostics(&path, "other hash"), None);
+}
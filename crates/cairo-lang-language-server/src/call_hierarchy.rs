@@ -0,0 +1,257 @@
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::{
+    FunctionWithBodyId, LanguageElementId, LookupItemId, ModuleItemId, TopLevelLanguageElementId,
+};
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::items::functions::GenericFunctionId;
+use cairo_lang_semantic::resolve::{ResolvedConcreteItem, ResolvedGenericItem};
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{ast, SyntaxNode, Terminal, TypedSyntaxNode};
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::Upcast;
+use itertools::Itertools;
+use lsp::{
+    CallHierarchyIncomingCall, CallHierarchyItem, CallHierarchyOutgoingCall, Position, Range,
+    SymbolKind,
+};
+
+use crate::{file, get_node_and_lookup_items, get_uri, node_range};
+
+/// Returns the call hierarchy item of the function at a position - the function referred to by the
+/// identifier at the position if there is one, or otherwise the function containing the position.
+pub fn prepare_call_hierarchy(
+    db: &RootDatabase,
+    file: FileId,
+    position: Position,
+) -> Option<Vec<CallHierarchyItem>> {
+    let (node, lookup_items) = get_node_and_lookup_items(db, file, position)?;
+    let function = referred_function(db, node, &lookup_items)
+        .or_else(|| lookup_items.into_iter().find(|item| is_function(*item)))?;
+    Some(vec![call_hierarchy_item(db, function)?])
+}
+
+/// Returns the calls to the function of a call hierarchy item, grouped by the functions making
+/// them.
+pub fn incoming_calls(
+    db: &RootDatabase,
+    item: &CallHierarchyItem,
+) -> Option<Vec<CallHierarchyIncomingCall>> {
+    let target = item_function(db, item)?;
+    let mut incoming_calls = vec![];
+    for caller in functions_with_body(db) {
+        let Ok(calls) = db.function_body_calls(caller) else {
+            continue;
+        };
+        let from_ranges = calls
+            .iter()
+            .filter(|(callee, _)| {
+                generic_function_item(db, callee.get_concrete(db).generic_function) == target
+            })
+            .filter_map(|(_, stable_ptr)| {
+                node_range(db, &stable_ptr.lookup(db.upcast()).as_syntax_node())
+            })
+            .sorted_by_key(|range| range.start)
+            .collect_vec();
+        if from_ranges.is_empty() {
+            continue;
+        }
+        let Some(from) = call_hierarchy_item(db, function_with_body_item(caller)) else {
+            continue;
+        };
+        incoming_calls.push(CallHierarchyIncomingCall { from, from_ranges });
+    }
+    Some(incoming_calls)
+}
+
+/// Returns the calls made by the function of a call hierarchy item, grouped by the called
+/// functions.
+pub fn outgoing_calls(
+    db: &RootDatabase,
+    item: &CallHierarchyItem,
+) -> Option<Vec<CallHierarchyOutgoingCall>> {
+    let caller = match item_function(db, item)? {
+        LookupItemId::ModuleItem(ModuleItemId::FreeFunction(free_function_id)) => {
+            FunctionWithBodyId::Free(free_function_id)
+        }
+        LookupItemId::ImplFunction(impl_function_id) => FunctionWithBodyId::Impl(impl_function_id),
+        LookupItemId::TraitFunction(trait_function_id)
+            if matches!(db.trait_function_body(trait_function_id), Ok(Some(_))) =>
+        {
+            FunctionWithBodyId::Trait(trait_function_id)
+        }
+        // Functions without a body make no calls.
+        _ => return Some(vec![]),
+    };
+    let mut ranges_by_callee = OrderedHashMap::<LookupItemId, Vec<Range>>::default();
+    for (callee, stable_ptr) in db.function_body_calls(caller).ok()?.iter() {
+        let callee = generic_function_item(db, callee.get_concrete(db).generic_function);
+        if let Some(range) = node_range(db, &stable_ptr.lookup(db.upcast()).as_syntax_node()) {
+            ranges_by_callee.entry(callee).or_default().push(range);
+        }
+    }
+    Some(
+        ranges_by_callee
+            .into_iter()
+            .filter_map(|(callee, from_ranges)| {
+                Some(CallHierarchyOutgoingCall {
+                    to: call_hierarchy_item(db, callee)?,
+                    from_ranges: from_ranges
+                        .into_iter()
+                        .sorted_by_key(|range| range.start)
+                        .collect(),
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Returns the function referred to by the identifier at `node`, if it is one.
+fn referred_function(
+    db: &RootDatabase,
+    node: SyntaxNode,
+    lookup_items: &[LookupItemId],
+) -> Option<LookupItemId> {
+    if node.kind(db) != SyntaxKind::TokenIdentifier {
+        return None;
+    }
+    let identifier = ast::TerminalIdentifier::from_syntax_node(db, node.parent()?);
+    lookup_items.iter().find_map(|lookup_item_id| {
+        if let Some(ResolvedConcreteItem::Function(function_id)) =
+            db.lookup_resolved_concrete_item_by_ptr(*lookup_item_id, identifier.stable_ptr())
+        {
+            return Some(generic_function_item(db, function_id.get_concrete(db).generic_function));
+        }
+        match db.lookup_resolved_generic_item_by_ptr(*lookup_item_id, identifier.stable_ptr())? {
+            ResolvedGenericItem::GenericFunction(generic_function) => {
+                Some(generic_function_item(db, generic_function))
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Returns the function of a call hierarchy item, by the position of its name.
+fn item_function(db: &RootDatabase, item: &CallHierarchyItem) -> Option<LookupItemId> {
+    let file = file(db, item.uri.clone());
+    let (_, lookup_items) = get_node_and_lookup_items(db, file, item.selection_range.start)?;
+    lookup_items.into_iter().find(|item| is_function(*item))
+}
+
+/// Returns the item defining a generic function. Functions of impls that are not known (e.g. impl
+/// generic parameters) are defined by the trait function.
+fn generic_function_item(db: &RootDatabase, generic_function: GenericFunctionId) -> LookupItemId {
+    match generic_function {
+        GenericFunctionId::Free(free_function_id) => {
+            LookupItemId::ModuleItem(ModuleItemId::FreeFunction(free_function_id))
+        }
+        GenericFunctionId::Extern(extern_function_id) => {
+            LookupItemId::ModuleItem(ModuleItemId::ExternFunction(extern_function_id))
+        }
+        GenericFunctionId::Impl(impl_generic_function) => {
+            match impl_generic_function.impl_function(db) {
+                Ok(Some(impl_function_id)) => LookupItemId::ImplFunction(impl_function_id),
+                _ => LookupItemId::TraitFunction(impl_generic_function.function),
+            }
+        }
+    }
+}
+
+/// Returns the item defining a function with a body.
+fn function_with_body_item(function_id: FunctionWithBodyId) -> LookupItemId {
+    match function_id {
+        FunctionWithBodyId::Free(free_function_id) => {
+            LookupItemId::ModuleItem(ModuleItemId::FreeFunction(free_function_id))
+        }
+        FunctionWithBodyId::Impl(impl_function_id) => LookupItemId::ImplFunction(impl_function_id),
+        FunctionWithBodyId::Trait(trait_function_id) => {
+            LookupItemId::TraitFunction(trait_function_id)
+        }
+    }
+}
+
+/// Returns true if the item is a function.
+fn is_function(item: LookupItemId) -> bool {
+    matches!(
+        item,
+        LookupItemId::ModuleItem(ModuleItemId::FreeFunction(_) | ModuleItemId::ExternFunction(_))
+            | LookupItemId::ImplFunction(_)
+            | LookupItemId::TraitFunction(_)
+    )
+}
+
+/// Returns all the functions with a body, in all the crates.
+fn functions_with_body(db: &RootDatabase) -> Vec<FunctionWithBodyId> {
+    let mut functions = vec![];
+    for crate_id in db.crates() {
+        for module_id in db.crate_modules(crate_id).iter() {
+            for free_function_id in
+                db.module_free_functions_ids(*module_id).unwrap_or_default().iter()
+            {
+                functions.push(FunctionWithBodyId::Free(*free_function_id));
+            }
+            for impl_def_id in db.module_impls_ids(*module_id).unwrap_or_default().iter() {
+                for impl_function_id in db.impl_functions(*impl_def_id).unwrap_or_default().values()
+                {
+                    functions.push(FunctionWithBodyId::Impl(*impl_function_id));
+                }
+            }
+            for trait_id in db.module_traits_ids(*module_id).unwrap_or_default().iter() {
+                for trait_function_id in db.trait_functions(*trait_id).unwrap_or_default().values()
+                {
+                    if matches!(db.trait_function_body(*trait_function_id), Ok(Some(_))) {
+                        functions.push(FunctionWithBodyId::Trait(*trait_function_id));
+                    }
+                }
+            }
+        }
+    }
+    functions
+}
+
+/// Returns the call hierarchy item of a function, or None if the item is not a function.
+fn call_hierarchy_item(db: &RootDatabase, item: LookupItemId) -> Option<CallHierarchyItem> {
+    let syntax_db = db.upcast();
+    let (stable_ptr, full_path, kind) = match item {
+        LookupItemId::ModuleItem(ModuleItemId::FreeFunction(id)) => {
+            (id.untyped_stable_ptr(db), id.full_path(db), SymbolKind::FUNCTION)
+        }
+        LookupItemId::ModuleItem(ModuleItemId::ExternFunction(id)) => {
+            (id.untyped_stable_ptr(db), id.full_path(db), SymbolKind::FUNCTION)
+        }
+        LookupItemId::ImplFunction(id) => {
+            (id.untyped_stable_ptr(db), id.full_path(db), SymbolKind::METHOD)
+        }
+        LookupItemId::TraitFunction(id) => {
+            (id.untyped_stable_ptr(db), id.full_path(db), SymbolKind::METHOD)
+        }
+        LookupItemId::ModuleItem(_) => return None,
+    };
+    let node = stable_ptr.lookup(syntax_db);
+    let declaration = match node.kind(syntax_db) {
+        SyntaxKind::FunctionWithBody => {
+            ast::FunctionWithBody::from_syntax_node(syntax_db, node.clone()).declaration(syntax_db)
+        }
+        SyntaxKind::ItemExternFunction => {
+            ast::ItemExternFunction::from_syntax_node(syntax_db, node.clone())
+                .declaration(syntax_db)
+        }
+        SyntaxKind::TraitItemFunction => {
+            ast::TraitItemFunction::from_syntax_node(syntax_db, node.clone()).declaration(syntax_db)
+        }
+        _ => return None,
+    };
+    let name = declaration.name(syntax_db);
+    Some(CallHierarchyItem {
+        name: name.text(syntax_db).into(),
+        kind,
+        tags: None,
+        detail: Some(full_path),
+        uri: get_uri(db, stable_ptr.file_id(syntax_db)),
+        range: node_range(db, &node)?,
+        selection_range: node_range(db, &name.as_syntax_node())?,
+        data: None,
+    })
+}
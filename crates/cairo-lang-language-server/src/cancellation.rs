@@ -0,0 +1,41 @@
+//! Cancellation of the requests computed on blocking threads.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A flag shared between a request and the function computing its result, set when the request is
+/// canceled by a `$/cancelRequest`. Long-running functions should poll it, and stop early once set.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+impl CancellationToken {
+    /// Marks the request as canceled.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns whether the request was canceled.
+    pub fn is_canceled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Returns a guard canceling the token when dropped, unless it is disarmed first.
+    pub fn drop_guard(&self) -> CancelOnDrop {
+        CancelOnDrop(Some(self.clone()))
+    }
+}
+
+/// Cancels a token when dropped, e.g. when the future of a request is dropped by the server.
+pub struct CancelOnDrop(Option<CancellationToken>);
+impl CancelOnDrop {
+    /// Drops the guard without canceling the token.
+    pub fn disarm(mut self) {
+        self.0 = None;
+    }
+}
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if let Some(token) = &self.0 {
+            token.cancel();
+        }
+    }
+}
@@ -0,0 +1,445 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::diagnostic_utils::StableLocation;
+use cairo_lang_defs::ids::{LanguageElementId, ModuleId};
+use cairo_lang_diagnostics::ToOption;
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::ids::{CrateLongId, FileId};
+use cairo_lang_filesystem::span::{TextOffset, TextSpan};
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_semantic::corelib::core_module;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{ast, SyntaxNode, Terminal, TypedSyntaxNode};
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use cairo_lang_utils::Upcast;
+use itertools::Itertools;
+use lsp::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Diagnostic, Position, Range, TextEdit, Url,
+    WorkspaceEdit,
+};
+
+use crate::unused_imports::{find_unused_imports, use_item_of_node, UNUSED_IMPORT_MESSAGE};
+use crate::{from_pos, position_to_offset};
+
+#[cfg(test)]
+#[path = "code_actions_test.rs"]
+mod test;
+
+/// The prefix of the message of the warning on unused variables.
+const UNUSED_VARIABLE_MESSAGE_PREFIX: &str = "Unused variable.";
+
+/// Returns the quick fixes for the given diagnostics of the file - prefixing unused variables with
+/// `_` and removing unused imports, as well as removing all the unused imports of the file.
+pub fn quick_fixes(
+    db: &RootDatabase,
+    file: FileId,
+    uri: Url,
+    diagnostics: Vec<Diagnostic>,
+) -> Vec<CodeActionOrCommand> {
+    let mut actions = vec![];
+    let mut has_unused_imports = false;
+    for diagnostic in diagnostics {
+        let Some(node) = node_at_position(db, file, diagnostic.range.start) else {
+            continue;
+        };
+        let (title, edits) = if diagnostic.message.starts_with(UNUSED_VARIABLE_MESSAGE_PREFIX) {
+            let Some(name) = variable_name(db, node) else {
+                continue;
+            };
+            let start = span_range(db, file, name.as_syntax_node().span_without_trivia(db)).start;
+            let edit = TextEdit { range: Range { start, end: start }, new_text: "_".into() };
+            (format!("Rename to `_{}`", name.text(db)), vec![edit])
+        } else if diagnostic.message.ends_with(UNUSED_IMPORT_MESSAGE) {
+            has_unused_imports = true;
+            let Some(leaf) = ancestor_of_kind(db, node, SyntaxKind::UsePathLeaf) else {
+                continue;
+            };
+            ("Remove unused import".into(), remove_use_leaves(db, file, vec![leaf]))
+        } else {
+            continue;
+        };
+        actions.push(quick_fix(title, uri.clone(), edits, vec![diagnostic]));
+    }
+
+    if has_unused_imports {
+        let leaves = file_unused_import_leaves(db, file);
+        if leaves.len() > 1 {
+            let edits = remove_use_leaves(db, file, leaves);
+            actions.push(quick_fix("Remove all unused imports in file".into(), uri, edits, vec![]));
+        }
+    }
+    actions
+}
+
+/// Creates a quick fix action applying the edits to the document.
+fn quick_fix(
+    title: String,
+    uri: Url,
+    edits: Vec<TextEdit>,
+    diagnostics: Vec<Diagnostic>,
+) -> CodeActionOrCommand {
+    code_action(title, CodeActionKind::QUICKFIX, uri, edits, diagnostics)
+}
+
+/// Creates an action of the given kind applying the edits to the document.
+pub fn code_action(
+    title: String,
+    kind: CodeActionKind,
+    uri: Url,
+    edits: Vec<TextEdit>,
+    diagnostics: Vec<Diagnostic>,
+) -> CodeActionOrCommand {
+    CodeActionOrCommand::CodeAction(CodeAction {
+        title,
+        kind: Some(kind),
+        diagnostics: if diagnostics.is_empty() { None } else { Some(diagnostics) },
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from([(uri, edits)])),
+            ..WorkspaceEdit::default()
+        }),
+        ..CodeAction::default()
+    })
+}
+
+/// The groups of organized imports, in their order in the file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum ImportGroup {
+    Corelib,
+    Dependency,
+    CurrentCrate,
+}
+
+/// Returns the source action organizing the imports of the file, if they are not organized
+/// already. The unused imports are removed, the imports of the same module are merged, and the
+/// imports are sorted in separate groups of corelib, dependency and current crate imports.
+/// Public imports, imports with attributes and imports with comments are left as is.
+pub fn organize_imports(db: &RootDatabase, file: FileId, uri: Url) -> Option<CodeActionOrCommand> {
+    let syntax_db = db.upcast();
+    let syntax = db.file_module_syntax(file).to_option()?;
+    let crate_id = db.file_modules(file).to_option()?.first()?.owning_crate(db.upcast());
+    let crate_names: Vec<_> = db
+        .crates()
+        .into_iter()
+        .map(|crate_id| match db.lookup_intern_crate(crate_id) {
+            CrateLongId::Real(name) | CrateLongId::Virtual { name, .. } => (crate_id, name),
+        })
+        .collect();
+    let classify = |root: &str| match crate_names.iter().find(|(_, name)| name.as_str() == root) {
+        Some((dependency_id, _)) if *dependency_id == crate_id => ImportGroup::CurrentCrate,
+        Some((dependency_id, _)) if *dependency_id == db.core_crate() => ImportGroup::Corelib,
+        Some(_) => ImportGroup::Dependency,
+        // Paths starting with an item of the corelib root, such as `array::ArrayTrait`, are
+        // resolved in the corelib. Other paths, such as `super` paths, are relative.
+        None if matches!(db.module_item_by_name(core_module(db), root.into()), Ok(Some(_))) => {
+            ImportGroup::Corelib
+        }
+        None => ImportGroup::CurrentCrate,
+    };
+    let unused: UnorderedHashSet<SyntaxStablePtrId> =
+        file_unused_import_leaves(db, file).iter().map(|leaf| leaf.stable_ptr()).collect();
+
+    let mut edits = vec![];
+    let mut item_lists = vec![syntax.items(syntax_db)];
+    while let Some(item_list) = item_lists.pop() {
+        let mut use_items = vec![];
+        for item in item_list.elements(syntax_db) {
+            match item {
+                ast::Item::Use(item_use)
+                    if item_use.attributes(syntax_db).elements(syntax_db).is_empty()
+                        && matches!(
+                            item_use.visibility(syntax_db),
+                            ast::Visibility::Default(_)
+                        ) =>
+                {
+                    use_items.push(item_use)
+                }
+                ast::Item::Module(module) => {
+                    if let ast::MaybeModuleBody::Some(body) = module.body(syntax_db) {
+                        item_lists.push(body.items(syntax_db));
+                    }
+                }
+                _ => {}
+            }
+        }
+        edits.extend(organize_use_items(db, file, &use_items, &unused, &classify));
+    }
+    if edits.is_empty() {
+        return None;
+    }
+    Some(code_action(
+        "Organize imports".into(),
+        CodeActionKind::SOURCE_ORGANIZE_IMPORTS,
+        uri,
+        edits,
+        vec![],
+    ))
+}
+
+/// Returns the edits organizing the given `use` items of a single item list, replacing the first
+/// of them with all the organized imports, and removing the rest.
+fn organize_use_items(
+    db: &RootDatabase,
+    file: FileId,
+    use_items: &[ast::ItemUse],
+    unused: &UnorderedHashSet<SyntaxStablePtrId>,
+    classify: &dyn Fn(&str) -> ImportGroup,
+) -> Vec<TextEdit> {
+    let syntax_db = db.upcast();
+    let (Some(first), Some(last)) = (use_items.first(), use_items.last()) else {
+        return vec![];
+    };
+    // Moving the imports around would lose their comments.
+    if use_items.iter().any(|item| item.as_syntax_node().get_text(syntax_db).contains("//")) {
+        return vec![];
+    }
+
+    // The imported leaves, by the group and the path of their module.
+    let mut imports = BTreeMap::<(ImportGroup, Vec<String>), BTreeSet<String>>::new();
+    for item in use_items {
+        collect_imports(syntax_db, &item.use_path(syntax_db), vec![], unused, &mut |path, leaf| {
+            let group = classify(path.first().unwrap_or(&leaf));
+            imports.entry((group, path)).or_default().insert(leaf);
+        });
+    }
+
+    let first_span = first.as_syntax_node().span_without_trivia(syntax_db);
+    let indent = " ".repeat(
+        first_span.start.position_in_file(db.upcast(), file).map_or(0, |position| position.col),
+    );
+    let rendered = imports
+        .into_iter()
+        .group_by(|((group, _), _)| *group)
+        .into_iter()
+        .map(|(_, group_imports)| {
+            group_imports
+                .flat_map(|((_, path), leaves)| {
+                    let prefix = path.iter().map(|segment| format!("{segment}::")).join("");
+                    if leaves.len() == 1 || path.is_empty() {
+                        leaves.into_iter().map(|leaf| format!("use {prefix}{leaf};")).collect_vec()
+                    } else {
+                        vec![format!("use {prefix}{{{}}};", leaves.into_iter().join(", "))]
+                    }
+                })
+                .join(&format!("\n{indent}"))
+        })
+        .join(&format!("\n\n{indent}"));
+
+    let last_span = last.as_syntax_node().span_without_trivia(syntax_db);
+    let original_span = TextSpan { start: first_span.start, end: last_span.end };
+    if db.file_content(file).map(|content| original_span.take(&content) == rendered) == Some(true) {
+        return vec![];
+    }
+
+    let mut edits = vec![];
+    let rest = if rendered.is_empty() {
+        use_items
+    } else {
+        edits.push(TextEdit { range: span_range(db, file, first_span), new_text: rendered });
+        &use_items[1..]
+    };
+    for item in rest {
+        edits.push(TextEdit {
+            range: span_range(db, file, item.as_syntax_node().span(syntax_db)),
+            new_text: "".into(),
+        });
+    }
+    edits
+}
+
+/// Calls `add` with the module path and the text of each used leaf of the use path, prefixed by
+/// `prefix`.
+fn collect_imports(
+    db: &dyn SyntaxGroup,
+    use_path: &ast::UsePath,
+    mut prefix: Vec<String>,
+    unused: &UnorderedHashSet<SyntaxStablePtrId>,
+    add: &mut dyn FnMut(Vec<String>, String),
+) {
+    match use_path {
+        ast::UsePath::Leaf(leaf) => {
+            if !unused.contains(&leaf.stable_ptr().untyped()) {
+                add(prefix, leaf.as_syntax_node().get_text_without_trivia(db));
+            }
+        }
+        ast::UsePath::Single(single) => {
+            prefix.push(single.ident(db).as_syntax_node().get_text_without_trivia(db));
+            collect_imports(db, &single.use_path(db), prefix, unused, add);
+        }
+        ast::UsePath::Multi(multi) => {
+            for path in multi.use_paths(db).elements(db) {
+                collect_imports(db, &path, prefix.clone(), unused, add);
+            }
+        }
+    }
+}
+
+/// Returns the deepest syntax node at the given position of the file.
+pub fn node_at_position(db: &RootDatabase, file: FileId, position: Position) -> Option<SyntaxNode> {
+    let syntax = db.file_syntax(file).to_option()?;
+    let offset = position_to_offset(db.file_summary(file)?, position, &db.file_content(file)?)?;
+    Some(syntax.lookup_offset(db.upcast(), offset))
+}
+
+/// Returns the first node of the given kind among the node and its ancestors.
+pub fn ancestor_of_kind(
+    db: &dyn SyntaxGroup,
+    mut node: SyntaxNode,
+    kind: SyntaxKind,
+) -> Option<SyntaxNode> {
+    while node.kind(db) != kind {
+        node = node.parent()?;
+    }
+    Some(node)
+}
+
+/// Returns the name of the variable declared at the given node - either a parameter, or the
+/// identifier of a local variable. The node may be a modifier of the variable, such as `mut`.
+fn variable_name(db: &RootDatabase, node: SyntaxNode) -> Option<ast::TerminalIdentifier> {
+    let syntax_db = db.upcast();
+    if let Some(param) = ancestor_of_kind(syntax_db, node.clone(), SyntaxKind::Param) {
+        return Some(ast::Param::from_syntax_node(syntax_db, param).name(syntax_db));
+    }
+    if let Some(pattern) = ancestor_of_kind(syntax_db, node.clone(), SyntaxKind::PatternIdentifier)
+    {
+        return Some(ast::PatternIdentifier::from_syntax_node(syntax_db, pattern).name(syntax_db));
+    }
+    let identifier = ancestor_of_kind(syntax_db, node, SyntaxKind::TerminalIdentifier)?;
+    Some(ast::TerminalIdentifier::from_syntax_node(syntax_db, identifier))
+}
+
+/// Returns the `use` leaves of the file reported as unused imports.
+fn file_unused_import_leaves(db: &RootDatabase, file: FileId) -> Vec<SyntaxNode> {
+    let mut modules = db.file_modules(file).map(|modules| (*modules).clone()).unwrap_or_default();
+    let mut i = 0;
+    while i < modules.len() {
+        for submodule_id in db.module_submodules_ids(modules[i]).unwrap_or_default().iter() {
+            modules.push(ModuleId::Submodule(*submodule_id));
+        }
+        i += 1;
+    }
+    modules
+        .into_iter()
+        .flat_map(|module_id| find_unused_imports(db, module_id))
+        .filter_map(|use_id| {
+            // Map the import to the user code, as it may originate from code generated by plugins.
+            let location = StableLocation::new(use_id.untyped_stable_ptr(db.upcast()))
+                .diagnostic_location(db.upcast())
+                .user_location(db.upcast());
+            if location.file_id != file {
+                return None;
+            }
+            let position = from_pos(location.span.start.position_in_file(db.upcast(), file)?);
+            let node = node_at_position(db, file, position)?;
+            ancestor_of_kind(db.upcast(), node, SyntaxKind::UsePathLeaf)
+        })
+        .unique_by(|leaf| leaf.stable_ptr())
+        .collect()
+}
+
+/// Returns the edits removing the given `use` leaves. Groups left empty are removed as well, groups
+/// left with a single path are collapsed into it, and `use` items left empty are removed entirely.
+fn remove_use_leaves(db: &RootDatabase, file: FileId, leaves: Vec<SyntaxNode>) -> Vec<TextEdit> {
+    let syntax_db = db.upcast();
+    let removed: UnorderedHashSet<SyntaxStablePtrId> =
+        leaves.iter().map(|leaf| leaf.stable_ptr()).collect();
+    let items = leaves
+        .into_iter()
+        .filter_map(|leaf| use_item_of_node(syntax_db, leaf))
+        .unique_by(|item| item.stable_ptr());
+    items
+        .map(|item| {
+            let use_path = item.use_path(syntax_db);
+            if is_removed(syntax_db, &use_path, &removed) {
+                TextEdit {
+                    range: span_range(db, file, item.as_syntax_node().span(syntax_db)),
+                    new_text: "".into(),
+                }
+            } else {
+                TextEdit {
+                    range: span_range(
+                        db,
+                        file,
+                        use_path.as_syntax_node().span_without_trivia(syntax_db),
+                    ),
+                    new_text: render_use_path(syntax_db, &use_path, &removed),
+                }
+            }
+        })
+        .collect()
+}
+
+/// Returns whether all the leaves of the use path are removed.
+fn is_removed(
+    db: &dyn SyntaxGroup,
+    use_path: &ast::UsePath,
+    removed: &UnorderedHashSet<SyntaxStablePtrId>,
+) -> bool {
+    match use_path {
+        ast::UsePath::Leaf(leaf) => removed.contains(&leaf.stable_ptr().untyped()),
+        ast::UsePath::Single(single) => is_removed(db, &single.use_path(db), removed),
+        ast::UsePath::Multi(multi) => {
+            multi.use_paths(db).elements(db).iter().all(|path| is_removed(db, path, removed))
+        }
+    }
+}
+
+/// Returns whether any of the leaves of the use path is removed.
+fn contains_removed(
+    db: &dyn SyntaxGroup,
+    use_path: &ast::UsePath,
+    removed: &UnorderedHashSet<SyntaxStablePtrId>,
+) -> bool {
+    match use_path {
+        ast::UsePath::Leaf(leaf) => removed.contains(&leaf.stable_ptr().untyped()),
+        ast::UsePath::Single(single) => contains_removed(db, &single.use_path(db), removed),
+        ast::UsePath::Multi(multi) => {
+            multi.use_paths(db).elements(db).iter().any(|path| contains_removed(db, path, removed))
+        }
+    }
+}
+
+/// Returns the text of a partially removed use path.
+fn render_use_path(
+    db: &dyn SyntaxGroup,
+    use_path: &ast::UsePath,
+    removed: &UnorderedHashSet<SyntaxStablePtrId>,
+) -> String {
+    if !contains_removed(db, use_path, removed) {
+        return use_path.as_syntax_node().get_text_without_trivia(db);
+    }
+    match use_path {
+        ast::UsePath::Leaf(leaf) => leaf.as_syntax_node().get_text_without_trivia(db),
+        ast::UsePath::Single(single) => format!(
+            "{}::{}",
+            single.ident(db).as_syntax_node().get_text_without_trivia(db),
+            render_use_path(db, &single.use_path(db), removed)
+        ),
+        ast::UsePath::Multi(multi) => {
+            let paths = multi
+                .use_paths(db)
+                .elements(db)
+                .iter()
+                .filter(|path| !is_removed(db, path, removed))
+                .map(|path| render_use_path(db, path, removed))
+                .collect_vec();
+            // A group left with a single path is collapsed into it.
+            match &paths[..] {
+                [path] => path.clone(),
+                _ => format!("{{{}}}", paths.join(", ")),
+            }
+        }
+    }
+}
+
+/// Converts a span of the file to an LSP range.
+pub fn span_range(db: &RootDatabase, file: FileId, span: TextSpan) -> Range {
+    let position = |offset: TextOffset| {
+        offset.position_in_file(db.upcast(), file).map(from_pos).unwrap_or_default()
+    };
+    Range { start: position(span.start), end: position(span.end) }
+}
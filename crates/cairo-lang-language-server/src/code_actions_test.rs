@@ -0,0 +1,134 @@
+use indoc::{formatdoc, indoc};
+use lsp::CodeActionOrCommand;
+
+use super::{organize_imports, quick_fixes};
+use crate::test_utils::{apply_code_action, setup_test_file_with_plugins, test_file_uri};
+use crate::{file_diagnostics, lsp_diagnostics};
+
+/// Returns the titles of the quick fixes for the diagnostics of the code, along with the texts
+/// resulting from applying them.
+fn fix(code: &str) -> Vec<(String, String)> {
+    let (db, file) = setup_test_file_with_plugins(code);
+    let diagnostics = lsp_diagnostics(&db, &file_diagnostics(&db, file));
+    quick_fixes(&db, file, test_file_uri(), diagnostics)
+        .iter()
+        .map(|action| {
+            let CodeActionOrCommand::CodeAction(code_action) = action else {
+                panic!("Expected a code action.");
+            };
+            (code_action.title.clone(), apply_code_action(code, action))
+        })
+        .collect()
+}
+
+/// Returns the text resulting from organizing the imports of the code, if they are not organized
+/// already.
+fn organize(code: &str) -> Option<String> {
+    let (db, file) = setup_test_file_with_plugins(code);
+    let action = organize_imports(&db, file, test_file_uri())?;
+    Some(apply_code_action(code, &action))
+}
+
+#[test]
+fn test_rename_unused_variable() {
+    assert_eq!(
+        fix("fn foo() {\n    let x = 5;\n}\n"),
+        [("Rename to `_x`".into(), "fn foo() {\n    let _x = 5;\n}\n".into())]
+    );
+}
+
+#[test]
+fn test_rename_unused_mut_variable() {
+    assert_eq!(
+        fix("fn foo() {\n    let mut x = 5;\n}\n"),
+        [("Rename to `_x`".into(), "fn foo() {\n    let mut _x = 5;\n}\n".into())]
+    );
+}
+
+#[test]
+fn test_rename_unused_mut_variable_in_pattern() {
+    assert_eq!(
+        fix("fn foo() {\n    let (mut x, _y) = (5, 6);\n}\n"),
+        [("Rename to `_x`".into(), "fn foo() {\n    let (mut _x, _y) = (5, 6);\n}\n".into())]
+    );
+}
+
+#[test]
+fn test_remove_unused_import_leaf() {
+    let code = indoc! {"
+        use core::integer::{u8_sqrt, u16_sqrt};
+        fn foo() -> u8 {
+            u8_sqrt(4)
+        }
+    "};
+    assert_eq!(
+        fix(code),
+        [(
+            "Remove unused import".into(),
+            code.replace("use core::integer::{u8_sqrt, u16_sqrt};", "use core::integer::u8_sqrt;")
+        )]
+    );
+}
+
+#[test]
+fn test_remove_unused_import_item() {
+    let code = indoc! {"
+        use core::integer::u8_sqrt;
+        use core::integer::u16_sqrt;
+        fn foo() -> u8 {
+            u8_sqrt(4)
+        }
+    "};
+    assert_eq!(
+        fix(code),
+        [("Remove unused import".into(), code.replace("use core::integer::u16_sqrt;\n", ""))]
+    );
+}
+
+#[test]
+fn test_remove_unused_imports_in_groups() {
+    let use_item = "use core::{box::BoxTrait, integer::{u8_sqrt, u16_sqrt}};";
+    let code = formatdoc! {"
+        {use_item}
+        fn foo() -> u8 {{
+            u8_sqrt(4)
+        }}
+    "};
+    let fixed = |title: &str, new_use_item| (title.into(), code.replace(use_item, new_use_item));
+    assert_eq!(
+        fix(&code),
+        [
+            fixed("Remove unused import", "use core::integer::{u8_sqrt, u16_sqrt};"),
+            fixed("Remove unused import", "use core::{box::BoxTrait, integer::u8_sqrt};"),
+            fixed("Remove all unused imports in file", "use core::integer::u8_sqrt;"),
+        ]
+    );
+}
+
+#[test]
+fn test_organize_imports() {
+    let code = indoc! {"
+        use super::b;
+        use core::integer::u16_sqrt;
+        use super::a;
+        use core::box::BoxTrait;
+        use core::integer::u8_sqrt;
+        fn a() {}
+        fn b() -> u8 {
+            a();
+            u16_sqrt(4) + u8_sqrt(4)
+        }
+    "};
+    let organized = indoc! {"
+        use core::integer::{u16_sqrt, u8_sqrt};
+
+        use super::{a, b};
+        fn a() {}
+        fn b() -> u8 {
+            a();
+            u16_sqrt(4) + u8_sqrt(4)
+        }
+    "};
+    assert_eq!(organize(code).as_deref(), Some(organized));
+    assert_eq!(organize(organized), None);
+}
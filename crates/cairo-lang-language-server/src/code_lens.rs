@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_defs::ids::{FunctionWithBodyId, TopLevelLanguageElementId};
+use cairo_lang_diagnostics::ToOption;
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_lowering::ids::SemanticFunctionWithBodyIdEx;
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_sierra::extensions::gas::CostTokenType;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_to_casm::metadata::{calc_metadata, MetadataComputationConfig};
+use cairo_lang_starknet::plugin::consts::{ABI_ATTR, ABI_ATTR_EMBED_V0_ARG, EXTERNAL_ATTR};
+use cairo_lang_syntax::node::helpers::QueryAttrs;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{ast, SyntaxNode, TypedSyntaxNode};
+use cairo_lang_utils::Upcast;
+use lsp::{CodeLens, Command, Range, TextDocumentIdentifier, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::run_test::{test_name, RunTestParams, RUN_TEST_COMMAND};
+use crate::{from_pos, function_with_body_from_ast};
+
+#[cfg(test)]
+#[path = "code_lens_test.rs"]
+mod test;
+
+/// The command showing the estimated gas cost of a function in a message, invoked by the gas
+/// estimate code lenses. Its argument is the text of the estimate.
+pub const SHOW_GAS_ESTIMATE_COMMAND: &str = "cairo1.showGasEstimate";
+
+/// The data of an unresolved gas estimate code lens, identifying its function.
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasEstimateLensData {
+    /// The document holding the function.
+    pub text_document: TextDocumentIdentifier,
+    /// The full path of the function.
+    pub function_path: String,
+    /// The hash of the inputs of the crate of the function, as the lens was created.
+    pub crate_hash: String,
+}
+
+/// The gas estimates of the functions, by the hashes of the inputs of their crates, along with the
+/// crate hashes the code lenses of each document were computed with.
+#[derive(Default)]
+pub struct GasEstimates {
+    /// The estimates of the functions of each crate revision, by the paths of the functions. Only
+    /// the latest revision of each crate is kept.
+    estimates: HashMap<String, HashMap<String, Option<String>>>,
+    /// The crate hash of each document with gas estimate code lenses, as of the last time they
+    /// were computed.
+    document_crate_hashes: HashMap<Url, String>,
+}
+
+impl GasEstimates {
+    /// Returns the estimate of a function, if computed for the given crate revision.
+    pub fn get(&self, data: &GasEstimateLensData) -> Option<Option<String>> {
+        self.estimates.get(&data.crate_hash)?.get(&data.function_path).cloned()
+    }
+
+    /// Records the estimate of a function in the given crate revision.
+    pub fn insert(&mut self, data: &GasEstimateLensData, estimate: Option<String>) {
+        self.estimates
+            .entry(data.crate_hash.clone())
+            .or_default()
+            .insert(data.function_path.clone(), estimate);
+    }
+
+    /// Records the crate hash the code lenses of the document were computed with, dropping the
+    /// estimates of revisions no document uses anymore.
+    pub fn record_document(&mut self, uri: Url, crate_hash: String) {
+        self.document_crate_hashes.insert(uri, crate_hash);
+        let used_hashes: Vec<_> = self.document_crate_hashes.values().cloned().collect();
+        self.estimates.retain(|crate_hash, _| used_hashes.contains(crate_hash));
+    }
+
+    /// Returns the documents with gas estimate code lenses, along with the crate hashes their
+    /// lenses were computed with.
+    pub fn documents(&self) -> impl Iterator<Item = (&Url, &String)> {
+        self.document_crate_hashes.iter()
+    }
+}
+
+/// Returns unresolved code lenses for the estimated gas cost of each external contract function in
+/// the file, where `crate_hash` is the hash of the inputs of the crate of the file. The estimates
+/// are computed as the lenses are resolved, by [resolve_gas_estimate_code_lens], as they require
+/// compiling the functions.
+pub fn gas_estimate_code_lenses(
+    db: &RootDatabase,
+    file: FileId,
+    uri: Url,
+    crate_hash: &str,
+) -> Option<Vec<CodeLens>> {
+    let syntax_db = db.upcast();
+    let syntax = db.file_syntax(file).to_option()?;
+    let mut code_lenses = vec![];
+    for node in syntax.descendants(syntax_db) {
+        if node.kind(syntax_db) != SyntaxKind::FunctionWithBody || !is_external_function(db, &node)
+        {
+            continue;
+        }
+        let Some(function_id) = function_with_body_from_ast(db, file, node.clone()) else {
+            continue;
+        };
+        let function = ast::FunctionWithBody::from_syntax_node(syntax_db, node);
+        let span = function.declaration(syntax_db).as_syntax_node().span_without_trivia(syntax_db);
+        let start = from_pos(span.start.position_in_file(db.upcast(), file)?);
+        let end = from_pos(span.end.position_in_file(db.upcast(), file)?);
+        let data = GasEstimateLensData {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            function_path: function_id.full_path(db.upcast()),
+            crate_hash: crate_hash.into(),
+        };
+        code_lenses.push(CodeLens {
+            range: Range { start, end },
+            command: None,
+            data: Some(serde_json::to_value(data).ok()?),
+        });
+    }
+    Some(code_lenses)
+}
+
+/// Resolves a gas estimate code lens, given the estimate of its function - see
+/// [estimate_function_gas].
+pub fn resolve_gas_estimate_code_lens(lens: CodeLens, estimate: Option<String>) -> CodeLens {
+    let title = estimate.unwrap_or_else(|| "Gas estimate unavailable".into());
+    let arguments = Some(vec![title.clone().into()]);
+    CodeLens {
+        command: Some(Command { title, command: SHOW_GAS_ESTIMATE_COMMAND.into(), arguments }),
+        ..lens
+    }
+}
+
+/// Returns the estimated gas cost of the function of a gas estimate code lens, or None if the
+/// function is not found or fails to compile.
+pub fn estimate_function_gas(
+    db: &RootDatabase,
+    file: FileId,
+    data: &GasEstimateLensData,
+) -> Option<String> {
+    let syntax_db = db.upcast();
+    let syntax = db.file_syntax(file).to_option()?;
+    let function_id = syntax
+        .descendants(syntax_db)
+        .filter(|node| node.kind(syntax_db) == SyntaxKind::FunctionWithBody)
+        .filter_map(|node| function_with_body_from_ast(db, file, node))
+        .find(|function_id| function_id.full_path(db.upcast()) == data.function_path)?;
+    estimate_gas(db, function_id)
+}
+
+/// Returns code lenses running each test function in the file, with or without profiling the
+/// resources it uses.
+pub fn test_code_lenses(db: &RootDatabase, file: FileId, uri: Url) -> Option<Vec<CodeLens>> {
+    let syntax_db = db.upcast();
+    let syntax = db.file_syntax(file).to_option()?;
+    let mut code_lenses = vec![];
+    for node in syntax.descendants(syntax_db) {
+        if node.kind(syntax_db) != SyntaxKind::FunctionWithBody {
+            continue;
+        }
+        let Some(function_id) = function_with_body_from_ast(db, file, node.clone()) else {
+            continue;
+        };
+        let Some(name) = test_name(db, function_id) else {
+            continue;
+        };
+        let function = ast::FunctionWithBody::from_syntax_node(syntax_db, node);
+        let span = function.declaration(syntax_db).as_syntax_node().span_without_trivia(syntax_db);
+        let start = from_pos(span.start.position_in_file(db.upcast(), file)?);
+        let end = from_pos(span.end.position_in_file(db.upcast(), file)?);
+        for (title, profile) in [("Run test", false), ("Run with gas profiling", true)] {
+            let params = RunTestParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                name: name.clone(),
+                profile,
+            };
+            code_lenses.push(CodeLens {
+                range: Range { start, end },
+                command: Some(Command {
+                    title: title.into(),
+                    command: RUN_TEST_COMMAND.into(),
+                    arguments: Some(vec![serde_json::to_value(params).ok()?]),
+                }),
+                data: None,
+            });
+        }
+    }
+    Some(code_lenses)
+}
+
+/// Returns true if the function is exposed as an external entry point of a contract, either
+/// directly or by being a member of an external impl.
+fn is_external_function(db: &RootDatabase, node: &SyntaxNode) -> bool {
+    let syntax_db = db.upcast();
+    let function = ast::FunctionWithBody::from_syntax_node(syntax_db, node.clone());
+    if function.has_attr(syntax_db, EXTERNAL_ATTR) {
+        return true;
+    }
+    // The function node is under `ItemImpl > ImplBody > ImplItemList`.
+    let Some(impl_node) = node.parent().and_then(|node| node.parent()?.parent()) else {
+        return false;
+    };
+    if impl_node.kind(syntax_db) != SyntaxKind::ItemImpl {
+        return false;
+    }
+    let item_impl = ast::ItemImpl::from_syntax_node(syntax_db, impl_node);
+    item_impl.has_attr(syntax_db, EXTERNAL_ATTR)
+        || item_impl.has_attr_with_arg(syntax_db, ABI_ATTR, ABI_ATTR_EMBED_V0_ARG)
+}
+
+/// Compiles the function to Sierra, runs the gas solver on it, and formats its estimated cost.
+/// Returns None for generic functions and for functions that fail to compile.
+fn estimate_gas(db: &RootDatabase, function_id: FunctionWithBodyId) -> Option<String> {
+    if !db.function_with_body_generic_params(function_id).ok()?.is_empty() {
+        return None;
+    }
+    let function = function_id.lowered(db).to_concrete(db).ok()?;
+    let program = db.get_sierra_program_for_functions(vec![function]).ok()?;
+    let metadata = calc_metadata(&program, MetadataComputationConfig::default()).ok()?;
+    let sierra_id = db.intern_sierra_function(function.function_id(db).ok()?);
+    let costs = metadata.gas_info.function_costs.get(&sierra_id)?;
+    let mut title =
+        format!("Estimated gas: {}", costs.get(&CostTokenType::Const).copied().unwrap_or_default());
+    for token_type in CostTokenType::iter_precost() {
+        if let Some(count) = costs.get(token_type).filter(|count| **count != 0) {
+            title += &format!(" + {count} {}", token_type.name());
+        }
+    }
+    Some(title)
+}
@@ -0,0 +1,63 @@
+use indoc::indoc;
+use lsp::Command;
+
+use super::{
+    estimate_function_gas, gas_estimate_code_lenses, resolve_gas_estimate_code_lens,
+    GasEstimateLensData, GasEstimates, SHOW_GAS_ESTIMATE_COMMAND,
+};
+use crate::test_utils::{setup_test_file, test_file_uri};
+
+const CONTRACT: &str = indoc! {"
+    mod contract {
+        #[external(v0)]
+        fn double(x: felt252) -> felt252 {
+            x + x
+        }
+
+        fn internal(x: felt252) -> felt252 {
+            x
+        }
+    }
+"};
+
+#[test]
+fn test_gas_estimate_code_lenses() {
+    let (db, file) = setup_test_file(CONTRACT);
+    let lenses = gas_estimate_code_lenses(&db, file, test_file_uri(), "hash").unwrap();
+    // Only the external function has a lens, which is resolved later.
+    let [lens] = &lenses[..] else { panic!("Expected a single lens, got {lenses:?}.") };
+    assert_eq!(lens.command, None);
+    let data: GasEstimateLensData = serde_json::from_value(lens.data.clone().unwrap()).unwrap();
+    assert_eq!(data.function_path, "test::contract::double");
+    assert_eq!(data.crate_hash, "hash");
+
+    let estimate = estimate_function_gas(&db, file, &data);
+    assert!(estimate.as_ref().is_some_and(|estimate| estimate.starts_with("Estimated gas: ")));
+    let resolved = resolve_gas_estimate_code_lens(lens.clone(), estimate.clone());
+    assert_eq!(
+        resolved.command,
+        Some(Command {
+            title: estimate.clone().unwrap(),
+            command: SHOW_GAS_ESTIMATE_COMMAND.into(),
+            arguments: Some(vec![estimate.unwrap().into()]),
+        })
+    );
+}
+
+#[test]
+fn test_gas_estimates_cache() {
+    let data = |crate_hash: &str| GasEstimateLensData {
+        text_document: lsp::TextDocumentIdentifier { uri: test_file_uri() },
+        function_path: "test::contract::double".into(),
+        crate_hash: crate_hash.into(),
+    };
+    let mut estimates = GasEstimates::default();
+    estimates.record_document(test_file_uri(), "old".into());
+    estimates.insert(&data("old"), Some("Estimated gas: 1".into()));
+    assert_eq!(estimates.get(&data("old")), Some(Some("Estimated gas: 1".into())));
+    assert_eq!(estimates.get(&data("new")), None);
+    // The estimates of a revision are dropped once no document uses it.
+    estimates.record_document(test_file_uri(), "new".into());
+    assert_eq!(estimates.get(&data("old")), None);
+    assert_eq!(estimates.documents().collect::<Vec<_>>(), [(&test_file_uri(), &"new".to_string())]);
+}
@@ -1,9 +1,11 @@
+use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_defs::ids::{
-    FunctionWithBodyId, LanguageElementId, LookupItemId, ModuleFileId, ModuleId, ModuleItemId,
-    TopLevelLanguageElementId, TraitFunctionId,
+    ExternTypeId, FunctionWithBodyId, GenericKind, LanguageElementId, LookupItemId, ModuleFileId,
+    ModuleId, ModuleItemId, TopLevelLanguageElementId, TraitFunctionId,
 };
 use cairo_lang_filesystem::ids::FileId;
 use cairo_lang_filesystem::span::TextOffset;
+use cairo_lang_semantic::corelib::{core_module, get_submodule};
 use cairo_lang_semantic::db::SemanticGroup;
 use cairo_lang_semantic::diagnostic::{NotFoundItemType, SemanticDiagnostics};
 use cairo_lang_semantic::expr::inference::infers::InferenceEmbeddings;
@@ -14,19 +16,41 @@ use cairo_lang_semantic::items::structure::SemanticStructEx;
 use cairo_lang_semantic::items::us::SemanticUseEx;
 use cairo_lang_semantic::lookup_item::{HasResolverData, LookupItemEx};
 use cairo_lang_semantic::lsp_helpers::TypeFilter;
-use cairo_lang_semantic::resolve::{ResolvedConcreteItem, ResolvedGenericItem, Resolver};
+use cairo_lang_semantic::resolve::{
+    ResolvedConcreteItem, ResolvedGenericItem, Resolver, ResolverData,
+};
 use cairo_lang_semantic::types::peel_snapshots;
-use cairo_lang_semantic::{ConcreteTypeId, Pattern, TypeLongId};
+use cairo_lang_semantic::{
+    ConcreteTypeId, GenericArgumentId, Mutability, Pattern, PatternBinding, TypeLongId,
+};
 use cairo_lang_syntax::node::ast::PathSegment;
-use cairo_lang_syntax::node::{ast, TypedSyntaxNode};
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{ast, SyntaxNode, Terminal, TypedSyntaxNode};
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+use cairo_lang_utils::Upcast;
+use itertools::Itertools;
 use lsp::{CompletionItem, CompletionItemKind, Position, Range, TextEdit};
+use rayon::prelude::*;
+use salsa::Database;
+use tracing::debug;
 
+use crate::cancellation::CancellationToken;
+use crate::known_types::{function_path, is_inferred, KnownTypes};
 use crate::{find_node_module, from_pos};
 
+#[cfg(test)]
+#[path = "completions_test.rs"]
+mod test;
+
+/// Returns the completions of the crates, the items of the current module, and the parameters and
+/// local variables in scope at the given offset of the file.
 pub fn generic_completions(
     db: &(dyn SemanticGroup + 'static),
     module_file_id: ModuleFileId,
     lookup_items: Vec<LookupItemId>,
+    file_id: FileId,
+    offset: TextOffset,
 ) -> Vec<CompletionItem> {
     let mut completions = vec![];
 
@@ -48,15 +72,30 @@ pub fn generic_completions(
         }
     }));
 
-    // Local variables and params.
     let Some(lookup_item_id) = lookup_items.into_iter().next() else {
         return completions;
     };
+
+    // Generic params of the item, including the ones of the enclosing impl or trait.
+    if let Ok(resolver_data) = lookup_item_id.resolver_data(db) {
+        completions.extend(resolver_data.generic_params.iter().filter_map(|generic_param_id| {
+            Some(CompletionItem {
+                label: generic_param_id.name(db.upcast())?.into(),
+                kind: Some(generic_param_completion_kind(generic_param_id.kind(db.upcast()))),
+                ..CompletionItem::default()
+            })
+        }));
+    }
+
+    // Local variables and params.
     let function_id = match lookup_item_id {
         LookupItemId::ModuleItem(ModuleItemId::FreeFunction(free_function_id)) => {
             FunctionWithBodyId::Free(free_function_id)
         }
         LookupItemId::ImplFunction(impl_function_id) => FunctionWithBodyId::Impl(impl_function_id),
+        LookupItemId::TraitFunction(trait_function_id) => {
+            FunctionWithBodyId::Trait(trait_function_id)
+        }
         _ => {
             return completions;
         }
@@ -64,27 +103,99 @@ pub fn generic_completions(
     let Ok(signature) = db.function_with_body_signature(function_id) else {
         return completions;
     };
-    for param in &signature.params {
-        completions.push(CompletionItem {
-            label: param.name.clone().into(),
-            kind: Some(CompletionItemKind::VARIABLE),
-            ..CompletionItem::default()
-        });
+    let mut local_names: OrderedHashSet<String> =
+        signature.params.iter().map(|param| param.name.to_string()).collect();
+    if let Ok(body) = db.function_body(function_id) {
+        let syntax_db = db.upcast();
+        for (_id, pattern) in &body.patterns {
+            let (Pattern::Variable(var) | Pattern::Binding(PatternBinding { variable: var, .. })) =
+                pattern
+            else {
+                continue;
+            };
+            // Variables bound in code generated by macros are not visible to the user.
+            let stable_ptr = var.stable_ptr.untyped();
+            if stable_ptr.file_id(syntax_db) == file_id
+                && is_in_scope(syntax_db, stable_ptr.lookup(syntax_db), offset)
+            {
+                // A shadowed name is completed once.
+                local_names.insert(var.name.to_string());
+            }
+        }
     }
+    completions.extend(local_names.into_iter().map(|name| CompletionItem {
+        label: name,
+        kind: Some(CompletionItemKind::VARIABLE),
+        ..CompletionItem::default()
+    }));
+    completions
+}
 
-    let Ok(body) = db.function_body(function_id) else {
-        return completions;
-    };
-    for (_id, pat) in &body.patterns {
-        if let Pattern::Variable(var) = pat {
-            completions.push(CompletionItem {
-                label: var.name.clone().into(),
-                kind: Some(CompletionItemKind::VARIABLE),
-                ..CompletionItem::default()
-            });
+/// Returns whether a variable bound by a pattern is in scope at an offset - the offset is after the
+/// `let` statement binding it and within its block, within the expression of the match arm binding
+/// it, or within the body of the `for` loop binding it.
+fn is_in_scope(db: &dyn SyntaxGroup, pattern: SyntaxNode, offset: TextOffset) -> bool {
+    let mut node = pattern;
+    while let Some(parent) = node.parent() {
+        match parent.kind(db) {
+            SyntaxKind::StatementLet => {
+                let Some(block) = parent.parent().and_then(|statements| statements.parent()) else {
+                    return false;
+                };
+                return parent.span_without_trivia(db).end <= offset
+                    && offset < block.span_without_trivia(db).end;
+            }
+            SyntaxKind::MatchArm => {
+                let arm = ast::MatchArm::from_syntax_node(db, parent);
+                let span = arm.expression(db).as_syntax_node().span_without_trivia(db);
+                return span.start <= offset && offset <= span.end;
+            }
+            SyntaxKind::ExprFor => {
+                let expr = ast::ExprFor::from_syntax_node(db, parent);
+                let span = expr.body(db).as_syntax_node().span_without_trivia(db);
+                return span.start < offset && offset < span.end;
+            }
+            _ => node = parent,
         }
     }
-    completions
+    // All the constructs binding variables by patterns are handled above.
+    false
+}
+
+/// Returns the completions of attribute names - the attributes declared by the plugins and the
+/// compiler.
+pub fn attribute_completions(db: &(dyn SemanticGroup + 'static)) -> Vec<CompletionItem> {
+    db.allowed_attributes()
+        .iter()
+        .map(|attr| CompletionItem {
+            label: attr.clone(),
+            kind: Some(CompletionItemKind::KEYWORD),
+            ..CompletionItem::default()
+        })
+        .collect()
+}
+
+/// Returns the completions of the traits in `#[derive(...)]` - the traits declared as derivable by
+/// the plugins.
+pub fn derive_completions(db: &(dyn SemanticGroup + 'static)) -> Vec<CompletionItem> {
+    db.macro_plugins()
+        .iter()
+        .flat_map(|plugin| plugin.declared_derives())
+        .unique()
+        .map(|derive| CompletionItem {
+            label: derive,
+            kind: Some(CompletionItemKind::INTERFACE),
+            ..CompletionItem::default()
+        })
+        .collect()
+}
+
+fn generic_param_completion_kind(kind: GenericKind) -> CompletionItemKind {
+    match kind {
+        GenericKind::Type => CompletionItemKind::TYPE_PARAMETER,
+        GenericKind::Const => CompletionItemKind::CONSTANT,
+        GenericKind::Impl | GenericKind::NegImpl => CompletionItemKind::INTERFACE,
+    }
 }
 
 fn resolved_generic_item_completion_kind(item: ResolvedGenericItem) -> CompletionItemKind {
@@ -180,35 +291,49 @@ pub fn colon_colon_completions(
     })
 }
 
+/// Returns the completions of the methods and members of the type of the left side of a dot
+/// expression. While the function is mid-edit and the type of a variable on the left side is no
+/// longer inferred, its last inferred type in `known_types` is used.
 pub fn dot_completions(
-    db: &dyn SemanticGroup,
+    db: &RootDatabase,
     file_id: FileId,
     lookup_items: Vec<LookupItemId>,
     expr: ast::ExprBinary,
+    known_types: Option<&KnownTypes>,
+    cancellation: &CancellationToken,
 ) -> Option<Vec<CompletionItem>> {
     let syntax_db = db.upcast();
     // Get a resolver in the current context.
     let lookup_item_id = lookup_items.into_iter().next()?;
     let function_with_body = lookup_item_id.function_with_body()?;
     let module_id = function_with_body.module_file_id(db.upcast()).0;
+    // The resolver data of the function holds its generic params, so the impls they are bound by
+    // are found for methods of a generic param type.
     let resolver_data = lookup_item_id.resolver_data(db).ok()?;
-    let resolver = Resolver::with_data(
-        db,
-        resolver_data.as_ref().clone_with_inference_id(db, InferenceId::NoContext),
-    );
 
     // Extract lhs node.
     let node = expr.lhs(syntax_db);
     let stable_ptr = node.stable_ptr().untyped();
-    // Get its semantic model.
-    let expr_id = db.lookup_expr_by_ptr(function_with_body, node.stable_ptr()).ok()?;
-    let semantic_expr = db.expr_semantic(function_with_body, expr_id);
-    // Get the type.
-    let ty = semantic_expr.ty();
-    if ty.is_missing(db) {
-        eprintln!("Type is missing");
+    // Get its semantic model and its type. Type aliases are already resolved to the aliased type.
+    let ty = db
+        .lookup_expr_by_ptr(function_with_body, node.stable_ptr())
+        .ok()
+        .map(|expr_id| db.expr_semantic(function_with_body, expr_id).ty())
+        .filter(|ty| is_inferred(db, *ty))
+        .or_else(|| {
+            let ast::Expr::Path(path) = &node else {
+                return None;
+            };
+            let [PathSegment::Simple(segment)] = &path.elements(syntax_db)[..] else {
+                return None;
+            };
+            let function_path = function_path(db.upcast(), function_with_body);
+            known_types?.var_type(db, &function_path, &segment.ident(syntax_db).text(syntax_db))
+        });
+    let Some(ty) = ty else {
+        debug!("Type is not inferred");
         return None;
-    }
+    };
 
     // Find relevant methods for type.
     let offset = if let Some(ModuleId::Submodule(submodule_id)) =
@@ -224,11 +349,13 @@ pub fn dot_completions(
         TextOffset::default()
     };
     let position = from_pos(offset.position_in_file(db.upcast(), file_id).unwrap());
-    let relevant_methods = find_methods_for_type(db, resolver, ty, stable_ptr);
+    let relevant_methods =
+        find_methods_for_type(db, module_id, &resolver_data, ty, stable_ptr, cancellation)?;
 
     let mut completions = Vec::new();
-    for trait_function in relevant_methods {
-        let Some(completion) = completion_for_method(db, module_id, trait_function, position)
+    for (unwrap_prefix, trait_function) in relevant_methods {
+        let Some(completion) =
+            completion_for_method(db, module_id, &unwrap_prefix, trait_function, position)
         else {
             continue;
         };
@@ -253,10 +380,11 @@ pub fn dot_completions(
     Some(completions)
 }
 
-/// Returns a completion item for a method.
+/// Returns a completion item for a method, called after the given unwrapping calls.
 fn completion_for_method(
     db: &dyn SemanticGroup,
     module_id: ModuleId,
+    unwrap_prefix: &str,
     trait_function: TraitFunctionId,
     position: Position,
 ) -> Option<CompletionItem> {
@@ -278,8 +406,8 @@ fn completion_for_method(
     }
 
     let completion = CompletionItem {
-        label: format!("{}()", name),
-        insert_text: Some(format!("{}(", name)),
+        label: format!("{unwrap_prefix}{name}()"),
+        insert_text: Some(format!("{unwrap_prefix}{name}(")),
         detail: Some(detail),
         kind: Some(CompletionItemKind::METHOD),
         additional_text_edits: Some(additional_text_edits),
@@ -305,50 +433,143 @@ fn module_has_trait(
     Some(false)
 }
 
-/// Finds all methods that can be called on a type.
+/// Finds all methods that can be called on a type from the crates visible from the given module.
+/// Methods whose `self` param is `@T` are found for an expression of type `T` as well, since the
+/// type filter matches snapshot heads and the inference conforms the type with an automatic
+/// snapshot.
+/// The methods of a generic param type are found through the impls of the generic params in
+/// `resolver_data`, e.g. a `+Trait<T>` param.
+/// The methods of the values wrapped by `Box<T>` and `Nullable<T>` are found as well, each along
+/// with the unwrapping calls to prefix it with (e.g. `unbox().`). Methods taking `ref self` are
+/// skipped for wrapped values, as the unwrapped value is not a variable.
+/// The candidates are checked in parallel, each worker on its own database snapshot. Returns None
+/// if the search was canceled, either by the request or by a pending change to the database.
 fn find_methods_for_type(
+    db: &RootDatabase,
+    module_id: ModuleId,
+    resolver_data: &ResolverData,
+    ty: cairo_lang_semantic::TypeId,
+    stable_ptr: cairo_lang_syntax::node::ids::SyntaxStablePtrId,
+    cancellation: &CancellationToken,
+) -> Option<Vec<(String, TraitFunctionId)>> {
+    // Collect the candidate methods of the type and of the types it wraps.
+    let mut candidates = OrderedHashSet::default();
+    let mut current = Some((ty, String::new()));
+    while let Some((ty, unwrap_prefix)) = current {
+        let type_filter = match ty.head(db) {
+            Some(head) => TypeFilter::TypeHead(head),
+            None => TypeFilter::NoFilter,
+        };
+        for crate_id in db.visible_crates(module_id.owning_crate(db.upcast())).iter().copied() {
+            let methods = db.methods_in_crate(crate_id, type_filter.clone());
+            candidates.extend(
+                methods
+                    .iter()
+                    .filter(|trait_function| {
+                        unwrap_prefix.is_empty() || !takes_ref_self(db, **trait_function)
+                    })
+                    .map(|trait_function| (ty, unwrap_prefix.clone(), *trait_function)),
+            );
+        }
+        current = wrapped_type(db, ty).map(|(inner_ty, unwrap_method)| {
+            (inner_ty, format!("{unwrap_prefix}{unwrap_method}()."))
+        });
+    }
+
+    // Split the candidates between the workers.
+    let candidates = candidates.into_iter().collect_vec();
+    let chunk_size = candidates.len().div_ceil(rayon::current_num_threads()).max(1);
+    let chunks =
+        candidates.chunks(chunk_size).map(|chunk| (db.snapshot(), chunk.to_vec())).collect_vec();
+    let relevant_methods = chunks
+        .into_par_iter()
+        .map(|(db, chunk)| {
+            let mut relevant_methods = vec![];
+            for (ty, unwrap_prefix, trait_function) in chunk {
+                if cancellation.is_canceled() || db.salsa_runtime().is_current_revision_canceled() {
+                    return None;
+                }
+                if method_fits_type(&db, resolver_data, ty, trait_function, stable_ptr) {
+                    relevant_methods.push((unwrap_prefix, trait_function));
+                }
+            }
+            Some(relevant_methods)
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(relevant_methods.into_iter().flatten().unique().collect())
+}
+
+/// Checks if the `self` param of a method is a `ref` param.
+fn takes_ref_self(db: &dyn SemanticGroup, trait_function: TraitFunctionId) -> bool {
+    db.trait_function_signature(trait_function).is_ok_and(|signature| {
+        signature.params.first().is_some_and(|param| param.mutability == Mutability::Reference)
+    })
+}
+
+/// Checks if a method can be called on a type, i.e. its `self` param fits the type and the trait
+/// has an impl for it.
+fn method_fits_type(
     db: &dyn SemanticGroup,
-    mut resolver: Resolver<'_>,
+    resolver_data: &ResolverData,
     ty: cairo_lang_semantic::TypeId,
+    trait_function: TraitFunctionId,
     stable_ptr: cairo_lang_syntax::node::ids::SyntaxStablePtrId,
-) -> Vec<TraitFunctionId> {
-    let type_filter = match ty.head(db) {
-        Some(head) => TypeFilter::TypeHead(head),
-        None => TypeFilter::NoFilter,
+) -> bool {
+    let mut resolver =
+        Resolver::with_data(db, resolver_data.clone_with_inference_id(db, InferenceId::NoContext));
+    let lookup_context = resolver.impl_lookup_context();
+    let mut inference = resolver.inference();
+    // Check if trait function signature's first param can fit our expr type.
+    let Some((concrete_trait_id, _)) = inference.infer_concrete_trait_by_self(
+        trait_function,
+        ty,
+        &lookup_context,
+        Some(stable_ptr),
+        |_| {},
+    ) else {
+        debug!("Can't fit");
+        return false;
     };
 
-    let mut relevant_methods = Vec::new();
-    // Find methods on type.
-    // TODO(spapini): Look only in current crate dependencies.
-    for crate_id in db.crates() {
-        let methods = db.methods_in_crate(crate_id, type_filter.clone());
-        for trait_function in methods.iter().copied() {
-            let clone_data =
-                &mut resolver.inference().clone_with_inference_id(db, InferenceId::NoContext);
-            let mut inference = clone_data.inference(db);
-            let lookup_context = resolver.impl_lookup_context();
-            // Check if trait function signature's first param can fit our expr type.
-            let Some((concrete_trait_id, _)) = inference.infer_concrete_trait_by_self(
-                trait_function,
-                ty,
-                &lookup_context,
-                Some(stable_ptr),
-                |_| {},
-            ) else {
-                eprintln!("Can't fit");
-                continue;
-            };
+    // Find impls for it.
+    inference.solve().ok();
+    matches!(
+        inference.trait_solution_set(concrete_trait_id, lookup_context),
+        Ok(SolutionSet::Unique(_) | SolutionSet::Ambiguous(_))
+    )
+}
 
-            // Find impls for it.
-            inference.solve().ok();
-            if !matches!(
-                inference.trait_solution_set(concrete_trait_id, lookup_context),
-                Ok(SolutionSet::Unique(_) | SolutionSet::Ambiguous(_))
-            ) {
-                continue;
-            }
-            relevant_methods.push(trait_function);
-        }
+/// Returns the inner type of a `Box<T>` or `Nullable<T>` type of the corelib, along with the name
+/// of the method unwrapping it.
+fn wrapped_type(
+    db: &dyn SemanticGroup,
+    ty: cairo_lang_semantic::TypeId,
+) -> Option<(cairo_lang_semantic::TypeId, &'static str)> {
+    let TypeLongId::Concrete(ConcreteTypeId::Extern(extern_ty)) = db.lookup_intern_type(ty) else {
+        return None;
+    };
+    let long_id = db.lookup_intern_concrete_extern_type(extern_ty);
+    let unwrap_method = [("box", "Box", "unbox"), ("nullable", "Nullable", "deref")]
+        .into_iter()
+        .find(|(submodule, name, _)| {
+            core_extern_type_id(db, submodule, name) == Some(long_id.extern_type_id)
+        })?
+        .2;
+    match long_id.generic_args[..] {
+        [GenericArgumentId::Type(inner_ty)] => Some((inner_ty, unwrap_method)),
+        _ => None,
+    }
+}
+
+/// Returns the id of an extern type defined in a submodule of the corelib.
+fn core_extern_type_id(
+    db: &dyn SemanticGroup,
+    submodule: &str,
+    name: &str,
+) -> Option<ExternTypeId> {
+    let module_id = get_submodule(db, core_module(db), submodule)?;
+    match db.module_item_by_name(module_id, name.into()).ok()?? {
+        ModuleItemId::ExternType(extern_type_id) => Some(extern_type_id),
+        _ => None,
     }
-    relevant_methods
 }
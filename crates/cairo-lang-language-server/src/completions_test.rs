@@ -0,0 +1,186 @@
+use indoc::indoc;
+use lsp::{CompletionItemKind, CompletionResponse, CompletionTriggerKind};
+
+use crate::cancellation::CancellationToken;
+use crate::completions as completions_at;
+use crate::test_utils::{offset_position, setup_test_file};
+
+/// The code completed in the tests, with methods taking the value, a snapshot of it and a reference
+/// to it, an alias of the type, and generic traits for generic params to be bound by.
+const CODE: &str = indoc! {"
+    #[derive(Drop)]
+    struct A {
+        x: felt252,
+    }
+    trait ATrait {
+        fn by_value(self: A) -> felt252;
+        fn by_snapshot(self: @A) -> felt252;
+        fn by_ref(ref self: A);
+    }
+    impl AImpl of ATrait {
+        fn by_value(self: A) -> felt252 {
+            self.x
+        }
+        fn by_snapshot(self: @A) -> felt252 {
+            *self.x
+        }
+        fn by_ref(ref self: A) {}
+    }
+    type AAlias = A;
+    trait GenericTrait<T> {
+        fn by_generic_value(self: T) -> T;
+        fn by_generic_snapshot(self: @T) -> @T;
+    }
+    trait UnboundTrait<T> {
+        fn by_unbound_value(self: T) -> T;
+    }
+"};
+
+/// Returns the sorted labels of the method completions after the given dot-terminated expression,
+/// in a function taking the given params.
+fn method_labels(params: &str, expr: &str) -> Vec<String> {
+    generic_method_labels("", params, expr)
+}
+
+/// Returns the sorted labels of the method completions after the given dot-terminated expression,
+/// in a function with the given generic params, taking the given params.
+fn generic_method_labels(generic_params: &str, params: &str, expr: &str) -> Vec<String> {
+    let code = format!("{CODE}fn foo{generic_params}({params}) {{\n    {expr}\n}}\n");
+    let (db, file) = setup_test_file(&code);
+    let position = offset_position(&code, code.find(expr).unwrap() + expr.len());
+    let Some(CompletionResponse::Array(completions)) = completions_at(
+        &db,
+        file,
+        position,
+        CompletionTriggerKind::TRIGGER_CHARACTER,
+        None,
+        &CancellationToken::default(),
+    ) else {
+        panic!("Expected completions.");
+    };
+    let mut labels = completions
+        .into_iter()
+        .filter(|completion| completion.label.contains("by_"))
+        .map(|completion| completion.label)
+        .collect::<Vec<_>>();
+    labels.sort();
+    labels
+}
+
+#[test]
+fn test_method_completions() {
+    assert_eq!(method_labels("a: A", "a."), ["by_ref()", "by_snapshot()", "by_value()"]);
+}
+
+#[test]
+fn test_method_completions_of_snapshot() {
+    assert_eq!(method_labels("a: @A", "a."), ["by_snapshot()"]);
+}
+
+#[test]
+fn test_method_completions_of_wrapped_value() {
+    assert_eq!(method_labels("a: Box<A>", "a."), ["unbox().by_snapshot()", "unbox().by_value()"]);
+    assert_eq!(
+        method_labels("a: Nullable<A>", "a."),
+        ["deref().by_snapshot()", "deref().by_value()"]
+    );
+}
+
+#[test]
+fn test_method_completions_of_type_alias() {
+    assert_eq!(method_labels("a: AAlias", "a."), ["by_ref()", "by_snapshot()", "by_value()"]);
+    assert_eq!(method_labels("a: @AAlias", "a."), ["by_snapshot()"]);
+}
+
+#[test]
+fn test_method_completions_of_generic_param() {
+    assert_eq!(
+        generic_method_labels("<T, +GenericTrait<T>>", "a: T", "a."),
+        ["by_generic_snapshot()", "by_generic_value()"]
+    );
+    assert_eq!(
+        generic_method_labels("<T, +GenericTrait<T>>", "a: @T", "a."),
+        ["by_generic_snapshot()"]
+    );
+    assert_eq!(
+        generic_method_labels("<T, impl TImpl: GenericTrait<T>>", "a: T", "a."),
+        ["by_generic_snapshot()", "by_generic_value()"]
+    );
+    assert!(generic_method_labels("<T>", "a: T", "a.").is_empty());
+}
+
+#[test]
+fn test_canceled_method_completions() {
+    let code = format!("{CODE}fn foo(a: A) {{\n    a.\n}}\n");
+    let (db, file) = setup_test_file(&code);
+    let position = offset_position(&code, code.find("a.\n").unwrap() + 2);
+    let cancellation = CancellationToken::default();
+    // Dropping the request cancels the token, stopping the search for the methods.
+    cancellation.drop_guard();
+    assert_eq!(
+        completions_at(
+            &db,
+            file,
+            position,
+            CompletionTriggerKind::TRIGGER_CHARACTER,
+            None,
+            &cancellation
+        ),
+        None
+    );
+}
+
+/// Returns the sorted labels of the variable completions at the `$` in the body of a function
+/// taking `param: felt252`.
+fn variable_labels(body: &str) -> Vec<String> {
+    let code = format!("fn foo(param: felt252) {{\n{body}\n}}\n");
+    let offset = code.find('$').unwrap();
+    let code = code.replace('$', "");
+    let (db, file) = setup_test_file(&code);
+    let Some(CompletionResponse::Array(completions)) = completions_at(
+        &db,
+        file,
+        offset_position(&code, offset),
+        CompletionTriggerKind::INVOKED,
+        None,
+        &CancellationToken::default(),
+    ) else {
+        panic!("Expected completions.");
+    };
+    let mut labels = completions
+        .into_iter()
+        .filter(|completion| completion.kind == Some(CompletionItemKind::VARIABLE))
+        .map(|completion| completion.label)
+        .collect::<Vec<_>>();
+    labels.sort();
+    labels
+}
+
+#[test]
+fn test_variable_completions_after_let() {
+    assert_eq!(variable_labels("    let x = 5;$\n    let y = 6;"), ["param", "x"]);
+}
+
+#[test]
+fn test_variable_completions_in_match_arm() {
+    let body = indoc! {"
+        let before = 1;
+        match Option::Some(before) {
+            v @ Option::Some(x) => { $ },
+            Option::None => {},
+        };
+        let after = 2;
+    "};
+    assert_eq!(variable_labels(body), ["before", "param", "v", "x"]);
+}
+
+#[test]
+fn test_variable_completions_in_for_loop() {
+    let body = indoc! {"
+        for item in array![1_u8].span() {
+            $
+        };
+        for other in array![2_u8].span() {};
+    "};
+    assert_eq!(variable_labels(body), ["item", "param"]);
+}
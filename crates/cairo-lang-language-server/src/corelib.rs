@@ -0,0 +1,70 @@
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use cairo_lang_filesystem::detect::detect_corelib;
+
+/// The environment variable overriding the path of the corelib.
+pub const CORELIB_PATH_ENV_VAR: &str = "CAIRO_LSP_CORELIB_PATH";
+
+/// The client configuration key holding the path of the corelib.
+pub const CORELIB_PATH_CONFIG_SECTION: &str = "cairo1.corelibPath";
+
+/// Where the corelib in use was found.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CorelibSource {
+    /// The corelib of the Scarb project the file belongs to.
+    Scarb,
+    /// The path in the [CORELIB_PATH_ENV_VAR] environment variable.
+    EnvVar,
+    /// The path in the client configuration under [CORELIB_PATH_CONFIG_SECTION].
+    Config,
+    /// The corelib bundled with the language server, or the one of the development tree.
+    Bundled,
+}
+impl Display for CorelibSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CorelibSource::Scarb => write!(f, "Scarb"),
+            CorelibSource::EnvVar => write!(f, "the `{CORELIB_PATH_ENV_VAR}` environment variable"),
+            CorelibSource::Config => write!(f, "the `{CORELIB_PATH_CONFIG_SECTION}` setting"),
+            CorelibSource::Bundled => write!(f, "the bundled corelib"),
+        }
+    }
+}
+
+/// Returns the path of the corelib and where it was found, in order of precedence - the corelib of
+/// the Scarb project, the environment variable, the client configuration, and the bundled corelib.
+pub fn find_corelib(
+    scarb_corelib: Option<PathBuf>,
+    config_corelib: Option<&str>,
+) -> Option<(PathBuf, CorelibSource)> {
+    scarb_corelib
+        .map(|path| (path, CorelibSource::Scarb))
+        .or_else(|| {
+            let value = std::env::var(CORELIB_PATH_ENV_VAR).ok()?;
+            Some((resolve_corelib_path(&value)?, CorelibSource::EnvVar))
+        })
+        .or_else(|| Some((resolve_corelib_path(config_corelib?)?, CorelibSource::Config)))
+        .or_else(|| Some((detect_corelib()?, CorelibSource::Bundled)))
+}
+
+/// Returns the message shown to the user when the corelib is not found.
+pub fn corelib_missing_message() -> String {
+    format!(
+        "Failed to find the Cairo corelib. Set its path with the `{CORELIB_PATH_CONFIG_SECTION}` \
+         setting or the `{CORELIB_PATH_ENV_VAR}` environment variable. Until then, paths from the \
+         corelib are not resolved."
+    )
+}
+
+/// Resolves a user-given corelib path, which may omit the `corelib/src` or `src` suffix.
+/// Returns None if the value is empty or no such directory exists.
+fn resolve_corelib_path(value: &str) -> Option<PathBuf> {
+    if value.is_empty() {
+        return None;
+    }
+    let root_path = Path::new(value);
+    [root_path.join("corelib").join("src"), root_path.join("src"), root_path.to_path_buf()]
+        .into_iter()
+        .find(|path| path.exists())
+}
@@ -0,0 +1,252 @@
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_defs::ids::{FunctionWithBodyId, LookupItemId, ModuleItemId};
+use cairo_lang_diagnostics::ToOption;
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::items::function_with_body::SemanticExprLookup;
+use cairo_lang_semantic::{Expr, ExprFunctionCallArg, ExprVarMemberPath, Pattern};
+use cairo_lang_syntax::node::helpers::GenericParamEx;
+use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{ast, SyntaxNode, Terminal, TypedSyntaxNode};
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use cairo_lang_utils::Upcast;
+use lsp::{DocumentHighlight, DocumentHighlightKind, Position};
+
+use crate::{find_definition, get_lookup_items, get_node_and_lookup_items, node_range};
+
+/// Returns the highlights of the occurrences in the file of the variable or item at a position.
+/// Occurrences of variables are marked as writes where the semantic model knows they are written -
+/// their declarations, assignments to them and passing them as `ref` arguments - and as reads
+/// otherwise.
+pub fn document_highlights(
+    db: &RootDatabase,
+    file: FileId,
+    position: Position,
+) -> Option<Vec<DocumentHighlight>> {
+    let syntax_db = db.upcast();
+    let (node, lookup_items) = get_node_and_lookup_items(db, file, position)?;
+    if node.kind(syntax_db) != SyntaxKind::TokenIdentifier {
+        return None;
+    }
+    let identifier = ast::TerminalIdentifier::from_syntax_node(syntax_db, node.parent()?);
+    let symbol = symbol_name(db, file, &identifier, &lookup_items)?;
+    let is_variable = matches!(
+        symbol.parent()?.kind(syntax_db),
+        SyntaxKind::Param | SyntaxKind::PatternIdentifier
+    );
+    let written_vars =
+        if is_variable { written_vars(db, &lookup_items) } else { Default::default() };
+    let root = db.file_syntax(file).to_option()?;
+    let mut highlights = vec![];
+    for node in occurrences(db, file, root, &symbol) {
+        let kind = if !is_variable {
+            DocumentHighlightKind::TEXT
+        } else if node == symbol || is_written(db, &node, &written_vars) {
+            DocumentHighlightKind::WRITE
+        } else {
+            DocumentHighlightKind::READ
+        };
+        let Some(range) = node_range(db, &node) else {
+            continue;
+        };
+        highlights.push(DocumentHighlight { range, kind: Some(kind) });
+    }
+    Some(highlights)
+}
+
+/// Returns the identifier nodes under a root node referring to a symbol, given by the name node of
+/// its declaration - including the name node itself.
+pub fn occurrences(
+    db: &RootDatabase,
+    file: FileId,
+    root: SyntaxNode,
+    symbol: &SyntaxNode,
+) -> Vec<SyntaxNode> {
+    let syntax_db = db.upcast();
+    let name = symbol.clone().get_text_without_trivia(syntax_db);
+    root.descendants(syntax_db)
+        .filter(|node| {
+            if node.kind(syntax_db) != SyntaxKind::TerminalIdentifier {
+                return false;
+            }
+            let occurrence = ast::TerminalIdentifier::from_syntax_node(syntax_db, node.clone());
+            if occurrence.text(syntax_db) != name {
+                return false;
+            }
+            let Some(lookup_items) = get_lookup_items(db, file, node.clone()) else {
+                return false;
+            };
+            symbol_name(db, file, &occurrence, &lookup_items).as_ref() == Some(symbol)
+        })
+        .collect()
+}
+
+/// Returns the name node of the declaration of the symbol an identifier refers to, or of the
+/// declaration the identifier is the name of. Occurrences of the same symbol have the same name
+/// node.
+pub fn symbol_name(
+    db: &RootDatabase,
+    file: FileId,
+    identifier: &ast::TerminalIdentifier,
+    lookup_items: &[LookupItemId],
+) -> Option<SyntaxNode> {
+    if let Some(stable_ptr) = find_definition(db, file, identifier, lookup_items) {
+        return declaration_name(db, stable_ptr.lookup(db.upcast()));
+    }
+    let node = identifier.as_syntax_node();
+    if is_variable_pattern(db, &node, lookup_items) {
+        return Some(node);
+    }
+    declaration_name(db, node.parent()?).filter(|name| *name == node)
+}
+
+/// Returns true if the identifier is the name of a variable bound by a pattern without modifiers,
+/// which is parsed as a path.
+fn is_variable_pattern(
+    db: &RootDatabase,
+    identifier: &SyntaxNode,
+    lookup_items: &[LookupItemId],
+) -> bool {
+    let Some(path) = identifier.parent().and_then(|segment| segment.parent()) else {
+        return false;
+    };
+    if path.kind(db.upcast()) != SyntaxKind::ExprPath {
+        return false;
+    }
+    let Some(function_id) = function_with_body(lookup_items) else {
+        return false;
+    };
+    db.lookup_pattern_by_ptr(function_id, ast::PatternPtr(path.stable_ptr())).is_ok_and(
+        |pattern_id| matches!(db.pattern_semantic(function_id, pattern_id), Pattern::Variable(_)),
+    )
+}
+
+/// Returns the innermost function with a body among the lookup items.
+fn function_with_body(lookup_items: &[LookupItemId]) -> Option<FunctionWithBodyId> {
+    lookup_items.iter().find_map(|item| match item {
+        LookupItemId::ModuleItem(ModuleItemId::FreeFunction(free_function_id)) => {
+            Some(FunctionWithBodyId::Free(*free_function_id))
+        }
+        LookupItemId::ImplFunction(impl_function_id) => {
+            Some(FunctionWithBodyId::Impl(*impl_function_id))
+        }
+        LookupItemId::TraitFunction(trait_function_id) => {
+            Some(FunctionWithBodyId::Trait(*trait_function_id))
+        }
+        _ => None,
+    })
+}
+
+/// Returns the name node of a declaration, or None if the node is not a declaration.
+fn declaration_name(db: &RootDatabase, node: SyntaxNode) -> Option<SyntaxNode> {
+    let syntax_db = db.upcast();
+    let name = match node.kind(syntax_db) {
+        // Local variables are declared by their name.
+        SyntaxKind::TerminalIdentifier => return Some(node),
+        SyntaxKind::PatternIdentifier => {
+            ast::PatternIdentifier::from_syntax_node(syntax_db, node).name(syntax_db)
+        }
+        SyntaxKind::Param => ast::Param::from_syntax_node(syntax_db, node).name(syntax_db),
+        SyntaxKind::FunctionDeclaration => {
+            ast::FunctionDeclaration::from_syntax_node(syntax_db, node).name(syntax_db)
+        }
+        SyntaxKind::FunctionWithBody => ast::FunctionWithBody::from_syntax_node(syntax_db, node)
+            .declaration(syntax_db)
+            .name(syntax_db),
+        SyntaxKind::ItemExternFunction => {
+            ast::ItemExternFunction::from_syntax_node(syntax_db, node)
+                .declaration(syntax_db)
+                .name(syntax_db)
+        }
+        SyntaxKind::TraitItemFunction => ast::TraitItemFunction::from_syntax_node(syntax_db, node)
+            .declaration(syntax_db)
+            .name(syntax_db),
+        SyntaxKind::ItemModule => {
+            ast::ItemModule::from_syntax_node(syntax_db, node).name(syntax_db)
+        }
+        // Inline modules are defined by their body.
+        SyntaxKind::ModuleBody => return declaration_name(db, node.parent()?),
+        SyntaxKind::ItemConstant => {
+            ast::ItemConstant::from_syntax_node(syntax_db, node).name(syntax_db)
+        }
+        SyntaxKind::ItemExternType => {
+            ast::ItemExternType::from_syntax_node(syntax_db, node).name(syntax_db)
+        }
+        SyntaxKind::ItemTrait => ast::ItemTrait::from_syntax_node(syntax_db, node).name(syntax_db),
+        SyntaxKind::ItemImpl => ast::ItemImpl::from_syntax_node(syntax_db, node).name(syntax_db),
+        SyntaxKind::ItemImplAlias => {
+            ast::ItemImplAlias::from_syntax_node(syntax_db, node).name(syntax_db)
+        }
+        SyntaxKind::ItemStruct => {
+            ast::ItemStruct::from_syntax_node(syntax_db, node).name(syntax_db)
+        }
+        SyntaxKind::ItemEnum => ast::ItemEnum::from_syntax_node(syntax_db, node).name(syntax_db),
+        SyntaxKind::ItemTypeAlias => {
+            ast::ItemTypeAlias::from_syntax_node(syntax_db, node).name(syntax_db)
+        }
+        SyntaxKind::Member => ast::Member::from_syntax_node(syntax_db, node).name(syntax_db),
+        SyntaxKind::Variant => ast::Variant::from_syntax_node(syntax_db, node).name(syntax_db),
+        kind if ast::GenericParam::is_variant(kind) => {
+            ast::GenericParam::from_syntax_node(syntax_db, node).name(syntax_db)?
+        }
+        _ => return None,
+    };
+    Some(name.as_syntax_node())
+}
+
+/// Returns the stable pointers of the variable expressions written in the body of the innermost
+/// function containing the lookup items - assigned to, or passed as `ref` arguments.
+fn written_vars(
+    db: &RootDatabase,
+    lookup_items: &[LookupItemId],
+) -> UnorderedHashSet<SyntaxStablePtrId> {
+    let mut written_vars = UnorderedHashSet::default();
+    let Some(function_id) = function_with_body(lookup_items) else {
+        return written_vars;
+    };
+    let Ok(body) = db.function_body(function_id) else {
+        return written_vars;
+    };
+    for (_, expr) in body.exprs.iter() {
+        match expr {
+            Expr::Assignment(assignment) => {
+                written_vars.insert(base_var_stable_ptr(&assignment.ref_arg));
+            }
+            Expr::FunctionCall(call) => {
+                for arg in &call.args {
+                    if let ExprFunctionCallArg::Reference(ref_arg) = arg {
+                        written_vars.insert(base_var_stable_ptr(ref_arg));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    written_vars
+}
+
+/// Returns the stable pointer of the variable expression a member path starts with.
+fn base_var_stable_ptr(member_path: &ExprVarMemberPath) -> SyntaxStablePtrId {
+    match member_path {
+        ExprVarMemberPath::Var(var) => var.stable_ptr.untyped(),
+        ExprVarMemberPath::Member { parent, .. } => base_var_stable_ptr(parent),
+    }
+}
+
+/// Returns true if the identifier node is in one of the written variable expressions.
+fn is_written(
+    db: &RootDatabase,
+    node: &SyntaxNode,
+    written_vars: &UnorderedHashSet<SyntaxStablePtrId>,
+) -> bool {
+    let mut node = node.clone();
+    while node.kind(db.upcast()) != SyntaxKind::ExprPath {
+        let Some(parent) = node.parent() else {
+            return false;
+        };
+        node = parent;
+    }
+    written_vars.contains(&node.stable_ptr())
+}
@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_diagnostics::ToOption;
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::ids::{FileId, FileKind, FileLongId, VirtualFile};
+use cairo_lang_formatter::format_string;
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{ast, SyntaxNode, TypedSyntaxNode};
+use cairo_lang_utils::Upcast;
+use lsp::{Position, TextDocumentIdentifier, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::{get_uri, position_to_offset};
+
+/// The name of the virtual file holding the expansion of an item.
+const EXPANSION_FILE_NAME: &str = "expanded";
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpandMacroRequest {
+    pub text_document: TextDocumentIdentifier,
+    pub position: Position,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+pub struct ExpandMacroResponse {
+    /// The URI of a virtual document holding the expansion, to be fetched with `vfs/provide`.
+    /// None if no macro plugin acts on the item at the position.
+    pub uri: Option<Url>,
+}
+
+/// Returns the URI of a virtual document holding the code generated by the macro plugins for the
+/// innermost item at a position that they act on, or None if there is no such item.
+pub fn expand_macro(db: &RootDatabase, file: FileId, position: Position) -> Option<Url> {
+    let offset = position_to_offset(db.file_summary(file)?, position, &db.file_content(file)?)?;
+    let node = db.file_syntax(file).to_option()?.lookup_offset(db.upcast(), offset);
+    let expansion =
+        enclosing_items(db, node).into_iter().find_map(|item| expand_item(db, file, item))?;
+    let expansion_file = db.intern_file(FileLongId::Virtual(VirtualFile {
+        parent: Some(file),
+        name: EXPANSION_FILE_NAME.into(),
+        content: Arc::new(format_string(db, expansion)),
+        code_mappings: Default::default(),
+        kind: FileKind::Module,
+    }));
+    Some(get_uri(db, expansion_file))
+}
+
+/// Returns the module items containing a node, from the innermost to the outermost.
+fn enclosing_items(db: &RootDatabase, node: SyntaxNode) -> Vec<ast::Item> {
+    let mut items = vec![];
+    let mut node = node;
+    while let Some(parent) = node.parent() {
+        if parent.kind(db) == SyntaxKind::ItemList {
+            items.push(ast::Item::from_syntax_node(db, node));
+        }
+        node = parent;
+    }
+    items
+}
+
+/// Returns the code of an item after running the macro plugins on it, the same way the module
+/// items are collected - including the kept original item, and the expansion of the generated
+/// items. Returns None if no plugin acts on the item.
+/// `module_file` is the file containing the item, which is the parent of the generated files.
+fn expand_item(db: &RootDatabase, module_file: FileId, item: ast::Item) -> Option<String> {
+    let syntax_db = db.upcast();
+    let mut generated_parts = vec![];
+    let mut remove_original_item = false;
+    for plugin in db.macro_plugins() {
+        let result = plugin.generate_code(syntax_db, item.clone());
+        if result.remove_original_item {
+            remove_original_item = true;
+        }
+        if let Some(generated) = result.code {
+            // Interns the same file as the module data does, so its syntax is reused.
+            let generated_file = db.intern_file(FileLongId::Virtual(VirtualFile {
+                parent: Some(module_file),
+                name: generated.name,
+                content: Arc::new(generated.content),
+                code_mappings: Arc::new(generated.code_mappings),
+                kind: FileKind::Module,
+            }));
+            let generated_items = db.file_module_syntax(generated_file).ok()?.items(syntax_db);
+            for generated_item in generated_items.elements(syntax_db) {
+                generated_parts.push(
+                    expand_item(db, generated_file, generated_item.clone())
+                        .unwrap_or_else(|| item_text(db, &generated_item)),
+                );
+            }
+        }
+        if remove_original_item {
+            break;
+        }
+    }
+    if generated_parts.is_empty() && !remove_original_item {
+        return None;
+    }
+    let original_part = (!remove_original_item).then(|| item_text(db, &item));
+    Some(original_part.into_iter().chain(generated_parts).collect::<Vec<_>>().join("\n\n"))
+}
+
+/// Returns the code of an item, including its attributes.
+fn item_text(db: &RootDatabase, item: &ast::Item) -> String {
+    item.as_syntax_node().get_text_without_trivia(db.upcast())
+}
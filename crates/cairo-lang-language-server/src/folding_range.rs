@@ -0,0 +1,85 @@
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_diagnostics::ToOption;
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::SyntaxNode;
+use cairo_lang_utils::Upcast;
+use lsp::{FoldingRange, FoldingRangeKind};
+
+use crate::node_range;
+
+/// Returns the folding ranges of the file: the braced bodies of functions, blocks, impls, traits,
+/// modules, match expressions, structs and enums, the multi-line groups of use paths, and the
+/// blocks of consecutive comment lines.
+pub fn folding_ranges(db: &RootDatabase, file: FileId) -> Option<Vec<FoldingRange>> {
+    let syntax_db = db.upcast();
+    let mut ranges = vec![];
+    for node in db.file_syntax(file).to_option()?.descendants(syntax_db) {
+        let kind = match node.kind(syntax_db) {
+            SyntaxKind::ExprBlock
+            | SyntaxKind::ImplBody
+            | SyntaxKind::TraitBody
+            | SyntaxKind::ModuleBody
+            | SyntaxKind::ExprMatch
+            | SyntaxKind::ItemStruct
+            | SyntaxKind::ItemEnum => None,
+            SyntaxKind::UsePathMulti => Some(FoldingRangeKind::Imports),
+            _ => continue,
+        };
+        ranges.extend(braces_folding_range(db, &node, kind));
+    }
+    ranges.extend(comment_folding_ranges(db, file)?);
+    Some(ranges)
+}
+
+/// Returns a folding range from the line of the opening brace of a node to the line of its closing
+/// brace, if they are on different lines.
+fn braces_folding_range(
+    db: &RootDatabase,
+    node: &SyntaxNode,
+    kind: Option<FoldingRangeKind>,
+) -> Option<FoldingRange> {
+    let syntax_db = db.upcast();
+    let children = db.get_children(node.clone());
+    let brace_line = |brace_kind| {
+        let brace = children.iter().find(|child| child.kind(syntax_db) == brace_kind)?;
+        Some(node_range(db, brace)?.start.line)
+    };
+    let start_line = brace_line(SyntaxKind::TerminalLBrace)?;
+    let end_line = brace_line(SyntaxKind::TerminalRBrace)?;
+    (start_line < end_line).then(|| FoldingRange {
+        start_line,
+        end_line,
+        kind,
+        ..Default::default()
+    })
+}
+
+/// Returns the folding ranges of the blocks of at least two consecutive lines that only contain a
+/// comment.
+fn comment_folding_ranges(db: &RootDatabase, file: FileId) -> Option<Vec<FoldingRange>> {
+    let content = db.file_content(file)?;
+    let mut ranges = vec![];
+    let mut block_start = None;
+    for (line, text) in content.lines().chain([""]).enumerate() {
+        let line = line as u32;
+        if text.trim_start().starts_with("//") {
+            block_start.get_or_insert(line);
+            continue;
+        }
+        if let Some(start_line) = block_start.take() {
+            if start_line + 1 < line {
+                ranges.push(FoldingRange {
+                    start_line,
+                    end_line: line - 1,
+                    kind: Some(FoldingRangeKind::Comment),
+                    ..Default::default()
+                });
+            }
+        }
+    }
+    Some(ranges)
+}
@@ -0,0 +1,216 @@
+use std::path::Path;
+
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_diagnostics::ToOption;
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::ids::{FileId, FileLongId};
+use cairo_lang_formatter::{get_formatted_file, FormatterConfig};
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::SyntaxNode;
+use cairo_lang_utils::Upcast;
+use diffy::{DiffOptions, Line};
+use lsp::{Position, Range, TextEdit};
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::{from_pos, position_to_offset, MAX_CRATE_DETECTION_DEPTH};
+
+/// The manifest files that may hold the formatter config of a project, in a `[tool.fmt]` table.
+const MANIFEST_FILE_NAMES: [&str; 2] = ["Scarb.toml", "cairo_project.toml"];
+
+/// Returns the edits formatting a file, or only the lines of `range` in it if given.
+/// The edits only replace the lines changed by the formatter, so unchanged code (and cursor
+/// positions in it) are left intact.
+pub fn formatting_edits(
+    db: &RootDatabase,
+    file: FileId,
+    range: Option<Range>,
+) -> Option<Vec<TextEdit>> {
+    let node = db.file_syntax(file).to_option()?;
+    db.file_syntax_diagnostics(file).check_error_free().ok()?;
+    let old_text = db.file_content(file)?;
+    let new_text = get_formatted_file(db.upcast(), &node, formatter_config(db, file));
+
+    let patch = DiffOptions::new().set_context_len(0).create_patch(&old_text, &new_text);
+    let mut edits = vec![];
+    for hunk in patch.hunks() {
+        let old_range = hunk.old_range();
+        // Hunk ranges are 1-based, unless they are empty.
+        let start = if old_range.is_empty() { old_range.start() } else { old_range.start() - 1 };
+        let end = start + old_range.len();
+        if let Some(range) = range {
+            let (first, last) = (range.start.line as usize, range.end.line as usize);
+            if end < first || start > last || (!old_range.is_empty() && end == first) {
+                continue;
+            }
+        }
+        let new_text = hunk
+            .lines()
+            .iter()
+            .filter_map(|line| match line {
+                Line::Insert(text) => Some(*text),
+                Line::Context(_) | Line::Delete(_) => None,
+            })
+            .collect();
+        edits.push(TextEdit {
+            range: Range {
+                start: Position { line: start as u32, character: 0 },
+                end: Position { line: end as u32, character: 0 },
+            },
+            new_text,
+        });
+    }
+    Some(edits)
+}
+
+/// Returns the edits for formatting while typing `typed` at `position`, which is right after the
+/// typed text. A newline only indents the new line, according to the nesting of brackets before
+/// it, as the code is usually incomplete while typing. A closing brace or a semicolon formats the
+/// innermost block containing it, aligning the arms of a match when closing one of its arms.
+pub fn on_type_formatting_edits(
+    db: &RootDatabase,
+    file: FileId,
+    position: Position,
+    typed: &str,
+    tab_size: u32,
+) -> Option<Vec<TextEdit>> {
+    if typed == "\n" {
+        let content = db.file_content(file)?;
+        return Some(
+            line_indentation_edit(&content, position.line, tab_size).into_iter().collect(),
+        );
+    }
+    let node = db.file_syntax(file).to_option()?;
+    let typed_position = Position { character: position.character.checked_sub(1)?, ..position };
+    let offset =
+        position_to_offset(db.file_summary(file)?, typed_position, &db.file_content(file)?)?;
+    let block = enclosing_block(db.upcast(), node.lookup_offset(db.upcast(), offset))?;
+    let span = block.span_without_trivia(db.upcast());
+    let range = Range {
+        start: from_pos(span.start.position_in_file(db.upcast(), file)?),
+        end: from_pos(span.end.position_in_file(db.upcast(), file)?),
+    };
+    formatting_edits(db, file, Some(range))
+}
+
+/// Returns the innermost block-like node containing `node`. For a block of a match arm, returns the
+/// whole match, so its arms are aligned together.
+fn enclosing_block(db: &dyn SyntaxGroup, node: SyntaxNode) -> Option<SyntaxNode> {
+    let mut node = Some(node);
+    while let Some(current) = node {
+        let parent = current.parent();
+        let is_block = match current.kind(db) {
+            SyntaxKind::ExprBlock => {
+                parent.as_ref().map(|parent| parent.kind(db)) != Some(SyntaxKind::MatchArm)
+            }
+            SyntaxKind::ExprMatch
+            | SyntaxKind::ModuleBody
+            | SyntaxKind::TraitBody
+            | SyntaxKind::ImplBody
+            | SyntaxKind::ItemStruct
+            | SyntaxKind::ItemEnum => true,
+            _ => false,
+        };
+        if is_block {
+            return Some(current);
+        }
+        node = parent;
+    }
+    None
+}
+
+/// Returns the edit setting the indentation of a line to the nesting depth of the brackets before
+/// it, or `None` if it is already indented so.
+fn line_indentation_edit(content: &str, line: u32, tab_size: u32) -> Option<TextEdit> {
+    let line_start =
+        content.split_inclusive('\n').take(line as usize).map(|line| line.len()).sum::<usize>();
+    let line_text = content[line_start..].split('\n').next()?;
+    let current_indentation =
+        line_text.chars().take_while(|c| *c == ' ' || *c == '\t').collect::<String>();
+    let mut depth = bracket_depth(&content[..line_start]);
+    if line_text.trim_start().starts_with(['}', ')', ']']) {
+        depth = depth.saturating_sub(1);
+    }
+    let indentation = " ".repeat(depth * tab_size as usize);
+    if indentation == current_indentation {
+        return None;
+    }
+    Some(TextEdit {
+        range: Range {
+            start: Position { line, character: 0 },
+            end: Position { line, character: current_indentation.len() as u32 },
+        },
+        new_text: indentation,
+    })
+}
+
+/// Returns the number of brackets left open at the end of `text`, ignoring brackets in comments
+/// and string literals.
+fn bracket_depth(text: &str) -> usize {
+    let mut depth: usize = 0;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => depth = depth.saturating_sub(1),
+            '/' if chars.peek() == Some(&'/') => {
+                chars.find(|c| *c == '\n');
+            }
+            '"' | '\'' => {
+                while let Some(literal_char) = chars.next() {
+                    match literal_char {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '\n' => break,
+                        _ if literal_char == c => break,
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    depth
+}
+
+/// Returns the formatter config of the project of a file, from the `[tool.fmt]` table of the
+/// nearest project manifest. Falls back to the default config.
+fn formatter_config(db: &RootDatabase, file: FileId) -> FormatterConfig {
+    let FileLongId::OnDisk(file_path) = db.lookup_intern_file(file) else {
+        return FormatterConfig::default();
+    };
+    file_path
+        .ancestors()
+        .skip(1)
+        .take(MAX_CRATE_DETECTION_DEPTH)
+        .flat_map(|directory| MANIFEST_FILE_NAMES.map(|name| directory.join(name)))
+        .find(|manifest_path| manifest_path.is_file())
+        .and_then(|manifest_path| manifest_formatter_config(&manifest_path))
+        .unwrap_or_default()
+}
+
+/// The part of a project manifest holding the formatter config.
+#[derive(Deserialize)]
+struct ManifestTools {
+    #[serde(default)]
+    tool: Tools,
+}
+#[derive(Default, Deserialize)]
+struct Tools {
+    fmt: Option<FormatterConfig>,
+}
+
+/// Reads the formatter config from the `[tool.fmt]` table of a project manifest.
+fn manifest_formatter_config(manifest_path: &Path) -> Option<FormatterConfig> {
+    let content = std::fs::read_to_string(manifest_path).ok()?;
+    match toml::from_str::<ManifestTools>(&content) {
+        Ok(manifest) => manifest.tool.fmt,
+        Err(err) => {
+            warn!("Failed to read the formatter config from '{}': {err}", manifest_path.display());
+            None
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_defs::ids::{
+    FunctionWithBodyId, ImplFunctionId, LanguageElementId, LookupItemId, ModuleItemId,
+    TraitFunctionId,
+};
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::items::functions::{GenericFunctionId, ImplGenericFunctionId};
+use cairo_lang_semantic::resolve::{ResolvedConcreteItem, ResolvedGenericItem};
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{ast, TypedSyntaxNode};
+use cairo_lang_utils::Upcast;
+use lsp::request::GotoImplementationResponse;
+use lsp::{Location, Position};
+
+use crate::{get_node_and_lookup_items, get_uri, node_range};
+
+/// Returns the implementations of the trait function at a position.
+/// For a call whose impl is known after inference, returns the function of the chosen impl.
+/// Otherwise, for a usage or the declaration of a trait function, returns its functions in all the
+/// impls of the trait, in all the crates.
+pub fn goto_implementation(
+    db: &RootDatabase,
+    file: FileId,
+    position: Position,
+) -> Option<GotoImplementationResponse> {
+    let (node, lookup_items) = get_node_and_lookup_items(db, file, position)?;
+    let implementations = if node.kind(db) == SyntaxKind::TokenIdentifier {
+        let identifier = ast::TerminalIdentifier::from_syntax_node(db, node.parent()?);
+        called_function(db, &identifier, &lookup_items)
+            .map(|function| generic_function_implementations(db, function))
+            .or_else(|| referred_function_implementations(db, &identifier, &lookup_items))
+    } else {
+        None
+    };
+    let implementations = implementations.or_else(|| match lookup_items.first()? {
+        LookupItemId::TraitFunction(trait_function_id) => {
+            Some(trait_function_implementations(db, *trait_function_id))
+        }
+        _ => None,
+    })?;
+    let mut locations: Vec<Location> = implementations
+        .into_iter()
+        .filter_map(|impl_function_id| impl_function_location(db, impl_function_id))
+        .collect();
+    match locations.len() {
+        0 => None,
+        1 => Some(GotoImplementationResponse::Scalar(locations.remove(0))),
+        _ => Some(GotoImplementationResponse::Array(locations)),
+    }
+}
+
+/// Returns the impl function called by the call whose function name is the identifier, as inferred
+/// in the body of the enclosing function. Returns None if the identifier is not the name of a call
+/// to a trait function.
+fn called_function(
+    db: &RootDatabase,
+    identifier: &ast::TerminalIdentifier,
+    lookup_items: &[LookupItemId],
+) -> Option<ImplGenericFunctionId> {
+    let function_id = lookup_items.iter().find_map(|item| match item {
+        LookupItemId::ModuleItem(ModuleItemId::FreeFunction(free_function_id)) => {
+            Some(FunctionWithBodyId::Free(*free_function_id))
+        }
+        LookupItemId::ImplFunction(impl_function_id) => {
+            Some(FunctionWithBodyId::Impl(*impl_function_id))
+        }
+        LookupItemId::TraitFunction(trait_function_id) => {
+            Some(FunctionWithBodyId::Trait(*trait_function_id))
+        }
+        _ => None,
+    })?;
+    // The identifier is under `ExprFunctionCall > ExprPath > PathSegmentList > PathSegment*`, and a
+    // method call is further wrapped by an `ExprBinary`.
+    let mut path = identifier.as_syntax_node();
+    while path.kind(db) != SyntaxKind::ExprPath {
+        path = path.parent()?;
+    }
+    let call = path.parent().filter(|node| node.kind(db) == SyntaxKind::ExprFunctionCall)?;
+    let method_call = call.parent().filter(|node| node.kind(db) == SyntaxKind::ExprBinary);
+    let call_ptrs: Vec<_> =
+        [Some(call), method_call].into_iter().flatten().map(|node| node.stable_ptr()).collect();
+    let calls = db.function_body_calls(function_id).ok()?;
+    let (function, _) =
+        calls.iter().find(|(_, stable_ptr)| call_ptrs.contains(&stable_ptr.untyped()))?;
+    match function.get_concrete(db).generic_function {
+        GenericFunctionId::Impl(impl_generic_function) => Some(impl_generic_function),
+        GenericFunctionId::Free(_) | GenericFunctionId::Extern(_) => None,
+    }
+}
+
+/// Returns the implementations of the trait function the identifier refers to, if it refers to
+/// one.
+fn referred_function_implementations(
+    db: &RootDatabase,
+    identifier: &ast::TerminalIdentifier,
+    lookup_items: &[LookupItemId],
+) -> Option<Vec<ImplFunctionId>> {
+    lookup_items.iter().find_map(|lookup_item_id| {
+        if let Some(ResolvedConcreteItem::Function(function_id)) =
+            db.lookup_resolved_concrete_item_by_ptr(*lookup_item_id, identifier.stable_ptr())
+        {
+            return match function_id.get_concrete(db).generic_function {
+                GenericFunctionId::Impl(function) => {
+                    Some(generic_function_implementations(db, function))
+                }
+                GenericFunctionId::Free(_) | GenericFunctionId::Extern(_) => None,
+            };
+        }
+        match db.lookup_resolved_generic_item_by_ptr(*lookup_item_id, identifier.stable_ptr())? {
+            ResolvedGenericItem::GenericFunction(GenericFunctionId::Impl(function)) => {
+                Some(generic_function_implementations(db, function))
+            }
+            ResolvedGenericItem::TraitFunction(trait_function_id) => {
+                Some(trait_function_implementations(db, trait_function_id))
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Returns the implementations of a function of an impl - the function itself if the impl is
+/// known, or otherwise all the implementations of the trait function.
+fn generic_function_implementations(
+    db: &RootDatabase,
+    function: ImplGenericFunctionId,
+) -> Vec<ImplFunctionId> {
+    match function.impl_function(db) {
+        Ok(Some(impl_function_id)) => vec![impl_function_id],
+        _ => trait_function_implementations(db, function.function),
+    }
+}
+
+/// Returns the functions implementing a trait function in all the impls of its trait, in all the
+/// crates.
+fn trait_function_implementations(
+    db: &RootDatabase,
+    trait_function_id: TraitFunctionId,
+) -> Vec<ImplFunctionId> {
+    let trait_id = trait_function_id.trait_id(db);
+    let mut implementations = vec![];
+    for crate_id in db.crates() {
+        let index = db.impl_index_in_crate(crate_id);
+        for impl_def_id in index.impls_by_trait.get(&trait_id).into_iter().flatten() {
+            if let Ok(Some(impl_function_id)) =
+                db.impl_function_by_trait_function(*impl_def_id, trait_function_id)
+            {
+                implementations.push(impl_function_id);
+            }
+        }
+    }
+    implementations
+}
+
+/// Returns the location of an impl function.
+fn impl_function_location(db: &RootDatabase, impl_function_id: ImplFunctionId) -> Option<Location> {
+    let stable_ptr = impl_function_id.untyped_stable_ptr(db);
+    let node = stable_ptr.lookup(db.upcast());
+    Some(Location {
+        uri: get_uri(db, stable_ptr.file_id(db.upcast())),
+        range: node_range(db, &node)?,
+    })
+}
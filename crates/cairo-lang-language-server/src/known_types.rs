@@ -0,0 +1,201 @@
+//! The types of the local variables of functions as of their last successful analysis. Completions
+//! fall back to them while a function is mid-edit and the type of a variable is no longer inferred,
+//! e.g. when its initializer does not parse.
+
+use std::collections::HashMap;
+
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::{
+    FunctionWithBodyId, GenericTypeId, LanguageElementId, ModuleId, ModuleItemId,
+    TopLevelLanguageElementId,
+};
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::{ConcreteTypeId, GenericArgumentId, Pattern, TypeId, TypeLongId};
+use cairo_lang_utils::OptionFrom;
+
+/// The types of the local variables of a function, by their names, or None for variables whose
+/// type is not inferred.
+pub type FunctionVarTypes = Vec<(String, Option<TypeId>)>;
+
+#[derive(Default)]
+pub struct KnownTypes {
+    /// The generation of the database the types belong to. Types are interned in the database, so
+    /// they are dropped when it is replaced.
+    generation: u64,
+    /// The types of the variables of functions, by the paths of the functions. Function ids are
+    /// not used, as they change whenever the file is edited.
+    functions: HashMap<String, HashMap<String, TypeId>>,
+}
+
+impl KnownTypes {
+    /// Returns the generation of the database the types belong to.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Drops all the types, as the database they belong to is replaced.
+    pub fn reset(&mut self) {
+        self.generation += 1;
+        self.functions.clear();
+    }
+
+    /// Returns the last inferred type of a local variable of a function, as of the current version
+    /// of the code.
+    pub fn var_type(
+        &self,
+        db: &dyn SemanticGroup,
+        function_path: &str,
+        name: &str,
+    ) -> Option<TypeId> {
+        refresh_type(db, *self.functions.get(function_path)?.get(name)?)
+    }
+
+    /// Records the types of the variables of functions, computed with the database of the given
+    /// generation. Variables whose type is not inferred keep their last inferred type.
+    pub fn record(&mut self, generation: u64, functions: Vec<(String, FunctionVarTypes)>) {
+        if generation != self.generation {
+            return;
+        }
+        for (function_path, var_types) in functions {
+            let previous = self.functions.remove(&function_path).unwrap_or_default();
+            let types = var_types
+                .into_iter()
+                .filter_map(|(name, ty)| {
+                    let ty = ty.or_else(|| previous.get(&name).copied())?;
+                    Some((name, ty))
+                })
+                .collect();
+            self.functions.insert(function_path, types);
+        }
+    }
+}
+
+/// Returns the types of the local variables of the functions in a file, by the paths of the
+/// functions.
+pub fn file_var_types(db: &dyn SemanticGroup, file: FileId) -> Vec<(String, FunctionVarTypes)> {
+    let mut modules = db.file_modules(file).map(|modules| (*modules).clone()).unwrap_or_default();
+    let mut i = 0;
+    while i < modules.len() {
+        for submodule_id in db.module_submodules_ids(modules[i]).unwrap_or_default().iter() {
+            let submodule_id = ModuleId::Submodule(*submodule_id);
+            // Submodules in other files are not part of the file.
+            if db.module_main_file(submodule_id) == Ok(file) {
+                modules.push(submodule_id);
+            }
+        }
+        i += 1;
+    }
+
+    let mut function_ids = vec![];
+    for module_id in modules {
+        function_ids.extend(
+            db.module_free_functions_ids(module_id)
+                .unwrap_or_default()
+                .iter()
+                .map(|free_function_id| FunctionWithBodyId::Free(*free_function_id)),
+        );
+        for impl_def_id in db.module_impls_ids(module_id).unwrap_or_default().iter() {
+            function_ids.extend(
+                db.impl_functions(*impl_def_id)
+                    .unwrap_or_default()
+                    .values()
+                    .map(|impl_function_id| FunctionWithBodyId::Impl(*impl_function_id)),
+            );
+        }
+    }
+
+    function_ids
+        .into_iter()
+        .filter_map(|function_id| {
+            let body = db.function_body(function_id).ok()?;
+            let var_types = body
+                .patterns
+                .iter()
+                .filter_map(|(_, pattern)| match pattern {
+                    Pattern::Variable(var) => Some((
+                        var.name.to_string(),
+                        Some(var.var.ty).filter(|ty| is_inferred(db, *ty)),
+                    )),
+                    _ => None,
+                })
+                .collect();
+            Some((function_path(db.upcast(), function_id), var_types))
+        })
+        .collect()
+}
+
+/// Returns the path of a function, e.g. `crate::module::MyImpl::foo`.
+pub fn function_path(db: &dyn DefsGroup, function_id: FunctionWithBodyId) -> String {
+    match function_id {
+        FunctionWithBodyId::Free(free_function_id) => free_function_id.full_path(db),
+        FunctionWithBodyId::Impl(impl_function_id) => format!(
+            "{}::{}",
+            impl_function_id.parent_module(db).full_path(db),
+            impl_function_id.full_path(db)
+        ),
+        FunctionWithBodyId::Trait(trait_function_id) => format!(
+            "{}::{}",
+            trait_function_id.parent_module(db).full_path(db),
+            trait_function_id.full_path(db)
+        ),
+    }
+}
+
+/// Returns a type as of the current version of the code. The ids of the items of a file change
+/// whenever the file is edited, so a type recorded before an edit may refer to items that are gone.
+/// Returns None if an item of the type no longer exists.
+fn refresh_type(db: &dyn SemanticGroup, ty: TypeId) -> Option<TypeId> {
+    let long_ty = match db.lookup_intern_type(ty) {
+        TypeLongId::Concrete(concrete_type_id) => {
+            let generic_type = refresh_generic_type(db, concrete_type_id.generic_type(db))?;
+            let generic_args = concrete_type_id
+                .generic_args(db)
+                .into_iter()
+                .map(|generic_arg| match generic_arg {
+                    GenericArgumentId::Type(ty) => {
+                        refresh_type(db, ty).map(GenericArgumentId::Type)
+                    }
+                    _ => Some(generic_arg),
+                })
+                .collect::<Option<_>>()?;
+            TypeLongId::Concrete(ConcreteTypeId::new(db, generic_type, generic_args))
+        }
+        TypeLongId::Tuple(tys) => TypeLongId::Tuple(
+            tys.into_iter().map(|ty| refresh_type(db, ty)).collect::<Option<_>>()?,
+        ),
+        TypeLongId::Snapshot(ty) => TypeLongId::Snapshot(refresh_type(db, ty)?),
+        long_ty => long_ty,
+    };
+    Some(db.intern_type(long_ty))
+}
+
+/// Returns the generic type of the same name in the same module, as of the current version of the
+/// code.
+fn refresh_generic_type(
+    db: &dyn SemanticGroup,
+    generic_type: GenericTypeId,
+) -> Option<GenericTypeId> {
+    let defs_db = db.upcast();
+    let module_id = refresh_module(db, generic_type.parent_module(defs_db))?;
+    let item = db.module_item_by_name(module_id, generic_type.name(defs_db)).ok()??;
+    GenericTypeId::option_from(item)
+}
+
+/// Returns the module of the same path, as of the current version of the code.
+fn refresh_module(db: &dyn SemanticGroup, module_id: ModuleId) -> Option<ModuleId> {
+    let ModuleId::Submodule(submodule_id) = module_id else {
+        return Some(module_id);
+    };
+    let defs_db = db.upcast();
+    let parent_module_id = refresh_module(db, submodule_id.parent_module(defs_db))?;
+    match db.module_item_by_name(parent_module_id, submodule_id.name(defs_db)).ok()?? {
+        ModuleItemId::Submodule(submodule_id) => Some(ModuleId::Submodule(submodule_id)),
+        _ => None,
+    }
+}
+
+/// Returns whether a type is inferred - it is neither missing nor an inference variable.
+pub fn is_inferred(db: &dyn SemanticGroup, ty: TypeId) -> bool {
+    !matches!(db.lookup_intern_type(ty), TypeLongId::Missing(_) | TypeLongId::Var(_))
+}
@@ -3,10 +3,12 @@
 //! Implements the LSP protocol over stdin/out.
 
 use std::collections::{HashMap, HashSet};
+use std::future::Future;
 use std::panic::AssertUnwindSafe;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
 
 use anyhow::{bail, Error};
 use cairo_lang_compiler::db::RootDatabase;
@@ -26,56 +28,132 @@ use cairo_lang_filesystem::db::{
     init_dev_corelib, AsFilesGroupMut, CrateConfiguration, Edition, FilesGroup, FilesGroupEx,
     PrivRawFileContentQuery,
 };
-use cairo_lang_filesystem::detect::detect_corelib;
 use cairo_lang_filesystem::ids::{CrateId, CrateLongId, Directory, FileId, FileLongId};
 use cairo_lang_filesystem::span::{FileSummary, TextOffset, TextPosition, TextWidth};
-use cairo_lang_formatter::{get_formatted_file, FormatterConfig};
 use cairo_lang_lowering::db::LoweringGroup;
 use cairo_lang_lowering::diagnostic::LoweringDiagnostic;
 use cairo_lang_parser::db::ParserGroup;
 use cairo_lang_parser::ParserDiagnostic;
 use cairo_lang_project::ProjectConfig;
 use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::diagnostic::{NotFoundItemType, SemanticDiagnostics};
+use cairo_lang_semantic::expr::inference::InferenceId;
 use cairo_lang_semantic::items::function_with_body::SemanticExprLookup;
 use cairo_lang_semantic::items::functions::GenericFunctionId;
 use cairo_lang_semantic::items::imp::ImplId;
 use cairo_lang_semantic::items::us::get_use_segments;
-use cairo_lang_semantic::resolve::{AsSegments, ResolvedConcreteItem, ResolvedGenericItem};
+use cairo_lang_semantic::plugin::PluginSuite;
+use cairo_lang_semantic::resolve::{
+    AsSegments, ResolvedConcreteItem, ResolvedGenericItem, Resolver,
+};
 use cairo_lang_semantic::{SemanticDiagnostic, TypeLongId};
 use cairo_lang_starknet::starknet_plugin_suite;
+use cairo_lang_starknet::unused_externals::UnusedExternalsAnalyzer;
 use cairo_lang_syntax::node::ast::PathSegment;
 use cairo_lang_syntax::node::db::SyntaxGroup;
 use cairo_lang_syntax::node::helpers::GetIdentifier;
 use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
 use cairo_lang_syntax::node::kind::SyntaxKind;
 use cairo_lang_syntax::node::utils::is_grandparent_of_kind;
-use cairo_lang_syntax::node::{ast, SyntaxNode, TypedSyntaxNode};
+use cairo_lang_syntax::node::{ast, SyntaxNode, Terminal, TypedSyntaxNode};
 use cairo_lang_test_plugin::test_plugin_suite;
 use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
 use cairo_lang_utils::{try_extract_matches, OptionHelper, Upcast};
-use log::warn;
+use itertools::Itertools;
 use lsp::notification::Notification;
-use salsa::InternKey;
+use salsa::{Database, InternKey};
 use semantic_highlighting::token_kind::SemanticTokenKind;
 use semantic_highlighting::SemanticTokensTraverser;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use smol_str::SmolStr;
 use tower_lsp::jsonrpc::{Error as LSPError, Result as LSPResult};
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer, LspService, Server};
+use tracing::{debug, error, info, info_span, trace, warn, Instrument};
 use vfs::{ProvideVirtualFileRequest, ProvideVirtualFileResponse};
 
-use crate::completions::{colon_colon_completions, dot_completions, generic_completions};
-use crate::scarb_service::{is_scarb_manifest_path, ScarbService};
+use crate::analysis_cache::{file_inputs_hash, AnalysisCache};
+use crate::cancellation::CancellationToken;
+use crate::call_hierarchy::{incoming_calls, outgoing_calls, prepare_call_hierarchy};
+use crate::code_actions::{organize_imports, quick_fixes};
+use crate::code_lens::{
+    estimate_function_gas, gas_estimate_code_lenses, resolve_gas_estimate_code_lens,
+    test_code_lenses, GasEstimateLensData, GasEstimates, SHOW_GAS_ESTIMATE_COMMAND,
+};
+use crate::completions::{
+    attribute_completions, colon_colon_completions, derive_completions, dot_completions,
+    generic_completions,
+};
+use crate::corelib::{corelib_missing_message, find_corelib, CORELIB_PATH_CONFIG_SECTION};
+use crate::document_highlight::document_highlights;
+use crate::expand_macro::{expand_macro, ExpandMacroRequest, ExpandMacroResponse};
+use crate::folding_range::folding_ranges;
+use crate::formatting::{formatting_edits, on_type_formatting_edits};
+use crate::goto_implementation::goto_implementation;
+use crate::known_types::{file_var_types, KnownTypes};
+use crate::linked_editing_range::linked_editing_ranges;
+use crate::logging::{configure_logging, init_logging};
+use crate::manifest::read_manifest_project;
+use crate::refactorings::{extract_variable, inline_variable};
+use crate::run_test::{
+    compile_test, run_test, RunTestParams, TestRunFinished, TestRunFinishedParams, TestRunStarted,
+    TestRunStartedParams, RUN_TEST_COMMAND,
+};
+use crate::scarb_service::ScarbService;
+use crate::server_status::{resident_memory_bytes, PendingTask, ServerStatusResponse};
+use crate::telemetry::{telemetry_sink_from_env, RequestMetrics, ResultCount, TelemetrySink};
+use crate::type_hierarchy::{prepare_type_hierarchy, subtypes, supertypes};
+use crate::unused_imports::UnusedImportsAnalyzer;
+use crate::view_compiled::{
+    view_compiled, CompiledCodeKind, ViewCompiledRequest, ViewCompiledResponse,
+};
+use crate::watched_files::{
+    crate_roots, is_project_manifest_path, project_files_in_directory, watched_files_registration,
+    watched_files_unregistration,
+};
 
+mod analysis_cache;
+mod call_hierarchy;
+mod code_actions;
+mod code_lens;
+mod corelib;
+mod document_highlight;
+mod folding_range;
+mod formatting;
+mod goto_implementation;
+mod known_types;
+mod linked_editing_range;
+mod logging;
+mod manifest;
+mod refactorings;
 mod scarb_service;
 mod semantic_highlighting;
+mod type_hierarchy;
+mod unused_imports;
+mod watched_files;
 
+pub mod cancellation;
 pub mod completions;
+pub mod expand_macro;
+pub mod run_test;
+pub mod server_status;
+pub mod telemetry;
 pub mod vfs;
+pub mod view_compiled;
+
+#[cfg(test)]
+#[path = "lib_test.rs"]
+mod test;
+#[cfg(test)]
+mod test_utils;
 
 const MAX_CRATE_DETECTION_DEPTH: usize = 20;
 const DEFAULT_CAIRO_LSP_DB_REPLACE_INTERVAL: u64 = 300;
+/// The client configuration key enabling the diagnostics of unused contract external functions.
+const UNUSED_EXTERNALS_CONFIG_SECTION: &str = "cairo1.enableUnusedExternalsDiagnostics";
+/// The time to wait after a document change before refreshing the diagnostics.
+const DIAGNOSTICS_DEBOUNCE: Duration = Duration::from_millis(200);
 
 pub async fn serve_language_service() {
     #[cfg(feature = "runtime-agnostic")]
@@ -85,24 +163,42 @@ pub async fn serve_language_service() {
     #[cfg(feature = "runtime-agnostic")]
     let (stdin, stdout) = (stdin.compat(), stdout.compat_write());
 
-    let db = configured_db();
+    init_logging();
+    let db = configured_db(false);
 
     let (service, socket) = LspService::build(|client| Backend::new(client, db))
         .custom_method("vfs/provide", Backend::vfs_provide)
+        .custom_method("cairo/expandMacro", Backend::expand_macro)
+        .custom_method("cairo/viewSierra", Backend::view_sierra)
+        .custom_method("cairo/viewCasm", Backend::view_casm)
+        .custom_method("cairo/serverStatus", Backend::server_status)
         .finish();
     Server::new(stdin, stdout, socket).serve(service).await;
 }
 
-fn configured_db() -> RootDatabase {
+/// Returns a database with the plugins of the language server.
+fn configured_db(unused_externals_diagnostics: bool) -> RootDatabase {
     let db = RootDatabase::builder()
         .with_cfg(CfgSet::from_iter([Cfg::name("test")]))
-        .with_plugin_suite(starknet_plugin_suite())
-        .with_plugin_suite(test_plugin_suite())
+        .with_plugin_suite(language_server_plugin_suite(unused_externals_diagnostics))
         .build()
         .expect("Failed to initialize Cairo compiler database.");
     db
 }
 
+/// Returns the plugins of the language server. The analysis of unused contract external functions
+/// walks the calls of all the tests of the project, so it only runs if enabled in the client
+/// configuration.
+fn language_server_plugin_suite(unused_externals_diagnostics: bool) -> PluginSuite {
+    let mut suite = starknet_plugin_suite();
+    if unused_externals_diagnostics {
+        suite.add_analyzer_plugin::<UnusedExternalsAnalyzer>();
+    }
+    suite.add(test_plugin_suite());
+    suite.add_analyzer_plugin::<UnusedImportsAnalyzer>();
+    suite
+}
+
 #[derive(Clone, Default, PartialEq, Eq)]
 pub struct FileDiagnostics {
     pub parser: Diagnostics<ParserDiagnostic>,
@@ -114,6 +210,8 @@ impl std::panic::UnwindSafe for FileDiagnostics {}
 pub struct State {
     pub file_diagnostics: HashMap<FileId, FileDiagnostics>,
     pub open_files: HashSet<FileId>,
+    /// The last opened or changed file, whose diagnostics are refreshed first.
+    pub focused_file: Option<FileId>,
 }
 impl std::panic::UnwindSafe for State {}
 
@@ -134,8 +232,24 @@ impl NotificationService {
     pub async fn notify_scarb_missing(&self) {
         self.client.send_notification::<ScarbPathMissing>(ScarbPathMissingParams {}).await;
     }
+    pub async fn notify_corelib_missing(&self) {
+        self.client.show_message(MessageType::ERROR, corelib_missing_message()).await;
+    }
 }
-pub struct Backend {
+/// The language server. Cheap to clone, sharing its state with the clones, so background tasks can
+/// run on it.
+#[derive(Clone)]
+pub struct Backend(Arc<BackendState>);
+impl std::ops::Deref for Backend {
+    type Target = BackendState;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// The state of the language server.
+pub struct BackendState {
     pub client: Client,
     // TODO(spapini): Remove this once we support ParallelDatabase.
     // State mutex should only be taken after db mutex is taken, to avoid deadlocks.
@@ -145,14 +259,50 @@ pub struct Backend {
     pub notification: NotificationService,
     last_replace: tokio::sync::Mutex<SystemTime>,
     db_replace_interval: Duration,
+    /// Counts the document changes, to debounce the diagnostics refreshes.
+    diagnostics_generation: AtomicU64,
+    /// The task waiting for the document changes to settle before refreshing the diagnostics.
+    debounced_diagnostics_task: std::sync::Mutex<Option<tokio::task::JoinHandle<()>>>,
+    /// Whether the client pulls the diagnostics of the open files.
+    pull_diagnostics: AtomicBool,
+    /// Whether the client supports `workspace/diagnostic/refresh` requests.
+    diagnostics_refresh_support: AtomicBool,
+    /// The destination of the request metrics, if telemetry is enabled.
+    telemetry: Option<Arc<dyn TelemetrySink>>,
+    /// Whether the user was told that the corelib is missing, since it was last found.
+    corelib_missing_reported: AtomicBool,
+    /// Whether the diagnostics of unused contract external functions are enabled in the client
+    /// configuration.
+    unused_externals_diagnostics: AtomicBool,
+    /// The crate roots whose files are watched, in addition to the files of the workspace.
+    watched_crate_roots: tokio::sync::Mutex<Vec<PathBuf>>,
+    /// The cache of the diagnostics of the project files, if enabled.
+    analysis_cache: Option<std::sync::Mutex<AnalysisCache>>,
+    /// Whether the diagnostics in the analysis cache were published, which is done once.
+    cached_diagnostics_published: AtomicBool,
+    /// The number of requests and background tasks waiting for the database or running on it.
+    pending_tasks: AtomicUsize,
+    /// The last inferred types of the variables of the functions in the open files.
+    known_types: Arc<std::sync::Mutex<KnownTypes>>,
+    /// The gas estimates of the contract functions, shown by the code lenses.
+    gas_estimates: Arc<std::sync::Mutex<GasEstimates>>,
 }
 fn from_pos(pos: TextPosition) -> Position {
     Position { line: pos.line as u32, character: pos.col as u32 }
 }
+/// Returns the range of a syntax node, without its trivia.
+fn node_range(db: &RootDatabase, node: &SyntaxNode) -> Option<Range> {
+    let file = node.stable_ptr().file_id(db.upcast());
+    let span = node.span_without_trivia(db.upcast());
+    Some(Range {
+        start: from_pos(span.start.position_in_file(db.upcast(), file)?),
+        end: from_pos(span.end.position_in_file(db.upcast(), file)?),
+    })
+}
 impl Backend {
     pub fn new(client: Client, db: RootDatabase) -> Self {
         let notification = NotificationService::new(client.clone());
-        Self {
+        Self(Arc::new(BackendState {
             client,
             db_mutex: db.into(),
             notification: notification.clone(),
@@ -165,22 +315,137 @@ impl Backend {
                     .and_then(|value| value.parse::<u64>().ok())
                     .unwrap_or(DEFAULT_CAIRO_LSP_DB_REPLACE_INTERVAL),
             ),
-        }
+            diagnostics_generation: AtomicU64::new(0),
+            debounced_diagnostics_task: Default::default(),
+            pull_diagnostics: AtomicBool::new(false),
+            diagnostics_refresh_support: AtomicBool::new(false),
+            telemetry: telemetry_sink_from_env(),
+            corelib_missing_reported: AtomicBool::new(false),
+            unused_externals_diagnostics: AtomicBool::new(false),
+            watched_crate_roots: Default::default(),
+            analysis_cache: AnalysisCache::from_env().map(Into::into),
+            cached_diagnostics_published: AtomicBool::new(false),
+            pending_tasks: AtomicUsize::new(0),
+            known_types: Default::default(),
+            gas_estimates: Default::default(),
+        }))
     }
 
-    /// Runs a function with a database snapshot.
-    /// Catches panics and returns Err.
+    /// Sets the destination of the request metrics, enabling the telemetry.
+    /// Must be called before the backend is cloned.
+    pub fn with_telemetry_sink(mut self, sink: Arc<dyn TelemetrySink>) -> Self {
+        Arc::get_mut(&mut self.0).expect("The backend is already shared.").telemetry = Some(sink);
+        self
+    }
+
+    /// Runs a function with a database snapshot, as [Self::with_db_cancelable], for functions that
+    /// do not poll the cancellation of the request.
     async fn with_db<F, T>(&self, f: F) -> LSPResult<T>
     where
-        F: FnOnce(&RootDatabase) -> T + std::panic::UnwindSafe,
+        F: FnOnce(&RootDatabase) -> T + std::panic::UnwindSafe + Send + 'static,
+        T: Send + 'static,
     {
+        self.with_db_cancelable(|db, _cancellation| f(db)).await
+    }
+
+    /// Runs a function with a database snapshot.
+    /// The function runs on a blocking thread, so the server keeps handling incoming messages
+    /// meanwhile. A `$/cancelRequest` drops the request, which cancels the token passed to the
+    /// function, and a change to the database makes the running function observe a canceled
+    /// revision - long-running functions should poll both, and stop early.
+    /// Catches panics and returns Err. Returns a `ContentModified` error if the database was about
+    /// to change when the function finished, as its result is stale.
+    async fn with_db_cancelable<F, T>(&self, f: F) -> LSPResult<T>
+    where
+        F: FnOnce(&RootDatabase, &CancellationToken) -> T + std::panic::UnwindSafe + Send + 'static,
+        T: Send + 'static,
+    {
+        let _pending_task = PendingTask::new(&self.pending_tasks);
+        let cancellation = CancellationToken::default();
+        let cancel_on_drop = cancellation.drop_guard();
         let db_mut = self.db_mut().await;
         let db = db_mut.snapshot();
         drop(db_mut);
-        std::panic::catch_unwind(AssertUnwindSafe(|| f(&db))).map_err(|_| {
-            eprintln!("Caught panic in LSP worker thread.");
-            LSPError::internal_error()
+        // The function runs in the span of the request, if any.
+        let span = tracing::Span::current();
+        let res = tokio::task::spawn_blocking(move || {
+            let _entered = span.enter();
+            if cancellation.is_canceled() {
+                return Err(LSPError::request_cancelled());
+            }
+            let res = std::panic::catch_unwind(AssertUnwindSafe(|| f(&db, &cancellation)))
+                .map_err(|_| {
+                    error!("Caught panic in LSP worker thread.");
+                    LSPError::internal_error()
+                })?;
+            if db.salsa_runtime().is_current_revision_canceled() {
+                return Err(LSPError::content_modified());
+            }
+            Ok(res)
         })
+        .await
+        .map_err(|_| LSPError::internal_error())?;
+        cancel_on_drop.disarm();
+        res
+    }
+
+    /// Runs a function with a database snapshot, as [Self::with_db], handling the given request.
+    /// See [Self::measured].
+    async fn with_db_measured<F, T>(&self, request: &'static str, f: F) -> LSPResult<T>
+    where
+        F: FnOnce(&RootDatabase) -> T + std::panic::UnwindSafe + Send + 'static,
+        T: ResultCount + Send + 'static,
+    {
+        self.measured(request, self.with_db(f)).await
+    }
+
+    /// Handles the given request by awaiting its result. The request is handled in a span named
+    /// after its method. Records the metrics of the request if telemetry is enabled.
+    async fn measured<T: ResultCount>(
+        &self,
+        request: &'static str,
+        res: impl Future<Output = LSPResult<T>>,
+    ) -> LSPResult<T> {
+        let start = Instant::now();
+        let res = res.instrument(info_span!("request", method = request)).await;
+        if let Some(telemetry) = &self.telemetry {
+            telemetry.record(&RequestMetrics {
+                request,
+                latency_ms: start.elapsed().as_secs_f64() * 1000.0,
+                succeeded: res.is_ok(),
+                result_count: res.as_ref().ok().map(ResultCount::result_count),
+            });
+        }
+        res
+    }
+
+    /// Refreshes the code lenses if the crate of any document with gas estimate lenses changed
+    /// since they were computed.
+    async fn refresh_stale_code_lenses(&self) {
+        let gas_estimates = self.gas_estimates.clone();
+        let Ok(changed) = self
+            .with_db(move |db| {
+                let documents: Vec<_> = gas_estimates
+                    .lock()
+                    .unwrap()
+                    .documents()
+                    .map(|(uri, crate_hash)| (uri.clone(), crate_hash.clone()))
+                    .collect();
+                let mut crate_hashes = HashMap::new();
+                documents.into_iter().any(|(uri, crate_hash)| {
+                    let file = file(db, uri);
+                    file_inputs_hash(db, file, &mut crate_hashes).as_ref() != Some(&crate_hash)
+                })
+            })
+            .await
+        else {
+            return;
+        };
+        if changed {
+            if let Err(err) = self.client.code_lens_refresh().await {
+                warn!("Failed to refresh code lenses: {err:?}");
+            }
+        }
     }
 
     /// Locks and gets a database instance.
@@ -192,78 +457,223 @@ impl Backend {
     // client.send_notification::<UpdateVirtualFile> call.
 
     // Refresh diagnostics and send diffs to client.
+    // Files are handled by priority - the focused file first, then the other open files, and the
+    // rest of the project files last. Stops early if a document changed meanwhile, as a debounced
+    // refresh follows the change.
+    #[tracing::instrument(skip_all)]
     async fn refresh_diagnostics(&self) -> LSPResult<()> {
-        let real_state = self.state_mutex.lock().await;
-        let state = real_state.clone();
-        drop(real_state);
-        let (state, res) = self
-            .with_db(|db| {
-                let mut state = state;
-                let mut res = vec![];
-                // Get all files. Try to go over open files first.
-                let mut files_set: OrderedHashSet<_> = state.open_files.iter().copied().collect();
+        let generation = self.diagnostics_generation.load(Ordering::SeqCst);
+        let state = self.state_mutex.lock().await.clone();
+        // With pull diagnostics the client requests the diagnostics of the open files itself.
+        let pull_diagnostics = self.pull_diagnostics.load(Ordering::SeqCst);
+        let open_files = state.open_files.clone();
+        let batches = self
+            .with_db(move |db| {
+                let mut open_files = state.open_files.iter().copied().collect_vec();
+                open_files.sort_by_key(|file_id| Some(*file_id) != state.focused_file);
+                let mut background_files = OrderedHashSet::<FileId>::default();
                 for crate_id in db.crates() {
                     for module_id in db.crate_modules(crate_id).iter() {
                         for file_id in
                             db.module_files(*module_id).unwrap_or_default().iter().copied()
                         {
-                            files_set.insert(file_id);
+                            if !state.open_files.contains(&file_id) {
+                                background_files.insert(file_id);
+                            }
                         }
                     }
                 }
+                let (focused_files, other_open_files) = if pull_diagnostics {
+                    (vec![], vec![])
+                } else {
+                    let other_open_files =
+                        open_files.split_off(state.focused_file.is_some() as usize);
+                    (open_files, other_open_files)
+                };
+                [focused_files, other_open_files, background_files.into_iter().collect()]
+            })
+            .await?;
 
-                // Get all diagnostics.
-                for file_id in files_set.iter().copied() {
-                    let uri = get_uri(db, file_id);
-                    let new_file_diagnostics = FileDiagnostics {
-                        parser: db.file_syntax_diagnostics(file_id),
-                        semantic: db.file_semantic_diagnostics(file_id).unwrap_or_default(),
-                        lowering: db.file_lowering_diagnostics(file_id).unwrap_or_default(),
-                    };
+        let pushed_files: HashSet<_> = batches.iter().flatten().copied().collect();
+        for files in batches {
+            if self.diagnostics_generation.load(Ordering::SeqCst) != generation {
+                return Ok(());
+            }
+            self.push_diagnostics(files).await?;
+        }
+        self.record_known_types(open_files).await?;
+
+        // Clear old diagnostics.
+        let old_files = self
+            .state_mutex
+            .lock()
+            .await
+            .file_diagnostics
+            .keys()
+            .copied()
+            .filter(|file_id| !pushed_files.contains(file_id))
+            .collect_vec();
+        let (old_files, uris) = self
+            .with_db(move |db| {
+                let uris = old_files.iter().map(|file_id| get_uri(db, *file_id)).collect_vec();
+                (old_files, uris)
+            })
+            .await?;
+        let mut state = self.state_mutex.lock().await;
+        for file_id in &old_files {
+            state.file_diagnostics.remove(file_id);
+        }
+        drop(state);
+        for uri in uris {
+            self.client.publish_diagnostics(uri, Vec::new(), None).await
+        }
+
+        // After handling of all diagnostics attempting to swap the database to reduce memory
+        // consumption.
+        self.maybe_sweep_database().await;
+
+        Ok(())
+    }
+
+    /// Records the types of the variables of the functions in the given files, for completions to
+    /// fall back to while the files are edited.
+    async fn record_known_types(&self, files: impl IntoIterator<Item = FileId>) -> LSPResult<()> {
+        let generation = self.known_types.lock().unwrap().generation();
+        let files = files.into_iter().collect_vec();
+        let functions = self
+            .with_db(move |db| {
+                files.into_iter().flat_map(|file_id| file_var_types(db, file_id)).collect_vec()
+            })
+            .await?;
+        self.known_types.lock().unwrap().record(generation, functions);
+        Ok(())
+    }
+
+    /// Computes the diagnostics of the given files, and publishes the ones that changed.
+    async fn push_diagnostics(&self, files: Vec<FileId>) -> LSPResult<()> {
+        if files.is_empty() {
+            return Ok(());
+        }
+        let state = self.state_mutex.lock().await.clone();
+        let cache_enabled = self.analysis_cache.is_some();
+        let (state, res, cached) = self
+            .with_db(move |db| {
+                let mut state = state;
+                let mut res = vec![];
+                let mut cached = vec![];
+                let mut crate_hashes = HashMap::new();
+                for file_id in files {
+                    // The diagnostics are stale if the database is about to change - stop early and
+                    // let the refresh following the change compute them.
+                    if db.salsa_runtime().is_current_revision_canceled() {
+                        break;
+                    }
+                    let new_file_diagnostics = file_diagnostics(db, file_id);
                     // Since we are using Arcs, this comparison should be efficient.
                     if let Some(old_file_diagnostics) = state.file_diagnostics.get(&file_id) {
                         if old_file_diagnostics == &new_file_diagnostics {
                             continue;
                         }
                     }
-                    let mut diags = Vec::new();
-                    get_diagnostics(db.upcast(), &mut diags, &new_file_diagnostics.parser);
-                    get_diagnostics(db.upcast(), &mut diags, &new_file_diagnostics.semantic);
-                    get_diagnostics(db.upcast(), &mut diags, &new_file_diagnostics.lowering);
+                    let diags = lsp_diagnostics(db, &new_file_diagnostics);
                     state.file_diagnostics.insert(file_id, new_file_diagnostics);
-
-                    res.push((uri, diags));
-                }
-
-                // Clear old diagnostics.
-                let old_files: Vec<_> = state.file_diagnostics.keys().copied().collect();
-                for file_id in old_files {
-                    if files_set.contains(&file_id) {
-                        continue;
+                    if let (true, FileLongId::OnDisk(path)) =
+                        (cache_enabled, db.lookup_intern_file(file_id))
+                    {
+                        if let Some(inputs_hash) =
+                            file_inputs_hash(db, file_id, &mut crate_hashes)
+                        {
+                            cached.push((path, inputs_hash, diags.clone()));
+                        }
                     }
-                    state.file_diagnostics.remove(&file_id);
-                    let uri = get_uri(db, file_id);
-                    res.push((uri, Vec::new()));
+                    res.push((get_uri(db, file_id), diags));
                 }
-
-                (state, res)
+                (state, res, cached)
             })
             .await?;
-        let mut real_state = self.state_mutex.lock().await;
-        *real_state = state;
-        drop(real_state);
+        self.state_mutex.lock().await.file_diagnostics = state.file_diagnostics;
+        if let Some(analysis_cache) = &self.analysis_cache {
+            let mut analysis_cache = analysis_cache.lock().unwrap();
+            for (path, inputs_hash, diags) in cached {
+                analysis_cache.insert(path, inputs_hash, diags);
+            }
+        }
 
         for (uri, diags) in res {
             self.client.publish_diagnostics(uri, diags, None).await
         }
-        // After handling of all diagnostics attempting to swap the database to reduce memory
-        // consumption.
-        self.maybe_sweep_database().await;
-
         Ok(())
     }
 
+    /// Publishes the diagnostics cached in a previous session for the files whose crate inputs did
+    /// not change since, until the analysis of the project computes them again.
+    /// Done once, when the first document is opened.
+    async fn publish_cached_diagnostics(&self) {
+        let Some(analysis_cache) = &self.analysis_cache else {
+            return;
+        };
+        if self.pull_diagnostics.load(Ordering::SeqCst)
+            || self.cached_diagnostics_published.swap(true, Ordering::SeqCst)
+        {
+            return;
+        }
+        let paths = analysis_cache.lock().unwrap().paths();
+        let db = self.db_mut().await;
+        let mut cached_diagnostics = vec![];
+        let mut crate_hashes = HashMap::new();
+        for path in paths {
+            let Ok(uri) = Url::from_file_path(&path) else {
+                continue;
+            };
+            let file_id = FileId::new(&*db, path.clone());
+            let Some(inputs_hash) = file_inputs_hash(&*db, file_id, &mut crate_hashes) else {
+                continue;
+            };
+            if let Some(diags) = analysis_cache.lock().unwrap().diagnostics(&path, &inputs_hash) {
+                cached_diagnostics.push((uri, diags.clone()));
+            }
+        }
+        drop(db);
+        for (uri, diags) in cached_diagnostics {
+            self.client.publish_diagnostics(uri, diags, None).await
+        }
+    }
+
+    /// Writes the analysis cache to disk, if enabled.
+    fn save_analysis_cache(&self) {
+        if let Some(analysis_cache) = &self.analysis_cache {
+            analysis_cache.lock().unwrap().save();
+        }
+    }
+
+    /// Schedules a refresh of the diagnostics in the background, once no other document change
+    /// arrives within [DIAGNOSTICS_DEBOUNCE]. A later change cancels the scheduled refresh, and
+    /// makes a running refresh stop early, as the refresh following that change takes over.
+    fn refresh_diagnostics_debounced(&self) {
+        self.diagnostics_generation.fetch_add(1, Ordering::SeqCst);
+        let backend = self.clone();
+        let task = tokio::spawn(async move {
+            tokio::time::sleep(DIAGNOSTICS_DEBOUNCE).await;
+            // The refresh runs in its own task, so canceling this task does not interrupt it
+            // halfway through publishing the diagnostics.
+            tokio::spawn(async move {
+                backend.refresh_diagnostics().await.ok();
+                if backend.pull_diagnostics.load(Ordering::SeqCst)
+                    && backend.diagnostics_refresh_support.load(Ordering::SeqCst)
+                {
+                    // The client pulls the diagnostics of the open files, so it has to be told
+                    // that they changed.
+                    backend.client.workspace_diagnostic_refresh().await.ok();
+                }
+            });
+        });
+        if let Some(previous_task) = self.debounced_diagnostics_task.lock().unwrap().replace(task) {
+            previous_task.abort();
+        }
+    }
+
     /// Checks if enough time passed since last db swap, and if so, swaps the database.
+    #[tracing::instrument(skip_all)]
     async fn maybe_sweep_database(&self) {
         let Ok(mut last_replace) = self.last_replace.try_lock() else {
             // Another thread is already swapping the database.
@@ -273,14 +683,17 @@ impl Backend {
             // Not enough time passed since last swap.
             return;
         }
-        eprintln!("DB swap - scheduled.");
+        debug!("DB swap - scheduled.");
         let mut db = self.db_mut().await;
-        eprintln!("DB swap - starting.");
-        let mut new_db = configured_db();
+        debug!("DB swap - starting.");
+        let mut new_db = configured_db(self.unused_externals_diagnostics.load(Ordering::Relaxed));
         new_db.set_file_overrides(db.file_overrides());
         *db = new_db;
+        self.known_types.lock().unwrap().reset();
         *last_replace = SystemTime::now();
-        eprintln!("DB swap - done.");
+        info!("DB swap - done.");
+        drop(db);
+        self.save_analysis_cache();
     }
 
     pub async fn vfs_provide(
@@ -294,41 +707,90 @@ impl Backend {
         .await
     }
 
-    /// Get corelib path fallback from the client configuration.
+    pub async fn expand_macro(
+        &self,
+        params: ExpandMacroRequest,
+    ) -> LSPResult<ExpandMacroResponse> {
+        self.with_db(move |db| {
+            let file = file(db, params.text_document.uri);
+            ExpandMacroResponse { uri: expand_macro(db, file, params.position) }
+        })
+        .await
+    }
+
+    pub async fn view_sierra(
+        &self,
+        params: ViewCompiledRequest,
+    ) -> LSPResult<ViewCompiledResponse> {
+        self.with_db(move |db| {
+            let file = file(db, params.text_document.uri);
+            view_compiled(db, file, params.position, CompiledCodeKind::Sierra)
+        })
+        .await
+    }
+
+    pub async fn view_casm(&self, params: ViewCompiledRequest) -> LSPResult<ViewCompiledResponse> {
+        self.with_db(move |db| {
+            let file = file(db, params.text_document.uri);
+            view_compiled(db, file, params.position, CompiledCodeKind::Casm)
+        })
+        .await
+    }
+
+    /// Reports the load of the server - its pending tasks and memory usage.
+    pub async fn server_status(&self) -> LSPResult<ServerStatusResponse> {
+        Ok(ServerStatusResponse {
+            queue_depth: self.pending_tasks.load(Ordering::SeqCst),
+            memory_bytes: resident_memory_bytes(),
+        })
+    }
+
+    /// Runs a test, notifying the client when the run starts and with its result when it ends.
+    /// The test is compiled with a database snapshot, which is released before the test runs, so a
+    /// long run does not block changes to the database.
+    async fn run_test(&self, params: RunTestParams) {
+        let RunTestParams { text_document, name, profile } = params;
+        self.client
+            .send_notification::<TestRunStarted>(TestRunStartedParams { name: name.clone() })
+            .await;
+        let test_name = name.clone();
+        let compiled = self
+            .with_db(move |db| {
+                let file = file(db, text_document.uri);
+                compile_test(db, file, &test_name)
+            })
+            .await;
+        let finished = match compiled {
+            Ok(Ok(compiled)) => {
+                let _pending_task = PendingTask::new(&self.pending_tasks);
+                let test_name = name.clone();
+                tokio::task::spawn_blocking(move || run_test(compiled, test_name, profile))
+                    .await
+                    .unwrap_or_else(|_| {
+                        error!("Caught panic in LSP worker thread.");
+                        TestRunFinishedParams::error(name, "The test run panicked.".into())
+                    })
+            }
+            Ok(Err(err)) => TestRunFinishedParams::error(name, format!("{err:?}")),
+            Err(err) => TestRunFinishedParams::error(name, err.message.into_owned()),
+        };
+        self.client.send_notification::<TestRunFinished>(finished).await;
+    }
+
+    /// Gets the corelib path from the client configuration.
     ///
     /// The value is set by the user under the `cairo1.corelibPath` key in client configuration.
     /// The value is not required to be set.
-    /// The path may omit the `corelib/src` or `src` suffix.
-    async fn get_corelib_fallback_path(&self) -> Option<PathBuf> {
-        const CORELIB_CONFIG_SECTION: &str = "cairo1.corelibPath";
+    async fn get_corelib_config_path(&self) -> Option<String> {
         let item = vec![ConfigurationItem {
             scope_uri: None,
-            section: Some(CORELIB_CONFIG_SECTION.to_string()),
+            section: Some(CORELIB_PATH_CONFIG_SECTION.to_string()),
         }];
         let corelib_response = self.client.configuration(item).await;
         match corelib_response.map_err(Error::from) {
             Ok(value_vec) => {
                 if let Some(Value::String(value)) = value_vec.get(0) {
-                    if !value.is_empty() {
-                        let root_path: PathBuf = value.into();
-
-                        let mut path = root_path.clone();
-                        path.push("corelib");
-                        path.push("src");
-                        if path.exists() {
-                            return Some(path);
-                        }
-
-                        let mut path = root_path.clone();
-                        path.push("src");
-                        if path.exists() {
-                            return Some(path);
-                        }
-
-                        if root_path.exists() {
-                            return Some(root_path);
-                        }
-                    }
+                    return Some(value.clone());
                 }
             }
             Err(err) => {
@@ -340,10 +802,57 @@ impl Backend {
         None
     }
 
+    /// Reads whether the diagnostics of unused contract external functions are enabled from the
+    /// client configuration, under the `cairo1.enableUnusedExternalsDiagnostics` key, and updates
+    /// the analyzer plugins if the setting changed.
+    async fn update_unused_externals_config(&self) {
+        let item = vec![ConfigurationItem {
+            scope_uri: None,
+            section: Some(UNUSED_EXTERNALS_CONFIG_SECTION.to_string()),
+        }];
+        let enabled = match self.client.configuration(item).await.map_err(Error::from) {
+            Ok(value_vec) => matches!(value_vec.first(), Some(Value::Bool(true))),
+            Err(err) => {
+                let err = err.context(format!(
+                    "Failed to get configuration under `{UNUSED_EXTERNALS_CONFIG_SECTION}` key."
+                ));
+                warn!("{err:?}");
+                return;
+            }
+        };
+        if self.unused_externals_diagnostics.swap(enabled, Ordering::Relaxed) == enabled {
+            return;
+        }
+        self.db_mut()
+            .await
+            .set_analyzer_plugins(language_server_plugin_suite(enabled).analyzer_plugins);
+        self.refresh_diagnostics().await.ok();
+    }
+
+    /// Finds the corelib and adds it to the system. If it is not found, reports it to the user,
+    /// once until it is found.
+    async fn setup_corelib(
+        &self,
+        db: &mut RootDatabase,
+        scarb_corelib: Option<PathBuf>,
+        config_corelib: Option<String>,
+    ) {
+        if let Some((corelib, source)) = find_corelib(scarb_corelib, config_corelib.as_deref()) {
+            info!("Using corelib at {} from {source}.", corelib.display());
+            init_dev_corelib(db, corelib);
+            self.corelib_missing_reported.store(false, Ordering::Relaxed);
+        } else {
+            warn!("Failed to find corelib path.");
+            if !self.corelib_missing_reported.swap(true, Ordering::Relaxed) {
+                self.notification.notify_corelib_missing().await;
+            }
+        }
+    }
+
     /// Tries to detect the crate root the config that contains a cairo file, and add it to the
     /// system.
     async fn detect_crate_for(&self, db: &mut RootDatabase, file_path: PathBuf) {
-        let corelib_fallback = self.get_corelib_fallback_path().await;
+        let corelib_config = self.get_corelib_config_path().await;
         if self.scarb.is_scarb_project(file_path.clone()) {
             if self.scarb.is_scarb_found() {
                 // Carrying out Scarb based setup.
@@ -356,11 +865,7 @@ impl Backend {
                         None
                     }
                 };
-                if let Some(corelib) = corelib.or_else(detect_corelib).or(corelib_fallback) {
-                    init_dev_corelib(db, corelib);
-                } else {
-                    warn!("Failed to find corelib path.");
-                }
+                self.setup_corelib(db, corelib, corelib_config).await;
 
                 match self.scarb.crate_source_paths(file_path).await {
                     Ok(source_paths) => {
@@ -376,15 +881,27 @@ impl Backend {
             } else {
                 warn!("Not resolving Scarb metadata from manifest file due to missing Scarb path.");
                 self.notification.notify_scarb_missing().await;
+
+                // Carrying out setup from the manifest files, without the dependencies fetched by
+                // Scarb.
+                match read_manifest_project(&file_path) {
+                    Ok(Some(project)) => {
+                        self.setup_corelib(db, None, corelib_config).await;
+                        db.use_cfg(&project.cfg_set);
+                        update_crate_roots(db, project.crates);
+                        return;
+                    }
+                    Ok(None) => {}
+                    Err(err) => {
+                        let err = err.context("Failed to read the project manifest file.");
+                        warn!("{err:?}");
+                    }
+                }
             }
         }
 
         // Scarb based setup not possible.
-        if let Some(corelib) = detect_corelib().or(corelib_fallback) {
-            init_dev_corelib(db, corelib);
-        } else {
-            warn!("Failed to find corelib path.");
-        }
+        self.setup_corelib(db, None, corelib_config).await;
 
         // Fallback to cairo_project manifest format.
         let mut path = file_path.clone();
@@ -400,7 +917,7 @@ impl Backend {
         // Fallback to a single file.
         if let Err(err) = setup_project(&mut *db, file_path.as_path()) {
             let file_path_s = file_path.to_string_lossy();
-            eprintln!("Error loading file {file_path_s} as a single crate: {err}");
+            warn!("Error loading file {file_path_s} as a single crate: {err}");
         }
     }
 
@@ -414,8 +931,28 @@ impl Backend {
             }
         }
         drop(db);
+        self.update_file_watchers().await;
         self.refresh_diagnostics().await
     }
+
+    /// Extends the file watchers to the roots of the crates of the project, which may be outside
+    /// the workspace, such as local checkouts of dependencies.
+    async fn update_file_watchers(&self) {
+        let crate_roots = crate_roots(&*self.db_mut().await);
+        let mut watched_crate_roots = self.watched_crate_roots.lock().await;
+        if *watched_crate_roots == crate_roots {
+            return;
+        }
+        let unregistration = watched_files_unregistration();
+        if let Err(err) = self.client.unregister_capability(vec![unregistration]).await {
+            warn!("Failed to unregister workspace/didChangeWatchedFiles event: {:#?}", err);
+        }
+        let registration = watched_files_registration(&crate_roots);
+        if let Err(err) = self.client.register_capability(vec![registration]).await {
+            warn!("Failed to register workspace/didChangeWatchedFiles event: {:#?}", err);
+        }
+        *watched_crate_roots = crate_roots;
+    }
 }
 
 #[derive(Debug)]
@@ -453,6 +990,8 @@ impl Notification for ScarbResolvingFinish {
 
 pub enum ServerCommands {
     Reload,
+    RunTest,
+    ShowGasEstimate,
 }
 
 impl TryFrom<String> for ServerCommands {
@@ -461,6 +1000,8 @@ impl TryFrom<String> for ServerCommands {
     fn try_from(value: String) -> anyhow::Result<Self> {
         match value.as_str() {
             "cairo1.reload" => Ok(ServerCommands::Reload),
+            RUN_TEST_COMMAND => Ok(ServerCommands::RunTest),
+            SHOW_GAS_ESTIMATE_COMMAND => Ok(ServerCommands::ShowGasEstimate),
             _ => bail!("Unrecognized command: {value}"),
         }
     }
@@ -468,12 +1009,26 @@ impl TryFrom<String> for ServerCommands {
 
 #[tower_lsp::async_trait]
 impl LanguageServer for Backend {
-    async fn initialize(&self, _: InitializeParams) -> LSPResult<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> LSPResult<InitializeResult> {
+        configure_logging(params.initialization_options.as_ref());
+        let pull_diagnostics = params
+            .capabilities
+            .text_document
+            .is_some_and(|text_document| text_document.diagnostic.is_some());
+        self.pull_diagnostics.store(pull_diagnostics, Ordering::SeqCst);
+        let diagnostics_refresh_support = params
+            .capabilities
+            .workspace
+            .as_ref()
+            .and_then(|workspace| workspace.diagnostic.as_ref())
+            .and_then(|diagnostic| diagnostic.refresh_support)
+            .unwrap_or_default();
+        self.diagnostics_refresh_support.store(diagnostics_refresh_support, Ordering::SeqCst);
         Ok(InitializeResult {
             server_info: None,
             capabilities: ServerCapabilities {
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
-                    TextDocumentSyncKind::FULL,
+                    TextDocumentSyncKind::INCREMENTAL,
                 )),
                 completion_provider: Some(CompletionOptions {
                     resolve_provider: Some(false),
@@ -483,7 +1038,11 @@ impl LanguageServer for Backend {
                     completion_item: None,
                 }),
                 execute_command_provider: Some(ExecuteCommandOptions {
-                    commands: vec!["cairo1.reload".to_string()],
+                    commands: vec![
+                        "cairo1.reload".to_string(),
+                        RUN_TEST_COMMAND.to_string(),
+                        SHOW_GAS_ESTIMATE_COMMAND.to_string(),
+                    ],
                     work_done_progress_options: Default::default(),
                 }),
                 workspace: Some(WorkspaceServerCapabilities {
@@ -505,8 +1064,40 @@ impl LanguageServer for Backend {
                     .into(),
                 ),
                 document_formatting_provider: Some(OneOf::Left(true)),
+                document_range_formatting_provider: Some(OneOf::Left(true)),
+                document_on_type_formatting_provider: Some(DocumentOnTypeFormattingOptions {
+                    first_trigger_character: "}".to_string(),
+                    more_trigger_character: Some(vec![";".to_string(), "\n".to_string()]),
+                }),
                 hover_provider: Some(HoverProviderCapability::Simple(true)),
                 definition_provider: Some(OneOf::Left(true)),
+                implementation_provider: Some(ImplementationProviderCapability::Simple(true)),
+                document_highlight_provider: Some(OneOf::Left(true)),
+                linked_editing_range_provider: Some(
+                    LinkedEditingRangeServerCapabilities::Simple(true),
+                ),
+                folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+                call_hierarchy_provider: Some(CallHierarchyServerCapability::Simple(true)),
+                code_lens_provider: Some(CodeLensOptions { resolve_provider: Some(true) }),
+                code_action_provider: Some(CodeActionProviderCapability::Options(
+                    CodeActionOptions {
+                        code_action_kinds: Some(vec![
+                            CodeActionKind::QUICKFIX,
+                            CodeActionKind::REFACTOR_EXTRACT,
+                            CodeActionKind::REFACTOR_INLINE,
+                            CodeActionKind::SOURCE_ORGANIZE_IMPORTS,
+                        ]),
+                        ..CodeActionOptions::default()
+                    },
+                )),
+                diagnostic_provider: Some(DiagnosticServerCapabilities::Options(
+                    DiagnosticOptions {
+                        identifier: Some("cairo".to_string()),
+                        inter_file_dependencies: true,
+                        workspace_diagnostics: false,
+                        work_done_progress_options: Default::default(),
+                    },
+                )),
                 ..ServerCapabilities::default()
             },
         })
@@ -514,50 +1105,72 @@ impl LanguageServer for Backend {
 
     async fn initialized(&self, _: InitializedParams) {
         // Register patterns for client file watcher.
-        // This is used to detect changes to Scarb.toml and invalidate .cairo files.
-        let registration_options = DidChangeWatchedFilesRegistrationOptions {
-            watchers: vec!["/**/*.cairo", "/**/Scarb.toml"]
-                .into_iter()
-                .map(|glob_pattern| FileSystemWatcher {
-                    glob_pattern: GlobPattern::String(glob_pattern.to_string()),
-                    kind: None,
-                })
-                .collect(),
+        // This is used to detect changes to project manifests and invalidate .cairo files.
+        // The watchers are extended with the crates outside the workspace once they are detected.
+        let registration = watched_files_registration(&[]);
+        let result = self.client.register_capability(vec![registration]).await;
+        if let Err(err) = result {
+            warn!("Failed to register workspace/didChangeWatchedFiles event: {:#?}", err);
+        }
+
+        // Register the type hierarchy provider, which has no static server capability in the
+        // supported protocol types.
+        let registration_options = TypeHierarchyRegistrationOptions {
+            text_document_registration_options: TextDocumentRegistrationOptions {
+                document_selector: Some(vec![DocumentFilter {
+                    language: Some("cairo".to_string()),
+                    scheme: None,
+                    pattern: None,
+                }]),
+            },
+            ..TypeHierarchyRegistrationOptions::default()
         };
         let registration = Registration {
-            id: "workspace/didChangeWatchedFiles".to_string(),
-            method: "workspace/didChangeWatchedFiles".to_string(),
+            id: "textDocument/prepareTypeHierarchy".to_string(),
+            method: "textDocument/prepareTypeHierarchy".to_string(),
             register_options: Some(serde_json::to_value(registration_options).unwrap()),
         };
         let result = self.client.register_capability(vec![registration]).await;
         if let Err(err) = result {
-            warn!("Failed to register workspace/didChangeWatchedFiles event: {:#?}", err);
+            warn!("Failed to register textDocument/prepareTypeHierarchy request: {:#?}", err);
         }
+
+        self.update_unused_externals_config().await;
     }
 
     async fn shutdown(&self) -> LSPResult<()> {
+        self.save_analysis_cache();
         Ok(())
     }
 
     async fn did_change_workspace_folders(&self, _: DidChangeWorkspaceFoldersParams) {}
 
-    async fn did_change_configuration(&self, _: DidChangeConfigurationParams) {}
+    async fn did_change_configuration(&self, _: DidChangeConfigurationParams) {
+        self.update_unused_externals_config().await;
+    }
 
     async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
-        // Invalidate changed cairo files.
+        // Invalidate changed cairo files, and the files in changed directories, which may be
+        // reported instead of their files, e.g. when deleted.
         let mut db = self.db_mut().await;
         for change in &params.changes {
-            if is_cairo_file_path(&change.uri) {
-                let file = file(&db, change.uri.clone());
+            let files = if is_cairo_file_path(&change.uri) {
+                vec![file(&db, change.uri.clone())]
+            } else if let Ok(path) = change.uri.to_file_path() {
+                project_files_in_directory(&*db, &path)
+            } else {
+                vec![]
+            };
+            for file in files {
                 PrivRawFileContentQuery.in_db_mut(db.as_files_group_mut()).invalidate(&file);
             }
         }
         drop(db);
-        // Reload workspace if Scarb.toml changed.
-        for change in params.changes {
-            if is_scarb_manifest_path(&change.uri) {
-                self.reload().await.ok();
-            }
+        // Reload workspace if a project manifest changed, which also refreshes the diagnostics.
+        if params.changes.iter().any(|change| is_project_manifest_path(&change.uri)) {
+            self.reload().await.ok();
+        } else {
+            self.refresh_diagnostics().await.ok();
         }
     }
 
@@ -568,6 +1181,28 @@ impl LanguageServer for Backend {
                 ServerCommands::Reload => {
                     self.reload().await?;
                 }
+                ServerCommands::RunTest => {
+                    let Some(run_test_params) = params
+                        .arguments
+                        .into_iter()
+                        .next()
+                        .and_then(|argument| serde_json::from_value(argument).ok())
+                    else {
+                        return Err(LSPError::invalid_params("Expected the test to run."));
+                    };
+                    // The results are sent as notifications, as the test may run for long - so
+                    // the command returns right away.
+                    let backend = self.clone();
+                    tokio::spawn(async move { backend.run_test(run_test_params).await });
+                    return Ok(None);
+                }
+                ServerCommands::ShowGasEstimate => {
+                    let Some(Value::String(estimate)) = params.arguments.into_iter().next() else {
+                        return Err(LSPError::invalid_params("Expected the estimate to show."));
+                    };
+                    self.client.show_message(MessageType::INFO, estimate).await;
+                    return Ok(None);
+                }
             }
         }
 
@@ -594,26 +1229,50 @@ impl LanguageServer for Backend {
         }
 
         let file = file(&db, uri.clone());
-        self.state_mutex.lock().await.open_files.insert(file);
+        let mut state = self.state_mutex.lock().await;
+        state.open_files.insert(file);
+        state.focused_file = Some(file);
+        drop(state);
         db.override_file_content(file, Some(Arc::new(params.text_document.text)));
         drop(db);
+        self.update_file_watchers().await;
+        self.publish_cached_diagnostics().await;
         self.refresh_diagnostics().await.ok();
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
-        let text =
-            if let [TextDocumentContentChangeEvent { text, .. }] = &params.content_changes[..] {
-                text
-            } else {
-                eprintln!("Unexpected format of document change.");
-                return;
-            };
         let mut db = self.db_mut().await;
         let uri = params.text_document.uri;
         let file = file(&db, uri.clone());
-        db.override_file_content(file, Some(Arc::new(text.into())));
+        let Some(content) = db.file_content(file) else {
+            warn!("Change of a document with unknown content.");
+            return;
+        };
+        let mut text = content.as_ref().clone();
+        for change in params.content_changes {
+            let range = change.range;
+            if apply_content_change(&mut text, change).is_none() {
+                // The changes are relative to each other, so the rest of them can not be applied
+                // either, and the content of the document is out of sync with the client until it
+                // is reopened.
+                error!("Invalid range {range:?} in change of document {uri}.");
+                drop(db);
+                self.client
+                    .show_message(
+                        MessageType::ERROR,
+                        format!(
+                            "Failed to apply a change of {uri}. Reopen the document to resync it \
+                             with the language server."
+                        ),
+                    )
+                    .await;
+                return;
+            }
+        }
+        db.override_file_content(file, Some(Arc::new(text)));
+        self.state_mutex.lock().await.focused_file = Some(file);
         drop(db);
-        self.refresh_diagnostics().await.ok();
+        self.refresh_diagnostics_debounced();
     }
 
     async fn did_save(&self, params: DidSaveTextDocumentParams) {
@@ -621,79 +1280,81 @@ impl LanguageServer for Backend {
         let file = file(&db, params.text_document.uri);
         PrivRawFileContentQuery.in_db_mut(db.as_files_group_mut()).invalidate(&file);
         db.override_file_content(file, None);
+        drop(db);
+        // The gas estimates of other documents may depend on the saved one. This is only checked
+        // on save, as the client requests the lenses of the edited document by itself.
+        self.refresh_stale_code_lenses().await;
     }
 
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
         let mut db = self.db_mut().await;
         let file = file(&db, params.text_document.uri);
-        self.state_mutex.lock().await.open_files.remove(&file);
+        let mut state = self.state_mutex.lock().await;
+        state.open_files.remove(&file);
+        if state.focused_file == Some(file) {
+            state.focused_file = None;
+        }
+        drop(state);
         db.override_file_content(file, None);
         drop(db);
         self.refresh_diagnostics().await.ok();
     }
 
+    async fn diagnostic(
+        &self,
+        params: DocumentDiagnosticParams,
+    ) -> LSPResult<DocumentDiagnosticReportResult> {
+        let items = self
+            .with_db_measured("textDocument/diagnostic", move |db| {
+                let file_id = file(db, params.text_document.uri);
+                lsp_diagnostics(db, &file_diagnostics(db, file_id))
+            })
+            .await?;
+        Ok(DocumentDiagnosticReportResult::Report(DocumentDiagnosticReport::Full(
+            RelatedFullDocumentDiagnosticReport {
+                related_documents: None,
+                full_document_diagnostic_report: FullDocumentDiagnosticReport {
+                    result_id: None,
+                    items,
+                },
+            },
+        )))
+    }
+
     async fn completion(&self, params: CompletionParams) -> LSPResult<Option<CompletionResponse>> {
-        self.with_db(|db| {
+        let known_types = self.known_types.clone();
+        let generation = known_types.lock().unwrap().generation();
+        let res = self.with_db_cancelable(move |db, cancellation| {
             let text_document_position = params.text_document_position;
             let file_uri = text_document_position.text_document.uri;
-            eprintln!("Complete {file_uri}");
+            debug!("Complete {file_uri}");
             let file = file(db, file_uri);
-            let mut position = text_document_position.position;
-            position.character = position.character.saturating_sub(1);
-
-            let Some((mut node, lookup_items)) = get_node_and_lookup_items(db, file, position)
-            else {
-                return None;
-            };
-
-            // Find module.
-            let module_id = find_node_module(db, file, node.clone()).on_none(|| {
-                eprintln!("Hover failed. Failed to find module.");
-            })?;
-            let file_index = FileIndex(0);
-            let module_file_id = ModuleFileId(module_id, file_index);
-
-            // Skip trivia.
-            while ast::Trivium::is_variant(node.kind(db))
-                || node.kind(db) == SyntaxKind::Trivia
-                || node.kind(db).is_token()
-            {
-                node = node.parent().unwrap_or(node);
-            }
-
             let trigger_kind =
                 params.context.map(|it| it.trigger_kind).unwrap_or(CompletionTriggerKind::INVOKED);
-
-            match completion_kind(db, node) {
-                CompletionKind::Dot(expr) => {
-                    dot_completions(db, file, lookup_items, expr).map(CompletionResponse::Array)
-                }
-                CompletionKind::ColonColon(segments) if !segments.is_empty() => {
-                    colon_colon_completions(db, module_file_id, lookup_items, segments)
-                        .map(CompletionResponse::Array)
-                }
-                _ if trigger_kind == CompletionTriggerKind::INVOKED => {
-                    Some(CompletionResponse::Array(generic_completions(
-                        db,
-                        module_file_id,
-                        lookup_items,
-                    )))
-                }
-                _ => None,
-            }
-        })
-        .await
+            // The known types are of another database if it was replaced meanwhile.
+            let known_types = known_types.lock().unwrap();
+            let known_types = (known_types.generation() == generation).then_some(&*known_types);
+            completions(
+                db,
+                file,
+                text_document_position.position,
+                trigger_kind,
+                known_types,
+                cancellation,
+            )
+        });
+        self.measured("textDocument/completion", res).await
     }
 
     async fn semantic_tokens_full(
         &self,
         params: SemanticTokensParams,
     ) -> LSPResult<Option<SemanticTokensResult>> {
-        self.with_db(|db| {
+        self.with_db_measured("textDocument/semanticTokens/full", |db| {
             let file_uri = params.text_document.uri;
             let file = file(db, file_uri.clone());
             let Ok(node) = db.file_syntax(file) else {
-                eprintln!("Semantic analysis failed. File '{file_uri}' does not exist.");
+                debug!("Semantic analysis failed. File '{file_uri}' does not exist.");
                 return None;
             };
 
@@ -713,39 +1374,127 @@ impl LanguageServer for Backend {
         &self,
         params: DocumentFormattingParams,
     ) -> LSPResult<Option<Vec<TextEdit>>> {
-        self.with_db(|db| {
+        self.with_db_measured("textDocument/formatting", |db| {
             let file_uri = params.text_document.uri;
             let file = file(db, file_uri.clone());
-            let node = db.file_syntax(file).ok().on_none(|| {
-                eprintln!("Formatting failed. File '{file_uri}' does not exist.");
-            })?;
-            db.file_syntax_diagnostics(file).check_error_free().ok().on_none(|| {
-                eprintln!("Formatting failed. Cannot properly parse '{file_uri}' exist.");
-            })?;
-            let new_text = get_formatted_file(db.upcast(), &node, FormatterConfig::default());
-
-            let file_summary = db.file_summary(file).on_none(|| {
-                eprintln!("Formatting failed. Cannot get summary for file '{file_uri}'.");
-            })?;
-            let old_line_count = file_summary.line_count().try_into().ok().on_none(|| {
-                eprintln!("Formatting failed. Line count out of bound in file '{file_uri}'.");
-            })?;
-
-            Some(vec![TextEdit {
-                range: Range {
-                    start: Position { line: 0, character: 0 },
-                    end: Position { line: old_line_count, character: 0 },
-                },
-                new_text,
-            }])
+            formatting_edits(db, file, None).on_none(|| {
+                debug!("Formatting failed. Cannot properly parse '{file_uri}'.");
+            })
+        })
+        .await
+    }
+
+    async fn range_formatting(
+        &self,
+        params: DocumentRangeFormattingParams,
+    ) -> LSPResult<Option<Vec<TextEdit>>> {
+        self.with_db_measured("textDocument/rangeFormatting", move |db| {
+            let file_uri = params.text_document.uri;
+            let file = file(db, file_uri.clone());
+            formatting_edits(db, file, Some(params.range)).on_none(|| {
+                debug!("Formatting failed. Cannot properly parse '{file_uri}'.");
+            })
+        })
+        .await
+    }
+
+    async fn on_type_formatting(
+        &self,
+        params: DocumentOnTypeFormattingParams,
+    ) -> LSPResult<Option<Vec<TextEdit>>> {
+        self.with_db_measured("textDocument/onTypeFormatting", move |db| {
+            let position = params.text_document_position;
+            let file = file(db, position.text_document.uri);
+            on_type_formatting_edits(
+                db,
+                file,
+                position.position,
+                &params.ch,
+                params.options.tab_size,
+            )
+        })
+        .await
+    }
+
+    async fn code_lens(&self, params: CodeLensParams) -> LSPResult<Option<Vec<CodeLens>>> {
+        let gas_estimates = self.gas_estimates.clone();
+        self.with_db_measured("textDocument/codeLens", move |db| {
+            let uri = params.text_document.uri;
+            let file = file(db, uri.clone());
+            // Each kind of lenses is returned even if the other is not available.
+            let gas_estimate_lenses =
+                file_inputs_hash(db, file, &mut HashMap::new()).and_then(|crate_hash| {
+                    let lenses = gas_estimate_code_lenses(db, file, uri.clone(), &crate_hash)?;
+                    gas_estimates.lock().unwrap().record_document(uri.clone(), crate_hash);
+                    Some(lenses)
+                });
+            let test_lenses = test_code_lenses(db, file, uri);
+            if gas_estimate_lenses.is_none() && test_lenses.is_none() {
+                return None;
+            }
+            Some(gas_estimate_lenses.into_iter().chain(test_lenses).flatten().collect())
+        })
+        .await
+    }
+
+    async fn code_lens_resolve(&self, lens: CodeLens) -> LSPResult<CodeLens> {
+        let Some(data) = lens
+            .data
+            .clone()
+            .and_then(|data| serde_json::from_value::<GasEstimateLensData>(data).ok())
+        else {
+            return Ok(lens);
+        };
+        // The estimates are cached per crate revision, as computing them requires compiling the
+        // functions.
+        if let Some(estimate) = self.gas_estimates.lock().unwrap().get(&data) {
+            return Ok(resolve_gas_estimate_code_lens(lens, estimate));
+        }
+        let gas_estimates = self.gas_estimates.clone();
+        self.with_db_measured("codeLens/resolve", move |db| {
+            let file = file(db, data.text_document.uri.clone());
+            let estimate = estimate_function_gas(db, file, &data);
+            // Only cached if the crate did not change since the lens was created.
+            if file_inputs_hash(db, file, &mut HashMap::new()).as_ref() == Some(&data.crate_hash) {
+                gas_estimates.lock().unwrap().insert(&data, estimate.clone());
+            }
+            resolve_gas_estimate_code_lens(lens, estimate)
+        })
+        .await
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> LSPResult<Option<CodeActionResponse>> {
+        self.with_db_measured("textDocument/codeAction", move |db| {
+            let uri = params.text_document.uri;
+            let file = file(db, uri.clone());
+            // Only the actions of the requested kinds, or their subkinds, are returned.
+            let requested = |kind: CodeActionKind| {
+                params.context.only.as_ref().is_none_or(|only| {
+                    only.iter().any(|requested| kind.as_str().starts_with(requested.as_str()))
+                })
+            };
+            let mut actions = vec![];
+            if requested(CodeActionKind::QUICKFIX) {
+                actions.extend(quick_fixes(db, file, uri.clone(), params.context.diagnostics));
+            }
+            if requested(CodeActionKind::REFACTOR_EXTRACT) {
+                actions.extend(extract_variable(db, file, uri.clone(), params.range));
+            }
+            if requested(CodeActionKind::REFACTOR_INLINE) {
+                actions.extend(inline_variable(db, file, uri.clone(), params.range.start));
+            }
+            if requested(CodeActionKind::SOURCE_ORGANIZE_IMPORTS) {
+                actions.extend(organize_imports(db, file, uri));
+            }
+            Some(actions)
         })
         .await
     }
 
     async fn hover(&self, params: HoverParams) -> LSPResult<Option<Hover>> {
-        self.with_db(|db| {
+        self.with_db_measured("textDocument/hover", move |db| {
             let file_uri = params.text_document_position_params.text_document.uri;
-            eprintln!("Hover {file_uri}");
+            debug!("Hover {file_uri}");
             let file = file(db, file_uri);
             let position = params.text_document_position_params.position;
             let Some((node, lookup_items)) = get_node_and_lookup_items(db, file, position) else {
@@ -761,6 +1510,9 @@ impl LanguageServer for Backend {
                 LookupItemId::ImplFunction(impl_function_id) => {
                     FunctionWithBodyId::Impl(impl_function_id)
                 }
+                LookupItemId::TraitFunction(trait_function_id) => {
+                    FunctionWithBodyId::Trait(trait_function_id)
+                }
                 _ => {
                     return None;
                 }
@@ -782,12 +1534,106 @@ impl LanguageServer for Backend {
         .await
     }
 
+    async fn prepare_call_hierarchy(
+        &self,
+        params: CallHierarchyPrepareParams,
+    ) -> LSPResult<Option<Vec<CallHierarchyItem>>> {
+        self.with_db_measured("textDocument/prepareCallHierarchy", move |db| {
+            let position = params.text_document_position_params;
+            let file = file(db, position.text_document.uri);
+            prepare_call_hierarchy(db, file, position.position)
+        })
+        .await
+    }
+
+    async fn incoming_calls(
+        &self,
+        params: CallHierarchyIncomingCallsParams,
+    ) -> LSPResult<Option<Vec<CallHierarchyIncomingCall>>> {
+        self.with_db_measured("callHierarchy/incomingCalls", move |db| {
+            incoming_calls(db, &params.item)
+        })
+        .await
+    }
+
+    async fn outgoing_calls(
+        &self,
+        params: CallHierarchyOutgoingCallsParams,
+    ) -> LSPResult<Option<Vec<CallHierarchyOutgoingCall>>> {
+        self.with_db_measured("callHierarchy/outgoingCalls", move |db| {
+            outgoing_calls(db, &params.item)
+        })
+        .await
+    }
+
+    async fn prepare_type_hierarchy(
+        &self,
+        params: TypeHierarchyPrepareParams,
+    ) -> LSPResult<Option<Vec<TypeHierarchyItem>>> {
+        self.with_db_measured("textDocument/prepareTypeHierarchy", move |db| {
+            let position = params.text_document_position_params;
+            let file = file(db, position.text_document.uri);
+            prepare_type_hierarchy(db, file, position.position)
+        })
+        .await
+    }
+
+    async fn supertypes(
+        &self,
+        params: TypeHierarchySupertypesParams,
+    ) -> LSPResult<Option<Vec<TypeHierarchyItem>>> {
+        self.with_db_measured("typeHierarchy/supertypes", move |db| supertypes(db, &params.item))
+            .await
+    }
+
+    async fn subtypes(
+        &self,
+        params: TypeHierarchySubtypesParams,
+    ) -> LSPResult<Option<Vec<TypeHierarchyItem>>> {
+        self.with_db_measured("typeHierarchy/subtypes", move |db| subtypes(db, &params.item)).await
+    }
+
+    async fn document_highlight(
+        &self,
+        params: DocumentHighlightParams,
+    ) -> LSPResult<Option<Vec<DocumentHighlight>>> {
+        self.with_db_measured("textDocument/documentHighlight", move |db| {
+            let position = params.text_document_position_params;
+            let file = file(db, position.text_document.uri);
+            document_highlights(db, file, position.position)
+        })
+        .await
+    }
+
+    async fn linked_editing_range(
+        &self,
+        params: LinkedEditingRangeParams,
+    ) -> LSPResult<Option<LinkedEditingRanges>> {
+        self.with_db_measured("textDocument/linkedEditingRange", move |db| {
+            let position = params.text_document_position_params;
+            let file = file(db, position.text_document.uri);
+            linked_editing_ranges(db, file, position.position)
+        })
+        .await
+    }
+
+    async fn folding_range(
+        &self,
+        params: FoldingRangeParams,
+    ) -> LSPResult<Option<Vec<FoldingRange>>> {
+        self.with_db_measured("textDocument/foldingRange", move |db| {
+            let file = file(db, params.text_document.uri);
+            folding_ranges(db, file)
+        })
+        .await
+    }
+
     async fn goto_definition(
         &self,
         params: GotoDefinitionParams,
     ) -> LSPResult<Option<GotoDefinitionResponse>> {
-        eprintln!("Goto definition");
-        self.with_db(|db| {
+        debug!("Goto definition");
+        self.with_db_measured("textDocument/definition", move |db| {
             let syntax_db = db.upcast();
             let file_uri = params.text_document_position_params.text_document.uri;
             let file = file(db, file_uri.clone());
@@ -817,6 +1663,18 @@ impl LanguageServer for Backend {
         })
         .await
     }
+
+    async fn goto_implementation(
+        &self,
+        params: request::GotoImplementationParams,
+    ) -> LSPResult<Option<request::GotoImplementationResponse>> {
+        self.with_db_measured("textDocument/implementation", move |db| {
+            let position = params.text_document_position_params;
+            let file = file(db, position.text_document.uri);
+            goto_implementation(db, file, position.position)
+        })
+        .await
+    }
 }
 
 fn find_definition(
@@ -829,7 +1687,7 @@ fn find_definition(
         if parent.kind(db) == SyntaxKind::ItemModule {
             let containing_module_id =
                 find_node_module(db, file, parent.clone()).on_none(|| {
-                    eprintln!("Hover failed. Failed to find module.");
+                    debug!("Hover failed. Failed to find module.");
                 })?;
 
             let submodule_id = db.intern_submodule(SubmoduleLongId(
@@ -853,7 +1711,85 @@ fn find_definition(
             return resolved_concrete_item_def(db.upcast(), item);
         }
     }
-    None
+
+    // Names the semantic model does not record - `super` segments and derived traits.
+    let module_id = find_node_module(db, file, identifier.as_syntax_node())?;
+    if identifier.text(db) == "super" {
+        let module_id = super_segment_module(db, module_id, identifier)?;
+        return Some(resolved_generic_item_def(
+            db.upcast(),
+            ResolvedGenericItem::Module(module_id),
+        ));
+    }
+    let path = derived_trait_path(db, identifier)?;
+    let mut resolver =
+        Resolver::new(db, ModuleFileId(module_id, FileIndex(0)), InferenceId::NoContext);
+    let mut diagnostics = SemanticDiagnostics::new(file);
+    let item =
+        resolver.resolve_generic_path(&mut diagnostics, &path, NotFoundItemType::Trait).ok()?;
+    Some(resolved_generic_item_def(db.upcast(), item))
+}
+
+/// Returns the module a `super` segment of a path in a module refers to - the ancestor of the
+/// module by the number of `super` segments up to the segment.
+fn super_segment_module(
+    db: &RootDatabase,
+    module_id: ModuleId,
+    identifier: &ast::TerminalIdentifier,
+) -> Option<ModuleId> {
+    let segment = identifier.as_syntax_node().parent()?;
+    let parent = segment.parent()?;
+    let is_super = |segment: &PathSegment| match segment {
+        PathSegment::Simple(simple) => simple.ident(db).text(db) == "super",
+        PathSegment::WithGenericArgs(_) => false,
+    };
+    let supers = match parent.kind(db) {
+        SyntaxKind::ExprPath => {
+            let elements = ast::ExprPath::from_syntax_node(db, parent).elements(db);
+            let index = elements.iter().position(|element| element.as_syntax_node() == segment)?;
+            if !elements[..index].iter().all(is_super) {
+                return None;
+            }
+            index + 1
+        }
+        SyntaxKind::UsePathSingle => {
+            let mut supers = 0;
+            let mut current = Some(parent);
+            while let Some(node) = current.filter(|node| node.kind(db) == SyntaxKind::UsePathSingle)
+            {
+                if !is_super(&ast::UsePathSingle::from_syntax_node(db, node.clone()).ident(db)) {
+                    return None;
+                }
+                supers += 1;
+                current = node.parent();
+            }
+            supers
+        }
+        _ => return None,
+    };
+    (0..supers).try_fold(module_id, |module_id, _| match module_id {
+        ModuleId::CrateRoot(_) => None,
+        ModuleId::Submodule(submodule_id) => Some(submodule_id.parent_module(db)),
+    })
+}
+
+/// Returns the path of a trait derived by a `#[derive(...)]` attribute, if the identifier is in
+/// one.
+fn derived_trait_path(
+    db: &RootDatabase,
+    identifier: &ast::TerminalIdentifier,
+) -> Option<ast::ExprPath> {
+    let path = identifier.as_syntax_node().parent()?.parent()?;
+    if path.kind(db) != SyntaxKind::ExprPath {
+        return None;
+    }
+    let mut current = path.clone();
+    while current.kind(db) != SyntaxKind::ArgListParenthesized {
+        current = current.parent()?;
+    }
+    let attr = ast::Attribute::from_syntax_node(db, current.parent()?);
+    (attr.attr(db).as_syntax_node().get_text_without_trivia(db) == DERIVE_ATTR)
+        .then(|| ast::ExprPath::from_syntax_node(db, path))
 }
 
 fn resolved_concrete_item_def(
@@ -919,10 +1855,76 @@ fn resolved_generic_item_def(db: &dyn DefsGroup, item: ResolvedGenericItem) -> S
 enum CompletionKind {
     Dot(ast::ExprBinary),
     ColonColon(Vec<PathSegment>),
+    /// The name of an attribute, in `#[...]`.
+    Attribute,
+    /// A derived trait, in `#[derive(...)]`.
+    Derive,
+}
+
+/// The attribute whose arguments are the derived traits.
+const DERIVE_ATTR: &str = "derive";
+
+/// Returns the completions at a position of a file. Returns None if the request was canceled.
+fn completions(
+    db: &RootDatabase,
+    file: FileId,
+    mut position: Position,
+    trigger_kind: CompletionTriggerKind,
+    known_types: Option<&KnownTypes>,
+    cancellation: &CancellationToken,
+) -> Option<CompletionResponse> {
+    // The variables in scope are determined by the cursor, rather than by the node before it.
+    let offset = position_to_offset(db.file_summary(file)?, position, &db.file_content(file)?)?;
+    position.character = position.character.saturating_sub(1);
+
+    let Some((mut node, lookup_items)) = get_node_and_lookup_items(db, file, position) else {
+        return None;
+    };
+
+    // Find module.
+    let module_id = find_node_module(db, file, node.clone()).on_none(|| {
+        debug!("Hover failed. Failed to find module.");
+    })?;
+    let file_index = FileIndex(0);
+    let module_file_id = ModuleFileId(module_id, file_index);
+
+    // Skip trivia.
+    while ast::Trivium::is_variant(node.kind(db))
+        || node.kind(db) == SyntaxKind::Trivia
+        || node.kind(db).is_token()
+    {
+        node = node.parent().unwrap_or(node);
+    }
+
+    match completion_kind(db, node) {
+        CompletionKind::Dot(expr) => {
+            dot_completions(db, file, lookup_items, expr, known_types, cancellation)
+                .map(CompletionResponse::Array)
+        }
+        CompletionKind::ColonColon(segments) if !segments.is_empty() => {
+            colon_colon_completions(db, module_file_id, lookup_items, segments)
+                .map(CompletionResponse::Array)
+        }
+        CompletionKind::Attribute => Some(CompletionResponse::Array(attribute_completions(db))),
+        CompletionKind::Derive => Some(CompletionResponse::Array(derive_completions(db))),
+        _ if trigger_kind == CompletionTriggerKind::INVOKED => {
+            Some(CompletionResponse::Array(generic_completions(
+                db,
+                module_file_id,
+                lookup_items,
+                file,
+                offset,
+            )))
+        }
+        _ => None,
+    }
 }
 
 fn completion_kind(db: &RootDatabase, node: SyntaxNode) -> CompletionKind {
-    eprintln!("node.kind: {:#?}", node.kind(db));
+    trace!("node.kind: {:#?}", node.kind(db));
+    if let Some(kind) = attribute_completion_kind(db, node.clone()) {
+        return kind;
+    }
     match node.kind(db) {
         SyntaxKind::TerminalDot => {
             let parent = node.parent().unwrap();
@@ -932,12 +1934,12 @@ fn completion_kind(db: &RootDatabase, node: SyntaxNode) -> CompletionKind {
         }
         SyntaxKind::TerminalColonColon => {
             let parent = node.parent().unwrap();
-            eprintln!("parent.kind: {:#?}", parent.kind(db));
+            trace!("parent.kind: {:#?}", parent.kind(db));
             if parent.kind(db) == SyntaxKind::ExprPath {
                 return completion_kind_from_path_node(db, parent);
             }
             let grandparent = parent.parent().unwrap();
-            eprintln!("grandparent.kind: {:#?}", grandparent.kind(db));
+            trace!("grandparent.kind: {:#?}", grandparent.kind(db));
             if grandparent.kind(db) == SyntaxKind::ExprPath {
                 return completion_kind_from_path_node(db, grandparent);
             }
@@ -950,41 +1952,41 @@ fn completion_kind(db: &RootDatabase, node: SyntaxNode) -> CompletionKind {
             } else if grandparent.kind(db) == SyntaxKind::UsePathSingle {
                 (ast::UsePath::Single(ast::UsePathSingle::from_syntax_node(db, grandparent)), false)
             } else {
-                eprintln!("Generic");
+                trace!("Generic");
                 return CompletionKind::ColonColon(vec![]);
             };
             let mut segments = vec![];
             let Ok(()) = get_use_segments(db.upcast(), &use_ast, &mut segments) else {
-                eprintln!("Generic");
+                trace!("Generic");
                 return CompletionKind::ColonColon(vec![]);
             };
             if should_pop {
                 segments.pop();
             }
-            eprintln!("ColonColon");
+            trace!("ColonColon");
             return CompletionKind::ColonColon(segments);
         }
         SyntaxKind::TerminalIdentifier => {
             let parent = node.parent().unwrap();
-            eprintln!("parent.kind: {:#?}", parent.kind(db));
+            trace!("parent.kind: {:#?}", parent.kind(db));
             let grandparent = parent.parent().unwrap();
-            eprintln!("grandparent.kind: {:#?}", grandparent.kind(db));
+            trace!("grandparent.kind: {:#?}", grandparent.kind(db));
             if grandparent.kind(db) == SyntaxKind::ExprPath {
                 if db.get_children(grandparent.clone())[0].stable_ptr() != parent.stable_ptr() {
                     // Not first segment.
-                    eprintln!("Not first segment");
+                    trace!("Not first segment");
                     return completion_kind_from_path_node(db, grandparent);
                 }
                 // First segment.
                 let grandgrandparent = grandparent.parent().unwrap();
-                eprintln!("grandgrandparent.kind: {:#?}", grandgrandparent.kind(db));
+                trace!("grandgrandparent.kind: {:#?}", grandgrandparent.kind(db));
                 if grandgrandparent.kind(db) == SyntaxKind::ExprBinary {
                     let expr = ast::ExprBinary::from_syntax_node(db, grandgrandparent.clone());
                     if matches!(
                         ast::ExprBinary::from_syntax_node(db, grandgrandparent).op(db),
                         ast::BinaryOperator::Dot(_)
                     ) {
-                        eprintln!("Dot");
+                        trace!("Dot");
                         return CompletionKind::Dot(expr);
                     }
                 }
@@ -995,24 +1997,47 @@ fn completion_kind(db: &RootDatabase, node: SyntaxNode) -> CompletionKind {
                 let Ok(()) =
                     get_use_segments(db.upcast(), &ast::UsePath::Leaf(use_ast), &mut segments)
                 else {
-                    eprintln!("Generic");
+                    trace!("Generic");
                     return CompletionKind::ColonColon(vec![]);
                 };
                 segments.pop();
-                eprintln!("ColonColon");
+                trace!("ColonColon");
                 return CompletionKind::ColonColon(segments);
             }
         }
         _ => (),
     }
-    eprintln!("Generic");
+    trace!("Generic");
     CompletionKind::ColonColon(vec![])
 }
 
+/// Returns the completion kind of a node in an attribute, or None if the node is not in one.
+fn attribute_completion_kind(db: &RootDatabase, node: SyntaxNode) -> Option<CompletionKind> {
+    // The child of the attribute node holding the node.
+    let mut attr_child = node.clone();
+    let mut current = node;
+    while current.kind(db) != SyntaxKind::Attribute {
+        attr_child = current.clone();
+        current = current.parent()?;
+    }
+    match attr_child.kind(db) {
+        SyntaxKind::Attribute
+        | SyntaxKind::TerminalHash
+        | SyntaxKind::TerminalLBrack
+        | SyntaxKind::ExprPath => Some(CompletionKind::Attribute),
+        SyntaxKind::ArgListParenthesized => {
+            let attr = ast::Attribute::from_syntax_node(db, current);
+            (attr.attr(db).as_syntax_node().get_text_without_trivia(db) == DERIVE_ATTR)
+                .then_some(CompletionKind::Derive)
+        }
+        _ => None,
+    }
+}
+
 fn completion_kind_from_path_node(db: &RootDatabase, parent: SyntaxNode) -> CompletionKind {
-    eprintln!("completion_kind_from_path_node: {}", parent.clone().get_text_without_trivia(db));
+    trace!("completion_kind_from_path_node: {}", parent.clone().get_text_without_trivia(db));
     let expr = ast::ExprPath::from_syntax_node(db, parent);
-    eprintln!("has_tail: {}", expr.has_tail(db));
+    trace!("has_tail: {}", expr.has_tail(db));
     let mut segments = expr.to_segments(db);
     if expr.has_tail(db) {
         segments.pop();
@@ -1130,36 +2155,47 @@ fn get_node_and_lookup_items(
     file: FileId,
     position: Position,
 ) -> Option<(SyntaxNode, Vec<LookupItemId>)> {
-    let mut res = Vec::new();
     let syntax_db = db.upcast();
     let filename = file.file_name(db.upcast());
 
     // Get syntax for file.
     let syntax = db.file_syntax(file).to_option().on_none(|| {
-        eprintln!("Formatting failed. File '{filename}' does not exist.");
+        debug!("Formatting failed. File '{filename}' does not exist.");
     })?;
 
     // Get file summary and content.
     let file_summary = db.file_summary(file).on_none(|| {
-        eprintln!("Hover failed. File '{filename}' does not exist.");
+        debug!("Hover failed. File '{filename}' does not exist.");
     })?;
     let content = db.file_content(file).on_none(|| {
-        eprintln!("Hover failed. File '{filename}' does not exist.");
+        debug!("Hover failed. File '{filename}' does not exist.");
     })?;
 
     // Find offset for position.
     let offset = position_to_offset(file_summary, position, &content)?;
     let node = syntax.lookup_offset(syntax_db, offset);
+    let lookup_items = get_lookup_items(db, file, node.clone())?;
+    Some((node, lookup_items))
+}
+
+/// Returns all the lookup items above a syntax node in a file, from the innermost to the
+/// outermost.
+fn get_lookup_items(
+    db: &(dyn SemanticGroup + 'static),
+    file: FileId,
+    node: SyntaxNode,
+) -> Option<Vec<LookupItemId>> {
+    let mut res = Vec::new();
 
     // Find module.
     let module_id = find_node_module(db, file, node.clone()).on_none(|| {
-        eprintln!("Hover failed. Failed to find module.");
+        debug!("Hover failed. Failed to find module.");
     })?;
     let file_index = FileIndex(0);
     let module_file_id = ModuleFileId(module_id, file_index);
 
     // Find containing function.
-    let mut item_node = node.clone();
+    let mut item_node = node;
     loop {
         for item in lookup_item_from_ast(db, module_file_id, item_node.clone()) {
             res.push(item);
@@ -1168,7 +2204,7 @@ fn get_node_and_lookup_items(
             Some(next_node) => {
                 item_node = next_node;
             }
-            None => return Some((node, res)),
+            None => return Some(res),
         }
     }
 }
@@ -1179,18 +2215,55 @@ fn position_to_offset(
     content: &str,
 ) -> Option<TextOffset> {
     let mut offset = *file_summary.line_offsets.get(position.line as usize).on_none(|| {
-        eprintln!("Hover failed. Position out of bounds.");
+        debug!("Hover failed. Position out of bounds.");
     })?;
     let mut chars_it = offset.take_from(content).chars();
     for _ in 0..position.character {
         let c = chars_it.next().on_none(|| {
-            eprintln!("Position does not exist.");
+            debug!("Position does not exist.");
         })?;
         offset = offset.add_width(TextWidth::from_char(c));
     }
     Some(offset)
 }
 
+/// Applies a document change to the text - either replacing a range or the entire text.
+/// Returns None if the range of the change ends before it starts.
+fn apply_content_change(text: &mut String, change: TextDocumentContentChangeEvent) -> Option<()> {
+    let Some(range) = change.range else {
+        *text = change.text;
+        return Some(());
+    };
+    let start = position_to_byte_offset(text, range.start);
+    let end = position_to_byte_offset(text, range.end);
+    if start > end {
+        return None;
+    }
+    text.replace_range(start..end, &change.text);
+    Some(())
+}
+
+/// Returns the byte offset of an LSP position in the text, where the character of the position is
+/// counted in UTF-16 code units. A character past the end of the line stands for the end of the
+/// line, and a line past the end of the text stands for the end of the text.
+fn position_to_byte_offset(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(line_end) => line_start += line_end + 1,
+            None => return text.len(),
+        }
+    }
+    let mut character = 0;
+    for (index, c) in text[line_start..].char_indices() {
+        if character >= position.character || c == '\n' {
+            return line_start + index;
+        }
+        character += c.len_utf16() as u32;
+    }
+    text.len()
+}
+
 fn find_node_module(
     db: &dyn SemanticGroup,
     main_file: FileId,
@@ -1221,6 +2294,29 @@ fn find_node_module(
     Some(module)
 }
 
+/// Returns the function with body of a `FunctionWithBody` node - a free function or an impl
+/// function.
+fn function_with_body_from_ast(
+    db: &dyn SemanticGroup,
+    file: FileId,
+    node: SyntaxNode,
+) -> Option<FunctionWithBodyId> {
+    let module_id = find_node_module(db, file, node.clone())?;
+    let module_file_id = ModuleFileId(module_id, FileIndex(0));
+    match lookup_item_from_ast(db, module_file_id, node)[..] {
+        [LookupItemId::ModuleItem(ModuleItemId::FreeFunction(free_function_id))] => {
+            Some(FunctionWithBodyId::Free(free_function_id))
+        }
+        [LookupItemId::ImplFunction(impl_function_id)] => {
+            Some(FunctionWithBodyId::Impl(impl_function_id))
+        }
+        [LookupItemId::TraitFunction(trait_function_id)] => {
+            Some(FunctionWithBodyId::Trait(trait_function_id))
+        }
+        _ => None,
+    }
+}
+
 /// If the node is an identifier, retrieves a hover hint for it.
 fn get_identifier_hint(
     db: &(dyn SemanticGroup + 'static),
@@ -1305,11 +2401,11 @@ fn nearest_semantic_pat(
 
 fn update_crate_roots(
     db: &mut dyn SemanticGroup,
-    source_paths: Vec<(CrateLongId, PathBuf, Edition)>,
+    source_paths: Vec<(CrateLongId, PathBuf, Edition, Option<Vec<SmolStr>>)>,
 ) {
     let source_paths = source_paths
         .into_iter()
-        .filter_map(|(crate_long_id, source_path, edition)| {
+        .filter_map(|(crate_long_id, source_path, edition, dependencies)| {
             let file_stem =
                 source_path.clone().file_stem().map(|x| x.to_string_lossy().to_string());
 
@@ -1322,22 +2418,31 @@ fn update_crate_roots(
             match (crate_root, file_stem) {
                 (Some(crate_root), Some(file_stem)) => {
                     let crate_id = db.intern_crate(crate_long_id);
-                    Some((crate_id, crate_root, edition, file_stem))
+                    Some((crate_id, crate_root, edition, dependencies, file_stem))
                 }
                 _ => None,
             }
         })
         .collect::<Vec<_>>();
 
-    for (crate_id, crate_root, edition, _file_stem) in source_paths.clone() {
-        let crate_root = Directory::Real(crate_root);
-        db.set_crate_config(crate_id, Some(CrateConfiguration { root: crate_root, edition }));
+    for (crate_id, crate_root, edition, dependencies, _file_stem) in source_paths.clone() {
+        let mut config =
+            CrateConfiguration::default_for_root(Directory::Real(crate_root)).with_edition(edition);
+        if let Some(dependencies) = dependencies {
+            config = config.with_dependencies(
+                dependencies
+                    .into_iter()
+                    .map(|name| db.intern_crate(CrateLongId::Real(name)))
+                    .collect(),
+            );
+        }
+        db.set_crate_config(crate_id, Some(config));
     }
 
     let source_paths = source_paths
         .into_iter()
-        .filter(|(_crate_id, _crate_root, _edition, file_stem)| *file_stem != "lib")
-        .map(|(crate_id, _crate_root, _edition, file_stem)| (crate_id, file_stem))
+        .filter(|(_crate_id, _crate_root, _edition, _dependencies, file_stem)| *file_stem != "lib")
+        .map(|(crate_id, _crate_root, _edition, _dependencies, file_stem)| (crate_id, file_stem))
         .collect::<Vec<_>>();
 
     inject_virtual_wrapper_lib(db, source_paths);
@@ -1398,6 +2503,24 @@ fn get_range(db: &dyn FilesGroup, location: &DiagnosticLocation) -> Range {
     Range { start, end }
 }
 
+/// Computes the diagnostics of a file.
+fn file_diagnostics(db: &RootDatabase, file_id: FileId) -> FileDiagnostics {
+    FileDiagnostics {
+        parser: db.file_syntax_diagnostics(file_id),
+        semantic: db.file_semantic_diagnostics(file_id).unwrap_or_default(),
+        lowering: db.file_lowering_diagnostics(file_id).unwrap_or_default(),
+    }
+}
+
+/// Converts the diagnostics of a file to LSP format.
+fn lsp_diagnostics(db: &RootDatabase, file_diagnostics: &FileDiagnostics) -> Vec<Diagnostic> {
+    let mut diags = Vec::new();
+    get_diagnostics(db.upcast(), &mut diags, &file_diagnostics.parser);
+    get_diagnostics(db.upcast(), &mut diags, &file_diagnostics.semantic);
+    get_diagnostics(db.upcast(), &mut diags, &file_diagnostics.lowering);
+    diags
+}
+
 /// Converts internal diagnostics to LSP format.
 fn get_diagnostics<T: DiagnosticEntry>(
     db: &T::DbType,
@@ -0,0 +1,60 @@
+use test_case::test_case;
+use tower_lsp::lsp_types::{Position, Range, TextDocumentContentChangeEvent};
+
+use super::{apply_content_change, position_to_byte_offset};
+
+const TEXT: &str = "fn foo() {\n    let s = 'é𝄞';\n}\n";
+
+#[test_case(0, 0, 0; "start")]
+#[test_case(0, 3, 3; "ascii")]
+#[test_case(0, 100, 10; "past the end of a line")]
+#[test_case(1, 13, 24; "before a two bytes character")]
+#[test_case(1, 14, 26; "before a four bytes character")]
+#[test_case(1, 16, 30; "after a surrogate pair")]
+#[test_case(2, 1, 34; "last line")]
+#[test_case(3, 0, 35; "end of the text")]
+#[test_case(7, 2, 35; "past the end of the text")]
+fn test_position_to_byte_offset(line: u32, character: u32, expected_offset: usize) {
+    assert_eq!(position_to_byte_offset(TEXT, Position { line, character }), expected_offset);
+}
+
+/// Returns a change replacing the given range with the given text.
+fn change(start: (u32, u32), end: (u32, u32), text: &str) -> TextDocumentContentChangeEvent {
+    TextDocumentContentChangeEvent {
+        range: Some(Range {
+            start: Position { line: start.0, character: start.1 },
+            end: Position { line: end.0, character: end.1 },
+        }),
+        range_length: None,
+        text: text.into(),
+    }
+}
+
+#[test]
+fn test_apply_content_change() {
+    let mut text = TEXT.to_string();
+    // Replacing the surrogate pair, counted as two UTF-16 code units.
+    apply_content_change(&mut text, change((1, 14), (1, 16), "x")).unwrap();
+    assert_eq!(text, "fn foo() {\n    let s = 'éx';\n}\n");
+    // Inserting across lines.
+    apply_content_change(&mut text, change((0, 10), (1, 4), " ")).unwrap();
+    assert_eq!(text, "fn foo() { let s = 'éx';\n}\n");
+    // Appending past the end of the text.
+    apply_content_change(&mut text, change((5, 0), (5, 0), "// end\n")).unwrap();
+    assert_eq!(text, "fn foo() { let s = 'éx';\n}\n// end\n");
+    // Replacing the entire text.
+    let full_change = TextDocumentContentChangeEvent {
+        range: None,
+        range_length: None,
+        text: "fn bar() {}\n".into(),
+    };
+    apply_content_change(&mut text, full_change).unwrap();
+    assert_eq!(text, "fn bar() {}\n");
+}
+
+#[test]
+fn test_apply_content_change_reversed_range() {
+    let mut text = TEXT.to_string();
+    assert_eq!(apply_content_change(&mut text, change((1, 4), (0, 2), "")), None);
+    assert_eq!(text, TEXT);
+}
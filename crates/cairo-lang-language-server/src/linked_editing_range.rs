@@ -0,0 +1,71 @@
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{ast, SyntaxNode, Terminal, TypedSyntaxNode};
+use cairo_lang_utils::Upcast;
+use lsp::{LinkedEditingRanges, Position};
+
+use crate::document_highlight::{occurrences, symbol_name};
+use crate::{get_node_and_lookup_items, node_range};
+
+/// The pattern of the text of linked ranges - an identifier.
+const IDENTIFIER_PATTERN: &str = "[a-zA-Z_][a-zA-Z0-9_]*";
+
+/// Returns the ranges of the occurrences of the local variable or parameter at a position, to be
+/// edited together. Returns None for other symbols, as their occurrences may be in other files,
+/// and for variables initializing struct members by the shorthand `S { x }`, where renaming the
+/// variable changes the member.
+pub fn linked_editing_ranges(
+    db: &RootDatabase,
+    file: FileId,
+    position: Position,
+) -> Option<LinkedEditingRanges> {
+    let syntax_db = db.upcast();
+    let (node, lookup_items) = get_node_and_lookup_items(db, file, position)?;
+    if node.kind(syntax_db) != SyntaxKind::TokenIdentifier {
+        return None;
+    }
+    let identifier = ast::TerminalIdentifier::from_syntax_node(syntax_db, node.parent()?);
+    let symbol = symbol_name(db, file, &identifier, &lookup_items)?;
+    let function = local_scope(db, &symbol)?;
+    if has_struct_arg_shorthand(db, &function, &identifier.text(syntax_db)) {
+        return None;
+    }
+    let occurrences = occurrences(db, file, function, &symbol);
+    let ranges =
+        occurrences.iter().map(|occurrence| node_range(db, occurrence)).collect::<Option<_>>()?;
+    Some(LinkedEditingRanges { ranges, word_pattern: Some(IDENTIFIER_PATTERN.to_string()) })
+}
+
+/// Returns the function a declaration name is local to - the function of a parameter or of a
+/// local variable.
+fn local_scope(db: &RootDatabase, symbol: &SyntaxNode) -> Option<SyntaxNode> {
+    let syntax_db = db.upcast();
+    let is_param = symbol.parent()?.kind(syntax_db) == SyntaxKind::Param;
+    let mut in_body = false;
+    let mut node = symbol.parent()?;
+    loop {
+        match node.kind(syntax_db) {
+            SyntaxKind::ExprBlock => in_body = true,
+            SyntaxKind::FunctionWithBody | SyntaxKind::TraitItemFunction => {
+                return (is_param || in_body).then_some(node);
+            }
+            _ => {}
+        }
+        node = node.parent()?;
+    }
+}
+
+/// Returns true if a struct member of the given name is initialized by the shorthand `S { x }` in
+/// the function.
+fn has_struct_arg_shorthand(db: &RootDatabase, function: &SyntaxNode, name: &str) -> bool {
+    let syntax_db = db.upcast();
+    function.descendants(syntax_db).any(|node| {
+        if node.kind(syntax_db) != SyntaxKind::StructArgSingle {
+            return false;
+        }
+        let arg = ast::StructArgSingle::from_syntax_node(syntax_db, node);
+        matches!(arg.arg_expr(syntax_db), ast::OptionStructArgExpr::Empty(_))
+            && arg.identifier(syntax_db).text(syntax_db) == name
+    })
+}
@@ -0,0 +1,213 @@
+//! Structured logging of the server, based on `tracing`.
+//!
+//! Events are written to stderr, prefixed by the spans they occurred in - e.g. every request runs
+//! in a `request{method=...}` span - and the duration of each span is logged at the `debug` level
+//! when it closes. The most verbose logged level is `warn` by default, and is set by the `logging`
+//! initialization option, e.g. `{"logging": {"level": "debug"}}`.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::Instant;
+
+use itertools::Itertools;
+use serde::Deserialize;
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{warn, Event, Level, Metadata, Subscriber};
+
+/// The most verbose level of the logged events.
+static MAX_LEVEL: RwLock<LevelFilter> = RwLock::new(LevelFilter::WARN);
+
+thread_local! {
+    /// The spans entered by the current thread, innermost last.
+    static ENTERED_SPANS: RefCell<Vec<u64>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The `logging` initialization option.
+#[derive(Debug, Default, Deserialize)]
+struct LoggingOptions {
+    /// The most verbose logged level - `off`, `error`, `warn`, `info`, `debug` or `trace`.
+    level: Option<String>,
+}
+
+/// Installs the logger as the global `tracing` subscriber. Does nothing if a subscriber is set
+/// already, e.g. by an embedder of the server.
+pub fn init_logging() {
+    let _ = tracing::subscriber::set_global_default(Logger::default());
+}
+
+/// Applies the `logging` option of the initialization options sent by the client, if set.
+pub fn configure_logging(initialization_options: Option<&serde_json::Value>) {
+    let Some(options) = initialization_options.and_then(|options| options.get("logging")) else {
+        return;
+    };
+    let options = match LoggingOptions::deserialize(options) {
+        Ok(options) => options,
+        Err(err) => {
+            warn!("Invalid logging options: {err}.");
+            return;
+        }
+    };
+    let Some(level) = options.level else {
+        return;
+    };
+    match level.parse::<LevelFilter>() {
+        Ok(level) => *MAX_LEVEL.write().unwrap() = level,
+        Err(_) => warn!("Invalid log level `{level}`."),
+    }
+}
+
+/// Returns the most verbose level of the logged events.
+fn max_level() -> LevelFilter {
+    *MAX_LEVEL.read().unwrap()
+}
+
+/// A `tracing` subscriber writing the enabled events to stderr. Spans are recorded regardless of
+/// the level, so that the events within them are logged with their context.
+#[derive(Default)]
+struct Logger {
+    /// The last id given to a span. Span ids start at 1.
+    last_span_id: AtomicU64,
+    /// The open spans, by their ids.
+    spans: Mutex<HashMap<u64, SpanData>>,
+}
+
+struct SpanData {
+    name: &'static str,
+    fields: Vec<String>,
+    start: Instant,
+    /// The number of handles to the span. The span is closed when the last one is dropped.
+    ref_count: usize,
+}
+impl SpanData {
+    /// Returns the name and fields of the span, e.g. `request{method=textDocument/hover}`.
+    fn label(&self) -> String {
+        if self.fields.is_empty() {
+            self.name.to_string()
+        } else {
+            format!("{}{{{}}}", self.name, self.fields.join(" "))
+        }
+    }
+}
+
+impl Subscriber for Logger {
+    fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        // The level may change at runtime, so it is checked for every span and event.
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.is_span() || *metadata.level() <= max_level()
+    }
+
+    fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+        let mut fields = FieldsFormatter::default();
+        attributes.record(&mut fields);
+        let id = self.last_span_id.fetch_add(1, Ordering::Relaxed) + 1;
+        self.spans.lock().unwrap().insert(
+            id,
+            SpanData {
+                name: attributes.metadata().name(),
+                fields: fields.fields,
+                start: Instant::now(),
+                ref_count: 1,
+            },
+        );
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut fields = FieldsFormatter::default();
+        values.record(&mut fields);
+        if let Some(span) = self.spans.lock().unwrap().get_mut(&span.into_u64()) {
+            span.fields.extend(fields.fields);
+        }
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = FieldsFormatter::default();
+        event.record(&mut fields);
+        let context = match event.parent() {
+            Some(parent) => vec![parent.into_u64()],
+            None if event.is_contextual() => ENTERED_SPANS.with(|spans| spans.borrow().clone()),
+            None => vec![],
+        };
+        let spans = self.spans.lock().unwrap();
+        let context =
+            context.iter().filter_map(|id| spans.get(id)).map(|span| span.label() + ": ").join("");
+        drop(spans);
+        let text = fields.message.into_iter().chain(fields.fields).join(" ");
+        eprintln!("{:5} {context}{text}", event.metadata().level());
+    }
+
+    fn enter(&self, span: &Id) {
+        ENTERED_SPANS.with(|spans| spans.borrow_mut().push(span.into_u64()));
+    }
+
+    fn exit(&self, span: &Id) {
+        ENTERED_SPANS.with(|spans| {
+            let mut spans = spans.borrow_mut();
+            if let Some(index) = spans.iter().rposition(|id| *id == span.into_u64()) {
+                spans.remove(index);
+            }
+        });
+    }
+
+    fn clone_span(&self, span: &Id) -> Id {
+        if let Some(span) = self.spans.lock().unwrap().get_mut(&span.into_u64()) {
+            span.ref_count += 1;
+        }
+        span.clone()
+    }
+
+    fn try_close(&self, span: Id) -> bool {
+        let mut spans = self.spans.lock().unwrap();
+        let Some(data) = spans.get_mut(&span.into_u64()) else {
+            return false;
+        };
+        data.ref_count -= 1;
+        if data.ref_count > 0 {
+            return false;
+        }
+        let data = spans.remove(&span.into_u64()).unwrap();
+        drop(spans);
+        if Level::DEBUG <= max_level() {
+            let elapsed_ms = data.start.elapsed().as_secs_f64() * 1000.0;
+            eprintln!("{:5} {}: finished in {elapsed_ms:.2} ms", Level::DEBUG, data.label());
+        }
+        true
+    }
+}
+
+/// Formats the fields of a span or an event - the `message` field as is, and the other fields as
+/// `name=value`.
+#[derive(Default)]
+struct FieldsFormatter {
+    message: Option<String>,
+    fields: Vec<String>,
+}
+impl FieldsFormatter {
+    fn add(&mut self, field: &Field, value: String) {
+        if field.name() == "message" {
+            self.message = Some(value);
+        } else {
+            self.fields.push(format!("{}={value}", field.name()));
+        }
+    }
+}
+impl Visit for FieldsFormatter {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.add(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.add(field, format!("{value:?}"));
+    }
+}
@@ -0,0 +1,228 @@
+//! Reading of Scarb manifests, for configuring the crates of a Scarb project when its metadata
+//! cannot be obtained from Scarb.
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use cairo_lang_filesystem::cfg::{Cfg, CfgSet};
+use cairo_lang_filesystem::db::Edition;
+use cairo_lang_filesystem::ids::CrateLongId;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+use itertools::Itertools;
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+use smol_str::SmolStr;
+use tracing::warn;
+
+use crate::scarb_service::SCARB_PROJECT_FILE_NAME;
+use crate::MAX_CRATE_DETECTION_DEPTH;
+
+#[cfg(test)]
+#[path = "manifest_test.rs"]
+mod test;
+
+/// The configuration of a project, as read from its manifests.
+pub struct ManifestProject {
+    /// The crates of the packages of the project and of their path dependencies, with the paths of
+    /// their main files, their editions and the names of the crates they depend on.
+    pub crates: Vec<(CrateLongId, PathBuf, Edition, Option<Vec<SmolStr>>)>,
+    /// The `feature` cfg options of the features Scarb enables by default in the packages, i.e. the
+    /// `default` ones of their `[features]` tables and the features these enable. The cfg set is
+    /// shared by all of the crates.
+    pub cfg_set: CfgSet,
+}
+
+/// The parts of a Scarb manifest describing the crates of a project.
+#[derive(Default, Deserialize)]
+struct Manifest {
+    package: Option<Package>,
+    #[serde(default)]
+    dependencies: OrderedHashMap<String, Dependency>,
+    workspace: Option<Workspace>,
+    /// The features of the package, each with the features it enables.
+    #[serde(default)]
+    features: OrderedHashMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    name: String,
+    edition: Option<InheritableEdition>,
+}
+
+/// A package edition, which may be inherited from the `[workspace.package]` table.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum InheritableEdition {
+    Edition(Edition),
+    Workspace { workspace: bool },
+}
+
+/// A dependency of a package. Only the path of path dependencies is of interest.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Dependency {
+    Detailed { path: Option<PathBuf> },
+    Version(IgnoredAny),
+}
+
+#[derive(Default, Deserialize)]
+struct Workspace {
+    #[serde(default)]
+    members: Vec<String>,
+    package: Option<WorkspacePackage>,
+}
+
+#[derive(Default, Deserialize)]
+struct WorkspacePackage {
+    edition: Option<Edition>,
+}
+
+/// The name of the feature listing the features enabled by default.
+const DEFAULT_FEATURE: &str = "default";
+
+/// Reads the manifest of the project containing a file, and of the workspace it belongs to.
+/// Returns None if the file is not in a Scarb project.
+pub fn read_manifest_project(file_path: &Path) -> Result<Option<ManifestProject>> {
+    let file_path = normalize(file_path.to_path_buf());
+    let Some(package_dir) = file_path
+        .ancestors()
+        .skip(1)
+        .take(MAX_CRATE_DETECTION_DEPTH)
+        .find(|directory| directory.join(SCARB_PROJECT_FILE_NAME).is_file())
+    else {
+        return Ok(None);
+    };
+    let (root_dir, root_manifest) = match find_workspace_root(package_dir)? {
+        Some(workspace_root) => workspace_root,
+        None => (package_dir.to_path_buf(), read_manifest(package_dir)?),
+    };
+
+    let workspace = root_manifest.workspace.unwrap_or_default();
+    let workspace_edition = workspace.package.and_then(|package| package.edition);
+    let mut package_dirs = VecDeque::from(member_dirs(&root_dir, &workspace.members));
+    if root_manifest.package.is_some() {
+        package_dirs.push_front(root_dir.clone());
+    }
+    if !package_dirs.iter().any(|dir| dir == package_dir) {
+        package_dirs.push_front(package_dir.to_path_buf());
+    }
+
+    let mut visited = OrderedHashSet::<PathBuf>::default();
+    let mut crates = vec![];
+    let mut cfg_set = CfgSet::new();
+    while let Some(dir) = package_dirs.pop_front() {
+        if !visited.insert(dir.clone()) {
+            continue;
+        }
+        let manifest = match read_manifest(&dir) {
+            Ok(manifest) => manifest,
+            Err(err) => {
+                warn!("{err:?}");
+                continue;
+            }
+        };
+        let Some(package) = manifest.package else {
+            continue;
+        };
+        let edition = match package.edition {
+            Some(InheritableEdition::Edition(edition)) => edition,
+            Some(InheritableEdition::Workspace { workspace: true }) => {
+                workspace_edition.unwrap_or_default()
+            }
+            _ => Edition::default(),
+        };
+        // Dependencies other than path dependencies are only fetched by Scarb.
+        package_dirs.extend(manifest.dependencies.values().filter_map(
+            |dependency| match dependency {
+                Dependency::Detailed { path: Some(path) } => Some(normalize(dir.join(path))),
+                _ => None,
+            },
+        ));
+        for feature in default_features(&manifest.features) {
+            cfg_set.insert(Cfg::kv("feature", feature));
+        }
+        let source_path = dir.join("src").join("lib.cairo");
+        if source_path.exists() {
+            let dependencies = manifest.dependencies.keys().map(|name| name.into()).collect();
+            crates.push((
+                CrateLongId::Real(package.name.into()),
+                source_path,
+                edition,
+                Some(dependencies),
+            ));
+        }
+    }
+
+    Ok(Some(ManifestProject { crates, cfg_set }))
+}
+
+/// Returns the features enabled by default in a package - the ones listed by the `default` feature,
+/// and the ones they enable, transitively.
+fn default_features(features: &OrderedHashMap<String, Vec<String>>) -> OrderedHashSet<String> {
+    let mut enabled = OrderedHashSet::default();
+    let mut pending: Vec<&String> =
+        features.get(DEFAULT_FEATURE).into_iter().flatten().rev().collect();
+    while let Some(feature) = pending.pop() {
+        if enabled.insert(feature.clone()) {
+            pending.extend(features.get(feature).into_iter().flatten().rev());
+        }
+    }
+    enabled
+}
+
+/// Reads and parses the manifest in a directory.
+fn read_manifest(directory: &Path) -> Result<Manifest> {
+    let manifest_path = directory.join(SCARB_PROJECT_FILE_NAME);
+    let content = fs::read_to_string(&manifest_path)
+        .with_context(|| format!("Failed to read `{}`.", manifest_path.display()))?;
+    toml::from_str(&content)
+        .with_context(|| format!("Failed to parse `{}`.", manifest_path.display()))
+}
+
+/// Returns the directory and manifest of the workspace whose members include the package in
+/// `package_dir`, if any.
+fn find_workspace_root(package_dir: &Path) -> Result<Option<(PathBuf, Manifest)>> {
+    for directory in package_dir.ancestors().take(MAX_CRATE_DETECTION_DEPTH) {
+        if !directory.join(SCARB_PROJECT_FILE_NAME).is_file() {
+            continue;
+        }
+        let manifest = read_manifest(directory)?;
+        let Some(workspace) = &manifest.workspace else {
+            continue;
+        };
+        if directory == package_dir
+            || member_dirs(directory, &workspace.members).iter().any(|dir| dir == package_dir)
+        {
+            return Ok(Some((directory.to_path_buf(), manifest)));
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the directories of the members of a workspace. Members are paths relative to the
+/// workspace root, where a `*` last component stands for all of the packages in a directory, in the
+/// order of their names.
+fn member_dirs(root_dir: &Path, members: &[String]) -> Vec<PathBuf> {
+    members
+        .iter()
+        .flat_map(|member| match member.strip_suffix("/*") {
+            Some(parent) => fs::read_dir(root_dir.join(parent))
+                .into_iter()
+                .flatten()
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .filter(|dir| dir.join(SCARB_PROJECT_FILE_NAME).is_file())
+                .sorted()
+                .collect(),
+            None => vec![root_dir.join(member)],
+        })
+        .map(normalize)
+        .collect()
+}
+
+/// Normalizes a path, so that paths to the same directory can be compared.
+fn normalize(path: PathBuf) -> PathBuf {
+    path.canonicalize().unwrap_or(path)
+}
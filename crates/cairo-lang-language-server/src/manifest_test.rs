@@ -0,0 +1,39 @@
+use std::fs;
+
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
+
+use super::{default_features, member_dirs, normalize};
+use crate::scarb_service::SCARB_PROJECT_FILE_NAME;
+
+#[test]
+fn test_default_features() {
+    let features = OrderedHashMap::from_iter(
+        [("default", vec!["a"]), ("a", vec!["b"]), ("b", vec!["a"]), ("c", vec![])].map(
+            |(name, enabled)| {
+                (name.to_string(), enabled.into_iter().map(String::from).collect_vec())
+            },
+        ),
+    );
+    assert_eq!(default_features(&features).into_iter().collect_vec(), ["a", "b"]);
+    assert!(default_features(&OrderedHashMap::default()).is_empty());
+}
+
+#[test]
+fn test_member_dirs_are_sorted() {
+    let root_dir = std::env::temp_dir().join(format!("manifest-test-{}", std::process::id()));
+    for name in ["b", "c", "a"] {
+        let dir = root_dir.join("packages").join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(SCARB_PROJECT_FILE_NAME), "").unwrap();
+    }
+    fs::create_dir_all(root_dir.join("packages").join("not_a_package")).unwrap();
+    let root_dir = normalize(root_dir);
+
+    let dirs = member_dirs(&root_dir, &["packages/*".into(), "z".into()]);
+    fs::remove_dir_all(&root_dir).unwrap();
+    assert_eq!(
+        dirs,
+        ["packages/a", "packages/b", "packages/c", "z"].map(|member| root_dir.join(member))
+    );
+}
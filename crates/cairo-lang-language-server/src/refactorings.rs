@@ -0,0 +1,490 @@
+//! Refactorings of function bodies - extracting an expression into a variable, and inlining a
+//! variable into its usage.
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_defs::ids::{FunctionWithBodyId, VarId};
+use cairo_lang_diagnostics::ToOption;
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_filesystem::span::{TextSpan, TextWidth};
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::items::function_with_body::SemanticExprLookup;
+use cairo_lang_semantic::{Expr, ExprFunctionCallArg, ExprVar, Pattern};
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{ast, SyntaxNode, Terminal, TypedSyntaxNode};
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use cairo_lang_utils::Upcast;
+use lsp::{CodeActionKind, CodeActionOrCommand, Position, Range, TextEdit, Url};
+
+use crate::code_actions::{ancestor_of_kind, code_action, node_at_position, span_range};
+use crate::{function_with_body_from_ast, position_to_offset};
+
+#[cfg(test)]
+#[path = "refactorings_test.rs"]
+mod test;
+
+/// The name of the variables introduced by extracting expressions. A number is appended to it if
+/// the name is already used in the function.
+const EXTRACTED_VARIABLE_NAME: &str = "value";
+
+/// Returns the actions extracting the expression selected in the range into a variable declared
+/// right before the statement containing it - replacing only the selected expression, or all of
+/// its occurrences in the same block, if the expression is pure and its variables are not modified
+/// between the occurrences.
+pub fn extract_variable(
+    db: &RootDatabase,
+    file: FileId,
+    uri: Url,
+    range: Range,
+) -> Vec<CodeActionOrCommand> {
+    let syntax_db = db.upcast();
+    let Some(expr) = selected_expr(db, file, range) else {
+        return vec![];
+    };
+    let Some(function_node) =
+        ancestor_of_kind(syntax_db, expr.clone(), SyntaxKind::FunctionWithBody)
+    else {
+        return vec![];
+    };
+    let Some(function_id) = function_with_body_from_ast(db, file, function_node.clone()) else {
+        return vec![];
+    };
+    if !is_extractable(db, function_id, &expr) {
+        return vec![];
+    }
+    let Some(statement) = enclosing_statement(syntax_db, expr.clone()) else {
+        return vec![];
+    };
+    let name = unused_variable_name(syntax_db, &function_node);
+    let text = expr.clone().get_text_without_trivia(syntax_db);
+    let mut actions = vec![code_action(
+        "Extract into variable".into(),
+        CodeActionKind::REFACTOR_EXTRACT,
+        uri.clone(),
+        extract_edits(db, file, &name, &text, std::slice::from_ref(&expr)),
+        vec![],
+    )];
+
+    if !is_pure(syntax_db, &expr) {
+        return actions;
+    }
+    // Other occurrences refer to the same variables only if they are not shadowed in between.
+    let vars = referenced_vars(db, function_id, &expr);
+    let statement_list = statement.parent().unwrap();
+    let occurrences: Vec<SyntaxNode> = statement_list
+        .descendants(syntax_db)
+        .filter(|node| {
+            node.kind(syntax_db) == expr.kind(syntax_db)
+                && node.clone().get_text_without_trivia(syntax_db) == text
+                && is_extractable(db, function_id, node)
+                && enclosing_statement(syntax_db, node.clone())
+                    .and_then(|statement| statement.parent())
+                    .as_ref()
+                    == Some(&statement_list)
+                && referenced_vars(db, function_id, node) == vars
+        })
+        .collect();
+    if let [first, .., last] = &occurrences[..] {
+        let span = TextSpan {
+            start: first.span_without_trivia(syntax_db).start,
+            end: last.span_without_trivia(syntax_db).end,
+        };
+        if !is_written_in(db, file, function_id, &vars, span) {
+            actions.push(code_action(
+                format!("Extract into variable, replacing all {} occurrences", occurrences.len()),
+                CodeActionKind::REFACTOR_EXTRACT,
+                uri,
+                extract_edits(db, file, &name, &text, &occurrences),
+                vec![],
+            ));
+        }
+    }
+    actions
+}
+
+/// Returns the action inlining the variable declared by the `let` statement at the position into
+/// its single usage, and removing the statement. The variable is inlined only if its value is pure
+/// and the variables it refers to are not modified before the usage.
+pub fn inline_variable(
+    db: &RootDatabase,
+    file: FileId,
+    uri: Url,
+    position: Position,
+) -> Option<CodeActionOrCommand> {
+    let syntax_db = db.upcast();
+    let node = node_at_position(db, file, position)?;
+    let statement = ancestor_of_kind(syntax_db, node, SyntaxKind::StatementLet)?;
+    let let_statement = ast::StatementLet::from_syntax_node(syntax_db, statement.clone());
+    // Variables without modifiers are parsed as single segment paths. Variables with modifiers,
+    // such as `mut`, are not inlined.
+    let pattern = let_statement.pattern(syntax_db);
+    let ast::Pattern::Path(path) = &pattern else {
+        return None;
+    };
+    let [ast::PathSegment::Simple(segment)] = &path.elements(syntax_db)[..] else {
+        return None;
+    };
+    let function_node =
+        ancestor_of_kind(syntax_db, statement.clone(), SyntaxKind::FunctionWithBody)?;
+    let function_id = function_with_body_from_ast(db, file, function_node)?;
+    let pattern_id = db.lookup_pattern_by_ptr(function_id, pattern.stable_ptr()).to_option()?;
+    let Pattern::Variable(variable) = db.pattern_semantic(function_id, pattern_id) else {
+        return None;
+    };
+    let body = db.function_body(function_id).to_option()?;
+    let usages: Vec<_> = body
+        .exprs
+        .iter()
+        .filter_map(|(_, expr)| match expr {
+            Expr::Var(ExprVar { var: VarId::Local(var_id), stable_ptr, .. })
+                if *var_id == variable.var.id =>
+            {
+                Some(stable_ptr.untyped())
+            }
+            _ => None,
+        })
+        .collect();
+    let [usage] = usages[..] else {
+        return None;
+    };
+    if usage.file_id(syntax_db) != file {
+        return None;
+    }
+    let usage = usage.lookup(syntax_db);
+    // Usages in struct constructor shorthands, such as `A { x }`, refer to the path of the
+    // constructor.
+    if usage.kind(syntax_db) != SyntaxKind::ExprPath
+        || usage.parent()?.kind(syntax_db) == SyntaxKind::ExprStructCtorCall
+    {
+        return None;
+    }
+    // Inlining into a loop would evaluate the expression on every iteration.
+    let mut ancestor = usage.parent()?;
+    while Some(&ancestor) != statement.parent().as_ref() {
+        if matches!(
+            ancestor.kind(syntax_db),
+            SyntaxKind::ExprLoop | SyntaxKind::ExprWhile | SyntaxKind::ExprFor
+        ) {
+            return None;
+        }
+        ancestor = ancestor.parent()?;
+    }
+
+    let rhs = let_statement.rhs(syntax_db);
+    if !is_pure(syntax_db, &rhs.as_syntax_node()) {
+        return None;
+    }
+    let vars = referenced_vars(db, function_id, &rhs.as_syntax_node());
+    let span = TextSpan {
+        start: statement.span_without_trivia(syntax_db).end,
+        end: usage.span_without_trivia(syntax_db).start,
+    };
+    if is_written_in(db, file, function_id, &vars, span) {
+        return None;
+    }
+    let rhs_text = rhs.as_syntax_node().get_text_without_trivia(syntax_db);
+    let new_text =
+        if needs_parentheses(syntax_db, &rhs, &usage) { format!("({rhs_text})") } else { rhs_text };
+    let edits = vec![
+        TextEdit {
+            range: span_range(db, file, removed_statement_span(db, file, &statement)?),
+            new_text: "".into(),
+        },
+        TextEdit { range: span_range(db, file, usage.span_without_trivia(syntax_db)), new_text },
+    ];
+    Some(code_action(
+        format!("Inline variable `{}`", segment.ident(syntax_db).text(syntax_db)),
+        CodeActionKind::REFACTOR_INLINE,
+        uri,
+        edits,
+        vec![],
+    ))
+}
+
+/// Returns the expression node whose span is the range, ignoring the whitespace around the
+/// selected text.
+fn selected_expr(db: &RootDatabase, file: FileId, range: Range) -> Option<SyntaxNode> {
+    let syntax_db = db.upcast();
+    let content = db.file_content(file)?;
+    let summary = db.file_summary(file)?;
+    let start = position_to_offset(summary.clone(), range.start, &content)?;
+    let end = position_to_offset(summary, range.end, &content)?;
+    let selected = TextSpan { start, end }.take(&content);
+    let trimmed = selected.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let start = start
+        .add_width(TextWidth::from_str(&selected[..selected.len() - selected.trim_start().len()]));
+    let span = TextSpan { start, end: start.add_width(TextWidth::from_str(trimmed)) };
+
+    let mut node = db.file_syntax(file).to_option()?.lookup_offset(syntax_db, start);
+    loop {
+        let node_span = node.span_without_trivia(syntax_db);
+        if node_span.start != span.start || node_span.end > span.end {
+            return None;
+        }
+        if node_span == span && ast::Expr::is_variant(node.kind(syntax_db)) {
+            return Some(node);
+        }
+        node = node.parent()?;
+    }
+}
+
+/// Returns whether the expression can be extracted into a variable - it is an expression of the
+/// function body, rather than a path of a call or a member name, and it is not assigned to or
+/// passed as a `ref` argument.
+fn is_extractable(db: &RootDatabase, function_id: FunctionWithBodyId, node: &SyntaxNode) -> bool {
+    let syntax_db = db.upcast();
+    let ptr = ast::Expr::from_syntax_node(syntax_db, node.clone()).stable_ptr();
+    if db.lookup_expr_by_ptr(function_id, ptr).is_err() {
+        return false;
+    }
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    match parent.kind(syntax_db) {
+        SyntaxKind::ExprFunctionCall
+        | SyntaxKind::ExprStructCtorCall
+        | SyntaxKind::ExprInlineMacro => return false,
+        SyntaxKind::ExprBinary => {
+            let binary = ast::ExprBinary::from_syntax_node(syntax_db, parent);
+            if matches!(binary.op(syntax_db), ast::BinaryOperator::Dot(_))
+                && binary.rhs(syntax_db).as_syntax_node() == *node
+            {
+                return false;
+            }
+        }
+        _ => {}
+    }
+
+    // The base of a written member path, such as `a` in `a.b = c`, is written as well.
+    let mut written = node.clone();
+    while let Some(parent) = written.parent() {
+        if parent.kind(syntax_db) != SyntaxKind::ExprBinary {
+            break;
+        }
+        let binary = ast::ExprBinary::from_syntax_node(syntax_db, parent.clone());
+        if binary.lhs(syntax_db).as_syntax_node() != written {
+            break;
+        }
+        match binary.op(syntax_db) {
+            ast::BinaryOperator::Dot(_) => written = parent,
+            ast::BinaryOperator::Eq(_)
+            | ast::BinaryOperator::PlusEq(_)
+            | ast::BinaryOperator::MinusEq(_)
+            | ast::BinaryOperator::MulEq(_)
+            | ast::BinaryOperator::DivEq(_)
+            | ast::BinaryOperator::ModEq(_) => return false,
+            _ => break,
+        }
+    }
+    let Some(arg) = written.parent().and_then(|clause| clause.parent()) else {
+        return true;
+    };
+    arg.kind(syntax_db) != SyntaxKind::Arg
+        || ast::Arg::from_syntax_node(syntax_db, arg)
+            .modifiers(syntax_db)
+            .elements(syntax_db)
+            .is_empty()
+}
+
+/// Returns whether evaluating the expression has no effects, so it may be evaluated at a different
+/// point - it consists only of variables, literals, member accesses and operators, and calls no
+/// functions.
+fn is_pure(db: &dyn SyntaxGroup, node: &SyntaxNode) -> bool {
+    node.descendants(db).all(|node| match node.kind(db) {
+        SyntaxKind::ExprFunctionCall
+        | SyntaxKind::ExprInlineMacro
+        | SyntaxKind::ExprBlock
+        | SyntaxKind::ExprMatch
+        | SyntaxKind::ExprIf
+        | SyntaxKind::ExprLoop
+        | SyntaxKind::ExprWhile
+        | SyntaxKind::ExprFor
+        | SyntaxKind::ExprErrorPropagate
+        | SyntaxKind::ExprIndexed => false,
+        SyntaxKind::ExprBinary => !matches!(
+            ast::ExprBinary::from_syntax_node(db, node).op(db),
+            ast::BinaryOperator::Eq(_)
+                | ast::BinaryOperator::PlusEq(_)
+                | ast::BinaryOperator::MinusEq(_)
+                | ast::BinaryOperator::MulEq(_)
+                | ast::BinaryOperator::DivEq(_)
+                | ast::BinaryOperator::ModEq(_)
+        ),
+        _ => true,
+    })
+}
+
+/// Returns whether any of the variables is modified by an expression of the function within the
+/// span - assigned to, or passed as a `ref` argument (including as the `self` of a method or the
+/// left operand of a compound assignment).
+fn is_written_in(
+    db: &RootDatabase,
+    file: FileId,
+    function_id: FunctionWithBodyId,
+    vars: &[VarId],
+    span: TextSpan,
+) -> bool {
+    let syntax_db = db.upcast();
+    let Some(body) = db.function_body(function_id).to_option() else {
+        return true;
+    };
+    body.exprs.iter().any(|(_, expr)| {
+        let is_written = match expr {
+            Expr::Assignment(assignment) => vars.contains(&assignment.ref_arg.base_var()),
+            Expr::FunctionCall(call) => call.args.iter().any(|arg| match arg {
+                ExprFunctionCallArg::Reference(path) => vars.contains(&path.base_var()),
+                ExprFunctionCallArg::Value(_) => false,
+            }),
+            _ => false,
+        };
+        if !is_written {
+            return false;
+        }
+        let ptr = expr.stable_ptr().untyped();
+        // The location of writes in generated code is unknown, so they are assumed to be in the
+        // span.
+        if ptr.file_id(syntax_db) != file {
+            return true;
+        }
+        let expr_span = ptr.lookup(syntax_db).span_without_trivia(syntax_db);
+        expr_span.start < span.end && span.start < expr_span.end
+    })
+}
+
+/// Returns the span to remove when removing a statement - the statement with its trailing trivia,
+/// keeping the comments before it.
+fn removed_statement_span(
+    db: &RootDatabase,
+    file: FileId,
+    statement: &SyntaxNode,
+) -> Option<TextSpan> {
+    let syntax_db = db.upcast();
+    let span = statement.span(syntax_db);
+    let start = statement.span_without_trivia(syntax_db).start;
+    let content = db.file_content(file)?;
+    let leading_trivia = TextSpan { start: span.start, end: start }.take(&content);
+    // The indentation of the statement is removed as well, unless it follows another statement on
+    // the same line.
+    let start = match leading_trivia.rfind('\n') {
+        Some(index) => span.start.add_width(TextWidth::from_str(&leading_trivia[..=index])),
+        None => start,
+    };
+    Some(TextSpan { start, end: span.end })
+}
+
+/// Returns the statement of the innermost block containing the expression, before which the
+/// variable of the extracted expression is declared. Returns None for expressions of match arms
+/// and `else if` conditions, which are not evaluated unconditionally.
+fn enclosing_statement(db: &dyn SyntaxGroup, mut node: SyntaxNode) -> Option<SyntaxNode> {
+    loop {
+        let parent = node.parent()?;
+        match parent.kind(db) {
+            SyntaxKind::StatementList => return Some(node),
+            SyntaxKind::MatchArm | SyntaxKind::ElseClause => return None,
+            _ => node = parent,
+        }
+    }
+}
+
+/// Returns the variables referred to by the paths in the expression, in order.
+fn referenced_vars(
+    db: &RootDatabase,
+    function_id: FunctionWithBodyId,
+    node: &SyntaxNode,
+) -> Vec<VarId> {
+    let syntax_db = db.upcast();
+    node.descendants(syntax_db)
+        .filter(|node| node.kind(syntax_db) == SyntaxKind::ExprPath)
+        .filter_map(|node| {
+            let ptr = ast::ExprPath::from_syntax_node(syntax_db, node).stable_ptr().into();
+            let expr_id = db.lookup_expr_by_ptr(function_id, ptr).to_option()?;
+            match db.expr_semantic(function_id, expr_id) {
+                Expr::Var(ExprVar { var, .. }) => Some(var),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Returns the edits declaring a variable with the given name and value before the statement of
+/// the first occurrence, and replacing the occurrences with the variable.
+fn extract_edits(
+    db: &RootDatabase,
+    file: FileId,
+    name: &str,
+    text: &str,
+    occurrences: &[SyntaxNode],
+) -> Vec<TextEdit> {
+    let syntax_db = db.upcast();
+    let Some(statement) = enclosing_statement(syntax_db, occurrences[0].clone()) else {
+        return vec![];
+    };
+    let start = statement.span_without_trivia(syntax_db).start;
+    let indent =
+        " ".repeat(start.position_in_file(db.upcast(), file).map_or(0, |position| position.col));
+    let declaration = format!("let {name} = {text};\n{indent}");
+    let mut edits = vec![TextEdit {
+        range: span_range(db, file, TextSpan { start, end: start }),
+        new_text: declaration.clone(),
+    }];
+    for occurrence in occurrences {
+        let span = occurrence.span_without_trivia(syntax_db);
+        // An occurrence starting the statement is replaced along with the declaration, as the
+        // edits may not overlap.
+        if span.start == start {
+            edits[0] = TextEdit {
+                range: span_range(db, file, span),
+                new_text: format!("{declaration}{name}"),
+            };
+        } else {
+            edits.push(TextEdit { range: span_range(db, file, span), new_text: name.into() });
+        }
+    }
+    edits
+}
+
+/// Returns a name for an extracted variable that is not used by any identifier of the function.
+fn unused_variable_name(db: &dyn SyntaxGroup, function_node: &SyntaxNode) -> String {
+    let identifiers: UnorderedHashSet<String> = function_node
+        .descendants(db)
+        .filter(|node| node.kind(db) == SyntaxKind::TerminalIdentifier)
+        .map(|node| ast::TerminalIdentifier::from_syntax_node(db, node).text(db).into())
+        .collect();
+    let mut name = EXTRACTED_VARIABLE_NAME.to_string();
+    let mut index = 0;
+    while identifiers.contains(&name) {
+        index += 1;
+        name = format!("{EXTRACTED_VARIABLE_NAME}_{index}");
+    }
+    name
+}
+
+/// Returns whether the inlined expression has to be parenthesized at the usage - it is an operator
+/// expression or a compound expression, used as an operand.
+fn needs_parentheses(db: &dyn SyntaxGroup, rhs: &ast::Expr, usage: &SyntaxNode) -> bool {
+    let is_compound = match rhs {
+        ast::Expr::Binary(binary) => !matches!(binary.op(db), ast::BinaryOperator::Dot(_)),
+        ast::Expr::Unary(_)
+        | ast::Expr::Block(_)
+        | ast::Expr::Match(_)
+        | ast::Expr::If(_)
+        | ast::Expr::Loop(_)
+        | ast::Expr::While(_)
+        | ast::Expr::For(_) => true,
+        _ => false,
+    };
+    is_compound
+        && usage.parent().is_some_and(|parent| {
+            matches!(
+                parent.kind(db),
+                SyntaxKind::ExprBinary
+                    | SyntaxKind::ExprUnary
+                    | SyntaxKind::ExprErrorPropagate
+                    | SyntaxKind::ExprIndexed
+            )
+        })
+}
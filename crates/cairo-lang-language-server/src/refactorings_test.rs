@@ -0,0 +1,146 @@
+use indoc::indoc;
+use lsp::Range;
+use test_case::test_case;
+
+use super::{extract_variable, inline_variable};
+use crate::test_utils::{apply_code_action, offset_position, setup_test_file, test_file_uri};
+
+/// Returns the range of the first occurrence of `needle` in the text.
+fn find_range(text: &str, needle: &str) -> Range {
+    let start = text.find(needle).unwrap();
+    Range { start: offset_position(text, start), end: offset_position(text, start + needle.len()) }
+}
+
+/// Returns the texts resulting from applying each of the actions extracting the first occurrence
+/// of `selected` in the code.
+fn extract(code: &str, selected: &str) -> Vec<String> {
+    let (db, file) = setup_test_file(code);
+    extract_variable(&db, file, test_file_uri(), find_range(code, selected))
+        .iter()
+        .map(|action| apply_code_action(code, action))
+        .collect()
+}
+
+/// Returns the text resulting from inlining the variable of the first `let` statement in the code,
+/// if it is inlined.
+fn inline(code: &str) -> Option<String> {
+    let (db, file) = setup_test_file(code);
+    let action = inline_variable(&db, file, test_file_uri(), find_range(code, "let").start)?;
+    Some(apply_code_action(code, &action))
+}
+
+#[test]
+fn test_extract_all_occurrences() {
+    let code = indoc! {"
+        fn foo(x: felt252) -> felt252 {
+            let a = x + 1;
+            let b = x + 1;
+            a + b
+        }
+    "};
+    assert_eq!(
+        extract(code, "x + 1"),
+        [
+            indoc! {"
+                fn foo(x: felt252) -> felt252 {
+                    let value = x + 1;
+                    let a = value;
+                    let b = x + 1;
+                    a + b
+                }
+            "},
+            indoc! {"
+                fn foo(x: felt252) -> felt252 {
+                    let value = x + 1;
+                    let a = value;
+                    let b = value;
+                    a + b
+                }
+            "},
+        ]
+    );
+}
+
+#[test_case("x += 1;"; "compound assignment")]
+#[test_case("x = 3;"; "assignment")]
+#[test_case("bar(ref x);"; "ref argument")]
+fn test_extract_occurrences_around_mutation(mutation: &str) {
+    let code = format!(
+        indoc! {"
+            fn bar(ref x: felt252) {{}}
+            fn foo(mut x: felt252) -> felt252 {{
+                let a = x + 1;
+                {}
+                let b = x + 1;
+                a + b
+            }}
+        "},
+        mutation
+    );
+    // Only the selected occurrence is extracted, as the other one has a different value.
+    assert_eq!(extract(&code, "x + 1").len(), 1);
+}
+
+#[test]
+fn test_extract_impure_occurrences() {
+    let code = indoc! {"
+        fn bar(x: felt252) -> felt252 { x }
+        fn foo(x: felt252) -> felt252 {
+            let a = bar(x);
+            let b = bar(x);
+            a + b
+        }
+    "};
+    // Only the selected occurrence is extracted, as the calls may not be merged.
+    assert_eq!(extract(code, "bar(x)").len(), 1);
+}
+
+#[test]
+fn test_inline_keeps_leading_comments() {
+    let code = indoc! {"
+        fn foo(x: felt252) -> felt252 {
+            // The next value.
+            let a = x + 1;
+            a * 2
+        }
+    "};
+    assert_eq!(
+        inline(code).unwrap(),
+        indoc! {"
+            fn foo(x: felt252) -> felt252 {
+                // The next value.
+                (x + 1) * 2
+            }
+        "}
+    );
+}
+
+#[test_case("x += 1;"; "compound assignment")]
+#[test_case("x = 3;"; "assignment")]
+#[test_case("bar(ref x);"; "ref argument")]
+fn test_inline_after_mutation(mutation: &str) {
+    let code = format!(
+        indoc! {"
+            fn bar(ref x: felt252) {{}}
+            fn foo(mut x: felt252) -> felt252 {{
+                let a = x + 1;
+                {}
+                a * x
+            }}
+        "},
+        mutation
+    );
+    assert_eq!(inline(&code), None);
+}
+
+#[test]
+fn test_inline_impure_value() {
+    let code = indoc! {"
+        fn bar(x: felt252) -> felt252 { x }
+        fn foo(x: felt252) -> felt252 {
+            let a = bar(x);
+            a * x
+        }
+    "};
+    assert_eq!(inline(code), None);
+}
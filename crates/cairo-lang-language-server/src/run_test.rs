@@ -0,0 +1,178 @@
+use std::collections::BTreeMap;
+
+use anyhow::Context;
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::FunctionWithBodyId;
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_runner::RunResultValue;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_test_plugin::test_config::TestConfig;
+use cairo_lang_test_plugin::{compile_test_prepared_db, try_extract_test_config, TestCompilation};
+use cairo_lang_test_runner::{
+    create_test_runner, format_for_panic, run_single_test, TestResult, TestStatus,
+};
+use cairo_lang_utils::Upcast;
+use lsp::notification::Notification;
+use lsp::TextDocumentIdentifier;
+use serde::{Deserialize, Serialize};
+
+/// The command running a single test, invoked by the test code lenses.
+pub const RUN_TEST_COMMAND: &str = "cairo1.runTest";
+
+/// The argument of [RUN_TEST_COMMAND].
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunTestParams {
+    /// The document holding the test.
+    pub text_document: TextDocumentIdentifier,
+    /// The name of the test, by which it is found in the compiled tests.
+    pub name: String,
+    /// Whether to collect the resources used by each function called by the test.
+    pub profile: bool,
+}
+
+#[derive(Debug)]
+pub struct TestRunStarted {}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+pub struct TestRunStartedParams {
+    pub name: String,
+}
+
+impl Notification for TestRunStarted {
+    type Params = TestRunStartedParams;
+    const METHOD: &'static str = "cairo/testRunStarted";
+}
+
+#[derive(Debug)]
+pub struct TestRunFinished {}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestRunFinishedParams {
+    pub name: String,
+    pub status: TestRunStatus,
+    /// Why the test failed, or why it could not run.
+    pub message: Option<String>,
+    /// The estimated gas usage of the test, if it ran.
+    pub gas_usage: Option<i64>,
+    /// The resources used by each function called by the test, if it was profiled.
+    pub profile: Option<Vec<FunctionProfile>>,
+}
+
+impl Notification for TestRunFinished {
+    type Params = TestRunFinishedParams;
+    const METHOD: &'static str = "cairo/testRunFinished";
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TestRunStatus {
+    Passed,
+    Failed,
+    /// The test could not be compiled or run.
+    Error,
+}
+
+/// The resources used by the calls to a function in a profiled test run.
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionProfile {
+    pub function: String,
+    pub calls: usize,
+    pub steps: usize,
+    pub memory_cells: usize,
+    pub builtins: BTreeMap<String, usize>,
+}
+
+impl TestRunFinishedParams {
+    /// Returns the params of a test run that could not complete.
+    pub fn error(name: String, message: String) -> Self {
+        Self {
+            name,
+            status: TestRunStatus::Error,
+            message: Some(message),
+            gas_usage: None,
+            profile: None,
+        }
+    }
+}
+
+/// Returns the name of the test defined by a function, or None if the function is not a test.
+pub fn test_name(db: &RootDatabase, function_id: FunctionWithBodyId) -> Option<String> {
+    let FunctionWithBodyId::Free(free_function_id) = function_id else {
+        return None;
+    };
+    let attrs = db.function_with_body_attributes(function_id).ok()?;
+    try_extract_test_config(db.upcast(), attrs).ok()??;
+    Some(cairo_lang_test_plugin::test_name(db, free_function_id))
+}
+
+/// A test compiled along with the other tests of its crate, which may run without the database.
+pub struct CompiledTest {
+    compilation: TestCompilation,
+    test: TestConfig,
+}
+
+/// Compiles the tests of the crate of a file, and finds the test with the given name.
+pub fn compile_test(db: &RootDatabase, file: FileId, name: &str) -> anyhow::Result<CompiledTest> {
+    let module_id = db
+        .file_modules(file)
+        .ok()
+        .and_then(|modules| modules.first().copied())
+        .context("The test file does not belong to a crate.")?;
+    let crate_id = module_id.owning_crate(db.upcast());
+    let compilation = compile_test_prepared_db(db, true, vec![crate_id], vec![crate_id])?;
+    let (_, test) = compilation
+        .named_tests
+        .iter()
+        .find(|(test_name, _)| test_name == name)
+        .cloned()
+        .with_context(|| format!("Test `{name}` was not found."))?;
+    Ok(CompiledTest { compilation, test })
+}
+
+/// Runs a compiled test with the given name.
+pub fn run_test(compiled: CompiledTest, name: String, profile: bool) -> TestRunFinishedParams {
+    let result = match run_compiled_test(compiled, &name, profile) {
+        Ok(result) => result,
+        Err(err) => return TestRunFinishedParams::error(name, format!("{err:?}")),
+    };
+    let (status, message) = match result.status {
+        TestStatus::Success => (TestRunStatus::Passed, None),
+        TestStatus::Fail(RunResultValue::Success(_)) => {
+            (TestRunStatus::Failed, Some("Expected panic but finished successfully.".into()))
+        }
+        TestStatus::Fail(RunResultValue::Panic(values)) => {
+            (TestRunStatus::Failed, Some(format_for_panic(values.into_iter())))
+        }
+    };
+    let profile = result.resources.map(|resources| {
+        resources
+            .into_iter()
+            .map(|(function, resources)| FunctionProfile {
+                function,
+                calls: resources.calls,
+                steps: resources.steps,
+                memory_cells: resources.memory_cells,
+                builtins: resources.builtins.into_iter().collect(),
+            })
+            .collect()
+    });
+    TestRunFinishedParams { name, status, message, gas_usage: result.gas_usage, profile }
+}
+
+/// Runs a compiled test with the given name, returning its result.
+fn run_compiled_test(
+    CompiledTest { compilation, test }: CompiledTest,
+    name: &str,
+    profile: bool,
+) -> anyhow::Result<TestResult> {
+    let runner = create_test_runner(
+        compilation.sierra_program,
+        compilation.function_set_costs,
+        compilation.contracts_info,
+    )?;
+    run_single_test(&runner, name, test, profile)
+}
@@ -4,13 +4,13 @@ use std::path::PathBuf;
 use anyhow::{Context, Result};
 use cairo_lang_filesystem::db::Edition;
 use cairo_lang_filesystem::ids::CrateLongId;
-use lsp::Url;
 use scarb_metadata::Metadata;
+use smol_str::SmolStr;
 
 use crate::NotificationService;
 
 const MAX_CRATE_DETECTION_DEPTH: usize = 20;
-const SCARB_PROJECT_FILE_NAME: &str = "Scarb.toml";
+pub const SCARB_PROJECT_FILE_NAME: &str = "Scarb.toml";
 
 pub struct ScarbService {
     scarb_path: Option<PathBuf>,
@@ -70,10 +70,12 @@ impl ScarbService {
         result
     }
 
+    /// Returns the crates of the compilation units of the project, with the paths of their main
+    /// files, their editions and the names of the crates they depend on.
     pub async fn crate_source_paths(
         &self,
         root_path: PathBuf,
-    ) -> Result<Vec<(CrateLongId, PathBuf, Edition)>> {
+    ) -> Result<Vec<(CrateLongId, PathBuf, Edition, Option<Vec<SmolStr>>)>> {
         let metadata = self
             .scarb_metadata(root_path)
             .await
@@ -86,10 +88,9 @@ impl ScarbService {
                 let source_path: PathBuf = component.source_path.into();
                 if source_path.exists() {
                     let crate_id = CrateLongId::Real(component.name.as_str().into());
-                    let edition = metadata
-                        .packages
-                        .iter()
-                        .find(|package| package.id == component.package)
+                    let package =
+                        metadata.packages.iter().find(|package| package.id == component.package);
+                    let edition = package
                         .and_then(|package| {
                             package
                                 .edition
@@ -97,7 +98,14 @@ impl ScarbService {
                                 .map(|edition| serde_json::from_value(edition.into()).unwrap())
                         })
                         .unwrap_or_default();
-                    Some((crate_id, source_path, edition))
+                    let dependencies = package.map(|package| {
+                        package
+                            .dependencies
+                            .iter()
+                            .map(|dependency| dependency.name.as_str().into())
+                            .collect()
+                    });
+                    Some((crate_id, source_path, edition, dependencies))
                 } else {
                     None
                 }
@@ -121,7 +129,3 @@ impl ScarbService {
         Ok(corelib_package)
     }
 }
-
-pub fn is_scarb_manifest_path(file_path: &Url) -> bool {
-    file_path.path().ends_with(SCARB_PROJECT_FILE_NAME)
-}
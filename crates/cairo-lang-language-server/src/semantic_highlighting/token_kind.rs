@@ -189,6 +189,9 @@ impl SemanticTokenKind {
                     LookupItemId::ImplFunction(impl_function_id) => {
                         FunctionWithBodyId::Impl(impl_function_id)
                     }
+                    LookupItemId::TraitFunction(trait_function_id) => {
+                        FunctionWithBodyId::Trait(trait_function_id)
+                    }
                     _ => {
                         continue;
                     }
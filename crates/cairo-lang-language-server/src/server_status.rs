@@ -0,0 +1,36 @@
+//! The `cairo/serverStatus` request, reporting the load of the server.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerStatusResponse {
+    /// The number of requests and background tasks waiting for the database or running on it.
+    pub queue_depth: usize,
+    /// The resident memory of the server process, in bytes. None if unknown on the platform.
+    pub memory_bytes: Option<u64>,
+}
+
+/// Counts a task as pending, as long as it is alive.
+pub struct PendingTask<'a>(&'a AtomicUsize);
+impl<'a> PendingTask<'a> {
+    pub fn new(pending_tasks: &'a AtomicUsize) -> Self {
+        pending_tasks.fetch_add(1, Ordering::SeqCst);
+        Self(pending_tasks)
+    }
+}
+impl Drop for PendingTask<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Returns the resident memory of the process in bytes, as reported by `/proc` on Linux.
+pub fn resident_memory_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let resident = status.lines().find_map(|line| line.strip_prefix("VmRSS:"))?;
+    let kilobytes: u64 = resident.trim().strip_suffix("kB")?.trim().parse().ok()?;
+    Some(kilobytes * 1024)
+}
@@ -0,0 +1,182 @@
+//! Opt-in telemetry of the language server requests.
+//!
+//! Only anonymized metrics are recorded - the request type, its latency and the number of results -
+//! and never file names or contents. Telemetry is enabled by setting the `CAIRO_LSP_TELEMETRY`
+//! environment variable to either a file path, to which the metrics are appended as JSON lines, or
+//! an `http://` or `https://` endpoint, to which each metrics record is posted as JSON.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::mpsc::{sync_channel, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use tower_lsp::lsp_types::{
+    CodeLens, CompletionResponse, GotoDefinitionResponse, Hover, LinkedEditingRanges,
+    SemanticTokensResult, Url,
+};
+use tracing::warn;
+
+/// The environment variable enabling the telemetry.
+const TELEMETRY_ENV_VAR: &str = "CAIRO_LSP_TELEMETRY";
+/// The timeout for posting a metrics record to an endpoint.
+const ENDPOINT_TIMEOUT: Duration = Duration::from_secs(5);
+/// The maximal number of metrics records waiting to be posted to an endpoint. Records are dropped
+/// while the queue is full.
+const ENDPOINT_QUEUE_CAPACITY: usize = 1024;
+
+/// The metrics of a handled request.
+#[derive(Clone, Debug, Serialize)]
+pub struct RequestMetrics {
+    /// The LSP method of the request, e.g. `textDocument/completion`.
+    pub request: &'static str,
+    /// The time it took to handle the request, in milliseconds.
+    pub latency_ms: f64,
+    /// Whether the request was handled successfully.
+    pub succeeded: bool,
+    /// The number of results of the request (e.g. completion items), if it succeeded.
+    pub result_count: Option<usize>,
+}
+
+/// A destination for request metrics. Implement this trait to collect the metrics in a custom way.
+pub trait TelemetrySink: Send + Sync {
+    /// Records the metrics of a handled request.
+    fn record(&self, metrics: &RequestMetrics);
+}
+
+/// The number of results of a request, for its metrics.
+pub trait ResultCount {
+    fn result_count(&self) -> usize;
+}
+impl<T: ResultCount> ResultCount for Option<T> {
+    fn result_count(&self) -> usize {
+        self.as_ref().map_or(0, ResultCount::result_count)
+    }
+}
+impl<T> ResultCount for Vec<T> {
+    fn result_count(&self) -> usize {
+        self.len()
+    }
+}
+impl ResultCount for CompletionResponse {
+    fn result_count(&self) -> usize {
+        match self {
+            CompletionResponse::Array(items) => items.len(),
+            CompletionResponse::List(list) => list.items.len(),
+        }
+    }
+}
+impl ResultCount for SemanticTokensResult {
+    fn result_count(&self) -> usize {
+        match self {
+            SemanticTokensResult::Tokens(tokens) => tokens.data.len(),
+            SemanticTokensResult::Partial(partial) => partial.data.len(),
+        }
+    }
+}
+impl ResultCount for CodeLens {
+    fn result_count(&self) -> usize {
+        1
+    }
+}
+impl ResultCount for Hover {
+    fn result_count(&self) -> usize {
+        1
+    }
+}
+impl ResultCount for GotoDefinitionResponse {
+    fn result_count(&self) -> usize {
+        match self {
+            GotoDefinitionResponse::Scalar(_) => 1,
+            GotoDefinitionResponse::Array(locations) => locations.len(),
+            GotoDefinitionResponse::Link(links) => links.len(),
+        }
+    }
+}
+impl ResultCount for LinkedEditingRanges {
+    fn result_count(&self) -> usize {
+        self.ranges.len()
+    }
+}
+
+/// Returns the telemetry sink configured by the `CAIRO_LSP_TELEMETRY` environment variable, if any.
+pub fn telemetry_sink_from_env() -> Option<Arc<dyn TelemetrySink>> {
+    let target = std::env::var(TELEMETRY_ENV_VAR).ok().filter(|target| !target.is_empty())?;
+    if target.starts_with("http://") || target.starts_with("https://") {
+        let sink = Url::parse(&target)
+            .map_err(|err| err.to_string())
+            .and_then(|url| EndpointTelemetrySink::new(url).map_err(|err| err.to_string()));
+        match sink {
+            Ok(sink) => return Some(Arc::new(sink)),
+            Err(err) => {
+                warn!("Telemetry disabled. Invalid endpoint `{target}`: {err}.");
+                return None;
+            }
+        }
+    }
+    match FileTelemetrySink::new(Path::new(&target)) {
+        Ok(sink) => Some(Arc::new(sink)),
+        Err(err) => {
+            warn!("Telemetry disabled. Failed to open `{target}`: {err}.");
+            None
+        }
+    }
+}
+
+/// A telemetry sink appending the metrics to a local file, one JSON object per line.
+pub struct FileTelemetrySink {
+    file: Mutex<File>,
+}
+impl FileTelemetrySink {
+    pub fn new(path: &Path) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { file: Mutex::new(file) })
+    }
+}
+impl TelemetrySink for FileTelemetrySink {
+    fn record(&self, metrics: &RequestMetrics) {
+        let Ok(line) = serde_json::to_string(metrics) else {
+            return;
+        };
+        if let Ok(mut file) = self.file.lock() {
+            writeln!(file, "{line}").ok();
+        }
+    }
+}
+
+/// A telemetry sink posting each metrics record as JSON to an HTTP endpoint.
+/// Records are posted one at a time by a background thread, and are dropped if they cannot be
+/// posted, or if too many records are waiting to be posted.
+pub struct EndpointTelemetrySink {
+    sender: SyncSender<RequestMetrics>,
+}
+impl EndpointTelemetrySink {
+    /// Starts the thread posting the records to the given endpoint.
+    pub fn new(url: Url) -> std::io::Result<Self> {
+        let (sender, receiver) = sync_channel::<RequestMetrics>(ENDPOINT_QUEUE_CAPACITY);
+        let agent = ureq::AgentBuilder::new().timeout(ENDPOINT_TIMEOUT).build();
+        std::thread::Builder::new().name("telemetry".into()).spawn(move || {
+            // A failure is only reported once until a record is posted again, to avoid a warning
+            // for every request while the endpoint is unavailable.
+            let mut failing = false;
+            for metrics in receiver {
+                match agent.post(url.as_str()).send_json(&metrics) {
+                    Ok(_) => failing = false,
+                    Err(err) if !failing => {
+                        warn!("Failed to post telemetry to `{url}`: {err}.");
+                        failing = true;
+                    }
+                    Err(_) => {}
+                }
+            }
+        })?;
+        Ok(Self { sender })
+    }
+}
+impl TelemetrySink for EndpointTelemetrySink {
+    fn record(&self, metrics: &RequestMetrics) {
+        self.sender.try_send(metrics.clone()).ok();
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::ModuleId;
+use cairo_lang_filesystem::db::{FilesGroup, FilesGroupEx};
+use cairo_lang_filesystem::ids::{
+    CrateLongId, Directory, FileId, FileKind, FileLongId, VirtualFile,
+};
+use itertools::Itertools;
+use lsp::{CodeActionOrCommand, Position, TextDocumentContentChangeEvent, Url};
+
+use crate::{apply_content_change, language_server_plugin_suite};
+
+/// Returns a database with the corelib, and the root file of a crate with the given content.
+pub fn setup_test_file(content: &str) -> (RootDatabase, FileId) {
+    add_test_file(RootDatabase::builder().detect_corelib().build().unwrap(), content)
+}
+
+/// Returns a database with the corelib and the plugins of the language server, and the root file
+/// of a crate with the given content.
+pub fn setup_test_file_with_plugins(content: &str) -> (RootDatabase, FileId) {
+    let db = RootDatabase::builder()
+        .detect_corelib()
+        .with_plugin_suite(language_server_plugin_suite(false))
+        .build()
+        .unwrap();
+    add_test_file(db, content)
+}
+
+/// Adds the root file of a crate with the given content to the database.
+fn add_test_file(mut db: RootDatabase, content: &str) -> (RootDatabase, FileId) {
+    let file = db.intern_file(FileLongId::Virtual(VirtualFile {
+        parent: None,
+        name: "lib.cairo".into(),
+        content: Arc::new(content.into()),
+        code_mappings: Default::default(),
+        kind: FileKind::Module,
+    }));
+    let crate_id = db.intern_crate(CrateLongId::Virtual {
+        name: "test".into(),
+        root: Directory::Virtual {
+            files: BTreeMap::from([("lib.cairo".into(), file)]),
+            dirs: Default::default(),
+        },
+    });
+    let config = db.crate_config(crate_id);
+    db.set_crate_config(crate_id, config);
+    assert_eq!(db.module_main_file(ModuleId::CrateRoot(crate_id)).unwrap(), file);
+    (db, file)
+}
+
+/// Returns the URI used for the file of [setup_test_file] in requests.
+pub fn test_file_uri() -> Url {
+    Url::parse("file:///lib.cairo").unwrap()
+}
+
+/// Returns the position of the given byte offset in the (ASCII) text.
+pub fn offset_position(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line = before.matches('\n').count();
+    let character = offset - before.rfind('\n').map_or(0, |index| index + 1);
+    Position { line: line as u32, character: character as u32 }
+}
+
+/// Returns the text resulting from applying the edits of a code action to the given text.
+pub fn apply_code_action(text: &str, action: &CodeActionOrCommand) -> String {
+    let CodeActionOrCommand::CodeAction(action) = action else {
+        panic!("Expected a code action.");
+    };
+    let changes = action.edit.as_ref().unwrap().changes.as_ref().unwrap();
+    let mut text = text.to_string();
+    // The edits do not overlap, so applying them from the last one keeps the positions of the
+    // others valid.
+    for edit in changes[&test_file_uri()]
+        .iter()
+        .sorted_by_key(|edit| (edit.range.start.line, edit.range.start.character))
+        .rev()
+    {
+        let change = TextDocumentContentChangeEvent {
+            range: Some(edit.range),
+            range_length: None,
+            text: edit.new_text.clone(),
+        };
+        apply_content_change(&mut text, change).unwrap();
+    }
+    text
+}
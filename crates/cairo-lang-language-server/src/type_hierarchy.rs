@@ -0,0 +1,179 @@
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_defs::ids::{
+    GenericTypeId, ImplDefId, LanguageElementId, LookupItemId, ModuleItemId,
+    TopLevelLanguageElementId, TraitId,
+};
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::resolve::ResolvedGenericItem;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{ast, SyntaxNode, TypedSyntaxNode};
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+use cairo_lang_utils::{OptionFrom, Upcast};
+use lsp::{Position, SymbolKind, TypeHierarchyItem};
+
+use crate::{file, get_node_and_lookup_items, get_uri, node_range};
+
+/// Returns the type hierarchy item of the trait, impl or type at a position - the one referred to
+/// by the identifier at the position if there is one, or otherwise the one containing the position.
+pub fn prepare_type_hierarchy(
+    db: &RootDatabase,
+    file: FileId,
+    position: Position,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let (node, lookup_items) = get_node_and_lookup_items(db, file, position)?;
+    let item = referred_item(db, node, &lookup_items).or_else(|| {
+        lookup_items.into_iter().find_map(|item| match item {
+            LookupItemId::ModuleItem(item) if is_type_hierarchy_item(item) => Some(item),
+            _ => None,
+        })
+    })?;
+    Some(vec![type_hierarchy_item(db, item)?])
+}
+
+/// Returns the supertypes of a type hierarchy item - the trait implemented by an impl, or the
+/// traits implemented for a type.
+pub fn supertypes(db: &RootDatabase, item: &TypeHierarchyItem) -> Option<Vec<TypeHierarchyItem>> {
+    let traits: Vec<TraitId> = match item_of_type_hierarchy_item(db, item)? {
+        ModuleItemId::Impl(impl_def_id) => {
+            db.impl_def_trait(impl_def_id).ok().into_iter().collect()
+        }
+        ModuleItemId::Trait(_) => vec![],
+        item => {
+            let generic_type = GenericTypeId::option_from(item)?;
+            let mut traits = OrderedHashSet::<TraitId>::default();
+            for crate_id in db.crates() {
+                let index = db.impl_index_in_crate(crate_id);
+                for impl_def_id in index.impls_by_type.get(&generic_type).into_iter().flatten() {
+                    if let Ok(trait_id) = db.impl_def_trait(*impl_def_id) {
+                        traits.insert(trait_id);
+                    }
+                }
+            }
+            traits.into_iter().collect()
+        }
+    };
+    Some(
+        traits
+            .into_iter()
+            .filter_map(|trait_id| type_hierarchy_item(db, ModuleItemId::Trait(trait_id)))
+            .collect(),
+    )
+}
+
+/// Returns the subtypes of a type hierarchy item - the impls of a trait, in all the crates.
+pub fn subtypes(db: &RootDatabase, item: &TypeHierarchyItem) -> Option<Vec<TypeHierarchyItem>> {
+    let ModuleItemId::Trait(trait_id) = item_of_type_hierarchy_item(db, item)? else {
+        return Some(vec![]);
+    };
+    let mut impls: Vec<ImplDefId> = vec![];
+    for crate_id in db.crates() {
+        let index = db.impl_index_in_crate(crate_id);
+        impls.extend(index.impls_by_trait.get(&trait_id).into_iter().flatten());
+    }
+    Some(
+        impls
+            .into_iter()
+            .filter_map(|impl_def_id| type_hierarchy_item(db, ModuleItemId::Impl(impl_def_id)))
+            .collect(),
+    )
+}
+
+/// Returns the trait, impl or type referred to by the identifier at `node`, if it is one.
+fn referred_item(
+    db: &RootDatabase,
+    node: SyntaxNode,
+    lookup_items: &[LookupItemId],
+) -> Option<ModuleItemId> {
+    if node.kind(db) != SyntaxKind::TokenIdentifier {
+        return None;
+    }
+    let identifier = ast::TerminalIdentifier::from_syntax_node(db, node.parent()?);
+    lookup_items.iter().find_map(|lookup_item_id| {
+        let resolved_item = db
+            .lookup_resolved_generic_item_by_ptr(*lookup_item_id, identifier.stable_ptr())
+            .or_else(|| {
+                db.lookup_resolved_concrete_item_by_ptr(*lookup_item_id, identifier.stable_ptr())?
+                    .generic(db)
+            })?;
+        match resolved_item {
+            ResolvedGenericItem::Trait(trait_id) => Some(ModuleItemId::Trait(trait_id)),
+            ResolvedGenericItem::Impl(impl_def_id) => Some(ModuleItemId::Impl(impl_def_id)),
+            ResolvedGenericItem::GenericType(GenericTypeId::Struct(id)) => {
+                Some(ModuleItemId::Struct(id))
+            }
+            ResolvedGenericItem::GenericType(GenericTypeId::Enum(id)) => {
+                Some(ModuleItemId::Enum(id))
+            }
+            ResolvedGenericItem::GenericType(GenericTypeId::Extern(id)) => {
+                Some(ModuleItemId::ExternType(id))
+            }
+            _ => None,
+        }
+    })
+}
+
+/// Returns the item of a type hierarchy item, by the position of its name.
+fn item_of_type_hierarchy_item(
+    db: &RootDatabase,
+    item: &TypeHierarchyItem,
+) -> Option<ModuleItemId> {
+    let file = file(db, item.uri.clone());
+    let (_, lookup_items) = get_node_and_lookup_items(db, file, item.selection_range.start)?;
+    lookup_items.into_iter().find_map(|item| match item {
+        LookupItemId::ModuleItem(item) if is_type_hierarchy_item(item) => Some(item),
+        _ => None,
+    })
+}
+
+/// Returns true if the item may appear in a type hierarchy, i.e. it is a trait, an impl or a type.
+fn is_type_hierarchy_item(item: ModuleItemId) -> bool {
+    matches!(
+        item,
+        ModuleItemId::Trait(_)
+            | ModuleItemId::Impl(_)
+            | ModuleItemId::Struct(_)
+            | ModuleItemId::Enum(_)
+            | ModuleItemId::ExternType(_)
+    )
+}
+
+/// Returns the type hierarchy item of an item, or None if the item may not appear in a type
+/// hierarchy.
+fn type_hierarchy_item(db: &RootDatabase, item: ModuleItemId) -> Option<TypeHierarchyItem> {
+    let syntax_db = db.upcast();
+    let (node, name, kind) = match item {
+        ModuleItemId::Trait(id) => {
+            let ast = id.stable_ptr(db).lookup(syntax_db);
+            (ast.as_syntax_node(), ast.name(syntax_db), SymbolKind::INTERFACE)
+        }
+        ModuleItemId::Impl(id) => {
+            let ast = id.stable_ptr(db).lookup(syntax_db);
+            (ast.as_syntax_node(), ast.name(syntax_db), SymbolKind::OBJECT)
+        }
+        ModuleItemId::Struct(id) => {
+            let ast = id.stable_ptr(db).lookup(syntax_db);
+            (ast.as_syntax_node(), ast.name(syntax_db), SymbolKind::STRUCT)
+        }
+        ModuleItemId::Enum(id) => {
+            let ast = id.stable_ptr(db).lookup(syntax_db);
+            (ast.as_syntax_node(), ast.name(syntax_db), SymbolKind::ENUM)
+        }
+        ModuleItemId::ExternType(id) => {
+            let ast = id.stable_ptr(db).lookup(syntax_db);
+            (ast.as_syntax_node(), ast.name(syntax_db), SymbolKind::CLASS)
+        }
+        _ => return None,
+    };
+    Some(TypeHierarchyItem {
+        name: item.name(db).into(),
+        kind,
+        tags: None,
+        detail: Some(item.full_path(db)),
+        uri: get_uri(db, item.untyped_stable_ptr(db).file_id(syntax_db)),
+        range: node_range(db, &node)?,
+        selection_range: node_range(db, &name.as_syntax_node())?,
+        data: None,
+    })
+}
@@ -0,0 +1,163 @@
+use cairo_lang_defs::ids::{
+    FunctionWithBodyId, LanguageElementId, ModuleId, ModuleItemId, TraitId, UseId,
+};
+use cairo_lang_defs::plugin::PluginDiagnostic;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::items::functions::GenericFunctionId;
+use cairo_lang_semantic::items::us::SemanticUseEx;
+use cairo_lang_semantic::plugin::AnalyzerPlugin;
+use cairo_lang_semantic::resolve::ResolvedGenericItem;
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{ast, SyntaxNode, TypedSyntaxNode};
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+
+#[cfg(test)]
+#[path = "unused_imports_test.rs"]
+mod test;
+
+/// The message of the warning on unused imports.
+pub const UNUSED_IMPORT_MESSAGE: &str = "Unused import.";
+
+/// Plugin to add warnings for imports that are not used by their module.
+#[derive(Default, Debug)]
+pub struct UnusedImportsAnalyzer;
+
+impl AnalyzerPlugin for UnusedImportsAnalyzer {
+    fn diagnostics(&self, db: &dyn SemanticGroup, module_id: ModuleId) -> Vec<PluginDiagnostic> {
+        find_unused_imports(db, module_id)
+            .into_iter()
+            .map(|use_id| {
+                PluginDiagnostic::warning(
+                    use_id.stable_ptr(db.upcast()).untyped(),
+                    UNUSED_IMPORT_MESSAGE.to_string(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Returns the imports of a module whose name is not used by the module or its submodules.
+/// Public imports and imports of impls are never reported, as they may be used implicitly.
+/// Trait imports are considered used if a function of the trait is called by the module.
+pub fn find_unused_imports(db: &dyn SemanticGroup, module_id: ModuleId) -> Vec<UseId> {
+    let Ok(use_ids) = db.module_uses_ids(module_id) else {
+        return vec![];
+    };
+    if use_ids.is_empty() {
+        return vec![];
+    }
+    let mut used_names = UnorderedHashSet::default();
+    collect_module_identifiers(db, module_id, false, &mut used_names);
+    let used_traits = called_traits(db, module_id);
+
+    let syntax_db = db.upcast();
+    use_ids
+        .iter()
+        .copied()
+        .filter(|use_id| {
+            let leaf = use_id.stable_ptr(db.upcast()).lookup(syntax_db);
+            if matches!(
+                use_item_of_node(syntax_db, leaf.as_syntax_node())
+                    .map(|item| item.visibility(syntax_db)),
+                Some(ast::Visibility::Pub(_))
+            ) {
+                return false;
+            }
+            if used_names.contains(use_id.name(db.upcast()).as_str()) {
+                return false;
+            }
+            match db.use_resolved_item(*use_id) {
+                Ok(ResolvedGenericItem::Trait(trait_id)) => !used_traits.contains(&trait_id),
+                Ok(ResolvedGenericItem::Impl(_)) | Err(_) => false,
+                Ok(_) => true,
+            }
+        })
+        .collect()
+}
+
+/// Collects the identifiers used by the items of a module and its submodules. The imports of the
+/// module itself are skipped, unless `include_uses` is set.
+fn collect_module_identifiers(
+    db: &dyn SemanticGroup,
+    module_id: ModuleId,
+    include_uses: bool,
+    identifiers: &mut UnorderedHashSet<String>,
+) {
+    let syntax_db = db.upcast();
+    for item_id in db.module_items(module_id).unwrap_or_default().iter() {
+        let node = match item_id {
+            ModuleItemId::Use(use_id) => {
+                if !include_uses {
+                    continue;
+                }
+                let leaf = use_id.stable_ptr(db.upcast()).lookup(syntax_db).as_syntax_node();
+                match use_item_of_node(syntax_db, leaf.clone()) {
+                    Some(item) => item.as_syntax_node(),
+                    None => leaf,
+                }
+            }
+            ModuleItemId::Submodule(submodule_id) => {
+                collect_module_identifiers(
+                    db,
+                    ModuleId::Submodule(*submodule_id),
+                    true,
+                    identifiers,
+                );
+                continue;
+            }
+            _ => item_id.untyped_stable_ptr(db.upcast()).lookup(syntax_db),
+        };
+        for descendant in node.descendants(syntax_db) {
+            if descendant.kind(syntax_db) == SyntaxKind::TokenIdentifier {
+                identifiers.insert(descendant.get_text(syntax_db));
+            }
+        }
+    }
+}
+
+/// Returns the traits whose functions are called by the functions of a module.
+fn called_traits(db: &dyn SemanticGroup, module_id: ModuleId) -> UnorderedHashSet<TraitId> {
+    let mut function_ids = vec![];
+    for free_function_id in db.module_free_functions_ids(module_id).unwrap_or_default().iter() {
+        function_ids.push(FunctionWithBodyId::Free(*free_function_id));
+    }
+    for impl_def_id in db.module_impls_ids(module_id).unwrap_or_default().iter() {
+        for impl_function_id in db.impl_functions(*impl_def_id).unwrap_or_default().values() {
+            function_ids.push(FunctionWithBodyId::Impl(*impl_function_id));
+        }
+    }
+    for trait_id in db.module_traits_ids(module_id).unwrap_or_default().iter() {
+        for trait_function_id in db.trait_functions(*trait_id).unwrap_or_default().values() {
+            function_ids.push(FunctionWithBodyId::Trait(*trait_function_id));
+        }
+    }
+
+    let mut traits = UnorderedHashSet::default();
+    for function_id in function_ids {
+        let Ok(body) = db.function_body(function_id) else {
+            continue;
+        };
+        for (_, expr) in body.exprs.iter() {
+            let cairo_lang_semantic::Expr::FunctionCall(call) = expr else {
+                continue;
+            };
+            if let GenericFunctionId::Impl(impl_generic_function) =
+                call.function.get_concrete(db).generic_function
+            {
+                traits.insert(impl_generic_function.function.trait_id(db.upcast()));
+            }
+        }
+    }
+    traits
+}
+
+/// Returns the `use` item containing the given node, if any.
+pub fn use_item_of_node(db: &dyn SyntaxGroup, mut node: SyntaxNode) -> Option<ast::ItemUse> {
+    loop {
+        if node.kind(db) == SyntaxKind::ItemUse {
+            return Some(ast::ItemUse::from_syntax_node(db, node));
+        }
+        node = node.parent()?;
+    }
+}
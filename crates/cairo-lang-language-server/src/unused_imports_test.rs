@@ -0,0 +1,77 @@
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_utils::Upcast;
+use indoc::indoc;
+use itertools::Itertools;
+
+use super::{find_unused_imports, UNUSED_IMPORT_MESSAGE};
+use crate::test_utils::{offset_position, setup_test_file_with_plugins};
+use crate::{file_diagnostics, lsp_diagnostics};
+
+/// Returns the names of the unused imports of the root module of the code.
+fn unused_imports(code: &str) -> Vec<String> {
+    let (db, file) = setup_test_file_with_plugins(code);
+    let module_id = *db.file_modules(file).unwrap().first().unwrap();
+    find_unused_imports(&db, module_id)
+        .into_iter()
+        .map(|use_id| use_id.name(db.upcast()).to_string())
+        .collect()
+}
+
+#[test]
+fn test_unused_imports() {
+    let code = indoc! {"
+        use core::integer::{u8_sqrt, u16_sqrt};
+        fn foo() -> u8 {
+            u8_sqrt(4)
+        }
+    "};
+    assert_eq!(unused_imports(code), ["u16_sqrt"]);
+}
+
+#[test]
+fn test_imports_used_by_submodule() {
+    let code = indoc! {"
+        use core::integer::{u8_sqrt, u16_sqrt};
+        mod inner {
+            fn foo() -> u8 {
+                super::u8_sqrt(4)
+            }
+        }
+    "};
+    assert_eq!(unused_imports(code), ["u16_sqrt"]);
+}
+
+#[test]
+fn test_public_and_impl_imports_are_not_reported() {
+    assert!(
+        unused_imports("pub use core::integer::u8_sqrt;\nuse core::integer::U8Add;\n").is_empty()
+    );
+}
+
+#[test]
+fn test_trait_imports_used_by_method_calls() {
+    let code = indoc! {"
+        use core::traits::{Into, TryInto};
+        fn foo() -> felt252 {
+            1_u8.into()
+        }
+    "};
+    assert_eq!(unused_imports(code), ["TryInto"]);
+}
+
+#[test]
+fn test_unused_imports_analyzer() {
+    let code = "use core::integer::{u8_sqrt, u16_sqrt};\nfn foo() -> u8 {\n    u8_sqrt(4)\n}\n";
+    let (db, file) = setup_test_file_with_plugins(code);
+    let warnings = lsp_diagnostics(&db, &file_diagnostics(&db, file))
+        .into_iter()
+        .map(|diagnostic| (diagnostic.range.start, diagnostic.message))
+        .collect_vec();
+    assert_eq!(
+        warnings,
+        [(
+            offset_position(code, code.find("u16_sqrt").unwrap()),
+            format!("Plugin diagnostic: {UNUSED_IMPORT_MESSAGE}")
+        )]
+    );
+}
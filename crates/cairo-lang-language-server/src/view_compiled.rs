@@ -0,0 +1,252 @@
+use std::sync::Arc;
+
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_diagnostics::ToOption;
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::ids::{FileId, FileKind, FileLongId, VirtualFile};
+use cairo_lang_lowering::ids::{ConcreteFunctionWithBodyId, SemanticFunctionWithBodyIdEx};
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_sierra::program::{Function, Program};
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use cairo_lang_sierra_to_casm::compiler::compile;
+use cairo_lang_sierra_to_casm::metadata::{calc_metadata, MetadataComputationConfig};
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::SyntaxNode;
+use cairo_lang_utils::Upcast;
+use itertools::Itertools;
+use lsp::{Location, Position, TextDocumentIdentifier, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::{function_with_body_from_ast, get_uri, node_range, position_to_offset};
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewCompiledRequest {
+    pub text_document: TextDocumentIdentifier,
+    /// A position in the function to compile. If missing, all the functions in the file are
+    /// compiled.
+    pub position: Option<Position>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+pub struct ViewCompiledResponse {
+    /// The URI of a virtual document holding the compiled code, to be fetched with `vfs/provide`.
+    /// None if there is no function to compile, or the compilation failed.
+    pub uri: Option<Url>,
+    /// The source locations of the functions in the compiled code.
+    pub mappings: Vec<CompiledCodeMapping>,
+}
+
+/// Maps a range of lines of the compiled code to the source of the function they were compiled
+/// from.
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompiledCodeMapping {
+    /// The first line of the function's code.
+    pub start_line: u32,
+    /// The line after the last line of the function's code.
+    pub end_line: u32,
+    /// The source of the function.
+    pub location: Location,
+}
+
+/// The kind of compiled code to view.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CompiledCodeKind {
+    Sierra,
+    Casm,
+}
+impl CompiledCodeKind {
+    /// The name of the virtual file holding the compiled code.
+    fn file_name(&self) -> &'static str {
+        match self {
+            CompiledCodeKind::Sierra => "sierra",
+            CompiledCodeKind::Casm => "casm",
+        }
+    }
+}
+
+/// Compiles the function at a position, or all the functions in the file if no position is given.
+/// Returns a virtual document with the resulting code, annotated with the names of the functions,
+/// along with mappings of the code of each function to its source.
+pub fn view_compiled(
+    db: &RootDatabase,
+    file: FileId,
+    position: Option<Position>,
+    kind: CompiledCodeKind,
+) -> ViewCompiledResponse {
+    let Some((content, mappings)) = compile_functions(db, file, position, kind) else {
+        return ViewCompiledResponse { uri: None, mappings: vec![] };
+    };
+    let compiled_file = db.intern_file(FileLongId::Virtual(VirtualFile {
+        parent: Some(file),
+        name: kind.file_name().into(),
+        content: Arc::new(content),
+        code_mappings: Default::default(),
+        kind: FileKind::Module,
+    }));
+    ViewCompiledResponse { uri: Some(get_uri(db, compiled_file)), mappings }
+}
+
+/// Compiles the functions to view, and returns the text of the compiled code with its mappings.
+fn compile_functions(
+    db: &RootDatabase,
+    file: FileId,
+    position: Option<Position>,
+    kind: CompiledCodeKind,
+) -> Option<(String, Vec<CompiledCodeMapping>)> {
+    let functions = functions_to_compile(db, file, position)?;
+    if functions.is_empty() {
+        return None;
+    }
+    let program = db.get_sierra_program_for_functions(functions).ok()?;
+    let mut writer = CompiledCodeWriter::default();
+    match kind {
+        CompiledCodeKind::Sierra => write_sierra(db, &program, &mut writer),
+        CompiledCodeKind::Casm => write_casm(db, &program, &mut writer)?,
+    }
+    Some((writer.lines.join("\n"), writer.mappings))
+}
+
+/// Returns the non-generic functions to compile - the innermost function containing the position,
+/// or all the functions in the file if no position is given.
+fn functions_to_compile(
+    db: &RootDatabase,
+    file: FileId,
+    position: Option<Position>,
+) -> Option<Vec<ConcreteFunctionWithBodyId>> {
+    let syntax_db = db.upcast();
+    let syntax = db.file_syntax(file).to_option()?;
+    let nodes: Vec<SyntaxNode> = match position {
+        Some(position) => {
+            let offset =
+                position_to_offset(db.file_summary(file)?, position, &db.file_content(file)?)?;
+            let mut node = syntax.lookup_offset(syntax_db, offset);
+            loop {
+                if node.kind(syntax_db) == SyntaxKind::FunctionWithBody {
+                    break vec![node];
+                }
+                node = node.parent()?;
+            }
+        }
+        None => syntax
+            .descendants(syntax_db)
+            .filter(|node| node.kind(syntax_db) == SyntaxKind::FunctionWithBody)
+            .collect(),
+    };
+    Some(
+        nodes
+            .into_iter()
+            .filter_map(|node| {
+                let function_id = function_with_body_from_ast(db, file, node)?;
+                if !db.function_with_body_generic_params(function_id).ok()?.is_empty() {
+                    return None;
+                }
+                function_id.lowered(db).to_concrete(db).ok()
+            })
+            .collect(),
+    )
+}
+
+/// Accumulates the lines of the compiled code, and the mappings of its functions to their source.
+#[derive(Default)]
+struct CompiledCodeWriter {
+    lines: Vec<String>,
+    mappings: Vec<CompiledCodeMapping>,
+}
+impl CompiledCodeWriter {
+    /// Writes the code of a function, headed by a comment with its name, and maps it to the
+    /// function's source if it has one.
+    fn write_function(
+        &mut self,
+        db: &RootDatabase,
+        function: &Function,
+        code: impl IntoIterator<Item = String>,
+    ) {
+        if !self.lines.is_empty() {
+            self.lines.push("".into());
+        }
+        self.lines.push(format!("// {}", function.id));
+        let start_line = self.lines.len() as u32;
+        self.lines.extend(code);
+        let end_line = self.lines.len() as u32;
+        if let Some(location) = function_location(db, function) {
+            self.mappings.push(CompiledCodeMapping { start_line, end_line, location });
+        }
+    }
+}
+
+/// Returns the source location of a Sierra function, if it was compiled from a function with body.
+fn function_location(db: &RootDatabase, function: &Function) -> Option<Location> {
+    let concrete =
+        db.lookup_intern_sierra_function(function.id.clone()).body(db.upcast()).ok()??;
+    let stable_location = concrete.stable_location(db).ok()?;
+    Some(Location {
+        uri: get_uri(db, stable_location.file_id(db)),
+        range: node_range(db, &stable_location.syntax_node(db))?,
+    })
+}
+
+/// Returns the functions of the program, in the order of their entry points, each with the range of
+/// its statements.
+fn functions_with_statements(program: &Program) -> Vec<(&Function, std::ops::Range<usize>)> {
+    let functions = program.funcs.iter().sorted_by_key(|function| function.entry_point.0);
+    let mut ends = functions.clone().skip(1).map(|function| function.entry_point.0);
+    functions
+        .map(|function| {
+            let end = ends.next().unwrap_or(program.statements.len());
+            (function, function.entry_point.0..end)
+        })
+        .collect()
+}
+
+/// Writes the Sierra code of the program - its declarations, the statements of each function, and
+/// the function signatures.
+fn write_sierra(db: &RootDatabase, program: &Program, writer: &mut CompiledCodeWriter) {
+    let program = replace_sierra_ids_in_program(db, program);
+    writer
+        .lines
+        .extend(program.type_declarations.iter().map(|declaration| format!("{declaration};")));
+    writer.lines.push("".into());
+    writer
+        .lines
+        .extend(program.libfunc_declarations.iter().map(|declaration| format!("{declaration};")));
+    for (function, statements) in functions_with_statements(&program) {
+        let code = statements.map(|idx| format!("{}; // {idx}", program.statements[idx]));
+        writer.write_function(db, function, code);
+    }
+    writer.lines.push("".into());
+    writer.lines.extend(program.funcs.iter().map(|function| format!("{function};")));
+}
+
+/// Writes the CASM code of the program, compiled from the Sierra code, split by functions.
+fn write_casm(db: &RootDatabase, program: &Program, writer: &mut CompiledCodeWriter) -> Option<()> {
+    let metadata = calc_metadata(program, MetadataComputationConfig::default()).ok()?;
+    let casm = compile(program, &metadata, false).ok()?;
+    let statement_offsets = &casm.debug_info.sierra_statement_info;
+    // The code offset of each instruction, to find the instructions of each function.
+    let instruction_offsets: Vec<usize> = casm
+        .instructions
+        .iter()
+        .scan(0, |offset, instruction| {
+            let instruction_offset = *offset;
+            *offset += instruction.body.op_size();
+            Some(instruction_offset)
+        })
+        .collect();
+    let program = replace_sierra_ids_in_program(db, program);
+    for (function, statements) in functions_with_statements(&program) {
+        let start = statement_offsets[statements.start].code_offset;
+        let end = statement_offsets[statements.end].code_offset;
+        let code = casm
+            .instructions
+            .iter()
+            .zip(&instruction_offsets)
+            .filter(|(_, offset)| (start..end).contains(*offset))
+            .map(|(instruction, _)| format!("{instruction};"));
+        writer.write_function(db, function, code);
+    }
+    Some(())
+}
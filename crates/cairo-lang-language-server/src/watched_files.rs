@@ -0,0 +1,94 @@
+//! Watching of the project files that are not open in the editor, so that changes made outside of
+//! it, such as edits to local dependencies or switching git branches, are picked up.
+use std::path::{Path, PathBuf};
+
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::ids::{Directory, FileId, FileLongId};
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+use lsp::{
+    DidChangeWatchedFilesRegistrationOptions, FileSystemWatcher, GlobPattern, OneOf, Registration,
+    RelativePattern, Unregistration, Url,
+};
+
+const WATCHED_FILES_METHOD: &str = "workspace/didChangeWatchedFiles";
+
+/// The files of a project whose changes are watched - Cairo files and project manifests.
+const WATCHED_FILE_PATTERNS: [&str; 3] = ["**/*.cairo", "**/Scarb.toml", "**/cairo_project.toml"];
+
+/// The names of the project manifest files, whose changes reload the project.
+const PROJECT_MANIFEST_FILE_NAMES: [&str; 2] = ["Scarb.toml", "cairo_project.toml"];
+
+/// Returns the registration of the watchers of the files of the workspace, and of the files in the
+/// given crate roots, which may be outside of the workspace.
+pub fn watched_files_registration(crate_roots: &[PathBuf]) -> Registration {
+    let workspace_watchers = WATCHED_FILE_PATTERNS.map(|pattern| FileSystemWatcher {
+        glob_pattern: GlobPattern::String(format!("/{pattern}")),
+        kind: None,
+    });
+    let crate_root_watchers = crate_roots.iter().filter_map(|crate_root| {
+        let base_uri = Url::from_directory_path(crate_root).ok()?;
+        Some(FileSystemWatcher {
+            glob_pattern: GlobPattern::Relative(RelativePattern {
+                base_uri: OneOf::Right(base_uri),
+                pattern: WATCHED_FILE_PATTERNS[0].to_string(),
+            }),
+            kind: None,
+        })
+    });
+    let registration_options = DidChangeWatchedFilesRegistrationOptions {
+        watchers: workspace_watchers.into_iter().chain(crate_root_watchers).collect(),
+    };
+    Registration {
+        id: WATCHED_FILES_METHOD.to_string(),
+        method: WATCHED_FILES_METHOD.to_string(),
+        register_options: Some(serde_json::to_value(registration_options).unwrap()),
+    }
+}
+
+/// Returns the unregistration of the watchers registered by [watched_files_registration].
+pub fn watched_files_unregistration() -> Unregistration {
+    Unregistration {
+        id: WATCHED_FILES_METHOD.to_string(),
+        method: WATCHED_FILES_METHOD.to_string(),
+    }
+}
+
+/// Returns the root directories of the crates in the db, sorted.
+pub fn crate_roots(db: &dyn FilesGroup) -> Vec<PathBuf> {
+    let mut crate_roots = db
+        .crates()
+        .into_iter()
+        .filter_map(|crate_id| match db.crate_config(crate_id)?.root {
+            Directory::Real(path) => Some(path),
+            Directory::Virtual { .. } => None,
+        })
+        .collect::<Vec<_>>();
+    crate_roots.sort();
+    crate_roots.dedup();
+    crate_roots
+}
+
+/// Returns whether a file is the manifest of a project.
+pub fn is_project_manifest_path(file_path: &Url) -> bool {
+    PROJECT_MANIFEST_FILE_NAMES.iter().any(|name| file_path.path().ends_with(name))
+}
+
+/// Returns the files of the modules of the crates in the db that are in a directory.
+/// Clients may report the deletion of a directory, rather than of each of the files in it.
+pub fn project_files_in_directory(db: &dyn SemanticGroup, directory: &Path) -> Vec<FileId> {
+    let mut files = OrderedHashSet::<FileId>::default();
+    for crate_id in db.crates() {
+        for module_id in db.crate_modules(crate_id).iter() {
+            for file_id in db.module_files(*module_id).unwrap_or_default().iter().copied() {
+                let FileLongId::OnDisk(path) = db.lookup_intern_file(file_id) else {
+                    continue;
+                };
+                if path.starts_with(directory) {
+                    files.insert(file_id);
+                }
+            }
+        }
+    }
+    files.into_iter().collect()
+}
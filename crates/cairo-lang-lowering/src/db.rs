@@ -3,6 +3,7 @@ use std::sync::Arc;
 use cairo_lang_defs as defs;
 use cairo_lang_defs::ids::{LanguageElementId, ModuleId, ModuleItemId};
 use cairo_lang_diagnostics::{Diagnostics, DiagnosticsBuilder, Maybe};
+use cairo_lang_filesystem::flag::OptimizationLevel;
 use cairo_lang_filesystem::ids::FileId;
 use cairo_lang_semantic::db::SemanticGroup;
 use cairo_lang_semantic::TypeId;
@@ -19,7 +20,15 @@ use crate::implicits::lower_implicits;
 use crate::inline::{apply_inlining, PrivInlineData};
 use crate::lower::{lower_semantic_function, MultiLowering};
 use crate::optimizations::branch_inversion::branch_inversion;
+use crate::optimizations::const_folding::const_folding;
+use crate::optimizations::cse::eliminate_common_subexpressions;
+use crate::optimizations::dead_storage_writes::{
+    dead_storage_writes_diagnostics, dead_storage_writes_elimination_enabled,
+    eliminate_dead_storage_writes,
+};
 use crate::optimizations::match_optimizer::optimize_matches;
+use crate::optimizations::peephole::peephole_optimizations;
+use crate::optimizations::{local_optimizations_enabled, optimization_level};
 use crate::optimizations::remappings::optimize_remappings;
 use crate::optimizations::reorder_statements::reorder_statements;
 use crate::panic::lower_panics;
@@ -324,7 +333,7 @@ fn priv_concrete_function_with_body_lowered_flat(
     Ok(Arc::new(lowered))
 }
 
-// Applies inlining.
+// Applies inlining - only of the functions that must be inlined if optimizations are disabled.
 fn priv_concrete_function_with_body_postinline_lowered(
     db: &dyn LoweringGroup,
     function: ids::ConcreteFunctionWithBodyId,
@@ -350,9 +359,11 @@ fn concrete_function_with_body_postpanic_lowered(
 }
 
 // * Optimizes remappings.
-// * Delays var definitions.
+// * Folds constants, applies peephole optimizations and eliminates common subexpressions (if the
+//   local optimizations are enabled).
+// * Delays var definitions (unless optimizations are disabled).
 // * Lowers implicits.
-// * Optimizes matches.
+// * Optimizes matches (unless optimizations are disabled).
 // * Optimizes remappings again.
 // * Reorganizes blocks (topological sort).
 fn concrete_function_with_body_lowered(
@@ -360,16 +371,29 @@ fn concrete_function_with_body_lowered(
     function: ids::ConcreteFunctionWithBodyId,
 ) -> Maybe<Arc<FlatLowered>> {
     let mut lowered = (*db.concrete_function_with_body_postpanic_lowered(function)?).clone();
+    if dead_storage_writes_elimination_enabled(db) {
+        eliminate_dead_storage_writes(db, function, &mut lowered)?;
+    }
+    let optimize = optimization_level(db) > OptimizationLevel::O0;
     optimize_remappings(&mut lowered);
-    // The call to `reorder_statements` before and after `branch_inversion` is intentional.
-    // See description of `branch_inversion` for more details.
-    reorder_statements(db, &mut lowered);
-    branch_inversion(db, &mut lowered);
-    reorder_statements(db, &mut lowered);
-    optimize_matches(&mut lowered);
+    if local_optimizations_enabled(db) {
+        const_folding(db, &mut lowered);
+        peephole_optimizations(db, &mut lowered);
+        eliminate_common_subexpressions(db, &mut lowered);
+    }
+    if optimize {
+        // The call to `reorder_statements` before and after `branch_inversion` is intentional.
+        // See description of `branch_inversion` for more details.
+        reorder_statements(db, &mut lowered);
+        branch_inversion(db, &mut lowered);
+        reorder_statements(db, &mut lowered);
+        optimize_matches(&mut lowered);
+    }
     lower_implicits(db, function, &mut lowered);
     optimize_remappings(&mut lowered);
-    reorder_statements(db, &mut lowered);
+    if optimize {
+        reorder_statements(db, &mut lowered);
+    }
     reorganize_blocks(&mut lowered);
     // Removed blocks may have caused some remappings to be redundent, so they need to be removed,
     // as SierraGen drop additions assumes all remappings are of used variables.
@@ -460,6 +484,8 @@ fn function_with_body_lowering_diagnostics(
             .unwrap_or_default(),
     );
 
+    diagnostics.extend(dead_storage_writes_diagnostics(db, function_id).unwrap_or_default());
+
     Ok(diagnostics.build())
 }
 
@@ -509,7 +535,16 @@ fn module_lowering_diagnostics(
             ModuleItemId::Enum(_) => {}
             ModuleItemId::TypeAlias(_) => {}
             ModuleItemId::ImplAlias(_) => {}
-            ModuleItemId::Trait(_) => {}
+            ModuleItemId::Trait(trait_id) => {
+                for trait_func in db.trait_functions(*trait_id)?.values() {
+                    if matches!(db.trait_function_body(*trait_func), Ok(Some(_))) {
+                        let function_id = defs::ids::FunctionWithBodyId::Trait(*trait_func);
+                        diagnostics.extend(
+                            db.semantic_function_with_body_lowering_diagnostics(function_id)?,
+                        );
+                    }
+                }
+            }
             ModuleItemId::Impl(impl_def_id) => {
                 for impl_func in db.impl_functions(*impl_def_id)?.values() {
                     let function_id = defs::ids::FunctionWithBodyId::Impl(*impl_func);
@@ -1,12 +1,13 @@
 use cairo_lang_defs::diagnostic_utils::StableLocation;
 use cairo_lang_diagnostics::{
     DiagnosticAdded, DiagnosticEntry, DiagnosticLocation, DiagnosticNote, Diagnostics,
-    DiagnosticsBuilder,
+    DiagnosticsBuilder, Severity,
 };
 use cairo_lang_filesystem::ids::FileId;
 use cairo_lang_semantic::corelib::LiteralError;
 use cairo_lang_semantic::db::SemanticGroup;
 use cairo_lang_semantic::expr::inference::InferenceError;
+use cairo_lang_semantic::TypeId;
 use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
 
 use crate::Location;
@@ -87,6 +88,9 @@ impl DiagnosticEntry for LoweringDiagnostic {
             LoweringDiagnosticKind::MemberPathLoop => {
                 "Currently, loops must change the entire variable.".into()
             }
+            LoweringDiagnosticKind::UnboundForLoopIterator => {
+                "The iterator of the `for` loop is not bound in the loop.".into()
+            }
             LoweringDiagnosticKind::UnexpectedError => {
                 "Unexpected error has occurred, Please submit a full bug report. \
                 See https://github.com/starkware-libs/cairo/issues/new/choose for instructions.\
@@ -94,6 +98,13 @@ impl DiagnosticEntry for LoweringDiagnostic {
                 .into()
             }
             LoweringDiagnosticKind::LiteralError(literal_error) => literal_error.format(db),
+            LoweringDiagnosticKind::ExpectedCLikeEnum { ty } => format!(
+                r#"Expected a C-like enum (an enum whose variants have no data), found "{}"."#,
+                ty.format(db)
+            ),
+            LoweringDiagnosticKind::DeadStorageWrite => {
+                "Redundant storage write: the value is overwritten before it can be read.".into()
+            }
         }
     }
 
@@ -114,6 +125,13 @@ impl DiagnosticEntry for LoweringDiagnostic {
         }
         self.location.stable_location.diagnostic_location(db.upcast())
     }
+
+    fn severity(&self) -> Severity {
+        match &self.kind {
+            LoweringDiagnosticKind::DeadStorageWrite => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -135,5 +153,8 @@ pub enum LoweringDiagnosticKind {
     NonExhaustiveMatchFelt252,
     CannotInlineFunctionThatMightCallItself,
     MemberPathLoop,
+    UnboundForLoopIterator,
     LiteralError(LiteralError),
+    ExpectedCLikeEnum { ty: TypeId },
+    DeadStorageWrite,
 }
@@ -6,6 +6,7 @@ use std::sync::Arc;
 
 use cairo_lang_defs::ids::LanguageElementId;
 use cairo_lang_diagnostics::{Diagnostics, Maybe};
+use cairo_lang_filesystem::flag::OptimizationLevel;
 use cairo_lang_semantic::items::functions::InlineConfiguration;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use itertools::{izip, Itertools};
@@ -15,6 +16,7 @@ use crate::db::LoweringGroup;
 use crate::diagnostic::{LoweringDiagnostic, LoweringDiagnosticKind, LoweringDiagnostics};
 use crate::ids::{ConcreteFunctionWithBodyId, FunctionWithBodyId};
 use crate::lower::context::{VarRequest, VariableAllocator};
+use crate::optimizations::optimization_level;
 use crate::utils::{Rebuilder, RebuilderEx};
 use crate::{
     BlockId, FlatBlock, FlatBlockEnd, FlatLowered, Statement, VarRemapping, VarUsage, VariableId,
@@ -298,8 +300,18 @@ impl<'db> FunctionInlinerRewriter<'db> {
                 self.inlining_success =
                     self.inlining_success.and_then(|()| inline_data.diagnostics.check_error_free());
 
+                // Without optimizations, only the functions that must be inlined are inlined.
+                // Functions without an inline attribute are only inlined by the heuristic from
+                // [OptimizationLevel::O2].
+                let use_heuristic = match optimization_level(self.variables.db) {
+                    OptimizationLevel::O0 => false,
+                    OptimizationLevel::O1 => {
+                        !matches!(inline_data.config, InlineConfiguration::None)
+                    }
+                    OptimizationLevel::O2 | OptimizationLevel::O3 => true,
+                };
                 if inline_data.info.is_inlinable
-                    && (inline_data.info.should_inline
+                    && ((inline_data.info.should_inline && use_heuristic)
                         || matches!(inline_data.config, InlineConfiguration::Always(_)))
                 {
                     if matches!(inline_data.config, InlineConfiguration::Should(_)) {
@@ -16,6 +16,8 @@ cairo_lang_test_utils::test_file_test!(
     "src/lower/test_data",
     {
         loop_ :"loop",
+        while_ :"while",
+        for_ :"for",
     },
     test_generated_function
 );
@@ -2,7 +2,7 @@ use cairo_lang_debug::DebugWithDb;
 use cairo_lang_diagnostics::Maybe;
 use cairo_lang_semantic as semantic;
 use cairo_lang_semantic::corelib;
-use cairo_lang_utils::extract_matches;
+use cairo_lang_utils::{ensure_sufficient_stack, extract_matches};
 use num_traits::Zero;
 use semantic::ExprFunctionCallArg;
 
@@ -213,7 +213,8 @@ fn lower_optional_else_block(
             match &expr {
                 semantic::Expr::Block(block) => lower_block(ctx, builder, block),
                 semantic::Expr::If(if_expr) => {
-                    let lowered_if = lower_expr_if(ctx, &mut builder, if_expr);
+                    let lowered_if =
+                        ensure_sufficient_stack(|| lower_expr_if(ctx, &mut builder, if_expr));
                     lowered_expr_to_block_scope_end(ctx, builder, lowered_if)
                 }
                 _ => unreachable!(),
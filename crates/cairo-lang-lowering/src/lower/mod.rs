@@ -5,12 +5,12 @@ use cairo_lang_semantic::corelib;
 use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
-use cairo_lang_utils::{extract_matches, try_extract_matches};
+use cairo_lang_utils::{ensure_sufficient_stack, extract_matches, try_extract_matches};
 use itertools::{chain, zip_eq, Itertools};
 use num_bigint::{BigInt, Sign};
 use semantic::corelib::{
     core_felt252_ty, core_submodule, get_core_function_id, get_core_ty_by_name, get_function_id,
-    never_ty, unit_ty, validate_literal,
+    get_generic_function_id, never_ty, unit_ty, validate_literal,
 };
 use semantic::items::enm::SemanticEnumEx;
 use semantic::items::structure::SemanticStructEx;
@@ -162,14 +162,11 @@ pub fn lower_loop_function(
     encapsulating_ctx: &mut EncapsulatingLoweringContext<'_>,
     function_id: FunctionWithBodyId,
     signature: Signature,
-    expr: &semantic::ExprLoop,
+    loop_expr_id: semantic::ExprId,
 ) -> Maybe<FlatLowered> {
     let mut ctx = LoweringContext::new(encapsulating_ctx, function_id, signature)?;
-    ctx.current_loop_expr = Some(expr.clone());
-
-    // Fetch body block expr.
-    let semantic_block =
-        extract_matches!(&ctx.function_body.exprs[expr.body], semantic::Expr::Block).clone();
+    ctx.current_loop_expr_id = Some(loop_expr_id);
+    let loop_expr = ctx.function_body.exprs[loop_expr_id].clone();
 
     // Initialize builder.
     let root_block_id = alloc_empty_block(&mut ctx);
@@ -189,18 +186,25 @@ pub fn lower_loop_function(
         .collect_vec();
 
     let root_ok = (|| {
-        let block_expr = (|| {
-            lower_expr_block(&mut ctx, &mut builder, &semantic_block)?;
-            // Add recursive call.
-            let signature = ctx.signature.clone();
-            call_loop_func(&mut ctx, signature, &mut builder, expr)
-        })();
+        let (block_expr, stable_ptr) = match &loop_expr {
+            semantic::Expr::Loop(expr) => {
+                let stable_ptr = ctx.function_body.exprs[expr.body].stable_ptr();
+                (lower_loop_iteration(&mut ctx, &mut builder, expr.body, loop_expr_id), stable_ptr)
+            }
+            semantic::Expr::While(expr) => {
+                (lower_while_iteration(&mut ctx, &mut builder, expr, loop_expr_id), expr.stable_ptr)
+            }
+            semantic::Expr::For(expr) => {
+                (lower_for_iteration(&mut ctx, &mut builder, expr, loop_expr_id), expr.stable_ptr)
+            }
+            _ => unreachable!("Only loop expressions are lowered into loop functions."),
+        };
         let block_sealed = lowered_expr_to_block_scope_end(&mut ctx, builder, block_expr)?;
 
         match block_sealed {
             SealedBlockBuilder::GotoCallsite { mut builder, expr } => {
                 // Convert to a return.
-                let location = ctx.get_location(semantic_block.stable_ptr.untyped());
+                let location = ctx.get_location(stable_ptr.untyped());
                 let var_usage = expr.unwrap_or_else(|| {
                     generators::StructConstruct {
                         inputs: vec![],
@@ -227,6 +231,156 @@ pub fn lower_loop_function(
     })
 }
 
+/// Lowers a single iteration of a `loop` - its body, followed by a recursive call to the loop
+/// function for the next iteration.
+fn lower_loop_iteration(
+    ctx: &mut LoweringContext<'_, '_>,
+    builder: &mut BlockBuilder,
+    body: semantic::ExprId,
+    loop_expr_id: semantic::ExprId,
+) -> LoweringResult<LoweredExpr> {
+    let semantic_block =
+        extract_matches!(&ctx.function_body.exprs[body], semantic::Expr::Block).clone();
+    lower_expr_block(ctx, builder, &semantic_block)?;
+    // Add recursive call.
+    let signature = ctx.signature.clone();
+    call_loop_func(ctx, signature, builder, loop_expr_id)
+}
+
+/// Lowers a single iteration of a `while` loop - matches on its condition, running an iteration of
+/// its body if it holds, and returning otherwise.
+fn lower_while_iteration(
+    ctx: &mut LoweringContext<'_, '_>,
+    builder: &mut BlockBuilder,
+    expr: &semantic::ExprWhile,
+    loop_expr_id: semantic::ExprId,
+) -> LoweringResult<LoweredExpr> {
+    log::trace!("Lowering a while loop: {:?}", expr.debug(&ctx.expr_formatter));
+    let condition = lower_expr_to_var_usage(ctx, builder, expr.condition)?;
+    let semantic_db = ctx.db.upcast();
+    let unit_ty = corelib::unit_ty(semantic_db);
+    let while_location = ctx.get_location(expr.stable_ptr.untyped());
+
+    // Main block.
+    let mut subscope_main = create_subscope_with_bound_refs(ctx, builder);
+    let block_main_id = subscope_main.block_id;
+    let main_block_var_id = ctx.new_var(VarRequest { ty: unit_ty, location: while_location });
+    let block_main_expr = lower_loop_iteration(ctx, &mut subscope_main, expr.body, loop_expr_id);
+    let block_main = lowered_expr_to_block_scope_end(ctx, subscope_main, block_main_expr)
+        .map_err(LoweringFlowError::Failed)?;
+
+    // Else block - exits the loop.
+    let subscope_else = create_subscope_with_bound_refs(ctx, builder);
+    let block_else_id = subscope_else.block_id;
+    let else_block_input_var_id = ctx.new_var(VarRequest { ty: unit_ty, location: while_location });
+    let block_else = lowered_expr_to_block_scope_end(
+        ctx,
+        subscope_else,
+        Ok(LoweredExpr::Tuple { exprs: vec![], location: while_location }),
+    )
+    .map_err(LoweringFlowError::Failed)?;
+
+    let match_info = MatchInfo::Enum(MatchEnumInfo {
+        concrete_enum_id: corelib::core_bool_enum(semantic_db),
+        input: condition,
+        arms: vec![
+            MatchArm {
+                variant_id: corelib::false_variant(semantic_db),
+                block_id: block_else_id,
+                var_ids: vec![else_block_input_var_id],
+            },
+            MatchArm {
+                variant_id: corelib::true_variant(semantic_db),
+                block_id: block_main_id,
+                var_ids: vec![main_block_var_id],
+            },
+        ],
+        location: while_location,
+    });
+    builder.merge_and_end_with_match(ctx, match_info, vec![block_main, block_else], while_location)
+}
+
+/// Lowers a single iteration of a `for` loop - advances its iterator, running an iteration of its
+/// body on the next item if there is one, and returning once the iterator is exhausted.
+fn lower_for_iteration(
+    ctx: &mut LoweringContext<'_, '_>,
+    builder: &mut BlockBuilder,
+    expr: &semantic::ExprFor,
+    loop_expr_id: semantic::ExprId,
+) -> LoweringResult<LoweredExpr> {
+    log::trace!("Lowering a for loop: {:?}", expr.debug(&ctx.expr_formatter));
+    let semantic_db = ctx.db.upcast();
+    let unit_ty = corelib::unit_ty(semantic_db);
+    let for_location = ctx.get_location(expr.stable_ptr.untyped());
+    let pattern = ctx.function_body.patterns[expr.pattern].clone();
+    let item_ty = pattern.ty();
+    let some_variant = corelib::option_some_variant(semantic_db, GenericArgumentId::Type(item_ty));
+    let none_variant = corelib::option_none_variant(semantic_db, GenericArgumentId::Type(item_ty));
+
+    // Advance the iterator.
+    let iterator = builder.get_ref(ctx, &expr.iterator_var).ok_or_else(|| {
+        LoweringFlowError::Failed(
+            ctx.diagnostics.report(expr.stable_ptr.untyped(), UnboundForLoopIterator),
+        )
+    })?;
+    let (extra_outputs, next_value) = perform_function_call(
+        ctx,
+        builder,
+        expr.next_function_id,
+        vec![iterator],
+        vec![expr.iterator_var.ty()],
+        ctx.db
+            .intern_type(TypeLongId::Concrete(ConcreteTypeId::Enum(some_variant.concrete_enum_id))),
+        for_location,
+    )?;
+    builder.update_ref(ctx, &expr.iterator_var, extra_outputs[0].var_id);
+    let next_value = next_value.as_var_usage(ctx, builder)?;
+
+    // Some block - binds the item and runs an iteration.
+    let mut subscope_some = create_subscope_with_bound_refs(ctx, builder);
+    let block_some_id = subscope_some.block_id;
+    let some_var_id = ctx.new_var(VarRequest { ty: item_ty, location: for_location });
+    let block_some_expr = lower_single_pattern(
+        ctx,
+        &mut subscope_some,
+        pattern,
+        LoweredExpr::AtVariable(VarUsage { var_id: some_var_id, location: for_location }),
+    )
+    .and_then(|()| lower_loop_iteration(ctx, &mut subscope_some, expr.body, loop_expr_id));
+    let block_some = lowered_expr_to_block_scope_end(ctx, subscope_some, block_some_expr)
+        .map_err(LoweringFlowError::Failed)?;
+
+    // None block - exits the loop.
+    let subscope_none = create_subscope_with_bound_refs(ctx, builder);
+    let block_none_id = subscope_none.block_id;
+    let none_var_id = ctx.new_var(VarRequest { ty: unit_ty, location: for_location });
+    let block_none = lowered_expr_to_block_scope_end(
+        ctx,
+        subscope_none,
+        Ok(LoweredExpr::Tuple { exprs: vec![], location: for_location }),
+    )
+    .map_err(LoweringFlowError::Failed)?;
+
+    let match_info = MatchInfo::Enum(MatchEnumInfo {
+        concrete_enum_id: some_variant.concrete_enum_id,
+        input: next_value,
+        arms: vec![
+            MatchArm {
+                variant_id: some_variant,
+                block_id: block_some_id,
+                var_ids: vec![some_var_id],
+            },
+            MatchArm {
+                variant_id: none_variant,
+                block_id: block_none_id,
+                var_ids: vec![none_var_id],
+            },
+        ],
+        location: for_location,
+    });
+    builder.merge_and_end_with_match(ctx, match_info, vec![block_some, block_none], for_location)
+}
+
 /// Lowers a semantic block.
 fn lower_block(
     ctx: &mut LoweringContext<'_, '_>,
@@ -326,8 +480,8 @@ pub fn lower_statement(
         }
         semantic::Statement::Continue(semantic::StatementContinue { stable_ptr }) => {
             log::trace!("Lowering a continue statement.");
-            let loop_expr = ctx.current_loop_expr.clone().unwrap();
-            let lowered_expr = call_loop_func(ctx, ctx.signature.clone(), builder, &loop_expr)?;
+            let loop_expr_id = ctx.current_loop_expr_id.unwrap();
+            let lowered_expr = call_loop_func(ctx, ctx.signature.clone(), builder, loop_expr_id)?;
             let ret_var = lowered_expr.as_var_usage(ctx, builder)?;
             return Err(LoweringFlowError::Return(ret_var, ctx.get_location(stable_ptr.untyped())));
         }
@@ -459,6 +613,19 @@ fn lower_single_pattern(
                 )?;
             }
         }
+        semantic::Pattern::Binding(semantic::PatternBinding {
+            variable, inner_pattern, ..
+        }) => {
+            let var_usage = lowered_expr.as_var_usage(ctx, builder)?;
+            let (var_usage, snapshot) = snapshot_matched_value(ctx, builder, var_usage);
+            bind_matched_value(ctx, builder, vec![variable], snapshot);
+            lower_single_pattern(
+                ctx,
+                builder,
+                ctx.function_body.patterns[inner_pattern].clone(),
+                LoweredExpr::AtVariable(var_usage),
+            )?;
+        }
         semantic::Pattern::EnumVariant(_) => unreachable!(),
         semantic::Pattern::Otherwise(_) => {}
         semantic::Pattern::Missing(_) => unreachable!("Missing pattern in semantic model."),
@@ -466,6 +633,76 @@ fn lower_single_pattern(
     Ok(())
 }
 
+/// Splits the variables binding the whole matched value off a pattern (e.g. `v` in
+/// `v @ Some(x)`), and returns them along with the rest of the pattern.
+fn split_pattern_bindings(
+    ctx: &LoweringContext<'_, '_>,
+    mut pattern: semantic::Pattern,
+) -> (Vec<semantic::PatternVariable>, semantic::Pattern) {
+    let mut bindings = vec![];
+    while let semantic::Pattern::Binding(binding) = pattern {
+        bindings.push(binding.variable);
+        pattern = ctx.function_body.patterns[binding.inner_pattern].clone();
+    }
+    (bindings, pattern)
+}
+
+/// Takes a snapshot of the matched value for the variables binding it, so that it doesn't have to
+/// be duplicatable. A matched snapshot is bound as is.
+/// Returns the value to keep matching on, and the value to bind.
+fn snapshot_matched_value(
+    ctx: &mut LoweringContext<'_, '_>,
+    builder: &mut BlockBuilder,
+    matched_value: VarUsage,
+) -> (VarUsage, VarUsage) {
+    let ty = ctx.variables[matched_value.var_id].ty;
+    if matches!(ctx.db.lookup_intern_type(ty), TypeLongId::Snapshot(_)) {
+        return (matched_value, matched_value);
+    }
+    let location = matched_value.location;
+    let (original, snapshot) =
+        generators::Snapshot { input: matched_value, location }.add(ctx, &mut builder.statements);
+    (VarUsage { var_id: original, location }, VarUsage { var_id: snapshot, location })
+}
+
+/// Binds the given pattern variables to the (snapshot of the) matched value.
+fn bind_matched_value(
+    ctx: &mut LoweringContext<'_, '_>,
+    builder: &mut BlockBuilder,
+    bindings: Vec<semantic::PatternVariable>,
+    matched_value: VarUsage,
+) {
+    for binding in bindings {
+        let sem_var = semantic::Variable::Local(binding.var);
+        builder.put_semantic(sem_var.id(), matched_value.var_id);
+        // TODO(spapini): Build semantic_defs in semantic model.
+        ctx.semantic_defs.insert(sem_var.id(), sem_var);
+    }
+}
+
+/// Returns true if the pattern matches any value of its type, i.e., it only binds or destructures.
+fn is_irrefutable_pattern(ctx: &LoweringContext<'_, '_>, pattern: &semantic::Pattern) -> bool {
+    let is_irrefutable_id = |pattern_id: &semantic::PatternId| {
+        is_irrefutable_pattern(ctx, &ctx.function_body.patterns[*pattern_id])
+    };
+    match pattern {
+        semantic::Pattern::Variable(_) | semantic::Pattern::Otherwise(_) => true,
+        semantic::Pattern::Tuple(semantic::PatternTuple { field_patterns, .. }) => {
+            field_patterns.iter().all(is_irrefutable_id)
+        }
+        semantic::Pattern::Struct(semantic::PatternStruct { field_patterns, .. }) => {
+            field_patterns.iter().all(|(_, pattern_id)| is_irrefutable_id(pattern_id))
+        }
+        semantic::Pattern::Binding(semantic::PatternBinding { inner_pattern, .. }) => {
+            is_irrefutable_id(inner_pattern)
+        }
+        semantic::Pattern::Literal(_)
+        | semantic::Pattern::StringLiteral(_)
+        | semantic::Pattern::EnumVariant(_)
+        | semantic::Pattern::Missing(_) => false,
+    }
+}
+
 /// Lowers a semantic expression to a VarUsage.
 ///
 /// For example, if we have the code:
@@ -486,6 +723,15 @@ fn lower_expr(
     ctx: &mut LoweringContext<'_, '_>,
     builder: &mut BlockBuilder,
     expr_id: semantic::ExprId,
+) -> LoweringResult<LoweredExpr> {
+    ensure_sufficient_stack(|| lower_expr_inner(ctx, builder, expr_id))
+}
+
+/// Same as [lower_expr], without growing the stack.
+fn lower_expr_inner(
+    ctx: &mut LoweringContext<'_, '_>,
+    builder: &mut BlockBuilder,
+    expr_id: semantic::ExprId,
 ) -> LoweringResult<LoweredExpr> {
     let expr = ctx.function_body.exprs[expr_id].clone();
     match &expr {
@@ -499,7 +745,9 @@ fn lower_expr(
         semantic::Expr::FunctionCall(expr) => lower_expr_function_call(ctx, expr, builder),
         semantic::Expr::Match(expr) => lower_expr_match(ctx, expr, builder),
         semantic::Expr::If(expr) => lower_expr_if(ctx, builder, expr),
-        semantic::Expr::Loop(expr) => lower_expr_loop(ctx, expr, builder),
+        semantic::Expr::Loop(_) | semantic::Expr::While(_) | semantic::Expr::For(_) => {
+            lower_expr_loop(ctx, expr_id, builder)
+        }
         semantic::Expr::Var(expr) => {
             let member_path = ExprVarMemberPath::Var(expr.clone());
             log::trace!("Lowering a variable: {:?}", expr.debug(&ctx.expr_formatter));
@@ -771,6 +1019,44 @@ fn lower_expr_desnap(
     ))
 }
 
+/// Checks that calls to the enum conversion functions of the core library are made with a C-like
+/// enum, as these are the only enums with discriminants.
+/// Generic parameters are rejected, as their substitutions are not checked - generic code should
+/// require `CLikeEnum` instead, which is checked at inference.
+fn check_enum_conversion_generic_arg(
+    ctx: &mut LoweringContext<'_, '_>,
+    expr: &semantic::ExprFunctionCall,
+) -> LoweringResult<()> {
+    let semantic_db = ctx.db.upcast();
+    let concrete_function = expr.function.get_concrete(semantic_db);
+    let enums_module = core_submodule(semantic_db, "enums");
+    if !["enum_to_felt252", "enum_try_from_felt252"].into_iter().any(|name| {
+        concrete_function.generic_function
+            == get_generic_function_id(semantic_db, enums_module, name.into())
+    }) {
+        return Ok(());
+    }
+    let [GenericArgumentId::Type(ty)] = concrete_function.generic_args[..] else {
+        return Ok(());
+    };
+    let is_c_like_enum = match semantic_db.lookup_intern_type(ty) {
+        TypeLongId::Concrete(ConcreteTypeId::Enum(concrete_enum_id)) => {
+            matches!(
+                semantic_db.enum_discriminants(concrete_enum_id.enum_id(semantic_db)),
+                Ok(Some(_))
+            )
+        }
+        TypeLongId::Missing(_) => true,
+        _ => false,
+    };
+    if is_c_like_enum {
+        return Ok(());
+    }
+    Err(LoweringFlowError::Failed(
+        ctx.diagnostics.report(expr.stable_ptr.untyped(), ExpectedCLikeEnum { ty }),
+    ))
+}
+
 /// Lowers an expression of type [semantic::ExprFunctionCall].
 fn lower_expr_function_call(
     ctx: &mut LoweringContext<'_, '_>,
@@ -798,6 +1084,8 @@ fn lower_expr_function_call(
         return Err(LoweringFlowError::Panic(input, location));
     }
 
+    check_enum_conversion_generic_arg(ctx, expr)?;
+
     // The following is relevant only to extern functions.
     if expr.function.try_get_extern_function_id(ctx.db.upcast()).is_some() {
         if let semantic::TypeLongId::Concrete(semantic::ConcreteTypeId::Enum(concrete_enum_id)) =
@@ -905,13 +1193,26 @@ fn perform_function_call(
     ))
 }
 
-/// Lowers an expression of type [semantic::ExprLoop].
+/// Lowers an expression of type [semantic::ExprLoop], [semantic::ExprWhile] or
+/// [semantic::ExprFor].
 fn lower_expr_loop(
     ctx: &mut LoweringContext<'_, '_>,
-    expr: &semantic::ExprLoop,
+    loop_expr_id: semantic::ExprId,
     builder: &mut BlockBuilder,
 ) -> LoweringResult<LoweredExpr> {
-    let usage = &ctx.block_usages.block_usages[expr.body];
+    if let semantic::Expr::For(expr) = ctx.function_body.exprs[loop_expr_id].clone() {
+        // The iterator is created before the loop, and is passed to the loop function.
+        let iterator = lower_expr_to_var_usage(ctx, builder, expr.expr_id)?;
+        let iterator_var = semantic::Variable::Local(semantic::LocalVariable {
+            id: extract_matches!(expr.iterator_var.base_var(), semantic::VarId::Local),
+            ty: expr.iterator_var.ty(),
+            is_mut: true,
+        });
+        builder.put_semantic(iterator_var.id(), iterator.var_id);
+        ctx.semantic_defs.insert(iterator_var.id(), iterator_var);
+    }
+    let element = loop_function_element(ctx, loop_expr_id);
+    let usage = &ctx.block_usages.block_usages[element];
 
     // Determine signature.
     let params = usage.usage.iter().map(|(_, expr)| expr.clone()).collect_vec();
@@ -920,7 +1221,7 @@ fn lower_expr_loop(
     let signature = Signature {
         params,
         extra_rets,
-        return_type: expr.ty,
+        return_type: ctx.function_body.exprs[loop_expr_id].ty(),
         implicits: vec![],
         panicable: ctx.signature.panicable,
     };
@@ -928,19 +1229,33 @@ fn lower_expr_loop(
     // Get the function id.
     let function = ctx.db.intern_lowering_function_with_body(FunctionWithBodyLongId::Generated {
         parent: ctx.semantic_function_id,
-        element: expr.body,
+        element,
     });
 
     // Generate the function.
     let encapsulating_ctx = std::mem::take(&mut ctx.encapsulating_ctx).unwrap();
-    let lowered = lower_loop_function(encapsulating_ctx, function, signature.clone(), expr)
+    let lowered = lower_loop_function(encapsulating_ctx, function, signature.clone(), loop_expr_id)
         .map_err(LoweringFlowError::Failed)?;
     // TODO(spapini): Recursive call.
-    encapsulating_ctx.lowerings.insert(expr.body, lowered);
+    encapsulating_ctx.lowerings.insert(element, lowered);
     ctx.encapsulating_ctx = Some(encapsulating_ctx);
-    ctx.current_loop_expr = Some(expr.clone());
+    ctx.current_loop_expr_id = Some(loop_expr_id);
 
-    call_loop_func(ctx, signature, builder, expr)
+    call_loop_func(ctx, signature, builder, loop_expr_id)
+}
+
+/// Returns the expression identifying the function generated for a loop expression - the body of
+/// a `loop`, or the whole expression of a `while` or a `for`, as their conditions are evaluated in
+/// the function.
+fn loop_function_element(
+    ctx: &LoweringContext<'_, '_>,
+    loop_expr_id: semantic::ExprId,
+) -> semantic::ExprId {
+    match &ctx.function_body.exprs[loop_expr_id] {
+        semantic::Expr::Loop(expr) => expr.body,
+        semantic::Expr::While(_) | semantic::Expr::For(_) => loop_expr_id,
+        _ => unreachable!("Only loop expressions are lowered into loop functions."),
+    }
 }
 
 /// Adds a call to an inner loop-generated function.
@@ -948,15 +1263,17 @@ fn call_loop_func(
     ctx: &mut LoweringContext<'_, '_>,
     signature: Signature,
     builder: &mut BlockBuilder,
-    expr: &semantic::ExprLoop,
+    loop_expr_id: semantic::ExprId,
 ) -> LoweringResult<LoweredExpr> {
-    let stable_ptr = expr.stable_ptr.untyped();
-    let location = ctx.get_location(expr.stable_ptr.untyped());
+    let loop_expr = &ctx.function_body.exprs[loop_expr_id];
+    let ty = loop_expr.ty();
+    let stable_ptr = loop_expr.stable_ptr().untyped();
+    let location = ctx.get_location(stable_ptr);
 
     // Call it.
     let function = ctx.db.intern_lowering_function(FunctionLongId::Generated(GeneratedFunction {
         parent: ctx.concrete_function_id.base_semantic_function(ctx.db),
-        element: expr.body,
+        element: loop_function_element(ctx, loop_expr_id),
     }));
     let inputs = signature
         .params
@@ -969,7 +1286,7 @@ fn call_loop_func(
         .collect::<LoweringResult<Vec<_>>>()?;
     let extra_ret_tys = signature.extra_rets.iter().map(|path| path.ty()).collect_vec();
     let call_result =
-        generators::Call { function, inputs, extra_ret_tys, ret_tys: vec![expr.ty], location }
+        generators::Call { function, inputs, extra_ret_tys, ret_tys: vec![ty], location }
             .add(ctx, &mut builder.statements);
 
     // Rebind the ref variables.
@@ -995,15 +1312,50 @@ fn lower_expr_match(
         return lower_expr_match_felt252(ctx, expr, match_input, builder);
     }
 
+    // A match whose first arm cannot fail (e.g. a tuple of bindings) destructures the matched
+    // value, as a `let` statement would.
+    let first_pattern =
+        expr.arms.first().map(|arm| ctx.function_body.patterns[arm.pattern].clone());
+    if let Some(pattern) = first_pattern.filter(|pattern| is_irrefutable_pattern(ctx, pattern)) {
+        if let [_, unreachable_arms @ ..] = &expr.arms[..] {
+            if let (Some(first), Some(last)) = (unreachable_arms.first(), unreachable_arms.last()) {
+                ctx.diagnostics.report(
+                    ctx.function_body.patterns[first.pattern].stable_ptr().untyped(),
+                    Unreachable {
+                        last_statement_ptr: ctx.function_body.exprs[last.expression]
+                            .stable_ptr()
+                            .untyped(),
+                    },
+                );
+            }
+        }
+        lower_single_pattern(ctx, builder, pattern, lowered_expr)?;
+        return lower_expr(ctx, builder, expr.arms[0].expression);
+    }
+
     // TODO(spapini): Use diagnostics.
     // TODO(spapini): Handle more than just enums.
-    if let LoweredExpr::ExternEnum(extern_enum) = lowered_expr {
-        return lower_optimized_extern_match(ctx, builder, extern_enum, &expr.arms);
-    }
+    // Binding the whole matched value requires it to be a variable, so the extern function is not
+    // matched directly in this case.
+    let binds_matched_value = expr.arms.iter().any(|arm| {
+        matches!(ctx.function_body.patterns[arm.pattern], semantic::Pattern::Binding(_))
+    });
+    let lowered_expr = match lowered_expr {
+        LoweredExpr::ExternEnum(extern_enum) if !binds_matched_value => {
+            return lower_optimized_extern_match(ctx, builder, extern_enum, &expr.arms);
+        }
+        lowered_expr => lowered_expr,
+    };
 
     let ExtractedEnumDetails { concrete_enum_id, concrete_variants, n_snapshots } =
         extract_concrete_enum(ctx, expr)?;
     let match_input = lowered_expr.as_var_usage(ctx, builder)?;
+    // The snapshot of the matched value is taken before the match, which consumes the value.
+    let (match_input, matched_snapshot) = if binds_matched_value {
+        snapshot_matched_value(ctx, builder, match_input)
+    } else {
+        (match_input, match_input)
+    };
 
     // Merge arm blocks.
 
@@ -1012,7 +1364,8 @@ fn lower_expr_match(
         .map(|(concrete_variant, arm)| {
             let mut subscope = create_subscope_with_bound_refs(ctx, builder);
             let block_id = subscope.block_id;
-            let pattern = ctx.function_body.patterns[arm.pattern].clone();
+            let (bindings, pattern) =
+                split_pattern_bindings(ctx, ctx.function_body.patterns[arm.pattern].clone());
 
             let enum_pattern = try_extract_matches!(&pattern, semantic::Pattern::EnumVariant)
                 .ok_or_else(|| {
@@ -1027,6 +1380,7 @@ fn lower_expr_match(
                         .report(pattern.stable_ptr().untyped(), UnsupportedMatchArmOutOfOrder),
                 ));
             }
+            bind_matched_value(ctx, &mut subscope, bindings, matched_snapshot);
 
             let lowering_inner_pattern_result = match &enum_pattern.inner_pattern {
                 Some(inner_pattern) => {
@@ -1177,6 +1531,12 @@ fn lower_felt252_arm_block(
 ) -> Maybe<SealedBlockBuilder> {
     let arm = &expr.arms[index];
     if index == expr.arms.len() - 1 {
+        let (bindings, _) =
+            split_pattern_bindings(ctx, ctx.function_body.patterns[arm.pattern].clone());
+        if !bindings.is_empty() {
+            let (_, snapshot) = snapshot_matched_value(ctx, &mut builder, match_input);
+            bind_matched_value(ctx, &mut builder, bindings, snapshot);
+        }
         return lower_tail_expr(ctx, builder, arm.expression);
     }
     let lowered = lower_expr_felt252_arm(ctx, expr, match_input, &mut builder, index);
@@ -1195,7 +1555,8 @@ fn lower_expr_felt252_arm(
     let arm = &expr.arms[index];
     let semantic_db = ctx.db.upcast();
 
-    let pattern = ctx.function_body.patterns[arm.pattern].clone();
+    let (bindings, pattern) =
+        split_pattern_bindings(ctx, ctx.function_body.patterns[arm.pattern].clone());
     let semantic::Pattern::Literal(semantic::PatternLiteral { literal, .. }) = pattern else {
         return Err(LoweringFlowError::Failed(
             ctx.diagnostics.report(pattern.stable_ptr().untyped(), UnsupportedMatchedValue),
@@ -1230,9 +1591,13 @@ fn lower_expr_felt252_arm(
     let else_block_input_var_id = ctx.new_var(VarRequest { ty: non_zero_type, location });
 
     let block_id: BlockId = alloc_empty_block(ctx);
+    let mut arm_builder = builder.child_block_builder(block_id);
+    if !bindings.is_empty() {
+        let (_, snapshot) = snapshot_matched_value(ctx, &mut arm_builder, match_input);
+        bind_matched_value(ctx, &mut arm_builder, bindings, snapshot);
+    }
     let match_arm_content_block =
-        lower_tail_expr(ctx, builder.child_block_builder(block_id), arm.expression)
-            .map_err(LoweringFlowError::Failed)?;
+        lower_tail_expr(ctx, arm_builder, arm.expression).map_err(LoweringFlowError::Failed)?;
 
     let else_block = create_subscope_with_bound_refs(ctx, builder);
     let block_else_id = else_block.block_id;
@@ -1278,7 +1643,10 @@ fn lower_expr_match_felt252(
             ctx.diagnostics.report(expr.stable_ptr.untyped(), NonExhaustiveMatchFelt252),
         ));
     }
-    let pattern = ctx.function_body.patterns[expr.arms.last().unwrap().pattern].clone();
+    let (_, pattern) = split_pattern_bindings(
+        ctx,
+        ctx.function_body.patterns[expr.arms.last().unwrap().pattern].clone(),
+    );
     let semantic::Pattern::Otherwise(_) = pattern else {
         return Err(LoweringFlowError::Failed(
             ctx.diagnostics.report(pattern.stable_ptr().untyped(), NonExhaustiveMatchFelt252),
@@ -8,6 +8,7 @@ use cairo_lang_semantic::{
     self as semantic, Expr, ExprFunctionCallArg, ExprId, ExprVarMemberPath, FunctionBody, Pattern,
     Statement, VarId,
 };
+use cairo_lang_utils::ensure_sufficient_stack;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
 use id_arena::Arena;
@@ -60,11 +61,61 @@ pub struct Usage {
     pub introductions: OrderedHashSet<VarId>,
 }
 
+impl Usage {
+    /// Removes the usages and changes of variables introduced in the scope, and the ones that are
+    /// members of other usages or changes.
+    fn finalize_as_scope(&mut self) {
+        for (member_path, _) in self.usage.clone() {
+            // Prune introductions from usages.
+            if self.introductions.contains(&member_path.base_var()) {
+                self.usage.swap_remove(&member_path);
+                continue;
+            }
+
+            // Prune usages that are members of other usages.
+            let mut current_path = member_path.clone();
+            while let MemberPath::Member { parent, .. } = current_path {
+                current_path = *parent.clone();
+                if self.usage.contains_key(&current_path) {
+                    self.usage.swap_remove(&member_path);
+                    break;
+                }
+            }
+        }
+        for (member_path, _) in self.changes.clone() {
+            // Prune introductions from changes.
+            if self.introductions.contains(&member_path.base_var()) {
+                self.changes.swap_remove(&member_path);
+            }
+
+            // Prune changes that are members of other changes.
+            let mut current_path = member_path.clone();
+            while let MemberPath::Member { parent, .. } = current_path {
+                current_path = *parent.clone();
+                if self.changes.contains_key(&current_path) {
+                    self.changes.swap_remove(&member_path);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Adds the usages and changes of an inner scope to this usage.
+    fn add_usage_and_changes(&mut self, usage: &Usage) {
+        for (path, expr) in usage.usage.iter() {
+            self.usage.insert(path.clone(), expr.clone());
+        }
+        for (path, expr) in usage.changes.iter() {
+            self.changes.insert(path.clone(), expr.clone());
+        }
+    }
+}
+
 /// Usages of variables and member paths in each semantic block of a function.
 #[derive(Debug, DebugWithDb)]
 #[debug_db(ExprFormatter<'a>)]
 pub struct BlockUsages {
-    /// Mapping from an [ExprId] for an block expression, to its [Usage].
+    /// Mapping from an [ExprId] for a block, a `while` or a `for` expression, to its [Usage].
     pub block_usages: OrderedHashMap<ExprId, Usage>,
 }
 impl BlockUsages {
@@ -76,6 +127,16 @@ impl BlockUsages {
     }
 
     fn handle_expr(&mut self, function_body: &FunctionBody, expr_id: ExprId, current: &mut Usage) {
+        ensure_sufficient_stack(|| self.handle_expr_inner(function_body, expr_id, current))
+    }
+
+    /// Same as [Self::handle_expr], without growing the stack.
+    fn handle_expr_inner(
+        &mut self,
+        function_body: &FunctionBody,
+        expr_id: ExprId,
+        current: &mut Usage,
+    ) {
         match &function_body.exprs[expr_id] {
             Expr::Tuple(expr) => {
                 for expr_id in &expr.items {
@@ -120,50 +181,33 @@ impl BlockUsages {
                 if let Some(expr_id) = expr.tail {
                     self.handle_expr(function_body, expr_id, &mut usage)
                 }
-                for (member_path, _) in usage.usage.clone() {
-                    // Prune introductions from usages.
-                    if usage.introductions.contains(&member_path.base_var()) {
-                        usage.usage.swap_remove(&member_path);
-                        continue;
-                    }
-
-                    // Prune usages that are members of other usages.
-                    let mut current_path = member_path.clone();
-                    while let MemberPath::Member { parent, .. } = current_path {
-                        current_path = *parent.clone();
-                        if usage.usage.contains_key(&current_path) {
-                            usage.usage.swap_remove(&member_path);
-                            break;
-                        }
-                    }
-                }
-                for (member_path, _) in usage.changes.clone() {
-                    // Prune introductions from changes.
-                    if usage.introductions.contains(&member_path.base_var()) {
-                        usage.changes.swap_remove(&member_path);
-                    }
-
-                    // Prune changes that are members of other changes.
-                    let mut current_path = member_path.clone();
-                    while let MemberPath::Member { parent, .. } = current_path {
-                        current_path = *parent.clone();
-                        if usage.changes.contains_key(&current_path) {
-                            usage.changes.swap_remove(&member_path);
-                            break;
-                        }
-                    }
-                }
-
-                for (path, expr) in usage.usage.iter() {
-                    current.usage.insert(path.clone(), expr.clone());
-                }
-                for (path, expr) in usage.changes.iter() {
-                    current.changes.insert(path.clone(), expr.clone());
-                }
-
+                usage.finalize_as_scope();
+                current.add_usage_and_changes(&usage);
                 self.block_usages.insert(expr_id, usage);
             }
             Expr::Loop(expr) => self.handle_expr(function_body, expr.body, current),
+            Expr::While(expr) => {
+                // The condition is evaluated in each iteration, so it is a part of the loop.
+                let mut usage = Default::default();
+                self.handle_expr(function_body, expr.condition, &mut usage);
+                self.handle_expr(function_body, expr.body, &mut usage);
+                usage.finalize_as_scope();
+                current.add_usage_and_changes(&usage);
+                self.block_usages.insert(expr_id, usage);
+            }
+            Expr::For(expr) => {
+                self.handle_expr(function_body, expr.expr_id, current);
+                // The iterator is introduced before the loop, and is advanced in each iteration.
+                current.introductions.insert(expr.iterator_var.base_var());
+                let mut usage = Usage::default();
+                usage.usage.insert((&expr.iterator_var).into(), expr.iterator_var.clone());
+                usage.changes.insert((&expr.iterator_var).into(), expr.iterator_var.clone());
+                Self::handle_pattern(&function_body.patterns, expr.pattern, &mut usage);
+                self.handle_expr(function_body, expr.body, &mut usage);
+                usage.finalize_as_scope();
+                current.add_usage_and_changes(&usage);
+                self.block_usages.insert(expr_id, usage);
+            }
             Expr::FunctionCall(expr) => {
                 for arg in &expr.args {
                     match arg {
@@ -240,6 +284,10 @@ impl BlockUsages {
                     Self::handle_pattern(arena, *inner_pattern, current);
                 }
             }
+            Pattern::Binding(pattern) => {
+                current.introductions.insert(VarId::Local(pattern.variable.var.id));
+                Self::handle_pattern(arena, pattern.inner_pattern, current);
+            }
             Pattern::Otherwise(_) => {}
             Pattern::Missing(_) => {}
         }
@@ -0,0 +1,124 @@
+#[cfg(test)]
+#[path = "const_folding_test.rs"]
+mod test;
+
+use std::collections::HashMap;
+
+use cairo_lang_semantic::corelib;
+use num_bigint::BigInt;
+use num_traits::Num;
+use once_cell::sync::Lazy;
+
+use crate::db::LoweringGroup;
+use crate::ids::{FunctionId, FunctionLongId};
+use crate::{FlatLowered, Statement, StatementLiteral, VariableId};
+
+/// The prime of the felt252 field.
+static PRIME: Lazy<BigInt> = Lazy::new(|| {
+    BigInt::from_str_radix("800000000000011000000000000000000000000000000000000000000000001", 16)
+        .unwrap()
+});
+
+/// An arithmetic operator of felt252 values.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Felt252Operator {
+    Add,
+    Sub,
+    Mul,
+}
+impl Felt252Operator {
+    /// Returns whether the order of the operands does not matter.
+    pub fn is_commutative(self) -> bool {
+        matches!(self, Felt252Operator::Add | Felt252Operator::Mul)
+    }
+
+    /// Applies the operator on the values, returning the result in the range `[0, PRIME)`.
+    pub fn apply(self, lhs: &BigInt, rhs: &BigInt) -> BigInt {
+        let result = match self {
+            Felt252Operator::Add => lhs + rhs,
+            Felt252Operator::Sub => lhs - rhs,
+            Felt252Operator::Mul => lhs * rhs,
+        };
+        ((result % &*PRIME) + &*PRIME) % &*PRIME
+    }
+}
+
+/// The ids of the extern functions applying the felt252 arithmetic operators.
+pub struct Felt252Operators {
+    add: FunctionId,
+    sub: FunctionId,
+    mul: FunctionId,
+}
+impl Felt252Operators {
+    pub fn new(db: &dyn LoweringGroup) -> Self {
+        let function_id =
+            |name: &str| {
+                db.intern_lowering_function(FunctionLongId::Semantic(
+                    corelib::get_core_function_id(db.upcast(), name.into(), vec![]),
+                ))
+            };
+        Self {
+            add: function_id("felt252_add"),
+            sub: function_id("felt252_sub"),
+            mul: function_id("felt252_mul"),
+        }
+    }
+
+    /// Returns the operator applied by a call to the given function, if any.
+    pub fn operator(&self, function: FunctionId) -> Option<Felt252Operator> {
+        if function == self.add {
+            Some(Felt252Operator::Add)
+        } else if function == self.sub {
+            Some(Felt252Operator::Sub)
+        } else if function == self.mul {
+            Some(Felt252Operator::Mul)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the values of the variables defined by literals.
+pub fn literal_values(lowered: &FlatLowered) -> HashMap<VariableId, BigInt> {
+    lowered
+        .blocks
+        .iter()
+        .flat_map(|(_, block)| &block.statements)
+        .filter_map(|statement| match statement {
+            Statement::Literal(stmt) => Some((stmt.output, stmt.value.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Replaces the felt252 additions, subtractions and multiplications of literals with a literal of
+/// their result.
+pub fn const_folding(db: &dyn LoweringGroup, lowered: &mut FlatLowered) {
+    if lowered.blocks.is_empty() {
+        return;
+    }
+    let operators = Felt252Operators::new(db);
+    let mut literals = literal_values(lowered);
+    for block in lowered.blocks.iter_mut() {
+        for statement in block.statements.iter_mut() {
+            let Statement::Call(stmt) = statement else {
+                continue;
+            };
+            let Some(operator) = operators.operator(stmt.function) else {
+                continue;
+            };
+            let [lhs, rhs] = &stmt.inputs[..] else {
+                continue;
+            };
+            let (Some(lhs), Some(rhs)) = (literals.get(&lhs.var_id), literals.get(&rhs.var_id))
+            else {
+                continue;
+            };
+            let value = operator.apply(lhs, rhs);
+            let output = stmt.outputs[0];
+            // Later operations on the result may be folded too.
+            literals.insert(output, value.clone());
+            *statement = Statement::Literal(StatementLiteral { value, output });
+        }
+    }
+}
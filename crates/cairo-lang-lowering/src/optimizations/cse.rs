@@ -0,0 +1,93 @@
+#[cfg(test)]
+#[path = "cse_test.rs"]
+mod test;
+
+use std::collections::{HashMap, HashSet};
+
+use cairo_lang_semantic::TypeId;
+use itertools::Itertools;
+
+use super::const_folding::{Felt252Operator, Felt252Operators};
+use super::{remove_statements, visit_blocks_with_inherited_state};
+use crate::borrow_check::analysis::StatementLocation;
+use crate::db::LoweringGroup;
+use crate::utils::{Rebuilder, RebuilderEx, VarRenamer};
+use crate::{FlatLowered, Statement, VariableId};
+
+/// An expression computed by a statement without side effects.
+#[derive(Clone, Hash, PartialEq, Eq)]
+enum Expression {
+    StructConstruct(Vec<VariableId>, TypeId),
+    StructDestructure(VariableId),
+    Felt252Operation(Felt252Operator, VariableId, VariableId),
+}
+
+/// Common subexpression elimination - removes a statement computing the same expression as a
+/// statement in a dominating block, replacing its outputs with the outputs of that statement.
+/// Applies to constructions of non-empty structs, struct destructures and felt252 arithmetic, whose
+/// outputs can be duplicated.
+pub fn eliminate_common_subexpressions(db: &dyn LoweringGroup, lowered: &mut FlatLowered) {
+    if lowered.blocks.is_empty() {
+        return;
+    }
+    let operators = Felt252Operators::new(db);
+    let mut renamer = VarRenamer::default();
+    let mut removed_statements = HashSet::<StatementLocation>::new();
+    // The state is the outputs of the expressions available in the block.
+    visit_blocks_with_inherited_state(
+        lowered,
+        |block_id, block, computed: &mut HashMap<Expression, Vec<VariableId>>| {
+            for (index, statement) in block.statements.iter().enumerate() {
+                let expression = match statement {
+                    // Constructing an empty struct is free, unlike duplicating it.
+                    Statement::StructConstruct(stmt) if stmt.inputs.is_empty() => None,
+                    Statement::StructConstruct(stmt) => Some(Expression::StructConstruct(
+                        stmt.inputs
+                            .iter()
+                            .map(|var_usage| renamer.map_var_id(var_usage.var_id))
+                            .collect(),
+                        lowered.variables[stmt.output].ty,
+                    )),
+                    Statement::StructDestructure(stmt) => {
+                        Some(Expression::StructDestructure(renamer.map_var_id(stmt.input.var_id)))
+                    }
+                    Statement::Call(stmt) => {
+                        operators.operator(stmt.function).and_then(|operator| {
+                            let (lhs, rhs) = stmt
+                                .inputs
+                                .iter()
+                                .map(|var_usage| renamer.map_var_id(var_usage.var_id))
+                                .collect_tuple()?;
+                            let (lhs, rhs) = if operator.is_commutative() && rhs < lhs {
+                                (rhs, lhs)
+                            } else {
+                                (lhs, rhs)
+                            };
+                            Some(Expression::Felt252Operation(operator, lhs, rhs))
+                        })
+                    }
+                    _ => None,
+                };
+                let Some(expression) = expression else {
+                    continue;
+                };
+                let outputs = statement.outputs();
+                if outputs.iter().any(|var| lowered.variables[*var].duplicatable.is_err()) {
+                    continue;
+                }
+                if let Some(computed_outputs) = computed.get(&expression) {
+                    for (output, computed_output) in outputs.iter().zip(computed_outputs) {
+                        renamer.renamed_vars.insert(*output, *computed_output);
+                    }
+                    removed_statements.insert((block_id, index));
+                } else {
+                    computed.insert(expression, outputs);
+                }
+            }
+        },
+    );
+    remove_statements(lowered, &removed_statements);
+    for block in lowered.blocks.iter_mut() {
+        *block = renamer.rebuild_block(block);
+    }
+}
@@ -0,0 +1,494 @@
+#[cfg(test)]
+#[path = "dead_storage_writes_test.rs"]
+mod test;
+
+use std::cmp::Reverse;
+
+use cairo_lang_defs::diagnostic_utils::StableLocation;
+use cairo_lang_defs::ids::{LanguageElementId, ModuleItemId, TraitId};
+use cairo_lang_diagnostics::{Diagnostics, DiagnosticsBuilder, Maybe};
+use cairo_lang_filesystem::flag::Flag;
+use cairo_lang_filesystem::ids::{FileLongId, FlagId, VirtualFile};
+use cairo_lang_semantic::corelib::{
+    core_crate, core_submodule, get_submodule, get_ty_by_name, unit_ty,
+};
+use cairo_lang_semantic::items::attribute::SemanticQueryAttrs;
+use cairo_lang_semantic::items::functions::{GenericFunctionId, ImplGenericFunctionId};
+use cairo_lang_semantic::items::imp::ImplId;
+use cairo_lang_semantic::{GenericArgumentId, TypeId};
+use cairo_lang_syntax::attribute::consts::STORAGE_WRITE_ATTR;
+use cairo_lang_utils::extract_matches;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use itertools::{chain, Itertools};
+
+use crate::borrow_check::analysis::{Analyzer, BackAnalysis, StatementLocation};
+use crate::db::LoweringGroup;
+use crate::diagnostic::{LoweringDiagnostic, LoweringDiagnosticKind};
+use crate::ids::{
+    ConcreteFunctionWithBodyId, ConcreteFunctionWithBodyLongId, FunctionId, FunctionLongId,
+    FunctionWithBodyId, Signature,
+};
+use crate::lower::context::{VarRequest, VariableAllocator};
+use crate::panic::PanicSignatureInfo;
+use crate::{
+    BlockId, FlatBlockEnd, FlatLowered, Location, MatchInfo, Statement, StatementCall, StatementEnumConstruct,
+    StatementStructConstruct, StatementStructDestructure, VarRemapping, VarUsage, VariableId,
+};
+
+/// Returns whether dead storage writes should be removed, as set by the
+/// `eliminate_dead_storage_writes` flag.
+pub fn dead_storage_writes_elimination_enabled(db: &dyn LoweringGroup) -> bool {
+    match db.get_flag(FlagId::new(db.upcast(), "eliminate_dead_storage_writes")) {
+        Some(flag) => extract_matches!(*flag, Flag::EliminateDeadStorageWrites),
+        None => false,
+    }
+}
+
+/// Removes the dead storage writes of a function: the calls to functions marked with
+/// `#[storage_write]` that are followed, on every path, by another call to the same function, or
+/// by a panic reverting them, before anything may access the storage.
+///
+/// Expects the lowering after the panics are lowered and before the implicits are.
+pub fn eliminate_dead_storage_writes(
+    db: &dyn LoweringGroup,
+    function: ConcreteFunctionWithBodyId,
+    lowered: &mut FlatLowered,
+) -> Maybe<()> {
+    let dead_writes = find_dead_storage_writes(db, function, lowered)?;
+    if dead_writes.is_empty() {
+        return Ok(());
+    }
+    let mut variables = VariableAllocator::new(
+        db,
+        function.function_with_body_id(db).base_semantic_function(db),
+        lowered.variables.clone(),
+    )?;
+
+    // Replace the calls in reverse order to prevent a replacement from invalidating the locations
+    // of the other calls.
+    for (block_id, index) in dead_writes.into_iter().sorted_by_key(|(_, index)| Reverse(*index)) {
+        let statements = &mut lowered.blocks[block_id].statements;
+        let call = extract_matches!(&statements[index], Statement::Call).clone();
+        let replacement = skipped_call_statements(db, &mut variables, &call)?;
+        statements.splice(index..=index, replacement);
+    }
+    lowered.variables = variables.variables;
+    Ok(())
+}
+
+/// Returns the statements defining the outputs of a skipped storage write call: the reference
+/// arguments are returned unchanged, along with the unit result, wrapped in `Ok` if the function
+/// may panic.
+fn skipped_call_statements(
+    db: &dyn LoweringGroup,
+    variables: &mut VariableAllocator<'_>,
+    call: &StatementCall,
+) -> Maybe<Vec<Statement>> {
+    let signature = call.function.signature(db)?;
+    let panic_info = PanicSignatureInfo::new(db, &signature);
+    let (ref_inputs, _) = split_ref_inputs(&signature, &call.inputs);
+    let location = call.location;
+
+    let unit = variables.new_var(VarRequest { ty: unit_ty(db.upcast()), location });
+    let ok_value = variables.new_var(VarRequest { ty: panic_info.ok_ty, location });
+    let mut statements = vec![
+        Statement::StructConstruct(StatementStructConstruct { inputs: vec![], output: unit }),
+        Statement::StructConstruct(StatementStructConstruct {
+            inputs: chain!(ref_inputs, [VarUsage { var_id: unit, location }]).collect(),
+            output: ok_value,
+        }),
+    ];
+    let input = VarUsage { var_id: ok_value, location };
+    statements.push(if variables[call.outputs[0]].ty == panic_info.panic_ty {
+        Statement::EnumConstruct(StatementEnumConstruct {
+            variant: panic_info.ok_variant,
+            input,
+            output: call.outputs[0],
+        })
+    } else {
+        Statement::StructDestructure(StatementStructDestructure {
+            input,
+            outputs: call.outputs.clone(),
+        })
+    });
+    Ok(statements)
+}
+
+/// Splits the arguments of a call into the ones passed by reference and the consumed ones.
+fn split_ref_inputs(signature: &Signature, inputs: &[VarUsage]) -> (Vec<VarUsage>, Vec<VarUsage>) {
+    let (ref_inputs, consumed_inputs): (Vec<_>, Vec<_>) = signature
+        .params
+        .iter()
+        .zip(inputs)
+        .partition(|(param, _)| signature.extra_rets.contains(param));
+    (
+        ref_inputs.into_iter().map(|(_, input)| *input).collect(),
+        consumed_inputs.into_iter().map(|(_, input)| *input).collect(),
+    )
+}
+
+/// Returns the warnings about the dead storage writes that are written directly in a function,
+/// whether or not they are removed.
+pub fn dead_storage_writes_diagnostics(
+    db: &dyn LoweringGroup,
+    function_id: FunctionWithBodyId,
+) -> Maybe<Diagnostics<LoweringDiagnostic>> {
+    let mut diagnostics = DiagnosticsBuilder::default();
+    let semantic_function = function_id.base_semantic_function(db);
+    // Only the functions with storage writes of their own, and which can be analyzed without
+    // choosing generic arguments, are checked.
+    let lowered = db.function_with_body_lowering(function_id)?;
+    let has_storage_writes = lowered.blocks.iter().any(|(_, block)| {
+        block.statements.iter().any(|stmt| {
+            matches!(stmt, Statement::Call(call) if is_storage_write(db, call.function))
+        })
+    });
+    if !has_storage_writes || !db.function_with_body_generic_params(semantic_function)?.is_empty()
+    {
+        return Ok(diagnostics.build());
+    }
+
+    let function = function_id.to_concrete(db)?;
+    let lowered = db.concrete_function_with_body_postpanic_lowered(function)?;
+    let function_location = StableLocation::new(semantic_function.untyped_stable_ptr(db.upcast()))
+        .diagnostic_location(db.upcast());
+    for (block_id, index) in find_dead_storage_writes(db, function, &lowered)? {
+        let call = extract_matches!(&lowered.blocks[block_id].statements[index], Statement::Call);
+        let stable_location = call.location.get(db).stable_location;
+        // Writes inlined from other functions are reported in their own function.
+        let call_location = stable_location.diagnostic_location(db.upcast());
+        if call_location.file_id == function_location.file_id
+            && function_location.span.contains(call_location.span)
+        {
+            diagnostics.add(LoweringDiagnostic {
+                location: Location::new(stable_location),
+                kind: LoweringDiagnosticKind::DeadStorageWrite,
+            });
+        }
+    }
+    Ok(diagnostics.build())
+}
+
+/// Returns the locations of the dead storage write calls in the lowering of a function, after its
+/// panics are lowered.
+fn find_dead_storage_writes(
+    db: &dyn LoweringGroup,
+    function: ConcreteFunctionWithBodyId,
+    lowered: &FlatLowered,
+) -> Maybe<Vec<StatementLocation>> {
+    if lowered.blocks.is_empty() {
+        return Ok(vec![]);
+    }
+    let panic_info = PanicSignatureInfo::new(db, &function.signature(db)?);
+    let panic_vars = lowered
+        .blocks
+        .iter()
+        .flat_map(|(_, block)| &block.statements)
+        .filter_map(|stmt| match stmt {
+            Statement::EnumConstruct(stmt) if stmt.variant == panic_info.err_variant => {
+                Some(stmt.output)
+            }
+            _ => None,
+        })
+        .collect();
+    let semantic_db = db.upcast();
+    let ctx = DeadStorageWritesContext {
+        db,
+        lowered,
+        panic_vars,
+        system_ty: get_ty_by_name(
+            semantic_db,
+            core_submodule(semantic_db, "starknet"),
+            "System".into(),
+            vec![],
+        ),
+        storage_access_cache: Default::default(),
+        dead_writes: vec![],
+    };
+    let mut analysis = BackAnalysis { lowered, block_info: Default::default(), analyzer: ctx };
+    analysis.get_root_info();
+    Ok(analysis.analyzer.dead_writes)
+}
+
+/// Returns whether a function is a storage write, which can be skipped when overwritten: a
+/// function of the corelib or of code generated by a plugin, marked with `#[storage_write]`,
+/// returning the unit type and only accessing the storage through fixed size `Store` impls of the
+/// corelib.
+fn is_storage_write(db: &dyn LoweringGroup, function: FunctionId) -> bool {
+    let Ok(Some(body)) = function.body(db) else {
+        return false;
+    };
+    let ConcreteFunctionWithBodyLongId::Semantic(semantic_function) = body.get(db) else {
+        return false;
+    };
+    let semantic_db = db.upcast();
+    let function_with_body_id = semantic_function.function_with_body_id(semantic_db);
+    is_trusted_storage_write_source(db, function_with_body_id)
+        && function_with_body_id.has_attr(semantic_db, STORAGE_WRITE_ATTR) == Ok(true)
+        && function.signature(db).map(|signature| signature.return_type)
+            == Ok(unit_ty(semantic_db))
+        && writes_fixed_size_corelib_stores(db, body)
+}
+
+/// Returns whether a function only accesses the storage by calling the `Store` impls of the
+/// corelib for types of a fixed size, and calls at least one of them. Otherwise, a following call
+/// to the function may not write to all the storage addresses the skipped call writes to - e.g.
+/// when writing an `Option`, or through a user `Store` impl.
+fn writes_fixed_size_corelib_stores(
+    db: &dyn LoweringGroup,
+    function: ConcreteFunctionWithBodyId,
+) -> bool {
+    let Some(store_trait) = core_store_trait(db) else {
+        return false;
+    };
+    let Ok(lowered) = db.priv_concrete_function_with_body_lowered_flat(function) else {
+        return false;
+    };
+    let semantic_db = db.upcast();
+    let system_ty = get_ty_by_name(
+        semantic_db,
+        core_submodule(semantic_db, "starknet"),
+        "System".into(),
+        vec![],
+    );
+    let may_access_storage = |function: FunctionId| {
+        db.function_implicits(function).map_or(true, |implicits| implicits.contains(&system_ty))
+    };
+    let mut has_store_calls = false;
+    for (_, block) in lowered.blocks.iter() {
+        if let FlatBlockEnd::Match { info: MatchInfo::Extern(extern_info) } = &block.end {
+            if may_access_storage(extern_info.function) {
+                return false;
+            }
+        }
+        for stmt in &block.statements {
+            let Statement::Call(call) = stmt else { continue };
+            match store_impl(db, store_trait, call.function) {
+                Some(impl_id) if is_fixed_size_corelib_store(db, store_trait, impl_id) => {
+                    has_store_calls = true;
+                }
+                Some(_) => return false,
+                None if may_access_storage(call.function) => return false,
+                None => {}
+            }
+        }
+    }
+    has_store_calls
+}
+
+/// Returns the `starknet::Store` trait of the corelib.
+fn core_store_trait(db: &dyn LoweringGroup) -> Option<TraitId> {
+    let semantic_db = db.upcast();
+    let storage_access =
+        get_submodule(semantic_db, core_submodule(semantic_db, "starknet"), "storage_access")?;
+    match db.module_item_by_name(storage_access, "Store".into()) {
+        Ok(Some(ModuleItemId::Trait(trait_id))) => Some(trait_id),
+        _ => None,
+    }
+}
+
+/// Returns the impl of the `Store` trait a function is called through, if it is a function of the
+/// trait.
+fn store_impl(
+    db: &dyn LoweringGroup,
+    store_trait: TraitId,
+    function: FunctionId,
+) -> Option<ImplId> {
+    let FunctionLongId::Semantic(function) = db.lookup_intern_lowering_function(function) else {
+        return None;
+    };
+    let GenericFunctionId::Impl(ImplGenericFunctionId { impl_id, function }) =
+        function.get_concrete(db.upcast()).generic_function
+    else {
+        return None;
+    };
+    (function.trait_id(db.upcast()) == store_trait).then_some(impl_id)
+}
+
+/// Returns whether a `Store` impl is an impl of the corelib which always writes the same number of
+/// storage slots: the impl for `felt252`, the impls for tuples of such types, and the impl for the
+/// types packed into such a type.
+fn is_fixed_size_corelib_store(
+    db: &dyn LoweringGroup,
+    store_trait: TraitId,
+    impl_id: ImplId,
+) -> bool {
+    let ImplId::Concrete(concrete_impl) = impl_id else {
+        return false;
+    };
+    let semantic_db = db.upcast();
+    let impl_def_id = concrete_impl.impl_def_id(semantic_db);
+    if impl_def_id.parent_module(db.upcast()) != store_trait.parent_module(db.upcast())
+        || !matches!(
+            impl_def_id.name(db.upcast()).as_str(),
+            "StoreFelt252"
+                | "StoreUsingPacking"
+                | "TupleSize0Store"
+                | "TupleSize1Store"
+                | "TupleSize2Store"
+                | "TupleSize3Store"
+                | "TupleSize4Store"
+        )
+    {
+        return false;
+    }
+    // The `Store` impls of the members must be of a fixed size as well. The other impl arguments,
+    // such as the `StorePacking` impls, do not affect the written slots.
+    db.lookup_intern_concrete_impl(concrete_impl).generic_args.into_iter().all(|arg| match arg {
+        GenericArgumentId::Impl(impl_id) => match impl_id.concrete_trait(semantic_db) {
+            Ok(concrete_trait) if concrete_trait.trait_id(semantic_db) == store_trait => {
+                is_fixed_size_corelib_store(db, store_trait, impl_id)
+            }
+            Ok(_) => true,
+            Err(_) => false,
+        },
+        _ => true,
+    })
+}
+
+/// Returns whether `#[storage_write]` is honored on the given function - it is only honored in the
+/// corelib and in code generated by plugins (e.g. the storage accessors of contracts), as removing
+/// calls to an arbitrary user function may change the behavior of the program.
+fn is_trusted_storage_write_source(
+    db: &dyn LoweringGroup,
+    function: cairo_lang_defs::ids::FunctionWithBodyId,
+) -> bool {
+    let module_file_id = function.module_file_id(db.upcast());
+    if module_file_id.0.owning_crate(db.upcast()) == core_crate(db.upcast()) {
+        return true;
+    }
+    let Ok(file_id) = module_file_id.file_id(db.upcast()) else {
+        return false;
+    };
+    matches!(
+        db.lookup_intern_file(file_id),
+        FileLongId::Virtual(VirtualFile { parent: Some(_), .. })
+    )
+}
+
+/// The storage writes that are dead at a point of the flow: the storage write functions that are
+/// called on every path from the point before any storage access. `None` if every path from the
+/// point panics, which kills all the storage writes.
+type DeadStorageWritesInfo = Option<OrderedHashSet<FunctionId>>;
+
+struct DeadStorageWritesContext<'a> {
+    db: &'a dyn LoweringGroup,
+    lowered: &'a FlatLowered,
+    /// The variables holding the `Err` variant of the panic result of the function.
+    panic_vars: UnorderedHashSet<VariableId>,
+    /// The `System` implicit type, required by any function that may access the storage.
+    system_ty: TypeId,
+    /// Whether each called function is a storage write, or may access the storage otherwise.
+    storage_access_cache: OrderedHashMap<FunctionId, StorageAccess>,
+    dead_writes: Vec<StatementLocation>,
+}
+
+/// The way a function may access the storage.
+#[derive(Clone, Copy, PartialEq)]
+enum StorageAccess {
+    None,
+    Write,
+    Any,
+}
+
+impl DeadStorageWritesContext<'_> {
+    fn storage_access(&mut self, function: FunctionId) -> StorageAccess {
+        let db = self.db;
+        let system_ty = self.system_ty;
+        *self.storage_access_cache.entry(function).or_insert_with(|| {
+            if is_storage_write(db, function) {
+                StorageAccess::Write
+            } else if db
+                .function_implicits(function)
+                .map_or(true, |implicits| implicits.contains(&system_ty))
+            {
+                StorageAccess::Any
+            } else {
+                StorageAccess::None
+            }
+        })
+    }
+}
+
+impl<'a> Analyzer<'a> for DeadStorageWritesContext<'a> {
+    type Info = DeadStorageWritesInfo;
+
+    fn visit_stmt(
+        &mut self,
+        info: &mut Self::Info,
+        statement_location: StatementLocation,
+        stmt: &Statement,
+    ) {
+        let Statement::Call(call) = stmt else { return };
+        match self.storage_access(call.function) {
+            StorageAccess::None => {}
+            StorageAccess::Write => {
+                // The consumed arguments are dropped when the call is skipped.
+                let droppable_inputs = || {
+                    let Ok(signature) = call.function.signature(self.db) else { return false };
+                    let (_, consumed_inputs) = split_ref_inputs(&signature, &call.inputs);
+                    consumed_inputs
+                        .iter()
+                        .all(|input| self.lowered.variables[input.var_id].droppable.is_ok())
+                };
+                let is_dead = info.as_ref().is_none_or(|writes| writes.contains(&call.function));
+                if is_dead && droppable_inputs() {
+                    self.dead_writes.push(statement_location);
+                }
+                if let Some(writes) = info {
+                    writes.insert(call.function);
+                }
+            }
+            StorageAccess::Any => *info = Some(Default::default()),
+        }
+    }
+
+    fn visit_goto(
+        &mut self,
+        _info: &mut Self::Info,
+        _statement_location: StatementLocation,
+        _target_block_id: BlockId,
+        _remapping: &VarRemapping,
+    ) {
+    }
+
+    fn merge_match(
+        &mut self,
+        _statement_location: StatementLocation,
+        match_info: &'a MatchInfo,
+        infos: &[Self::Info],
+    ) -> Self::Info {
+        if let MatchInfo::Extern(extern_info) = match_info {
+            if self.storage_access(extern_info.function) != StorageAccess::None {
+                return Some(Default::default());
+            }
+        }
+        infos.iter().cloned().reduce(|merged, info| match (merged, info) {
+            (None, info) | (info, None) => info,
+            (Some(merged), Some(info)) => {
+                Some(merged.into_iter().filter(|function| info.contains(function)).collect())
+            }
+        })?
+    }
+
+    fn info_from_return(
+        &mut self,
+        _statement_location: StatementLocation,
+        vars: &[VarUsage],
+    ) -> Self::Info {
+        match vars.last() {
+            Some(var) if self.panic_vars.contains(&var.var_id) => None,
+            _ => Some(Default::default()),
+        }
+    }
+
+    fn info_from_panic(
+        &mut self,
+        _statement_location: StatementLocation,
+        _var: &VarUsage,
+    ) -> Self::Info {
+        None
+    }
+}
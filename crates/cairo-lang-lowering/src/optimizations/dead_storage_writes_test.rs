@@ -0,0 +1,109 @@
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+
+use cairo_lang_debug::DebugWithDb;
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::plugin::{MacroPlugin, PluginGeneratedFile, PluginResult};
+use cairo_lang_semantic::test_utils::setup_test_function;
+use cairo_lang_syntax::attribute::consts::STORAGE_WRITE_ATTR;
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::helpers::QueryAttrs;
+use cairo_lang_syntax::node::{ast, TypedSyntaxNode};
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use once_cell::sync::Lazy;
+
+use super::eliminate_dead_storage_writes;
+use crate::db::LoweringGroup;
+use crate::fmt::LoweredFormatter;
+use crate::ids::ConcreteFunctionWithBodyId;
+use crate::test_utils::LoweringDatabaseForTesting;
+
+/// The attribute marking the free functions [StorageWritePlugin] generates as storage writes.
+const GENERATED_STORAGE_WRITE_ATTR: &str = "generated_storage_write";
+
+/// A plugin moving the free functions marked with `#[generated_storage_write]` to a generated file,
+/// where they are marked with `#[storage_write]`, which is only honored in generated code.
+#[derive(Debug)]
+struct StorageWritePlugin;
+
+impl MacroPlugin for StorageWritePlugin {
+    fn generate_code(&self, db: &dyn SyntaxGroup, item_ast: ast::Item) -> PluginResult {
+        match item_ast {
+            ast::Item::FreeFunction(func) if func.has_attr(db, GENERATED_STORAGE_WRITE_ATTR) => {
+                PluginResult {
+                    code: Some(PluginGeneratedFile {
+                        name: "storage_write".into(),
+                        content: func.as_syntax_node().get_text(db).replace(
+                            &format!("#[{GENERATED_STORAGE_WRITE_ATTR}]"),
+                            &format!("#[{STORAGE_WRITE_ATTR}]"),
+                        ),
+                        code_mappings: vec![],
+                        aux_data: None,
+                    }),
+                    diagnostics: vec![],
+                    remove_original_item: true,
+                }
+            }
+            _ => PluginResult::default(),
+        }
+    }
+
+    fn declared_attributes(&self) -> Vec<String> {
+        vec![GENERATED_STORAGE_WRITE_ATTR.to_string()]
+    }
+}
+
+/// A database with [StorageWritePlugin] in addition to the default plugins.
+static SHARED_DB: Lazy<Mutex<LoweringDatabaseForTesting>> = Lazy::new(|| {
+    let mut db = LoweringDatabaseForTesting::new();
+    let mut plugins = db.macro_plugins();
+    plugins.push(Arc::new(StorageWritePlugin));
+    db.set_macro_plugins(plugins);
+    Mutex::new(db)
+});
+
+cairo_lang_test_utils::test_file_test!(
+    dead_storage_writes,
+    "src/optimizations/test_data",
+    {
+        dead_storage_writes :"dead_storage_writes",
+    },
+    test_dead_storage_writes
+);
+
+fn test_dead_storage_writes(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let db = &mut SHARED_DB.lock().unwrap().snapshot();
+    let (test_function, semantic_diagnostics) = setup_test_function(
+        db,
+        inputs["function"].as_str(),
+        inputs["function_name"].as_str(),
+        inputs["module_code"].as_str(),
+    )
+    .split();
+    let function_id =
+        ConcreteFunctionWithBodyId::from_semantic(db, test_function.concrete_function_id);
+
+    let before =
+        db.concrete_function_with_body_postpanic_lowered(function_id).unwrap().deref().clone();
+    let lowering_diagnostics = db.module_lowering_diagnostics(test_function.module_id).unwrap();
+
+    let mut after = before.clone();
+    eliminate_dead_storage_writes(db, function_id, &mut after).unwrap();
+
+    TestRunnerResult::success(OrderedHashMap::from([
+        ("semantic_diagnostics".into(), semantic_diagnostics),
+        (
+            "before".into(),
+            format!("{:?}", before.debug(&LoweredFormatter::new(db, &before.variables))),
+        ),
+        (
+            "after".into(),
+            format!("{:?}", after.debug(&LoweredFormatter::new(db, &after.variables))),
+        ),
+        ("lowering_diagnostics".into(), lowering_diagnostics.format(db)),
+    ]))
+}
@@ -1,4 +1,85 @@
+use std::collections::HashSet;
+
+use cairo_lang_filesystem::flag::{Flag, OptimizationLevel};
+use cairo_lang_filesystem::ids::FlagId;
+use cairo_lang_utils::extract_matches;
+
+use crate::borrow_check::analysis::StatementLocation;
+use crate::db::LoweringGroup;
+use crate::{BlockId, FlatBlock, FlatBlockEnd, FlatLowered};
+
 pub mod branch_inversion;
+pub mod const_folding;
+pub mod cse;
+pub mod dead_storage_writes;
 pub mod match_optimizer;
+pub mod peephole;
 pub mod remappings;
 pub mod reorder_statements;
+
+/// Returns the optimization level of the compilation, as set by the `optimization_level` flag.
+pub fn optimization_level(db: &dyn LoweringGroup) -> OptimizationLevel {
+    match db.get_flag(FlagId::new(db.upcast(), "optimization_level")) {
+        Some(flag) => extract_matches!(*flag, Flag::OptimizationLevel),
+        None => OptimizationLevel::default(),
+    }
+}
+
+/// Returns whether the local optimization passes should be applied, as set by the
+/// `local_optimizations` flag, or by the optimization level if the flag is not set.
+pub fn local_optimizations_enabled(db: &dyn LoweringGroup) -> bool {
+    match db.get_flag(FlagId::new(db.upcast(), "local_optimizations")) {
+        Some(flag) => extract_matches!(*flag, Flag::LocalOptimizations),
+        None => optimization_level(db) >= OptimizationLevel::O3,
+    }
+}
+
+/// Visits the reachable blocks of a function, starting from the root. A block with a single
+/// predecessor is visited with the state its predecessor was left with, and any other block with
+/// a default state, so the state of a block only holds facts about the blocks dominating it.
+pub fn visit_blocks_with_inherited_state<State: Clone + Default>(
+    lowered: &FlatLowered,
+    mut visit: impl FnMut(BlockId, &FlatBlock, &mut State),
+) {
+    if lowered.blocks.is_empty() {
+        return;
+    }
+    let successors = |block: &FlatBlock| match &block.end {
+        FlatBlockEnd::Goto(target_block_id, _) => vec![*target_block_id],
+        FlatBlockEnd::Match { info } => info.arms().iter().map(|arm| arm.block_id).collect(),
+        FlatBlockEnd::Return(_) | FlatBlockEnd::Panic(_) | FlatBlockEnd::NotSet => vec![],
+    };
+    let mut predecessor_counts = vec![0; lowered.blocks.len()];
+    for (_, block) in lowered.blocks.iter() {
+        for successor in successors(block) {
+            predecessor_counts[successor.0] += 1;
+        }
+    }
+    let mut visited = vec![false; lowered.blocks.len()];
+    let mut stack = vec![(BlockId::root(), State::default())];
+    while let Some((block_id, mut state)) = stack.pop() {
+        if visited[block_id.0] {
+            continue;
+        }
+        visited[block_id.0] = true;
+        let block = &lowered.blocks[block_id];
+        visit(block_id, block, &mut state);
+        for successor in successors(block) {
+            let successor_state =
+                if predecessor_counts[successor.0] == 1 { state.clone() } else { State::default() };
+            stack.push((successor, successor_state));
+        }
+    }
+}
+
+/// Removes the statements at the given locations.
+pub fn remove_statements(lowered: &mut FlatLowered, locations: &HashSet<StatementLocation>) {
+    for (block_id, block) in lowered.blocks.iter_mut().enumerate() {
+        block.statements = std::mem::take(&mut block.statements)
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| !locations.contains(&(BlockId(block_id), *index)))
+            .map(|(_, statement)| statement)
+            .collect();
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+#[path = "peephole_test.rs"]
+mod test;
+
+use std::collections::{HashMap, HashSet};
+
+use num_traits::{One, Zero};
+
+use super::const_folding::{literal_values, Felt252Operator, Felt252Operators};
+use super::{remove_statements, visit_blocks_with_inherited_state};
+use crate::borrow_check::analysis::StatementLocation;
+use crate::db::LoweringGroup;
+use crate::utils::{Rebuilder, RebuilderEx, VarRenamer};
+use crate::{FlatBlockEnd, FlatLowered, Statement, VariableId};
+
+/// Returns the number of times each variable is used.
+fn variable_usage_counts(lowered: &FlatLowered) -> HashMap<VariableId, usize> {
+    let mut counts = HashMap::<VariableId, usize>::new();
+    for (_, block) in lowered.blocks.iter() {
+        let statement_inputs = block.statements.iter().flat_map(|statement| statement.inputs());
+        let end_inputs = match &block.end {
+            FlatBlockEnd::Return(returns) => returns.clone(),
+            FlatBlockEnd::Panic(data) => vec![*data],
+            FlatBlockEnd::Goto(_, remapping) => remapping.values().copied().collect(),
+            FlatBlockEnd::Match { info } => info.inputs(),
+            FlatBlockEnd::NotSet => vec![],
+        };
+        for var_usage in statement_inputs.chain(end_inputs) {
+            *counts.entry(var_usage.var_id).or_default() += 1;
+        }
+    }
+    counts
+}
+
+/// Replaces short sequences of statements with simpler ones:
+/// * A destructure of a struct constructed in a dominating block is replaced by the members the
+///   struct was constructed from. The construction is removed too if the destructure was the only
+///   use of the struct.
+/// * A desnap of a snapshot taken in a dominating block is replaced by the original value.
+/// * Adding or subtracting 0 from a felt252, or multiplying it by 1, is replaced by the felt252.
+pub fn peephole_optimizations(db: &dyn LoweringGroup, lowered: &mut FlatLowered) {
+    if lowered.blocks.is_empty() {
+        return;
+    }
+    let operators = Felt252Operators::new(db);
+    let literals = literal_values(lowered);
+    let usage_counts = variable_usage_counts(lowered);
+    let mut renamer = VarRenamer::default();
+    let mut removed_statements = HashSet::<StatementLocation>::new();
+    // The state is the locations of the statements defining the variables available in the block.
+    visit_blocks_with_inherited_state(
+        lowered,
+        |block_id, block, definitions: &mut HashMap<VariableId, StatementLocation>| {
+            for (index, statement) in block.statements.iter().enumerate() {
+                let location = (block_id, index);
+                for output in statement.outputs() {
+                    definitions.insert(output, location);
+                }
+                let definition = |var: VariableId| {
+                    let (block_id, index) = definitions.get(&var)?;
+                    Some((*block_id, *index, &lowered.blocks[*block_id].statements[*index]))
+                };
+                match statement {
+                    Statement::StructDestructure(stmt) => {
+                        let input = renamer.map_var_id(stmt.input.var_id);
+                        let Some((
+                            construct_block_id,
+                            construct_index,
+                            Statement::StructConstruct(construct),
+                        )) = definition(input)
+                        else {
+                            continue;
+                        };
+                        let single_use =
+                            input == stmt.input.var_id && usage_counts.get(&input) == Some(&1);
+                        let members_duplicatable = construct.inputs.iter().all(|var_usage| {
+                            lowered.variables[var_usage.var_id].duplicatable.is_ok()
+                        });
+                        if !single_use && !members_duplicatable {
+                            continue;
+                        }
+                        if single_use {
+                            removed_statements.insert((construct_block_id, construct_index));
+                        }
+                        removed_statements.insert(location);
+                        for (output, member) in stmt.outputs.iter().zip(&construct.inputs) {
+                            renamer.renamed_vars.insert(*output, member.var_id);
+                        }
+                    }
+                    Statement::Desnap(stmt) => {
+                        let input = renamer.map_var_id(stmt.input.var_id);
+                        let Some((_, _, Statement::Snapshot(snapshot))) = definition(input) else {
+                            continue;
+                        };
+                        if snapshot.output_snapshot != input
+                            || lowered.variables[stmt.output].duplicatable.is_err()
+                        {
+                            continue;
+                        }
+                        removed_statements.insert(location);
+                        renamer.renamed_vars.insert(stmt.output, snapshot.output_original);
+                    }
+                    Statement::Call(stmt) => {
+                        let Some(operator) = operators.operator(stmt.function) else {
+                            continue;
+                        };
+                        let [lhs, rhs] = &stmt.inputs[..] else {
+                            continue;
+                        };
+                        let lhs = renamer.map_var_id(lhs.var_id);
+                        let rhs = renamer.map_var_id(rhs.var_id);
+                        let is_zero = |var| literals.get(&var).is_some_and(|value| value.is_zero());
+                        let is_one = |var| literals.get(&var).is_some_and(|value| value.is_one());
+                        let result = match operator {
+                            Felt252Operator::Add | Felt252Operator::Sub if is_zero(rhs) => lhs,
+                            Felt252Operator::Add if is_zero(lhs) => rhs,
+                            Felt252Operator::Mul if is_one(rhs) => lhs,
+                            Felt252Operator::Mul if is_one(lhs) => rhs,
+                            _ => continue,
+                        };
+                        removed_statements.insert(location);
+                        renamer.renamed_vars.insert(stmt.outputs[0], result);
+                    }
+                    _ => {}
+                }
+            }
+        },
+    );
+    remove_statements(lowered, &removed_statements);
+    for block in lowered.blocks.iter_mut() {
+        *block = renamer.rebuild_block(block);
+    }
+}
@@ -9,6 +9,7 @@
 
 use std::collections::{HashMap, HashSet};
 
+use cairo_lang_utils::ensure_sufficient_stack;
 use itertools::Itertools;
 
 use crate::utils::{Rebuilder, RebuilderEx};
@@ -44,9 +45,10 @@ struct Context {
 }
 impl Context {
     fn set_used(&mut self, var: VariableId) {
-        if self.variable_used.insert(var) {
-            for src in self.dest_to_srcs.get(&var).cloned().unwrap_or_default() {
-                self.set_used(src);
+        let mut stack = vec![var];
+        while let Some(var) = stack.pop() {
+            if self.variable_used.insert(var) {
+                stack.extend(self.dest_to_srcs.get(&var).into_iter().flatten());
             }
         }
     }
@@ -59,7 +61,7 @@ impl Rebuilder for Context {
         } else {
             let srcs = self.dest_to_srcs.get(&var).cloned().unwrap_or_default();
             let src_representatives: HashSet<_> =
-                srcs.iter().map(|src| self.map_var_id(*src)).collect();
+                srcs.iter().map(|src| ensure_sufficient_stack(|| self.map_var_id(*src))).collect();
             let src_representatives = src_representatives.into_iter().collect_vec();
             let new_var =
                 if let [single_var] = &src_representatives[..] { *single_var } else { var };
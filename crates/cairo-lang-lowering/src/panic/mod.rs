@@ -98,11 +98,11 @@ pub struct PanicSignatureInfo {
     /// The types of all the variables returned on OK: Reference variables and the original result.
     ok_ret_tys: Vec<TypeId>,
     /// The type of the Ok() variant.
-    ok_ty: TypeId,
+    pub ok_ty: TypeId,
     /// The Ok() variant.
-    ok_variant: ConcreteVariant,
+    pub ok_variant: ConcreteVariant,
     /// The Err() variant.
-    err_variant: ConcreteVariant,
+    pub err_variant: ConcreteVariant,
     /// The PanicResult concrete type - the new return type of the function.
     pub panic_ty: TypeId,
 }
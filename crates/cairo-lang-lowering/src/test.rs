@@ -1,8 +1,12 @@
 use std::ops::Deref;
+use std::sync::Arc;
 
 use cairo_lang_debug::DebugWithDb;
 use cairo_lang_defs::ids::LanguageElementId;
 use cairo_lang_diagnostics::DiagnosticsBuilder;
+use cairo_lang_filesystem::db::FilesGroupEx;
+use cairo_lang_filesystem::flag::Flag;
+use cairo_lang_filesystem::ids::FlagId;
 use cairo_lang_semantic::test_utils::{setup_test_expr, setup_test_function};
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
@@ -39,6 +43,7 @@ cairo_lang_test_utils::test_file_test!(
         generics :"generics",
         extern_ :"extern",
         arm_pattern_destructure :"arm_pattern_destructure",
+        binding_pattern :"binding_pattern",
         if_ :"if",
         implicits :"implicits",
         logical_operator :"logical_operator",
@@ -65,6 +70,15 @@ cairo_lang_test_utils::test_file_test!(
     test_function_lowering_phases
 );
 
+cairo_lang_test_utils::test_file_test!(
+    optimization_levels,
+    "src/test_data",
+    {
+        optimization_levels :"optimization_levels",
+    },
+    test_function_lowering_with_optimization_level
+);
+
 fn test_function_lowering(
     inputs: &OrderedHashMap<String, String>,
     _args: &OrderedHashMap<String, String>,
@@ -98,6 +112,38 @@ fn test_function_lowering(
     ]))
 }
 
+/// Tests the lowering of a function with the optimization level given by the
+/// `optimization_level` input.
+fn test_function_lowering_with_optimization_level(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let db = &mut LoweringDatabaseForTesting::new();
+    let optimization_level = inputs["optimization_level"].parse().unwrap();
+    db.set_flag(
+        FlagId::new(db, "optimization_level"),
+        Some(Arc::new(Flag::OptimizationLevel(optimization_level))),
+    );
+    let (test_function, semantic_diagnostics) = setup_test_function(
+        db,
+        inputs["function"].as_str(),
+        inputs["function_name"].as_str(),
+        inputs["module_code"].as_str(),
+    )
+    .split();
+    let function_id =
+        ConcreteFunctionWithBodyId::from_semantic(db, test_function.concrete_function_id);
+
+    let lowered = db.concrete_function_with_body_lowered(function_id).unwrap();
+    let diagnostics = db.module_lowering_diagnostics(test_function.module_id).unwrap_or_default();
+
+    TestRunnerResult::success(OrderedHashMap::from([
+        ("semantic_diagnostics".into(), semantic_diagnostics),
+        ("lowering_diagnostics".into(), diagnostics.format(db)),
+        ("lowering_flat".into(), formatted_lowered(db, &lowered)),
+    ]))
+}
+
 /// Tests all the lowering phases of a function (tracking logic in
 /// `concrete_function_with_body_lowered`).
 /// Can be used to debug cases where the transition of a specific lowering phase fails.
@@ -207,3 +253,26 @@ note: this error originates in auto-generated destructor logic.
 "}
     );
 }
+
+#[test]
+fn test_long_flat_expr() {
+    let db = &mut LoweringDatabaseForTesting::default();
+    let length = 1000;
+    for function in [
+        format!("fn foo(x: felt252) -> felt252 {{ {} }}", vec!["x"; length].join(" + ")),
+        format!(
+            "fn foo(x: felt252) -> felt252 {{ if x == 0 {{ 0 }}{} else {{ 1 }} }}",
+            " else if x == 1 { 1 }".repeat(length)
+        ),
+    ] {
+        let (test_function, semantic_diagnostics) =
+            setup_test_function(db, &function, "foo", "").split();
+        assert_eq!(semantic_diagnostics, "");
+        let function_id =
+            ConcreteFunctionWithBodyId::from_semantic(db, test_function.concrete_function_id);
+        assert!(db.concrete_function_with_body_lowered(function_id).is_ok());
+        let diagnostics =
+            db.module_lowering_diagnostics(test_function.module_id).unwrap_or_default();
+        assert_eq!(diagnostics.format(db), "");
+    }
+}
@@ -37,17 +37,23 @@ impl LoweringDatabaseForTesting {
         LoweringDatabaseForTesting { storage: self.storage.snapshot() }
     }
 }
-pub static SHARED_DB: Lazy<Mutex<LoweringDatabaseForTesting>> = Lazy::new(|| {
-    let mut res = LoweringDatabaseForTesting { storage: Default::default() };
-    init_files_group(&mut res);
-    let suite = get_default_plugin_suite();
-    res.set_macro_plugins(suite.plugins);
-    res.set_inline_macro_plugins(suite.inline_macro_plugins.into());
-    res.set_analyzer_plugins(suite.analyzer_plugins);
-    let corelib_path = detect_corelib().expect("Corelib not found in default location.");
-    init_dev_corelib(&mut res, corelib_path);
-    Mutex::new(res)
-});
+impl LoweringDatabaseForTesting {
+    /// Creates a new database, for tests that set its inputs. Other tests should use the default
+    /// database, which snapshots [SHARED_DB].
+    pub fn new() -> Self {
+        let mut res = LoweringDatabaseForTesting { storage: Default::default() };
+        init_files_group(&mut res);
+        let suite = get_default_plugin_suite();
+        res.set_macro_plugins(suite.plugins);
+        res.set_inline_macro_plugins(suite.inline_macro_plugins.into());
+        res.set_analyzer_plugins(suite.analyzer_plugins);
+        let corelib_path = detect_corelib().expect("Corelib not found in default location.");
+        init_dev_corelib(&mut res, corelib_path);
+        res
+    }
+}
+pub static SHARED_DB: Lazy<Mutex<LoweringDatabaseForTesting>> =
+    Lazy::new(|| Mutex::new(LoweringDatabaseForTesting::new()));
 impl Default for LoweringDatabaseForTesting {
     fn default() -> Self {
         SHARED_DB.lock().unwrap().snapshot()
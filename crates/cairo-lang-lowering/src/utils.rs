@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 
 use crate::{
@@ -146,3 +148,24 @@ pub trait RebuilderEx: Rebuilder {
 }
 
 impl<T: Rebuilder> RebuilderEx for T {}
+
+/// A rebuilder renaming variables, following chains of renames.
+#[derive(Default)]
+pub struct VarRenamer {
+    pub renamed_vars: HashMap<VariableId, VariableId>,
+}
+
+impl Rebuilder for VarRenamer {
+    fn map_var_id(&mut self, var: VariableId) -> VariableId {
+        let Some(new_var) = self.renamed_vars.get(&var).copied() else {
+            return var;
+        };
+        let new_var = self.map_var_id(new_var);
+        self.renamed_vars.insert(var, new_var);
+        new_var
+    }
+
+    fn map_block_id(&mut self, block: BlockId) -> BlockId {
+        block
+    }
+}
@@ -17,10 +17,12 @@ pub enum ParserDiagnosticKind {
     SkippedElement { element_name: SmolStr },
     MissingToken(SyntaxKind),
     MissingExpression,
+    ExprNestedTooDeeply,
     MissingPathSegment,
     MissingTypeClause,
     MissingTypeExpression,
     MissingWrappedArgList,
+    MissingIn,
     ItemInlineMacroWithoutBang { identifier: SmolStr, bracket_type: SyntaxKind },
     ReservedIdentifier { identifier: SmolStr },
     UnderscoreNotAllowedAsIdentifier,
@@ -51,6 +53,9 @@ impl DiagnosticEntry for ParserDiagnostic {
             ParserDiagnosticKind::MissingExpression => {
                 "Missing tokens. Expected an expression.".to_string()
             }
+            ParserDiagnosticKind::ExprNestedTooDeeply => {
+                "Skipped tokens. Expression is nested too deeply.".to_string()
+            }
             ParserDiagnosticKind::MissingPathSegment => {
                 "Missing tokens. Expected a path segment.".to_string()
             }
@@ -64,6 +69,7 @@ impl DiagnosticEntry for ParserDiagnostic {
                                                             list wrapped in either parentheses, \
                                                             brackets, or braces."
                 .to_string(),
+            ParserDiagnosticKind::MissingIn => "Missing tokens. Expected 'in'.".to_string(),
             ParserDiagnosticKind::ItemInlineMacroWithoutBang { identifier, bracket_type } => {
                 let (left, right) = match bracket_type {
                     SyntaxKind::TerminalLParen => ("(", ")"),
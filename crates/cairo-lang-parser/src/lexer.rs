@@ -12,6 +12,7 @@ use cairo_lang_syntax::node::kind::SyntaxKind;
 use cairo_lang_syntax::node::Token;
 use smol_str::SmolStr;
 
+#[derive(Clone)]
 pub struct Lexer<'a> {
     db: &'a dyn SyntaxGroup,
     text: &'a str,
@@ -199,6 +200,7 @@ impl<'a> Lexer<'a> {
             "match" => TokenKind::Match,
             "if" => TokenKind::If,
             "loop" => TokenKind::Loop,
+            "while" => TokenKind::While,
             "continue" => TokenKind::Continue,
             "break" => TokenKind::Break,
             "else" => TokenKind::Else,
@@ -361,6 +363,7 @@ enum TokenKind {
     Match,
     If,
     Loop,
+    While,
     Continue,
     Break,
     Else,
@@ -447,6 +450,7 @@ fn token_kind_to_terminal_syntax_kind(kind: TokenKind) -> SyntaxKind {
         TokenKind::Match => SyntaxKind::TerminalMatch,
         TokenKind::If => SyntaxKind::TerminalIf,
         TokenKind::Loop => SyntaxKind::TerminalLoop,
+        TokenKind::While => SyntaxKind::TerminalWhile,
         TokenKind::Continue => SyntaxKind::TerminalContinue,
         TokenKind::Break => SyntaxKind::TerminalBreak,
         TokenKind::Else => SyntaxKind::TerminalElse,
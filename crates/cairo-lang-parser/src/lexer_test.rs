@@ -58,6 +58,7 @@ fn terminal_kind_to_text(kind: SyntaxKind) -> Vec<&'static str> {
         SyntaxKind::TerminalMatch => vec!["match"],
         SyntaxKind::TerminalIf => vec!["if"],
         SyntaxKind::TerminalLoop => vec!["loop"],
+        SyntaxKind::TerminalWhile => vec!["while"],
         SyntaxKind::TerminalBreak => vec!["break"],
         SyntaxKind::TerminalElse => vec!["else"],
         SyntaxKind::TerminalUse => vec!["use"],
@@ -133,6 +134,7 @@ fn terminal_kinds() -> Vec<SyntaxKind> {
         SyntaxKind::TerminalMatch,
         SyntaxKind::TerminalIf,
         SyntaxKind::TerminalLoop,
+        SyntaxKind::TerminalWhile,
         SyntaxKind::TerminalBreak,
         SyntaxKind::TerminalElse,
         SyntaxKind::TerminalUse,
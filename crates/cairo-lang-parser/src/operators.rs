@@ -28,7 +28,7 @@ pub fn get_post_operator_precedence(kind: SyntaxKind) -> Option<usize> {
         | SyntaxKind::TerminalLE
         | SyntaxKind::TerminalGE => Some(7),
         SyntaxKind::TerminalAndAnd => Some(8),
-        SyntaxKind::TerminalOrOr => Some(9),
+        SyntaxKind::TerminalOrOr | SyntaxKind::TerminalDotDot => Some(9),
         SyntaxKind::TerminalEq
         | SyntaxKind::TerminalPlusEq
         | SyntaxKind::TerminalMinusEq
@@ -8,6 +8,7 @@ use cairo_lang_syntax::node::ast::*;
 use cairo_lang_syntax::node::db::SyntaxGroup;
 use cairo_lang_syntax::node::kind::SyntaxKind;
 use cairo_lang_syntax::node::{SyntaxNode, Token, TypedSyntaxNode};
+use cairo_lang_utils::ensure_sufficient_stack;
 use syntax::node::green::{GreenNode, GreenNodeDetails};
 use syntax::node::ids::GreenId;
 
@@ -39,6 +40,9 @@ pub struct Parser<'a> {
     diagnostics: &'a mut DiagnosticsBuilder<ParserDiagnostic>,
     /// An accumulating vector of pending skipped tokens diagnostics.
     pending_skipped_token_diagnostics: Vec<PendingParserDiagnostic>,
+    /// The nesting depth of the expression currently being parsed. See
+    /// [MAX_EXPR_NESTING_DEPTH].
+    expr_nesting_depth: usize,
 }
 
 /// The possible results of a try_parse_* function failing to parse.
@@ -76,6 +80,10 @@ pub type TryParseResult<GreenElement> = Result<GreenElement, TryParseFailure>;
 // Should only be called after checking the current token.
 
 const MAX_PRECEDENCE: usize = 1000;
+/// The maximal nesting depth of expressions, in parentheses, blocks, calls, etc. (but not in chains
+/// of binary operators or of `else if`s). Deeper expressions are skipped and reported, as they are
+/// pathological inputs that are too costly to handle in later phases.
+const MAX_EXPR_NESTING_DEPTH: usize = 500;
 const TOP_LEVEL_ITEM_DESCRIPTION: &str = "Const/Module/Use/FreeFunction/ExternFunction/ExternType/\
                                           Trait/Impl/Struct/Enum/TypeAlias/InlineMacro";
 const TRAIT_ITEM_DESCRIPTION: &str = "trait item";
@@ -109,6 +117,7 @@ impl<'a> Parser<'a> {
             last_trivia_length: Default::default(),
             diagnostics,
             pending_skipped_token_diagnostics: Default::default(),
+            expr_nesting_depth: 0,
         };
         let green = parser.parse_syntax_file();
         SyntaxFile::from_syntax_node(db, SyntaxNode::new_root(db, file_id, green.0))
@@ -134,6 +143,7 @@ impl<'a> Parser<'a> {
             last_trivia_length: Default::default(),
             diagnostics,
             pending_skipped_token_diagnostics: Default::default(),
+            expr_nesting_depth: 0,
         };
         let green = parser.parse_expr();
         if let Err(SkippedError(span)) = parser.skip_until(is_of_kind!()) {
@@ -1010,6 +1020,7 @@ impl<'a> Parser<'a> {
             SyntaxKind::TerminalOrOr => self.take::<TerminalOrOr>().into(),
             SyntaxKind::TerminalOr => self.take::<TerminalOr>().into(),
             SyntaxKind::TerminalXor => self.take::<TerminalXor>().into(),
+            SyntaxKind::TerminalDotDot => self.take::<TerminalDotDot>().into(),
             _ => unreachable!(),
         }
     }
@@ -1036,6 +1047,24 @@ impl<'a> Parser<'a> {
         &mut self,
         parent_precedence: usize,
         lbrace_allowed: LbraceAllowed,
+    ) -> TryParseResult<ExprGreen> {
+        if self.expr_nesting_depth >= MAX_EXPR_NESTING_DEPTH {
+            self.skip_too_deeply_nested_expr();
+            return Ok(Expr::missing(self.db));
+        }
+        let outer_nesting_depth = self.expr_nesting_depth;
+        self.expr_nesting_depth += 1;
+        let expr = self.try_parse_expr_limited_nested(parent_precedence, lbrace_allowed);
+        self.expr_nesting_depth = outer_nesting_depth;
+        expr
+    }
+
+    /// Same as [Self::try_parse_expr_limited], without increasing the nesting depth for the parsed
+    /// expression.
+    fn try_parse_expr_limited_nested(
+        &mut self,
+        parent_precedence: usize,
+        lbrace_allowed: LbraceAllowed,
     ) -> TryParseResult<ExprGreen> {
         let mut expr = self.try_parse_atom_or_unary(lbrace_allowed)?;
 
@@ -1053,7 +1082,13 @@ impl<'a> Parser<'a> {
                 ExprIndexed::new_green(self.db, expr, lbrack, index_expr, rbrack).into()
             } else {
                 let op = self.parse_binary_operator();
-                let rhs = self.parse_expr_limited(precedence, lbrace_allowed);
+                // Chains of binary operators are flat code, so their operands don't count as
+                // nested expressions.
+                let rhs = match self.try_parse_expr_limited_nested(precedence, lbrace_allowed) {
+                    Ok(rhs) => rhs,
+                    Err(_) => self
+                        .create_and_report_missing::<Expr>(ParserDiagnosticKind::MissingExpression),
+                };
                 ExprBinary::new_green(self.db, expr, op, rhs).into()
             };
         }
@@ -1102,6 +1137,11 @@ impl<'a> Parser<'a> {
     fn try_parse_atom(&mut self, lbrace_allowed: LbraceAllowed) -> TryParseResult<ExprGreen> {
         // TODO(yuval): support paths starting with "::".
         match self.peek().kind {
+            SyntaxKind::TerminalIdentifier
+                if lbrace_allowed == LbraceAllowed::Allow && self.is_peek_for_loop() =>
+            {
+                Ok(self.expect_for_expr().into())
+            }
             SyntaxKind::TerminalIdentifier => {
                 // Call parse_path() and not expect_path(), because it's cheap.
                 let path = self.parse_path();
@@ -1136,7 +1176,9 @@ impl<'a> Parser<'a> {
             SyntaxKind::TerminalLoop if lbrace_allowed == LbraceAllowed::Allow => {
                 Ok(self.expect_loop_expr().into())
             }
-
+            SyntaxKind::TerminalWhile if lbrace_allowed == LbraceAllowed::Allow => {
+                Ok(self.expect_while_expr().into())
+            }
             _ => {
                 // TODO(yuval): report to diagnostics.
                 Err(TryParseFailure::SkipToken)
@@ -1542,7 +1584,9 @@ impl<'a> Parser<'a> {
         let else_clause: OptionElseClauseGreen = if self.peek().kind == SyntaxKind::TerminalElse {
             let else_kw = self.take::<TerminalElse>();
             let else_block_or_if = if self.peek().kind == SyntaxKind::TerminalIf {
-                BlockOrIfGreen::from(self.expect_if_expr())
+                // An `else if` chain is flat code, so it doesn't count as nesting, although each
+                // `if` is a child of the previous one.
+                BlockOrIfGreen::from(ensure_sufficient_stack(|| self.expect_if_expr()))
             } else {
                 BlockOrIfGreen::from(self.parse_block())
             };
@@ -1563,6 +1607,79 @@ impl<'a> Parser<'a> {
         ExprLoop::new_green(self.db, loop_kw, body)
     }
 
+    /// Assumes the current token is `While`.
+    /// Expected pattern: `while <condition> <block>`.
+    fn expect_while_expr(&mut self) -> ExprWhileGreen {
+        let while_kw = self.take::<TerminalWhile>();
+        let condition = self.parse_expr_limited(MAX_PRECEDENCE, LbraceAllowed::Forbid);
+        let body = self.parse_block();
+
+        ExprWhile::new_green(self.db, while_kw, condition, body)
+    }
+
+    /// Assumes the current token is `For`.
+    /// Expected pattern: `for <pattern> in <expr> <block>`.
+    fn expect_for_expr(&mut self) -> ExprForGreen {
+        // `for` is not a keyword, so it is parsed as an identifier.
+        let for_kw = self.take::<TerminalIdentifier>();
+        let pattern = self.parse_pattern();
+        // `in` is not a keyword, so it is parsed as an identifier.
+        let in_identifier = if self.peek().kind == SyntaxKind::TerminalIdentifier
+            && self.peek().text == "in"
+        {
+            self.take::<TerminalIdentifier>()
+        } else {
+            self.create_and_report_missing::<TerminalIdentifier>(ParserDiagnosticKind::MissingIn)
+        };
+        let expr = self.parse_expr_limited(MAX_PRECEDENCE, LbraceAllowed::Forbid);
+        let body = self.parse_block();
+
+        ExprFor::new_green(self.db, for_kw, pattern, in_identifier, expr, body)
+    }
+
+    /// Returns whether the next terminal is a `for` starting a for loop.
+    /// `for` is not a keyword, so that items and variables named `for` keep working. It starts a
+    /// for loop only if it is followed by the start of a pattern, which can't follow a path
+    /// expression. A tuple pattern is ambiguous with a call of a function named `for`, so in that
+    /// case it is a for loop only if the parenthesized part is followed by `in`.
+    fn is_peek_for_loop(&self) -> bool {
+        let peek = self.peek();
+        if peek.kind != SyntaxKind::TerminalIdentifier || peek.text != "for" {
+            return false;
+        }
+        let mut lexer = self.lexer.clone();
+        let Some(next) = lexer.next() else {
+            return false;
+        };
+        match next.kind {
+            SyntaxKind::TerminalIdentifier
+            | SyntaxKind::TerminalUnderscore
+            | SyntaxKind::TerminalLiteralNumber
+            | SyntaxKind::TerminalShortString
+            | SyntaxKind::TerminalRef
+            | SyntaxKind::TerminalMut => true,
+            SyntaxKind::TerminalLParen => {
+                let mut depth = 1;
+                for terminal in lexer.by_ref() {
+                    match terminal.kind {
+                        SyntaxKind::TerminalLParen => depth += 1,
+                        SyntaxKind::TerminalRParen => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                lexer.next().is_some_and(|terminal| {
+                    terminal.kind == SyntaxKind::TerminalIdentifier && terminal.text == "in"
+                })
+            }
+            _ => false,
+        }
+    }
+
     /// Returns a GreenId of a node with a MatchArm kind or TryParseFailure if a match arm can't be
     /// parsed.
     pub fn try_parse_match_arm(&mut self) -> TryParseResult<MatchArmGreen> {
@@ -1579,7 +1696,8 @@ impl<'a> Parser<'a> {
         if !modifier_list.is_empty() {
             let modifiers = ModifierList::new_green(self.db, modifier_list);
             let name = self.parse_identifier();
-            return Ok(PatternIdentifier::new_green(self.db, modifiers, name).into());
+            let binding = PatternIdentifier::new_green(self.db, modifiers, name);
+            return Ok(self.parse_pattern_binding(binding));
         };
 
         // TODO(yuval): Support "Or" patterns.
@@ -1630,7 +1748,15 @@ impl<'a> Parser<'a> {
                         // sufficient.
                         match children.len() {
                             // 0 => return None, - unreachable
-                            1 => path.into(),
+                            1 => match self.try_extract_simple_identifier(children[0]) {
+                                Some(name) if self.peek().kind == SyntaxKind::TerminalAt => {
+                                    let modifiers = ModifierList::new_green(self.db, vec![]);
+                                    let binding =
+                                        PatternIdentifier::new_green(self.db, modifiers, name);
+                                    self.parse_pattern_binding(binding)
+                                }
+                                _ => path.into(),
+                            },
                             _ => PatternEnum::new_green(
                                 self.db,
                                 path,
@@ -1658,6 +1784,28 @@ impl<'a> Parser<'a> {
             _ => return Err(TryParseFailure::SkipToken),
         })
     }
+    /// Returns a GreenId of a node with kind PatternBinding if the binding is followed by `@` (e.g.
+    /// `x @ Some(y)`), or of the binding itself otherwise.
+    fn parse_pattern_binding(&mut self, binding: PatternIdentifierGreen) -> PatternGreen {
+        if self.peek().kind != SyntaxKind::TerminalAt {
+            return binding.into();
+        }
+        let at = self.take::<TerminalAt>();
+        let pattern = self.parse_pattern();
+        PatternBinding::new_green(self.db, binding, at, pattern).into()
+    }
+
+    /// Returns the identifier of a path segment without generic args, or None for other segments.
+    fn try_extract_simple_identifier(&self, segment: GreenId) -> Option<TerminalIdentifierGreen> {
+        let green_node = self.db.lookup_intern_green(segment);
+        match (green_node.kind, &green_node.details) {
+            (SyntaxKind::PathSegmentSimple, GreenNodeDetails::Node { children, width: _ }) => {
+                Some(TerminalIdentifierGreen(children[0]))
+            }
+            _ => None,
+        }
+    }
+
     /// Returns a GreenId of a node with some Pattern kind (see
     /// [syntax::node::ast::Pattern]).
     fn parse_pattern(&mut self) -> PatternGreen {
@@ -1929,7 +2077,20 @@ impl<'a> Parser<'a> {
         };
 
         let type_clause = self.parse_option_type_clause();
-        Ok(Variant::new_green(self.db, attributes, name, type_clause))
+        let discriminant = self.parse_option_variant_discriminant();
+        Ok(Variant::new_green(self.db, attributes, name, type_clause, discriminant))
+    }
+
+    /// Returns a GreenId of a node with kind VariantDiscriminant or OptionVariantDiscriminantEmpty
+    /// if an explicit discriminant of an enum variant can't be parsed.
+    fn parse_option_variant_discriminant(&mut self) -> OptionVariantDiscriminantGreen {
+        if self.peek().kind == SyntaxKind::TerminalEq {
+            let eq = self.take::<TerminalEq>();
+            let value = self.parse_expr();
+            VariantDiscriminant::new_green(self.db, eq, value).into()
+        } else {
+            OptionVariantDiscriminantEmpty::new_green(self.db).into()
+        }
     }
 
     /// Expected pattern: `<PathSegment>(::<PathSegment>)*`
@@ -2441,13 +2602,9 @@ impl<'a> Parser<'a> {
         let mut diag_start = None;
         let mut diag_end = None;
         while !should_stop(self.peek().kind) {
-            let terminal = self.take_raw();
-            diag_start.get_or_insert(self.offset);
-            diag_end = Some(self.offset.add_width(TextWidth::from_str(&terminal.text)));
-
-            self.pending_trivia.extend(terminal.leading_trivia);
-            self.pending_trivia.push(TokenSkipped::new_green(self.db, terminal.text).into());
-            self.pending_trivia.extend(terminal.trailing_trivia);
+            let span = self.skip_terminal_silently();
+            diag_start.get_or_insert(span.start);
+            diag_end = Some(span.end);
         }
         if let (Some(diag_start), Some(diag_end)) = (diag_start, diag_end) {
             Err(SkippedError(TextSpan { start: diag_start, end: diag_end }))
@@ -2456,6 +2613,60 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Skips the rest of an expression nested deeper than [MAX_EXPR_NESTING_DEPTH] and reports it.
+    /// Terminals are skipped up to the end of the enclosing brackets, or up to the end of the
+    /// enclosing statement or list element.
+    fn skip_too_deeply_nested_expr(&mut self) {
+        let mut open_brackets: usize = 0;
+        let mut span = TextSpan { start: self.offset, end: self.offset };
+        let mut skipped_any = false;
+        loop {
+            match self.peek().kind {
+                SyntaxKind::TerminalEndOfFile => break,
+                SyntaxKind::TerminalLParen
+                | SyntaxKind::TerminalLBrace
+                | SyntaxKind::TerminalLBrack => open_brackets += 1,
+                SyntaxKind::TerminalRParen
+                | SyntaxKind::TerminalRBrace
+                | SyntaxKind::TerminalRBrack => {
+                    if open_brackets == 0 {
+                        break;
+                    }
+                    open_brackets -= 1;
+                }
+                SyntaxKind::TerminalSemicolon | SyntaxKind::TerminalComma if open_brackets == 0 => {
+                    break;
+                }
+                _ => {}
+            }
+            let skipped_span = self.skip_terminal_silently();
+            if !skipped_any {
+                span.start = skipped_span.start;
+                skipped_any = true;
+            }
+            span.end = skipped_span.end;
+        }
+        self.diagnostics.add(ParserDiagnostic {
+            file_id: self.file_id,
+            kind: ParserDiagnosticKind::ExprNestedTooDeeply,
+            span,
+        });
+    }
+
+    /// Skips the next, non-taken, terminal without reporting a diagnostic, and returns the span of
+    /// its token.
+    fn skip_terminal_silently(&mut self) -> TextSpan {
+        let terminal = self.take_raw();
+        let span = TextSpan {
+            start: self.offset,
+            end: self.offset.add_width(TextWidth::from_str(&terminal.text)),
+        };
+        self.pending_trivia.extend(terminal.leading_trivia);
+        self.pending_trivia.push(TokenSkipped::new_green(self.db, terminal.text).into());
+        self.pending_trivia.extend(terminal.trailing_trivia);
+        span
+    }
+
     /// Builds a new terminal to replace the given terminal by gluing the recently skipped terminals
     /// to the given terminal as extra leading trivia.
     fn add_trivia_to_terminal<Terminal: syntax::node::Terminal>(
@@ -165,6 +165,9 @@ cairo_lang_test_utils::test_file_test!(
         enum_: "enum",
         expr: "expr",
         loop_: "loop",
+        while_: "while",
+        for_: "for",
+        range: "range",
         item_free_function: "item_free_function",
         function_signature: "function_signature",
         function_call: "function_call",
@@ -197,3 +200,40 @@ cairo_lang_test_utils::test_file_test!(
     },
     test_partial_parser_tree_with_trivia
 );
+
+#[test]
+fn test_deeply_nested_expr() {
+    let db = &SimpleParserDatabase::default();
+    let nesting = 10000;
+    for expr in [
+        format!("{}0{}", "(".repeat(nesting), ")".repeat(nesting)),
+        format!("{}0", "-".repeat(nesting)),
+        format!("{}0{}", "foo(".repeat(nesting), ")".repeat(nesting)),
+    ] {
+        let code = format!("fn foo() {{ let x = {expr}; x }}");
+        let file_id = create_virtual_file(db, "dummy_file.cairo", &code);
+        let (syntax_root, diagnostics) = get_syntax_root_and_diagnostics(db, file_id, &code);
+        assert!(
+            diagnostics.format(db).contains("Skipped tokens. Expression is nested too deeply.")
+        );
+        // The skipped tokens are kept as trivia.
+        assert_eq!(syntax_root.get_text(db), code);
+    }
+}
+
+#[test]
+fn test_long_flat_expr() {
+    let db = &SimpleParserDatabase::default();
+    let length = 10000;
+    for expr in [
+        vec!["0"; length].join(" + "),
+        format!("x{}", ".y".repeat(length)),
+        format!("if x {{}}{}", " else if x {}".repeat(length)),
+    ] {
+        let code = format!("fn foo() {{ let x = {expr}; x }}");
+        let file_id = create_virtual_file(db, "dummy_file.cairo", &code);
+        let (syntax_root, diagnostics) = get_syntax_root_and_diagnostics(db, file_id, &code);
+        assert_eq!(diagnostics.format(db), "");
+        assert_eq!(syntax_root.get_text(db), code);
+    }
+}
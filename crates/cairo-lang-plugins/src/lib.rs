@@ -3,7 +3,9 @@ use std::sync::Arc;
 
 use cairo_lang_defs::plugin::MacroPlugin;
 
-use crate::plugins::{ConfigPlugin, DerivePlugin, GenerateTraitPlugin, PanicablePlugin};
+use crate::plugins::{
+    CLikeEnumPlugin, ConfigPlugin, DerivePlugin, GenerateTraitPlugin, PanicablePlugin,
+};
 
 pub mod plugins;
 #[cfg(any(feature = "testing", test))]
@@ -19,6 +21,7 @@ pub fn get_base_plugins() -> Vec<Arc<dyn MacroPlugin>> {
     vec![
         Arc::new(ConfigPlugin::default()),
         Arc::new(DerivePlugin::default()),
+        Arc::new(CLikeEnumPlugin::default()),
         Arc::new(GenerateTraitPlugin::default()),
         Arc::new(PanicablePlugin::default()),
     ]
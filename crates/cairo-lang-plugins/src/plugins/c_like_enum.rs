@@ -0,0 +1,74 @@
+use cairo_lang_defs::plugin::{MacroPlugin, PluginGeneratedFile, PluginResult};
+use cairo_lang_syntax::node::ast::{OptionTypeClause, OptionWrappedGenericParamList};
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::helpers::GenericParamEx;
+use cairo_lang_syntax::node::{ast, Terminal, TypedSyntaxNode};
+use indoc::formatdoc;
+use itertools::Itertools;
+
+/// Plugin implementing `core::enums::CLikeEnum` for every C-like enum - an enum whose variants have
+/// no data.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct CLikeEnumPlugin;
+
+impl MacroPlugin for CLikeEnumPlugin {
+    fn generate_code(&self, db: &dyn SyntaxGroup, item_ast: ast::Item) -> PluginResult {
+        let ast::Item::Enum(enum_ast) = item_ast else {
+            return PluginResult::default();
+        };
+        // Enums without variants have no values to convert.
+        let variants = enum_ast.variants(db).elements(db);
+        if variants.is_empty() || !variants.iter().all(|variant| is_data_less(db, variant)) {
+            return PluginResult::default();
+        }
+        let name = enum_ast.name(db).text(db);
+        let (generic_params, generic_args) = match enum_ast.generic_params(db) {
+            OptionWrappedGenericParamList::Empty(_) => ("".to_string(), "".to_string()),
+            OptionWrappedGenericParamList::WrappedGenericParamList(params) => {
+                let generic_args = params
+                    .generic_params(db)
+                    .elements(db)
+                    .iter()
+                    .map(|param| param.name(db).map(|n| n.text(db)).unwrap_or_else(|| "_".into()))
+                    .join(", ");
+                (params.as_syntax_node().get_text_without_trivia(db), format!("<{generic_args}>"))
+            }
+        };
+        let ty = format!("{name}{generic_args}");
+        PluginResult {
+            code: Some(PluginGeneratedFile {
+                name: "c_like_enum".into(),
+                content: formatdoc! {"
+                    impl {name}CLikeEnum{generic_params} of core::enums::CLikeEnum<{ty}> {{
+                        fn to_felt252(self: {ty}) -> felt252 nopanic {{
+                            core::enums::enum_to_felt252(self)
+                        }}
+                        fn try_from_felt252(value: felt252) -> core::option::Option<{ty}> nopanic {{
+                            core::enums::enum_try_from_felt252(value)
+                        }}
+                    }}
+                "},
+                code_mappings: vec![],
+                aux_data: None,
+            }),
+            diagnostics: vec![],
+            remove_original_item: false,
+        }
+    }
+
+    fn declared_attributes(&self) -> Vec<String> {
+        vec![]
+    }
+}
+
+/// Returns whether a variant has no data - has no type, or the unit type.
+fn is_data_less(db: &dyn SyntaxGroup, variant: &ast::Variant) -> bool {
+    match variant.type_clause(db) {
+        OptionTypeClause::Empty(_) => true,
+        OptionTypeClause::TypeClause(type_clause) => matches!(
+            type_clause.ty(db),
+            ast::Expr::Tuple(tuple) if tuple.expressions(db).elements(db).is_empty()
+        ),
+    }
+}
@@ -61,6 +61,23 @@ impl MacroPlugin for DerivePlugin {
     fn declared_attributes(&self) -> Vec<String> {
         vec![DERIVE_ATTR.to_string(), default::DEFAULT_ATTR.to_string()]
     }
+
+    fn declared_derives(&self) -> Vec<String> {
+        [
+            "Clone",
+            "Copy",
+            "Debug",
+            "Default",
+            "Destruct",
+            "Drop",
+            "Hash",
+            "PanicDestruct",
+            "PartialEq",
+            "Serde",
+        ]
+        .map(String::from)
+        .to_vec()
+    }
 }
 
 /// Information on struct members or enum variants.
@@ -1,8 +1,10 @@
+pub use c_like_enum::*;
 pub use config::*;
 pub use derive::*;
 pub use generate_trait::*;
 pub use panicable::*;
 
+mod c_like_enum;
 mod config;
 mod derive;
 mod generate_trait;
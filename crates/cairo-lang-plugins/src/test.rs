@@ -24,6 +24,7 @@ cairo_lang_test_utils::test_file_test!(
     expand_plugin,
     "src/test_data",
     {
+        c_like_enum: "c_like_enum",
         config: "config",
         derive: "derive",
         generate_trait: "generate_trait",
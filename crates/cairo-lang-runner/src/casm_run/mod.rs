@@ -49,6 +49,7 @@ mod test;
 
 mod contract_address;
 mod dict_manager;
+pub mod state_diff;
 
 // TODO(orizi): This def is duplicated.
 /// Returns the Beta value of the Starkware elliptic curve.
@@ -136,6 +137,9 @@ pub struct StarknetState {
     deployed_contracts: HashMap<Felt252, Felt252>,
     /// A mapping from contract address to logs.
     logs: HashMap<Felt252, ContractLogs>,
+    /// All the events emitted in the run with the address of their contract, in order - including
+    /// the ones popped from the logs.
+    emitted_events: Vec<(Felt252, Log)>,
     /// The simulated execution info.
     exec_info: ExecutionInfo,
     next_id: Felt252,
@@ -912,6 +916,7 @@ impl<'a> CairoHintProcessor<'a> {
     ) -> Result<SyscallResult, HintError> {
         deduct_gas!(gas_counter, EMIT_EVENT);
         let contract = self.starknet_state.exec_info.contract_address.clone();
+        self.starknet_state.emitted_events.push((contract.clone(), (keys.clone(), data.clone())));
         self.starknet_state.logs.entry(contract).or_default().events.push_back((keys, data));
         Ok(SyscallResult::Success(vec![]))
     }
@@ -2015,6 +2020,15 @@ pub fn execute_core_hint(
                 insert_value_to_cellref!(vm, g0_or_no_inv, Felt252::from(0))?;
             }
         }
+        CoreHint::FieldInverse { val, inverse } => {
+            let val = Fq::from(get_val(vm, val)?.to_biguint());
+            let res: BigUint = val
+                .inverse()
+                .ok_or_else(|| HintError::CustomHint(Box::from("Inverse of zero.".to_string())))?
+                .into_bigint()
+                .into();
+            insert_value_to_cellref!(vm, inverse, Felt252::from(res))?;
+        }
     };
     Ok(())
 }
@@ -2076,7 +2090,7 @@ pub fn extract_buffer(buffer: &ResOperand) -> (&CellRef, Felt252) {
     (cell, base_offset)
 }
 
-/// Provides context for the `additional_initialization` callback function of [run_function].
+/// Provides context for the `additional_initialization` callback function of [run_bytecode].
 pub struct RunFunctionContext<'a> {
     pub vm: &'a mut VirtualMachine,
     pub data_len: usize,
@@ -2086,6 +2100,8 @@ type RunFunctionRes = (Vec<Option<Felt252>>, usize);
 
 /// Runs `program` on layout with prime, and returns the memory layout and ap value.
 /// Allows injecting custom HintProcessor.
+/// The program must not use constants, as they are not part of its instructions - see
+/// [run_bytecode] for running an assembled program.
 pub fn run_function<'a, 'b: 'a, Instructions>(
     vm: &mut VirtualMachine,
     instructions: Instructions,
@@ -2099,11 +2115,34 @@ pub fn run_function<'a, 'b: 'a, Instructions>(
 where
     Instructions: Iterator<Item = &'a Instruction> + Clone,
 {
-    let data: Vec<MaybeRelocatable> = instructions
-        .flat_map(|inst| inst.assemble().encode())
-        .map(Felt252::from)
-        .map(MaybeRelocatable::from)
-        .collect();
+    let bytecode: Vec<BigInt> = instructions.flat_map(|inst| inst.assemble().encode()).collect();
+    run_bytecode(
+        vm,
+        bytecode.iter(),
+        builtins,
+        additional_initialization,
+        hint_processor,
+        hints_dict,
+    )
+}
+
+/// Runs `bytecode` on layout with prime, and returns the memory layout and ap value.
+/// Allows injecting custom HintProcessor.
+pub fn run_bytecode<'a, Bytecode>(
+    vm: &mut VirtualMachine,
+    bytecode: Bytecode,
+    builtins: Vec<BuiltinName>,
+    additional_initialization: fn(
+        context: RunFunctionContext<'_>,
+    ) -> Result<(), Box<CairoRunError>>,
+    hint_processor: &mut dyn HintProcessor,
+    hints_dict: HashMap<usize, Vec<HintParams>>,
+) -> Result<RunFunctionRes, Box<CairoRunError>>
+where
+    Bytecode: Iterator<Item = &'a BigInt> + Clone,
+{
+    let data: Vec<MaybeRelocatable> =
+        bytecode.map(Felt252::from).map(MaybeRelocatable::from).collect();
 
     let data_len = data.len();
     let program = Program::new(
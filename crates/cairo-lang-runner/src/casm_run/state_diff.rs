@@ -0,0 +1,120 @@
+//! Export of the changes a run made to the simulated Starknet state, in the JSON formats of the
+//! Starknet JSON-RPC API, as used by devnets.
+use cairo_felt::Felt252;
+use cairo_lang_utils::bigint::BigUintAsHex;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use super::StarknetState;
+
+/// The changes a run made to the simulated Starknet state, along with the events emitted in it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StarknetStateArtifact {
+    pub state_diff: StateDiff,
+    pub events: Vec<EmittedEvent>,
+}
+
+/// A state diff, as returned by `starknet_getStateUpdate`.
+/// The simulated state does not track declarations and nonces, so their lists are always empty.
+/// Contracts whose class was replaced in the run are listed as deployed with their final class.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDiff {
+    pub storage_diffs: Vec<ContractStorageDiff>,
+    pub deprecated_declared_classes: Vec<BigUintAsHex>,
+    pub declared_classes: Vec<DeclaredClass>,
+    pub deployed_contracts: Vec<DeployedContract>,
+    pub replaced_classes: Vec<ReplacedClass>,
+    pub nonces: Vec<ContractNonce>,
+}
+
+/// The storage values written to a contract.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractStorageDiff {
+    pub address: BigUintAsHex,
+    pub storage_entries: Vec<StorageEntry>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageEntry {
+    pub key: BigUintAsHex,
+    pub value: BigUintAsHex,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeclaredClass {
+    pub class_hash: BigUintAsHex,
+    pub compiled_class_hash: BigUintAsHex,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeployedContract {
+    pub address: BigUintAsHex,
+    pub class_hash: BigUintAsHex,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplacedClass {
+    pub contract_address: BigUintAsHex,
+    pub class_hash: BigUintAsHex,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractNonce {
+    pub contract_address: BigUintAsHex,
+    pub nonce: BigUintAsHex,
+}
+
+/// An event, as returned by `starknet_getEvents`, without the block and transaction information.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmittedEvent {
+    pub from_address: BigUintAsHex,
+    pub keys: Vec<BigUintAsHex>,
+    pub data: Vec<BigUintAsHex>,
+}
+
+fn as_hex(value: &Felt252) -> BigUintAsHex {
+    BigUintAsHex { value: value.to_biguint() }
+}
+
+impl StarknetState {
+    /// Returns the changes made to the state since it was created, along with the events emitted,
+    /// including the ones already popped from the logs.
+    /// The contracts and storage entries are sorted by address, to make the artifact deterministic.
+    pub fn artifact(&self) -> StarknetStateArtifact {
+        let storage_diffs = self
+            .storage
+            .iter()
+            .sorted_by_key(|(address, _)| *address)
+            .map(|(address, storage)| ContractStorageDiff {
+                address: as_hex(address),
+                storage_entries: storage
+                    .iter()
+                    .sorted_by_key(|(key, _)| *key)
+                    .map(|(key, value)| StorageEntry { key: as_hex(key), value: as_hex(value) })
+                    .collect(),
+            })
+            .collect();
+        let deployed_contracts = self
+            .deployed_contracts
+            .iter()
+            .sorted_by_key(|(address, _)| *address)
+            .map(|(address, class_hash)| DeployedContract {
+                address: as_hex(address),
+                class_hash: as_hex(class_hash),
+            })
+            .collect();
+        let events = self
+            .emitted_events
+            .iter()
+            .map(|(from_address, (keys, data))| EmittedEvent {
+                from_address: as_hex(from_address),
+                keys: keys.iter().map(as_hex).collect(),
+                data: data.iter().map(as_hex).collect(),
+            })
+            .collect();
+        StarknetStateArtifact {
+            state_diff: StateDiff { storage_diffs, deployed_contracts, ..Default::default() },
+            events,
+        }
+    }
+}
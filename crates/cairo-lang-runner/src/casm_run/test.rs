@@ -456,3 +456,29 @@ fn test_calculate_contract_address() {
         deployed_contract_address
     );
 }
+
+#[test]
+fn test_starknet_state_artifact() {
+    let mut state = StarknetState::default();
+    state.storage.entry(Felt252::from(2)).or_default().insert(Felt252::from(10), Felt252::from(7));
+    state.storage.entry(Felt252::from(1)).or_default().insert(Felt252::from(11), Felt252::from(8));
+    state.deployed_contracts.insert(Felt252::from(2), Felt252::from(255));
+    state.emitted_events.push((Felt252::from(2), (vec![Felt252::from(3)], vec![Felt252::from(4)])));
+    assert_eq!(
+        serde_json::to_value(state.artifact()).unwrap(),
+        serde_json::json!({
+            "state_diff": {
+                "storage_diffs": [
+                    {"address": "0x1", "storage_entries": [{"key": "0xb", "value": "0x8"}]},
+                    {"address": "0x2", "storage_entries": [{"key": "0xa", "value": "0x7"}]},
+                ],
+                "deprecated_declared_classes": [],
+                "declared_classes": [],
+                "deployed_contracts": [{"address": "0x2", "class_hash": "0xff"}],
+                "replaced_classes": [],
+                "nonces": [],
+            },
+            "events": [{"from_address": "0x2", "keys": ["0x3"], "data": ["0x4"]}],
+        })
+    );
+}
@@ -36,11 +36,15 @@ use cairo_vm::vm::vm_core::VirtualMachine;
 use casm_run::hint_to_hint_params;
 pub use casm_run::{CairoHintProcessor, StarknetState};
 use itertools::chain;
+use num_bigint::BigInt;
 use num_traits::ToPrimitive;
+pub use profiling::FunctionResources;
 use thiserror::Error;
 
 pub mod casm_run;
+pub mod profiling;
 pub mod short_string;
+pub mod value_format;
 
 #[derive(Debug, Error)]
 pub enum RunnerError {
@@ -190,31 +194,87 @@ impl SierraCasmRunner {
         available_gas: Option<usize>,
         starknet_state: StarknetState,
     ) -> Result<RunResultStarknet, RunnerError> {
+        let mut vm = VirtualMachine::new(true);
+        self.run_function_with_starknet_context_and_vm(
+            func,
+            &mut vm,
+            args,
+            available_gas,
+            starknet_state,
+        )
+        .map(|(result, _)| result)
+    }
+
+    /// Runs the vm starting from a function in the context of a given starknet state, and returns
+    /// the resources used by each function called in the run along with its result.
+    pub fn run_function_with_resources(
+        &self,
+        func: &Function,
+        args: &[Arg],
+        available_gas: Option<usize>,
+        starknet_state: StarknetState,
+    ) -> Result<(RunResultStarknet, OrderedHashMap<String, FunctionResources>), RunnerError> {
+        let mut vm = VirtualMachine::new(true);
+        let (result, entry_code_size) = self.run_function_with_starknet_context_and_vm(
+            func,
+            &mut vm,
+            args,
+            available_gas,
+            starknet_state,
+        )?;
+        let trace = vm.get_relocated_trace().map_err(|err| Box::new(CairoRunError::from(err)))?;
+        let resources = self.collect_function_resources(trace, &result.memory, entry_code_size);
+        Ok((result, resources))
+    }
+
+    /// Runs the vm starting from a function in the context of a given starknet state, using the
+    /// given vm. Returns the result of the run, and the size of the code added before the program
+    /// to call the function.
+    fn run_function_with_starknet_context_and_vm(
+        &self,
+        func: &Function,
+        vm: &mut VirtualMachine,
+        args: &[Arg],
+        available_gas: Option<usize>,
+        starknet_state: StarknetState,
+    ) -> Result<(RunResultStarknet, usize), RunnerError> {
         let initial_gas = self.get_initial_available_gas(func, available_gas)?;
         let (entry_code, builtins) = self.create_entry_code(func, args, initial_gas)?;
+        let entry_code_size = entry_code.iter().map(|instruction| instruction.body.op_size()).sum();
         let footer = self.create_code_footer();
-        let instructions =
-            chain!(entry_code.iter(), self.casm_program.instructions.iter(), footer.iter());
-        let (hints_dict, string_to_hint) = build_hints_dict(instructions.clone());
+        let instructions = chain!(entry_code.iter(), self.casm_program.instructions.iter());
+        let (hints_dict, string_to_hint) = build_hints_dict(instructions);
+        let assembled_program = self.casm_program.assemble_ex(&entry_code, &footer);
         let mut hint_processor = CairoHintProcessor {
             runner: Some(self),
             starknet_state,
             string_to_hint,
             run_resources: RunResources::default(),
         };
-        self.run_function(func, &mut hint_processor, hints_dict, instructions, builtins).map(|v| {
+        let result = self.run_function_bytecode_with_vm(
+            func,
+            vm,
+            &mut hint_processor,
+            hints_dict,
+            assembled_program.bytecode.iter(),
+            builtins,
+        )?;
+        Ok((
             RunResultStarknet {
-                gas_counter: v.gas_counter,
-                memory: v.memory,
-                value: v.value,
+                gas_counter: result.gas_counter,
+                memory: result.memory,
+                value: result.value,
                 starknet_state: hint_processor.starknet_state,
-            }
-        })
+            },
+            entry_code_size,
+        ))
     }
 
     /// Runs the vm starting from a function with custom hint processor. Function may have
     /// implicits, but no other ref params. The cost of the function is deducted from
     /// available_gas before the execution begins.
+    /// The program must not use constants, as they are not part of its instructions - see
+    /// [Self::run_function_bytecode_with_vm] for running an assembled program.
     ///
     /// Allows injecting Cairo VirtualMachine
     pub fn run_function_with_vm<'a, Instructions>(
@@ -229,9 +289,36 @@ impl SierraCasmRunner {
     where
         Instructions: Iterator<Item = &'a Instruction> + Clone,
     {
-        let (cells, ap) = casm_run::run_function(
+        let bytecode: Vec<BigInt> =
+            instructions.flat_map(|instruction| instruction.assemble().encode()).collect();
+        self.run_function_bytecode_with_vm(
+            func,
+            vm,
+            hint_processor,
+            hints_dict,
+            bytecode.iter(),
+            builtins,
+        )
+    }
+
+    /// Runs the vm starting from a function with custom hint processor, as
+    /// [Self::run_function_with_vm], given the bytecode of the assembled program, including its
+    /// constants.
+    pub fn run_function_bytecode_with_vm<'a, Bytecode>(
+        &self,
+        func: &Function,
+        vm: &mut VirtualMachine,
+        hint_processor: &mut dyn HintProcessor,
+        hints_dict: HashMap<usize, Vec<HintParams>>,
+        bytecode: Bytecode,
+        builtins: Vec<BuiltinName>,
+    ) -> Result<RunResult, RunnerError>
+    where
+        Bytecode: Iterator<Item = &'a BigInt> + Clone,
+    {
+        let (cells, ap) = casm_run::run_bytecode(
             vm,
-            instructions,
+            bytecode,
             builtins,
             |context| {
                 let vm = context.vm;
@@ -288,6 +375,8 @@ impl SierraCasmRunner {
     /// Runs the vm starting from a function with custom hint processor. Function may have
     /// implicits, but no other ref params. The cost of the function is deducted from
     /// available_gas before the execution begins.
+    /// The program must not use constants, as they are not part of its instructions - see
+    /// [Self::run_function_bytecode] for running an assembled program.
     pub fn run_function<'a, Instructions>(
         &self,
         func: &Function,
@@ -303,6 +392,30 @@ impl SierraCasmRunner {
         self.run_function_with_vm(func, &mut vm, hint_processor, hints_dict, instructions, builtins)
     }
 
+    /// Runs the vm starting from a function with custom hint processor, as [Self::run_function],
+    /// given the bytecode of the assembled program, including its constants.
+    pub fn run_function_bytecode<'a, Bytecode>(
+        &self,
+        func: &Function,
+        hint_processor: &mut dyn HintProcessor,
+        hints_dict: HashMap<usize, Vec<HintParams>>,
+        bytecode: Bytecode,
+        builtins: Vec<BuiltinName>,
+    ) -> Result<RunResult, RunnerError>
+    where
+        Bytecode: Iterator<Item = &'a BigInt> + Clone,
+    {
+        let mut vm = VirtualMachine::new(true);
+        self.run_function_bytecode_with_vm(
+            func,
+            &mut vm,
+            hint_processor,
+            hints_dict,
+            bytecode,
+            builtins,
+        )
+    }
+
     /// Handling the main return value to create a `RunResultValue`.
     fn handle_main_return_value(
         &self,
@@ -310,37 +423,46 @@ impl SierraCasmRunner {
         values: Vec<Felt252>,
         cells: &[Option<Felt252>],
     ) -> Result<RunResultValue, RunnerError> {
-        let info = self.get_info(&ty);
-        let long_id = &info.long_id;
-        Ok(
-            if long_id.generic_id == EnumType::ID
-                && matches!(&long_id.generic_args[0], GenericArg::UserType(ut)
-                if ut.debug_name.as_ref().unwrap().starts_with("core::panics::PanicResult::"))
-            {
-                // The function includes a panic wrapper.
-                if values[0] != Felt252::from(0) {
-                    // The run resulted in a panic, returning the error data.
-                    let err_data_start = values[values.len() - 2].to_usize().unwrap();
-                    let err_data_end = values[values.len() - 1].to_usize().unwrap();
-                    RunResultValue::Panic(
-                        cells[err_data_start..err_data_end]
-                            .iter()
-                            .cloned()
-                            .map(|cell| cell.unwrap())
-                            .collect(),
-                    )
-                } else {
-                    // The run resulted successfully, returning the inner value.
-                    let inner_ty = extract_matches!(&long_id.generic_args[1], GenericArg::Type);
-                    let inner_ty_size = self.type_sizes[inner_ty] as usize;
-                    let skip_size = values.len() - inner_ty_size;
-                    RunResultValue::Success(values.into_iter().skip(skip_size).collect())
-                }
+        Ok(if let Some(inner_ty) = self.panic_result_inner_type(&ty) {
+            // The function includes a panic wrapper.
+            if values[0] != Felt252::from(0) {
+                // The run resulted in a panic, returning the error data.
+                let err_data_start = values[values.len() - 2].to_usize().unwrap();
+                let err_data_end = values[values.len() - 1].to_usize().unwrap();
+                RunResultValue::Panic(
+                    cells[err_data_start..err_data_end]
+                        .iter()
+                        .cloned()
+                        .map(|cell| cell.unwrap())
+                        .collect(),
+                )
             } else {
-                // No panic wrap - so always successful.
-                RunResultValue::Success(values)
-            },
-        )
+                // The run resulted successfully, returning the inner value.
+                let inner_ty_size = self.type_sizes[inner_ty] as usize;
+                let skip_size = values.len() - inner_ty_size;
+                RunResultValue::Success(values.into_iter().skip(skip_size).collect())
+            }
+        } else {
+            // No panic wrap - so always successful.
+            RunResultValue::Success(values)
+        })
+    }
+
+    /// Returns the type of the value of a successful run if `ty` is the `PanicResult` wrapping the
+    /// return value of a function that may panic.
+    fn panic_result_inner_type<'a>(
+        &'a self,
+        ty: &cairo_lang_sierra::ids::ConcreteTypeId,
+    ) -> Option<&'a cairo_lang_sierra::ids::ConcreteTypeId> {
+        let long_id = &self.get_info(ty).long_id;
+        if long_id.generic_id == EnumType::ID
+            && matches!(&long_id.generic_args[0], GenericArg::UserType(ut)
+                if ut.debug_name.as_ref().unwrap().starts_with("core::panics::PanicResult::"))
+        {
+            Some(extract_matches!(&long_id.generic_args[1], GenericArg::Type))
+        } else {
+            None
+        }
     }
 
     /// Returns the final values and type of all `func`s returning variables.
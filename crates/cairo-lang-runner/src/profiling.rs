@@ -0,0 +1,150 @@
+//! Collection of the execution resources used by each function of a run.
+use std::collections::{HashMap, HashSet};
+
+use cairo_felt::Felt252;
+use cairo_lang_casm::instructions::InstructionBody;
+use cairo_lang_sierra::extensions::bitwise::BitwiseType;
+use cairo_lang_sierra::extensions::ec::EcOpType;
+use cairo_lang_sierra::extensions::pedersen::PedersenType;
+use cairo_lang_sierra::extensions::poseidon::PoseidonType;
+use cairo_lang_sierra::extensions::range_check::RangeCheckType;
+use cairo_lang_sierra::extensions::NamedType;
+use cairo_lang_sierra::ids::{ConcreteTypeId, FunctionId, GenericTypeId};
+use cairo_lang_sierra::program::Function;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_vm::vm::trace::trace_entry::TraceEntry;
+use num_traits::ToPrimitive;
+
+use crate::SierraCasmRunner;
+
+/// The builtins whose usage is collected for each function.
+const PROFILED_BUILTINS: [GenericTypeId; 5] =
+    [RangeCheckType::ID, PedersenType::ID, BitwiseType::ID, EcOpType::ID, PoseidonType::ID];
+
+/// The execution resources used by the calls to a function, including the resources used by the
+/// functions it calls. Recursive calls are counted once, as part of the outermost call.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FunctionResources {
+    /// The number of calls to the function, not including recursive calls.
+    pub calls: usize,
+    /// The number of executed steps.
+    pub steps: usize,
+    /// The number of memory cells allocated on the execution stack.
+    pub memory_cells: usize,
+    /// The number of instances used of each builtin, by the name of the builtin type.
+    pub builtins: OrderedHashMap<String, usize>,
+}
+
+/// A call to a function that has not returned yet.
+struct Frame<'a> {
+    function: &'a Function,
+    /// The frame pointer of the call.
+    fp: usize,
+    /// The index in the trace of the first step of the call.
+    first_step: usize,
+}
+
+impl SierraCasmRunner {
+    /// Returns the resources used by each function called in a run, by the name of the function -
+    /// which matches the functions of programs compiled from different versions of the code.
+    /// `trace` and `memory` are the relocated trace and memory of the run, and `entry_code_size`
+    /// is the size of the code added before the program to call the run function.
+    pub(crate) fn collect_function_resources(
+        &self,
+        trace: &[TraceEntry],
+        memory: &[Option<Felt252>],
+        entry_code_size: usize,
+    ) -> OrderedHashMap<String, FunctionResources> {
+        let mut resources = OrderedHashMap::<String, FunctionResources>::default();
+        let Some(first_entry) = trace.first() else {
+            return resources;
+        };
+        // The run starts at the beginning of the entry code, followed by the program code.
+        let code_start = first_entry.pc + entry_code_size;
+        let statement_info = &self.casm_program.debug_info.sierra_statement_info;
+        let functions_by_offset: HashMap<usize, &Function> = self
+            .sierra_program
+            .funcs
+            .iter()
+            .map(|function| (statement_info[function.entry_point.0].code_offset, function))
+            .collect();
+        let mut ret_offsets = HashSet::new();
+        let mut offset = 0;
+        for instruction in &self.casm_program.instructions {
+            if matches!(instruction.body, InstructionBody::Ret(_)) {
+                ret_offsets.insert(offset);
+            }
+            offset += instruction.body.op_size();
+        }
+
+        let mut frames: Vec<Frame<'_>> = vec![];
+        let mut active_calls = HashMap::<&FunctionId, usize>::new();
+        for (step, entry) in trace.iter().enumerate() {
+            let Some(offset) = entry.pc.checked_sub(code_start) else {
+                continue;
+            };
+            if let Some(&function) = functions_by_offset.get(&offset) {
+                // A jump to the entry point of the current function is not a call.
+                if frames.last().map(|frame| frame.fp) != Some(entry.fp) {
+                    *active_calls.entry(&function.id).or_default() += 1;
+                    frames.push(Frame { function, fp: entry.fp, first_step: step });
+                }
+            }
+            if !ret_offsets.contains(&offset)
+                || frames.last().map(|frame| frame.fp) != Some(entry.fp)
+            {
+                continue;
+            }
+            let frame = frames.pop().unwrap();
+            let active = active_calls.get_mut(&frame.function.id).unwrap();
+            *active -= 1;
+            if *active != 0 {
+                continue;
+            }
+            let function_resources = resources.entry(frame.function.id.to_string()).or_default();
+            function_resources.calls += 1;
+            function_resources.steps += step - frame.first_step + 1;
+            function_resources.memory_cells += entry.ap - frame.fp;
+            for (builtin, used) in self.builtins_usage(frame.function, frame.fp, entry.ap, memory) {
+                *function_resources.builtins.entry(builtin).or_default() += used;
+            }
+        }
+        resources
+    }
+
+    /// Returns the number of instances used of each builtin by a call to a function, by comparing
+    /// the builtin pointers it was called with to the ones it returned. `fp` is the frame pointer
+    /// of the call, and `ap` is the allocation pointer when it returned.
+    fn builtins_usage(
+        &self,
+        function: &Function,
+        fp: usize,
+        ap: usize,
+        memory: &[Option<Felt252>],
+    ) -> Vec<(String, usize)> {
+        let params_start = fp - 2 - self.types_size(&function.signature.param_types);
+        let rets_start = ap - self.types_size(&function.signature.ret_types);
+        PROFILED_BUILTINS
+            .iter()
+            .filter_map(|builtin| {
+                let param_offset = self.type_offset(&function.signature.param_types, builtin)?;
+                let ret_offset = self.type_offset(&function.signature.ret_types, builtin)?;
+                let before = memory.get(params_start + param_offset)?.as_ref()?.to_usize()?;
+                let after = memory.get(rets_start + ret_offset)?.as_ref()?.to_usize()?;
+                Some((builtin.to_string(), after - before))
+            })
+            .collect()
+    }
+
+    /// Returns the total size of the types.
+    fn types_size(&self, types: &[ConcreteTypeId]) -> usize {
+        types.iter().map(|ty| self.type_sizes[ty] as usize).sum()
+    }
+
+    /// Returns the offset of the first type of the given generic type, or None if there is none.
+    fn type_offset(&self, types: &[ConcreteTypeId], generic_ty: &GenericTypeId) -> Option<usize> {
+        let index =
+            types.iter().position(|ty| self.get_info(ty).long_id.generic_id == *generic_ty)?;
+        Some(self.types_size(&types[..index]))
+    }
+}
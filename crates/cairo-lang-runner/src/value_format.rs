@@ -0,0 +1,218 @@
+//! Formatting of the values returned by a run, according to their Sierra types.
+use cairo_felt::Felt252;
+use cairo_lang_defs::ids::TopLevelLanguageElementId;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_sierra::extensions::core::CoreTypeConcrete;
+use cairo_lang_sierra::ids::ConcreteTypeId;
+use cairo_lang_sierra::program::{Function, GenericArg};
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
+use num_traits::ToPrimitive;
+
+use crate::SierraCasmRunner;
+
+/// The names of the members of user types by the path of the type - the fields of structs and the
+/// variants of enums. Sierra programs only hold the types of the members.
+pub type UserTypeMemberNames = OrderedHashMap<String, Vec<String>>;
+
+/// Returns the names of the members of all the structs and enums of the crates in the db.
+pub fn user_type_member_names(db: &dyn SemanticGroup) -> UserTypeMemberNames {
+    let mut names = UserTypeMemberNames::default();
+    for crate_id in db.crates() {
+        for module_id in db.crate_modules(crate_id).iter() {
+            for struct_id in db.module_structs_ids(*module_id).unwrap_or_default().iter() {
+                if let Ok(members) = db.struct_members(*struct_id) {
+                    names.insert(
+                        struct_id.full_path(db.upcast()),
+                        members.keys().map(|name| name.to_string()).collect(),
+                    );
+                }
+            }
+            for enum_id in db.module_enums_ids(*module_id).unwrap_or_default().iter() {
+                if let Ok(variants) = db.enum_variants(*enum_id) {
+                    names.insert(
+                        enum_id.full_path(db.upcast()),
+                        variants.keys().map(|name| name.to_string()).collect(),
+                    );
+                }
+            }
+        }
+    }
+    names
+}
+
+impl SierraCasmRunner {
+    /// Formats the value returned by a successful run of `func`, as returned in
+    /// [crate::RunResultValue::Success], according to its type: structs with their field names,
+    /// enums with their variant names, and arrays with their elements read from the `memory` of
+    /// the run.
+    /// Returns None if the value does not match its type, or holds a type that cannot be formatted.
+    pub fn format_return_value(
+        &self,
+        func: &Function,
+        values: &[Felt252],
+        memory: &[Option<Felt252>],
+        member_names: &UserTypeMemberNames,
+    ) -> Option<String> {
+        let mut ty = func.signature.ret_types.last()?;
+        if let Some(inner_ty) = self.panic_result_inner_type(ty) {
+            // Functions that may panic return their value wrapped in a single member tuple.
+            ty = match self.sierra_program_registry.get_type(inner_ty).ok()? {
+                CoreTypeConcrete::Struct(struct_type) => match &struct_type.members[..] {
+                    [member_ty] => member_ty,
+                    _ => inner_ty,
+                },
+                _ => inner_ty,
+            };
+        }
+        if self.type_sizes.get(ty).copied()? as usize != values.len() {
+            return None;
+        }
+        ValueFormatter { runner: self, memory, member_names }.format(ty, values)
+    }
+}
+
+struct ValueFormatter<'a> {
+    runner: &'a SierraCasmRunner,
+    memory: &'a [Option<Felt252>],
+    member_names: &'a UserTypeMemberNames,
+}
+
+impl ValueFormatter<'_> {
+    /// Formats a value of type `ty`, held in `values`.
+    fn format(&self, ty: &ConcreteTypeId, values: &[Felt252]) -> Option<String> {
+        Some(match self.runner.sierra_program_registry.get_type(ty).ok()? {
+            CoreTypeConcrete::Felt252(_)
+            | CoreTypeConcrete::Uint8(_)
+            | CoreTypeConcrete::Uint16(_)
+            | CoreTypeConcrete::Uint32(_)
+            | CoreTypeConcrete::Uint64(_)
+            | CoreTypeConcrete::Uint128(_) => values.first()?.to_string(),
+            CoreTypeConcrete::Sint8(_)
+            | CoreTypeConcrete::Sint16(_)
+            | CoreTypeConcrete::Sint32(_)
+            | CoreTypeConcrete::Sint64(_)
+            | CoreTypeConcrete::Sint128(_) => values.first()?.to_signed_felt().to_string(),
+            CoreTypeConcrete::Snapshot(info) => self.format(&info.ty, values)?,
+            CoreTypeConcrete::Box(info) => {
+                self.format(&info.ty, &self.read(values.first()?, &info.ty)?)?
+            }
+            CoreTypeConcrete::Nullable(info) => {
+                if *values.first()? == Felt252::from(0) {
+                    "null".into()
+                } else {
+                    self.format(&info.ty, &self.read(values.first()?, &info.ty)?)?
+                }
+            }
+            CoreTypeConcrete::Array(info) => {
+                let [start, end] = values else { return None };
+                let element_size = self.size(&info.ty)?;
+                let elements = self.read_range(start.to_usize()?, end.to_usize()?)?;
+                let elements = if element_size == 0 {
+                    vec![]
+                } else {
+                    elements
+                        .chunks(element_size)
+                        .map(|element| self.format(&info.ty, element))
+                        .collect::<Option<Vec<_>>>()?
+                };
+                format!("[{}]", elements.join(", "))
+            }
+            CoreTypeConcrete::Struct(struct_type) => {
+                let mut offset = 0;
+                let mut members = vec![];
+                for member_ty in &struct_type.members {
+                    let size = self.size(member_ty)?;
+                    members.push(self.format(member_ty, values.get(offset..offset + size)?)?);
+                    offset += size;
+                }
+                let name = user_type_name(&struct_type.info.long_id.generic_args)?;
+                if name == "Tuple" {
+                    return Some(match &members[..] {
+                        [member] => format!("({member},)"),
+                        _ => format!("({})", members.join(", ")),
+                    });
+                }
+                let short_name = short_type_name(name);
+                if members.is_empty() {
+                    short_name.into()
+                } else if let Some(field_names) = self.member_names_of(name, members.len()) {
+                    let fields = field_names
+                        .iter()
+                        .zip(members)
+                        .map(|(field_name, member)| format!("{field_name}: {member}"))
+                        .join(", ");
+                    format!("{short_name} {{ {fields} }}")
+                } else {
+                    format!("{short_name}({})", members.join(", "))
+                }
+            }
+            CoreTypeConcrete::Enum(enum_type) => {
+                let num_variants = enum_type.variants.len();
+                let selector = values.first()?.to_usize()?;
+                // Enums with more than two variants use the relative jump to their variant's
+                // branch in `enum_match` as the variant selector.
+                let index =
+                    if num_variants <= 2 { selector } else { num_variants - selector.div_ceil(2) };
+                let variant_ty = enum_type.variants.get(index)?;
+                // The variant's value is padded to the size of the enum from the start.
+                let variant_size = self.size(variant_ty)?;
+                let variant_value =
+                    self.format(variant_ty, values.get(values.len() - variant_size..)?)?;
+                let name = user_type_name(&enum_type.info.long_id.generic_args)?;
+                if name == "core::bool" {
+                    return Some((index != 0).to_string());
+                }
+                let variant_name = match self.member_names_of(name, num_variants) {
+                    Some(variant_names) => variant_names[index].clone(),
+                    None => index.to_string(),
+                };
+                let short_name = short_type_name(name);
+                if variant_value == "()" {
+                    format!("{short_name}::{variant_name}")
+                } else {
+                    format!("{short_name}::{variant_name}({variant_value})")
+                }
+            }
+            _ => return None,
+        })
+    }
+
+    /// Returns the size of a type, in memory cells.
+    fn size(&self, ty: &ConcreteTypeId) -> Option<usize> {
+        self.runner.type_sizes.get(ty).map(|size| *size as usize)
+    }
+
+    /// Reads a value of type `ty` from the memory, at the address `ptr`.
+    fn read(&self, ptr: &Felt252, ty: &ConcreteTypeId) -> Option<Vec<Felt252>> {
+        let start = ptr.to_usize()?;
+        self.read_range(start, start + self.size(ty)?)
+    }
+
+    /// Reads the memory cells in the range `start..end`, all of which must be initialized.
+    fn read_range(&self, start: usize, end: usize) -> Option<Vec<Felt252>> {
+        self.memory.get(start..end)?.iter().cloned().collect()
+    }
+
+    /// Returns the names of the members of a user type, if known and matching the number of its
+    /// members.
+    fn member_names_of(&self, name: &str, num_members: usize) -> Option<&Vec<String>> {
+        let base_name = name.split("::<").next().unwrap_or(name);
+        self.member_names.get(base_name).filter(|names| names.len() == num_members)
+    }
+}
+
+/// Returns the path of a struct or enum, with its generic arguments, from the generic arguments of
+/// its Sierra type.
+fn user_type_name(generic_args: &[GenericArg]) -> Option<&str> {
+    match generic_args.first()? {
+        GenericArg::UserType(user_type) => user_type.debug_name.as_deref(),
+        _ => None,
+    }
+}
+
+/// Returns the name of a type without the path of its module and its generic arguments.
+fn short_type_name(name: &str) -> &str {
+    let base_name = name.split("::<").next().unwrap_or(name);
+    base_name.rsplit("::").next().unwrap_or(base_name)
+}
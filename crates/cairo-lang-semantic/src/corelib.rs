@@ -391,6 +391,7 @@ pub fn core_binary_operator(
         BinaryOperator::GE(_) => ("PartialOrd", "ge", false),
         BinaryOperator::LT(_) => ("PartialOrd", "lt", false),
         BinaryOperator::GT(_) => ("PartialOrd", "gt", false),
+        BinaryOperator::DotDot(_) => ("RangeOp", "range", false),
         BinaryOperator::And(_) => ("BitAnd", "bitand", false),
         BinaryOperator::Or(_) => ("BitOr", "bitor", false),
         BinaryOperator::Xor(_) => ("BitXor", "bitxor", false),
@@ -17,6 +17,7 @@ use cairo_lang_syntax::node::ast::{self, TraitItemFunction};
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
 use cairo_lang_utils::Upcast;
+use num_bigint::BigInt;
 use smol_str::SmolStr;
 
 use crate::diagnostic::SemanticDiagnosticKind;
@@ -240,6 +241,10 @@ pub trait SemanticGroup:
     /// Returns the semantic model of a variant.
     #[salsa::invoke(items::enm::variant_semantic)]
     fn variant_semantic(&self, enum_id: EnumId, variant_id: VariantId) -> Maybe<semantic::Variant>;
+    /// Returns the discriminants of the variants of an enum, in the order of the variants, or None
+    /// if the enum is not C-like - i.e. some of its variants have data.
+    #[salsa::invoke(items::enm::enum_discriminants)]
+    fn enum_discriminants(&self, enum_id: EnumId) -> Maybe<Option<Vec<BigInt>>>;
     /// Returns the resolution resolved_items of an enum definition.
     #[salsa::invoke(items::enm::enum_definition_resolver_data)]
     fn enum_definition_resolver_data(&self, enum_id: EnumId) -> Maybe<Arc<ResolverData>>;
@@ -534,6 +539,13 @@ pub trait SemanticGroup:
         &self,
         impl_def_id: ImplDefId,
     ) -> Diagnostics<SemanticDiagnostic>;
+    /// Returns the impls of a trait that conflict with impls of the same trait in other crates,
+    /// each mapped to the impls it conflicts with.
+    #[salsa::invoke(items::imp::trait_conflicting_impls)]
+    fn trait_conflicting_impls(
+        &self,
+        trait_id: TraitId,
+    ) -> Arc<OrderedHashMap<ImplDefId, Vec<ImplDefId>>>;
     /// Returns the functions in the impl.
     #[salsa::invoke(items::imp::impl_functions)]
     fn impl_functions(
@@ -773,6 +785,13 @@ pub trait SemanticGroup:
     /// Returns the body of a function (with a body).
     #[salsa::invoke(items::function_with_body::function_body)]
     fn function_body(&self, function_id: FunctionWithBodyId) -> Maybe<Arc<FunctionBody>>;
+    /// Returns the function calls in the body of a function (with a body), along with the
+    /// expressions making them.
+    #[salsa::invoke(items::function_with_body::function_body_calls)]
+    fn function_body_calls(
+        &self,
+        function_id: FunctionWithBodyId,
+    ) -> Maybe<Arc<Vec<(FunctionId, ast::ExprPtr)>>>;
 
     // Extern function.
     // ================
@@ -1008,6 +1027,16 @@ pub trait SemanticGroup:
         crate_id: CrateId,
         type_filter: lsp_helpers::TypeFilter,
     ) -> Arc<Vec<TraitFunctionId>>;
+    /// Returns the crates whose items may be used from the given crate - the crate itself, the
+    /// corelib, and its dependencies, direct and transitive, as the items of transitive
+    /// dependencies may reach the crate through the direct ones. Returns all the crates if any of
+    /// these does not declare its dependencies.
+    #[salsa::invoke(lsp_helpers::visible_crates)]
+    fn visible_crates(&self, crate_id: CrateId) -> Arc<Vec<CrateId>>;
+    /// Returns the impls of a crate, indexed by the traits they implement and by the types they
+    /// implement them for.
+    #[salsa::invoke(lsp_helpers::impl_index_in_crate)]
+    fn impl_index_in_crate(&self, crate_id: CrateId) -> Arc<lsp_helpers::ImplIndex>;
 }
 
 impl<T: Upcast<dyn SemanticGroup + 'static>> Elongate for T {
@@ -14,6 +14,7 @@ use cairo_lang_filesystem::ids::FileId;
 use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
 use cairo_lang_syntax::node::TypedSyntaxNode;
 use itertools::Itertools;
+use num_bigint::BigInt;
 use smol_str::SmolStr;
 
 use crate::corelib::LiteralError;
@@ -134,6 +135,14 @@ impl DiagnosticEntry for SemanticDiagnostic {
             SemanticDiagnosticKind::ImplAliasCycle => {
                 "Cycle detected while resolving 'impls alias' items.".into()
             }
+            SemanticDiagnosticKind::ConflictingImpls { trait_id, other_impl_def_id } => {
+                format!(
+                    "Conflicting implementations of trait `{}`. Impl `{}` of another crate \
+                     implements it for overlapping types.",
+                    trait_id.full_path(db.upcast()),
+                    other_impl_def_id.full_path(db.upcast())
+                )
+            }
             SemanticDiagnosticKind::ImplRequirementCycle => {
                 "Cycle detected while resolving generic param. Try specifying the generic impl \
                  parameter explicitly to break the cycle."
@@ -196,15 +205,6 @@ impl DiagnosticEntry for SemanticDiagnostic {
                     function_id.name(defs_db),
                 )
             }
-            SemanticDiagnosticKind::TraitFunctionWithBody { trait_id, function_id } => {
-                let defs_db = db.upcast();
-                format!(
-                    "Trait function `{}::{}` has a body. Trait functions with body are not \
-                     supported.",
-                    trait_id.name(defs_db),
-                    function_id.name(defs_db),
-                )
-            }
             SemanticDiagnosticKind::ParameterShouldBeReference {
                 impl_def_id,
                 impl_function_id,
@@ -327,6 +327,26 @@ impl DiagnosticEntry for SemanticDiagnostic {
                     enum_id.full_path(db.upcast())
                 )
             }
+            SemanticDiagnosticKind::EnumDiscriminantNotLiteral => {
+                "Enum discriminants must be felt252 number literals.".into()
+            }
+            SemanticDiagnosticKind::EnumDiscriminantWithDataVariants => {
+                "Explicit discriminants are only supported in enums whose variants have no data."
+                    .into()
+            }
+            SemanticDiagnosticKind::DuplicateEnumDiscriminant { enum_id, value } => {
+                format!(
+                    r#"Discriminant {value} is already used by another variant of enum "{}"."#,
+                    enum_id.full_path(db.upcast())
+                )
+            }
+            SemanticDiagnosticKind::ImplicitEnumDiscriminantOutOfRange { enum_id, value } => {
+                format!(
+                    "The implicit discriminant {value} of a variant of enum \"{}\" does not fit \
+                     within the range of type core::felt252.",
+                    enum_id.full_path(db.upcast())
+                )
+            }
             SemanticDiagnosticKind::ParamNameRedefinition { function_title_id, param_name } => {
                 format!(
                     r#"Redefinition of parameter name "{param_name}" in function "{}"."#,
@@ -336,6 +356,9 @@ impl DiagnosticEntry for SemanticDiagnostic {
             SemanticDiagnosticKind::IfConditionNotBool { condition_ty } => {
                 format!(r#"If condition has type "{}", expected bool."#, condition_ty.format(db))
             }
+            SemanticDiagnosticKind::WhileConditionNotBool { condition_ty } => {
+                format!(r#"While condition has type "{}", expected bool."#, condition_ty.format(db))
+            }
             SemanticDiagnosticKind::IncompatibleMatchArms { match_ty, arm_ty } => format!(
                 r#"Match arms have incompatible types: "{}" and "{}""#,
                 match_ty.format(db),
@@ -543,7 +566,21 @@ impl DiagnosticEntry for SemanticDiagnostic {
                     ty.format(db)
                 )
             }
+            SemanticDiagnosticKind::NoImplementationOfIterator { ty, inference_errors } => {
+                if inference_errors.is_empty() {
+                    format!("Type `{}` does not implement the `Iterator` trait.", ty.format(db))
+                } else {
+                    format!(
+                        "Type `{}` could not be iterated over.\n{}",
+                        ty.format(db),
+                        inference_errors.format(db)
+                    )
+                }
+            }
 
+            SemanticDiagnosticKind::RefutablePatternInForLoop => {
+                "Refutable patterns are not supported in for loops.".into()
+            }
             SemanticDiagnosticKind::UnsupportedInlineArguments => {
                 "Unsupported `inline` arguments.".into()
             }
@@ -583,6 +620,9 @@ impl DiagnosticEntry for SemanticDiagnostic {
             SemanticDiagnosticKind::BreakOnlyAllowedInsideALoop => {
                 "`break` only allowed inside a `loop`.".into()
             }
+            SemanticDiagnosticKind::BreakWithValueOnlyAllowedInsideALoop => {
+                "Can only break with a value inside a `loop`.".into()
+            }
             SemanticDiagnosticKind::ReturnNotAllowedInsideALoop => {
                 "`return` not allowed inside a `loop`.".into()
             }
@@ -687,6 +727,10 @@ pub enum SemanticDiagnosticKind {
     TypeAliasCycle,
     ImplAliasCycle,
     ImplRequirementCycle,
+    ConflictingImpls {
+        trait_id: TraitId,
+        other_impl_def_id: ImplDefId,
+    },
     MissingMember {
         member_name: SmolStr,
     },
@@ -713,10 +757,6 @@ pub enum SemanticDiagnosticKind {
         trait_id: TraitId,
         function_id: TraitFunctionId,
     },
-    TraitFunctionWithBody {
-        trait_id: TraitId,
-        function_id: TraitFunctionId,
-    },
     ParameterShouldBeReference {
         impl_def_id: ImplDefId,
         impl_function_id: ImplFunctionId,
@@ -771,6 +811,16 @@ pub enum SemanticDiagnosticKind {
         enum_id: EnumId,
         variant_name: SmolStr,
     },
+    EnumDiscriminantNotLiteral,
+    EnumDiscriminantWithDataVariants,
+    DuplicateEnumDiscriminant {
+        enum_id: EnumId,
+        value: BigInt,
+    },
+    ImplicitEnumDiscriminantOutOfRange {
+        enum_id: EnumId,
+        value: BigInt,
+    },
     ParamNameRedefinition {
         function_title_id: FunctionTitleId,
         param_name: SmolStr,
@@ -778,6 +828,9 @@ pub enum SemanticDiagnosticKind {
     IfConditionNotBool {
         condition_ty: semantic::TypeId,
     },
+    WhileConditionNotBool {
+        condition_ty: semantic::TypeId,
+    },
     IncompatibleMatchArms {
         match_ty: semantic::TypeId,
         arm_ty: semantic::TypeId,
@@ -904,6 +957,11 @@ pub enum SemanticDiagnosticKind {
         inference_errors: TraitInferenceErrors,
     },
     MultipleImplementationOfIndexOperator(semantic::TypeId),
+    NoImplementationOfIterator {
+        ty: semantic::TypeId,
+        inference_errors: TraitInferenceErrors,
+    },
+    RefutablePatternInForLoop,
     UnsupportedInlineArguments,
     RedundantInlineAttribute,
     InlineAttrForExternFunctionNotAllowed,
@@ -911,6 +969,7 @@ pub enum SemanticDiagnosticKind {
     TailExpressionNotAllowedInLoop,
     ContinueOnlyAllowedInsideALoop,
     BreakOnlyAllowedInsideALoop,
+    BreakWithValueOnlyAllowedInsideALoop,
     ReturnNotAllowedInsideALoop,
     ErrorPropagateNotAllowedInsideALoop,
     ImplicitPrecedenceAttrForExternFunctionNotAllowed,
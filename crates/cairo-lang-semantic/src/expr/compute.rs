@@ -23,7 +23,9 @@ use cairo_lang_utils::ordered_hash_map::{Entry, OrderedHashMap};
 use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
 use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
 use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
-use cairo_lang_utils::{extract_matches, try_extract_matches, OptionHelper};
+use cairo_lang_utils::{
+    ensure_sufficient_stack, extract_matches, try_extract_matches, OptionHelper,
+};
 use id_arena::Arena;
 use itertools::{chain, zip_eq};
 use num_bigint::BigInt;
@@ -35,8 +37,8 @@ use super::inference::infers::InferenceEmbeddings;
 use super::inference::{Inference, InferenceError};
 use super::objects::*;
 use super::pattern::{
-    Pattern, PatternEnumVariant, PatternLiteral, PatternMissing, PatternOtherwise, PatternTuple,
-    PatternVariable,
+    Pattern, PatternBinding, PatternEnumVariant, PatternLiteral, PatternMissing, PatternOtherwise,
+    PatternTuple, PatternVariable,
 };
 use crate::corelib::{
     core_binary_operator, core_bool_ty, core_unary_operator, false_literal_expr, get_core_trait,
@@ -109,7 +111,7 @@ pub struct ComputationContext<'ctx> {
     pub statements: Arena<semantic::Statement>,
     /// Definitions of semantic variables.
     pub semantic_defs: UnorderedHashMap<semantic::VarId, semantic::Variable>,
-    loop_flow_merge: Option<FlowMergeTypeHelper>,
+    loop_ctx: Option<LoopContext>,
 }
 impl<'ctx> ComputationContext<'ctx> {
     pub fn new(
@@ -133,7 +135,7 @@ impl<'ctx> ComputationContext<'ctx> {
             patterns: Arena::default(),
             statements: Arena::default(),
             semantic_defs,
-            loop_flow_merge: None,
+            loop_ctx: None,
         }
     }
 
@@ -251,6 +253,14 @@ fn wrap_maybe_with_missing(
 pub fn maybe_compute_expr_semantic(
     ctx: &mut ComputationContext<'_>,
     syntax: &ast::Expr,
+) -> Maybe<Expr> {
+    ensure_sufficient_stack(|| maybe_compute_expr_semantic_inner(ctx, syntax))
+}
+
+/// Same as [maybe_compute_expr_semantic], without growing the stack.
+fn maybe_compute_expr_semantic_inner(
+    ctx: &mut ComputationContext<'_>,
+    syntax: &ast::Expr,
 ) -> Maybe<Expr> {
     let db = ctx.db;
     let syntax_db = db.upcast();
@@ -282,6 +292,8 @@ pub fn maybe_compute_expr_semantic(
         ast::Expr::Match(expr_match) => compute_expr_match_semantic(ctx, expr_match),
         ast::Expr::If(expr_if) => compute_expr_if_semantic(ctx, expr_if),
         ast::Expr::Loop(expr_loop) => compute_expr_loop_semantic(ctx, expr_loop),
+        ast::Expr::While(expr_while) => compute_expr_while_semantic(ctx, expr_while),
+        ast::Expr::For(expr_for) => compute_expr_for_semantic(ctx, expr_for),
         ast::Expr::ErrorPropagate(expr) => compute_expr_error_propagate_semantic(ctx, expr),
         ast::Expr::InlineMacro(expr) => compute_expr_inline_macro_semantic(ctx, expr),
         ast::Expr::Missing(_) | ast::Expr::FieldInitShorthand(_) => {
@@ -750,7 +762,17 @@ pub fn compute_expr_block_semantic(
     })
 }
 
+/// The context of the innermost loop containing the computed expression.
+#[derive(Debug)]
+enum LoopContext {
+    /// Inside a `loop` - merges the types of its `break` statements.
+    Loop(FlowMergeTypeHelper),
+    /// Inside a `while` or a `for` loop - its `break` statements may not have values.
+    While,
+}
+
 /// Helper for merging the return types of branch blocks (match or if else).
+#[derive(Debug)]
 struct FlowMergeTypeHelper {
     never_type: TypeId,
     final_type: Option<TypeId>,
@@ -868,7 +890,8 @@ fn compute_expr_if_semantic(ctx: &mut ComputationContext<'_>, syntax: &ast::Expr
                     (Some(else_block.clone()), else_block.ty())
                 }
                 BlockOrIf::If(expr_if) => {
-                    let else_if = compute_expr_if_semantic(ctx, &expr_if)?;
+                    let else_if =
+                        ensure_sufficient_stack(|| compute_expr_if_semantic(ctx, &expr_if))?;
                     (Some(else_if.clone()), else_if.ty())
                 }
             }
@@ -899,52 +922,173 @@ fn compute_expr_loop_semantic(
     let db = ctx.db;
     let syntax_db = db.upcast();
 
-    let (body, new_flow_merge) = ctx.run_in_subscope(|new_ctx| {
-        let old_flow_merge = new_ctx.loop_flow_merge.replace(FlowMergeTypeHelper::new(db));
+    let (body, loop_ctx) = ctx.run_in_subscope(|new_ctx| {
+        let old_loop_ctx =
+            new_ctx.loop_ctx.replace(LoopContext::Loop(FlowMergeTypeHelper::new(db)));
+        let body = compute_loop_body_semantic(
+            new_ctx,
+            &syntax.body(syntax_db),
+            syntax.stable_ptr().into(),
+        );
+        let loop_ctx = std::mem::replace(&mut new_ctx.loop_ctx, old_loop_ctx).unwrap();
+        (body, loop_ctx)
+    });
+    let flow_merge = extract_matches!(loop_ctx, LoopContext::Loop);
 
-        let mut statements = syntax.body(syntax_db).statements(syntax_db).elements(syntax_db);
-        // Remove the typed tail expression, if exists.
-        let tail = get_tail_expression(syntax_db, statements.as_slice());
-        if tail.is_some() {
-            statements.pop();
-        }
+    Ok(Expr::Loop(ExprLoop {
+        body,
+        ty: flow_merge.get_final_type(),
+        stable_ptr: syntax.stable_ptr().into(),
+    }))
+}
 
-        // Convert statements to semantic model.
-        let statements_semantic: Vec<_> = statements
-            .into_iter()
-            .filter_map(|statement_syntax| {
-                compute_statement_semantic(new_ctx, statement_syntax).to_option()
-            })
-            .collect();
-        let tail = tail.map(|tail| compute_expr_semantic(new_ctx, &tail));
-        if let Some(tail) = &tail {
-            if !tail.ty().is_missing(db) && !tail.ty().is_unit(db) && tail.ty() != never_ty(db) {
-                new_ctx
-                    .diagnostics
-                    .report_by_ptr(tail.stable_ptr().untyped(), TailExpressionNotAllowedInLoop);
-            }
+/// Computes the semantic model of an expression of type [ast::ExprWhile].
+fn compute_expr_while_semantic(
+    ctx: &mut ComputationContext<'_>,
+    syntax: &ast::ExprWhile,
+) -> Maybe<Expr> {
+    let db = ctx.db;
+    let syntax_db = db.upcast();
+
+    // The condition is evaluated at the start of every iteration, so it is a part of the loop.
+    let (condition, body) = ctx.run_in_subscope(|new_ctx| {
+        let old_loop_ctx = new_ctx.loop_ctx.replace(LoopContext::While);
+        let condition = compute_expr_semantic(new_ctx, &syntax.condition(syntax_db));
+        if new_ctx.resolver.inference().conform_ty(condition.ty(), core_bool_ty(db)).is_err() {
+            new_ctx.diagnostics.report_by_ptr(
+                condition.stable_ptr().untyped(),
+                WhileConditionNotBool { condition_ty: condition.ty() },
+            );
         }
+        let body = syntax.body(syntax_db);
+        let body = compute_loop_body_semantic(new_ctx, &body, body.stable_ptr().into());
+        new_ctx.loop_ctx = old_loop_ctx;
+        (condition.id, body)
+    });
 
-        let new_flow_merge =
-            std::mem::replace(&mut new_ctx.loop_flow_merge, old_flow_merge).unwrap();
+    Ok(Expr::While(ExprWhile {
+        condition,
+        body,
+        ty: unit_ty(db),
+        stable_ptr: syntax.stable_ptr().into(),
+    }))
+}
 
-        let body = new_ctx.exprs.alloc(Expr::Block(ExprBlock {
-            statements: statements_semantic,
-            tail: tail.map(|tail| tail.id),
-            ty: unit_ty(db),
-            stable_ptr: syntax.stable_ptr().into(),
-        }));
+/// Computes the semantic model of an expression of type [ast::ExprFor].
+fn compute_expr_for_semantic(
+    ctx: &mut ComputationContext<'_>,
+    syntax: &ast::ExprFor,
+) -> Maybe<Expr> {
+    let db = ctx.db;
+    let syntax_db = db.upcast();
 
-        (body, new_flow_merge)
+    let expr_syntax = syntax.expr(syntax_db);
+    let expr = compute_expr_semantic(ctx, &expr_syntax);
+    ctx.reduce_ty(expr.ty()).check_not_missing(db)?;
+    let iterator_trait = get_core_trait(db, "Iterator".into());
+    let (next_function_id, iterator_expr, _) = compute_method_function_call_data(
+        ctx,
+        &[iterator_trait],
+        "next".into(),
+        expr,
+        expr_syntax.stable_ptr().untyped(),
+        None,
+        |ty, _, inference_errors| NoImplementationOfIterator { ty, inference_errors },
+        |_, _, _| unreachable!("`Iterator` is the only candidate trait."),
+    )?;
+    // The type of the items is only known once the impl of the iterator is inferred.
+    ctx.resolver.inference().solve().ok();
+    let next_return_ty =
+        ctx.reduce_ty(db.concrete_function_signature(next_function_id)?.return_type);
+    let (some_variant, _) = unwrap_error_propagation_type(db, next_return_ty)
+        .expect("`Iterator::next` must return an `Option`.");
+
+    // The iterator is held by a variable with no name, identified by the `in` of the loop.
+    let iterator_var = LocalVariable {
+        id: db.intern_local_var(LocalVarLongId(
+            ctx.resolver.module_file_id,
+            syntax.identifier(syntax_db).stable_ptr(),
+        )),
+        ty: iterator_expr.ty(),
+        is_mut: true,
+    };
+    let iterator_var = Variable::Local(iterator_var);
+    let iterator_member_path = ExprVarMemberPath::Var(ExprVar {
+        var: iterator_var.id(),
+        ty: iterator_expr.ty(),
+        stable_ptr: expr_syntax.stable_ptr(),
+    });
+    ctx.semantic_defs.insert(iterator_var.id(), iterator_var);
+
+    let (pattern, body) = ctx.run_in_subscope(|new_ctx| {
+        let old_loop_ctx = new_ctx.loop_ctx.replace(LoopContext::While);
+        let pattern_syntax = syntax.pattern(syntax_db);
+        let pattern = compute_pattern_semantic(new_ctx, &pattern_syntax, some_variant.ty);
+        // Every item is bound by the pattern, as there is no other branch to take.
+        if pattern.is_refutable(&new_ctx.patterns) {
+            new_ctx.diagnostics.report(&pattern_syntax, RefutablePatternInForLoop);
+        }
+        for v in pattern.variables(&new_ctx.patterns) {
+            let var_def = Variable::Local(v.var.clone());
+            new_ctx.environment.variables.insert(v.name.clone(), var_def.clone());
+            new_ctx.semantic_defs.insert(var_def.id(), var_def);
+        }
+        let body = syntax.body(syntax_db);
+        let body = compute_loop_body_semantic(new_ctx, &body, body.stable_ptr().into());
+        new_ctx.loop_ctx = old_loop_ctx;
+        (pattern.id, body)
     });
 
-    Ok(Expr::Loop(ExprLoop {
+    Ok(Expr::For(ExprFor {
+        expr_id: iterator_expr.id,
+        iterator_var: iterator_member_path,
+        next_function_id,
+        pattern,
         body,
-        ty: new_flow_merge.get_final_type(),
+        ty: unit_ty(db),
         stable_ptr: syntax.stable_ptr().into(),
     }))
 }
 
+/// Computes the semantic model of the body of a loop, in the context of the loop.
+fn compute_loop_body_semantic(
+    ctx: &mut ComputationContext<'_>,
+    syntax: &ast::ExprBlock,
+    stable_ptr: ast::ExprPtr,
+) -> ExprId {
+    let db = ctx.db;
+    let syntax_db = db.upcast();
+
+    let mut statements = syntax.statements(syntax_db).elements(syntax_db);
+    // Remove the typed tail expression, if exists.
+    let tail = get_tail_expression(syntax_db, statements.as_slice());
+    if tail.is_some() {
+        statements.pop();
+    }
+
+    // Convert statements to semantic model.
+    let statements_semantic: Vec<_> = statements
+        .into_iter()
+        .filter_map(|statement_syntax| {
+            compute_statement_semantic(ctx, statement_syntax).to_option()
+        })
+        .collect();
+    let tail = tail.map(|tail| compute_expr_semantic(ctx, &tail));
+    if let Some(tail) = &tail {
+        if !tail.ty().is_missing(db) && !tail.ty().is_unit(db) && tail.ty() != never_ty(db) {
+            ctx.diagnostics
+                .report_by_ptr(tail.stable_ptr().untyped(), TailExpressionNotAllowedInLoop);
+        }
+    }
+
+    ctx.exprs.alloc(Expr::Block(ExprBlock {
+        statements: statements_semantic,
+        tail: tail.map(|tail| tail.id),
+        ty: unit_ty(db),
+        stable_ptr,
+    }))
+}
+
 /// Computes the semantic model of an expression of type [ast::ExprErrorPropagate].
 fn compute_expr_error_propagate_semantic(
     ctx: &mut ComputationContext<'_>,
@@ -963,7 +1107,7 @@ fn compute_expr_error_propagate_semantic(
         UnsupportedOutsideOfFunctionFeatureName::ErrorPropagate,
     )?;
     // Disallow error propagation inside a loop.
-    if ctx.loop_flow_merge.is_some() {
+    if ctx.loop_ctx.is_some() {
         ctx.diagnostics.report(syntax, SemanticDiagnosticKind::ErrorPropagateNotAllowedInsideALoop);
     }
     let (_, func_err_variant) = unwrap_error_propagation_type(ctx.db, func_signature.return_type)
@@ -1229,15 +1373,46 @@ fn maybe_compute_pattern_semantic(
             }
             // TODO(spapini): Make sure this is a simple identifier. In particular, no generics.
             let identifier = path.elements(syntax_db)[0].identifier_ast(syntax_db);
-            create_variable_pattern(ctx, identifier, &[], ty, path.stable_ptr().into())
+            Pattern::Variable(create_variable_pattern(
+                ctx,
+                identifier,
+                &[],
+                ty,
+                path.stable_ptr().into(),
+            ))
         }
-        ast::Pattern::Identifier(identifier) => create_variable_pattern(
+        ast::Pattern::Identifier(identifier) => Pattern::Variable(create_variable_pattern(
             ctx,
             identifier.name(syntax_db),
             &identifier.modifiers(syntax_db).elements(syntax_db),
             ty,
             identifier.stable_ptr().into(),
-        ),
+        )),
+        ast::Pattern::Binding(pattern_binding) => {
+            let binding = pattern_binding.binding(syntax_db);
+            // The matched value is also matched by the inner pattern, so the variable is bound to a
+            // snapshot of it, unless it is a snapshot already.
+            let ty = ctx.reduce_ty(ty);
+            let binding_ty = match ctx.db.lookup_intern_type(ty) {
+                TypeLongId::Snapshot(_) => ty,
+                _ => wrap_in_snapshots(ctx.db, ty, 1),
+            };
+            let variable = create_variable_pattern(
+                ctx,
+                binding.name(syntax_db),
+                &binding.modifiers(syntax_db).elements(syntax_db),
+                binding_ty,
+                binding.stable_ptr().into(),
+            );
+            let inner_pattern =
+                compute_pattern_semantic(ctx, &pattern_binding.pattern(syntax_db), ty);
+            Pattern::Binding(PatternBinding {
+                variable,
+                inner_pattern: inner_pattern.id,
+                ty,
+                stable_ptr: pattern_binding.stable_ptr(),
+            })
+        }
         ast::Pattern::Struct(pattern_struct) => {
             let pattern_ty = try_extract_matches!(
                 ctx.resolver.resolve_concrete_path(
@@ -1299,13 +1474,13 @@ fn maybe_compute_pattern_semantic(
                             continue;
                         };
                         let ty = wrap_in_snapshots(ctx.db, member.ty, n_snapshots);
-                        let pattern = create_variable_pattern(
+                        let pattern = Pattern::Variable(create_variable_pattern(
                             ctx,
                             name,
                             &single.modifiers(syntax_db).elements(syntax_db),
                             ty,
                             single.stable_ptr().into(),
-                        );
+                        ));
                         field_patterns.push((member, ctx.patterns.alloc(pattern)));
                     }
                     PatternStructParam::WithExpr(with_expr) => {
@@ -1383,7 +1558,7 @@ fn create_variable_pattern(
     modifier_list: &[ast::Modifier],
     ty: TypeId,
     stable_ptr: ast::PatternPtr,
-) -> Pattern {
+) -> PatternVariable {
     let syntax_db = ctx.db.upcast();
     let var_id = ctx
         .db
@@ -1397,11 +1572,11 @@ fn create_variable_pattern(
             false
         }
     };
-    Pattern::Variable(PatternVariable {
+    PatternVariable {
         name: identifier.text(syntax_db),
         var: LocalVariable { id: var_id, ty, is_mut },
         stable_ptr,
-    })
+    }
 }
 
 /// Creates a struct constructor semantic expression from its AST.
@@ -2105,7 +2280,7 @@ pub fn compute_statement_semantic(
             })
         }
         ast::Statement::Continue(continue_syntax) => {
-            if ctx.loop_flow_merge.is_none() {
+            if ctx.loop_ctx.is_none() {
                 return Err(ctx
                     .diagnostics
                     .report(continue_syntax, ContinueOnlyAllowedInsideALoop));
@@ -2115,7 +2290,7 @@ pub fn compute_statement_semantic(
             })
         }
         ast::Statement::Return(return_syntax) => {
-            if ctx.loop_flow_merge.is_some() {
+            if ctx.loop_ctx.is_some() {
                 return Err(ctx.diagnostics.report(return_syntax, ReturnNotAllowedInsideALoop));
             }
 
@@ -2158,15 +2333,27 @@ pub fn compute_statement_semantic(
                     (Some(expr.id), expr.ty(), expr.stable_ptr().untyped())
                 }
             };
-            let Some(flow_merge) = ctx.loop_flow_merge.as_mut() else {
-                return Err(ctx.diagnostics.report(break_syntax, BreakOnlyAllowedInsideALoop));
-            };
-            if let Err((current_ty, break_ty)) =
-                flow_merge.try_merge_types(&mut ctx.resolver.inference(), ctx.db, ty)
-            {
-                ctx.diagnostics
-                    .report_by_ptr(stable_ptr, IncompatibleLoopBreakTypes { current_ty, break_ty });
-            };
+            match ctx.loop_ctx.as_mut() {
+                None => {
+                    return Err(ctx.diagnostics.report(break_syntax, BreakOnlyAllowedInsideALoop));
+                }
+                Some(LoopContext::Loop(flow_merge)) => {
+                    if let Err((current_ty, break_ty)) =
+                        flow_merge.try_merge_types(&mut ctx.resolver.inference(), ctx.db, ty)
+                    {
+                        ctx.diagnostics.report_by_ptr(
+                            stable_ptr,
+                            IncompatibleLoopBreakTypes { current_ty, break_ty },
+                        );
+                    };
+                }
+                Some(LoopContext::While) => {
+                    if expr_option.is_some() {
+                        ctx.diagnostics
+                            .report_by_ptr(stable_ptr, BreakWithValueOnlyAllowedInsideALoop);
+                    }
+                }
+            }
             semantic::Statement::Break(semantic::StatementBreak {
                 expr_option,
                 stable_ptr: syntax.stable_ptr(),
@@ -168,6 +168,16 @@ impl InferenceError {
                          literal.",
                         generic_type.debug(db)
                     );
+                } else if trait_id == get_core_trait(db, "CLikeEnum".into()) {
+                    let generic_type = extract_matches!(
+                        concrete_trait_id.generic_args(db)[0],
+                        GenericArgumentId::Type
+                    );
+                    return format!(
+                        "Expected a C-like enum (an enum whose variants have no data), found \
+                         `{:?}`.",
+                        generic_type.debug(db)
+                    );
                 } else if trait_id == get_core_trait(db, "StringLiteral".into()) {
                     let generic_type = extract_matches!(
                         concrete_trait_id.generic_args(db)[0],
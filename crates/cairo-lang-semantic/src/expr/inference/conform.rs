@@ -234,7 +234,9 @@ impl<'db> InferenceConform for Inference<'db> {
                     generic_args,
                 })))
             }
-            ImplId::GenericParameter(_) => Err(InferenceError::ImplKindMismatch { impl0, impl1 }),
+            ImplId::GenericParameter(_) | ImplId::SelfImpl(_) => {
+                Err(InferenceError::ImplKindMismatch { impl0, impl1 })
+            }
         }
     }
 
@@ -308,7 +310,7 @@ impl<'db> InferenceConform for Inference<'db> {
                 &self.db.lookup_intern_concrete_impl(*concrete_impl_id).generic_args,
                 var,
             ),
-            ImplId::GenericParameter(_) => false,
+            ImplId::GenericParameter(_) | ImplId::SelfImpl(_) => false,
             ImplId::ImplVar(new_var) => {
                 if InferenceVar::Impl(new_var.get(self.db).id) == var {
                     return true;
@@ -111,6 +111,10 @@ impl<'db> InferenceEmbeddings for Inference<'db> {
                 self.conform_traits(concrete_trait_id, imp_concrete_trait_id)?;
                 ImplId::GenericParameter(param_id)
             }
+            UninferredImpl::SelfImpl(self_concrete_trait_id) => {
+                self.conform_traits(concrete_trait_id, self_concrete_trait_id)?;
+                ImplId::SelfImpl(self_concrete_trait_id)
+            }
         };
         Ok(impl_id)
     }
@@ -219,7 +223,7 @@ impl<'db> InferenceEmbeddings for Inference<'db> {
             let generic_arg =
                 self.infer_generic_arg(&generic_param, lookup_context.clone(), stable_ptr)?;
             generic_args.push(generic_arg);
-            substitution.0.insert(generic_param.id(), generic_arg);
+            substitution.param_map.insert(generic_param.id(), generic_arg);
         }
         Ok(generic_args)
     }
@@ -122,6 +122,8 @@ pub enum Expr {
     LogicalOperator(ExprLogicalOperator),
     Block(ExprBlock),
     Loop(ExprLoop),
+    While(ExprWhile),
+    For(ExprFor),
     FunctionCall(ExprFunctionCall),
     Match(ExprMatch),
     If(ExprIf),
@@ -145,6 +147,8 @@ impl Expr {
             Expr::LogicalOperator(expr) => expr.ty,
             Expr::Block(expr) => expr.ty,
             Expr::Loop(expr) => expr.ty,
+            Expr::While(expr) => expr.ty,
+            Expr::For(expr) => expr.ty,
             Expr::FunctionCall(expr) => expr.ty,
             Expr::Match(expr) => expr.ty,
             Expr::If(expr) => expr.ty,
@@ -168,6 +172,8 @@ impl Expr {
             Expr::LogicalOperator(expr) => expr.stable_ptr,
             Expr::Block(expr) => expr.stable_ptr,
             Expr::Loop(expr) => expr.stable_ptr,
+            Expr::While(expr) => expr.stable_ptr,
+            Expr::For(expr) => expr.stable_ptr,
             Expr::FunctionCall(expr) => expr.stable_ptr,
             Expr::Match(expr) => expr.stable_ptr,
             Expr::If(expr) => expr.stable_ptr,
@@ -247,6 +253,36 @@ pub struct ExprLoop {
     pub stable_ptr: ast::ExprPtr,
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq, DebugWithDb, SemanticObject)]
+#[debug_db(ExprFormatter<'a>)]
+pub struct ExprWhile {
+    pub condition: ExprId,
+    pub body: ExprId,
+    pub ty: semantic::TypeId,
+    #[hide_field_debug_with_db]
+    #[dont_rewrite]
+    pub stable_ptr: ast::ExprPtr,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, DebugWithDb, SemanticObject)]
+#[debug_db(ExprFormatter<'a>)]
+pub struct ExprFor {
+    /// The iterated expression, evaluated once before the loop.
+    pub expr_id: ExprId,
+    /// The variable holding the iterator throughout the loop.
+    #[hide_field_debug_with_db]
+    pub iterator_var: ExprVarMemberPath,
+    /// The `Iterator::next` function of the iterator.
+    pub next_function_id: FunctionId,
+    /// The pattern the items of the iterator are bound to.
+    pub pattern: PatternId,
+    pub body: ExprId,
+    pub ty: semantic::TypeId,
+    #[hide_field_debug_with_db]
+    #[dont_rewrite]
+    pub stable_ptr: ast::ExprPtr,
+}
+
 /// A sequence of member accesses of a variable. For example: a, a.b, a.b.c, ...
 #[derive(Clone, Debug, Hash, PartialEq, Eq, SemanticObject)]
 pub enum ExprVarMemberPath {
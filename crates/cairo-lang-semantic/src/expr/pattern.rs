@@ -24,6 +24,7 @@ pub enum Pattern {
     Struct(PatternStruct),
     Tuple(PatternTuple),
     EnumVariant(PatternEnumVariant),
+    Binding(PatternBinding),
     Otherwise(PatternOtherwise),
     Missing(PatternMissing),
 }
@@ -36,6 +37,7 @@ impl Pattern {
             Pattern::Struct(pattern_struct) => pattern_struct.ty,
             Pattern::Tuple(pattern_tuple) => pattern_tuple.ty,
             Pattern::EnumVariant(pattern_enum_variant) => pattern_enum_variant.ty,
+            Pattern::Binding(pattern_binding) => pattern_binding.ty,
             Pattern::Otherwise(pattern_otherwise) => pattern_otherwise.ty,
             Pattern::Missing(pattern_missing) => pattern_missing.ty,
         }
@@ -60,6 +62,11 @@ impl Pattern {
                     None => vec![],
                 }
             }
+            Pattern::Binding(pattern_binding) => {
+                let mut variables = vec![pattern_binding.variable.clone()];
+                variables.extend(arena[pattern_binding.inner_pattern].variables(arena));
+                variables
+            }
             Pattern::Literal(_)
             | Pattern::StringLiteral(_)
             | Pattern::Otherwise(_)
@@ -67,6 +74,26 @@ impl Pattern {
         }
     }
 
+    /// Returns whether the pattern may not match some values of its type, i.e. it matches a literal
+    /// or an enum variant.
+    pub fn is_refutable(&self, arena: &Arena<Pattern>) -> bool {
+        match self {
+            Pattern::Literal(_) | Pattern::StringLiteral(_) | Pattern::EnumVariant(_) => true,
+            Pattern::Struct(pattern_struct) => pattern_struct
+                .field_patterns
+                .iter()
+                .any(|(_member, pattern)| arena[*pattern].is_refutable(arena)),
+            Pattern::Tuple(pattern_tuple) => pattern_tuple
+                .field_patterns
+                .iter()
+                .any(|pattern| arena[*pattern].is_refutable(arena)),
+            Pattern::Binding(pattern_binding) => {
+                arena[pattern_binding.inner_pattern].is_refutable(arena)
+            }
+            Pattern::Variable(_) | Pattern::Otherwise(_) | Pattern::Missing(_) => false,
+        }
+    }
+
     pub fn stable_ptr(&self) -> ast::PatternPtr {
         match self {
             Pattern::Literal(pattern) => pattern.stable_ptr,
@@ -75,6 +102,7 @@ impl Pattern {
             Pattern::Struct(pattern) => pattern.stable_ptr.into(),
             Pattern::Tuple(pattern) => pattern.stable_ptr.into(),
             Pattern::EnumVariant(pattern) => pattern.stable_ptr.into(),
+            Pattern::Binding(pattern) => pattern.stable_ptr.into(),
             Pattern::Otherwise(pattern) => pattern.stable_ptr.into(),
             Pattern::Missing(pattern) => pattern.stable_ptr,
         }
@@ -152,6 +180,19 @@ pub struct PatternEnumVariant {
     pub stable_ptr: ast::PatternEnumPtr,
 }
 
+/// A pattern that binds the matched value to a variable, while also matching it against an inner
+/// pattern. For example, `x @ Some(y)`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, DebugWithDb, SemanticObject)]
+#[debug_db(ExprFormatter<'a>)]
+pub struct PatternBinding {
+    pub variable: PatternVariable,
+    pub inner_pattern: PatternId,
+    pub ty: semantic::TypeId,
+    #[hide_field_debug_with_db]
+    #[dont_rewrite]
+    pub stable_ptr: ast::PatternBindingPtr,
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq, DebugWithDb, SemanticObject)]
 #[debug_db(ExprFormatter<'a>)]
 pub struct PatternOtherwise {
@@ -39,6 +39,8 @@ cairo_lang_test_utils::test_file_test!(
         snapshot: "snapshot",
         statements: "statements",
         structure: "structure",
+        while_: "while",
+        for_: "for",
     },
     test_function_diagnostics
 );
@@ -223,3 +225,33 @@ fn test_function_body() {
     let param = extract_matches!(var, VarId::Param);
     assert_eq!(param.name(db), "a");
 }
+
+#[test]
+fn test_deeply_nested_expr() {
+    let db_val = SemanticDatabaseForTesting::default();
+    let nesting = 10000;
+    let (_, diagnostics) = setup_test_function(
+        &db_val,
+        &format!("fn foo() -> felt252 {{ {}0{} }}", "(".repeat(nesting), ")".repeat(nesting)),
+        "foo",
+        "",
+    )
+    .split();
+    assert!(diagnostics.contains("Expression is nested too deeply."));
+}
+
+#[test]
+fn test_long_flat_expr() {
+    let db_val = SemanticDatabaseForTesting::default();
+    let length = 2000;
+    for function in [
+        format!("fn foo(x: felt252) -> felt252 {{ {} }}", vec!["x"; length].join(" + ")),
+        format!(
+            "fn foo(x: felt252) -> felt252 {{ if x == 0 {{ 0 }}{} else {{ 1 }} }}",
+            " else if x == 1 { 1 }".repeat(length)
+        ),
+    ] {
+        let (_, diagnostics) = setup_test_function(&db_val, &function, "foo", "").split();
+        assert_eq!(diagnostics, "");
+    }
+}
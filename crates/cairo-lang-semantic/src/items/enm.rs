@@ -8,12 +8,15 @@ use cairo_lang_proc_macros::{DebugWithDb, SemanticObject};
 use cairo_lang_syntax::attribute::structured::{Attribute, AttributeListStructurize};
 use cairo_lang_syntax::node::{ast, Terminal, TypedSyntaxNode};
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
 use cairo_lang_utils::Upcast;
 use itertools::enumerate;
+use num_bigint::BigInt;
+use num_traits::Zero;
 use smol_str::SmolStr;
 
 use super::generics::{semantic_generic_params, GenericParamsData};
-use crate::corelib::unit_ty;
+use crate::corelib::{core_felt252_ty, unit_ty, validate_literal};
 use crate::db::SemanticGroup;
 use crate::diagnostic::SemanticDiagnosticKind::*;
 use crate::diagnostic::SemanticDiagnostics;
@@ -145,6 +148,7 @@ pub struct EnumDefinitionData {
     diagnostics: Diagnostics<SemanticDiagnostic>,
     variants: OrderedHashMap<SmolStr, VariantId>,
     variant_semantic: OrderedHashMap<VariantId, Variant>,
+    discriminants: Option<Vec<BigInt>>,
     resolver_data: Arc<ResolverData>,
 }
 
@@ -196,7 +200,8 @@ pub fn priv_enum_definition_data(
     // Variants.
     let mut variants = OrderedHashMap::default();
     let mut variant_semantic = OrderedHashMap::default();
-    for (variant_idx, variant) in enumerate(enum_ast.variants(syntax_db).elements(syntax_db)) {
+    let variant_asts = enum_ast.variants(syntax_db).elements(syntax_db);
+    for (variant_idx, variant) in enumerate(&variant_asts) {
         let id = db.intern_variant(VariantLongId(module_file_id, variant.stable_ptr()));
         let ty = match variant.type_clause(syntax_db) {
             ast::OptionTypeClause::Empty(_) => unit_ty(db),
@@ -206,7 +211,7 @@ pub fn priv_enum_definition_data(
         };
         let variant_name = variant.name(syntax_db).text(syntax_db);
         if let Some(_other_variant) = variants.insert(variant_name.clone(), id) {
-            diagnostics.report(&variant, EnumVariantRedefinition { enum_id, variant_name });
+            diagnostics.report(variant, EnumVariantRedefinition { enum_id, variant_name });
         }
         variant_semantic.insert(id, Variant { enum_id, id, ty, idx: variant_idx });
     }
@@ -219,16 +224,99 @@ pub fn priv_enum_definition_data(
     for (_, variant) in variant_semantic.iter_mut() {
         variant.ty = resolver.inference().rewrite(variant.ty).no_err();
     }
+    let discriminants =
+        compute_discriminants(db, &mut diagnostics, enum_id, &variant_asts, &variant_semantic);
 
     let resolver_data = Arc::new(resolver.data);
     Ok(EnumDefinitionData {
         diagnostics: diagnostics.build(),
         variants,
         variant_semantic,
+        discriminants,
         resolver_data,
     })
 }
 
+/// Computes the discriminants of the variants of an enum - the explicit discriminant of a variant
+/// if it has one, and otherwise the discriminant of the previous variant plus one, starting from 0.
+/// Returns None if a variant of the enum has data, as only C-like enums have discriminants.
+fn compute_discriminants(
+    db: &dyn SemanticGroup,
+    diagnostics: &mut SemanticDiagnostics,
+    enum_id: EnumId,
+    variant_asts: &[ast::Variant],
+    variant_semantic: &OrderedHashMap<VariantId, Variant>,
+) -> Option<Vec<BigInt>> {
+    let syntax_db = db.upcast();
+    if variant_semantic.values().any(|variant| variant.ty != unit_ty(db)) {
+        for variant_ast in variant_asts {
+            if let ast::OptionVariantDiscriminant::VariantDiscriminant(discriminant_ast) =
+                variant_ast.discriminant(syntax_db)
+            {
+                diagnostics.report(&discriminant_ast, EnumDiscriminantWithDataVariants);
+            }
+        }
+        return None;
+    }
+    let mut discriminants = vec![];
+    let mut used_discriminants = UnorderedHashSet::default();
+    let mut next_discriminant = BigInt::zero();
+    for variant_ast in variant_asts {
+        let discriminant = match variant_ast.discriminant(syntax_db) {
+            ast::OptionVariantDiscriminant::Empty(_) => {
+                // The implicit discriminant follows the previous one, and may exceed the range.
+                if validate_literal(db, core_felt252_ty(db), next_discriminant.clone()).is_err() {
+                    let value = next_discriminant.clone();
+                    diagnostics.report(
+                        variant_ast,
+                        ImplicitEnumDiscriminantOutOfRange { enum_id, value },
+                    );
+                }
+                next_discriminant
+            }
+            ast::OptionVariantDiscriminant::VariantDiscriminant(discriminant_ast) => {
+                // On an invalid discriminant, continue from the implicit one.
+                explicit_discriminant(db, diagnostics, &discriminant_ast.value(syntax_db))
+                    .unwrap_or(next_discriminant)
+            }
+        };
+        if !used_discriminants.insert(discriminant.clone()) {
+            let value = discriminant.clone();
+            diagnostics.report(variant_ast, DuplicateEnumDiscriminant { enum_id, value });
+        }
+        next_discriminant = &discriminant + 1;
+        discriminants.push(discriminant);
+    }
+    Some(discriminants)
+}
+
+/// Returns the value of an explicit discriminant of a variant, which must be a felt252 number
+/// literal. Reports a diagnostic and returns None if it is not.
+fn explicit_discriminant(
+    db: &dyn SemanticGroup,
+    diagnostics: &mut SemanticDiagnostics,
+    value_ast: &ast::Expr,
+) -> Option<BigInt> {
+    let syntax_db = db.upcast();
+    let ast::Expr::Literal(literal) = value_ast else {
+        diagnostics.report(value_ast, EnumDiscriminantNotLiteral);
+        return None;
+    };
+    let Some((value, suffix)) = literal.numeric_value_and_suffix(syntax_db) else {
+        diagnostics.report(value_ast, EnumDiscriminantNotLiteral);
+        return None;
+    };
+    if suffix.map(|suffix| suffix != "felt252").unwrap_or_default() {
+        diagnostics.report(value_ast, EnumDiscriminantNotLiteral);
+        return None;
+    }
+    if let Err(err) = validate_literal(db, core_felt252_ty(db), value.clone()) {
+        diagnostics.report(value_ast, LiteralError(err));
+        return None;
+    }
+    Some(value)
+}
+
 /// Query implementation of [crate::db::SemanticGroup::enum_definition_diagnostics].
 pub fn enum_definition_diagnostics(
     db: &dyn SemanticGroup,
@@ -253,6 +341,11 @@ pub fn enum_variants(
     Ok(db.priv_enum_definition_data(enum_id)?.variants)
 }
 
+/// Query implementation of [crate::db::SemanticGroup::enum_discriminants].
+pub fn enum_discriminants(db: &dyn SemanticGroup, enum_id: EnumId) -> Maybe<Option<Vec<BigInt>>> {
+    Ok(db.priv_enum_definition_data(enum_id)?.discriminants)
+}
+
 /// Query implementation of [crate::db::SemanticGroup::variant_semantic].
 pub fn variant_semantic(
     db: &dyn SemanticGroup,
@@ -2,6 +2,7 @@ use cairo_lang_debug::DebugWithDb;
 use cairo_lang_defs::ids::ModuleItemId;
 use cairo_lang_utils::extract_matches;
 use indoc::indoc;
+use num_bigint::BigInt;
 use pretty_assertions::assert_eq;
 use test_log::test;
 
@@ -71,3 +72,93 @@ fn test_enum() {
             c: VariantId(test::c), ty: ()"}
     );
 }
+
+#[test]
+fn test_enum_discriminants() {
+    let db_val = SemanticDatabaseForTesting::default();
+    let db = &db_val;
+    let (test_module, diagnostics) = setup_test_module(
+        db,
+        indoc::indoc! {"
+            enum Status {
+                Ok: (),
+                Created = 5,
+                Accepted,
+                NotFound = 404,
+                Redirect = 6,
+                Invalid = 1_u8,
+                Overflow = 0x800000000000011000000000000000000000000000000000000000000000001,
+            }
+
+            enum WithData {
+                A: felt252,
+                B = 1,
+            }
+        "},
+    )
+    .split();
+    assert_eq!(
+        diagnostics,
+        indoc! {r#"
+        error: Discriminant 6 is already used by another variant of enum "test::Status".
+         --> lib.cairo:6:5
+            Redirect = 6,
+            ^**********^
+
+        error: Enum discriminants must be felt252 number literals.
+         --> lib.cairo:7:15
+            Invalid = 1_u8,
+                      ^**^
+
+        error: The value does not fit within the range of type core::felt252.
+         --> lib.cairo:8:16
+            Overflow = 0x800000000000011000000000000000000000000000000000000000000000001,
+                       ^***************************************************************^
+
+        error: Explicit discriminants are only supported in enums whose variants have no data.
+         --> lib.cairo:13:7
+            B = 1,
+              ^*^
+
+        "#}
+    );
+
+    let enum_id = |name: &str| {
+        extract_matches!(
+            db.module_item_by_name(test_module.module_id, name.into()).unwrap().unwrap(),
+            ModuleItemId::Enum
+        )
+    };
+    assert_eq!(
+        db.enum_discriminants(enum_id("Status")).unwrap().unwrap(),
+        [0, 5, 6, 404, 6, 7, 8].map(BigInt::from)
+    );
+    assert_eq!(db.enum_discriminants(enum_id("WithData")).unwrap(), None);
+}
+
+#[test]
+fn test_enum_implicit_discriminant_out_of_range() {
+    let db_val = SemanticDatabaseForTesting::default();
+    let db = &db_val;
+    let (_, diagnostics) = setup_test_module(
+        db,
+        indoc::indoc! {"
+            enum Status {
+                First,
+                Last = 0x800000000000011000000000000000000000000000000000000000000000000,
+                AfterLast,
+            }
+        "},
+    )
+    .split();
+    assert_eq!(
+        diagnostics,
+        indoc! {r#"
+        error: The implicit discriminant 3618502788666131213697322783095070105623107215331596699973092056135872020481 of a variant of enum "test::Status" does not fit within the range of type core::felt252.
+         --> lib.cairo:4:5
+            AfterLast,
+            ^*******^
+
+        "#}
+    );
+}
@@ -1,6 +1,7 @@
 use cairo_lang_debug::DebugWithDb;
 use cairo_lang_defs::ids::{FunctionWithBodyId, ModuleItemId};
-use cairo_lang_utils::extract_matches;
+use cairo_lang_syntax::node::TypedSyntaxNode;
+use cairo_lang_utils::{extract_matches, Upcast};
 use pretty_assertions::assert_eq;
 use test_log::test;
 
@@ -90,3 +91,52 @@ fn test_expr_lookup() {
     let attributes = db.function_with_body_attributes(function_id).unwrap();
     assert_eq!(format!("{:?}", attributes.debug(db)), "[Attribute { id: \"inline\" }]");
 }
+
+#[test]
+fn test_function_body_calls() {
+    let db_val = SemanticDatabaseForTesting::default();
+    let db = &db_val;
+    let test_module = setup_test_module(
+        db,
+        indoc::indoc! {"
+            fn foo(a: felt252) -> felt252 {
+                bar(a) + bar(a * 2)
+            }
+            fn bar(a: felt252) -> felt252 {
+                a
+            }
+        "},
+    )
+    .unwrap();
+    let module_id = test_module.module_id;
+
+    let function_id = FunctionWithBodyId::Free(extract_matches!(
+        db.module_item_by_name(module_id, "foo".into()).unwrap().unwrap(),
+        ModuleItemId::FreeFunction
+    ));
+    let mut calls = db
+        .function_body_calls(function_id)
+        .unwrap()
+        .iter()
+        .map(|(function, stable_ptr)| {
+            format!(
+                "{:?}: {}",
+                function.debug(db),
+                stable_ptr
+                    .lookup(db.upcast())
+                    .as_syntax_node()
+                    .get_text_without_trivia(db.upcast())
+            )
+        })
+        .collect::<Vec<_>>();
+    calls.sort();
+    assert_eq!(
+        calls,
+        [
+            "core::Felt252Add::add: bar(a) + bar(a * 2)",
+            "core::Felt252Mul::mul: a * 2",
+            "test::bar: bar(a * 2)",
+            "test::bar: bar(a)"
+        ]
+    );
+}
@@ -7,7 +7,7 @@ use cairo_lang_syntax::attribute::consts::{IMPLICIT_PRECEDENCE_ATTR, INLINE_ATTR
 use cairo_lang_syntax::attribute::structured::{Attribute, AttributeArg, AttributeArgVariant};
 use cairo_lang_syntax::node::{ast, TypedSyntaxNode};
 use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
-use cairo_lang_utils::Upcast;
+use cairo_lang_utils::{try_extract_matches, Upcast};
 use id_arena::Arena;
 use itertools::Itertools;
 
@@ -17,7 +17,7 @@ use crate::db::SemanticGroup;
 use crate::diagnostic::{SemanticDiagnosticKind, SemanticDiagnostics};
 use crate::items::functions::ImplicitPrecedence;
 use crate::resolve::ResolverData;
-use crate::{semantic, ExprId, PatternId, SemanticDiagnostic, TypeId};
+use crate::{semantic, ExprId, FunctionId, PatternId, SemanticDiagnostic, TypeId};
 
 // === Declaration ===
 
@@ -35,6 +35,9 @@ pub fn function_declaration_diagnostics(
         FunctionWithBodyId::Impl(impl_function_id) => db
             .priv_impl_function_declaration_data(impl_function_id)
             .map(|x| x.function_declaration_data),
+        FunctionWithBodyId::Trait(trait_function_id) => {
+            db.priv_trait_function_declaration_data(trait_function_id)
+        }
     };
     declaration_data.map(|data| data.diagnostics).unwrap_or_default()
 }
@@ -51,6 +54,9 @@ pub fn function_declaration_inline_config(
         FunctionWithBodyId::Impl(impl_function_id) => {
             db.impl_function_declaration_inline_config(impl_function_id)
         }
+        FunctionWithBodyId::Trait(trait_function_id) => {
+            db.trait_function_declaration_inline_config(trait_function_id)
+        }
     }
 }
 
@@ -66,6 +72,9 @@ pub fn function_declaration_implicit_precedence(
         FunctionWithBodyId::Impl(impl_function_id) => {
             db.impl_function_declaration_implicit_precedence(impl_function_id)
         }
+        FunctionWithBodyId::Trait(trait_function_id) => {
+            db.trait_function_declaration_implicit_precedence(trait_function_id)
+        }
     }
 }
 
@@ -77,6 +86,9 @@ pub fn function_with_body_signature(
     match function_id {
         FunctionWithBodyId::Free(free_function_id) => db.free_function_signature(free_function_id),
         FunctionWithBodyId::Impl(impl_function_id) => db.impl_function_signature(impl_function_id),
+        FunctionWithBodyId::Trait(trait_function_id) => {
+            db.trait_function_signature(trait_function_id)
+        }
     }
 }
 
@@ -95,6 +107,11 @@ pub fn function_with_body_generic_params(
             res.extend(db.impl_function_generic_params(impl_function_id)?);
             Ok(res)
         }
+        FunctionWithBodyId::Trait(trait_function_id) => {
+            let mut res = db.trait_generic_params(trait_function_id.trait_id(db.upcast()))?;
+            res.extend(db.trait_function_generic_params(trait_function_id)?);
+            Ok(res)
+        }
     }
 }
 
@@ -111,6 +128,9 @@ pub fn function_with_body_attributes(
             .priv_impl_function_declaration_data(impl_function_id)?
             .function_declaration_data
             .attributes),
+        FunctionWithBodyId::Trait(trait_function_id) => {
+            db.trait_function_attributes(trait_function_id)
+        }
     }
 }
 
@@ -149,6 +169,9 @@ pub fn function_body_diagnostics(
         FunctionWithBodyId::Impl(impl_function_id) => {
             db.priv_impl_function_body_data(impl_function_id)
         }
+        FunctionWithBodyId::Trait(trait_function_id) => {
+            return db.trait_function_body_diagnostics(trait_function_id);
+        }
     };
     body_data.map(|data| data.diagnostics).unwrap_or_default()
 }
@@ -173,9 +196,27 @@ pub fn function_body(
         FunctionWithBodyId::Impl(impl_function_id) => {
             Ok(db.priv_impl_function_body_data(impl_function_id)?.body)
         }
+        FunctionWithBodyId::Trait(trait_function_id) => {
+            db.trait_function_body(trait_function_id)?.to_maybe()
+        }
     }
 }
 
+/// Query implementation of [crate::db::SemanticGroup::function_body_calls].
+pub fn function_body_calls(
+    db: &dyn SemanticGroup,
+    function_id: FunctionWithBodyId,
+) -> Maybe<Arc<Vec<(FunctionId, ast::ExprPtr)>>> {
+    let body = db.function_body(function_id)?;
+    Ok(Arc::new(
+        body.exprs
+            .iter()
+            .filter_map(|(_, expr)| try_extract_matches!(expr, semantic::Expr::FunctionCall))
+            .map(|call| (call.function, call.stable_ptr))
+            .collect(),
+    ))
+}
+
 // =========================================================
 
 /// Query implementation of [crate::db::SemanticGroup::expr_semantic].
@@ -218,6 +259,10 @@ pub trait SemanticExprLookup<'a>: Upcast<dyn SemanticGroup + 'a> {
             FunctionWithBodyId::Impl(impl_function_id) => {
                 self.upcast().priv_impl_function_body_data(impl_function_id)
             }
+            FunctionWithBodyId::Trait(trait_function_id) => self
+                .upcast()
+                .priv_trait_function_body_data(trait_function_id)
+                .and_then(|data| data.to_maybe()),
         };
         body_data?.expr_lookup.get(&ptr).copied().to_maybe()
     }
@@ -233,6 +278,10 @@ pub trait SemanticExprLookup<'a>: Upcast<dyn SemanticGroup + 'a> {
             FunctionWithBodyId::Impl(impl_function_id) => {
                 self.upcast().priv_impl_function_body_data(impl_function_id)
             }
+            FunctionWithBodyId::Trait(trait_function_id) => self
+                .upcast()
+                .priv_trait_function_body_data(trait_function_id)
+                .and_then(|data| data.to_maybe()),
         };
         body_data?.pattern_lookup.get(&ptr).copied().to_maybe()
     }
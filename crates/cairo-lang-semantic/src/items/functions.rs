@@ -6,6 +6,7 @@ use cairo_lang_defs::diagnostic_utils::StableLocation;
 use cairo_lang_defs::ids::{
     ExternFunctionId, FreeFunctionId, FunctionTitleId, FunctionWithBodyId, ImplFunctionId,
     LanguageElementId, ModuleItemId, ParamLongId, TopLevelLanguageElementId, TraitFunctionId,
+    TraitId,
 };
 use cairo_lang_diagnostics::{skip_diagnostic, Diagnostics, Maybe};
 use cairo_lang_filesystem::ids::UnstableSalsaId;
@@ -28,8 +29,8 @@ use crate::resolve::{Resolver, ResolverData};
 use crate::substitution::{GenericSubstitution, SemanticRewriter, SubstitutionRewriter};
 use crate::types::resolve_type;
 use crate::{
-    semantic, semantic_object_for_id, ConcreteImplId, ConcreteImplLongId, GenericArgumentId,
-    GenericParam, SemanticDiagnostic, TypeId,
+    semantic, semantic_object_for_id, ConcreteImplId, ConcreteImplLongId, ConcreteTraitLongId,
+    GenericArgumentId, GenericParam, SemanticDiagnostic, TypeId,
 };
 
 /// A generic function of an impl.
@@ -45,7 +46,7 @@ impl ImplGenericFunctionId {
             ImplId::Concrete(concrete_impl_id) => {
                 concrete_impl_id.get_impl_function(db, self.function)
             }
-            ImplId::GenericParameter(_) | ImplId::ImplVar(_) => Ok(None),
+            ImplId::GenericParameter(_) | ImplId::ImplVar(_) | ImplId::SelfImpl(_) => Ok(None),
         }
     }
     /// Converts to ImplGenericFunctionWithBodyId if this is a function of a concrete impl.
@@ -68,10 +69,7 @@ impl ImplGenericFunctionId {
         &self,
         db: &dyn SemanticGroup,
     ) -> Maybe<Option<GenericFunctionWithBodyId>> {
-        let Some(impl_generic_with_body) = self.to_impl_generic_with_body(db)? else {
-            return Ok(None);
-        };
-        Ok(Some(GenericFunctionWithBodyId::Impl(impl_generic_with_body)))
+        GenericFunctionWithBodyId::from_generic(db, GenericFunctionId::Impl(*self))
     }
     pub fn format(&self, db: &dyn SemanticGroup) -> SmolStr {
         format!("{}::{}", self.impl_id.name(db.upcast()), self.function.name(db.upcast())).into()
@@ -99,6 +97,7 @@ impl GenericFunctionId {
                 impl_id: ImplId::Concrete(id.concrete_impl_id),
                 function: db.impl_function_trait_function(id.function)?,
             }),
+            GenericFunctionWithBodyId::Trait(id) => GenericFunctionId::Impl(id),
         })
     }
     pub fn format(&self, db: &dyn SemanticGroup) -> String {
@@ -208,24 +207,31 @@ pub struct ImplGenericFunctionWithBodyId {
 pub enum GenericFunctionWithBodyId {
     Free(FreeFunctionId),
     Impl(ImplGenericFunctionWithBodyId),
+    /// The default implementation of a trait function, used by an impl that does not override it.
+    /// The impl is [ImplId::SelfImpl] when the default implementation is lowered on its own.
+    Trait(ImplGenericFunctionId),
 }
 impl GenericFunctionWithBodyId {
     pub fn from_generic(db: &dyn SemanticGroup, other: GenericFunctionId) -> Maybe<Option<Self>> {
         Ok(Some(match other {
             GenericFunctionId::Free(id) => GenericFunctionWithBodyId::Free(id),
-            GenericFunctionId::Impl(ImplGenericFunctionId {
-                impl_id: ImplId::Concrete(concrete_impl_id),
-                function,
-            }) => {
-                let Some(impl_function) =
-                    db.impl_function_by_trait_function(concrete_impl_id.impl_def_id(db), function)?
-                else {
+            GenericFunctionId::Impl(id) => {
+                let ImplId::Concrete(concrete_impl_id) = id.impl_id else {
                     return Ok(None);
                 };
-                GenericFunctionWithBodyId::Impl(ImplGenericFunctionWithBodyId {
-                    concrete_impl_id,
-                    function: impl_function,
-                })
+                let function = id.function;
+                if let Some(impl_function) =
+                    db.impl_function_by_trait_function(concrete_impl_id.impl_def_id(db), function)?
+                {
+                    GenericFunctionWithBodyId::Impl(ImplGenericFunctionWithBodyId {
+                        concrete_impl_id,
+                        function: impl_function,
+                    })
+                } else if db.trait_function_body(function)?.is_some() {
+                    GenericFunctionWithBodyId::Trait(id)
+                } else {
+                    return Ok(None);
+                }
             }
             _ => return Ok(None),
         }))
@@ -237,6 +243,7 @@ impl GenericFunctionWithBodyId {
                 format!("{}::{}", imp.concrete_impl_id.name(db), imp.function.name(db.upcast()))
                     .into()
             }
+            GenericFunctionWithBodyId::Trait(id) => id.format(db),
         }
     }
 
@@ -249,6 +256,14 @@ impl GenericFunctionWithBodyId {
                 imp.concrete_impl_id.impl_def_id(db).full_path(defs_db),
                 imp.function.name(defs_db)
             ),
+            GenericFunctionWithBodyId::Trait(id) => match id.impl_id {
+                ImplId::Concrete(concrete_impl_id) => format!(
+                    "{}::{}",
+                    concrete_impl_id.impl_def_id(db).full_path(defs_db),
+                    id.function.name(defs_db)
+                ),
+                _ => id.function.full_path(defs_db),
+            },
         }
     }
     pub fn stable_location(&self, db: &dyn SemanticGroup) -> StableLocation {
@@ -259,6 +274,7 @@ impl GenericFunctionWithBodyId {
             GenericFunctionWithBodyId::Impl(impl_function) => {
                 impl_function.function.stable_location(db.upcast())
             }
+            GenericFunctionWithBodyId::Trait(id) => id.function.stable_location(db.upcast()),
         }
     }
 }
@@ -274,6 +290,7 @@ impl ConcreteFunctionWithBody {
         match self.generic_function {
             GenericFunctionWithBodyId::Free(id) => FunctionWithBodyId::Free(id),
             GenericFunctionWithBodyId::Impl(id) => FunctionWithBodyId::Impl(id.function),
+            GenericFunctionWithBodyId::Trait(id) => FunctionWithBodyId::Trait(id.function),
         }
     }
     pub fn substitution(&self, db: &dyn SemanticGroup) -> Maybe<GenericSubstitution> {
@@ -296,6 +313,19 @@ impl ConcreteFunctionWithBody {
                     .collect_vec(),
                 )
             }
+            GenericFunctionWithBodyId::Trait(f) => {
+                let concrete_trait_id = db.impl_concrete_trait(f.impl_id)?;
+                GenericSubstitution::new(
+                    &chain!(
+                        db.trait_function_generic_params(f.function)?,
+                        db.trait_generic_params(concrete_trait_id.trait_id(db))?
+                    )
+                    .collect_vec(),
+                    &chain!(self.generic_args.iter().copied(), concrete_trait_id.generic_args(db))
+                        .collect_vec(),
+                )
+                .with_self_impl(f.impl_id)
+            }
         })
     }
     pub fn from_no_generics_free(
@@ -338,6 +368,17 @@ impl ConcreteFunctionWithBody {
                     generic_args,
                 }
             }
+            FunctionWithBodyId::Trait(trait_function_id) => {
+                let params = db.trait_function_generic_params(trait_function_id)?;
+                let generic_args = generic_params_to_args(params, db)?;
+                ConcreteFunctionWithBody {
+                    generic_function: GenericFunctionWithBodyId::Trait(ImplGenericFunctionId {
+                        impl_id: trait_self_impl(db, trait_function_id.trait_id(db.upcast()))?,
+                        function: trait_function_id,
+                    }),
+                    generic_args,
+                }
+            }
         })
     }
     pub fn concrete(&self, db: &dyn SemanticGroup) -> Maybe<ConcreteFunction> {
@@ -357,6 +398,13 @@ impl ConcreteFunctionWithBody {
     }
 }
 
+/// Returns the `Self` impl used inside the default implementations of the functions of a trait,
+/// which implements the trait with its own generic params.
+pub fn trait_self_impl(db: &dyn SemanticGroup, trait_id: TraitId) -> Maybe<ImplId> {
+    let generic_args = generic_params_to_args(db.trait_generic_params(trait_id)?, db)?;
+    Ok(ImplId::SelfImpl(db.intern_concrete_trait(ConcreteTraitLongId { trait_id, generic_args })))
+}
+
 /// Converts each generic param to a generic argument that passes the same generic param.
 fn generic_params_to_args(
     params: Vec<GenericParam>,
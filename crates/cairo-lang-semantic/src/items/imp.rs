@@ -35,7 +35,7 @@ use super::functions::{
 use super::generics::{semantic_generic_params, GenericArgumentHead, GenericParamsData};
 use super::structure::SemanticStructEx;
 use super::trt::{ConcreteTraitGenericFunctionId, ConcreteTraitGenericFunctionLongId};
-use crate::corelib::{copy_trait, drop_trait};
+use crate::corelib::{copy_trait, core_crate, drop_trait};
 use crate::db::SemanticGroup;
 use crate::diagnostic::SemanticDiagnosticKind::{self, *};
 use crate::diagnostic::{NotFoundItemType, SemanticDiagnostics};
@@ -43,7 +43,7 @@ use crate::expr::compute::{compute_root_expr, ComputationContext, Environment};
 use crate::expr::inference::canonic::ResultNoErrEx;
 use crate::expr::inference::infers::InferenceEmbeddings;
 use crate::expr::inference::solver::SolutionSet;
-use crate::expr::inference::{ImplVarId, InferenceError, InferenceId};
+use crate::expr::inference::{ImplVarId, InferenceData, InferenceError, InferenceId};
 use crate::items::function_with_body::get_implicit_precedence;
 use crate::items::functions::ImplicitPrecedence;
 use crate::items::us::SemanticUseEx;
@@ -114,13 +114,16 @@ pub enum ImplId {
     Concrete(ConcreteImplId),
     GenericParameter(GenericParamId),
     ImplVar(ImplVarId),
+    /// The impl referred to as `Self` inside the default implementations of the functions of a
+    /// trait. Substituted with the actual impl when a default implementation is used.
+    SelfImpl(ConcreteTraitId),
 }
 impl ImplId {
     /// Returns the [ImplHead] of an impl if available.
     pub fn head(&self, db: &dyn SemanticGroup) -> Option<ImplHead> {
         Some(match self {
             ImplId::Concrete(concrete) => ImplHead::Concrete(concrete.impl_def_id(db)),
-            ImplId::GenericParameter(_) | ImplId::ImplVar(_) => return None,
+            ImplId::GenericParameter(_) | ImplId::ImplVar(_) | ImplId::SelfImpl(_) => return None,
         })
     }
     pub fn name(&self, db: &dyn SemanticGroup) -> SmolStr {
@@ -130,6 +133,7 @@ impl ImplId {
                 generic_param_impl.name(db.upcast()).unwrap_or_else(|| "_".into())
             }
             ImplId::ImplVar(var) => format!("{var:?}").into(),
+            ImplId::SelfImpl(_) => "Self".into(),
         }
     }
     pub fn concrete_trait(&self, db: &dyn SemanticGroup) -> Maybe<ConcreteTraitId> {
@@ -146,6 +150,7 @@ impl DebugWithDb<dyn SemanticGroup> for ImplId {
             ImplId::Concrete(concrete_impl_id) => write!(f, "{:?}", concrete_impl_id.debug(db)),
             ImplId::GenericParameter(param) => write!(f, "{:?}", param.debug(db)),
             ImplId::ImplVar(var) => write!(f, "?{}", var.get(db).id.0),
+            ImplId::SelfImpl(_) => write!(f, "Self"),
         }
     }
 }
@@ -316,6 +321,7 @@ pub fn impl_concrete_trait(db: &dyn SemanticGroup, impl_id: ImplId) -> Maybe<Con
             param_impl.concrete_trait
         }
         ImplId::ImplVar(var) => Ok(var.get(db).concrete_trait_id),
+        ImplId::SelfImpl(concrete_trait_id) => Ok(concrete_trait_id),
     }
 }
 
@@ -428,10 +434,113 @@ pub fn impl_semantic_definition_diagnostics(
         diagnostics.extend(db.impl_function_declaration_diagnostics(*impl_function_id));
         diagnostics.extend(db.impl_function_body_diagnostics(*impl_function_id));
     }
+    if let Ok(coherence_diagnostics) = impl_coherence_diagnostics(db, impl_def_id) {
+        diagnostics.extend(coherence_diagnostics);
+    }
 
     diagnostics.build()
 }
 
+/// Returns diagnostics for the impls in other crates that conflict with an impl, i.e. implement its
+/// trait for overlapping generic arguments. Without this check, such conflicts only surface as
+/// ambiguity errors where the trait is used.
+fn impl_coherence_diagnostics(
+    db: &dyn SemanticGroup,
+    impl_def_id: ImplDefId,
+) -> Maybe<Diagnostics<SemanticDiagnostic>> {
+    let trait_id = db.impl_def_concrete_trait(impl_def_id)?.trait_id(db);
+    let conflicting_impls = db.trait_conflicting_impls(trait_id);
+    let Some(other_impl_def_ids) = conflicting_impls.get(&impl_def_id) else {
+        return Ok(Diagnostics::default());
+    };
+    let module_file_id = impl_def_id.module_file_id(db.upcast());
+    let mut diagnostics = SemanticDiagnostics::new(module_file_id.file_id(db.upcast())?);
+    let syntax_db = db.upcast();
+    let trait_path_syntax =
+        impl_def_id.stable_ptr(db.upcast()).lookup(syntax_db).trait_path(syntax_db);
+    for other_impl_def_id in other_impl_def_ids {
+        diagnostics.report(
+            &trait_path_syntax,
+            ConflictingImpls { trait_id, other_impl_def_id: *other_impl_def_id },
+        );
+    }
+    Ok(diagnostics.build())
+}
+
+/// Query implementation of [crate::db::SemanticGroup::trait_conflicting_impls].
+pub fn trait_conflicting_impls(
+    db: &dyn SemanticGroup,
+    trait_id: TraitId,
+) -> Arc<OrderedHashMap<ImplDefId, Vec<ImplDefId>>> {
+    // Impls of the corelib are not checked, as existing code redefines some of them and calls them
+    // by name. Impls with generic impl params are not checked either, as they may be disjoint by
+    // their bounds, which is only known where they are used.
+    let core_crate_id = core_crate(db);
+    let mut impls = vec![];
+    for crate_id in db.crates() {
+        if crate_id == core_crate_id {
+            continue;
+        }
+        let index = db.impl_index_in_crate(crate_id);
+        for impl_def_id in index.impls_by_trait.get(&trait_id).into_iter().flatten() {
+            let Ok(generic_params) = db.impl_def_generic_params(*impl_def_id) else {
+                continue;
+            };
+            if generic_params.iter().all(|param| !matches!(param, GenericParam::Impl(_))) {
+                impls.push((crate_id, *impl_def_id));
+            }
+        }
+    }
+    let mut conflicting_impls = OrderedHashMap::<ImplDefId, Vec<ImplDefId>>::default();
+    for (i, (crate_id, impl_def_id)) in impls.iter().enumerate() {
+        for (other_crate_id, other_impl_def_id) in &impls[i + 1..] {
+            if crate_id == other_crate_id {
+                continue;
+            }
+            if impl_def_overlaps(db, *impl_def_id, *other_impl_def_id)
+                || impl_def_overlaps(db, *other_impl_def_id, *impl_def_id)
+            {
+                conflicting_impls.entry(*impl_def_id).or_default().push(*other_impl_def_id);
+                conflicting_impls.entry(*other_impl_def_id).or_default().push(*impl_def_id);
+            }
+        }
+    }
+    conflicting_impls.into()
+}
+
+/// Returns true if `other_impl_def_id` implements the concrete trait of `impl_def_id` for some
+/// generic arguments, with its generic impl params satisfied. The generic params of `impl_def_id`
+/// stand for any type, and are only satisfied by its own generic impl params.
+fn impl_def_overlaps(
+    db: &dyn SemanticGroup,
+    impl_def_id: ImplDefId,
+    other_impl_def_id: ImplDefId,
+) -> bool {
+    let (Ok(concrete_trait_id), Ok(generic_params)) =
+        (db.impl_def_concrete_trait(impl_def_id), db.impl_def_generic_params(impl_def_id))
+    else {
+        return false;
+    };
+    let lookup_context = ImplLookupContext::new(
+        impl_def_id.module_file_id(db.upcast()).0,
+        generic_params.iter().map(|param| param.id()).collect(),
+    );
+    let mut inference_data = InferenceData::new(InferenceId::NoContext);
+    let mut inference = inference_data.inference(db);
+    if inference
+        .infer_impl(
+            UninferredImpl::Def(other_impl_def_id),
+            concrete_trait_id,
+            &lookup_context,
+            None,
+        )
+        .is_err()
+    {
+        return false;
+    }
+    matches!(inference.solution_set(), Ok(SolutionSet::Unique(_)))
+}
+
 /// Query implementation of [crate::db::SemanticGroup::impl_functions].
 pub fn impl_functions(
     db: &dyn SemanticGroup,
@@ -573,12 +682,15 @@ pub fn priv_impl_definition_data(
 
     // It is later verified that all items in this impl match items from `concrete_trait`.
     // To ensure exact match (up to trait functions with default implementation), it is sufficient
-    // to verify here that all items in `concrete_trait` appear in this impl.
-    // TODO(yuval): Once default implementation of trait functions is supported, filter such
-    // functions out.
+    // to verify here that all items in `concrete_trait` without a default implementation appear in
+    // this impl.
     let trait_item_names = db
-        .trait_functions(db.lookup_intern_concrete_trait(concrete_trait).trait_id)?
-        .into_keys()
+        .trait_function_asts(db.lookup_intern_concrete_trait(concrete_trait).trait_id)?
+        .into_iter()
+        .filter(|(_, function_ast)| {
+            matches!(function_ast.body(syntax_db), ast::MaybeTraitFunctionBody::None(_))
+        })
+        .map(|(trait_function_id, _)| trait_function_id.name(defs_db))
         .collect::<OrderedHashSet<_>>();
     let missing_items_in_impl =
         trait_item_names.difference(&impl_item_names).cloned().collect::<Vec<_>>();
@@ -821,10 +933,13 @@ impl PartialOrd for ModuleIdById {
 pub struct ImplLookupContext {
     pub modules: BTreeSet<ModuleIdById>,
     pub generic_params: Vec<GenericParamId>,
+    /// The concrete trait of the `Self` impl, inside the default implementations of trait
+    /// functions.
+    pub self_impl_trait: Option<ConcreteTraitId>,
 }
 impl ImplLookupContext {
     pub fn new(module_id: ModuleId, generic_params: Vec<GenericParamId>) -> ImplLookupContext {
-        Self { modules: [ModuleIdById(module_id)].into(), generic_params }
+        Self { modules: [ModuleIdById(module_id)].into(), generic_params, self_impl_trait: None }
     }
 
     pub fn insert_module(&mut self, module_id: ModuleId) -> bool {
@@ -838,6 +953,7 @@ pub enum UninferredImpl {
     Def(ImplDefId),
     ImplAlias(ImplAliasId),
     GenericParam(GenericParamId),
+    SelfImpl(ConcreteTraitId),
 }
 impl UninferredImpl {
     pub fn concrete_trait(&self, db: &dyn SemanticGroup) -> Maybe<ConcreteTraitId> {
@@ -852,6 +968,7 @@ impl UninferredImpl {
                     extract_matches!(db.generic_param_semantic(*param)?, GenericParam::Impl);
                 param.concrete_trait
             }
+            UninferredImpl::SelfImpl(concrete_trait_id) => Ok(*concrete_trait_id),
         }
     }
 
@@ -867,6 +984,7 @@ impl UninferredImpl {
                     extract_matches!(db.generic_param_semantic(*param)?, GenericParam::Impl);
                 param.concrete_trait.map(|concrete_trait| concrete_trait.trait_id(db))
             }
+            UninferredImpl::SelfImpl(concrete_trait_id) => Ok(concrete_trait_id.trait_id(db)),
         }
     }
 
@@ -876,6 +994,9 @@ impl UninferredImpl {
             UninferredImpl::Def(impl_def_id) => impl_def_id.module_file_id(defs_db).0,
             UninferredImpl::ImplAlias(impl_alias_id) => impl_alias_id.module_file_id(defs_db).0,
             UninferredImpl::GenericParam(param) => param.module_file_id(defs_db).0,
+            UninferredImpl::SelfImpl(concrete_trait_id) => {
+                concrete_trait_id.trait_id(db).module_file_id(defs_db).0
+            }
         }
     }
 }
@@ -889,6 +1010,7 @@ impl DebugWithDb<dyn SemanticGroup> for UninferredImpl {
             UninferredImpl::GenericParam(param) => {
                 write!(f, "generic param {}", param.name(db.upcast()).unwrap_or_else(|| "_".into()))
             }
+            UninferredImpl::SelfImpl(_) => write!(f, "Self"),
         }
     }
 }
@@ -925,6 +1047,13 @@ pub fn find_candidates_at_context(
         }
         res.insert(UninferredImpl::GenericParam(*generic_param_id));
     }
+    if let Some(self_impl_trait) = lookup_context.self_impl_trait {
+        if self_impl_trait.trait_id(db) == filter.trait_id
+            && concrete_trait_fits_trait_filter(db, self_impl_trait, &filter).unwrap_or_default()
+        {
+            res.insert(UninferredImpl::SelfImpl(self_impl_trait));
+        }
+    }
     for module_id in chain!(lookup_context.modules.iter().map(|x| &x.0)) {
         let Ok(imps) = db.module_impl_ids_for_trait_filter(*module_id, filter.clone()) else {
             continue;
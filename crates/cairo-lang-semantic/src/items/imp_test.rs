@@ -1,6 +1,14 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
 use cairo_lang_debug::DebugWithDb;
-use cairo_lang_defs::ids::ModuleItemId;
-use cairo_lang_utils::extract_matches;
+use cairo_lang_defs::ids::{ModuleId, ModuleItemId};
+use cairo_lang_filesystem::db::{CrateConfiguration, FilesGroup, FilesGroupEx};
+use cairo_lang_filesystem::ids::{
+    CrateId, CrateLongId, Directory, FileKind, FileLongId, VirtualFile,
+};
+use cairo_lang_utils::{extract_matches, Upcast};
+use itertools::Itertools;
 use pretty_assertions::assert_eq;
 use test_log::test;
 
@@ -46,3 +54,147 @@ fn test_impl() {
 
     db.impl_def_concrete_trait(impl_def_id).unwrap();
 }
+
+#[test]
+fn test_impl_index_in_crate() {
+    let db_val = SemanticDatabaseForTesting::default();
+    let db = &db_val;
+    let (test_module, diagnostics) = setup_test_module(
+        db,
+        indoc::indoc! {"
+            #[derive(Drop)]
+            struct A {}
+            #[derive(Drop)]
+            struct B {}
+            trait MyTrait<T> {}
+            impl MyTraitA of MyTrait<A> {}
+            impl MyTraitB of MyTrait<B> {}
+            impl MyTraitSpanA of MyTrait<Span<A>> {}
+        "},
+    )
+    .split();
+    assert_eq!(diagnostics, "");
+
+    let index = db.impl_index_in_crate(test_module.crate_id);
+    let impls_by_trait = index
+        .impls_by_trait
+        .iter()
+        .map(|(trait_id, impls)| {
+            format!(
+                "{}: {}",
+                trait_id.name(db.upcast()),
+                impls.iter().map(|impl_def_id| impl_def_id.name(db.upcast())).join(", ")
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(impls_by_trait, ["MyTrait: MyTraitA, MyTraitB, MyTraitSpanA", "Drop: ADrop, BDrop"]);
+    let impls_by_type = index
+        .impls_by_type
+        .iter()
+        .map(|(generic_type, impls)| {
+            format!(
+                "{}: {}",
+                generic_type.format(db.upcast()),
+                impls.iter().map(|impl_def_id| impl_def_id.name(db.upcast())).join(", ")
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        impls_by_type,
+        ["test::A: MyTraitA, ADrop", "test::B: MyTraitB, BDrop", "core::array::Span: MyTraitSpanA"]
+    );
+}
+
+/// Sets up a crate with the given name and content, as one of the crates of the database.
+fn setup_named_crate(db: &mut SemanticDatabaseForTesting, name: &str, content: &str) -> CrateId {
+    let file_id = db.intern_file(FileLongId::Virtual(VirtualFile {
+        parent: None,
+        name: "lib.cairo".into(),
+        content: Arc::new(content.into()),
+        code_mappings: Default::default(),
+        kind: FileKind::Module,
+    }));
+    let root = Directory::Virtual {
+        files: BTreeMap::from([("lib.cairo".into(), file_id)]),
+        dirs: Default::default(),
+    };
+    let crate_id = db.intern_crate(CrateLongId::Virtual { name: name.into(), root: root.clone() });
+    db.set_crate_config(crate_id, Some(CrateConfiguration::default_for_root(root)));
+    crate_id
+}
+
+#[test]
+fn test_conflicting_impls_across_crates() {
+    let mut db_val = SemanticDatabaseForTesting::new_empty();
+    setup_named_crate(
+        &mut db_val,
+        "dep",
+        indoc::indoc! {"
+            impl DepFelt252IntoU8 of Into<felt252, u8> {
+                fn into(self: felt252) -> u8 {
+                    0
+                }
+            }
+            impl DepArraySnapshotIntoU16<T> of Into<@Array<T>, u16> {
+                fn into(self: @Array<T>) -> u16 {
+                    0
+                }
+            }
+            impl DepIntoU32<T, +Drop<T>> of Into<T, u32> {
+                fn into(self: T) -> u32 {
+                    0
+                }
+            }
+        "},
+    );
+    let crate_id = setup_named_crate(
+        &mut db_val,
+        "test",
+        indoc::indoc! {"
+            impl Felt252IntoU8 of Into<felt252, u8> {
+                fn into(self: felt252) -> u8 {
+                    1
+                }
+            }
+            impl Felt252ArraySnapshotIntoU16 of Into<@Array<felt252>, u16> {
+                fn into(self: @Array<felt252>) -> u16 {
+                    1
+                }
+            }
+            impl Felt252IntoU32 of Into<felt252, u32> {
+                fn into(self: felt252) -> u32 {
+                    1
+                }
+            }
+            impl AlsoFelt252IntoU8 of Into<felt252, u8> {
+                fn into(self: felt252) -> u8 {
+                    2
+                }
+            }
+        "},
+    );
+    let db = &db_val;
+    let diagnostics = db.module_semantic_diagnostics(ModuleId::CrateRoot(crate_id)).unwrap();
+    // Impls of the same crate, and impls whose generic impl params may make them disjoint, are
+    // not reported.
+    assert_eq!(
+        diagnostics.format(db),
+        indoc::indoc! {"
+            error: Conflicting implementations of trait `core::traits::Into`. Impl `dep::DepFelt252IntoU8` of another crate implements it for overlapping types.
+             --> lib.cairo:1:23
+            impl Felt252IntoU8 of Into<felt252, u8> {
+                                  ^***************^
+
+            error: Conflicting implementations of trait `core::traits::Into`. Impl `dep::DepArraySnapshotIntoU16` of another crate implements it for overlapping types.
+             --> lib.cairo:6:37
+            impl Felt252ArraySnapshotIntoU16 of Into<@Array<felt252>, u16> {
+                                                ^************************^
+
+            error: Conflicting implementations of trait `core::traits::Into`. Impl `dep::DepFelt252IntoU8` of another crate implements it for overlapping types.
+             --> lib.cairo:16:27
+            impl AlsoFelt252IntoU8 of Into<felt252, u8> {
+                                      ^***************^
+
+        "}
+    );
+}
@@ -2,8 +2,8 @@ use std::sync::Arc;
 
 use cairo_lang_debug::DebugWithDb;
 use cairo_lang_defs::ids::{
-    FunctionTitleId, LanguageElementId, LookupItemId, ModuleItemId, TopLevelLanguageElementId,
-    TraitFunctionId, TraitFunctionLongId, TraitId,
+    FunctionTitleId, FunctionWithBodyId, LanguageElementId, LookupItemId, ModuleItemId,
+    TopLevelLanguageElementId, TraitFunctionId, TraitFunctionLongId, TraitId,
 };
 use cairo_lang_diagnostics::{Diagnostics, DiagnosticsBuilder, Maybe, ToMaybe};
 use cairo_lang_proc_macros::{DebugWithDb, SemanticObject};
@@ -18,12 +18,13 @@ use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
 use smol_str::SmolStr;
 
 use super::function_with_body::{get_implicit_precedence, get_inline_config, FunctionBodyData};
-use super::functions::{FunctionDeclarationData, ImplicitPrecedence, InlineConfiguration};
+use super::functions::{
+    trait_self_impl, FunctionDeclarationData, ImplicitPrecedence, InlineConfiguration,
+};
 use super::generics::{semantic_generic_params, GenericParamsData};
 use super::imp::{GenericsHeadFilter, TraitFilter};
 use crate::db::SemanticGroup;
-use crate::diagnostic::SemanticDiagnosticKind::{self, *};
-use crate::diagnostic::SemanticDiagnostics;
+use crate::diagnostic::{SemanticDiagnosticKind, SemanticDiagnostics};
 use crate::expr::compute::{compute_root_expr, ComputationContext, Environment};
 use crate::expr::inference::canonic::ResultNoErrEx;
 use crate::expr::inference::InferenceId;
@@ -297,6 +298,7 @@ pub fn trait_semantic_definition_diagnostics(
     diagnostics.extend(data.diagnostics);
     for trait_function_id in data.function_asts.keys() {
         diagnostics.extend(db.trait_function_declaration_diagnostics(*trait_function_id));
+        diagnostics.extend(db.trait_function_body_diagnostics(*trait_function_id));
     }
 
     diagnostics.build()
@@ -529,14 +531,6 @@ pub fn priv_trait_function_declaration_data(
         &signature,
         &signature_syntax,
     );
-    // Validate trait function body is empty.
-    if matches!(function_syntax.body(syntax_db), ast::MaybeTraitFunctionBody::Some(_)) {
-        diagnostics.report(
-            &function_syntax.body(syntax_db),
-            TraitFunctionWithBody { trait_id, function_id: trait_function_id },
-        );
-    }
-
     let attributes = function_syntax.attributes(syntax_db).structurize(syntax_db);
     let resolver_data = Arc::new(resolver.data);
 
@@ -651,29 +645,30 @@ pub fn priv_trait_function_body_data(
     let trait_id = trait_function_id.trait_id(defs_db);
     let data = db.priv_trait_semantic_definition_data(trait_id)?;
     let function_syntax = &data.function_asts[trait_function_id];
+    let function_body = match function_syntax.body(db.upcast()) {
+        ast::MaybeTraitFunctionBody::Some(expr_block) => expr_block,
+        ast::MaybeTraitFunctionBody::None(_) => return Ok(None),
+    };
     // Compute declaration semantic.
     let trait_function_declaration_data =
         db.priv_trait_function_declaration_data(trait_function_id)?;
-    let parent_resolver_data = db.trait_resolver_data(trait_id)?;
+    let parent_resolver_data = trait_function_declaration_data.resolver_data;
     let inference_id =
         InferenceId::LookupItemDefinition(LookupItemId::TraitFunction(trait_function_id));
-    let resolver =
+    let mut resolver =
         Resolver::with_data(db, (*parent_resolver_data).clone_with_inference_id(db, inference_id));
+    resolver.self_impl = Some(trait_self_impl(db, trait_id)?);
     let environment = trait_function_declaration_data.environment;
 
     // Compute body semantic expr.
     let mut ctx = ComputationContext::new(
         db,
         &mut diagnostics,
-        None,
+        Some(FunctionWithBodyId::Trait(trait_function_id)),
         resolver,
         Some(&trait_function_declaration_data.signature),
         environment,
     );
-    let function_body = match function_syntax.body(db.upcast()) {
-        ast::MaybeTraitFunctionBody::Some(expr_block) => expr_block,
-        ast::MaybeTraitFunctionBody::None(_) => return Ok(None),
-    };
     let return_type = trait_function_declaration_data.signature.return_type;
     let body_expr = compute_root_expr(&mut ctx, &function_body, return_type)?;
     let ComputationContext { exprs, patterns, statements, resolver, .. } = ctx;
@@ -32,6 +32,9 @@ impl LookupItemEx for LookupItemId {
             LookupItemId::ImplFunction(impl_function_id) => {
                 Some(FunctionWithBodyId::Impl(*impl_function_id))
             }
+            LookupItemId::TraitFunction(trait_function_id) => {
+                Some(FunctionWithBodyId::Trait(*trait_function_id))
+            }
             _ => None,
         }
     }
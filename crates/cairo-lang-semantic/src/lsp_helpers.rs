@@ -1,10 +1,13 @@
 use std::sync::Arc;
 
-use cairo_lang_defs::ids::{ModuleId, TraitFunctionId};
+use cairo_lang_defs::ids::{GenericTypeId, ImplDefId, ModuleId, TraitFunctionId, TraitId};
 use cairo_lang_filesystem::ids::CrateId;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
 
 use crate::db::SemanticGroup;
 use crate::types::TypeHead;
+use crate::GenericArgumentId;
 
 /// A filter for types.
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
@@ -73,3 +76,67 @@ pub fn methods_in_crate(
     }
     result.into()
 }
+
+/// Query implementation of [crate::db::SemanticGroup::visible_crates].
+pub fn visible_crates(db: &dyn SemanticGroup, crate_id: CrateId) -> Arc<Vec<CrateId>> {
+    let mut visible_crates = OrderedHashSet::default();
+    visible_crates.insert(crate_id);
+    visible_crates.insert(db.core_crate());
+    let mut pending = vec![crate_id];
+    while let Some(crate_id) = pending.pop() {
+        let Some(config) = db.crate_config(crate_id) else {
+            continue;
+        };
+        let Some(dependencies) = config.dependencies else {
+            visible_crates.extend(db.crates());
+            break;
+        };
+        for dependency in dependencies {
+            if db.crate_config(dependency).is_some() && visible_crates.insert(dependency) {
+                pending.push(dependency);
+            }
+        }
+    }
+    Arc::new(visible_crates.into_iter().collect())
+}
+
+/// The impls of a crate, indexed by the traits they implement and by the types they implement them
+/// for.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImplIndex {
+    /// The impls of each trait.
+    pub impls_by_trait: OrderedHashMap<TraitId, Vec<ImplDefId>>,
+    /// The impls of traits for each type, i.e. the impls whose concrete trait has a generic
+    /// argument of that type.
+    pub impls_by_type: OrderedHashMap<GenericTypeId, Vec<ImplDefId>>,
+}
+
+/// Query implementation of [crate::db::SemanticGroup::impl_index_in_crate].
+pub fn impl_index_in_crate(db: &dyn SemanticGroup, crate_id: CrateId) -> Arc<ImplIndex> {
+    let mut index = ImplIndex::default();
+    for module_id in db.crate_modules(crate_id).iter() {
+        for impl_def_id in db.module_impls_ids(*module_id).unwrap_or_default().iter().copied() {
+            let Ok(concrete_trait_id) = db.impl_def_concrete_trait(impl_def_id) else {
+                continue;
+            };
+            index
+                .impls_by_trait
+                .entry(concrete_trait_id.trait_id(db))
+                .or_default()
+                .push(impl_def_id);
+            let mut generic_types = OrderedHashSet::<GenericTypeId>::default();
+            for generic_arg in concrete_trait_id.generic_args(db) {
+                let GenericArgumentId::Type(ty) = generic_arg else {
+                    continue;
+                };
+                if let Some(TypeHead::Concrete(generic_type)) = ty.head(db) {
+                    generic_types.insert(generic_type);
+                }
+            }
+            for generic_type in generic_types {
+                index.impls_by_type.entry(generic_type).or_default().push(impl_def_id);
+            }
+        }
+    }
+    index.into()
+}
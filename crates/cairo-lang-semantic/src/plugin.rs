@@ -11,6 +11,11 @@ use crate::db::SemanticGroup;
 pub trait AnalyzerPlugin: std::fmt::Debug + Sync + Send {
     /// Runs the plugin on a module.
     fn diagnostics(&self, db: &dyn SemanticGroup, module_id: ModuleId) -> Vec<PluginDiagnostic>;
+
+    /// A name identifying the plugin, stable across compilations.
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
 }
 
 /// A suite of plugins.
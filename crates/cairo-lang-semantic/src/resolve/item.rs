@@ -126,6 +126,9 @@ impl ResolvedConcreteItem {
                 ImplId::Concrete(concrete_impl_id) => ResolvedGenericItem::Impl(
                     db.lookup_intern_concrete_impl(*concrete_impl_id).impl_def_id,
                 ),
+                ImplId::SelfImpl(concrete_trait_id) => {
+                    ResolvedGenericItem::Trait(concrete_trait_id.trait_id(db))
+                }
                 ImplId::GenericParameter(_) | ImplId::ImplVar(_) => return None,
             },
         })
@@ -97,6 +97,8 @@ pub struct ResolverData {
     generic_param_by_name: OrderedHashMap<SmolStr, GenericParamId>,
     /// All generic parameters accessible to the resolver.
     pub generic_params: Vec<GenericParamId>,
+    /// The impl referred to as `Self`, inside the default implementations of trait functions.
+    pub self_impl: Option<ImplId>,
     /// Lookback map for resolved identifiers in path. Used in "Go to definition".
     pub resolved_items: ResolvedItems,
     /// Inference data for the resolver.
@@ -108,6 +110,7 @@ impl ResolverData {
             module_file_id,
             generic_param_by_name: Default::default(),
             generic_params: Default::default(),
+            self_impl: None,
             resolved_items: Default::default(),
             inference_data: InferenceData::new(inference_id),
         }
@@ -121,6 +124,7 @@ impl ResolverData {
             module_file_id: self.module_file_id,
             generic_param_by_name: self.generic_param_by_name.clone(),
             generic_params: self.generic_params.clone(),
+            self_impl: self.self_impl,
             resolved_items: self.resolved_items.clone(),
             inference_data: self.inference_data.clone_with_inference_id(db, inference_id),
         }
@@ -173,6 +177,7 @@ impl<'db> Resolver<'db> {
                 module_file_id,
                 generic_param_by_name: Default::default(),
                 generic_params: Default::default(),
+                self_impl: None,
                 resolved_items: Default::default(),
                 inference_data: InferenceData::new(inference_id),
             },
@@ -750,6 +755,12 @@ impl<'db> Resolver<'db> {
             };
             return Some(item);
         }
+        // Inside the default implementation of a trait function, `Self` is the impl using it.
+        if ident == "Self" {
+            if let Some(self_impl) = self.data.self_impl {
+                return Some(ResolvedConcreteItem::Impl(self_impl));
+            }
+        }
         // TODO(spapini): Resolve local variables.
 
         None
@@ -879,7 +890,12 @@ impl<'db> Resolver<'db> {
     }
 
     pub fn impl_lookup_context(&self) -> ImplLookupContext {
-        ImplLookupContext::new(self.module_file_id.0, self.generic_params.clone())
+        let mut lookup_context =
+            ImplLookupContext::new(self.module_file_id.0, self.generic_params.clone());
+        if let Some(ImplId::SelfImpl(concrete_trait_id)) = self.self_impl {
+            lookup_context.self_impl_trait = Some(concrete_trait_id);
+        }
+        lookup_context
     }
 
     pub fn resolve_generic_args(
@@ -965,7 +981,7 @@ impl<'db> Resolver<'db> {
                 diagnostics,
             )?;
             resolved_args.push(generic_arg);
-            substitution.0.insert(generic_param.id(), generic_arg);
+            substitution.param_map.insert(generic_param.id(), generic_arg);
         }
 
         Ok(resolved_args)
@@ -31,17 +31,32 @@ use crate::{
 };
 /// A substitution of generic arguments in generic parameters. Used for concretization.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
-pub struct GenericSubstitution(pub OrderedHashMap<GenericParamId, GenericArgumentId>);
+pub struct GenericSubstitution {
+    pub param_map: OrderedHashMap<GenericParamId, GenericArgumentId>,
+    /// The impl substituted for `Self` inside the default implementations of trait functions.
+    pub self_impl: Option<ImplId>,
+}
 impl GenericSubstitution {
     pub fn new(generic_params: &[GenericParam], generic_args: &[GenericArgumentId]) -> Self {
-        GenericSubstitution(
-            zip_eq(generic_params.iter().map(|param| param.id()), generic_args.iter().copied())
-                .collect(),
-        )
+        GenericSubstitution {
+            param_map: zip_eq(
+                generic_params.iter().map(|param| param.id()),
+                generic_args.iter().copied(),
+            )
+            .collect(),
+            self_impl: None,
+        }
+    }
+    pub fn with_self_impl(mut self, self_impl: ImplId) -> Self {
+        self.self_impl = Some(self_impl);
+        self
     }
     pub fn concat(mut self, other: GenericSubstitution) -> Self {
-        for (key, value) in other.0.into_iter() {
-            self.0.insert(key, value);
+        for (key, value) in other.param_map.into_iter() {
+            self.param_map.insert(key, value);
+        }
+        if other.self_impl.is_some() {
+            self.self_impl = other.self_impl;
         }
         self
     }
@@ -50,13 +65,14 @@ impl Deref for GenericSubstitution {
     type Target = OrderedHashMap<GenericParamId, GenericArgumentId>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.param_map
     }
 }
 #[allow(clippy::derived_hash_with_manual_eq)]
 impl std::hash::Hash for GenericSubstitution {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.0.iter().collect_vec().hash(state);
+        self.param_map.iter().collect_vec().hash(state);
+        self.self_impl.hash(state);
     }
 }
 
@@ -271,6 +287,8 @@ macro_rules! add_expr_rewrites {
         $crate::prune_single!(__regular_helper, ExprMatch, $($exclude)*);
         $crate::prune_single!(__regular_helper, ExprIf, $($exclude)*);
         $crate::prune_single!(__regular_helper, ExprLoop, $($exclude)*);
+        $crate::prune_single!(__regular_helper, ExprWhile, $($exclude)*);
+        $crate::prune_single!(__regular_helper, ExprFor, $($exclude)*);
         $crate::prune_single!(__regular_helper, ExprLiteral, $($exclude)*);
         $crate::prune_single!(__regular_helper, ExprStringLiteral, $($exclude)*);
         $crate::prune_single!(__regular_helper, ExprMemberAccess, $($exclude)*);
@@ -294,6 +312,7 @@ macro_rules! add_expr_rewrites {
         $crate::prune_single!(__regular_helper, PatternStruct, $($exclude)*);
         $crate::prune_single!(__regular_helper, PatternTuple, $($exclude)*);
         $crate::prune_single!(__regular_helper, PatternEnumVariant, $($exclude)*);
+        $crate::prune_single!(__regular_helper, PatternBinding, $($exclude)*);
         $crate::prune_single!(__regular_helper, PatternOtherwise, $($exclude)*);
         $crate::prune_single!(__regular_helper, PatternMissing, $($exclude)*);
         $crate::prune_single!(__regular_helper, LocalVariable, $($exclude)*);
@@ -326,13 +345,21 @@ impl<'a> SemanticRewriter<TypeLongId, DiagnosticAdded> for SubstitutionRewriter<
 }
 impl<'a> SemanticRewriter<ImplId, DiagnosticAdded> for SubstitutionRewriter<'a> {
     fn rewrite(&mut self, value: ImplId) -> Maybe<ImplId> {
-        if let ImplId::GenericParameter(generic_param) = value {
-            if let Some(generic_arg) = self.substitution.get(&generic_param) {
-                let impl_id = *extract_matches!(generic_arg, GenericArgumentId::Impl);
-                // TODO(GIL): Reduce and check for cycles when the substitution is created.
-                // Substitution is guaranteed to not contain its own variables.
-                return Ok(impl_id);
+        match value {
+            ImplId::GenericParameter(generic_param) => {
+                if let Some(generic_arg) = self.substitution.get(&generic_param) {
+                    let impl_id = *extract_matches!(generic_arg, GenericArgumentId::Impl);
+                    // TODO(GIL): Reduce and check for cycles when the substitution is created.
+                    // Substitution is guaranteed to not contain its own variables.
+                    return Ok(impl_id);
+                }
+            }
+            ImplId::SelfImpl(_) => {
+                if let Some(self_impl) = self.substitution.self_impl {
+                    return Ok(self_impl);
+                }
             }
+            _ => {}
         }
         value.default_rewrite(self)
     }
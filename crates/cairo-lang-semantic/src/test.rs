@@ -1,5 +1,7 @@
 use cairo_lang_debug::DebugWithDb;
 use cairo_lang_defs::ids::ModuleItemId;
+use cairo_lang_filesystem::db::{CrateConfiguration, FilesGroup, FilesGroupEx};
+use cairo_lang_filesystem::ids::{CrateLongId, Directory};
 use indoc::indoc;
 
 use crate::db::SemanticGroup;
@@ -31,3 +33,30 @@ fn test_resolve() {
         _ => panic!("Expected a free function"),
     };
 }
+
+#[test]
+fn test_visible_crates() {
+    let mut db_val = SemanticDatabaseForTesting::new_empty();
+    let db = &mut db_val;
+    let [a, b, c, d, not_configured] = ["a", "b", "c", "d", "not_configured"]
+        .map(|name| db.intern_crate(CrateLongId::Real(name.into())));
+    for (crate_id, dependencies) in
+        [(a, Some(vec![b, not_configured])), (b, Some(vec![c])), (c, Some(vec![])), (d, None)]
+    {
+        let root = Directory::Virtual { files: Default::default(), dirs: Default::default() };
+        let config = CrateConfiguration::default_for_root(root);
+        let config = match dependencies {
+            Some(dependencies) => config.with_dependencies(dependencies),
+            None => config,
+        };
+        db.set_crate_config(crate_id, Some(config));
+    }
+    let core = db.core_crate();
+
+    // The configured dependencies are visible, including the transitive ones.
+    assert_eq!(*db.visible_crates(a), vec![a, core, b, c]);
+    assert_eq!(*db.visible_crates(b), vec![b, core, c]);
+    assert_eq!(*db.visible_crates(c), vec![c, core]);
+    // Crates that do not declare their dependencies see all the crates.
+    assert_eq!(*db.visible_crates(d), vec![d, core, a, b, c]);
+}
@@ -12,6 +12,20 @@ pub struct ApChangeInfo {
     pub function_ap_change: OrderedHashMap<FunctionId, usize>,
 }
 
+impl ApChangeInfo {
+    /// Returns the ap change of calling the given function, not including the call itself - or
+    /// `None` if it is unknown.
+    pub fn known_function_ap_change(&self, function_id: &FunctionId) -> Option<usize> {
+        self.function_ap_change.get(function_id).copied()
+    }
+
+    /// Returns the ap alignment required at the given statement - 0 for statements not requiring
+    /// any.
+    pub fn variable_value(&self, idx: &StatementIdx) -> usize {
+        self.variable_values.get(idx).copied().unwrap_or_default()
+    }
+}
+
 impl std::fmt::Display for ApChangeInfo {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for (statement_idx, value) in self
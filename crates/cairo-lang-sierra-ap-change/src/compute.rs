@@ -2,6 +2,7 @@ use std::collections::hash_map::Entry;
 
 use cairo_lang_sierra::algorithm::topological_order::get_topological_ordering;
 use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType};
+use cairo_lang_sierra::extensions::custom::LibfuncExtensions;
 use cairo_lang_sierra::extensions::gas::CostTokenType;
 use cairo_lang_sierra::ids::{ConcreteTypeId, FunctionId};
 use cairo_lang_sierra::program::{Program, Statement, StatementIdx};
@@ -78,8 +79,16 @@ impl<'a, TokenUsages: Fn(StatementIdx, CostTokenType) -> usize>
     ApChangeCalcHelper<'a, TokenUsages>
 {
     /// Creates a new helper.
-    fn new(program: &'a Program, token_usages: TokenUsages) -> Result<Self, ApChangeError> {
-        let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(program)?;
+    fn new(
+        program: &'a Program,
+        libfunc_extensions: &LibfuncExtensions,
+        token_usages: TokenUsages,
+    ) -> Result<Self, ApChangeError> {
+        let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new_with_extensions(
+            program,
+            Default::default(),
+            libfunc_extensions,
+        )?;
         let type_sizes = get_type_size_map(program, &registry).unwrap();
         Ok(Self {
             program,
@@ -354,9 +363,10 @@ impl<'a, TokenUsages: Fn(StatementIdx, CostTokenType) -> usize>
 /// Calculates ap change information for a given program.
 pub fn calc_ap_changes<TokenUsages: Fn(StatementIdx, CostTokenType) -> usize>(
     program: &Program,
+    libfunc_extensions: &LibfuncExtensions,
     token_usages: TokenUsages,
 ) -> Result<ApChangeInfo, ApChangeError> {
-    let mut helper = ApChangeCalcHelper::new(program, token_usages)?;
+    let mut helper = ApChangeCalcHelper::new(program, libfunc_extensions, token_usages)?;
     helper.calc_locals_and_function_ap_changes()?;
     let ap_tracked_topological_ordering = helper.tracked_ap_change_topological_order()?;
     // Seting tracking info for function entry points.
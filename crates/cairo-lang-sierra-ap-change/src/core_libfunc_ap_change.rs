@@ -4,6 +4,7 @@ use cairo_lang_sierra::extensions::boolean::BoolConcreteLibfunc;
 use cairo_lang_sierra::extensions::boxing::BoxConcreteLibfunc;
 use cairo_lang_sierra::extensions::bytes31::Bytes31ConcreteLibfunc;
 use cairo_lang_sierra::extensions::casts::CastConcreteLibfunc;
+use cairo_lang_sierra::extensions::const_type::ConstConcreteLibfunc;
 use cairo_lang_sierra::extensions::core::CoreConcreteLibfunc;
 use cairo_lang_sierra::extensions::ec::EcConcreteLibfunc;
 use cairo_lang_sierra::extensions::enm::EnumConcreteLibfunc;
@@ -16,9 +17,9 @@ use cairo_lang_sierra::extensions::felt252_dict::{
 use cairo_lang_sierra::extensions::gas::{
     BuiltinCostWithdrawGasLibfunc, CostTokenType, GasConcreteLibfunc,
 };
-use cairo_lang_sierra::extensions::int::signed::{SintConcrete, SintTraits};
+use cairo_lang_sierra::extensions::int::signed::{SintConcrete, SmallSintTraits};
 use cairo_lang_sierra::extensions::int::signed128::Sint128Concrete;
-use cairo_lang_sierra::extensions::int::unsigned::{UintConcrete, UintTraits};
+use cairo_lang_sierra::extensions::int::unsigned::{SmallUintTraits, UintConcrete};
 use cairo_lang_sierra::extensions::int::unsigned128::Uint128Concrete;
 use cairo_lang_sierra::extensions::int::unsigned256::Uint256Concrete;
 use cairo_lang_sierra::extensions::int::unsigned512::Uint512Concrete;
@@ -79,9 +80,22 @@ pub fn core_libfunc_ap_change<InfoProvider: InvocationApChangeInfoProvider>(
                     .map(ApChange::Known)
                     .to_vec()
             }
+            ArrayConcreteLibfunc::SnapshotMultiPopFront(libfunc) => {
+                if info_provider.type_size(&libfunc.ty) == 1 { [3, 4] } else { [4, 5] }
+                    .map(ApChange::Known)
+                    .to_vec()
+            }
+            ArrayConcreteLibfunc::SnapshotMultiPopBack(libfunc) => {
+                if info_provider.type_size(&libfunc.ty) == 1 { [4, 4] } else { [5, 5] }
+                    .map(ApChange::Known)
+                    .to_vec()
+            }
             ArrayConcreteLibfunc::Len(libfunc) => {
                 vec![ApChange::Known(if info_provider.type_size(&libfunc.ty) == 1 { 0 } else { 1 })]
             }
+            ArrayConcreteLibfunc::AppendSpan(_) | ArrayConcreteLibfunc::Concat(_) => {
+                vec![ApChange::Unknown, ApChange::Unknown]
+            }
         },
         CoreConcreteLibfunc::BranchAlign(_) => vec![ApChange::FromMetadata],
         CoreConcreteLibfunc::Bool(libfunc) => match libfunc {
@@ -125,6 +139,7 @@ pub fn core_libfunc_ap_change<InfoProvider: InvocationApChangeInfoProvider>(
                 vec![ApChange::Known(if op == Felt252BinaryOperator::Div { 1 } else { 0 })]
             }
             Felt252Concrete::IsZero(_) => vec![ApChange::Known(0), ApChange::Known(0)],
+            Felt252Concrete::Inverse(_) => vec![ApChange::Known(2)],
         },
         CoreConcreteLibfunc::FunctionCall(libfunc) => {
             vec![ApChange::FunctionCall(libfunc.function.id.clone())]
@@ -170,7 +185,11 @@ pub fn core_libfunc_ap_change<InfoProvider: InvocationApChangeInfoProvider>(
         },
         CoreConcreteLibfunc::Uint256(libfunc) => match libfunc {
             Uint256Concrete::IsZero(_) => vec![ApChange::Known(0), ApChange::Known(0)],
+            Uint256Concrete::OverflowingAdd(_) | Uint256Concrete::OverflowingSub(_) => {
+                vec![ApChange::Known(9), ApChange::Known(10)]
+            }
             Uint256Concrete::Divmod(_) => vec![ApChange::Known(19)],
+            Uint256Concrete::WideMul(_) => vec![ApChange::Known(25)],
             Uint256Concrete::SquareRoot(_) => vec![ApChange::Known(25)],
             Uint256Concrete::InvModN(_) => vec![ApChange::Known(46), ApChange::Known(14)],
         },
@@ -213,6 +232,12 @@ pub fn core_libfunc_ap_change<InfoProvider: InvocationApChangeInfoProvider>(
             EnumConcreteLibfunc::Match(libfunc) | EnumConcreteLibfunc::SnapshotMatch(libfunc) => {
                 vec![ApChange::Known(0); libfunc.signature.branch_signatures.len()]
             }
+            EnumConcreteLibfunc::ToFelt252(libfunc) => {
+                vec![ApChange::Known(libfunc.discriminants.len())]
+            }
+            EnumConcreteLibfunc::TryFromFelt252(libfunc) => {
+                vec![ApChange::Known(libfunc.discriminants.len() + 1); 2]
+            }
         },
         CoreConcreteLibfunc::Struct(libfunc) => match libfunc {
             StructConcreteLibfunc::Construct(_)
@@ -282,6 +307,10 @@ pub fn core_libfunc_ap_change<InfoProvider: InvocationApChangeInfoProvider>(
             Felt252DictEntryConcreteLibfunc::Get(_) => vec![ApChange::Known(0)],
             Felt252DictEntryConcreteLibfunc::Finalize(_) => vec![ApChange::Known(0)],
         },
+        CoreConcreteLibfunc::Const(libfunc) => match libfunc {
+            ConstConcreteLibfunc::AsBox(_) => vec![ApChange::Known(3)],
+            ConstConcreteLibfunc::AsImmediate(_) => vec![ApChange::Known(0)],
+        },
         CoreConcreteLibfunc::Bytes31(libfunc) => match libfunc {
             Bytes31ConcreteLibfunc::Const(_) | Bytes31ConcreteLibfunc::ToFelt252(_) => {
                 vec![ApChange::Known(0)]
@@ -290,11 +319,14 @@ pub fn core_libfunc_ap_change<InfoProvider: InvocationApChangeInfoProvider>(
                 vec![ApChange::Known(5), ApChange::Known(6)]
             }
         },
+        CoreConcreteLibfunc::Custom(libfunc) => {
+            libfunc.branch_costs.iter().map(|cost| ApChange::Known(cost.ap_change)).collect()
+        }
     }
 }
 
 /// Returns the ap changes for u8/u16/u32/u64 libfuncs.
-fn uint_ap_change<TUintTraits: UintTraits + IntMulTraits + IsZeroTraits>(
+fn uint_ap_change<TUintTraits: SmallUintTraits + IntMulTraits + IsZeroTraits>(
     libfunc: &UintConcrete<TUintTraits>,
 ) -> Vec<ApChange> {
     match libfunc {
@@ -313,12 +345,15 @@ fn uint_ap_change<TUintTraits: UintTraits + IntMulTraits + IsZeroTraits>(
         UintConcrete::IsZero(_) => vec![ApChange::Known(0), ApChange::Known(0)],
         UintConcrete::Divmod(_) => vec![ApChange::Known(5)],
         UintConcrete::WideMul(_) => vec![ApChange::Known(0)],
+        UintConcrete::CheckedMul(_) => vec![ApChange::Known(3), ApChange::Known(3)],
+        UintConcrete::LessThan(_) => vec![ApChange::Known(2), ApChange::Known(3)],
+        UintConcrete::LessThanOrEqual(_) => vec![ApChange::Known(3), ApChange::Known(2)],
         UintConcrete::Bitwise(_) => vec![ApChange::Known(0)],
     }
 }
 
 /// Returns the ap changes for s8/s16/s32/s64 libfuncs.
-fn sint_ap_change<TSintTraits: SintTraits + IntMulTraits + IsZeroTraits>(
+fn sint_ap_change<TSintTraits: SmallSintTraits + IntMulTraits + IsZeroTraits>(
     libfunc: &SintConcrete<TSintTraits>,
 ) -> Vec<ApChange> {
     match libfunc {
@@ -331,5 +366,6 @@ fn sint_ap_change<TSintTraits: SintTraits + IntMulTraits + IsZeroTraits>(
             vec![ApChange::Known(4), ApChange::Known(4), ApChange::Known(4)]
         }
         SintConcrete::Diff(_) => vec![ApChange::Known(2), ApChange::Known(3)],
+        SintConcrete::DivRem(_) => vec![ApChange::Known(19), ApChange::Known(19)],
     }
 }
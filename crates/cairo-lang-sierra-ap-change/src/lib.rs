@@ -1,6 +1,13 @@
 //! Sierra AP change model.
+//!
+//! The ap-change analysis of a Sierra program finds the change of the `ap` register caused by
+//! calling each of its functions, when it is known, and the ap alignment required at each
+//! `branch_align` statement. Its results are used for computing the costs of a program, for
+//! compiling it to CASM, and for verifying compiled programs - all should run it through
+//! [analyze_ap_changes].
 use ap_change_info::ApChangeInfo;
 use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType};
+use cairo_lang_sierra::extensions::custom::LibfuncExtensions;
 use cairo_lang_sierra::extensions::gas::CostTokenType;
 use cairo_lang_sierra::ids::{ConcreteTypeId, FunctionId};
 use cairo_lang_sierra::program::{Program, StatementIdx};
@@ -19,6 +26,9 @@ pub mod compute;
 pub mod core_libfunc_ap_change;
 mod generate_equations;
 
+#[cfg(test)]
+mod test;
+
 /// Describes the effect on the `ap` register in a given libfunc branch.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ApChange {
@@ -60,6 +70,36 @@ pub enum ApChangeError {
     SolvingApChangeEquationFailed,
 }
 
+/// The algorithm used by the ap-change analysis.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ApChangeSolver {
+    /// Generates equations for the ap changes and solves them.
+    #[default]
+    EquationSolver,
+    /// Computes the ap changes directly, in linear time - see [compute::calc_ap_changes].
+    Linear,
+}
+
+/// Runs the ap-change analysis of a program with the given solver.
+///
+/// `token_usages` provides the usages of each token at each statement, as found by the pre-cost
+/// solver - libfuncs using builtins may change `ap` according to the number of usages.
+pub fn analyze_ap_changes<TokenUsages: Fn(StatementIdx, CostTokenType) -> usize>(
+    program: &Program,
+    libfunc_extensions: &LibfuncExtensions,
+    solver: ApChangeSolver,
+    token_usages: TokenUsages,
+) -> Result<ApChangeInfo, ApChangeError> {
+    match solver {
+        ApChangeSolver::EquationSolver => {
+            calc_ap_changes(program, libfunc_extensions, token_usages)
+        }
+        ApChangeSolver::Linear => {
+            compute::calc_ap_changes(program, libfunc_extensions, token_usages)
+        }
+    }
+}
+
 /// Helper to implement the `InvocationApChangeInfoProvider` for the equation generation.
 struct InvocationApChangeInfoProviderForEqGen<'a, TokenUsages: Fn(CostTokenType) -> usize> {
     /// Registry for providing the sizes of the types.
@@ -83,9 +123,14 @@ impl<'a, TokenUsages: Fn(CostTokenType) -> usize> InvocationApChangeInfoProvider
 /// Calculates gas information for a given program.
 pub fn calc_ap_changes<TokenUsages: Fn(StatementIdx, CostTokenType) -> usize>(
     program: &Program,
+    libfunc_extensions: &LibfuncExtensions,
     token_usages: TokenUsages,
 ) -> Result<ApChangeInfo, ApChangeError> {
-    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(program)?;
+    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new_with_extensions(
+        program,
+        Default::default(),
+        libfunc_extensions,
+    )?;
     let type_sizes = get_type_size_map(program, &registry).unwrap();
     let equations = generate_equations::generate_equations(program, |idx, libfunc_id| {
         let libfunc = registry.get_libfunc(libfunc_id)?;
@@ -0,0 +1,37 @@
+use cairo_lang_sierra::ProgramParser;
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+
+use crate::{analyze_ap_changes, ApChangeSolver};
+
+cairo_lang_test_utils::test_file_test!(
+    test_ap_changes,
+    "src/test_data",
+    {
+        ap_changes :"ap_changes",
+    },
+    test_ap_changes
+);
+
+fn test_ap_changes(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let program = ProgramParser::new().parse(&inputs["program"]).unwrap();
+    let ap_change_info =
+        analyze_ap_changes(&program, &Default::default(), ApChangeSolver::EquationSolver, |_, _| 0)
+            .unwrap();
+    let linear_ap_change_info =
+        analyze_ap_changes(&program, &Default::default(), ApChangeSolver::Linear, |_, _| 0)
+            .unwrap();
+    // The equation solver also provides the alignments of 0, so only the displays are compared.
+    assert_eq!(
+        linear_ap_change_info.to_string(),
+        ap_change_info.to_string(),
+        "The ap-change solvers disagree."
+    );
+    TestRunnerResult::success(OrderedHashMap::from([(
+        "ap_change_info".into(),
+        ap_change_info.to_string(),
+    )]))
+}
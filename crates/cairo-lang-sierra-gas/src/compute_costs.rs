@@ -685,6 +685,15 @@ impl SpecificCostContextTrait<PreCost> for PreCostContext {
     }
 }
 
+/// Returns the cost of a `branch_align` statement, which aligns the ap by `ap_change`.
+pub fn branch_align_cost(ap_change: usize) -> ConstCost {
+    if ap_change == 0 {
+        ConstCost::default()
+    } else {
+        ConstCost { steps: 1, holes: ap_change as i32, range_checks: 0 }
+    }
+}
+
 pub struct PostcostContext<'a> {
     pub get_ap_change_fn: &'a dyn Fn(&StatementIdx) -> usize,
     pub precost_gas_info: &'a GasInfo,
@@ -729,14 +738,7 @@ impl<'a> SpecificCostContextTrait<i32> for PostcostContext<'a> {
     ) -> WalletInfo<i32> {
         let branch_cost_val = match branch_cost {
             BranchCost::Regular { const_cost, pre_cost: _ } => const_cost.cost(),
-            BranchCost::BranchAlign => {
-                let ap_change = (self.get_ap_change_fn)(idx);
-                if ap_change == 0 {
-                    0
-                } else {
-                    ConstCost { steps: 1, holes: ap_change as i32, range_checks: 0 }.cost()
-                }
-            }
+            BranchCost::BranchAlign => branch_align_cost((self.get_ap_change_fn)(idx)).cost(),
             BranchCost::FunctionCall { const_cost, function } => {
                 wallet_at_fn(&function.entry_point).value + const_cost.cost()
             }
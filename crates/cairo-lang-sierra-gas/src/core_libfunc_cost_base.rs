@@ -5,6 +5,7 @@ use cairo_lang_sierra::extensions::boolean::BoolConcreteLibfunc;
 use cairo_lang_sierra::extensions::boxing::BoxConcreteLibfunc;
 use cairo_lang_sierra::extensions::bytes31::Bytes31ConcreteLibfunc;
 use cairo_lang_sierra::extensions::casts::CastConcreteLibfunc;
+use cairo_lang_sierra::extensions::const_type::ConstConcreteLibfunc;
 use cairo_lang_sierra::extensions::core::CoreConcreteLibfunc::{self, *};
 use cairo_lang_sierra::extensions::ec::EcConcreteLibfunc;
 use cairo_lang_sierra::extensions::enm::EnumConcreteLibfunc;
@@ -19,9 +20,9 @@ use cairo_lang_sierra::extensions::gas::GasConcreteLibfunc::{
     BuiltinWithdrawGas, GetAvailableGas, GetBuiltinCosts, RedepositGas, WithdrawGas,
 };
 use cairo_lang_sierra::extensions::gas::{BuiltinCostWithdrawGasLibfunc, CostTokenType};
-use cairo_lang_sierra::extensions::int::signed::{SintConcrete, SintTraits};
+use cairo_lang_sierra::extensions::int::signed::{SintConcrete, SmallSintTraits};
 use cairo_lang_sierra::extensions::int::signed128::Sint128Concrete;
-use cairo_lang_sierra::extensions::int::unsigned::{UintConcrete, UintTraits};
+use cairo_lang_sierra::extensions::int::unsigned::{SmallUintTraits, UintConcrete};
 use cairo_lang_sierra::extensions::int::unsigned128::Uint128Concrete;
 use cairo_lang_sierra::extensions::int::unsigned256::Uint256Concrete;
 use cairo_lang_sierra::extensions::int::unsigned512::Uint512Concrete;
@@ -59,6 +60,33 @@ pub const DICT_SQUASH_FIXED_COST: ConstCost = ConstCost { steps: 57, holes: 0, r
 pub const SEGMENT_ARENA_ALLOCATION_COST: ConstCost =
     ConstCost { steps: 8, holes: 0, range_checks: 0 };
 
+/// The cost of copying a single element of the given size in `array_append_span` and
+/// `array_concat`. This cost is not charged statically, but withdrawn from the gas counter by the
+/// libfuncs for each copied element.
+pub const fn array_copy_element_cost(element_size: i32) -> ConstCost {
+    ConstCost::steps(2 * element_size + 4)
+}
+
+/// The costs of `array_append_span`, by branch, not dependent on the number of copied elements.
+pub const fn array_append_span_fixed_costs(element_size: i32) -> [ConstCost; 2] {
+    // Computing the number of elements from the number of cells takes another step.
+    let division_steps = if element_size == 1 { 0 } else { 1 };
+    [
+        ConstCost { steps: 21 + division_steps, holes: 0, range_checks: 1 },
+        ConstCost { steps: 7 + division_steps, holes: 0, range_checks: 1 },
+    ]
+}
+
+/// The costs of `array_concat`, by branch, not dependent on the number of copied elements.
+pub const fn array_concat_fixed_costs(element_size: i32) -> [ConstCost; 2] {
+    // Computing the number of elements from the number of cells takes another step.
+    let division_steps = if element_size == 1 { 0 } else { 1 };
+    [
+        ConstCost { steps: 28 + division_steps, holes: 0, range_checks: 1 },
+        ConstCost { steps: 9 + division_steps, holes: 0, range_checks: 1 },
+    ]
+}
+
 /// The operation required for extracting a libfunc's cost.
 pub trait CostOperations {
     type CostType: Clone;
@@ -240,12 +268,39 @@ pub fn core_libfunc_cost(
                     ]
                 }
             }
+            ArrayConcreteLibfunc::SnapshotMultiPopFront(libfunc) => {
+                let (success_steps, failure_steps) =
+                    if info_provider.type_size(&libfunc.ty) == 1 { (4, 6) } else { (5, 7) };
+                vec![
+                    (ConstCost::steps(success_steps) + ConstCost::range_checks(1)).into(),
+                    (ConstCost::steps(failure_steps) + ConstCost::range_checks(1)).into(),
+                ]
+            }
+            ArrayConcreteLibfunc::SnapshotMultiPopBack(libfunc) => {
+                let (success_steps, failure_steps) =
+                    if info_provider.type_size(&libfunc.ty) == 1 { (5, 6) } else { (6, 7) };
+                vec![
+                    (ConstCost::steps(success_steps) + ConstCost::range_checks(1)).into(),
+                    (ConstCost::steps(failure_steps) + ConstCost::range_checks(1)).into(),
+                ]
+            }
             ArrayConcreteLibfunc::Len(libfunc) => {
                 vec![
                     ConstCost::steps(if info_provider.type_size(&libfunc.ty) == 1 { 0 } else { 1 })
                         .into(),
                 ]
             }
+            // The cost of copying the elements is withdrawn from the gas counter by the libfuncs.
+            ArrayConcreteLibfunc::AppendSpan(libfunc) => {
+                array_append_span_fixed_costs(info_provider.type_size(&libfunc.ty) as i32)
+                    .map(BranchCost::from)
+                    .to_vec()
+            }
+            ArrayConcreteLibfunc::Concat(libfunc) => {
+                array_concat_fixed_costs(info_provider.type_size(&libfunc.ty) as i32)
+                    .map(BranchCost::from)
+                    .to_vec()
+            }
         },
         Uint8(libfunc) => uint_libfunc_cost(libfunc),
         Uint16(libfunc) => uint_libfunc_cost(libfunc),
@@ -307,6 +362,16 @@ pub fn core_libfunc_cost(
                     .collect_vec(),
                 }
             }
+            EnumConcreteLibfunc::ToFelt252(libfunc) => {
+                // One comparison for each variant but the last, and an additional jump unless
+                // there is a single variant.
+                let n = libfunc.discriminants.len() as i32;
+                vec![ConstCost::steps(2 * n + if n > 1 { 1 } else { 0 }).into()]
+            }
+            EnumConcreteLibfunc::TryFromFelt252(libfunc) => {
+                let n = libfunc.discriminants.len() as i32;
+                vec![ConstCost::steps(2 * n + 3).into(), ConstCost::steps(2 * n + 2).into()]
+            }
         },
         Struct(
             StructConcreteLibfunc::Construct(_)
@@ -366,6 +431,10 @@ pub fn core_libfunc_cost(
             }
             Felt252DictEntryConcreteLibfunc::Finalize(_) => vec![ConstCost::steps(1).into()],
         },
+        CoreConcreteLibfunc::Const(libfunc) => match libfunc {
+            ConstConcreteLibfunc::AsBox(_) => vec![ConstCost::steps(3).into()],
+            ConstConcreteLibfunc::AsImmediate(_) => vec![ConstCost::default().into()],
+        },
         CoreConcreteLibfunc::Bytes31(libfunc) => match libfunc {
             Bytes31ConcreteLibfunc::Const(_) | Bytes31ConcreteLibfunc::ToFelt252(_) => {
                 vec![ConstCost::default().into()]
@@ -375,6 +444,14 @@ pub fn core_libfunc_cost(
                 (ConstCost { steps: 9, holes: 0, range_checks: 3 }).into(),
             ],
         },
+        Custom(libfunc) => libfunc
+            .branch_costs
+            .iter()
+            .map(|cost| {
+                ConstCost { steps: cost.steps, holes: cost.holes, range_checks: cost.range_checks }
+                    .into()
+            })
+            .collect(),
     }
 }
 
@@ -491,7 +568,7 @@ fn statement_vars_cost<'a, Ops: CostOperations, TokenTypes: Iterator<Item = &'a
 }
 
 /// Returns costs for u64/u32/u16/u8 libfuncs.
-fn uint_libfunc_cost<TUintTraits: UintTraits + IsZeroTraits + IntMulTraits>(
+fn uint_libfunc_cost<TUintTraits: SmallUintTraits + IsZeroTraits + IntMulTraits>(
     libfunc: &UintConcrete<TUintTraits>,
 ) -> Vec<BranchCost> {
     match libfunc {
@@ -528,6 +605,24 @@ fn uint_libfunc_cost<TUintTraits: UintTraits + IsZeroTraits + IntMulTraits>(
         UintConcrete::Divmod(_) => {
             vec![BranchCost::from(ConstCost { steps: 7, holes: 0, range_checks: 3 })]
         }
+        UintConcrete::CheckedMul(_) => {
+            vec![
+                (ConstCost { steps: 4, holes: 0, range_checks: 1 }).into(),
+                (ConstCost { steps: 5, holes: 0, range_checks: 1 }).into(),
+            ]
+        }
+        UintConcrete::LessThan(_) => {
+            vec![
+                (ConstCost { steps: 3, holes: 0, range_checks: 1 }).into(),
+                (ConstCost { steps: 5, holes: 0, range_checks: 1 }).into(),
+            ]
+        }
+        UintConcrete::LessThanOrEqual(_) => {
+            vec![
+                (ConstCost { steps: 4, holes: 0, range_checks: 1 }).into(),
+                (ConstCost { steps: 4, holes: 0, range_checks: 1 }).into(),
+            ]
+        }
         UintConcrete::Bitwise(_) => {
             vec![BranchCost::Regular {
                 const_cost: ConstCost::steps(2),
@@ -597,7 +692,12 @@ fn u256_libfunc_cost(libfunc: &Uint256Concrete) -> Vec<ConstCost> {
         Uint256Concrete::IsZero(_) => {
             vec![steps(2), steps(2)]
         }
+        Uint256Concrete::OverflowingAdd(_) | Uint256Concrete::OverflowingSub(_) => vec![
+            ConstCost { steps: 11, holes: 0, range_checks: 2 },
+            ConstCost { steps: 13, holes: 0, range_checks: 2 },
+        ],
         Uint256Concrete::Divmod(_) => vec![ConstCost { steps: 26, holes: 0, range_checks: 6 }],
+        Uint256Concrete::WideMul(_) => vec![ConstCost { steps: 20, holes: 0, range_checks: 7 }],
         Uint256Concrete::SquareRoot(_) => vec![ConstCost { steps: 30, holes: 0, range_checks: 7 }],
         Uint256Concrete::InvModN(_) => vec![
             ConstCost { steps: 40, holes: 0, range_checks: 9 },
@@ -614,7 +714,7 @@ fn u512_libfunc_cost(libfunc: &Uint512Concrete) -> Vec<ConstCost> {
 }
 
 /// Returns costs for i64/i32/i16/i8 libfuncs.
-fn sint_libfunc_cost<TSintTraits: SintTraits + IsZeroTraits + IntMulTraits>(
+fn sint_libfunc_cost<TSintTraits: SmallSintTraits + IsZeroTraits + IntMulTraits>(
     libfunc: &SintConcrete<TSintTraits>,
 ) -> Vec<BranchCost> {
     match libfunc {
@@ -640,6 +740,10 @@ fn sint_libfunc_cost<TSintTraits: SintTraits + IsZeroTraits + IntMulTraits>(
             (ConstCost { steps: 3, holes: 0, range_checks: 1 }).into(),
             (ConstCost { steps: 5, holes: 0, range_checks: 1 }).into(),
         ],
+        SintConcrete::DivRem(_) => vec![
+            ConstCost { steps: 20, holes: 0, range_checks: 5 }.into(),
+            ConstCost { steps: 21, holes: 0, range_checks: 5 }.into(),
+        ],
     }
 }
 
@@ -692,5 +796,6 @@ fn felt252_libfunc_cost(libfunc: &Felt252Concrete) -> Vec<ConstCost> {
         Felt252Concrete::IsZero(_) => {
             vec![ConstCost::steps(1), ConstCost::steps(1)]
         }
+        Felt252Concrete::Inverse(_) => vec![ConstCost::steps(2)],
     }
 }
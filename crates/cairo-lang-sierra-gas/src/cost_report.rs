@@ -0,0 +1,165 @@
+//! Reports of the costs of the statements and functions of a Sierra program, for gas estimation
+//! tooling.
+
+use std::fmt::Display;
+
+use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType};
+use cairo_lang_sierra::extensions::gas::CostTokenType;
+use cairo_lang_sierra::ids::FunctionId;
+use cairo_lang_sierra::program::{Program, Statement, StatementIdx};
+use cairo_lang_sierra::program_registry::ProgramRegistry;
+use cairo_lang_sierra_type_size::get_type_size_map;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::compute_costs::branch_align_cost;
+use crate::core_libfunc_cost_base::core_libfunc_cost;
+use crate::gas_info::GasInfo;
+use crate::objects::{BranchCost, ConstCost};
+use crate::CostError;
+
+/// The costs of a Sierra program, by statement and by function.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct CostReport {
+    /// The costs of the statements, ordered by their index.
+    pub statements: Vec<StatementCostReport>,
+    /// The costs of the functions, in their order in the program.
+    pub functions: Vec<FunctionCostReport>,
+}
+
+/// The cost of a single statement.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct StatementCostReport {
+    pub idx: StatementIdx,
+    /// The costs of the branches of the invoked libfunc - empty for `return` statements.
+    pub branches: Vec<BranchCostReport>,
+    /// The solved values of the gas variables of the statement - e.g. the amounts withdrawn by
+    /// `withdraw_gas` or the amounts redeposited by `redeposit_gas`.
+    pub gas_variables: OrderedHashMap<CostTokenType, i64>,
+}
+
+/// The cost of a single branch of a libfunc invocation.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct BranchCostReport {
+    pub steps: i32,
+    pub holes: i32,
+    pub range_checks: i32,
+    /// The usages of the builtins other than the range check.
+    pub builtins: OrderedHashMap<CostTokenType, i32>,
+    /// The function called in the branch - the cost of running it is not included.
+    pub called_function: Option<FunctionId>,
+}
+impl BranchCostReport {
+    fn new(branch_cost: BranchCost, ap_change: usize) -> Self {
+        let (const_cost, builtins, called_function) = match branch_cost {
+            BranchCost::Regular { const_cost, pre_cost } => (const_cost, pre_cost.0, None),
+            BranchCost::FunctionCall { const_cost, function } => {
+                (const_cost, Default::default(), Some(function.id))
+            }
+            BranchCost::BranchAlign => (branch_align_cost(ap_change), Default::default(), None),
+            BranchCost::WithdrawGas { const_cost, .. } => (const_cost, Default::default(), None),
+            BranchCost::RedepositGas => Default::default(),
+        };
+        let ConstCost { steps, holes, range_checks } = const_cost;
+        Self { steps, holes, range_checks, builtins, called_function }
+    }
+}
+
+/// The cost of a single function.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct FunctionCostReport {
+    pub id: FunctionId,
+    pub entry_point: StatementIdx,
+    /// The solved costs of calling the function.
+    pub costs: OrderedHashMap<CostTokenType, i64>,
+}
+
+/// Computes the cost report of a program, given the solution of its costs - `gas_info` is expected
+/// to be the combination of the pre-cost and post-cost solutions.
+///
+/// `get_ap_change_fn` provides the ap change fixed by each `branch_align` statement.
+pub fn compute_cost_report(
+    program: &Program,
+    gas_info: &GasInfo,
+    get_ap_change_fn: &dyn Fn(&StatementIdx) -> usize,
+) -> Result<CostReport, CostError> {
+    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(program)?;
+    let type_sizes = get_type_size_map(program, &registry).unwrap();
+    let mut gas_variables: OrderedHashMap<StatementIdx, OrderedHashMap<CostTokenType, i64>> =
+        Default::default();
+    for ((idx, token_type), value) in gas_info.variable_values.iter() {
+        gas_variables.entry(*idx).or_default().insert(*token_type, *value);
+    }
+    let statements = program
+        .statements
+        .iter()
+        .enumerate()
+        .map(|(idx, statement)| {
+            let idx = StatementIdx(idx);
+            let branches = match statement {
+                Statement::Invocation(invocation) => {
+                    let libfunc = registry.get_libfunc(&invocation.libfunc_id)?;
+                    core_libfunc_cost(libfunc, &type_sizes)
+                        .into_iter()
+                        .map(|branch_cost| {
+                            BranchCostReport::new(branch_cost, get_ap_change_fn(&idx))
+                        })
+                        .collect()
+                }
+                Statement::Return(_) => vec![],
+            };
+            let gas_variables = gas_variables.get(&idx).cloned().unwrap_or_default();
+            Ok(StatementCostReport { idx, branches, gas_variables })
+        })
+        .collect::<Result<_, CostError>>()?;
+    let functions = program
+        .funcs
+        .iter()
+        .map(|func| FunctionCostReport {
+            id: func.id.clone(),
+            entry_point: func.entry_point,
+            costs: gas_info.function_costs.get(&func.id).cloned().unwrap_or_default(),
+        })
+        .collect();
+    Ok(CostReport { statements, functions })
+}
+
+impl Display for CostReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for statement in &self.statements {
+            write!(f, "#{}:", statement.idx)?;
+            for branch in &statement.branches {
+                write!(f, " {branch};")?;
+            }
+            if !statement.gas_variables.is_empty() {
+                write!(f, " gas: {:?}", statement.gas_variables)?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f)?;
+        for function in &self.functions {
+            writeln!(f, "{}@{}: {:?}", function.id, function.entry_point, function.costs)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for BranchCostReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let counts =
+            [("steps", self.steps), ("holes", self.holes), ("range_checks", self.range_checks)]
+                .into_iter()
+                .filter(|(_, count)| *count != 0)
+                .map(|(name, count)| format!("{name}: {count}"))
+                .chain(
+                    self.builtins
+                        .iter()
+                        .filter(|(_, count)| **count != 0)
+                        .map(|(token_type, count)| format!("{token_type:?}: {count}")),
+                )
+                .chain(self.called_function.iter().map(|function| format!("call: {function}")))
+                .join(", ");
+        write!(f, "[{counts}]")
+    }
+}
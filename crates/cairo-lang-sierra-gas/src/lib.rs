@@ -4,6 +4,7 @@
 
 use cairo_lang_eq_solver::Expr;
 use cairo_lang_sierra::extensions::core::{CoreConcreteLibfunc, CoreLibfunc, CoreType};
+use cairo_lang_sierra::extensions::custom::LibfuncExtensions;
 use cairo_lang_sierra::extensions::gas::{CostTokenType, GasConcreteLibfunc};
 use cairo_lang_sierra::ids::{ConcreteLibfuncId, ConcreteTypeId, FunctionId};
 use cairo_lang_sierra::program::{Program, Statement, StatementIdx};
@@ -26,6 +27,7 @@ pub mod core_libfunc_cost;
 mod core_libfunc_cost_base;
 mod core_libfunc_cost_expr;
 mod cost_expr;
+pub mod cost_report;
 pub mod gas_info;
 mod generate_equations;
 pub mod objects;
@@ -91,9 +93,14 @@ impl CostInfoProvider for TypeSizeMap {
 // TODO(lior): Remove this function once [compute_precost_info] is used.
 pub fn calc_gas_precost_info(
     program: &Program,
+    libfunc_extensions: &LibfuncExtensions,
     function_set_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
 ) -> Result<GasInfo, CostError> {
-    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(program)?;
+    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new_with_extensions(
+        program,
+        Default::default(),
+        libfunc_extensions,
+    )?;
     calc_gas_info_inner(
         program,
         |statement_future_cost, idx, libfunc_id| -> Vec<OrderedHashMap<CostTokenType, Expr<Var>>> {
@@ -108,8 +115,15 @@ pub fn calc_gas_precost_info(
 }
 
 /// Calculates gas pre-cost information for a given program - the gas costs of non-step tokens.
-pub fn compute_precost_info(program: &Program) -> Result<GasInfo, CostError> {
-    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(program)?;
+pub fn compute_precost_info(
+    program: &Program,
+    libfunc_extensions: &LibfuncExtensions,
+) -> Result<GasInfo, CostError> {
+    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new_with_extensions(
+        program,
+        Default::default(),
+        libfunc_extensions,
+    )?;
     let type_sizes = get_type_size_map(program, &registry).unwrap();
 
     compute_costs::compute_costs(
@@ -129,11 +143,16 @@ pub fn compute_precost_info(program: &Program) -> Result<GasInfo, CostError> {
 // TODO(lior): Remove this function once [compute_postcost_info] is used.
 pub fn calc_gas_postcost_info<ApChangeVarValue: Fn(StatementIdx) -> usize>(
     program: &Program,
+    libfunc_extensions: &LibfuncExtensions,
     function_set_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
     precost_gas_info: &GasInfo,
     ap_change_var_value: ApChangeVarValue,
 ) -> Result<GasInfo, CostError> {
-    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(program)?;
+    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new_with_extensions(
+        program,
+        Default::default(),
+        libfunc_extensions,
+    )?;
     let type_sizes = get_type_size_map(program, &registry).unwrap();
     calc_gas_info_inner(
         program,
@@ -265,11 +284,16 @@ fn calc_gas_info_inner<
 /// Calculates gas postcost information for a given program - the gas costs of step token.
 pub fn compute_postcost_info(
     program: &Program,
+    libfunc_extensions: &LibfuncExtensions,
     get_ap_change_fn: &dyn Fn(&StatementIdx) -> usize,
     precost_gas_info: &GasInfo,
     enforced_function_costs: &OrderedHashMap<FunctionId, i32>,
 ) -> Result<GasInfo, CostError> {
-    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(program)?;
+    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new_with_extensions(
+        program,
+        Default::default(),
+        libfunc_extensions,
+    )?;
     let type_size_map = get_type_size_map(program, &registry).unwrap();
     let specific_cost_context =
         compute_costs::PostcostContext { get_ap_change_fn, precost_gas_info };
@@ -5,6 +5,7 @@ use cairo_lang_sierra::program::Program;
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 
+use crate::cost_report::compute_cost_report;
 use crate::{calc_gas_postcost_info, calc_gas_precost_info};
 
 cairo_lang_test_utils::test_file_test!(
@@ -16,6 +17,15 @@ cairo_lang_test_utils::test_file_test!(
     test_solve_gas
 );
 
+cairo_lang_test_utils::test_file_test!(
+    test_cost_report,
+    "src/test_data",
+    {
+        cost_report :"cost_report",
+    },
+    test_cost_report
+);
+
 /// Returns a parsed example program from the example directory.
 fn get_example_program(name: &str) -> Program {
     // Pop the "/sierra_gas" suffix.
@@ -31,9 +41,16 @@ fn test_solve_gas(
     let path = &inputs["test_file_name"];
     let program = get_example_program(path);
 
-    let gas_info0 = calc_gas_precost_info(&program, Default::default()).unwrap();
-    let gas_info1 =
-        calc_gas_postcost_info(&program, Default::default(), &gas_info0, |_| 0).unwrap();
+    let gas_info0 =
+        calc_gas_precost_info(&program, &Default::default(), Default::default()).unwrap();
+    let gas_info1 = calc_gas_postcost_info(
+        &program,
+        &Default::default(),
+        Default::default(),
+        &gas_info0,
+        |_| 0,
+    )
+    .unwrap();
     let gas_info = gas_info0.combine(gas_info1);
 
     TestRunnerResult::success(OrderedHashMap::from([(
@@ -41,3 +58,26 @@ fn test_solve_gas(
         format!("{gas_info}"),
     )]))
 }
+
+fn test_cost_report(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let path = &inputs["test_file_name"];
+    let program = get_example_program(path);
+
+    let gas_info0 =
+        calc_gas_precost_info(&program, &Default::default(), Default::default()).unwrap();
+    let gas_info1 = calc_gas_postcost_info(
+        &program,
+        &Default::default(),
+        Default::default(),
+        &gas_info0,
+        |_| 0,
+    )
+    .unwrap();
+    let gas_info = gas_info0.combine(gas_info1);
+    let report = compute_cost_report(&program, &gas_info, &|_| 0).unwrap();
+
+    TestRunnerResult::success(OrderedHashMap::from([("cost_report".into(), format!("{report}"))]))
+}
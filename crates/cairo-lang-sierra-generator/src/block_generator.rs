@@ -3,6 +3,7 @@
 mod test;
 
 use cairo_lang_diagnostics::Maybe;
+use cairo_lang_utils::ensure_sufficient_stack;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use itertools::{chain, enumerate, zip_eq};
 use lowering::borrow_check::analysis::StatementLocation;
@@ -128,7 +129,8 @@ pub fn generate_block_code(
                     id: *context.block_label(*target_block_id),
                 }));
 
-                let code = generate_block_code(context, *target_block_id)?;
+                let code =
+                    ensure_sufficient_stack(|| generate_block_code(context, *target_block_id))?;
                 statements.extend(code);
             } else {
                 statements.push(jump_statement(
@@ -469,7 +471,7 @@ fn generate_match_code(
         // Add branch_align to equalize gas costs across the merging paths.
         statements.push(simple_statement(branch_align_libfunc_id(context.get_db()), &[], &[]));
 
-        let code = generate_block_code(context, *block_id)?;
+        let code = ensure_sufficient_stack(|| generate_block_code(context, *block_id))?;
         statements.extend(code);
     }
 
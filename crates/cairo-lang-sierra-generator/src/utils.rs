@@ -325,6 +325,24 @@ pub fn get_concrete_libfunc_id(
             }
         });
     }
+    // The enum conversion libfuncs also get the discriminants of the variants of the enum.
+    if ["enum_to_felt252", "enum_try_from_felt252"].contains(&extern_id.name(db.upcast()).as_str())
+    {
+        if let [semantic::GenericArgumentId::Type(ty)] = concrete_function.generic_args[..] {
+            let discriminants = c_like_enum_discriminants(db, ty).unwrap_or_default();
+            generic_args.extend(discriminants.into_iter().map(program::GenericArg::Value));
+        }
+    }
 
     (None, generic_libfunc_id(db, extern_id, generic_args))
 }
+
+/// Returns the discriminants of the variants of the given type, if it is a C-like enum.
+fn c_like_enum_discriminants(db: &dyn SierraGenGroup, ty: semantic::TypeId) -> Option<Vec<BigInt>> {
+    let semantic::TypeLongId::Concrete(semantic::ConcreteTypeId::Enum(concrete_enum_id)) =
+        db.lookup_intern_type(ty)
+    else {
+        return None;
+    };
+    db.enum_discriminants(concrete_enum_id.enum_id(db.upcast())).ok().flatten()
+}
@@ -5,7 +5,7 @@ use cairo_lang_casm::ap_change::{ApChangeError, ApplyApChange};
 use cairo_lang_sierra::edit_state::{put_results, take_args};
 use cairo_lang_sierra::ids::{FunctionId, VarId};
 use cairo_lang_sierra::program::{BranchInfo, Function, StatementIdx};
-use cairo_lang_sierra_type_size::TypeSizeMap;
+use cairo_lang_sierra_type_size::TypeLayoutMap;
 use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
 use itertools::zip_eq;
 use thiserror::Error;
@@ -121,14 +121,14 @@ impl ProgramAnnotations {
         functions: &[Function],
         metadata: &Metadata,
         gas_usage_check: bool,
-        type_sizes: &TypeSizeMap,
+        type_layouts: &TypeLayoutMap,
     ) -> Result<Self, AnnotationError> {
         let mut annotations = ProgramAnnotations::new(n_statements);
         for func in functions {
             annotations.set_or_assert(
                 func.entry_point,
                 StatementAnnotations {
-                    refs: build_function_parameters_refs(func, type_sizes).map_err(|error| {
+                    refs: build_function_parameters_refs(func, type_layouts).map_err(|error| {
                         AnnotationError::ReferencesError { statement_idx: func.entry_point, error }
                     })?,
                     function_id: func.id.clone(),
@@ -388,10 +388,11 @@ impl ProgramAnnotations {
         // TODO(ilya): Don't use linear search.
         let func = &functions.iter().find(|func| func.id == annotations.function_id).unwrap();
 
-        let expected_ap_tracking = match metadata.ap_change_info.function_ap_change.get(&func.id) {
-            Some(x) => ApTracking::Enabled { ap_change: *x, base: ApTrackingBase::FunctionStart },
-            None => ApTracking::Disabled,
-        };
+        let expected_ap_tracking =
+            metadata.ap_change_info.known_function_ap_change(&func.id).map_or(
+                ApTracking::Disabled,
+                |ap_change| ApTracking::Enabled { ap_change, base: ApTrackingBase::FunctionStart },
+            );
         if annotations.environment.ap_tracking != expected_ap_tracking {
             return Err(AnnotationError::InvalidFunctionApChange {
                 statement_idx,
@@ -1,21 +1,34 @@
 use std::fmt::Display;
 
+use cairo_lang_casm::hints::Hint;
 use cairo_lang_casm::instructions::{Instruction, InstructionBody, RetInstruction};
-use cairo_lang_sierra::extensions::core::{CoreConcreteLibfunc, CoreLibfunc, CoreType};
+use cairo_lang_sierra::extensions::const_type::{ConstConcreteLibfunc, ConstConcreteType};
+use cairo_lang_sierra::extensions::core::{
+    CoreConcreteLibfunc, CoreLibfunc, CoreType, CoreTypeConcrete,
+};
+use cairo_lang_sierra::extensions::gas::CostTokenType;
 use cairo_lang_sierra::extensions::lib_func::SierraApChange;
 use cairo_lang_sierra::extensions::ConcreteLibfunc;
-use cairo_lang_sierra::ids::VarId;
-use cairo_lang_sierra::program::{BranchTarget, Invocation, Program, Statement, StatementIdx};
+use cairo_lang_sierra::ids::{ConcreteTypeId, VarId};
+use cairo_lang_sierra::program::{
+    BranchTarget, GenericArg, Invocation, Program, Statement, StatementIdx,
+};
 use cairo_lang_sierra::program_registry::{ProgramRegistry, ProgramRegistryError};
-use cairo_lang_sierra_type_size::get_type_size_map;
-use itertools::zip_eq;
+use cairo_lang_sierra_type_size::{get_type_layout_map, get_type_size_map, TypeSizeMap};
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::{chain, repeat_n, zip_eq, Itertools};
+use num_bigint::BigInt;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::annotations::{AnnotationError, ProgramAnnotations, StatementAnnotations};
+use crate::invocations::custom::CasmLibfuncExtensions;
+use crate::invocations::enm::get_variant_selector;
 use crate::invocations::{
     check_references_on_stack, compile_invocation, InvocationError, ProgramInfo,
 };
 use crate::metadata::Metadata;
+use crate::peephole::optimize_instructions;
 use crate::references::{check_types_match, ReferencesError};
 use crate::relocations::{relocate_instructions, RelocationEntry};
 
@@ -46,12 +59,16 @@ pub enum CompilationError {
         source_statement_idx: StatementIdx,
         destination_statement_idx: StatementIdx,
     },
+    #[error("The data of the const type {0} does not match its inner type.")]
+    ConstDataMismatch(ConcreteTypeId),
 }
 
 /// The casm program representation.
 #[derive(Debug, Eq, PartialEq)]
 pub struct CairoProgram {
     pub instructions: Vec<Instruction>,
+    /// The constants of the program. Their segment is placed right after the instructions.
+    pub consts_info: ConstsInfo,
     pub debug_info: CairoProgramDebugInfo,
 }
 impl Display for CairoProgram {
@@ -59,15 +76,241 @@ impl Display for CairoProgram {
         for instruction in &self.instructions {
             writeln!(f, "{instruction};")?
         }
+        for value in &self.consts_info.segment {
+            writeln!(f, "dw {value};")?
+        }
+        Ok(())
+    }
+}
+impl CairoProgram {
+    /// Returns a view of the program that is displayed as its instructions interleaved with the
+    /// statements of `sierra_program` they were compiled from, and the gas costs of these
+    /// statements.
+    ///
+    /// `sierra_program` must be the program this program was compiled from.
+    pub fn annotated<'a>(&'a self, sierra_program: &'a Program) -> AnnotatedCairoProgram<'a> {
+        AnnotatedCairoProgram { cairo_program: self, sierra_program }
+    }
+
+    /// Assembles the program into bytecode: the encoded instructions, followed by the constants
+    /// segment.
+    pub fn assemble(&self) -> AssembledCairoProgram {
+        self.assemble_ex(&[], &[])
+    }
+
+    /// Assembles the program like [Self::assemble], between the given `header` and `footer`
+    /// instructions.
+    pub fn assemble_ex(
+        &self,
+        header: &[Instruction],
+        footer: &[Instruction],
+    ) -> AssembledCairoProgram {
+        let mut program = AssembledCairoProgram { bytecode: vec![], hints: vec![] };
+        for instruction in chain!(header, &self.instructions) {
+            program.push_instruction(instruction);
+        }
+        program.bytecode.extend(self.consts_info.segment.iter().cloned());
+        for instruction in footer {
+            program.push_instruction(instruction);
+        }
+        program
+    }
+}
+
+/// A casm program along with the Sierra program it was compiled from, for auditing the compiled
+/// code. See [CairoProgram::annotated].
+pub struct AnnotatedCairoProgram<'a> {
+    cairo_program: &'a CairoProgram,
+    sierra_program: &'a Program,
+}
+impl Display for AnnotatedCairoProgram<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut statements = zip_eq(
+            self.sierra_program.statements.iter().enumerate(),
+            // The last entry is the end of the code, and has no statement.
+            &self.cairo_program.debug_info.sierra_statement_info
+                [..self.sierra_program.statements.len()],
+        )
+        .peekable();
+        let mut offset = 0;
+        for instruction in &self.cairo_program.instructions {
+            // Statements compiled to no instructions share their offset with the next statement.
+            while let Some(((idx, statement), info)) =
+                statements.next_if(|(_, info)| info.code_offset <= offset)
+            {
+                write_statement_annotation(f, idx, statement, info)?;
+            }
+            writeln!(f, "{instruction};")?;
+            offset += instruction.body.op_size();
+        }
+        for ((idx, statement), info) in statements {
+            write_statement_annotation(f, idx, statement, info)?;
+        }
+        for value in &self.cairo_program.consts_info.segment {
+            writeln!(f, "dw {value};")?
+        }
         Ok(())
     }
 }
 
+/// Writes a comment line with a Sierra statement and the gas costs of its branches.
+fn write_statement_annotation(
+    f: &mut std::fmt::Formatter<'_>,
+    idx: usize,
+    statement: &Statement,
+    info: &SierraStatementDebugInfo,
+) -> std::fmt::Result {
+    write!(f, "// #{idx}: {statement};")?;
+    if info.branch_costs.is_empty() {
+        return writeln!(f);
+    }
+    let branch_costs = info.branch_costs.iter().map(|costs| {
+        let costs = costs
+            .iter()
+            .filter(|(_, cost)| **cost != 0)
+            .map(|(token_type, cost)| format!("{token_type:?}: {cost}"))
+            .join(", ");
+        format!("[{costs}]")
+    });
+    writeln!(f, " cost: {}", branch_costs.format(", "))
+}
+
+/// The bytecode of an assembled casm program.
+#[derive(Debug, Eq, PartialEq)]
+pub struct AssembledCairoProgram {
+    /// The bytecode of the program.
+    pub bytecode: Vec<BigInt>,
+    /// The hints of the program, by the bytecode offsets of their instructions.
+    pub hints: Vec<(usize, Vec<Hint>)>,
+}
+impl AssembledCairoProgram {
+    /// Appends an instruction to the bytecode, along with its hints.
+    fn push_instruction(&mut self, instruction: &Instruction) {
+        if !instruction.hints.is_empty() {
+            self.hints.push((self.bytecode.len(), instruction.hints.clone()))
+        }
+        self.bytecode.extend(instruction.assemble().encode());
+    }
+}
+
+/// The constants of a program.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct ConstsInfo {
+    /// The value of each `Const` type of the program, as the cells of its inner type.
+    pub values: OrderedHashMap<ConcreteTypeId, Vec<BigInt>>,
+    /// The offset of each `Const` type boxed by `const_as_box` within the constants segment.
+    pub segment_offsets: OrderedHashMap<ConcreteTypeId, usize>,
+    /// The content of the constants segment, which is placed right after the program code.
+    pub segment: Vec<BigInt>,
+}
+impl ConstsInfo {
+    /// Computes the values of the `Const` types of the program, and lays out the constants boxed
+    /// by `const_as_box` in the constants segment.
+    pub fn new(
+        program: &Program,
+        registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+        type_sizes: &TypeSizeMap,
+    ) -> Result<Self, Box<CompilationError>> {
+        let mut consts_info = Self::default();
+        for declaration in &program.type_declarations {
+            if let CoreTypeConcrete::Const(const_type) = get_type(registry, &declaration.id)? {
+                consts_info.values.insert(
+                    declaration.id.clone(),
+                    extract_const_value(registry, type_sizes, &declaration.id, const_type)?,
+                );
+            }
+        }
+        for declaration in &program.libfunc_declarations {
+            let libfunc = registry
+                .get_libfunc(&declaration.id)
+                .map_err(CompilationError::ProgramRegistryError)?;
+            if let CoreConcreteLibfunc::Const(ConstConcreteLibfunc::AsBox(libfunc)) = libfunc {
+                if consts_info.segment_offsets.contains_key(&libfunc.const_type) {
+                    continue;
+                }
+                consts_info
+                    .segment_offsets
+                    .insert(libfunc.const_type.clone(), consts_info.segment.len());
+                let value = consts_info.values.get(&libfunc.const_type).unwrap();
+                consts_info.segment.extend(value.iter().cloned());
+            }
+        }
+        Ok(consts_info)
+    }
+}
+
+/// Returns the concrete type of the given id.
+fn get_type<'a>(
+    registry: &'a ProgramRegistry<CoreType, CoreLibfunc>,
+    id: &ConcreteTypeId,
+) -> Result<&'a CoreTypeConcrete, Box<CompilationError>> {
+    Ok(registry.get_type(id).map_err(CompilationError::ProgramRegistryError)?)
+}
+
+/// Returns the value of a `Const` type, as the cells of its inner type.
+fn extract_const_value(
+    registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+    type_sizes: &TypeSizeMap,
+    id: &ConcreteTypeId,
+    const_type: &ConstConcreteType,
+) -> Result<Vec<BigInt>, Box<CompilationError>> {
+    let mismatch = || Box::new(CompilationError::ConstDataMismatch(id.clone()));
+    // Returns the value of a `Const` type given as a generic argument.
+    let extract_inner = |arg: &GenericArg| {
+        let GenericArg::Type(inner_id) = arg else {
+            return Err(mismatch());
+        };
+        let CoreTypeConcrete::Const(inner_const_type) = get_type(registry, inner_id)? else {
+            return Err(mismatch());
+        };
+        extract_const_value(registry, type_sizes, inner_id, inner_const_type)
+    };
+    match (get_type(registry, &const_type.inner_ty)?, &const_type.inner_data[..]) {
+        (CoreTypeConcrete::Struct(_), members) => {
+            let mut value = vec![];
+            for member in members {
+                value.extend(extract_inner(member)?);
+            }
+            Ok(value)
+        }
+        (CoreTypeConcrete::Enum(enum_type), [GenericArg::Value(index), variant]) => {
+            let index: usize = index.try_into().map_err(|_| mismatch())?;
+            let variant_selector =
+                get_variant_selector(enum_type.variants.len(), index).map_err(|_| mismatch())?;
+            let variant_value = extract_inner(variant)?;
+            let enum_size = type_sizes.get(&const_type.inner_ty).ok_or_else(mismatch)?;
+            let num_padding =
+                (*enum_size as usize).checked_sub(1 + variant_value.len()).ok_or_else(mismatch)?;
+            Ok(chain!(
+                [BigInt::from(variant_selector)],
+                repeat_n(BigInt::from(0), num_padding),
+                variant_value
+            )
+            .collect())
+        }
+        (CoreTypeConcrete::Enum(_), _) => Err(mismatch()),
+        (_, [GenericArg::Value(value)]) => Ok(vec![value.clone()]),
+        (_, _) => Err(mismatch()),
+    }
+}
+
 /// The debug information of a compilation from Sierra to casm.
 #[derive(Debug, Eq, PartialEq)]
 pub struct SierraStatementDebugInfo {
     /// The offset of the sierra statement within the bytecode.
     pub code_offset: usize,
+    /// The gas cost of each branch of the sierra statement, per token type. Empty for return
+    /// statements.
+    pub branch_costs: Vec<OrderedHashMap<CostTokenType, i64>>,
+}
+
+/// The debug information of a single casm instruction.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct InstructionDebugInfo {
+    /// The offset of the instruction within the bytecode.
+    pub code_offset: usize,
+    /// The Sierra statement the instruction was compiled from.
+    pub sierra_statement_idx: StatementIdx,
 }
 
 /// The debug information of a compilation from Sierra to casm.
@@ -75,6 +318,8 @@ pub struct SierraStatementDebugInfo {
 pub struct CairoProgramDebugInfo {
     /// The debug information per Sierra statement.
     pub sierra_statement_info: Vec<SierraStatementDebugInfo>,
+    /// The debug information per casm instruction, in the order of the instructions.
+    pub instruction_info: Vec<InstructionDebugInfo>,
 }
 
 /// Ensure the basic structure of the invocation is the same as the library function.
@@ -101,10 +346,36 @@ pub fn check_basic_structure(
     }
 }
 
+/// Configuration for the Sierra to CASM compilation.
+#[derive(Clone, Default)]
+pub struct SierraToCasmConfig {
+    /// Whether to check the gas usage of the program.
+    pub gas_usage_check: bool,
+    /// Whether to apply the peephole optimizations of [optimize_instructions] to the compiled
+    /// CASM.
+    pub peephole_optimizations: bool,
+    /// The extensions implementing the libfuncs not defined by the core library, along with their
+    /// CASM lowerings. The metadata should be computed with the same extensions.
+    pub libfunc_extensions: CasmLibfuncExtensions,
+}
+
 pub fn compile(
     program: &Program,
     metadata: &Metadata,
     gas_usage_check: bool,
+) -> Result<CairoProgram, Box<CompilationError>> {
+    compile_with_config(
+        program,
+        metadata,
+        &SierraToCasmConfig { gas_usage_check, ..Default::default() },
+    )
+}
+
+/// Compiles a Sierra program to CASM with the given configuration.
+pub fn compile_with_config(
+    program: &Program,
+    metadata: &Metadata,
+    config: &SierraToCasmConfig,
 ) -> Result<CairoProgram, Box<CompilationError>> {
     let mut instructions = Vec::new();
     let mut relocations: Vec<RelocationEntry> = Vec::new();
@@ -112,20 +383,25 @@ pub fn compile(
     // Maps statement_idx to program_offset. The last value (for statement_idx=number-of-statements)
     // contains the final offset (the size of the program code segment).
     let mut statement_offsets = Vec::with_capacity(program.statements.len());
+    let mut statement_branch_costs = Vec::with_capacity(program.statements.len());
 
-    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new_with_ap_change(
+    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new_with_extensions(
         program,
         metadata.ap_change_info.function_ap_change.clone(),
+        config.libfunc_extensions.libfunc_extensions(),
     )
     .map_err(CompilationError::ProgramRegistryError)?;
+    let type_layouts = get_type_layout_map(program, &registry)
+        .ok_or(CompilationError::FailedBuildingTypeInformation)?;
     let type_sizes = get_type_size_map(program, &registry)
         .ok_or(CompilationError::FailedBuildingTypeInformation)?;
+    let consts_info = ConstsInfo::new(program, &registry, &type_sizes)?;
     let mut program_annotations = ProgramAnnotations::create(
         program.statements.len(),
         &program.funcs,
         metadata,
-        gas_usage_check,
-        &type_sizes,
+        config.gas_usage_check,
+        &type_layouts,
     )
     .map_err(|err| Box::new(err.into()))?;
 
@@ -139,7 +415,7 @@ pub fn compile(
                 let (annotations, return_refs) = program_annotations
                     .get_annotations_after_take_args(statement_idx, ref_ids.iter())
                     .map_err(|err| Box::new(err.into()))?;
-                return_refs.iter().for_each(|r| r.validate(&type_sizes));
+                return_refs.iter().for_each(|r| r.validate(&type_layouts));
 
                 if let Some(var_id) = annotations.refs.keys().next() {
                     return Err(Box::new(CompilationError::DanglingReferences {
@@ -164,6 +440,7 @@ pub fn compile(
                     _ => CompilationError::InvocationError { statement_idx, error },
                 })?;
 
+                statement_branch_costs.push(vec![]);
                 let ret_instruction = RetInstruction {};
                 program_offset += ret_instruction.op_size();
                 instructions.push(Instruction::new(InstructionBody::Ret(ret_instruction), false));
@@ -186,9 +463,14 @@ pub fn compile(
                 check_types_match(&invoke_refs, &param_types).map_err(|error| {
                     Box::new(AnnotationError::ReferencesError { statement_idx, error }.into())
                 })?;
-                invoke_refs.iter().for_each(|r| r.validate(&type_sizes));
+                invoke_refs.iter().for_each(|r| r.validate(&type_layouts));
                 let compiled_invocation = compile_invocation(
-                    ProgramInfo { metadata, type_sizes: &type_sizes },
+                    ProgramInfo {
+                        metadata,
+                        type_sizes: &type_sizes,
+                        consts_info: &consts_info,
+                        libfunc_extensions: &config.libfunc_extensions,
+                    },
                     invocation,
                     libfunc,
                     statement_idx,
@@ -197,6 +479,19 @@ pub fn compile(
                 )
                 .map_err(|error| CompilationError::InvocationError { statement_idx, error })?;
 
+                statement_branch_costs.push(
+                    compiled_invocation
+                        .results
+                        .iter()
+                        .map(|branch_changes| {
+                            branch_changes
+                                .gas_change
+                                .iter()
+                                .map(|(token_type, change)| (*token_type, -change))
+                                .collect()
+                        })
+                        .collect(),
+                );
                 for instruction in &compiled_invocation.instructions {
                     program_offset += instruction.body.op_size();
                 }
@@ -247,22 +542,55 @@ pub fn compile(
         }
     }
 
-    // Push the final offset at the end of `statement_offsets`.
+    // Push the final offset at the end of `statement_offsets`, which has no costs.
     statement_offsets.push(program_offset);
+    statement_branch_costs.push(vec![]);
+
+    if config.peephole_optimizations {
+        optimize_instructions(&mut instructions, &mut relocations, &mut statement_offsets);
+    }
 
-    relocate_instructions(&relocations, &statement_offsets, &mut instructions);
+    relocate_instructions(&relocations, &statement_offsets, &consts_info, &mut instructions);
+    let instruction_info = get_instruction_info(&instructions, &statement_offsets);
 
     Ok(CairoProgram {
         instructions,
+        consts_info,
         debug_info: CairoProgramDebugInfo {
-            sierra_statement_info: statement_offsets
-                .into_iter()
-                .map(|code_offset| SierraStatementDebugInfo { code_offset })
+            instruction_info,
+            sierra_statement_info: zip_eq(statement_offsets, statement_branch_costs)
+                .map(|(code_offset, branch_costs)| SierraStatementDebugInfo {
+                    code_offset,
+                    branch_costs,
+                })
                 .collect(),
         },
     })
 }
 
+/// Returns the debug information of each instruction, given the bytecode offset of each statement.
+fn get_instruction_info(
+    instructions: &[Instruction],
+    statement_offsets: &[usize],
+) -> Vec<InstructionDebugInfo> {
+    let mut instruction_info = Vec::with_capacity(instructions.len());
+    let mut statement_idx = 0;
+    let mut code_offset = 0;
+    for instruction in instructions {
+        // The instruction belongs to the last statement starting at or before it, as statements
+        // compiled to no instructions share their offset with the next statement.
+        while statement_offsets[statement_idx + 1] <= code_offset {
+            statement_idx += 1;
+        }
+        instruction_info.push(InstructionDebugInfo {
+            code_offset,
+            sierra_statement_idx: StatementIdx(statement_idx),
+        });
+        code_offset += instruction.body.op_size();
+    }
+    instruction_info
+}
+
 /// Returns true if `statement` is an invocation of the branch_align libfunc.
 fn is_branch_align(
     registry: &ProgramRegistry<CoreType, CoreLibfunc>,
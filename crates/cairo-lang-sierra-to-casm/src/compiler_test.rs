@@ -1,9 +1,10 @@
+use cairo_lang_sierra::program::{Program, StatementIdx};
 use cairo_lang_sierra::ProgramParser;
 use indoc::indoc;
 use pretty_assertions;
 use test_case::test_case;
 
-use crate::compiler::compile;
+use crate::compiler::{compile, compile_with_config, InstructionDebugInfo, SierraToCasmConfig};
 use crate::metadata::{calc_metadata, calc_metadata_ap_change_only};
 use crate::test_utils::{read_sierra_example_file, strip_comments_and_linebreaks};
 
@@ -232,6 +233,63 @@ use crate::test_utils::{read_sierra_example_file, strip_comments_and_linebreaks}
                 ret;
             "};
             "u128")]
+#[test_case(indoc! {"
+                type u128 = u128;
+                type u256 = Struct<ut@core::integer::u256, u128, u128>;
+                type BoxU256 = Box<u256>;
+                type ConstU128Low = Const<u128, 7>;
+                type ConstU128High = Const<u128, 5>;
+                type ConstU256 = Const<u256, ConstU128Low, ConstU128High>;
+
+                libfunc const_as_immediate<ConstU256> = const_as_immediate<ConstU256>;
+                libfunc const_as_box<ConstU256> = const_as_box<ConstU256>;
+                libfunc store_temp<u256> = store_temp<u256>;
+                libfunc store_temp<BoxU256> = store_temp<BoxU256>;
+
+                const_as_box<ConstU256>() -> ([2]);
+                const_as_immediate<ConstU256>() -> ([1]);
+                store_temp<u256>([1]) -> ([1]);
+                store_temp<BoxU256>([2]) -> ([2]);
+                return ([1], [2]);
+
+                test_program@0() -> (u256, BoxU256);
+            "},
+            false,
+            indoc! {"
+                call rel 12;
+                [ap + 0] = [ap + -1] + 8, ap++;
+                [ap + 0] = 7, ap++;
+                [ap + 0] = 5, ap++;
+                [ap + 0] = [ap + -3], ap++;
+                ret;
+                dw 7;
+                dw 5;
+            "};
+            "consts")]
+#[test_case(indoc! {"
+                type u128 = u128;
+                type u256 = Struct<ut@core::integer::u256, u128, u128>;
+                type U128OrU256 = Enum<ut@U128OrU256, u128, u256>;
+                type ConstU128 = Const<u128, 7>;
+                type ConstU128OrU256 = Const<U128OrU256, 0, ConstU128>;
+
+                libfunc const_as_immediate<ConstU128OrU256> = const_as_immediate<ConstU128OrU256>;
+                libfunc store_temp<U128OrU256> = store_temp<U128OrU256>;
+
+                const_as_immediate<ConstU128OrU256>() -> ([1]);
+                store_temp<U128OrU256>([1]) -> ([1]);
+                return ([1]);
+
+                test_program@0() -> (U128OrU256);
+            "},
+            false,
+            indoc! {"
+                [ap + 0] = 0, ap++;
+                [ap + 0] = 0, ap++;
+                [ap + 0] = 7, ap++;
+                ret;
+            "};
+            "enum_const")]
 #[test_case(read_sierra_example_file("fib_no_gas").as_str(),
             false,
             indoc! {"
@@ -813,3 +871,127 @@ fn compiler_errors(sierra_code: &str, expected_result: &str) {
         expected_result
     );
 }
+
+/// Returns a program branching on whether its argument is zero.
+fn is_zero_program() -> Program {
+    ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+
+            libfunc branch_align = branch_align;
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc felt252_const_1 = felt252_const<1>;
+            libfunc drop_nz_felt252 = drop<NonZeroFelt252>;
+            libfunc store_temp_felt252 = store_temp<felt252>;
+
+            felt252_is_zero([1]) { fallthrough() 5([1]) };
+            branch_align() -> ();
+            felt252_const_1() -> ([1]);
+            store_temp_felt252([1]) -> ([1]);
+            return ([1]);
+            branch_align() -> ();
+            drop_nz_felt252([1]) -> ();
+            felt252_const_1() -> ([1]);
+            store_temp_felt252([1]) -> ([1]);
+            return ([1]);
+
+            test_program@0([1]: felt252) -> (felt252);
+        "})
+        .unwrap()
+}
+
+#[test]
+fn annotated_sierra_to_casm() {
+    let program = is_zero_program();
+    let cairo_program =
+        compile(&program, &calc_metadata(&program, Default::default()).unwrap(), true)
+            .expect("Compilation failed.");
+    pretty_assertions::assert_eq!(
+        cairo_program.annotated(&program).to_string(),
+        indoc! {"
+            // #0: felt252_is_zero([1]) { fallthrough() 5([1]) }; cost: [Const: 100], [Const: 100]
+            jmp rel 5 if [fp + -3] != 0;
+            // #1: branch_align() -> (); cost: []
+            // #2: felt252_const_1() -> ([1]); cost: []
+            // #3: store_temp_felt252([1]) -> ([1]); cost: [Const: 100]
+            [ap + 0] = 1, ap++;
+            // #4: return([1]);
+            ret;
+            // #5: branch_align() -> (); cost: []
+            // #6: drop_nz_felt252([1]) -> (); cost: []
+            // #7: felt252_const_1() -> ([1]); cost: []
+            // #8: store_temp_felt252([1]) -> ([1]); cost: [Const: 100]
+            [ap + 0] = 1, ap++;
+            // #9: return([1]);
+            ret;
+        "}
+    );
+}
+
+#[test]
+fn instruction_debug_info() {
+    let program = is_zero_program();
+    let cairo_program =
+        compile(&program, &calc_metadata(&program, Default::default()).unwrap(), true)
+            .expect("Compilation failed.");
+    pretty_assertions::assert_eq!(
+        cairo_program.debug_info.instruction_info,
+        [(0, 0), (2, 3), (4, 4), (5, 8), (7, 9)]
+            .map(|(code_offset, statement_idx)| InstructionDebugInfo {
+                code_offset,
+                sierra_statement_idx: StatementIdx(statement_idx),
+            })
+            .to_vec()
+    );
+}
+
+#[test]
+fn peephole_optimizations() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+
+            libfunc branch_align = branch_align;
+            libfunc jump = jump;
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc drop_nz_felt252 = drop<NonZeroFelt252>;
+
+            felt252_is_zero([1]) { fallthrough() 3([1]) };
+            branch_align() -> ();
+            jump() { 5() };
+            branch_align() -> ();
+            drop_nz_felt252([1]) -> ();
+            return ();
+
+            test_program@0([1]: felt252) -> ();
+        "})
+        .unwrap();
+    let metadata = calc_metadata_ap_change_only(&program).unwrap();
+    let compile_with_peephole = |peephole_optimizations| {
+        compile_with_config(
+            &program,
+            &metadata,
+            &SierraToCasmConfig { peephole_optimizations, ..Default::default() },
+        )
+        .unwrap()
+        .to_string()
+    };
+    // The optimizations are only applied if enabled.
+    pretty_assertions::assert_eq!(
+        compile_with_peephole(false),
+        indoc! {"
+            jmp rel 4 if [fp + -3] != 0;
+            jmp rel 2;
+            ret;
+        "}
+    );
+    pretty_assertions::assert_eq!(
+        compile_with_peephole(true),
+        indoc! {"
+            jmp rel 2 if [fp + -3] != 0;
+            ret;
+        "}
+    );
+}
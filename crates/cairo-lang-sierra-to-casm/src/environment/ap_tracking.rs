@@ -7,13 +7,13 @@ pub fn update_ap_tracking(
     ap_tracking: ApTracking,
     ap_change: ApChange,
 ) -> Result<ApTracking, ApChangeError> {
-    Ok(match (ap_tracking, ap_change) {
-        (ApTracking::Enabled { ap_change: current, base }, ApChange::Known(change)) => {
-            ApTracking::Enabled {
-                ap_change: current.checked_add(change).ok_or(ApChangeError::OffsetOverflow)?,
-                base,
+    Ok(match ap_tracking {
+        ApTracking::Enabled { ap_change: current, base } => {
+            match ApChange::Known(current).checked_add(ap_change)? {
+                ApChange::Known(ap_change) => ApTracking::Enabled { ap_change, base },
+                ApChange::Unknown => ApTracking::Disabled,
             }
         }
-        _ => ApTracking::Disabled,
+        ApTracking::Disabled => ApTracking::Disabled,
     })
 }
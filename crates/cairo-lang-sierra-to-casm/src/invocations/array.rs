@@ -1,12 +1,19 @@
-use cairo_lang_casm::builder::CasmBuilder;
+use cairo_lang_casm::builder::{CasmBuildResult, CasmBuilder, Var};
 use cairo_lang_casm::casm_build_extend;
 use cairo_lang_sierra::extensions::array::ArrayConcreteLibfunc;
 use cairo_lang_sierra::ids::ConcreteTypeId;
+use cairo_lang_sierra_gas::core_libfunc_cost::{
+    array_append_span_fixed_costs, array_concat_fixed_costs, array_copy_element_cost,
+};
+use cairo_lang_sierra_gas::objects::ConstCost;
+use itertools::chain;
 
 use super::{CompiledInvocation, CompiledInvocationBuilder, InvocationError};
 use crate::invocations::{
     add_input_variables, get_non_fallthrough_statement_id, CostValidationInfo,
 };
+use crate::references::ReferenceExpression;
+use crate::relocations::{Relocation, RelocationEntry};
 
 /// Builds instructions for Sierra array operations.
 pub fn build(
@@ -28,7 +35,15 @@ pub fn build(
         }
         ArrayConcreteLibfunc::Get(libfunc) => build_array_get(&libfunc.ty, builder),
         ArrayConcreteLibfunc::Slice(libfunc) => build_array_slice(&libfunc.ty, builder),
+        ArrayConcreteLibfunc::SnapshotMultiPopFront(libfunc) => {
+            build_array_snapshot_multi_pop(&libfunc.ty, builder, true)
+        }
+        ArrayConcreteLibfunc::SnapshotMultiPopBack(libfunc) => {
+            build_array_snapshot_multi_pop(&libfunc.ty, builder, false)
+        }
         ArrayConcreteLibfunc::Len(libfunc) => build_array_len(&libfunc.ty, builder),
+        ArrayConcreteLibfunc::AppendSpan(libfunc) => build_array_append_span(&libfunc.ty, builder),
+        ArrayConcreteLibfunc::Concat(libfunc) => build_array_concat(&libfunc.ty, builder),
     }
 }
 
@@ -304,6 +319,81 @@ fn build_array_slice(
     ))
 }
 
+/// Handles instructions for popping multiple values from the front or the back of an array
+/// snapshot.
+fn build_array_snapshot_multi_pop(
+    elem_ty: &ConcreteTypeId,
+    builder: CompiledInvocationBuilder<'_>,
+    from_front: bool,
+) -> Result<CompiledInvocation, InvocationError> {
+    let [expr_range_check, expr_arr, expr_count] = builder.try_get_refs()?;
+    let range_check = expr_range_check.try_unpack_single()?;
+    let [arr_start, arr_end] = expr_arr.try_unpack()?;
+    let count = expr_count.try_unpack_single()?;
+
+    let element_size = builder.program_info.type_sizes[elem_ty];
+
+    let mut casm_builder = CasmBuilder::default();
+    add_input_variables! {casm_builder,
+        deref count;
+        deref arr_start;
+        deref arr_end;
+        buffer(1) range_check;
+    };
+    casm_build_extend! {casm_builder,
+        let orig_range_check = range_check;
+        // Compute the length of the array (in cells).
+        tempvar array_length_in_cells = arr_end - arr_start;
+    };
+    let count_in_cells = if element_size == 1 {
+        count
+    } else {
+        casm_build_extend! {casm_builder,
+            const element_size = element_size;
+            // Compute the size of the popped values (in cells).
+            tempvar popped_size = count * element_size;
+        };
+        popped_size
+    };
+    casm_build_extend! {casm_builder,
+        // Check that there are enough values to pop.
+        tempvar has_enough;
+        hint TestLessThanOrEqual {lhs: count_in_cells, rhs: array_length_in_cells} into {dst: has_enough};
+        jump HasEnough if has_enough != 0;
+        // Not enough values. Assert that count_in_cells - 1 - length is in [0, 2^128).
+        const one = 1;
+        tempvar length_plus_1 = array_length_in_cells + one;
+        tempvar count_length_diff = count_in_cells - length_plus_1;
+        assert count_length_diff = *(range_check++);
+        jump Failure;
+
+        HasEnough:
+        // Assert that length - count_in_cells is in [0, 2^128). Note that it is smaller than 2^128
+        // as the index type is u32.
+        tempvar length_count_diff = array_length_in_cells - count_in_cells;
+        assert length_count_diff = *(range_check++);
+    };
+    let failure_handle = get_non_fallthrough_statement_id(&builder);
+    let (rest, popped) = if from_front {
+        casm_build_extend!(casm_builder, let popped_end = arr_start + count_in_cells;);
+        ([popped_end, arr_end], [arr_start, popped_end])
+    } else {
+        casm_build_extend!(casm_builder, tempvar popped_start = arr_end - count_in_cells;);
+        ([arr_start, popped_start], [popped_start, arr_end])
+    };
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [
+            ("Fallthrough", &[&[range_check], &rest, &popped], None),
+            ("Failure", &[&[range_check], &[arr_start, arr_end]], Some(failure_handle)),
+        ],
+        CostValidationInfo {
+            range_check_info: Some((orig_range_check, range_check)),
+            extra_costs: None,
+        },
+    ))
+}
+
 /// Handles a Sierra statement for getting the length of an array.
 fn build_array_len(
     elem_ty: &ConcreteTypeId,
@@ -335,3 +425,325 @@ fn build_array_len(
         Default::default(),
     ))
 }
+
+/// Handles a Sierra statement for appending the elements of an array snapshot to an array.
+fn build_array_append_span(
+    elem_ty: &ConcreteTypeId,
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    let [expr_range_check, expr_gas_counter, expr_arr, expr_span] = builder.try_get_refs()?;
+    let range_check = expr_range_check.try_unpack_single()?;
+    let gas_counter = expr_gas_counter.try_unpack_single()?;
+    let [arr_start, arr_end] = expr_arr.try_unpack()?;
+    let [span_start, span_end] = expr_span.try_unpack()?;
+    let element_size = builder.program_info.type_sizes[elem_ty];
+
+    let mut casm_builder = CasmBuilder::default();
+    add_input_variables! {casm_builder,
+        buffer(1) range_check;
+        deref gas_counter;
+        deref arr_start;
+        deref arr_end;
+        deref span_start;
+        deref span_end;
+    };
+    casm_build_extend! {casm_builder,
+        tempvar n_cells = span_end - span_start;
+    };
+    let updated_gas =
+        add_copy_gas_withdrawal(&mut casm_builder, range_check, gas_counter, n_cells, element_size);
+    casm_build_extend! {casm_builder,
+        // Push the arguments of the copy function that are not yet temporary variables.
+        tempvar copy_range_check = range_check;
+        tempvar copy_arr_start = arr_start;
+        tempvar copy_arr_end = arr_end;
+        tempvar copy_span_start = span_start;
+        let (final_range_check, final_gas, final_arr_start, final_arr_end) = call CopySpans;
+        jump Done;
+        CopySpans:
+    };
+    let (copied_arr_end, mut copy_steps, element_steps) = add_copy_loop(
+        &mut casm_builder,
+        "CopySpan",
+        [copy_arr_end, copy_span_start, n_cells],
+        &[copy_range_check, updated_gas, copy_arr_start],
+        element_size,
+    );
+    casm_build_extend! {casm_builder,
+        tempvar ret_range_check = copy_range_check;
+        tempvar ret_gas = updated_gas;
+        tempvar ret_arr_start = copy_arr_start;
+        tempvar ret_arr_end = copied_arr_end;
+        ret;
+        #{ copy_steps += steps; steps = 0; }
+        Done:
+    };
+    let failure_handle = get_non_fallthrough_statement_id(&builder);
+    let CasmBuildResult {
+        instructions,
+        branches: [(success_state, _), (failure_state, failure_relocations)],
+    } = casm_builder.build(["Fallthrough", "Failure"]);
+    validate_copy_costs(
+        element_size,
+        [success_state.steps as i32 + copy_steps, failure_state.steps as i32],
+        element_steps,
+        array_append_span_fixed_costs(element_size.into()),
+    );
+
+    Ok(builder.build(
+        instructions,
+        failure_relocations
+            .into_iter()
+            .map(|instruction_idx| RelocationEntry {
+                instruction_idx,
+                relocation: Relocation::RelativeStatementId(failure_handle),
+            })
+            .collect(),
+        [
+            vec![
+                ReferenceExpression::from_cell(success_state.get_adjusted(final_range_check)),
+                ReferenceExpression::from_cell(success_state.get_adjusted(final_gas)),
+                ReferenceExpression {
+                    cells: vec![
+                        success_state.get_adjusted(final_arr_start),
+                        success_state.get_adjusted(final_arr_end),
+                    ],
+                },
+            ]
+            .into_iter(),
+            vec![
+                ReferenceExpression::from_cell(failure_state.get_adjusted(range_check)),
+                ReferenceExpression::from_cell(failure_state.get_adjusted(gas_counter)),
+                ReferenceExpression {
+                    cells: vec![
+                        failure_state.get_adjusted(arr_start),
+                        failure_state.get_adjusted(arr_end),
+                    ],
+                },
+            ]
+            .into_iter(),
+        ]
+        .into_iter(),
+    ))
+}
+
+/// Handles a Sierra statement for concatenating two array snapshots into a new array.
+fn build_array_concat(
+    elem_ty: &ConcreteTypeId,
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    let [expr_range_check, expr_gas_counter, expr_lhs, expr_rhs] = builder.try_get_refs()?;
+    let range_check = expr_range_check.try_unpack_single()?;
+    let gas_counter = expr_gas_counter.try_unpack_single()?;
+    let [lhs_start, lhs_end] = expr_lhs.try_unpack()?;
+    let [rhs_start, rhs_end] = expr_rhs.try_unpack()?;
+    let element_size = builder.program_info.type_sizes[elem_ty];
+
+    let mut casm_builder = CasmBuilder::default();
+    add_input_variables! {casm_builder,
+        buffer(1) range_check;
+        deref gas_counter;
+        deref lhs_start;
+        deref lhs_end;
+        deref rhs_start;
+        deref rhs_end;
+    };
+    casm_build_extend! {casm_builder,
+        tempvar lhs_cells = lhs_end - lhs_start;
+        tempvar rhs_cells = rhs_end - rhs_start;
+        tempvar n_cells = lhs_cells + rhs_cells;
+    };
+    let updated_gas =
+        add_copy_gas_withdrawal(&mut casm_builder, range_check, gas_counter, n_cells, element_size);
+    casm_build_extend! {casm_builder,
+        tempvar arr_start;
+        hint AllocSegment {} into {dst: arr_start};
+        ap += 1;
+        // Push the arguments of the copy function that are not yet temporary variables.
+        tempvar copy_range_check = range_check;
+        tempvar copy_lhs_start = lhs_start;
+        tempvar copy_rhs_start = rhs_start;
+        let (final_range_check, final_gas, final_arr_start, final_arr_end) = call CopySpans;
+        jump Done;
+        CopySpans:
+    };
+    let persistent_vars = [copy_range_check, updated_gas, arr_start, copy_rhs_start, rhs_cells];
+    let (lhs_copy_end, mut copy_steps, element_steps) = add_copy_loop(
+        &mut casm_builder,
+        "CopyLhs",
+        [arr_start, copy_lhs_start, lhs_cells],
+        &persistent_vars,
+        element_size,
+    );
+    let (arr_end, rhs_copy_steps, rhs_element_steps) = add_copy_loop(
+        &mut casm_builder,
+        "CopyRhs",
+        [lhs_copy_end, copy_rhs_start, rhs_cells],
+        &persistent_vars,
+        element_size,
+    );
+    assert_eq!(element_steps, rhs_element_steps);
+    copy_steps += rhs_copy_steps;
+    casm_build_extend! {casm_builder,
+        tempvar ret_range_check = copy_range_check;
+        tempvar ret_gas = updated_gas;
+        tempvar ret_arr_start = arr_start;
+        tempvar ret_arr_end = arr_end;
+        ret;
+        #{ copy_steps += steps; steps = 0; }
+        Done:
+    };
+    let failure_handle = get_non_fallthrough_statement_id(&builder);
+    let CasmBuildResult {
+        instructions,
+        branches: [(success_state, _), (failure_state, failure_relocations)],
+    } = casm_builder.build(["Fallthrough", "Failure"]);
+    validate_copy_costs(
+        element_size,
+        [success_state.steps as i32 + copy_steps, failure_state.steps as i32],
+        element_steps,
+        array_concat_fixed_costs(element_size.into()),
+    );
+
+    Ok(builder.build(
+        instructions,
+        failure_relocations
+            .into_iter()
+            .map(|instruction_idx| RelocationEntry {
+                instruction_idx,
+                relocation: Relocation::RelativeStatementId(failure_handle),
+            })
+            .collect(),
+        [
+            vec![
+                ReferenceExpression::from_cell(success_state.get_adjusted(final_range_check)),
+                ReferenceExpression::from_cell(success_state.get_adjusted(final_gas)),
+                ReferenceExpression {
+                    cells: vec![
+                        success_state.get_adjusted(final_arr_start),
+                        success_state.get_adjusted(final_arr_end),
+                    ],
+                },
+            ]
+            .into_iter(),
+            vec![
+                ReferenceExpression::from_cell(failure_state.get_adjusted(range_check)),
+                ReferenceExpression::from_cell(failure_state.get_adjusted(gas_counter)),
+            ]
+            .into_iter(),
+        ]
+        .into_iter(),
+    ))
+}
+
+/// Adds the code withdrawing the cost of copying the elements of `n_cells` cells from the gas
+/// counter, jumping to `Failure` if there is not enough gas. Returns the updated gas counter.
+fn add_copy_gas_withdrawal(
+    casm_builder: &mut CasmBuilder,
+    range_check: Var,
+    gas_counter: Var,
+    n_cells: Var,
+    element_size: i16,
+) -> Var {
+    let n_elements = if element_size == 1 {
+        n_cells
+    } else {
+        casm_build_extend! {casm_builder,
+            const element_size = element_size;
+            tempvar n_elements = n_cells / element_size;
+        };
+        n_elements
+    };
+    casm_build_extend! {casm_builder,
+        const element_cost = array_copy_element_cost(element_size.into()).cost();
+        tempvar required_gas = n_elements * element_cost;
+        tempvar has_enough_gas;
+        hint TestLessThanOrEqual {lhs: required_gas, rhs: gas_counter} into {dst: has_enough_gas};
+        jump HasEnoughGas if has_enough_gas != 0;
+        // Proving that `required_gas > gas_counter`.
+        const one = 1;
+        tempvar gas_counter_plus_1 = gas_counter + one;
+        tempvar gas_deficit = required_gas - gas_counter_plus_1;
+        assert gas_deficit = *(range_check++);
+        jump Failure;
+        HasEnoughGas:
+        tempvar updated_gas = gas_counter - required_gas;
+        assert updated_gas = *(range_check++);
+    };
+    updated_gas
+}
+
+/// Adds a loop copying `n_cells` cells from `src` to `dst`, where `[dst, src, n_cells]` are given
+/// in `copy_vars`. `persistent_vars` are the variables that remain usable after the loop, and
+/// `name` is the prefix of the labels of the loop.
+/// Returns the end of the copied cells in the destination, the number of steps not dependent on the
+/// number of copied elements, and the number of steps per copied element.
+fn add_copy_loop(
+    casm_builder: &mut CasmBuilder,
+    name: &str,
+    copy_vars: [Var; 3],
+    persistent_vars: &[Var],
+    element_size: i16,
+) -> (Var, i32, i32) {
+    let mut fixed_steps = 0;
+    let mut element_steps = 0;
+    let [dst, src, n_cells] = copy_vars;
+    casm_build_extend! {casm_builder,
+        const size = element_size;
+        tempvar loop_dst = dst;
+        tempvar loop_src = src;
+        tempvar remaining_cells = n_cells;
+    };
+    let loop_vars = [loop_dst, loop_src, remaining_cells];
+    casm_builder.rescope(chain!(
+        loop_vars.map(|var| (var, var)),
+        persistent_vars.iter().map(|var| (*var, *var)),
+        [(size, size)]
+    ));
+    casm_build_extend!(casm_builder, #{ fixed_steps += steps; steps = 0; });
+    let loop_label = format!("{name}Loop");
+    let done_label = format!("{name}Done");
+    casm_builder.jump_nz(remaining_cells, loop_label.clone());
+    casm_builder.jump(done_label.clone());
+    casm_builder.label(loop_label.clone());
+    // The jump into the loop is counted in the fixed steps.
+    casm_builder.reset_steps();
+    for offset in 0..element_size {
+        casm_build_extend! {casm_builder,
+            tempvar cell = loop_src[offset];
+            assert cell = loop_dst[offset];
+        };
+    }
+    casm_build_extend! {casm_builder,
+        tempvar next_dst = loop_dst + size;
+        tempvar next_src = loop_src + size;
+        tempvar next_remaining_cells = remaining_cells - size;
+    };
+    casm_builder.rescope(chain!(
+        [(loop_dst, next_dst), (loop_src, next_src), (remaining_cells, next_remaining_cells)],
+        persistent_vars.iter().map(|var| (*var, *var)),
+        [(size, size)]
+    ));
+    casm_build_extend!(casm_builder, #{ element_steps += steps; steps = 0; });
+    casm_builder.jump_nz(remaining_cells, loop_label);
+    // The jump back to the loop start is paid per element, as it follows each copied element.
+    element_steps += 1;
+    casm_builder.label(done_label);
+    (loop_dst, fixed_steps, element_steps)
+}
+
+/// Validates that the costs of the code built for `array_append_span` or `array_concat` match the
+/// costs used by the gas computation.
+fn validate_copy_costs(
+    element_size: i16,
+    branch_steps: [i32; 2],
+    element_steps: i32,
+    expected_fixed_costs: [ConstCost; 2],
+) {
+    // Manually counted, a single range check is used by each branch.
+    assert_eq!(
+        branch_steps.map(|steps| ConstCost { steps, holes: 0, range_checks: 1 }),
+        expected_fixed_costs
+    );
+    assert_eq!(ConstCost::steps(element_steps), array_copy_element_cost(element_size.into()));
+}
@@ -0,0 +1,71 @@
+use cairo_lang_casm::casm;
+use cairo_lang_casm::cell_expression::CellExpression;
+use cairo_lang_casm::operand::{CellRef, Register};
+use cairo_lang_sierra::extensions::const_type::{ConstAsConcreteLibfunc, ConstConcreteLibfunc};
+
+use super::{CompiledInvocation, CompiledInvocationBuilder, InvocationError};
+use crate::references::ReferenceExpression;
+use crate::relocations::{Relocation, RelocationEntry};
+
+/// Builds instructions for Sierra const operations.
+pub fn build(
+    libfunc: &ConstConcreteLibfunc,
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    match libfunc {
+        ConstConcreteLibfunc::AsBox(libfunc) => build_const_as_box(libfunc, builder),
+        ConstConcreteLibfunc::AsImmediate(libfunc) => build_const_as_immediate(libfunc, builder),
+    }
+}
+
+/// Handles the const_as_immediate libfunc, returning the value of the const as immediates.
+fn build_const_as_immediate(
+    libfunc: &ConstAsConcreteLibfunc,
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    let value = builder
+        .program_info
+        .consts_info
+        .values
+        .get(&libfunc.const_type)
+        .ok_or(InvocationError::UnknownTypeData)?;
+    let cells = value.iter().cloned().map(CellExpression::Immediate).collect();
+    Ok(builder.build_only_reference_changes([ReferenceExpression { cells }].into_iter()))
+}
+
+/// Handles the const_as_box libfunc, returning a pointer to the value of the const in the
+/// constants segment.
+fn build_const_as_box(
+    libfunc: &ConstAsConcreteLibfunc,
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    if !builder.program_info.consts_info.segment_offsets.contains_key(&libfunc.const_type) {
+        return Err(InvocationError::UnknownTypeData);
+    }
+    let ctx = casm! {
+        // The relocation table will point the `call` to the end of the program where there will
+        // be a `ret` instruction.
+        call rel 0;
+        // After calling an empty function, `[ap - 1]` contains the current `pc`.
+        // Using the relocation below, the immediate value (`0`) will be changed so that it will
+        // compute a pointer to the value of the const in the constants segment.
+        [ap] = [ap - 1] + 0, ap++;
+    };
+    let relocations = vec![
+        RelocationEntry { instruction_idx: 0, relocation: Relocation::EndOfProgram },
+        RelocationEntry {
+            instruction_idx: 1,
+            relocation: Relocation::ConstStart(libfunc.const_type.clone()),
+        },
+    ];
+    Ok(builder.build(
+        ctx.instructions,
+        relocations,
+        [vec![ReferenceExpression::from_cell(CellExpression::Deref(CellRef {
+            register: Register::AP,
+            offset: -1,
+        }))]
+        .into_iter()]
+        .into_iter(),
+    ))
+}
@@ -0,0 +1,72 @@
+//! CASM lowering of libfuncs provided by downstream crates - see
+//! [cairo_lang_sierra::extensions::custom].
+
+use std::sync::Arc;
+
+use cairo_lang_sierra::extensions::custom::{
+    CustomConcreteLibfunc, LibfuncExtension, LibfuncExtensionError, LibfuncExtensions,
+};
+use cairo_lang_sierra::ids::GenericLibfuncId;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+
+use super::{CompiledInvocation, CompiledInvocationBuilder, InvocationError};
+
+#[cfg(test)]
+#[path = "custom_test.rs"]
+mod test;
+
+/// Trait for implementing a libfunc outside of the core library, including its CASM lowering.
+pub trait CasmLibfuncExtension: LibfuncExtension {
+    /// Builds the CASM instructions of an invocation of the libfunc.
+    /// The ap changes and costs of the result must match
+    /// [LibfuncExtension::branch_costs].
+    fn build(
+        &self,
+        libfunc: &CustomConcreteLibfunc,
+        builder: CompiledInvocationBuilder<'_>,
+    ) -> Result<CompiledInvocation, InvocationError>;
+}
+
+/// A registry of libfunc extensions along with their CASM lowerings, by their generic libfunc ids.
+#[derive(Clone, Default)]
+pub struct CasmLibfuncExtensions {
+    /// The Sierra side of the registered extensions.
+    libfunc_extensions: LibfuncExtensions,
+    /// The CASM lowerings of the registered extensions.
+    casm_extensions: OrderedHashMap<GenericLibfuncId, Arc<dyn CasmLibfuncExtension>>,
+}
+impl CasmLibfuncExtensions {
+    /// Registers a libfunc extension under the given id, both as a Sierra libfunc and for compiling
+    /// it to CASM. Fails if the id is already in use.
+    pub fn register<T: CasmLibfuncExtension + 'static>(
+        &mut self,
+        id: GenericLibfuncId,
+        extension: Arc<T>,
+    ) -> Result<(), LibfuncExtensionError> {
+        self.libfunc_extensions.register(id.clone(), extension.clone())?;
+        self.casm_extensions.insert(id, extension);
+        Ok(())
+    }
+
+    /// Returns the Sierra side of the registered extensions, for creating program registries and
+    /// computing metadata.
+    pub fn libfunc_extensions(&self) -> &LibfuncExtensions {
+        &self.libfunc_extensions
+    }
+}
+
+/// Builds instructions for Sierra custom libfuncs.
+pub fn build(
+    libfunc: &CustomConcreteLibfunc,
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    let Some(extension) =
+        builder.program_info.libfunc_extensions.casm_extensions.get(&libfunc.id).cloned()
+    else {
+        return Err(InvocationError::NotImplementedStr {
+            invocation: builder.invocation.clone(),
+            message: format!("No CASM lowering provided for `{}`.", libfunc.id),
+        });
+    };
+    extension.build(libfunc, builder)
+}
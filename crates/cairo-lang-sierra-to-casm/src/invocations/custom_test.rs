@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use cairo_lang_casm::builder::CasmBuilder;
+use cairo_lang_casm::casm_build_extend;
+use cairo_lang_sierra::extensions::custom::{
+    CustomBranchCost, CustomConcreteLibfunc, LibfuncExtension,
+};
+use cairo_lang_sierra::extensions::felt252::Felt252Type;
+use cairo_lang_sierra::extensions::lib_func::{
+    LibfuncSignature, OutputVarInfo, SierraApChange, SignatureSpecializationContext,
+};
+use cairo_lang_sierra::extensions::{NamedType, OutputVarReferenceInfo, SpecializationError};
+use cairo_lang_sierra::program::{GenericArg, Program};
+use cairo_lang_sierra::simulation::value::CoreValue;
+use cairo_lang_sierra::simulation::LibfuncSimulationError;
+use cairo_lang_sierra::program_registry::ProgramRegistryError;
+use cairo_lang_sierra::ProgramParser;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::{CasmLibfuncExtension, CasmLibfuncExtensions};
+use crate::compiler::{compile, compile_with_config, CompilationError, SierraToCasmConfig};
+use crate::invocations::{
+    CompiledInvocation, CompiledInvocationBuilder, CostValidationInfo, InvocationError,
+};
+use crate::metadata::{calc_metadata, MetadataComputationConfig};
+use crate::test_utils::strip_comments_and_linebreaks;
+
+/// A libfunc extension tripling a felt252.
+struct Felt252Triple;
+impl LibfuncExtension for Felt252Triple {
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        _args: &[GenericArg],
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        let felt252_ty = context.get_concrete_type(Felt252Type::id(), &[])?;
+        Ok(LibfuncSignature::new_non_branch(
+            vec![felt252_ty.clone()],
+            vec![OutputVarInfo {
+                ty: felt252_ty,
+                ref_info: OutputVarReferenceInfo::NewTempVar { idx: 0 },
+            }],
+            SierraApChange::Known { new_vars_only: false },
+        ))
+    }
+
+    fn branch_costs(&self, _args: &[GenericArg]) -> Vec<CustomBranchCost> {
+        vec![CustomBranchCost { steps: 2, ap_change: 2, ..Default::default() }]
+    }
+
+    fn simulate(
+        &self,
+        _args: &[GenericArg],
+        inputs: Vec<CoreValue>,
+    ) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+        match &inputs[..] {
+            [CoreValue::Felt252(value)] => Ok((vec![CoreValue::Felt252(value * 3)], 0)),
+            [_] => Err(LibfuncSimulationError::WrongArgType),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        }
+    }
+}
+impl CasmLibfuncExtension for Felt252Triple {
+    fn build(
+        &self,
+        _libfunc: &CustomConcreteLibfunc,
+        builder: CompiledInvocationBuilder<'_>,
+    ) -> Result<CompiledInvocation, InvocationError> {
+        let [value] = builder.try_get_single_cells()?;
+        let mut casm_builder = CasmBuilder::default();
+        let value = casm_builder.add_var(value.clone());
+        casm_build_extend! {casm_builder,
+            tempvar doubled = value + value;
+            tempvar tripled = doubled + value;
+        };
+        Ok(builder.build_from_casm_builder(
+            casm_builder,
+            [("Fallthrough", &[&[tripled]], None)],
+            CostValidationInfo::default(),
+        ))
+    }
+}
+
+/// Returns a program calling `triple` on its argument, with `triple` being the given libfunc.
+fn triple_program(libfunc: &str) -> Program {
+    ProgramParser::new()
+        .parse(&format!(
+            indoc! {"
+                type felt252 = felt252;
+
+                libfunc triple = {};
+
+                triple([0]) -> ([0]);
+                return ([0]);
+
+                main@0([0]: felt252) -> (felt252);
+            "},
+            libfunc
+        ))
+        .unwrap()
+}
+
+#[test]
+fn test_compile_custom_libfunc() {
+    let mut libfunc_extensions = CasmLibfuncExtensions::default();
+    libfunc_extensions.register("test_felt252_triple".into(), Arc::new(Felt252Triple)).unwrap();
+    let program = triple_program("test_felt252_triple");
+    let metadata = calc_metadata(
+        &program,
+        MetadataComputationConfig {
+            libfunc_extensions: libfunc_extensions.libfunc_extensions().clone(),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let config =
+        SierraToCasmConfig { gas_usage_check: true, libfunc_extensions, ..Default::default() };
+    assert_eq!(
+        compile_with_config(&program, &metadata, &config).unwrap().to_string(),
+        strip_comments_and_linebreaks(indoc! {"
+            [ap + 0] = [fp + -3] + [fp + -3], ap++;
+            [ap + 0] = [ap + -1] + [fp + -3], ap++;
+            ret;
+        "})
+    );
+}
+
+#[test]
+fn test_compile_custom_libfunc_without_extension() {
+    let mut libfunc_extensions = CasmLibfuncExtensions::default();
+    libfunc_extensions.register("test_felt252_triple".into(), Arc::new(Felt252Triple)).unwrap();
+    let program = triple_program("test_felt252_triple");
+    let metadata = calc_metadata(
+        &program,
+        MetadataComputationConfig {
+            libfunc_extensions: libfunc_extensions.libfunc_extensions().clone(),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    // Compiling without providing the extension fails, as the libfunc is unknown.
+    assert!(matches!(
+        *compile(&program, &metadata, true).unwrap_err(),
+        CompilationError::ProgramRegistryError(error)
+            if matches!(*error, ProgramRegistryError::LibfuncSpecialization { .. })
+    ));
+}
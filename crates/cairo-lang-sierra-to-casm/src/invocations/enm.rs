@@ -1,7 +1,10 @@
+use cairo_lang_casm::builder::CasmBuilder;
 use cairo_lang_casm::cell_expression::CellExpression;
 use cairo_lang_casm::operand::CellRef;
-use cairo_lang_casm::{casm, casm_extend};
-use cairo_lang_sierra::extensions::enm::{EnumConcreteLibfunc, EnumInitConcreteLibfunc};
+use cairo_lang_casm::{casm, casm_build_extend, casm_extend};
+use cairo_lang_sierra::extensions::enm::{
+    EnumConcreteLibfunc, EnumDiscriminantsConcreteLibfunc, EnumInitConcreteLibfunc,
+};
 use cairo_lang_sierra::extensions::ConcreteLibfunc;
 use cairo_lang_sierra::ids::ConcreteTypeId;
 use cairo_lang_sierra::program::{BranchInfo, BranchTarget};
@@ -12,7 +15,7 @@ use num_bigint::BigInt;
 use super::{
     CompiledInvocation, CompiledInvocationBuilder, InvocationError, ReferenceExpressionView,
 };
-use crate::invocations::ProgramInfo;
+use crate::invocations::{add_input_variables, get_non_fallthrough_statement_id, ProgramInfo};
 use crate::references::{ReferenceExpression, ReferencesError};
 use crate::relocations::{Relocation, RelocationEntry};
 
@@ -28,6 +31,13 @@ pub fn build(
         EnumConcreteLibfunc::Match(_) | EnumConcreteLibfunc::SnapshotMatch(_) => {
             build_enum_match(builder)
         }
+        EnumConcreteLibfunc::ToFelt252(EnumDiscriminantsConcreteLibfunc {
+            discriminants, ..
+        }) => build_enum_to_felt252(builder, discriminants),
+        EnumConcreteLibfunc::TryFromFelt252(EnumDiscriminantsConcreteLibfunc {
+            discriminants,
+            ..
+        }) => build_enum_try_from_felt252(builder, discriminants),
     }
 }
 
@@ -56,30 +66,7 @@ fn build_enum_init(
 ) -> Result<CompiledInvocation, InvocationError> {
     let [expression] = builder.try_get_refs()?;
     let init_arg_cells = &expression.cells;
-    let variant_selector = if num_variants <= 2 {
-        // For num_branches <= 2, we use the index as the variant_selector as the `match`
-        // implementation jumps to the index 0 statement on 0, and to the index 1 statement on
-        // 1.
-        index
-    } else {
-        // For num_branches > 2, the `enum_match` libfunc is implemented using a jump table. In
-        // order to optimize `enum_match`, we define the variant_selector as the relevant
-        // relative jump in case we match the actual variant.
-        //
-        // - To jump to the variant in index 0, we skip the jump table and directly jump to it. Its
-        //   location is (2 * n - 1) CASM steps ahead, where n is the number of variants in this
-        //   enum (2 per variant but the first variant, and 1 for the first jump with a deref
-        //   operand).
-        // - To jump to the variant in index k, we add "jump rel (2 * (n - k) - 1)" as the first
-        //   jump is of size 1 and the rest of the jump instructions are with an immediate operand,
-        //   which makes them of size 2.
-        match (num_variants - index).checked_mul(2) {
-            Some(double) => double - 1,
-            None => {
-                return Err(InvocationError::IntegerOverflow);
-            }
-        }
-    };
+    let variant_selector = get_variant_selector(num_variants, index)?;
 
     let variant_size = builder
         .program_info
@@ -109,6 +96,118 @@ fn build_enum_init(
     Ok(builder.build_only_reference_changes(output_expressions))
 }
 
+/// Returns the variant selector of the variant at `index` of an enum with `num_variants` variants.
+pub(crate) fn get_variant_selector(
+    num_variants: usize,
+    index: usize,
+) -> Result<usize, InvocationError> {
+    Ok(if num_variants <= 2 {
+        // For num_branches <= 2, we use the index as the variant_selector as the `match`
+        // implementation jumps to the index 0 statement on 0, and to the index 1 statement on
+        // 1.
+        index
+    } else {
+        // For num_branches > 2, the `enum_match` libfunc is implemented using a jump table. In
+        // order to optimize `enum_match`, we define the variant_selector as the relevant
+        // relative jump in case we match the actual variant.
+        //
+        // - To jump to the variant in index 0, we skip the jump table and directly jump to it. Its
+        //   location is (2 * n - 1) CASM steps ahead, where n is the number of variants in this
+        //   enum (2 per variant but the first variant, and 1 for the first jump with a deref
+        //   operand).
+        // - To jump to the variant in index k, we add "jump rel (2 * (n - k) - 1)" as the first
+        //   jump is of size 1 and the rest of the jump instructions are with an immediate operand,
+        //   which makes them of size 2.
+        match (num_variants - index).checked_mul(2) {
+            Some(double) => double - 1,
+            None => {
+                return Err(InvocationError::IntegerOverflow);
+            }
+        }
+    })
+}
+
+/// Handles statement for converting a value of a C-like enum to the discriminant of its variant.
+/// Compares the variant selector to the selectors of the variants one by one, where the last
+/// variant needs no comparison. All the cells are allocated in advance, so that all the paths end
+/// with the same AP change.
+fn build_enum_to_felt252(
+    builder: CompiledInvocationBuilder<'_>,
+    discriminants: &[BigInt],
+) -> Result<CompiledInvocation, InvocationError> {
+    let [selector] = builder.try_get_single_cells()?;
+    let num_variants = discriminants.len();
+    let mut casm_builder = CasmBuilder::default();
+    add_input_variables! {casm_builder, deref selector; };
+    casm_build_extend! {casm_builder, tempvar value; };
+    let diffs: Vec<_> = (1..num_variants).map(|_| casm_builder.alloc_var(false)).collect();
+    casm_build_extend! {casm_builder, ap += num_variants; };
+    for (index, (discriminant, diff)) in discriminants.iter().zip(diffs).enumerate() {
+        let next_variant = format!("NotVariant{index}");
+        casm_build_extend! {casm_builder,
+            const variant_selector = get_variant_selector(num_variants, index)?;
+            assert selector = diff + variant_selector;
+        };
+        casm_builder.jump_nz(diff, next_variant.clone());
+        casm_build_extend! {casm_builder,
+            const variant_discriminant = discriminant.clone();
+            assert value = variant_discriminant;
+            jump Done;
+        };
+        casm_builder.label(next_variant);
+    }
+    casm_build_extend! {casm_builder,
+        const last_discriminant = discriminants[num_variants - 1].clone();
+        assert value = last_discriminant;
+        Done:
+    };
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [("Fallthrough", &[&[value]], None)],
+        Default::default(),
+    ))
+}
+
+/// Handles statement for converting a felt252 to the value of a C-like enum whose variant has it
+/// as its discriminant. Compares the value to the discriminants of the variants one by one. All
+/// the cells are allocated in advance, so that all the paths end with the same AP change.
+fn build_enum_try_from_felt252(
+    builder: CompiledInvocationBuilder<'_>,
+    discriminants: &[BigInt],
+) -> Result<CompiledInvocation, InvocationError> {
+    let [value] = builder.try_get_single_cells()?;
+    let num_variants = discriminants.len();
+    let mut casm_builder = CasmBuilder::default();
+    add_input_variables! {casm_builder, deref value; };
+    casm_build_extend! {casm_builder, tempvar selector; };
+    let diffs: Vec<_> = (0..num_variants).map(|_| casm_builder.alloc_var(false)).collect();
+    casm_build_extend! {casm_builder, ap += num_variants + 1; };
+    for (index, (discriminant, diff)) in discriminants.iter().zip(diffs).enumerate() {
+        let next_variant = format!("NotVariant{index}");
+        casm_build_extend! {casm_builder,
+            const variant_discriminant = discriminant.clone();
+            assert value = diff + variant_discriminant;
+        };
+        casm_builder.jump_nz(diff, next_variant.clone());
+        casm_build_extend! {casm_builder,
+            const variant_selector = get_variant_selector(num_variants, index)?;
+            assert selector = variant_selector;
+            jump Found;
+        };
+        casm_builder.label(next_variant);
+    }
+    casm_build_extend! {casm_builder,
+        jump Failure;
+        Found:
+    };
+    let failure_handle = get_non_fallthrough_statement_id(&builder);
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [("Fallthrough", &[&[selector]], None), ("Failure", &[], Some(failure_handle))],
+        Default::default(),
+    ))
+}
+
 /// Handles statement for matching an enum.
 fn build_enum_match(
     builder: CompiledInvocationBuilder<'_>,
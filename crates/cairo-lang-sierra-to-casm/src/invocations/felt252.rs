@@ -29,6 +29,7 @@ pub fn build(
             Felt252OperationWithConstConcreteLibfunc { operator, c, .. },
         )) => build_felt252_op_with_const(builder, *operator, c.clone()),
         Felt252Concrete::IsZero(_) => build_is_zero(builder),
+        Felt252Concrete::Inverse(_) => build_felt252_inverse(builder),
         Felt252Concrete::Const(libfunc) => Ok(builder.build_only_reference_changes(
             [ReferenceExpression::from_cell(CellExpression::Immediate(libfunc.c.clone()))]
                 .into_iter(),
@@ -73,6 +74,28 @@ fn build_felt252_op_with_const(
     ))
 }
 
+/// Handles the inverse of a non-zero felt252.
+fn build_felt252_inverse(
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    let [value] = builder.try_get_single_cells()?;
+    let mut casm_builder = CasmBuilder::default();
+    add_input_variables! {casm_builder, deref value; };
+    casm_build_extend! {casm_builder,
+        const one = 1;
+        tempvar inverse;
+        hint FieldInverse {val: value} into {inverse: inverse};
+        // Verify the inverse provided by the hint.
+        tempvar product = inverse * value;
+        assert product = one;
+    };
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [("Fallthrough", &[&[inverse]], None)],
+        Default::default(),
+    ))
+}
+
 /// Helper for the build felt252 binary op functions: returns the res Var and the extra costs for a
 /// binary operation.
 fn bin_op_helper(
@@ -5,6 +5,7 @@ use cairo_lang_sierra::extensions::int::{IntConstConcreteLibfunc, IntTraits};
 use num_bigint::BigInt;
 
 use super::{CompiledInvocation, CompiledInvocationBuilder, InvocationError};
+use crate::invocations::misc::sub_with_rc;
 use crate::invocations::{
     add_input_variables, get_non_fallthrough_statement_id, CostValidationInfo,
 };
@@ -62,23 +63,8 @@ fn build_small_diff(
         deref a;
         deref b;
     };
-    casm_build_extend! {casm_builder,
-        let orig_range_check = range_check;
-        tempvar a_ge_b;
-        tempvar a_minus_b = a - b;
-        const u128_limit = BigInt::from(u128::MAX) + BigInt::from(1);
-        const limit = limit;
-        hint TestLessThan {lhs: a_minus_b, rhs: limit} into {dst: a_ge_b};
-        jump NoOverflow if a_ge_b != 0;
-        // Overflow (negative):
-        // Here we know that 0 - (limit - 1) <= a - b < 0.
-        tempvar fixed_a_minus_b = a_minus_b + u128_limit;
-        assert fixed_a_minus_b = *(range_check++);
-        let wrapping_a_minus_b = a_minus_b + limit;
-        jump Target;
-    NoOverflow:
-        assert a_minus_b = *(range_check++);
-    };
+    casm_build_extend!(casm_builder, let orig_range_check = range_check;);
+    let (a_minus_b, wrapping_a_minus_b) = sub_with_rc(&mut casm_builder, range_check, a, b, limit);
 
     let failure_handle_statement_id = get_non_fallthrough_statement_id(&builder);
     Ok(builder.build_from_casm_builder(
@@ -106,21 +92,14 @@ fn build_128bit_diff(
         deref a;
         deref b;
     };
-    casm_build_extend! {casm_builder,
-        let orig_range_check = range_check;
-        tempvar a_ge_b;
-        tempvar a_minus_b = a - b;
-        const u128_limit = BigInt::from(u128::MAX) + BigInt::from(1);
-        hint TestLessThan {lhs: a_minus_b, rhs: u128_limit} into {dst: a_ge_b};
-        jump NoOverflow if a_ge_b != 0;
-        // Overflow (negative):
-        // Here we know that 0 - (2**128 - 1) <= a - b < 0.
-        tempvar wrapping_a_minus_b = a_minus_b + u128_limit;
-        assert wrapping_a_minus_b = *(range_check++);
-        jump Target;
-    NoOverflow:
-        assert a_minus_b = *(range_check++);
-    };
+    casm_build_extend!(casm_builder, let orig_range_check = range_check;);
+    let (a_minus_b, wrapping_a_minus_b) = sub_with_rc(
+        &mut casm_builder,
+        range_check,
+        a,
+        b,
+        BigInt::from(u128::MAX) + BigInt::from(1),
+    );
     Ok(builder.build_from_casm_builder(
         casm_builder,
         [
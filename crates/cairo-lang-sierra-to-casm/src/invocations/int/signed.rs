@@ -1,7 +1,7 @@
 use cairo_felt::Felt252;
 use cairo_lang_casm::builder::CasmBuilder;
 use cairo_lang_casm::casm_build_extend;
-use cairo_lang_sierra::extensions::int::signed::{SintConcrete, SintTraits};
+use cairo_lang_sierra::extensions::int::signed::{SintConcrete, SmallSintTraits};
 use cairo_lang_sierra::extensions::int::{IntMulTraits, IntOperator};
 use cairo_lang_sierra::extensions::is_zero::IsZeroTraits;
 use cairo_lang_sierra::program::{BranchInfo, BranchTarget};
@@ -14,6 +14,10 @@ use crate::invocations::{
     CostValidationInfo, InvocationError,
 };
 
+#[cfg(test)]
+#[path = "signed_test.rs"]
+mod test;
+
 /// Handles a signed integer conversion from felt252.
 /// `[min_value, max_value]` is the range of the signed integer.
 pub fn build_sint_from_felt252(
@@ -174,9 +178,109 @@ pub fn build_sint_overflowing_operation(
     ))
 }
 
+/// Handles the division with remainder of signed integers, rounding toward zero.
+/// `[min_value, max_value]` is the range of the signed integer.
+/// Note: this function assumes that the range of the integer is small enough for the product of
+/// any 128-bit number and a value of the integer to be far smaller than the prime.
+pub fn build_sint_div_rem(
+    builder: CompiledInvocationBuilder<'_>,
+    min_value: i128,
+    max_value: i128,
+) -> Result<CompiledInvocation, InvocationError> {
+    let [range_check, a, b] = builder.try_get_single_cells()?;
+    let mut casm_builder = CasmBuilder::default();
+    add_input_variables! {casm_builder,
+        buffer(4) range_check;
+        deref a;
+        deref b;
+    };
+    casm_build_extend! {casm_builder,
+        let orig_range_check = range_check;
+        // Unverified values computed by the hints: the absolute values of the operands, the
+        // quotient and remainder of their division, and whether the quotient is non-negative.
+        tempvar a_abs;
+        tempvar b_abs;
+        tempvar q_abs;
+        tempvar r_abs;
+        tempvar q_is_non_negative;
+        tempvar q_abs_if_non_negative;
+        tempvar twice_q_abs_if_non_negative;
+        tempvar q_shifted;
+        tempvar qb;
+        tempvar r_shifted;
+        tempvar r_fixed;
+        tempvar b_squared;
+        tempvar r_squared;
+        tempvar r_squared_plus_one;
+        tempvar b_squared_minus_r_squared_minus_one;
+        tempvar ra;
+        tempvar q_minus_overflow_value;
+        // The results are the last allocated variables.
+        tempvar q;
+        tempvar r;
+        let a_squared_expr = a * a;
+        let b_squared_expr = b * b;
+        let ab = a * b;
+        hint SquareRoot {value: a_squared_expr} into {dst: a_abs};
+        hint SquareRoot {value: b_squared_expr} into {dst: b_abs};
+        hint DivMod {lhs: a_abs, rhs: b_abs} into {quotient: q_abs, remainder: r_abs};
+        const u128_limit = (BigInt::from(u128::MAX) + 1) as BigInt;
+        hint TestLessThan {lhs: ab, rhs: u128_limit} into {dst: q_is_non_negative};
+        // q = (2 * q_is_non_negative - 1) * q_abs.
+        assert q_abs_if_non_negative = q_is_non_negative * q_abs;
+        assert twice_q_abs_if_non_negative = q_abs_if_non_negative + q_abs_if_non_negative;
+        assert q = twice_q_abs_if_non_negative - q_abs;
+        // Verify `min_value <= q < min_value + 2**128`.
+        const minus_min_value = -BigInt::from(min_value);
+        assert q_shifted = q + minus_min_value;
+        assert q_shifted = *(range_check++);
+        // Define `r` as `a - q * b`. Since `|q * b| < 2**128 * 2**63`, this is an equality as
+        // integers (rather than only as field elements).
+        assert qb = q * b;
+        assert a = qb + r;
+        // Verify `min_value <= r <= max_value`.
+        assert r_shifted = r + minus_min_value;
+        assert r_shifted = *(range_check++);
+        // r + 2**128 - max_value - 1 < 2**128 ==> r <= max_value
+        const max_value_fixer = BigInt::from(u128::MAX) - BigInt::from(max_value);
+        assert r_fixed = r + max_value_fixer;
+        assert r_fixed = *(range_check++);
+        // Verify `|r| < |b|` by constraining `0 <= b**2 - r**2 - 1`.
+        assert b_squared = b * b;
+        assert r_squared = r * r;
+        const one = 1;
+        assert r_squared_plus_one = r_squared + one;
+        assert b_squared_minus_r_squared_minus_one = b_squared - r_squared_plus_one;
+        assert b_squared_minus_r_squared_minus_one = *(range_check++);
+        // Verify that `r` is zero or has the sign of `a` by constraining `0 <= r * a`.
+        assert ra = r * a;
+        assert ra = *(range_check++);
+        // The verified `q` and `r` are the unique quotient and remainder of the division rounding
+        // toward zero. Therefore `q <= max_value + 1`, where equality happens only when dividing
+        // `min_value` by -1.
+        const overflow_value = BigInt::from(max_value) + 1;
+        assert q_minus_overflow_value = q - overflow_value;
+        jump NoOverflow if q_minus_overflow_value != 0;
+        jump Overflow;
+    NoOverflow:
+    };
+    let overflow_handle_statement_id = get_non_fallthrough_statement_id(&builder);
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [
+            ("Fallthrough", &[&[range_check], &[q], &[r]], None),
+            ("Overflow", &[&[range_check]], Some(overflow_handle_statement_id)),
+        ],
+        CostValidationInfo {
+            range_check_info: Some((orig_range_check, range_check)),
+            extra_costs: None,
+        },
+    ))
+}
+
 /// Builds instructions for Sierra i8/i16/i32/i64 operations.
 pub fn build_sint<
-    TSintTraits: SintTraits + IntMulTraits + IsZeroTraits,
+    TSintTraits: SmallSintTraits + IntMulTraits + IsZeroTraits,
     const MIN_VALUE: i128,
     const MAX_VALUE: i128,
 >(
@@ -196,5 +300,6 @@ pub fn build_sint<
         SintConcrete::Diff(_) => {
             build_small_diff(builder, BigInt::from(MAX_VALUE) + 1 - BigInt::from(MIN_VALUE))
         }
+        SintConcrete::DivRem(_) => build_sint_div_rem(builder, MIN_VALUE, MAX_VALUE),
     }
 }
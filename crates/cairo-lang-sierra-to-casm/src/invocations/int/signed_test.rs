@@ -0,0 +1,66 @@
+use cairo_lang_casm::executor::Vm;
+use cairo_lang_casm::operand::prime;
+use num_bigint::BigInt;
+use test_case::test_case;
+
+use crate::invocations::test_utils::run_libfunc;
+
+/// Runs the `div_rem` libfunc of the signed integer type named `ty` on the given values.
+/// Returns the quotient and the remainder, or `None` if the overflow branch was taken.
+/// Also checks that all the range checked values are u128s - i.e. that the run would be accepted
+/// by the range check builtin.
+fn run_div_rem(ty: &str, a: i64, b: i64) -> Option<(i64, i64)> {
+    let mut vm = Vm::new();
+    let range_check = vm.add_segment();
+    let field_element = |value: i64| (BigInt::from(value) + prime()) % prime();
+    let args = [BigInt::from(range_check), field_element(a), field_element(b)];
+    let (branch, values) = run_libfunc(&mut vm, &format!("{ty}_div_rem"), &args).unwrap();
+    let range_check_end = usize::try_from(&values[0]).unwrap();
+    assert_eq!(range_check_end - range_check, 5);
+    let u128_limit = BigInt::from(1) << 128;
+    let all_u128 = (range_check..range_check_end)
+        .all(|address| vm.memory.get(address).expect("Unset range check cell.") < &u128_limit);
+    assert!(all_u128);
+    let signed_value = |value: &BigInt| {
+        let value = if value > &(prime() >> 1) { value - prime() } else { value.clone() };
+        i64::try_from(value).unwrap()
+    };
+    match branch {
+        0 => Some((signed_value(&values[1]), signed_value(&values[2]))),
+        1 => None,
+        _ => panic!("Unexpected branch {branch}."),
+    }
+}
+
+#[test_case(7, 2 => Some((3, 1)); "positive by positive")]
+#[test_case(-7, 2 => Some((-3, -1)); "negative by positive")]
+#[test_case(7, -2 => Some((-3, 1)); "positive by negative")]
+#[test_case(-7, -2 => Some((3, -1)); "negative by negative")]
+#[test_case(0, -5 => Some((0, 0)); "zero")]
+#[test_case(6, -3 => Some((-2, 0)); "exact")]
+#[test_case(127, -128 => Some((0, 127)); "max by min")]
+#[test_case(-128, 127 => Some((-1, -1)); "min by max")]
+#[test_case(-128, 1 => Some((-128, 0)); "min by one")]
+#[test_case(-128, -1 => None; "overflow")]
+fn test_i8_div_rem(a: i64, b: i64) -> Option<(i64, i64)> {
+    run_div_rem("i8", a, b)
+}
+
+#[test]
+fn test_i8_div_rem_all_divisors() {
+    for b in (i8::MIN..=i8::MAX).filter(|b| *b != 0) {
+        for a in [i8::MIN, i8::MIN + 1, -100, -1, 0, 1, 99, i8::MAX] {
+            let expected = a.checked_div(b).map(|q| (i64::from(q), i64::from(a % b)));
+            assert_eq!(run_div_rem("i8", a.into(), b.into()), expected, "{a} / {b}");
+        }
+    }
+}
+
+#[test_case(i64::MAX, i64::MIN => Some((0, i64::MAX)); "max by min")]
+#[test_case(i64::MIN, 2 => Some((i64::MIN / 2, 0)); "min by two")]
+#[test_case(i64::MIN + 1, -1 => Some((i64::MAX, 0)); "almost min by minus one")]
+#[test_case(-1_000_000_007, 1_000 => Some((-1_000_000, -7)); "big negative")]
+#[test_case(i64::MIN, -1 => None; "overflow")]
+fn test_i64_div_rem(a: i64, b: i64) -> Option<(i64, i64)> {
+    run_div_rem("i64", a, b)
+}
@@ -3,13 +3,13 @@ use std::ops::Shl;
 use cairo_felt::Felt252;
 use cairo_lang_casm::builder::CasmBuilder;
 use cairo_lang_casm::casm_build_extend;
-use cairo_lang_sierra::extensions::int::unsigned::{UintConcrete, UintTraits};
+use cairo_lang_sierra::extensions::int::unsigned::{SmallUintTraits, UintConcrete};
 use cairo_lang_sierra::extensions::int::{IntMulTraits, IntOperator};
 use cairo_lang_sierra::extensions::is_zero::IsZeroTraits;
 use num_bigint::{BigInt, ToBigInt};
 
 use super::{build_const, build_small_diff, build_small_wide_mul};
-use crate::invocations::misc::validate_under_limit;
+use crate::invocations::misc::{compare_with_rc, validate_under_limit};
 use crate::invocations::{
     add_input_variables, bitwise, get_non_fallthrough_statement_id, misc, CompiledInvocation,
     CompiledInvocationBuilder, CostValidationInfo, InvocationError,
@@ -66,6 +66,83 @@ fn build_small_uint_overflowing_add(
     ))
 }
 
+/// Handles a small uint multiplication, failing on overflow.
+/// All parameters values are smaller than `limit`, where `limit <= 2**64`, so the product is
+/// smaller than 2**128.
+fn build_small_uint_checked_mul(
+    builder: CompiledInvocationBuilder<'_>,
+    limit: u128,
+) -> Result<CompiledInvocation, InvocationError> {
+    let failure_handle_statement_id = get_non_fallthrough_statement_id(&builder);
+    let [range_check, a, b] = builder.try_get_single_cells()?;
+    let mut casm_builder = CasmBuilder::default();
+    add_input_variables! {casm_builder,
+        buffer(0) range_check;
+        deref a;
+        deref b;
+    };
+    casm_build_extend! {casm_builder,
+            let orig_range_check = range_check;
+            const limit_fixer = (u128::MAX - limit + 1);
+            const limit = limit;
+            tempvar a_mul_b = a * b;
+            tempvar no_overflow;
+            hint TestLessThan {lhs: a_mul_b, rhs: limit} into {dst: no_overflow};
+            jump NoOverflow if no_overflow != 0;
+            // Overflow:
+            // Here we know that `limit <= a * b < limit**2 <= 2**128`.
+            tempvar a_mul_b_minus_limit = a_mul_b - limit;
+            assert a_mul_b_minus_limit = *(range_check++);
+            jump Target;
+        NoOverflow:
+            // Here we know that `0 <= a * b < limit`
+            // ==> `0 <= a * b + 2**128 - limit < 2**128`.
+            tempvar fixed_a_mul_b = a_mul_b + limit_fixer;
+            assert fixed_a_mul_b = *(range_check++);
+    };
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [
+            ("Fallthrough", &[&[range_check], &[a_mul_b]], None),
+            ("Target", &[&[range_check]], Some(failure_handle_statement_id)),
+        ],
+        CostValidationInfo {
+            range_check_info: Some((orig_range_check, range_check)),
+            extra_costs: None,
+        },
+    ))
+}
+
+/// Handles a small uint `a < b` check, or `a <= b` check if `strict` is false.
+/// All parameters values are smaller than 2**64, so the differences between them are in the range
+/// `(-2**64, 2**64)`, and are non-negative exactly when they are in the range check range.
+fn build_small_uint_compare(
+    builder: CompiledInvocationBuilder<'_>,
+    strict: bool,
+) -> Result<CompiledInvocation, InvocationError> {
+    let target_statement_id = get_non_fallthrough_statement_id(&builder);
+    let [range_check, a, b] = builder.try_get_single_cells()?;
+    let mut casm_builder = CasmBuilder::default();
+    add_input_variables! {casm_builder,
+        buffer(0) range_check;
+        deref a;
+        deref b;
+    };
+    casm_build_extend!(casm_builder, let orig_range_check = range_check;);
+    compare_with_rc(&mut casm_builder, range_check, a, b, strict);
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [
+            ("Fallthrough", &[&[range_check]], None),
+            ("Target", &[&[range_check]], Some(target_statement_id)),
+        ],
+        CostValidationInfo {
+            range_check_info: Some((orig_range_check, range_check)),
+            extra_costs: None,
+        },
+    ))
+}
+
 /// Handles a small uint conversion from felt252.
 fn build_small_uint_from_felt252<const LIMIT: u128, const K: u8>(
     builder: CompiledInvocationBuilder<'_>,
@@ -241,7 +318,7 @@ pub fn build_sqrt(
 }
 
 /// Builds instructions for Sierra u8/u16/u32/u64 operations.
-pub fn build_uint<TUintTraits: UintTraits + IntMulTraits + IsZeroTraits, const LIMIT: u128>(
+pub fn build_uint<TUintTraits: SmallUintTraits + IntMulTraits + IsZeroTraits, const LIMIT: u128>(
     libfunc: &UintConcrete<TUintTraits>,
     builder: CompiledInvocationBuilder<'_>,
 ) -> Result<CompiledInvocation, InvocationError> {
@@ -259,5 +336,8 @@ pub fn build_uint<TUintTraits: UintTraits + IntMulTraits + IsZeroTraits, const L
         UintConcrete::Divmod(_) => build_divmod::<LIMIT>(builder),
         UintConcrete::Bitwise(_) => bitwise::build(builder),
         UintConcrete::WideMul(_) => build_small_wide_mul(builder),
+        UintConcrete::CheckedMul(_) => build_small_uint_checked_mul(builder, LIMIT),
+        UintConcrete::LessThan(_) => build_small_uint_compare(builder, true),
+        UintConcrete::LessThanOrEqual(_) => build_small_uint_compare(builder, false),
     }
 }
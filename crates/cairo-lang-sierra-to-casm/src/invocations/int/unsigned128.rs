@@ -11,6 +11,10 @@ use crate::invocations::{
     CompiledInvocationBuilder, CostValidationInfo, InvocationError,
 };
 
+#[cfg(test)]
+#[path = "unsigned128_test.rs"]
+mod test;
+
 /// Builds instructions for Sierra u128 operations.
 pub fn build(
     libfunc: &Uint128Concrete,
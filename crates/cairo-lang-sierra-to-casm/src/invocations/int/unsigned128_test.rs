@@ -0,0 +1,44 @@
+use cairo_lang_casm::executor::Vm;
+use num_bigint::BigInt;
+use test_case::test_case;
+
+use crate::invocations::test_utils::run_libfunc;
+
+/// Runs a u128 libfunc whose first argument is the range check pointer, and returns the branch
+/// taken and the values of its results, without the range check pointer.
+fn run_with_range_check(libfunc: &str, args: &[u128]) -> (usize, Vec<BigInt>) {
+    let mut vm = Vm::new();
+    let range_check = vm.add_segment();
+    let args = [BigInt::from(range_check)].into_iter().chain(args.iter().map(|arg| (*arg).into()));
+    let (branch, mut values) = run_libfunc(&mut vm, libfunc, &args.collect::<Vec<_>>()).unwrap();
+    assert!(values.remove(0) >= BigInt::from(range_check));
+    (branch, values)
+}
+
+#[test_case(2, 3 => (0, 5); "no overflow")]
+#[test_case(u128::MAX, 0 => (0, u128::MAX); "max")]
+#[test_case(u128::MAX, 2 => (1, 1); "overflow")]
+#[test_case(1 << 127, 1 << 127 => (1, 0); "wrap to zero")]
+fn test_u128_overflowing_add(a: u128, b: u128) -> (usize, u128) {
+    let (branch, values) = run_with_range_check("u128_overflowing_add", &[a, b]);
+    (branch, u128::try_from(&values[0]).unwrap())
+}
+
+#[test_case(5, 3 => (0, 2); "no overflow")]
+#[test_case(3, 3 => (0, 0); "equal")]
+#[test_case(3, 5 => (1, u128::MAX - 1); "overflow")]
+#[test_case(0, u128::MAX => (1, 1); "max overflow")]
+fn test_u128_overflowing_sub(a: u128, b: u128) -> (usize, u128) {
+    let (branch, values) = run_with_range_check("u128_overflowing_sub", &[a, b]);
+    (branch, u128::try_from(&values[0]).unwrap())
+}
+
+#[test_case(17, 5 => (3, 2); "small")]
+#[test_case(4, 5 => (0, 4); "smaller than divisor")]
+#[test_case(u128::MAX, 1 => (u128::MAX, 0); "big quotient")]
+#[test_case(u128::MAX, u128::MAX - 1 => (1, 1); "big divisor")]
+fn test_u128_safe_divmod(a: u128, b: u128) -> (u128, u128) {
+    let (branch, values) = run_with_range_check("u128_safe_divmod", &[a, b]);
+    assert_eq!(branch, 0);
+    (u128::try_from(&values[0]).unwrap(), u128::try_from(&values[1]).unwrap())
+}
@@ -8,6 +8,10 @@ use crate::invocations::{
     CompiledInvocationBuilder, CostValidationInfo, InvocationError,
 };
 
+#[cfg(test)]
+#[path = "unsigned256_test.rs"]
+mod test;
+
 /// Builds instructions for Sierra u256 operations.
 pub fn build(
     libfunc: &Uint256Concrete,
@@ -15,7 +19,10 @@ pub fn build(
 ) -> Result<CompiledInvocation, InvocationError> {
     match libfunc {
         Uint256Concrete::IsZero(_) => build_u256_is_zero(builder),
+        Uint256Concrete::OverflowingAdd(_) => build_u256_overflowing_add(builder),
+        Uint256Concrete::OverflowingSub(_) => build_u256_overflowing_sub(builder),
         Uint256Concrete::Divmod(_) => build_u256_divmod(builder),
+        Uint256Concrete::WideMul(_) => build_u256_wide_mul(builder),
         Uint256Concrete::SquareRoot(_) => build_u256_sqrt(builder),
         Uint256Concrete::InvModN(_) => build_u256_inv_mod_n(builder),
     }
@@ -42,6 +49,140 @@ fn build_u256_is_zero(
     ))
 }
 
+/// Generates casm instructions for `u256_overflowing_add()`.
+fn build_u256_overflowing_add(
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    let failure_handle_statement_id = get_non_fallthrough_statement_id(&builder);
+    let [range_check, a, b] = builder.try_get_refs()?;
+    let [range_check] = range_check.try_unpack()?;
+    let [a0, a1] = a.try_unpack()?;
+    let [b0, b1] = b.try_unpack()?;
+
+    let mut casm_builder = CasmBuilder::default();
+    add_input_variables! {casm_builder,
+        buffer(1) range_check;
+        deref a0;
+        deref a1;
+        deref b0;
+        deref b1;
+    };
+    casm_build_extend! {casm_builder,
+            const one = 1;
+            const u128_limit = (BigInt::from(u128::MAX) + 1) as BigInt;
+            let orig_range_check = range_check;
+            // Limb0:
+            // `no_carry0` is 1 if `a0 + b0 < 2**128`, and 0 otherwise.
+            tempvar no_carry0;
+            tempvar a0_plus_b0 = a0 + b0;
+            hint TestLessThan {lhs: a0_plus_b0, rhs: u128_limit} into {dst: no_carry0};
+            assert no_carry0 = no_carry0 * no_carry0;
+            // `res0 = a0 + b0 - 2**128 * (1 - no_carry0)`, which is in the u128 range only if
+            // `no_carry0` is correct.
+            tempvar no_carry0_fix = no_carry0 * u128_limit;
+            tempvar wrapping_a0_plus_b0 = a0_plus_b0 - u128_limit;
+            tempvar res0 = wrapping_a0_plus_b0 + no_carry0_fix;
+            assert res0 = *(range_check++);
+            // Limb1:
+            // `a1 + b1 + 1 - no_carry0`, adding the carry of limb0.
+            tempvar a1_plus_b1 = a1 + b1;
+            tempvar a1_plus_b1_plus_one = a1_plus_b1 + one;
+            tempvar res1 = a1_plus_b1_plus_one - no_carry0;
+            tempvar no_overflow;
+            hint TestLessThan {lhs: res1, rhs: u128_limit} into {dst: no_overflow};
+            jump NoOverflow if no_overflow != 0;
+            // Overflow:
+            // Here we know that 2**128 <= res1 < 2 * 2**128.
+            tempvar wrapping_res1 = res1 - u128_limit;
+            assert wrapping_res1 = *(range_check++);
+            jump Target;
+        NoOverflow:
+            assert res1 = *(range_check++);
+    };
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [
+            ("Fallthrough", &[&[range_check], &[res0, res1]], None),
+            (
+                "Target",
+                &[&[range_check], &[res0, wrapping_res1]],
+                Some(failure_handle_statement_id),
+            ),
+        ],
+        CostValidationInfo {
+            range_check_info: Some((orig_range_check, range_check)),
+            extra_costs: None,
+        },
+    ))
+}
+
+/// Generates casm instructions for `u256_overflow_sub()`.
+fn build_u256_overflowing_sub(
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    let failure_handle_statement_id = get_non_fallthrough_statement_id(&builder);
+    let [range_check, a, b] = builder.try_get_refs()?;
+    let [range_check] = range_check.try_unpack()?;
+    let [a0, a1] = a.try_unpack()?;
+    let [b0, b1] = b.try_unpack()?;
+
+    let mut casm_builder = CasmBuilder::default();
+    add_input_variables! {casm_builder,
+        buffer(1) range_check;
+        deref a0;
+        deref a1;
+        deref b0;
+        deref b1;
+    };
+    casm_build_extend! {casm_builder,
+            const one = 1;
+            const u128_limit = (BigInt::from(u128::MAX) + 1) as BigInt;
+            let orig_range_check = range_check;
+            // Limb0:
+            // `no_borrow0` is 1 if `a0 >= b0`, and 0 otherwise.
+            tempvar no_borrow0;
+            tempvar a0_minus_b0 = a0 - b0;
+            hint TestLessThan {lhs: a0_minus_b0, rhs: u128_limit} into {dst: no_borrow0};
+            assert no_borrow0 = no_borrow0 * no_borrow0;
+            // `res0 = a0 - b0 + 2**128 * (1 - no_borrow0)`, which is in the u128 range only if
+            // `no_borrow0` is correct.
+            tempvar no_borrow0_fix = no_borrow0 * u128_limit;
+            tempvar wrapping_a0_minus_b0 = a0_minus_b0 + u128_limit;
+            tempvar res0 = wrapping_a0_minus_b0 - no_borrow0_fix;
+            assert res0 = *(range_check++);
+            // Limb1:
+            // `a1 - b1 - 1 + no_borrow0`, subtracting the borrow of limb0.
+            tempvar a1_minus_b1 = a1 - b1;
+            tempvar a1_minus_b1_plus_no_borrow0 = a1_minus_b1 + no_borrow0;
+            tempvar res1 = a1_minus_b1_plus_no_borrow0 - one;
+            tempvar no_overflow;
+            hint TestLessThan {lhs: res1, rhs: u128_limit} into {dst: no_overflow};
+            jump NoOverflow if no_overflow != 0;
+            // Overflow (negative):
+            // Here we know that -2**128 <= res1 < 0.
+            tempvar wrapping_res1 = res1 + u128_limit;
+            assert wrapping_res1 = *(range_check++);
+            jump Target;
+        NoOverflow:
+            assert res1 = *(range_check++);
+    };
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [
+            ("Fallthrough", &[&[range_check], &[res0, res1]], None),
+            (
+                "Target",
+                &[&[range_check], &[res0, wrapping_res1]],
+                Some(failure_handle_statement_id),
+            ),
+        ],
+        CostValidationInfo {
+            range_check_info: Some((orig_range_check, range_check)),
+            extra_costs: None,
+        },
+    ))
+}
+
 /// Generates casm instructions for `u256_safe_divmod()`.
 fn build_u256_divmod(
     builder: CompiledInvocationBuilder<'_>,
@@ -186,6 +327,100 @@ fn build_u256_divmod(
     ))
 }
 
+/// Generates casm instructions for `u256_wide_mul()`.
+fn build_u256_wide_mul(
+    builder: CompiledInvocationBuilder<'_>,
+) -> Result<CompiledInvocation, InvocationError> {
+    let [range_check, a, b] = builder.try_get_refs()?;
+    let [range_check] = range_check.try_unpack()?;
+    let [a0, a1] = a.try_unpack()?;
+    let [b0, b1] = b.try_unpack()?;
+
+    let mut casm_builder = CasmBuilder::default();
+    add_input_variables! {casm_builder,
+        buffer(6) range_check;
+        deref a0;
+        deref a1;
+        deref b0;
+        deref b1;
+    };
+    casm_build_extend! {casm_builder,
+        const u128_limit = (BigInt::from(u128::MAX) + 1) as BigInt;
+        const u128_bound_minus_3 = u128::MAX - 2;
+        let orig_range_check = range_check;
+        // The products of the limbs, split to 128-bit halves. The products are verified by the
+        // returned guarantees.
+        tempvar a0b0_high;
+        tempvar a0b0_low;
+        hint WideMul128 { lhs: a0, rhs: b0 } into { high: a0b0_high, low: a0b0_low };
+        tempvar a0b1_high;
+        tempvar a0b1_low;
+        hint WideMul128 { lhs: a0, rhs: b1 } into { high: a0b1_high, low: a0b1_low };
+        tempvar a1b0_high;
+        tempvar a1b0_low;
+        hint WideMul128 { lhs: a1, rhs: b0 } into { high: a1b0_high, low: a1b0_low };
+        tempvar a1b1_high;
+        tempvar a1b1_low;
+        hint WideMul128 { lhs: a1, rhs: b1 } into { high: a1b1_high, low: a1b1_low };
+    }
+    casm_build_extend! {casm_builder,
+        // Limb1:
+        // `sum1 = a0b0_high + a0b1_low + a1b0_low < 3 * 2**128`, split to `carry1 * 2**128 + res1`
+        // where `carry1` is in [0, 2].
+        tempvar carry1;
+        tempvar res1;
+        tempvar partial_sum1 = a0b0_high + a0b1_low;
+        tempvar sum1 = partial_sum1 + a1b0_low;
+        hint DivMod { lhs: sum1, rhs: u128_limit } into { quotient: carry1, remainder: res1 };
+        assert carry1 = *(range_check++);
+        tempvar carry1_fixed = carry1 + u128_bound_minus_3;
+        assert carry1_fixed = *(range_check++);
+        assert res1 = *(range_check++);
+        tempvar carry1_shifted = carry1 * u128_limit;
+        assert sum1 = carry1_shifted + res1;
+        // Limb2:
+        // `sum2 = a0b1_high + a1b0_high + a1b1_low + carry1 < 3 * 2**128`, split to
+        // `carry2 * 2**128 + res2` where `carry2` is in [0, 2].
+        tempvar carry2;
+        tempvar res2;
+        tempvar partial_sum2 = a0b1_high + a1b0_high;
+        tempvar partial_sum2_with_low = partial_sum2 + a1b1_low;
+        tempvar sum2 = partial_sum2_with_low + carry1;
+        hint DivMod { lhs: sum2, rhs: u128_limit } into { quotient: carry2, remainder: res2 };
+        assert carry2 = *(range_check++);
+        tempvar carry2_fixed = carry2 + u128_bound_minus_3;
+        assert carry2_fixed = *(range_check++);
+        assert res2 = *(range_check++);
+        tempvar carry2_shifted = carry2 * u128_limit;
+        assert sum2 = carry2_shifted + res2;
+        // Limb3:
+        // The product is less than 2**512, so `res3` is a u128 given correct products.
+        tempvar res3 = a1b1_high + carry2;
+        assert res3 = *(range_check++);
+        // Skip the cells filled by the hints.
+        ap += 6;
+    };
+    Ok(builder.build_from_casm_builder(
+        casm_builder,
+        [(
+            "Fallthrough",
+            &[
+                &[range_check],
+                &[a0b0_low, res1, res2, res3],
+                &[a0, b0, a0b0_high, a0b0_low],
+                &[a0, b1, a0b1_high, a0b1_low],
+                &[a1, b0, a1b0_high, a1b0_low],
+                &[a1, b1, a1b1_high, a1b1_low],
+            ],
+            None,
+        )],
+        CostValidationInfo {
+            range_check_info: Some((orig_range_check, range_check)),
+            extra_costs: None,
+        },
+    ))
+}
+
 /// Generates casm instructions for `u256_sqrt`.
 fn build_u256_sqrt(
     builder: CompiledInvocationBuilder<'_>,
@@ -0,0 +1,72 @@
+use cairo_lang_casm::executor::Vm;
+use cairo_lang_casm::hints::{CoreHint, CoreHintBase, Hint};
+use cairo_lang_casm::operand::prime;
+use num_bigint::BigInt;
+use test_case::test_case;
+
+use crate::invocations::test_utils::run_libfunc_with_hint_handler;
+
+/// Runs `u256_wide_mul` on the given u256 values (as `[low, high]`), with `div_mod` returning the
+/// quotient and the remainder for the `DivMod` hints of the carries.
+/// Returns the limbs of the result, and whether all the range checked values are u128s - i.e.
+/// whether the run would be accepted by the range check builtin.
+fn run_u256_wide_mul(
+    a: [u128; 2],
+    b: [u128; 2],
+    mut div_mod: impl FnMut(BigInt) -> (BigInt, BigInt),
+) -> (Vec<BigInt>, bool) {
+    let mut vm = Vm::new();
+    let range_check = vm.add_segment();
+    let [a, b] = [a, b].map(|value| value.map(BigInt::from));
+    let args: [&[BigInt]; 3] = [&[BigInt::from(range_check)], &a, &b];
+    let mut hint_handler = |vm: &mut Vm, hint: &Hint| match hint {
+        Hint::Core(CoreHintBase::Core(CoreHint::DivMod { lhs, quotient, remainder, .. })) => {
+            let (q, r) = div_mod(vm.get_res(lhs)?);
+            vm.insert_cell(quotient, q)?;
+            vm.insert_cell(remainder, r)
+        }
+        _ => vm.execute_hint(hint),
+    };
+    let (branch, mut values) =
+        run_libfunc_with_hint_handler(&mut vm, "u256_wide_mul", &args, &mut hint_handler).unwrap();
+    assert_eq!(branch, 0);
+    let range_check_end = usize::try_from(values.remove(0)).unwrap();
+    let u128_limit = BigInt::from(1) << 128;
+    let all_u128 = (range_check..range_check_end)
+        .all(|address| vm.memory.get(address).expect("Unset range check cell.") < &u128_limit);
+    values.truncate(4);
+    (values, all_u128)
+}
+
+#[test_case([2, 0], [3, 0] => [6, 0, 0, 0]; "small")]
+#[test_case([1, 0], [0, 1] => [0, 1, 0, 0]; "shifted")]
+#[test_case([0, u128::MAX], [0, u128::MAX] => [0, 0, 1, u128::MAX - 1]; "high limbs")]
+#[test_case([u128::MAX, u128::MAX], [u128::MAX, u128::MAX] => [1, 0, u128::MAX - 1, u128::MAX];
+    "max")]
+fn test_u256_wide_mul(a: [u128; 2], b: [u128; 2]) -> [u128; 4] {
+    let u128_limit = BigInt::from(1) << 128;
+    let (values, all_u128) =
+        run_u256_wide_mul(a, b, |lhs| (&lhs / &u128_limit, &lhs % &u128_limit));
+    assert!(all_u128);
+    values
+        .iter()
+        .map(|value| u128::try_from(value).unwrap())
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap()
+}
+
+/// Tests that a run with forged negative carries, which satisfies all the asserts of the libfunc,
+/// is rejected by the range checks.
+#[test]
+fn test_u256_wide_mul_forged_carries() {
+    let u128_limit = BigInt::from(1) << 128;
+    // For `1 * 2**128`, the first carry sum is 1, which is forged as `-t * 2**128 + 0` (as
+    // `t * 2**128 = prime - 1`). The second carry sum is then `-t`, forged as `-1 * 2**128 +
+    // (2**128 - t)`.
+    let t: BigInt = (prime() - 1) / &u128_limit;
+    let mut forged = [(prime() - &t, BigInt::from(0)), (prime() - 1, &u128_limit - &t)].into_iter();
+    let (values, all_u128) = run_u256_wide_mul([1, 0], [0, 1], |_| forged.next().unwrap());
+    assert_ne!(values, [0, 1, 0, 0].map(BigInt::from));
+    assert!(!all_u128);
+}
@@ -1,4 +1,4 @@
-use cairo_lang_casm::ap_change::ApplyApChange;
+use cairo_lang_casm::ap_change::{ApChange, ApplyApChange};
 use cairo_lang_casm::cell_expression::{CellExpression, CellOperator};
 use cairo_lang_casm::instructions::Instruction;
 use cairo_lang_casm::operand::{CellRef, Register};
@@ -56,9 +56,12 @@ fn get_store_instructions<DstCells: Iterator<Item = CellRef>>(
         return Err(InvocationError::NotSized(builder.invocation.clone()));
     }
     let mut ctx = casm!();
-    let mut ap_change = 0;
+    let mut ap_change = ApChange::Known(0);
     for (dst, cell_expr_orig) in zip_eq(dst_cells, &src_expr.cells) {
-        let cell_expr = cell_expr_orig.clone().apply_known_ap_change(ap_change as usize).unwrap();
+        let cell_expr = cell_expr_orig
+            .clone()
+            .apply_ap_change(ap_change)
+            .map_err(|_| InvocationError::InvalidReferenceExpressionForArgument)?;
         match cell_expr {
             CellExpression::Deref(operand) => add_instruction!(ctx, dst = operand),
             CellExpression::DoubleDeref(operand, offset) => {
@@ -75,7 +78,9 @@ fn get_store_instructions<DstCells: Iterator<Item = CellRef>>(
             },
         }
         if matches!(dst.register, Register::AP) {
-            ap_change += 1;
+            ap_change = ap_change
+                .checked_add(ApChange::Known(1))
+                .map_err(|_| InvocationError::InvalidReferenceExpressionForArgument)?;
             ctx.instructions.last_mut().unwrap().inc_ap = true;
         }
     }
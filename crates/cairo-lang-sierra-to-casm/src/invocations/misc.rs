@@ -103,14 +103,7 @@ pub fn build_identity(
 pub fn build_branch_align(
     builder: CompiledInvocationBuilder<'_>,
 ) -> Result<CompiledInvocation, InvocationError> {
-    let ap_fix = builder
-        .program_info
-        .metadata
-        .ap_change_info
-        .variable_values
-        .get(&builder.idx)
-        .copied()
-        .unwrap_or(0);
+    let ap_fix = builder.program_info.metadata.ap_change_info.variable_value(&builder.idx);
     Ok(builder.build(
         if ap_fix > 0 { casm! {ap += ap_fix;}.instructions } else { vec![] },
         vec![],
@@ -146,6 +139,94 @@ pub fn build_cell_eq(
     ))
 }
 
+/// Helper to add code that compares `lhs` and `rhs`, using the range check to validate the result.
+/// Jumps to a label named `Target` if `lhs < rhs` (or `lhs <= rhs` if `strict` is false), and
+/// otherwise falls through. The difference between the values must be in the range
+/// `(-2**128, 2**128)`, so that exactly one of `rhs - lhs - 1` and `lhs - rhs` (or `rhs - lhs` and
+/// `lhs - rhs - 1` if `strict` is false) is in the range check range.
+pub fn compare_with_rc(
+    casm_builder: &mut CasmBuilder,
+    range_check: Var,
+    lhs: Var,
+    rhs: Var,
+    strict: bool,
+) {
+    casm_build_extend! {casm_builder,
+        const one = 1;
+        tempvar is_false;
+    };
+    if strict {
+        casm_build_extend! {casm_builder,
+            hint TestLessThanOrEqual {lhs: rhs, rhs: lhs} into {dst: is_false};
+            jump False if is_false != 0;
+            // Verify `lhs < rhs` by constraining `0 <= rhs - lhs - 1`.
+            tempvar rhs_minus_lhs = rhs - lhs;
+            tempvar rhs_minus_lhs_minus_1 = rhs_minus_lhs - one;
+            assert rhs_minus_lhs_minus_1 = *(range_check++);
+            jump Target;
+        False:
+            // Verify `lhs >= rhs` by constraining `0 <= lhs - rhs`.
+            tempvar lhs_minus_rhs = lhs - rhs;
+            assert lhs_minus_rhs = *(range_check++);
+        };
+    } else {
+        casm_build_extend! {casm_builder,
+            hint TestLessThan {lhs: rhs, rhs: lhs} into {dst: is_false};
+            jump False if is_false != 0;
+            // Verify `lhs <= rhs` by constraining `0 <= rhs - lhs`.
+            tempvar rhs_minus_lhs = rhs - lhs;
+            assert rhs_minus_lhs = *(range_check++);
+            jump Target;
+        False:
+            // Verify `lhs > rhs` by constraining `0 <= lhs - rhs - 1`.
+            tempvar lhs_minus_rhs = lhs - rhs;
+            tempvar lhs_minus_rhs_minus_1 = lhs_minus_rhs - one;
+            assert lhs_minus_rhs_minus_1 = *(range_check++);
+        };
+    }
+}
+
+/// Helper to add code that computes `lhs - rhs`, using the range check to validate its sign.
+/// Jumps to a label named `Target` if `lhs < rhs`, and otherwise falls through. The difference
+/// must be in the range `(-limit, limit)`, where `limit <= 2**128`.
+/// Returns the difference, and its wrapping value `lhs - rhs + limit`, which is only valid on the
+/// jump to `Target`.
+pub fn sub_with_rc(
+    casm_builder: &mut CasmBuilder,
+    range_check: Var,
+    lhs: Var,
+    rhs: Var,
+    limit: BigInt,
+) -> (Var, Var) {
+    let u128_limit = BigInt::from(u128::MAX) + BigInt::from(1);
+    let is_u128_limit = limit == u128_limit;
+    casm_build_extend! {casm_builder,
+        tempvar lhs_ge_rhs;
+        tempvar lhs_minus_rhs = lhs - rhs;
+        const u128_limit = u128_limit;
+        const limit = limit;
+        hint TestLessThan {lhs: lhs_minus_rhs, rhs: limit} into {dst: lhs_ge_rhs};
+        jump NoOverflow if lhs_ge_rhs != 0;
+        // Overflow (negative):
+        // Here we know that 0 - (limit - 1) <= lhs - rhs < 0.
+        tempvar fixed_lhs_minus_rhs = lhs_minus_rhs + u128_limit;
+        assert fixed_lhs_minus_rhs = *(range_check++);
+    };
+    // The fixed difference is the wrapping one for a limit of `2**128`.
+    let wrapping_lhs_minus_rhs = if is_u128_limit {
+        fixed_lhs_minus_rhs
+    } else {
+        casm_build_extend!(casm_builder, let wrapping_lhs_minus_rhs = lhs_minus_rhs + limit;);
+        wrapping_lhs_minus_rhs
+    };
+    casm_build_extend! {casm_builder,
+        jump Target;
+    NoOverflow:
+        assert lhs_minus_rhs = *(range_check++);
+    };
+    (lhs_minus_rhs, wrapping_lhs_minus_rhs)
+}
+
 /// Helper to add code that validates that variable `value` is smaller than `limit`, with `K`
 /// constant for checking this bound. `auxiliary_vars` are the variables already allocated used for
 /// execution of the algorithm, requires different sizes for different `K`s, for 1 requires 4, for 2
@@ -21,6 +21,7 @@ use itertools::{chain, zip_eq, Itertools};
 use thiserror::Error;
 use {cairo_lang_casm, cairo_lang_sierra};
 
+use crate::compiler::ConstsInfo;
 use crate::environment::frame_state::{FrameState, FrameStateError};
 use crate::environment::Environment;
 use crate::metadata::Metadata;
@@ -36,9 +37,11 @@ mod boolean;
 mod boxing;
 mod bytes31;
 mod casts;
+mod const_type;
+pub mod custom;
 mod debug;
 mod ec;
-mod enm;
+pub(crate) mod enm;
 mod felt252;
 mod felt252_dict;
 mod function_call;
@@ -52,6 +55,8 @@ mod poseidon;
 mod starknet;
 mod structure;
 
+#[cfg(test)]
+mod test;
 #[cfg(test)]
 mod test_utils;
 
@@ -252,9 +257,9 @@ pub fn check_references_on_stack(refs: &[ReferenceValue]) -> Result<(), Invocati
 }
 
 /// The cells per returned Sierra variables, in casm-builder vars.
-type VarCells = [Var];
+pub type VarCells = [Var];
 /// The configuration for all Sierra variables returned from a libfunc.
-type AllVars<'a> = [&'a VarCells];
+pub type AllVars<'a> = [&'a VarCells];
 
 impl<'a> InvocationApChangeInfoProvider for CompiledInvocationBuilder<'a> {
     fn type_size(&self, ty: &ConcreteTypeId) -> usize {
@@ -278,13 +283,7 @@ impl<'a> InvocationCostInfoProvider for CompiledInvocationBuilder<'a> {
     }
 
     fn ap_change_var_value(&self) -> usize {
-        self.program_info
-            .metadata
-            .ap_change_info
-            .variable_values
-            .get(&self.idx)
-            .copied()
-            .unwrap_or_default()
+        self.program_info.metadata.ap_change_info.variable_value(&self.idx)
     }
 
     fn token_usages(&self, token_type: CostTokenType) -> usize {
@@ -294,7 +293,7 @@ impl<'a> InvocationCostInfoProvider for CompiledInvocationBuilder<'a> {
 
 /// Information required for validating libfunc cost.
 #[derive(Default)]
-struct CostValidationInfo<const BRANCH_COUNT: usize> {
+pub struct CostValidationInfo<const BRANCH_COUNT: usize> {
     /// Range check variables at start and end of the libfunc.
     /// Assumes only directly used as buffer.
     pub range_check_info: Option<(Var, Var)>,
@@ -379,17 +378,10 @@ impl CompiledInvocationBuilder<'_> {
                         .program_info
                         .metadata
                         .ap_change_info
-                        .function_ap_change
-                        .get(&id)
+                        .known_function_ap_change(&id)
                         .map_or(ApChange::Unknown, |x| ApChange::Known(x + 2)),
                     cairo_lang_sierra_ap_change::ApChange::FromMetadata => ApChange::Known(
-                        *self
-                            .program_info
-                            .metadata
-                            .ap_change_info
-                            .variable_values
-                            .get(&self.idx)
-                            .unwrap_or(&0),
+                        self.program_info.metadata.ap_change_info.variable_value(&self.idx),
                     ),
                     cairo_lang_sierra_ap_change::ApChange::Unknown => ApChange::Unknown,
                 };
@@ -415,7 +407,7 @@ impl CompiledInvocationBuilder<'_> {
 
     /// Builds a `CompiledInvocation` from a casm builder and branch extractions.
     /// Per branch requires `(name, result_variables, target_statement_id)`.
-    fn build_from_casm_builder<const BRANCH_COUNT: usize>(
+    pub fn build_from_casm_builder<const BRANCH_COUNT: usize>(
         self,
         casm_builder: CasmBuilder,
         branch_extractions: [(&str, &AllVars<'_>, Option<StatementIdx>); BRANCH_COUNT],
@@ -562,6 +554,9 @@ impl CompiledInvocationBuilder<'_> {
 pub struct ProgramInfo<'a> {
     pub metadata: &'a Metadata,
     pub type_sizes: &'a TypeSizeMap,
+    pub consts_info: &'a ConstsInfo,
+    /// The extensions implementing the libfuncs not defined by the core library.
+    pub libfunc_extensions: &'a custom::CasmLibfuncExtensions,
 }
 
 /// Given a Sierra invocation statement and concrete libfunc, creates a compiled casm representation
@@ -638,7 +633,9 @@ pub fn compile_invocation(
         CoreConcreteLibfunc::Felt252DictEntry(libfunc) => {
             felt252_dict::build_entry(libfunc, builder)
         }
+        CoreConcreteLibfunc::Const(libfunc) => const_type::build(libfunc, builder),
         CoreConcreteLibfunc::Bytes31(libfunc) => bytes31::build(libfunc, builder),
+        CoreConcreteLibfunc::Custom(libfunc) => custom::build(libfunc, builder),
     }
 }
 
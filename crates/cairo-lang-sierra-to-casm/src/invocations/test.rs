@@ -0,0 +1,14 @@
+use super::test_utils::test_compile_libfunc;
+
+cairo_lang_test_utils::test_file_test!(
+    compile_libfunc,
+    "src/invocations/test_data",
+    {
+        felt252: "felt252",
+        u128: "u128",
+        u256: "u256",
+        uint: "uint",
+        errors: "errors",
+    },
+    test_compile_libfunc
+);
@@ -1,5 +1,9 @@
 use cairo_lang_casm::ap_change::ApChange;
+use cairo_lang_casm::cell_expression::{CellExpression, CellOperator};
+use cairo_lang_casm::executor::{ExecutionError, Vm};
+use cairo_lang_casm::hints::Hint;
 use cairo_lang_casm::instructions::Instruction;
+use cairo_lang_casm::operand::{CellRef, DerefOrImmediate, Register};
 use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType};
 use cairo_lang_sierra::extensions::lib_func::{
     SignatureSpecializationContext, SpecializationContext,
@@ -7,21 +11,26 @@ use cairo_lang_sierra::extensions::lib_func::{
 use cairo_lang_sierra::extensions::type_specialization_context::TypeSpecializationContext;
 use cairo_lang_sierra::extensions::types::TypeInfo;
 use cairo_lang_sierra::extensions::{
-    ConcreteLibfunc, ConcreteType, GenericLibfuncEx, GenericTypeEx,
+    ConcreteLibfunc, ConcreteType, ExtensionError, GenericLibfuncEx, GenericTypeEx,
 };
 use cairo_lang_sierra::ids::{ConcreteTypeId, VarId};
 use cairo_lang_sierra::program::{BranchInfo, BranchTarget, Invocation, StatementIdx};
 use cairo_lang_sierra_ap_change::ap_change_info::ApChangeInfo;
 use cairo_lang_sierra_gas::gas_info::GasInfo;
 use cairo_lang_sierra_type_size::TypeSizeMap;
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use itertools::{zip_eq, Itertools};
+use num_bigint::BigInt;
+use thiserror::Error;
 
-use super::{compile_invocation, CompiledInvocation, ProgramInfo};
+use super::{compile_invocation, CompiledInvocation, InvocationError, ProgramInfo};
+use crate::compiler::ConstsInfo;
 use crate::environment::gas_wallet::GasWallet;
 use crate::environment::Environment;
 use crate::metadata::Metadata;
 use crate::references::{IntroductionPoint, ReferenceExpression, ReferenceValue};
-use crate::relocations::RelocationEntry;
+use crate::relocations::{Relocation, RelocationEntry};
 
 /// Creates a Felt252BinaryOperator from a token operator.
 #[macro_export]
@@ -231,13 +240,39 @@ impl std::fmt::Debug for ReducedCompiledInvocation {
 ///
 /// Currently, only works if all the libfunc's types (both inputs and output) are of size 1.
 pub fn compile_libfunc(libfunc: &str, refs: Vec<ReferenceExpression>) -> ReducedCompiledInvocation {
+    try_compile_libfunc(libfunc, refs).expect("Failed to compile invocation.")
+}
+
+/// Error compiling a libfunc by [try_compile_libfunc].
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum CompileLibfuncError {
+    #[error("Failed to parse the libfunc id `{0}`.")]
+    LibfuncIdParse(String),
+    #[error("{0}")]
+    Specialization(#[from] ExtensionError),
+    #[error("{0}")]
+    Invocation(#[from] InvocationError),
+}
+
+/// Compiles a libfunc into a [ReducedCompiledInvocation], the same as [compile_libfunc], but
+/// returns an error instead of panicking if the libfunc fails to specialize or compile.
+pub fn try_compile_libfunc(
+    libfunc: &str,
+    refs: Vec<ReferenceExpression>,
+) -> Result<ReducedCompiledInvocation, CompileLibfuncError> {
     let long_id = cairo_lang_sierra::ConcreteLibfuncLongIdParser::new()
-        .parse(libfunc.to_string().as_str())
-        .unwrap();
+        .parse(libfunc)
+        .map_err(|_| CompileLibfuncError::LibfuncIdParse(libfunc.to_string()))?;
     let context = MockSpecializationContext {};
     let libfunc =
-        CoreLibfunc::specialize_by_id(&context, &long_id.generic_id, &long_id.generic_args)
-            .unwrap();
+        CoreLibfunc::specialize_by_id(&context, &long_id.generic_id, &long_id.generic_args)?;
+    if refs.len() != libfunc.param_signatures().len() {
+        return Err(InvocationError::WrongNumberOfArguments {
+            expected: libfunc.param_signatures().len(),
+            actual: refs.len(),
+        }
+        .into());
+    }
 
     let mut type_sizes: TypeSizeMap = Default::default();
     for param in libfunc.param_signatures() {
@@ -260,6 +295,8 @@ pub fn compile_libfunc(libfunc: &str, refs: Vec<ReferenceExpression>) -> Reduced
             },
         },
         type_sizes: &type_sizes,
+        consts_info: &ConstsInfo::default(),
+        libfunc_extensions: &Default::default(),
     };
 
     let args: Vec<ReferenceValue> = zip_eq(refs, libfunc.param_signatures())
@@ -276,31 +313,253 @@ pub fn compile_libfunc(libfunc: &str, refs: Vec<ReferenceExpression>) -> Reduced
         .collect();
 
     let environment = Environment::new(GasWallet::Disabled);
-    ReducedCompiledInvocation::new(
-        compile_invocation(
-            program_info,
-            &Invocation {
-                libfunc_id: "".into(),
-                args: (0..args.len() as u64).map(VarId::new).collect(),
-                branches: libfunc
-                    .branch_signatures()
+    Ok(ReducedCompiledInvocation::new(compile_invocation(
+        program_info,
+        &Invocation {
+            libfunc_id: "".into(),
+            args: (0..args.len() as u64).map(VarId::new).collect(),
+            branches: libfunc
+                .branch_signatures()
+                .iter()
+                .enumerate()
+                .map(|(i, branch)| BranchInfo {
+                    target: if libfunc.fallthrough() == Some(i) {
+                        BranchTarget::Fallthrough
+                    } else {
+                        BranchTarget::Statement(StatementIdx(i))
+                    },
+                    results: (0..branch.vars.len() as u64).map(VarId::new).collect(),
+                })
+                .collect(),
+        },
+        &libfunc,
+        StatementIdx(0),
+        &args,
+        environment,
+    )?))
+}
+
+/// Runs a libfunc compiled by [compile_libfunc] on the given argument values, using `vm`.
+///
+/// The arguments are pushed to the stack of `vm` before the run, and builtin pointers should be
+/// the starts of segments allocated by [Vm::add_segment]. The constraints of builtins are not
+/// validated. Returns the index of the branch taken and the values of its results.
+pub fn run_libfunc(
+    vm: &mut Vm,
+    libfunc: &str,
+    args: &[BigInt],
+) -> Result<(usize, Vec<BigInt>), ExecutionError> {
+    let args = args.iter().map(std::slice::from_ref).collect_vec();
+    run_libfunc_with_hint_handler(vm, libfunc, &args, &mut Vm::execute_hint)
+}
+
+/// Same as [run_libfunc], but with the cells of each argument given separately - so arguments may
+/// be of types of any size, and with the hints run by `hint_handler` - e.g. for running the
+/// libfunc with hints that return forged values.
+pub fn run_libfunc_with_hint_handler(
+    vm: &mut Vm,
+    libfunc: &str,
+    args: &[&[BigInt]],
+    hint_handler: &mut impl FnMut(&mut Vm, &Hint) -> Result<(), ExecutionError>,
+) -> Result<(usize, Vec<BigInt>), ExecutionError> {
+    let n_cells: usize = args.iter().map(|arg| arg.len()).sum();
+    let mut offset = -(n_cells as i16);
+    let mut refs = vec![];
+    for arg in args {
+        let mut cells = vec![];
+        for value in arg.iter() {
+            vm.push(value.clone())?;
+            cells.push(CellExpression::Deref(CellRef { register: Register::AP, offset }));
+            offset += 1;
+        }
+        refs.push(ReferenceExpression { cells });
+    }
+    let ReducedCompiledInvocation { mut instructions, relocations, results } =
+        compile_libfunc(libfunc, refs);
+
+    let mut instruction_offsets = vec![];
+    let mut code_size = 0;
+    for instruction in &instructions {
+        instruction_offsets.push(code_size);
+        code_size += instruction.body.op_size();
+    }
+    // The fallthrough branch ends at the end of the code, and the jumps to the statement of
+    // branch `i` are relocated to `code_size + 1 + i`, which is out of the code as well.
+    let statement_offsets = (0..results.len()).map(|i| code_size + 1 + i).collect_vec();
+    for RelocationEntry { instruction_idx, relocation } in &relocations {
+        relocation.apply(
+            instruction_offsets[*instruction_idx],
+            &statement_offsets,
+            &ConstsInfo::default(),
+            &mut instructions[*instruction_idx],
+        );
+    }
+    vm.run(&instructions, hint_handler)?;
+
+    let branch = if vm.pc == code_size {
+        // The fallthrough branch is the only branch which is not jumped to.
+        (0..results.len())
+            .find(|i| {
+                !relocations.iter().any(|entry| {
+                    entry.relocation == Relocation::RelativeStatementId(StatementIdx(*i))
+                })
+            })
+            .expect("No fallthrough branch.")
+    } else {
+        vm.pc - code_size - 1
+    };
+    let values = results[branch]
+        .refs
+        .iter()
+        .flat_map(|expr| &expr.cells)
+        .map(|cell| vm.get_cell_expression(cell))
+        .collect::<Result<_, _>>()?;
+    Ok((branch, values))
+}
+
+/// Parses a reference expression from its textual representation - its cells separated by commas,
+/// each cell in one of the forms `[ap + 1]`, `[[fp - 2] + 3]`, `[ap + 1] + [fp]`, `[ap] * 5` or
+/// `7`.
+pub fn parse_ref_expr(text: &str) -> Option<ReferenceExpression> {
+    let text: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if text.is_empty() {
+        return Some(ReferenceExpression { cells: vec![] });
+    }
+    Some(ReferenceExpression {
+        cells: text.split(',').map(parse_cell_expr).collect::<Option<_>>()?,
+    })
+}
+
+/// Parses a cell expression, given without whitespaces.
+fn parse_cell_expr(text: &str) -> Option<CellExpression> {
+    if let Some(inner) = text.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        if inner.starts_with('[') {
+            let (cell, offset) = split_cell_ref(inner)?;
+            let offset = match offset {
+                "" => 0,
+                _ => offset.strip_prefix('+').unwrap_or(offset).parse().ok()?,
+            };
+            return Some(CellExpression::DoubleDeref(cell, offset));
+        }
+    }
+    if !text.starts_with('[') {
+        return Some(CellExpression::Immediate(text.parse().ok()?));
+    }
+    let (a, rest) = split_cell_ref(text)?;
+    if rest.is_empty() {
+        return Some(CellExpression::Deref(a));
+    }
+    let op = match rest.chars().next()? {
+        '+' => CellOperator::Add,
+        '-' => CellOperator::Sub,
+        '*' => CellOperator::Mul,
+        '/' => CellOperator::Div,
+        _ => return None,
+    };
+    let b = &rest[1..];
+    let b = if b.starts_with('[') {
+        let (b, rest) = split_cell_ref(b)?;
+        if !rest.is_empty() {
+            return None;
+        }
+        DerefOrImmediate::Deref(b)
+    } else {
+        DerefOrImmediate::from(b.parse::<BigInt>().ok()?)
+    };
+    Some(CellExpression::BinOp { op, a, b })
+}
+
+/// Splits a text starting with a cell reference such as `[ap + 1]` to the cell reference and the
+/// rest of the text.
+fn split_cell_ref(text: &str) -> Option<(CellRef, &str)> {
+    let end = text.find(']')?;
+    let inner = text[..end].strip_prefix('[')?;
+    let (register, offset) = if let Some(offset) = inner.strip_prefix("ap") {
+        (Register::AP, offset)
+    } else {
+        (Register::FP, inner.strip_prefix("fp")?)
+    };
+    let offset = if offset.is_empty() {
+        0
+    } else if let Some(offset) = offset.strip_prefix('-') {
+        -offset.parse::<i16>().ok()?
+    } else {
+        offset.strip_prefix('+')?.parse().ok()?
+    };
+    Some((CellRef { register, offset }, &text[end + 1..]))
+}
+
+/// Formats a reference expression in the form parsed by [parse_ref_expr].
+pub fn format_ref_expr(expr: &ReferenceExpression) -> String {
+    expr.cells.iter().map(format_cell_expr).join(", ")
+}
+
+/// Formats a cell expression in the form parsed by [parse_cell_expr].
+fn format_cell_expr(cell: &CellExpression) -> String {
+    match cell {
+        CellExpression::Deref(cell) => format!("{cell}"),
+        CellExpression::DoubleDeref(cell, offset) => format!("[{cell} + {offset}]"),
+        CellExpression::Immediate(value) => format!("{value}"),
+        CellExpression::BinOp { op, a, b } => {
+            let op = match op {
+                CellOperator::Add => '+',
+                CellOperator::Sub => '-',
+                CellOperator::Mul => '*',
+                CellOperator::Div => '/',
+            };
+            format!("{a} {op} {b}")
+        }
+    }
+}
+
+/// Formats the branch changes of a compiled invocation, each branch as a line with its index and
+/// AP change, followed by an indented line per result reference.
+fn format_branch_changes(results: &[ReducedBranchChanges]) -> String {
+    results
+        .iter()
+        .enumerate()
+        .map(|(i, branch)| {
+            let mut lines = vec![format!("branch {i}: ap change {:?}", branch.ap_change)];
+            lines.extend(branch.refs.iter().map(|r| format!("    {}", format_ref_expr(r))));
+            lines.join("\n")
+        })
+        .join("\n")
+}
+
+/// Compiles a libfunc, and returns the resulting CASM, relocations and branch changes, or the
+/// compilation error.
+///
+/// Inputs:
+/// - `libfunc` - the concrete libfunc id, e.g. `u128_overflowing_add`.
+/// - `refs` - the references of the arguments, one per line, in the form parsed by
+///   [parse_ref_expr].
+///
+/// Outputs on success - `casm`, `relocations` and `branches`, and on failure - `error`.
+pub fn test_compile_libfunc(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let refs = inputs["refs"]
+        .lines()
+        .map(|line| {
+            parse_ref_expr(line).unwrap_or_else(|| panic!("Failed to parse reference `{line}`."))
+        })
+        .collect();
+    match try_compile_libfunc(&inputs["libfunc"], refs) {
+        Ok(compiled) => TestRunnerResult::success(OrderedHashMap::from([
+            ("casm".into(), compiled.instructions.iter().map(|inst| format!("{inst};")).join("\n")),
+            (
+                "relocations".into(),
+                compiled
+                    .relocations
                     .iter()
-                    .enumerate()
-                    .map(|(i, branch)| BranchInfo {
-                        target: if libfunc.fallthrough() == Some(i) {
-                            BranchTarget::Fallthrough
-                        } else {
-                            BranchTarget::Statement(StatementIdx(i))
-                        },
-                        results: (0..branch.vars.len() as u64).map(VarId::new).collect(),
-                    })
-                    .collect(),
-            },
-            &libfunc,
-            StatementIdx(0),
-            &args,
-            environment,
-        )
-        .expect("Failed to compile invocation."),
-    )
+                    .map(|entry| format!("{}: {:?}", entry.instruction_idx, entry.relocation))
+                    .join("\n"),
+            ),
+            ("branches".into(), format_branch_changes(&compiled.results)),
+        ])),
+        Err(err) => {
+            TestRunnerResult::success(OrderedHashMap::from([("error".into(), err.to_string())]))
+        }
+    }
 }
@@ -7,6 +7,7 @@ pub mod compiler;
 pub mod environment;
 pub mod invocations;
 pub mod metadata;
+pub mod peephole;
 pub mod references;
 pub mod relocations;
 #[cfg(any(feature = "testing", test))]
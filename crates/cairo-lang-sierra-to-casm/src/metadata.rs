@@ -1,9 +1,9 @@
+use cairo_lang_sierra::extensions::custom::LibfuncExtensions;
 use cairo_lang_sierra::extensions::gas::CostTokenType;
 use cairo_lang_sierra::ids::FunctionId;
 use cairo_lang_sierra::program::Program;
 use cairo_lang_sierra_ap_change::ap_change_info::ApChangeInfo;
-use cairo_lang_sierra_ap_change::compute::calc_ap_changes as linear_calc_ap_changes;
-use cairo_lang_sierra_ap_change::{calc_ap_changes, ApChangeError};
+use cairo_lang_sierra_ap_change::{analyze_ap_changes, ApChangeError, ApChangeSolver};
 use cairo_lang_sierra_gas::gas_info::GasInfo;
 use cairo_lang_sierra_gas::{
     calc_gas_postcost_info, calc_gas_precost_info, compute_postcost_info, compute_precost_info,
@@ -41,12 +41,19 @@ pub struct MetadataComputationConfig {
     /// If true, uses a linear-time algorithm for calculating ap changes, instead of solving
     /// equations.
     pub linear_ap_change_solver: bool,
+    /// The extensions implementing the libfuncs not defined by the core library.
+    pub libfunc_extensions: LibfuncExtensions,
 }
 
 /// Calculates the metadata for a Sierra program, with ap change info only.
 pub fn calc_metadata_ap_change_only(program: &Program) -> Result<Metadata, MetadataError> {
     Ok(Metadata {
-        ap_change_info: calc_ap_changes(program, |_, _| 0)?,
+        ap_change_info: analyze_ap_changes(
+            program,
+            &LibfuncExtensions::default(),
+            ApChangeSolver::EquationSolver,
+            |_, _| 0,
+        )?,
         gas_info: GasInfo {
             variable_values: Default::default(),
             function_costs: Default::default(),
@@ -71,16 +78,21 @@ pub fn calc_metadata(
             )
         })
         .collect();
-    let pre_gas_info = calc_gas_precost_info(program, pre_function_set_costs)?;
-    let pre_gas_info2 = compute_precost_info(program)?;
+    let libfunc_extensions = &config.libfunc_extensions;
+    let pre_gas_info = calc_gas_precost_info(program, libfunc_extensions, pre_function_set_costs)?;
+    let pre_gas_info2 = compute_precost_info(program, libfunc_extensions)?;
     pre_gas_info.assert_eq_variables(&pre_gas_info2);
     pre_gas_info.assert_eq_functions(&pre_gas_info2);
 
+    let ap_change_solver = if config.linear_ap_change_solver {
+        ApChangeSolver::Linear
+    } else {
+        ApChangeSolver::EquationSolver
+    };
     let ap_change_info =
-        if config.linear_ap_change_solver { linear_calc_ap_changes } else { calc_ap_changes }(
-            program,
-            |idx, token_type| pre_gas_info.variable_values[(idx, token_type)] as usize,
-        )?;
+        analyze_ap_changes(program, libfunc_extensions, ap_change_solver, |idx, token_type| {
+            pre_gas_info.variable_values[(idx, token_type)] as usize
+        })?;
 
     let post_function_set_costs = config
         .function_set_costs
@@ -95,10 +107,13 @@ pub fn calc_metadata(
             )
         })
         .collect();
-    let mut post_gas_info =
-        calc_gas_postcost_info(program, post_function_set_costs, &pre_gas_info, |idx| {
-            ap_change_info.variable_values.get(&idx).copied().unwrap_or_default()
-        })?;
+    let mut post_gas_info = calc_gas_postcost_info(
+        program,
+        libfunc_extensions,
+        post_function_set_costs,
+        &pre_gas_info,
+        |idx| ap_change_info.variable_value(&idx),
+    )?;
 
     if config.linear_gas_solver {
         let enforced_function_costs: OrderedHashMap<FunctionId, i32> = config
@@ -108,7 +123,8 @@ pub fn calc_metadata(
             .collect();
         let post_gas_info2 = compute_postcost_info(
             program,
-            &|idx| ap_change_info.variable_values.get(idx).copied().unwrap_or_default(),
+            libfunc_extensions,
+            &|idx| ap_change_info.variable_value(idx),
             &pre_gas_info2,
             &enforced_function_costs,
         )?;
@@ -0,0 +1,208 @@
+use std::collections::{HashMap, HashSet};
+
+use cairo_lang_casm::instructions::{
+    CallInstruction, Instruction, InstructionBody, JnzInstruction, JumpInstruction,
+};
+use cairo_lang_casm::operand::DerefOrImmediate;
+use num_bigint::BigInt;
+
+use crate::relocations::{Relocation, RelocationEntry};
+
+#[cfg(test)]
+#[path = "peephole_test.rs"]
+mod test;
+
+/// Removes trivial instruction patterns from a compiled program, before its relocations are
+/// applied:
+/// * Unconditional relative jumps to the next instruction.
+/// * Unreachable instructions following an unconditional jump or a `ret`.
+///
+/// The removed instructions do not change `ap`, so the reference and ap tracking of the program
+/// remain valid. Copies such as `[ap + 0] = [ap + -1], ap++` are kept for the same reason.
+/// `relocations` and `statement_offsets` are updated to match the remaining instructions, and the
+/// relative jumps within invocations are retargeted.
+pub fn optimize_instructions(
+    instructions: &mut Vec<Instruction>,
+    relocations: &mut Vec<RelocationEntry>,
+    statement_offsets: &mut [usize],
+) {
+    let mut offsets = Vec::with_capacity(instructions.len() + 1);
+    let mut program_offset = 0;
+    for instruction in instructions.iter() {
+        offsets.push(program_offset);
+        program_offset += instruction.body.op_size();
+    }
+    offsets.push(program_offset);
+
+    let relocated: HashMap<usize, &Relocation> =
+        relocations.iter().map(|entry| (entry.instruction_idx, &entry.relocation)).collect();
+    let statement_starts: HashSet<usize> = statement_offsets.iter().copied().collect();
+
+    // The targets of the relative jumps within invocations, which are resolved already.
+    let mut internal_targets = HashSet::new();
+    for (idx, instruction) in instructions.iter().enumerate() {
+        if relocated.contains_key(&idx) {
+            continue;
+        }
+        match jump_kind(instruction) {
+            JumpKind::Relative(value) => {
+                internal_targets.insert((offsets[idx] as i128 + value) as usize);
+            }
+            // The targets of absolute jumps would be moved by the removals.
+            JumpKind::Absolute => return,
+            JumpKind::Dynamic | JumpKind::None => {}
+        }
+    }
+
+    let mut removed = vec![false; instructions.len()];
+    let mut reachable = true;
+    // Whether the current instructions may be the targets of a dynamic jump, such as the jump
+    // table of an enum match, and are therefore kept as is.
+    let mut pinned = false;
+    for (idx, instruction) in instructions.iter().enumerate() {
+        if statement_starts.contains(&offsets[idx]) {
+            reachable = true;
+            pinned = false;
+        } else if internal_targets.contains(&offsets[idx]) {
+            reachable = true;
+        }
+        let removable = !pinned && instruction.hints.is_empty();
+        if removable
+            && (!reachable
+                || is_jump_to_next(
+                    instruction,
+                    offsets[idx],
+                    relocated.get(&idx).copied(),
+                    statement_offsets,
+                ))
+        {
+            removed[idx] = true;
+            continue;
+        }
+        match &instruction.body {
+            InstructionBody::Jump(_) | InstructionBody::Ret(_) => reachable = false,
+            _ => {}
+        }
+        if jump_kind(instruction) == JumpKind::Dynamic {
+            pinned = true;
+        }
+    }
+    if !removed.contains(&true) {
+        return;
+    }
+
+    // Maps the original offsets and instruction indices to the new ones.
+    let mut new_offsets = Vec::with_capacity(offsets.len());
+    let mut new_indices = Vec::with_capacity(instructions.len());
+    let mut removed_size = 0;
+    let mut removed_count = 0;
+    for (idx, instruction) in instructions.iter().enumerate() {
+        new_offsets.push(offsets[idx] - removed_size);
+        new_indices.push(idx - removed_count);
+        if removed[idx] {
+            removed_size += instruction.body.op_size();
+            removed_count += 1;
+        }
+    }
+    new_offsets.push(program_offset - removed_size);
+    let new_offset = |offset: usize| new_offsets[offsets.binary_search(&offset).unwrap()];
+
+    for (idx, instruction) in instructions.iter_mut().enumerate() {
+        if removed[idx] || relocated.contains_key(&idx) {
+            continue;
+        }
+        if let Some(value) = relative_jump_value(instruction) {
+            let target = offsets[idx] as i128 + i128::try_from(&*value).unwrap();
+            *value = (new_offset(target as usize) as i128 - new_offsets[idx] as i128).into();
+        }
+    }
+    relocations.retain(|entry| !removed[entry.instruction_idx]);
+    for entry in relocations.iter_mut() {
+        entry.instruction_idx = new_indices[entry.instruction_idx];
+    }
+    for statement_offset in statement_offsets.iter_mut() {
+        *statement_offset = new_offset(*statement_offset);
+    }
+    let mut removed_iter = removed.into_iter();
+    instructions.retain(|_| !removed_iter.next().unwrap());
+}
+
+/// The kind of the control flow change of an instruction.
+#[derive(Debug, Eq, PartialEq)]
+enum JumpKind {
+    /// Not a jump or a call.
+    None,
+    /// A relative jump or call by the given immediate value.
+    Relative(i128),
+    /// An absolute jump or call.
+    Absolute,
+    /// A relative jump or call by a value from memory.
+    Dynamic,
+}
+
+/// Returns the kind of the control flow change of an instruction.
+fn jump_kind(instruction: &Instruction) -> JumpKind {
+    let (target, relative) = match &instruction.body {
+        InstructionBody::Jump(JumpInstruction { target, relative })
+        | InstructionBody::Call(CallInstruction { target, relative }) => (target, *relative),
+        InstructionBody::Jnz(JnzInstruction { jump_offset, .. }) => (jump_offset, true),
+        _ => return JumpKind::None,
+    };
+    match target {
+        _ if !relative => JumpKind::Absolute,
+        DerefOrImmediate::Immediate(value) => {
+            JumpKind::Relative(i128::try_from(&value.value).unwrap())
+        }
+        DerefOrImmediate::Deref(_) => JumpKind::Dynamic,
+    }
+}
+
+/// Returns the immediate value of a relative jump or call, if the instruction is one.
+fn relative_jump_value(instruction: &mut Instruction) -> Option<&mut BigInt> {
+    match &mut instruction.body {
+        InstructionBody::Jump(JumpInstruction {
+            target: DerefOrImmediate::Immediate(value),
+            relative: true,
+        })
+        | InstructionBody::Call(CallInstruction {
+            target: DerefOrImmediate::Immediate(value),
+            relative: true,
+        })
+        | InstructionBody::Jnz(JnzInstruction {
+            jump_offset: DerefOrImmediate::Immediate(value),
+            ..
+        }) => Some(&mut value.value),
+        _ => None,
+    }
+}
+
+/// Returns true if the instruction, at the given offset, is an unconditional relative jump to the
+/// instruction right after it.
+fn is_jump_to_next(
+    instruction: &Instruction,
+    offset: usize,
+    relocation: Option<&Relocation>,
+    statement_offsets: &[usize],
+) -> bool {
+    let Instruction {
+        body:
+            InstructionBody::Jump(JumpInstruction {
+                target: DerefOrImmediate::Immediate(value),
+                relative: true,
+            }),
+        inc_ap: false,
+        ..
+    } = instruction
+    else {
+        return false;
+    };
+    let target = match relocation {
+        None => offset as i128 + &value.value,
+        // The relocation adds the offset of the statement, relative to the jump.
+        Some(Relocation::RelativeStatementId(statement_idx)) => {
+            statement_offsets[statement_idx.0] as i128 + &value.value
+        }
+        Some(Relocation::EndOfProgram | Relocation::ConstStart(_)) => return false,
+    };
+    target == BigInt::from(offset + instruction.body.op_size())
+}
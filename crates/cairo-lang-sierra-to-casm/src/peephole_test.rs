@@ -0,0 +1,108 @@
+use cairo_lang_casm::{casm, deref};
+use cairo_lang_sierra::program::StatementIdx;
+use pretty_assertions::assert_eq;
+
+use super::optimize_instructions;
+use crate::relocations::{Relocation, RelocationEntry};
+
+#[test]
+fn test_jump_to_next_instruction() {
+    let mut instructions = casm! {
+        jmp rel 4 if [fp + -3] != 0;
+        jmp rel 2;
+        [ap + 0] = 1, ap++;
+        jmp rel 0;
+        ret;
+    }
+    .instructions;
+    let mut relocations = vec![RelocationEntry {
+        instruction_idx: 3,
+        relocation: Relocation::RelativeStatementId(StatementIdx(1)),
+    }];
+    let mut statement_offsets = vec![0, 8, 9];
+    optimize_instructions(&mut instructions, &mut relocations, &mut statement_offsets);
+    assert_eq!(
+        instructions,
+        casm! {
+            jmp rel 2 if [fp + -3] != 0;
+            [ap + 0] = 1, ap++;
+            ret;
+        }
+        .instructions
+    );
+    assert_eq!(relocations, vec![]);
+    assert_eq!(statement_offsets, vec![0, 4, 5]);
+}
+
+#[test]
+fn test_unreachable_instructions() {
+    let mut instructions = casm! {
+        jmp rel 6 if [fp + -3] != 0;
+        jmp rel 0;
+        [ap + 0] = 1, ap++;
+        [ap + 0] = 2, ap++;
+        ret;
+        [ap + 0] = 3, ap++;
+    }
+    .instructions;
+    let mut relocations = vec![RelocationEntry {
+        instruction_idx: 1,
+        relocation: Relocation::RelativeStatementId(StatementIdx(1)),
+    }];
+    let mut statement_offsets = vec![0, 8, 11];
+    optimize_instructions(&mut instructions, &mut relocations, &mut statement_offsets);
+    assert_eq!(
+        instructions,
+        casm! {
+            jmp rel 4 if [fp + -3] != 0;
+            jmp rel 0;
+            [ap + 0] = 2, ap++;
+            ret;
+        }
+        .instructions
+    );
+    assert_eq!(
+        relocations,
+        vec![RelocationEntry {
+            instruction_idx: 1,
+            relocation: Relocation::RelativeStatementId(StatementIdx(1)),
+        }]
+    );
+    assert_eq!(statement_offsets, vec![0, 6, 7]);
+}
+
+#[test]
+fn test_jump_table_is_kept() {
+    let selector = deref!([ap - 1]);
+    let mut instructions = casm! {
+        jmp rel selector;
+        jmp rel 0;
+        jmp rel 0;
+        ret;
+    }
+    .instructions;
+    let mut relocations = vec![
+        RelocationEntry {
+            instruction_idx: 1,
+            relocation: Relocation::RelativeStatementId(StatementIdx(1)),
+        },
+        RelocationEntry {
+            instruction_idx: 2,
+            relocation: Relocation::RelativeStatementId(StatementIdx(1)),
+        },
+    ];
+    let mut statement_offsets = vec![0, 5, 6];
+    optimize_instructions(&mut instructions, &mut relocations, &mut statement_offsets);
+    assert_eq!(
+        instructions,
+        casm! {
+            jmp rel selector;
+            jmp rel 0;
+            jmp rel 0;
+            ret;
+        }
+        .instructions
+    );
+    assert_eq!(relocations.len(), 2);
+    assert_eq!(statement_offsets, vec![0, 5, 6]);
+}
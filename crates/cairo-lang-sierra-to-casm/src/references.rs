@@ -5,7 +5,7 @@ use cairo_lang_casm::cell_expression::CellExpression;
 use cairo_lang_casm::operand::{CellRef, Register};
 use cairo_lang_sierra::ids::{ConcreteTypeId, VarId};
 use cairo_lang_sierra::program::{Function, StatementIdx};
-use cairo_lang_sierra_type_size::TypeSizeMap;
+use cairo_lang_sierra_type_size::TypeLayoutMap;
 use cairo_lang_utils::casts::IntoOrPanic;
 use thiserror::Error;
 use {cairo_lang_casm, cairo_lang_sierra};
@@ -41,10 +41,11 @@ pub struct ReferenceValue {
 impl ReferenceValue {
     /// Should never actually fail - since this was built by the type system.
     /// This is just a sanity check, and therefore it panics instead of returning an error.
-    pub fn validate(&self, type_sizes: &TypeSizeMap) {
-        let size = *type_sizes.get(&self.ty).expect("ReferenceValue has unknown type");
+    pub fn validate(&self, type_layouts: &TypeLayoutMap) {
+        let layout = type_layouts.get(&self.ty).expect("ReferenceValue has unknown type");
         let actual_size: i16 = self.expression.cells.len().into_or_panic();
-        assert_eq!(actual_size, size, "ReferenceValue type size mismatch.");
+        assert_eq!(actual_size, layout.size, "ReferenceValue type size mismatch.");
+        assert_eq!(actual_size == 0, layout.zero_sized, "ReferenceValue zero size mismatch.");
     }
 }
 
@@ -126,14 +127,15 @@ impl ApplyApChange for ReferenceExpression {
 /// Builds the HashMap of references to the parameters of a function.
 pub fn build_function_parameters_refs(
     func: &Function,
-    type_sizes: &TypeSizeMap,
+    type_layouts: &TypeLayoutMap,
 ) -> Result<StatementRefs, ReferencesError> {
     let mut refs = HashMap::with_capacity(func.params.len());
     let mut offset = -3_i16;
     for (param_idx, param) in func.params.iter().rev().enumerate() {
-        let size = type_sizes
+        let size = type_layouts
             .get(&param.ty)
-            .ok_or_else(|| ReferencesError::UnknownType(param.ty.clone()))?;
+            .ok_or_else(|| ReferencesError::UnknownType(param.ty.clone()))?
+            .size;
         if refs
             .insert(
                 param.id.clone(),
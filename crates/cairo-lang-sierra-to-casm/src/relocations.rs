@@ -3,9 +3,12 @@ use cairo_lang_casm::instructions::{
     JumpInstruction,
 };
 use cairo_lang_casm::operand::{BinOpOperand, DerefOrImmediate, ResOperand};
+use cairo_lang_sierra::ids::ConcreteTypeId;
 use cairo_lang_sierra::program::StatementIdx;
 use cairo_lang_sierra_gas::objects::ConstCost;
 
+use crate::compiler::ConstsInfo;
+
 type CodeOffset = usize;
 
 #[derive(Debug, Eq, PartialEq)]
@@ -14,8 +17,11 @@ pub enum Relocation {
     /// that is being relocated.
     RelativeStatementId(StatementIdx),
     /// Adds the offset between the current statement index and the end of the program code
-    /// segment.
+    /// segment, which is right after the constants segment.
     EndOfProgram,
+    /// Adds the offset between the current statement index and the start of the given `Const`
+    /// type's value within the constants segment, which is right after the code.
+    ConstStart(ConcreteTypeId),
 }
 
 impl Relocation {
@@ -23,11 +29,17 @@ impl Relocation {
         &self,
         instruction_offset: CodeOffset,
         statement_offsets: &[CodeOffset],
+        consts_info: &ConstsInfo,
         instruction: &mut Instruction,
     ) {
+        let code_size = *statement_offsets.last().unwrap();
         let target_pc = match self {
             Relocation::RelativeStatementId(statement_idx) => statement_offsets[statement_idx.0],
-            Relocation::EndOfProgram => *statement_offsets.last().unwrap(),
+            Relocation::EndOfProgram => code_size + consts_info.segment.len(),
+            Relocation::ConstStart(const_type) => {
+                code_size
+                    + consts_info.segment_offsets.get(const_type).expect("Const not in segment.")
+            }
         };
 
         match instruction {
@@ -92,6 +104,7 @@ pub struct RelocationEntry {
 pub fn relocate_instructions(
     relocations: &[RelocationEntry],
     statement_offsets: &[usize],
+    consts_info: &ConstsInfo,
     instructions: &mut [Instruction],
 ) {
     let mut program_offset = 0;
@@ -102,7 +115,7 @@ pub fn relocate_instructions(
             Some(RelocationEntry { instruction_idx: relocation_idx, relocation })
                 if *relocation_idx == instruction_idx =>
             {
-                relocation.apply(program_offset, statement_offsets, instruction);
+                relocation.apply(program_offset, statement_offsets, consts_info, instruction);
                 relocation_entry = relocations_iter.next();
             }
             _ => (),
@@ -1,20 +1,60 @@
 use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType, CoreTypeConcrete};
 use cairo_lang_sierra::extensions::starknet::StarkNetTypeConcrete;
+use cairo_lang_sierra::extensions::ConcreteType;
 use cairo_lang_sierra::ids::ConcreteTypeId;
 use cairo_lang_sierra::program::Program;
 use cairo_lang_sierra::program_registry::ProgramRegistry;
 use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
 
 pub type TypeSizeMap = UnorderedHashMap<ConcreteTypeId, i16>;
+pub type TypeLayoutMap = UnorderedHashMap<ConcreteTypeId, TypeLayout>;
+
+/// The layout of a concrete type, as used when tracking the values of the type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TypeLayout {
+    /// The number of cells a value of the type takes.
+    pub size: i16,
+    /// Can the type be stored by any of the store commands.
+    pub storable: bool,
+    /// Can the type be (trivially) dropped.
+    pub droppable: bool,
+    /// Can the type be (trivially) duplicated.
+    pub duplicatable: bool,
+    /// Is the type zero sized.
+    pub zero_sized: bool,
+}
 
 /// Returns a mapping for the sizes of all types for the given program.
 pub fn get_type_size_map(
     program: &Program,
     registry: &ProgramRegistry<CoreType, CoreLibfunc>,
 ) -> Option<TypeSizeMap> {
-    let mut type_sizes = TypeSizeMap::default();
+    let type_layouts = get_type_layout_map(program, registry)?;
+    Some(
+        program
+            .type_declarations
+            .iter()
+            .filter_map(|declaration| {
+                Some((declaration.id.clone(), type_layouts.get(&declaration.id)?.size))
+            })
+            .collect(),
+    )
+}
+
+/// Returns a mapping for the layouts of all types for the given program.
+pub fn get_type_layout_map(
+    program: &Program,
+    registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+) -> Option<TypeLayoutMap> {
+    let mut type_layouts = TypeLayoutMap::default();
+    let type_size = |type_layouts: &TypeLayoutMap, ty: &ConcreteTypeId| {
+        type_layouts.get(ty).map(|layout: &TypeLayout| layout.size)
+    };
     for declaration in &program.type_declarations {
-        let size = match registry.get_type(&declaration.id).ok()? {
+        let concrete_type = registry.get_type(&declaration.id).ok()?;
+        let size = match concrete_type {
+            // Constants are only materialized by the const libfuncs, and are never stored.
+            CoreTypeConcrete::Const(_) => continue,
             CoreTypeConcrete::Felt252(_)
             | CoreTypeConcrete::GasBuiltin(_)
             | CoreTypeConcrete::Bitwise(_)
@@ -52,26 +92,36 @@ pub fn get_type_size_map(
             CoreTypeConcrete::NonZero(wrapped_ty)
             | CoreTypeConcrete::Snapshot(wrapped_ty)
             | CoreTypeConcrete::Uninitialized(wrapped_ty) => {
-                type_sizes.get(&wrapped_ty.ty).cloned()
+                type_size(&type_layouts, &wrapped_ty.ty)
             }
             CoreTypeConcrete::EcState(_) => Some(3),
             CoreTypeConcrete::Uint128MulGuarantee(_) => Some(4),
             CoreTypeConcrete::Enum(enum_type) => {
                 let mut size = 1;
                 for variant in &enum_type.variants {
-                    size = size.max(type_sizes.get(variant).cloned()? + 1);
+                    size = size.max(type_size(&type_layouts, variant)? + 1);
                 }
                 Some(size)
             }
             CoreTypeConcrete::Struct(struct_type) => {
                 let mut size = 0;
                 for member in &struct_type.members {
-                    size += type_sizes.get(member).cloned()?;
+                    size += type_size(&type_layouts, member)?;
                 }
                 Some(size)
             }
         }?;
-        type_sizes.insert(declaration.id.clone(), size);
+        let info = concrete_type.info();
+        type_layouts.insert(
+            declaration.id.clone(),
+            TypeLayout {
+                size,
+                storable: info.storable,
+                droppable: info.droppable,
+                duplicatable: info.duplicatable,
+                zero_sized: info.zero_sized,
+            },
+        );
     }
-    Some(type_sizes)
+    Some(type_layouts)
 }
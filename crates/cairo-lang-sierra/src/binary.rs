@@ -0,0 +1,376 @@
+//! A compact binary encoding of Sierra programs, for loading programs without parsing their textual
+//! form.
+//!
+//! The encoding starts with [MAGIC] and the format version, followed by a table of the generic ids
+//! used by the declarations, and then by the declarations, statements and functions of the
+//! program. Declarations refer to their generic id by its index in the table, so each generic id is
+//! stored once. Integers are encoded as LEB128 varints.
+//!
+//! Debug names are not encoded - a decoded program has none. Use [crate::debug_info::DebugInfo] to
+//! keep them.
+
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+use itertools::chain;
+use num_bigint::{BigInt, BigUint};
+use smol_str::SmolStr;
+use thiserror::Error;
+
+use crate::ids::{
+    ConcreteLibfuncId, ConcreteTypeId, FunctionId, GenericLibfuncId, GenericTypeId, UserTypeId,
+    VarId,
+};
+use crate::program::{
+    BranchInfo, BranchTarget, ConcreteLibfuncLongId, ConcreteTypeLongId, DeclaredTypeInfo,
+    Function, FunctionSignature, GenericArg, Invocation, LibfuncDeclaration, Param, Program,
+    Statement, StatementIdx, TypeDeclaration,
+};
+
+#[cfg(test)]
+#[path = "binary_test.rs"]
+mod test;
+
+/// The bytes every binary encoded program starts with.
+pub const MAGIC: &[u8; 4] = b"SIRB";
+/// The version of the binary format, bumped on every change of the encoding.
+const FORMAT_VERSION: u64 = 1;
+
+const TYPE_STORABLE: u64 = 0b0001;
+const TYPE_DROPPABLE: u64 = 0b0010;
+const TYPE_DUPLICATABLE: u64 = 0b0100;
+const TYPE_ZERO_SIZED: u64 = 0b1000;
+/// Marks a type declaration with a declared type info, to tell it apart from one without.
+const TYPE_INFO_MARKER: u64 = 0b10000;
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum BinaryDecodingError {
+    #[error("The input is not a binary Sierra program.")]
+    InvalidMagic,
+    #[error("Unsupported binary Sierra format version {0}.")]
+    UnsupportedVersion(u64),
+    #[error("Unexpected end of input.")]
+    UnexpectedEndOfInput,
+    #[error("Invalid input for decoding.")]
+    InvalidInput,
+    #[error("Unexpected bytes after the end of the program.")]
+    TrailingBytes,
+}
+
+/// Encodes a Sierra program into the binary format.
+pub fn encode_program(program: &Program) -> Vec<u8> {
+    let mut encoder = Encoder { output: MAGIC.to_vec(), generic_ids: OrderedHashMap::default() };
+    encoder.varint(FORMAT_VERSION);
+    let generic_ids: OrderedHashSet<&SmolStr> = chain!(
+        program.type_declarations.iter().map(|declaration| &declaration.long_id.generic_id.0),
+        program.libfunc_declarations.iter().map(|declaration| &declaration.long_id.generic_id.0)
+    )
+    .collect();
+    encoder.len(generic_ids.len());
+    for (index, generic_id) in generic_ids.into_iter().enumerate() {
+        encoder.bytes(generic_id.as_bytes());
+        encoder.generic_ids.insert(generic_id.clone(), index);
+    }
+    encoder.program(program);
+    encoder.output
+}
+
+/// Decodes a Sierra program encoded by [encode_program].
+pub fn decode_program(input: &[u8]) -> Result<Program, BinaryDecodingError> {
+    let input = input.strip_prefix(MAGIC).ok_or(BinaryDecodingError::InvalidMagic)?;
+    let mut decoder = Decoder { input, generic_ids: vec![] };
+    let version = decoder.varint()?;
+    if version != FORMAT_VERSION {
+        return Err(BinaryDecodingError::UnsupportedVersion(version));
+    }
+    decoder.generic_ids = decoder.vec(|decoder| {
+        std::str::from_utf8(decoder.bytes()?)
+            .map(SmolStr::from)
+            .map_err(|_| BinaryDecodingError::InvalidInput)
+    })?;
+    let program = decoder.program()?;
+    if !decoder.input.is_empty() {
+        return Err(BinaryDecodingError::TrailingBytes);
+    }
+    Ok(program)
+}
+
+/// Helper for encoding a program.
+struct Encoder {
+    output: Vec<u8>,
+    /// The index in the generic id table of each generic id.
+    generic_ids: OrderedHashMap<SmolStr, usize>,
+}
+impl Encoder {
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.output.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.output.push(value as u8);
+    }
+
+    fn len(&mut self, len: usize) {
+        self.varint(len as u64);
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.len(bytes.len());
+        self.output.extend_from_slice(bytes);
+    }
+
+    fn generic_id(&mut self, generic_id: &SmolStr) {
+        self.len(self.generic_ids[generic_id.clone()]);
+    }
+
+    fn program(&mut self, program: &Program) {
+        self.len(program.type_declarations.len());
+        for declaration in &program.type_declarations {
+            self.varint(declaration.id.id);
+            self.generic_id(&declaration.long_id.generic_id.0);
+            self.generic_args(&declaration.long_id.generic_args);
+            self.varint(match &declaration.declared_type_info {
+                Some(info) => {
+                    TYPE_INFO_MARKER
+                        | (if info.storable { TYPE_STORABLE } else { 0 })
+                        | (if info.droppable { TYPE_DROPPABLE } else { 0 })
+                        | (if info.duplicatable { TYPE_DUPLICATABLE } else { 0 })
+                        | (if info.zero_sized { TYPE_ZERO_SIZED } else { 0 })
+                }
+                None => 0,
+            });
+        }
+        self.len(program.libfunc_declarations.len());
+        for declaration in &program.libfunc_declarations {
+            self.varint(declaration.id.id);
+            self.generic_id(&declaration.long_id.generic_id.0);
+            self.generic_args(&declaration.long_id.generic_args);
+        }
+        self.len(program.statements.len());
+        for statement in &program.statements {
+            match statement {
+                Statement::Invocation(invocation) => {
+                    self.varint(0);
+                    self.varint(invocation.libfunc_id.id);
+                    self.var_ids(&invocation.args);
+                    self.len(invocation.branches.len());
+                    for branch in &invocation.branches {
+                        // Statement targets are shifted by one to make room for the fallthrough.
+                        self.len(match branch.target {
+                            BranchTarget::Fallthrough => 0,
+                            BranchTarget::Statement(StatementIdx(idx)) => idx + 1,
+                        });
+                        self.var_ids(&branch.results);
+                    }
+                }
+                Statement::Return(vars) => {
+                    self.varint(1);
+                    self.var_ids(vars);
+                }
+            }
+        }
+        self.len(program.funcs.len());
+        for func in &program.funcs {
+            self.varint(func.id.id);
+            self.type_ids(&func.signature.param_types);
+            self.type_ids(&func.signature.ret_types);
+            self.len(func.params.len());
+            for param in &func.params {
+                self.varint(param.id.id);
+                self.varint(param.ty.id);
+            }
+            self.len(func.entry_point.0);
+        }
+    }
+
+    fn generic_args(&mut self, args: &[GenericArg]) {
+        self.len(args.len());
+        for arg in args {
+            match arg {
+                GenericArg::UserType(id) => {
+                    self.varint(0);
+                    self.bytes(&id.id.to_bytes_le());
+                }
+                GenericArg::Type(id) => {
+                    self.varint(1);
+                    self.varint(id.id);
+                }
+                GenericArg::Value(value) => {
+                    self.varint(2);
+                    self.bytes(&value.to_signed_bytes_le());
+                }
+                GenericArg::UserFunc(id) => {
+                    self.varint(3);
+                    self.varint(id.id);
+                }
+                GenericArg::Libfunc(id) => {
+                    self.varint(4);
+                    self.varint(id.id);
+                }
+            }
+        }
+    }
+
+    fn type_ids(&mut self, ids: &[ConcreteTypeId]) {
+        self.len(ids.len());
+        for id in ids {
+            self.varint(id.id);
+        }
+    }
+
+    fn var_ids(&mut self, ids: &[VarId]) {
+        self.len(ids.len());
+        for id in ids {
+            self.varint(id.id);
+        }
+    }
+}
+
+/// Helper for decoding a program.
+struct Decoder<'a> {
+    input: &'a [u8],
+    /// The generic id table of the program.
+    generic_ids: Vec<SmolStr>,
+}
+impl<'a> Decoder<'a> {
+    fn varint(&mut self) -> Result<u64, BinaryDecodingError> {
+        let mut value: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let (byte, rest) =
+                self.input.split_first().ok_or(BinaryDecodingError::UnexpectedEndOfInput)?;
+            self.input = rest;
+            let bits = u64::from(byte & 0x7f);
+            // Reject bits that do not fit in a u64.
+            if bits << shift >> shift != bits {
+                return Err(BinaryDecodingError::InvalidInput);
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(BinaryDecodingError::InvalidInput)
+    }
+
+    fn len(&mut self) -> Result<usize, BinaryDecodingError> {
+        usize::try_from(self.varint()?).map_err(|_| BinaryDecodingError::InvalidInput)
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], BinaryDecodingError> {
+        let len = self.len()?;
+        if len > self.input.len() {
+            return Err(BinaryDecodingError::UnexpectedEndOfInput);
+        }
+        let (bytes, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(bytes)
+    }
+
+    /// Decodes a length prefixed sequence of items, each decoded by `item`.
+    fn vec<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, BinaryDecodingError>,
+    ) -> Result<Vec<T>, BinaryDecodingError> {
+        let len = self.len()?;
+        // Every item takes at least a byte, so the length is bounded by the remaining input.
+        if len > self.input.len() {
+            return Err(BinaryDecodingError::UnexpectedEndOfInput);
+        }
+        (0..len).map(|_| item(self)).collect()
+    }
+
+    fn generic_id(&mut self) -> Result<SmolStr, BinaryDecodingError> {
+        let index = self.len()?;
+        self.generic_ids.get(index).cloned().ok_or(BinaryDecodingError::InvalidInput)
+    }
+
+    fn program(&mut self) -> Result<Program, BinaryDecodingError> {
+        let type_declarations = self.vec(|decoder| {
+            let id = ConcreteTypeId::new(decoder.varint()?);
+            let generic_id = GenericTypeId(decoder.generic_id()?);
+            let generic_args = decoder.generic_args()?;
+            let info = decoder.varint()?;
+            let declared_type_info = match info {
+                0 => None,
+                _ if info & TYPE_INFO_MARKER != 0 && info < TYPE_INFO_MARKER << 1 => {
+                    Some(DeclaredTypeInfo {
+                        storable: info & TYPE_STORABLE != 0,
+                        droppable: info & TYPE_DROPPABLE != 0,
+                        duplicatable: info & TYPE_DUPLICATABLE != 0,
+                        zero_sized: info & TYPE_ZERO_SIZED != 0,
+                    })
+                }
+                _ => return Err(BinaryDecodingError::InvalidInput),
+            };
+            Ok(TypeDeclaration {
+                id,
+                long_id: ConcreteTypeLongId { generic_id, generic_args },
+                declared_type_info,
+            })
+        })?;
+        let libfunc_declarations = self.vec(|decoder| {
+            let id = ConcreteLibfuncId::new(decoder.varint()?);
+            let generic_id = GenericLibfuncId(decoder.generic_id()?);
+            let generic_args = decoder.generic_args()?;
+            Ok(LibfuncDeclaration {
+                id,
+                long_id: ConcreteLibfuncLongId { generic_id, generic_args },
+            })
+        })?;
+        let statements = self.vec(|decoder| {
+            Ok(match decoder.varint()? {
+                0 => {
+                    let libfunc_id = ConcreteLibfuncId::new(decoder.varint()?);
+                    let args = decoder.var_ids()?;
+                    let branches = decoder.vec(|decoder| {
+                        let target = match decoder.len()? {
+                            0 => BranchTarget::Fallthrough,
+                            idx => BranchTarget::Statement(StatementIdx(idx - 1)),
+                        };
+                        Ok(BranchInfo { target, results: decoder.var_ids()? })
+                    })?;
+                    Statement::Invocation(Invocation { libfunc_id, args, branches })
+                }
+                1 => Statement::Return(decoder.var_ids()?),
+                _ => return Err(BinaryDecodingError::InvalidInput),
+            })
+        })?;
+        let funcs = self.vec(|decoder| {
+            let id = FunctionId::new(decoder.varint()?);
+            let signature = FunctionSignature {
+                param_types: decoder.type_ids()?,
+                ret_types: decoder.type_ids()?,
+            };
+            let params = decoder.vec(|decoder| {
+                let id = VarId::new(decoder.varint()?);
+                Ok(Param { id, ty: ConcreteTypeId::new(decoder.varint()?) })
+            })?;
+            let entry_point = StatementIdx(decoder.len()?);
+            Ok(Function { id, signature, params, entry_point })
+        })?;
+        Ok(Program { type_declarations, libfunc_declarations, statements, funcs })
+    }
+
+    fn generic_args(&mut self) -> Result<Vec<GenericArg>, BinaryDecodingError> {
+        self.vec(|decoder| {
+            Ok(match decoder.varint()? {
+                0 => GenericArg::UserType(UserTypeId {
+                    id: BigUint::from_bytes_le(decoder.bytes()?),
+                    debug_name: None,
+                }),
+                1 => GenericArg::Type(ConcreteTypeId::new(decoder.varint()?)),
+                2 => GenericArg::Value(BigInt::from_signed_bytes_le(decoder.bytes()?)),
+                3 => GenericArg::UserFunc(FunctionId::new(decoder.varint()?)),
+                4 => GenericArg::Libfunc(ConcreteLibfuncId::new(decoder.varint()?)),
+                _ => return Err(BinaryDecodingError::InvalidInput),
+            })
+        })
+    }
+
+    fn type_ids(&mut self) -> Result<Vec<ConcreteTypeId>, BinaryDecodingError> {
+        self.vec(|decoder| Ok(ConcreteTypeId::new(decoder.varint()?)))
+    }
+
+    fn var_ids(&mut self) -> Result<Vec<VarId>, BinaryDecodingError> {
+        self.vec(|decoder| Ok(VarId::new(decoder.varint()?)))
+    }
+}
@@ -0,0 +1,70 @@
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+use test_case::test_case;
+
+use super::{decode_program, encode_program, BinaryDecodingError, MAGIC};
+use crate::program::Program;
+use crate::ProgramParser;
+
+/// Returns a program using every kind of generic arg and statement.
+fn program() -> Program {
+    ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type u128 = u128 [storable: true, drop: true, dup: true, zero_sized: false];
+            type Unit = Struct<ut@Unit> [storable: true, drop: true, dup: true, zero_sized: true];
+            type NonZeroFelt252 = NonZero<felt252>;
+            type ConstMinus5 = Const<felt252, -5>;
+            type ConstBig = Const<u128, 340282366920938463463374607431768211455>;
+
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc branch_align = branch_align;
+            libfunc drop_nz_felt252 = drop<NonZeroFelt252>;
+            libfunc call_foo = function_call<user@foo>;
+            libfunc store_temp_felt252 = store_temp<felt252>;
+
+            felt252_is_zero(x) { fallthrough() 3(x) };
+            branch_align() -> ();
+            return (y);
+            branch_align() -> ();
+            drop_nz_felt252(x) -> ();
+            call_foo(x, y) -> (y);
+            return (y);
+
+            foo@0(x: felt252, y: felt252) -> (felt252);
+        "})
+        .unwrap()
+}
+
+#[test]
+fn test_round_trip() {
+    let program = program();
+    let encoded = encode_program(&program);
+    assert!(encoded.starts_with(MAGIC));
+    assert_eq!(decode_program(&encoded), Ok(program));
+}
+
+#[test]
+fn test_generic_ids_are_stored_once() {
+    let encoded = encode_program(&program());
+    let occurrences = |name: &[u8]| encoded.windows(name.len()).filter(|w| *w == name).count();
+    assert_eq!(occurrences(b"Const"), 1);
+    assert_eq!(occurrences(b"branch_align"), 1);
+}
+
+#[test]
+fn test_debug_names_are_dropped() {
+    let decoded = decode_program(&encode_program(&program())).unwrap();
+    assert_eq!(decoded.type_declarations[0].id.debug_name, None);
+    assert_eq!(decoded.funcs[0].id.debug_name, None);
+}
+
+#[test_case(b"", BinaryDecodingError::InvalidMagic; "empty")]
+#[test_case(b"type felt252 = felt252;", BinaryDecodingError::InvalidMagic; "text")]
+#[test_case(b"SIRB\x02", BinaryDecodingError::UnsupportedVersion(2); "version")]
+#[test_case(b"SIRB\x01\x01\x05abc", BinaryDecodingError::UnexpectedEndOfInput; "truncated")]
+#[test_case(b"SIRB\x01\x00\x01\x00\x00", BinaryDecodingError::InvalidInput; "unknown generic id")]
+#[test_case(b"SIRB\x01\x00\x00\x00\x00\x00\x00", BinaryDecodingError::TrailingBytes; "trailing")]
+fn test_decoding_errors(input: &[u8], expected: BinaryDecodingError) {
+    assert_eq!(decode_program(input), Err(expected));
+}
@@ -0,0 +1,124 @@
+//! Canonical form of Sierra programs, for checking whether two programs represent the same logic.
+
+use std::collections::HashMap;
+
+use sha3::{Digest, Keccak256};
+
+use crate::ids::{ConcreteLibfuncId, ConcreteTypeId, FunctionId, UserTypeId, VarId};
+use crate::program::{GenericArg, Program, Statement};
+
+#[cfg(test)]
+#[path = "canonical_test.rs"]
+mod test;
+
+/// Returns the canonical form of a program - the program with all the debug names removed, and
+/// with its ids renumbered. Types, libfuncs and user functions are numbered by the order of their
+/// declarations, and variables are numbered per function by the order of their first appearance.
+/// Two programs that differ only in their ids and debug names have the same canonical form.
+pub fn canonicalize(program: &Program) -> Program {
+    let type_ids = declaration_indices(program.type_declarations.iter().map(|decl| decl.id.id));
+    let libfunc_ids =
+        declaration_indices(program.libfunc_declarations.iter().map(|decl| decl.id.id));
+    let function_ids = declaration_indices(program.funcs.iter().map(|func| func.id.id));
+    let type_id = |id: &ConcreteTypeId| ConcreteTypeId::new(canonical_id(&type_ids, id.id));
+    let libfunc_id =
+        |id: &ConcreteLibfuncId| ConcreteLibfuncId::new(canonical_id(&libfunc_ids, id.id));
+    let function_id = |id: &FunctionId| FunctionId::new(canonical_id(&function_ids, id.id));
+    let generic_args = |args: &[GenericArg]| -> Vec<GenericArg> {
+        args.iter()
+            .map(|arg| match arg {
+                GenericArg::UserType(id) => {
+                    GenericArg::UserType(UserTypeId { id: id.id.clone(), debug_name: None })
+                }
+                GenericArg::Type(id) => GenericArg::Type(type_id(id)),
+                GenericArg::Value(value) => GenericArg::Value(value.clone()),
+                GenericArg::UserFunc(id) => GenericArg::UserFunc(function_id(id)),
+                GenericArg::Libfunc(id) => GenericArg::Libfunc(libfunc_id(id)),
+            })
+            .collect()
+    };
+
+    let mut canonical = program.clone();
+    for decl in &mut canonical.type_declarations {
+        decl.id = type_id(&decl.id);
+        decl.long_id.generic_args = generic_args(&decl.long_id.generic_args);
+    }
+    for decl in &mut canonical.libfunc_declarations {
+        decl.id = libfunc_id(&decl.id);
+        decl.long_id.generic_args = generic_args(&decl.long_id.generic_args);
+    }
+
+    // The variables of each function are renumbered separately, where the function of a statement
+    // is the one with the closest entry point before it.
+    let mut var_ids_per_function = vec![HashMap::<u64, u64>::new(); canonical.funcs.len() + 1];
+    let mut function_by_entry_point: Vec<_> =
+        canonical.funcs.iter().enumerate().map(|(idx, func)| (func.entry_point.0, idx)).collect();
+    function_by_entry_point.sort();
+    for (func, var_ids) in canonical.funcs.iter_mut().zip(&mut var_ids_per_function) {
+        func.id = function_id(&func.id);
+        for ty in func.signature.param_types.iter_mut().chain(&mut func.signature.ret_types) {
+            *ty = type_id(ty);
+        }
+        for param in &mut func.params {
+            param.id = var_id(var_ids, &param.id);
+            param.ty = type_id(&param.ty);
+        }
+    }
+    for (statement_idx, statement) in canonical.statements.iter_mut().enumerate() {
+        // Statements before the first entry point use a separate numbering.
+        let var_ids = match function_by_entry_point
+            .partition_point(|(entry_point, _)| *entry_point <= statement_idx)
+        {
+            0 => var_ids_per_function.last_mut().unwrap(),
+            position => &mut var_ids_per_function[function_by_entry_point[position - 1].1],
+        };
+        match statement {
+            Statement::Invocation(invocation) => {
+                invocation.libfunc_id = libfunc_id(&invocation.libfunc_id);
+                for arg in &mut invocation.args {
+                    *arg = var_id(var_ids, arg);
+                }
+                for branch in &mut invocation.branches {
+                    for result in &mut branch.results {
+                        *result = var_id(var_ids, result);
+                    }
+                }
+            }
+            Statement::Return(vars) => {
+                for var in vars {
+                    *var = var_id(var_ids, var);
+                }
+            }
+        }
+    }
+    canonical
+}
+
+/// Returns the canonical hash of a program - the Keccak256 hash of its canonical form (see
+/// [canonicalize]), as a hex string.
+pub fn canonical_hash(program: &Program) -> String {
+    let mut hasher = Keccak256::new();
+    hasher.update(canonicalize(program).to_string().as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Returns a mapping from the ids of declarations to their indices.
+fn declaration_indices(ids: impl Iterator<Item = u64>) -> HashMap<u64, u64> {
+    let mut indices = HashMap::new();
+    for id in ids {
+        let next_index = indices.len() as u64;
+        indices.entry(id).or_insert(next_index);
+    }
+    indices
+}
+
+/// Returns the canonical id of a declared id. Undeclared ids are numbered after all declarations.
+fn canonical_id(indices: &HashMap<u64, u64>, id: u64) -> u64 {
+    indices.get(&id).copied().unwrap_or(indices.len() as u64 + id)
+}
+
+/// Returns the canonical id of a variable, numbering it if it is the first time it is seen.
+fn var_id(var_ids: &mut HashMap<u64, u64>, var: &VarId) -> VarId {
+    let next_index = var_ids.len() as u64;
+    VarId::new(*var_ids.entry(var.id).or_insert(next_index))
+}
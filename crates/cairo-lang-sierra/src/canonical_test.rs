@@ -0,0 +1,72 @@
+use indoc::indoc;
+
+use super::{canonical_hash, canonicalize};
+use crate::ProgramParser;
+
+#[test]
+fn test_canonicalize() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+
+            libfunc felt252_add = felt252_add;
+            libfunc store_temp_felt252 = store_temp<felt252>;
+
+            felt252_add(x, y) -> (sum);
+            store_temp_felt252(sum) -> (sum);
+            return(sum);
+
+            Add@0(x: felt252, y: felt252) -> (felt252);
+        "})
+        .unwrap();
+    assert_eq!(
+        canonicalize(&program).to_string(),
+        indoc! {"
+            type [0] = felt252;
+            type [1] = NonZero<[0]>;
+
+            libfunc [0] = felt252_add;
+            libfunc [1] = store_temp<[0]>;
+
+            [0]([0], [1]) -> ([2]); // 0
+            [1]([2]) -> ([2]); // 1
+            return([2]); // 2
+
+            [0]@0([0]: [0], [1]: [0]) -> ([0]);
+        "}
+    );
+}
+
+#[test]
+fn test_canonical_hash() {
+    let named = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            libfunc felt252_add = felt252_add;
+            felt252_add(x, y) -> (sum);
+            return(sum);
+            Add@0(x: felt252, y: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let renumbered = ProgramParser::new()
+        .parse(indoc! {"
+            type [7] = felt252;
+            libfunc [3] = felt252_add;
+            [3]([12], [4]) -> ([5]);
+            return([5]);
+            [9]@0([12]: [7], [4]: [7]) -> ([7]);
+        "})
+        .unwrap();
+    let different = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            libfunc felt252_sub = felt252_sub;
+            felt252_sub(x, y) -> (diff);
+            return(diff);
+            Sub@0(x: felt252, y: felt252) -> (felt252);
+        "})
+        .unwrap();
+    assert_eq!(canonical_hash(&named), canonical_hash(&renumbered));
+    assert_ne!(canonical_hash(&named), canonical_hash(&different));
+}
@@ -0,0 +1,153 @@
+//! Dead code elimination for Sierra programs.
+
+use std::collections::{HashMap, HashSet};
+
+use itertools::{chain, zip_eq};
+
+use crate::extensions::core::{CoreLibfunc, CoreType};
+use crate::extensions::ConcreteLibfunc;
+use crate::ids::{ConcreteLibfuncId, ConcreteTypeId, FunctionId};
+use crate::program::{Function, GenericArg, Program, Statement, StatementIdx};
+use crate::program_registry::{ProgramRegistry, ProgramRegistryError};
+
+#[cfg(test)]
+#[path = "dead_code_test.rs"]
+mod test;
+
+/// Returns a copy of the program, keeping only what is reachable from the functions in `roots`.
+///
+/// Removes the functions not called (directly or indirectly) by any of the roots, the statements
+/// unreachable from the entry point of any of the remaining functions, and the declarations of
+/// the libfuncs and types no longer used. The relative order of everything kept is preserved.
+///
+/// Expects all the statement indices in the program to be in bounds, as checked by
+/// [validate_program](crate::validation::validate_program).
+pub fn eliminate_dead_code(
+    program: &Program,
+    roots: &[FunctionId],
+) -> Result<Program, Box<ProgramRegistryError>> {
+    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(program)?;
+    let libfunc_declarations: HashMap<_, _> = program
+        .libfunc_declarations
+        .iter()
+        .map(|declaration| (&declaration.id, &declaration.long_id))
+        .collect();
+    let type_declarations: HashMap<_, _> = program
+        .type_declarations
+        .iter()
+        .map(|declaration| (&declaration.id, &declaration.long_id))
+        .collect();
+
+    let mut used_funcs = HashSet::<FunctionId>::new();
+    let mut used_libfuncs = HashSet::<ConcreteLibfuncId>::new();
+    let mut reachable = vec![false; program.statements.len()];
+    let mut pending_funcs = roots.to_vec();
+    let mut pending_libfuncs = Vec::<ConcreteLibfuncId>::new();
+    let mut pending_types = Vec::<ConcreteTypeId>::new();
+    while !pending_funcs.is_empty() || !pending_libfuncs.is_empty() {
+        while let Some(func_id) = pending_funcs.pop() {
+            if !used_funcs.insert(func_id.clone()) {
+                continue;
+            }
+            let func = registry.get_function(&func_id)?;
+            pending_types
+                .extend(chain!(&func.signature.param_types, &func.signature.ret_types).cloned());
+            let mut pending_statements = vec![func.entry_point];
+            while let Some(statement_idx) = pending_statements.pop() {
+                if std::mem::replace(&mut reachable[statement_idx.0], true) {
+                    continue;
+                }
+                if let Statement::Invocation(invocation) = &program.statements[statement_idx.0] {
+                    pending_libfuncs.push(invocation.libfunc_id.clone());
+                    pending_statements.extend(
+                        invocation.branches.iter().map(|branch| statement_idx.next(&branch.target)),
+                    );
+                }
+            }
+        }
+        while let Some(libfunc_id) = pending_libfuncs.pop() {
+            if !used_libfuncs.insert(libfunc_id.clone()) {
+                continue;
+            }
+            let libfunc = registry.get_libfunc(&libfunc_id)?;
+            pending_types.extend(libfunc.param_signatures().iter().map(|param| param.ty.clone()));
+            pending_types.extend(libfunc.output_types().into_iter().flatten());
+            for generic_arg in &libfunc_declarations[&libfunc_id].generic_args {
+                match generic_arg {
+                    GenericArg::UserFunc(func_id) => pending_funcs.push(func_id.clone()),
+                    GenericArg::Libfunc(libfunc_id) => pending_libfuncs.push(libfunc_id.clone()),
+                    GenericArg::Type(ty) => pending_types.push(ty.clone()),
+                    GenericArg::UserType(_) | GenericArg::Value(_) => {}
+                }
+            }
+        }
+    }
+
+    let mut used_types = HashSet::<ConcreteTypeId>::new();
+    while let Some(ty) = pending_types.pop() {
+        if !used_types.insert(ty.clone()) {
+            continue;
+        }
+        registry.get_type(&ty)?;
+        pending_types.extend(type_declarations[&ty].generic_args.iter().filter_map(
+            |generic_arg| match generic_arg {
+                GenericArg::Type(ty) => Some(ty.clone()),
+                _ => None,
+            },
+        ));
+    }
+
+    let funcs: Vec<_> =
+        program.funcs.iter().filter(|func| used_funcs.contains(&func.id)).cloned().collect();
+    let (statements, funcs) = retain_statements(&program.statements, &funcs, &reachable);
+    Ok(Program {
+        type_declarations: program
+            .type_declarations
+            .iter()
+            .filter(|declaration| used_types.contains(&declaration.id))
+            .cloned()
+            .collect(),
+        libfunc_declarations: program
+            .libfunc_declarations
+            .iter()
+            .filter(|declaration| used_libfuncs.contains(&declaration.id))
+            .cloned()
+            .collect(),
+        statements,
+        funcs,
+    })
+}
+
+/// Returns the statements marked in `keep`, and the functions with their entry points relocated
+/// accordingly.
+/// Jumps to a removed statement are relocated to the kept statement following it.
+pub(crate) fn retain_statements(
+    statements: &[Statement],
+    funcs: &[Function],
+    keep: &[bool],
+) -> (Vec<Statement>, Vec<Function>) {
+    // The new index of each statement - the number of kept statements preceding it.
+    let new_indices: Vec<usize> = keep
+        .iter()
+        .scan(0, |n_kept, is_kept| {
+            let new_index = *n_kept;
+            *n_kept += usize::from(*is_kept);
+            Some(new_index)
+        })
+        .collect();
+    let relocate = |statement_idx: StatementIdx| StatementIdx(new_indices[statement_idx.0]);
+    (
+        zip_eq(statements, keep)
+            .filter(|(_, is_kept)| **is_kept)
+            .map(|(statement, _)| statement.clone().map(relocate))
+            .collect(),
+        funcs
+            .iter()
+            .map(|func| {
+                let mut func = func.clone();
+                func.entry_point = relocate(func.entry_point);
+                func
+            })
+            .collect(),
+    )
+}
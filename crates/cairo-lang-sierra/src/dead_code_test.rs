@@ -0,0 +1,123 @@
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::eliminate_dead_code;
+use crate::program::Program;
+use crate::program_registry::ProgramRegistryError;
+use crate::ProgramParser;
+
+fn parse(code: &str) -> Program {
+    ProgramParser::new().parse(code).unwrap()
+}
+
+#[test]
+fn test_eliminate_dead_code() {
+    let program = parse(indoc! {"
+        type felt252 = felt252;
+        type NonZeroFelt252 = NonZero<felt252>;
+        type u128 = u128;
+        type Tuple = Struct<ut@Tuple, felt252, felt252>;
+
+        libfunc branch_align = branch_align;
+        libfunc felt252_is_zero = felt252_is_zero;
+        libfunc drop_nz_felt252 = drop<NonZeroFelt252>;
+        libfunc felt252_drop = drop<felt252>;
+        libfunc u128_drop = drop<u128>;
+        libfunc struct_construct = struct_construct<Tuple>;
+        libfunc store_temp_felt252 = store_temp<felt252>;
+        libfunc store_temp_tuple = store_temp<Tuple>;
+        libfunc call_id = function_call<user@id>;
+        libfunc jump = jump;
+
+        felt252_is_zero(a) { fallthrough() 4(a) };
+        branch_align() -> ();
+        call_id(b) -> (b);
+        return (b);
+        branch_align() -> ();
+        drop_nz_felt252(a) -> ();
+        jump() { 8() };
+        felt252_drop(b) -> ();
+        return (b);
+        return (x);
+        u128_drop(x) -> ();
+        return ();
+        struct_construct(a, b) -> (t);
+        store_temp_tuple(t) -> (t);
+        return (t);
+        store_temp_felt252(x) -> (x);
+        return (x);
+
+        main@0(a: felt252, b: felt252) -> (felt252);
+        id@9(x: felt252) -> (felt252);
+        unused_u128@10(x: u128) -> ();
+        unused_tuple@12(a: felt252, b: felt252) -> (Tuple);
+    "});
+    let main = program.funcs[0].id.clone();
+    assert_eq!(
+        eliminate_dead_code(&program, &[main]),
+        Ok(parse(indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+
+            libfunc branch_align = branch_align;
+            libfunc felt252_is_zero = felt252_is_zero;
+            libfunc drop_nz_felt252 = drop<NonZeroFelt252>;
+            libfunc call_id = function_call<user@id>;
+            libfunc jump = jump;
+
+            felt252_is_zero(a) { fallthrough() 4(a) };
+            branch_align() -> ();
+            call_id(b) -> (b);
+            return (b);
+            branch_align() -> ();
+            drop_nz_felt252(a) -> ();
+            jump() { 7() };
+            return (b);
+            return (x);
+
+            main@0(a: felt252, b: felt252) -> (felt252);
+            id@8(x: felt252) -> (felt252);
+        "}))
+    );
+}
+
+#[test]
+fn test_eliminate_dead_code_keeps_types_of_kept_types() {
+    let program = parse(indoc! {"
+        type felt252 = felt252;
+        type NonZeroFelt252 = NonZero<felt252>;
+
+        libfunc drop_nz_felt252 = drop<NonZeroFelt252>;
+
+        drop_nz_felt252(a) -> ();
+        return ();
+
+        foo@0(a: NonZeroFelt252) -> ();
+    "});
+    let foo = program.funcs[0].id.clone();
+    assert_eq!(eliminate_dead_code(&program, &[foo]), Ok(program));
+}
+
+#[test]
+fn test_eliminate_dead_code_without_roots() {
+    let program = parse(indoc! {"
+        type felt252 = felt252;
+
+        libfunc felt252_drop = drop<felt252>;
+
+        felt252_drop(a) -> ();
+        return ();
+
+        foo@0(a: felt252) -> ();
+    "});
+    assert_eq!(eliminate_dead_code(&program, &[]), Ok(parse("")));
+}
+
+#[test]
+fn test_eliminate_dead_code_unknown_root() {
+    let program = parse("");
+    assert_eq!(
+        eliminate_dead_code(&program, &["foo".into()]),
+        Err(Box::new(ProgramRegistryError::MissingFunction("foo".into())))
+    );
+}
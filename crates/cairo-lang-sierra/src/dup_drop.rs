@@ -0,0 +1,188 @@
+//! Elimination of redundant `dup`, `drop` and `rename` invocations in Sierra programs.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use itertools::Itertools;
+
+use crate::dead_code::retain_statements;
+use crate::extensions::drop::DropLibfunc;
+use crate::extensions::duplicate::DupLibfunc;
+use crate::extensions::lib_func::SignatureOnlyGenericLibfunc;
+use crate::extensions::mem::RenameLibfunc;
+use crate::ids::VarId;
+use crate::program::{BranchInfo, BranchTarget, Program, Statement};
+
+#[cfg(test)]
+#[path = "dup_drop_test.rs"]
+mod test;
+
+/// Returns a copy of the program, without the `dup`, `drop` and `rename` invocations that cancel
+/// out.
+///
+/// Within each basic block:
+/// * A `dup` one of whose copies is only dropped is removed along with the `drop`.
+/// * A `rename` is removed.
+///
+/// The variable that remained is replaced by the original variable in the statement consuming it,
+/// so a rewrite only applies if that statement is in the same basic block and the original
+/// variable is not redefined before it. The declarations of the libfuncs are kept even if no
+/// longer invoked - see [eliminate_dead_code](crate::dead_code::eliminate_dead_code).
+pub fn eliminate_redundant_dup_drop(program: &Program) -> Program {
+    let generic_ids: HashMap<_, _> = program
+        .libfunc_declarations
+        .iter()
+        .map(|declaration| (&declaration.id, declaration.long_id.generic_id.0.as_str()))
+        .collect();
+    let generic_id = |statement: &Statement| match statement {
+        Statement::Invocation(invocation) => generic_ids.get(&invocation.libfunc_id).copied(),
+        Statement::Return(_) => None,
+    };
+    let keep = vec![true; program.statements.len()];
+    let mut statements = Statements { statements: program.statements.clone(), keep };
+    for block in basic_blocks(program) {
+        for idx in block.clone() {
+            let statement = &statements.statements[idx];
+            let Statement::Invocation(invocation) = statement else {
+                continue;
+            };
+            let ([input], [BranchInfo { target: BranchTarget::Fallthrough, results }]) =
+                (&invocation.args[..], &invocation.branches[..])
+            else {
+                continue;
+            };
+            let rest = idx + 1..block.end;
+            let is_drop =
+                |idx: usize| generic_id(&statements.statements[idx]) == Some(DropLibfunc::STR_ID);
+            let rewrite = match (generic_id(statement), &results[..]) {
+                (Some(DupLibfunc::STR_ID), [first, second]) => [(first, second), (second, first)]
+                    .into_iter()
+                    .find_map(|(dropped, remaining)| {
+                        let drop_idx = statements.find_consumer(rest.clone(), dropped)?;
+                        if !is_drop(drop_idx) {
+                            return None;
+                        }
+                        let replacement =
+                            statements.find_replacement(rest.clone(), input, remaining)?;
+                        Some((Some(drop_idx), replacement))
+                    }),
+                (Some(RenameLibfunc::STR_ID), [output]) => statements
+                    .find_replacement(rest, input, output)
+                    .map(|replacement| (None, replacement)),
+                _ => None,
+            };
+            let Some((drop_idx, replacement)) = rewrite else {
+                continue;
+            };
+            statements.keep[idx] = false;
+            if let Some(drop_idx) = drop_idx {
+                statements.keep[drop_idx] = false;
+            }
+            if let Some((consumer_idx, from, to)) = replacement {
+                replace_arg(&mut statements.statements[consumer_idx], &from, to);
+            }
+        }
+    }
+    let (statements, funcs) =
+        retain_statements(&statements.statements, &program.funcs, &statements.keep);
+    Program {
+        type_declarations: program.type_declarations.clone(),
+        libfunc_declarations: program.libfunc_declarations.clone(),
+        statements,
+        funcs,
+    }
+}
+
+/// A replacement of a variable by another in the arguments of the statement at an index.
+type Replacement = Option<(usize, VarId, VarId)>;
+
+/// The statements of a program, along with whether each of them is kept.
+struct Statements {
+    statements: Vec<Statement>,
+    keep: Vec<bool>,
+}
+impl Statements {
+    /// Returns the replacement of `remaining` by `input` required for removing an invocation
+    /// consuming `input` and outputting `remaining`, where `range` is the rest of the basic block.
+    ///
+    /// Returns `Some(None)` if no replacement is required, and `None` if the invocation can't be
+    /// removed.
+    fn find_replacement(
+        &self,
+        range: Range<usize>,
+        input: &VarId,
+        remaining: &VarId,
+    ) -> Option<Replacement> {
+        if input == remaining {
+            return Some(None);
+        }
+        let consumer_idx = self.find_consumer(range.clone(), remaining)?;
+        let redefined = (range.start..consumer_idx).any(|idx| {
+            self.keep[idx]
+                && matches!(&self.statements[idx], Statement::Invocation(invocation)
+                    if invocation.branches.iter().any(|branch| branch.results.contains(input)))
+        });
+        if redefined {
+            return None;
+        }
+        Some(Some((consumer_idx, remaining.clone(), input.clone())))
+    }
+
+    /// Returns the index of the first kept statement in `range` consuming `var`.
+    fn find_consumer(&self, mut range: Range<usize>, var: &VarId) -> Option<usize> {
+        range.find(|idx| self.keep[*idx] && args(&self.statements[*idx]).contains(var))
+    }
+}
+
+/// Returns the variables consumed by a statement.
+fn args(statement: &Statement) -> &[VarId] {
+    match statement {
+        Statement::Invocation(invocation) => &invocation.args,
+        Statement::Return(vars) => vars,
+    }
+}
+
+/// Replaces `from` by `to` in the variables consumed by a statement.
+fn replace_arg(statement: &mut Statement, from: &VarId, to: VarId) {
+    let args = match statement {
+        Statement::Invocation(invocation) => &mut invocation.args,
+        Statement::Return(vars) => vars,
+    };
+    for arg in args.iter_mut().filter(|arg| *arg == from) {
+        *arg = to.clone();
+    }
+}
+
+/// Returns the ranges of the basic blocks of the program - maximal sequences of statements that
+/// are only entered at their first statement, and only continue to the next statement.
+fn basic_blocks(program: &Program) -> Vec<Range<usize>> {
+    let n_statements = program.statements.len();
+    let mut is_block_start = vec![false; n_statements + 1];
+    for func in &program.funcs {
+        is_block_start[func.entry_point.0] = true;
+    }
+    for (idx, statement) in program.statements.iter().enumerate() {
+        match statement {
+            Statement::Invocation(invocation)
+                if invocation.branches.len() == 1
+                    && invocation.branches[0].target == BranchTarget::Fallthrough => {}
+            Statement::Invocation(invocation) => {
+                is_block_start[idx + 1] = true;
+                for branch in &invocation.branches {
+                    if let BranchTarget::Statement(target) = branch.target {
+                        is_block_start[target.0] = true;
+                    }
+                }
+            }
+            Statement::Return(_) => is_block_start[idx + 1] = true,
+        }
+    }
+    is_block_start[0] = true;
+    is_block_start[n_statements] = true;
+    is_block_start
+        .iter()
+        .positions(|is_start| *is_start)
+        .tuple_windows()
+        .map(|(start, end)| start..end)
+        .collect()
+}
@@ -0,0 +1,183 @@
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+use test_case::test_case;
+
+use super::eliminate_redundant_dup_drop;
+use crate::program::Program;
+use crate::ProgramParser;
+
+/// The declarations used by the tested programs.
+const DECLARATIONS: &str = indoc! {"
+    type felt252 = felt252;
+    type NonZeroFelt252 = NonZero<felt252>;
+
+    libfunc branch_align = branch_align;
+    libfunc felt252_add = felt252_add;
+    libfunc felt252_is_zero = felt252_is_zero;
+    libfunc felt252_dup = dup<felt252>;
+    libfunc felt252_drop = drop<felt252>;
+    libfunc felt252_rename = rename<felt252>;
+    libfunc drop_nz_felt252 = drop<NonZeroFelt252>;
+    libfunc store_temp_felt252 = store_temp<felt252>;
+    libfunc jump = jump;
+"};
+
+/// Parses a program with [DECLARATIONS].
+fn parse(code: &str) -> Program {
+    ProgramParser::new().parse(&format!("{DECLARATIONS}\n{code}")).unwrap()
+}
+
+#[test_case(indoc! {"
+                felt252_dup(a) -> (a, b);
+                felt252_drop(b) -> ();
+                store_temp_felt252(a) -> (a);
+                return (a);
+
+                foo@0(a: felt252) -> (felt252);
+            "},
+            indoc! {"
+                store_temp_felt252(a) -> (a);
+                return (a);
+
+                foo@0(a: felt252) -> (felt252);
+            "};
+            "dropped second copy")]
+#[test_case(indoc! {"
+                felt252_dup(a) -> (a, b);
+                felt252_drop(a) -> ();
+                store_temp_felt252(b) -> (b);
+                return (b);
+
+                foo@0(a: felt252) -> (felt252);
+            "},
+            indoc! {"
+                store_temp_felt252(a) -> (b);
+                return (b);
+
+                foo@0(a: felt252) -> (felt252);
+            "};
+            "dropped first copy")]
+#[test_case(indoc! {"
+                felt252_dup(a) -> (a, b);
+                felt252_add(a, b) -> (c);
+                store_temp_felt252(c) -> (c);
+                return (c);
+
+                foo@0(a: felt252) -> (felt252);
+            "},
+            indoc! {"
+                felt252_dup(a) -> (a, b);
+                felt252_add(a, b) -> (c);
+                store_temp_felt252(c) -> (c);
+                return (c);
+
+                foo@0(a: felt252) -> (felt252);
+            "};
+            "used copies")]
+#[test_case(indoc! {"
+                felt252_rename(a) -> (b);
+                store_temp_felt252(b) -> (b);
+                return (b);
+
+                foo@0(a: felt252) -> (felt252);
+            "},
+            indoc! {"
+                store_temp_felt252(a) -> (b);
+                return (b);
+
+                foo@0(a: felt252) -> (felt252);
+            "};
+            "rename")]
+#[test_case(indoc! {"
+                felt252_rename(a) -> (b);
+                jump() { 2() };
+                store_temp_felt252(b) -> (b);
+                return (b);
+
+                foo@0(a: felt252) -> (felt252);
+            "},
+            indoc! {"
+                felt252_rename(a) -> (b);
+                jump() { 2() };
+                store_temp_felt252(b) -> (b);
+                return (b);
+
+                foo@0(a: felt252) -> (felt252);
+            "};
+            "rename consumed in another block")]
+#[test_case(indoc! {"
+                felt252_rename(a) -> (b);
+                felt252_dup(c) -> (c, a);
+                felt252_add(a, c) -> (c);
+                felt252_add(b, c) -> (c);
+                store_temp_felt252(c) -> (c);
+                return (c);
+
+                foo@0(a: felt252, c: felt252) -> (felt252);
+            "},
+            indoc! {"
+                felt252_rename(a) -> (b);
+                felt252_dup(c) -> (c, a);
+                felt252_add(a, c) -> (c);
+                felt252_add(b, c) -> (c);
+                store_temp_felt252(c) -> (c);
+                return (c);
+
+                foo@0(a: felt252, c: felt252) -> (felt252);
+            "};
+            "rename of a redefined variable")]
+#[test_case(indoc! {"
+                felt252_dup(a) -> (a, b);
+                felt252_is_zero(a) { fallthrough() 5(a) };
+                branch_align() -> ();
+                felt252_drop(b) -> ();
+                return ();
+                branch_align() -> ();
+                drop_nz_felt252(a) -> ();
+                felt252_drop(b) -> ();
+                return ();
+
+                foo@0(a: felt252) -> ();
+            "},
+            indoc! {"
+                felt252_dup(a) -> (a, b);
+                felt252_is_zero(a) { fallthrough() 5(a) };
+                branch_align() -> ();
+                felt252_drop(b) -> ();
+                return ();
+                branch_align() -> ();
+                drop_nz_felt252(a) -> ();
+                felt252_drop(b) -> ();
+                return ();
+
+                foo@0(a: felt252) -> ();
+            "};
+            "dropped in other blocks")]
+#[test_case(indoc! {"
+                felt252_dup(a) -> (a, b);
+                felt252_drop(b) -> ();
+                felt252_is_zero(a) { fallthrough() 5(a) };
+                branch_align() -> ();
+                return ();
+                branch_align() -> ();
+                drop_nz_felt252(a) -> ();
+                return ();
+
+                foo@0(a: felt252) -> ();
+                bar@5(a: NonZeroFelt252) -> ();
+            "},
+            indoc! {"
+                felt252_is_zero(a) { fallthrough() 3(a) };
+                branch_align() -> ();
+                return ();
+                branch_align() -> ();
+                drop_nz_felt252(a) -> ();
+                return ();
+
+                foo@0(a: felt252) -> ();
+                bar@3(a: NonZeroFelt252) -> ();
+            "};
+            "relocated statements")]
+fn test_eliminate_redundant_dup_drop(code: &str, expected: &str) {
+    assert_eq!(eliminate_redundant_dup_drop(&parse(code)), parse(expected));
+}
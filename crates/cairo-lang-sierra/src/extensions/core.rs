@@ -5,6 +5,8 @@ use super::boolean::BoolLibfunc;
 use super::branch_align::BranchAlignLibfunc;
 use super::bytes31::{Bytes31Libfunc, Bytes31Type};
 use super::casts::CastLibfunc;
+use super::const_type::{ConstLibfunc, ConstType};
+use super::custom::CustomLibfunc;
 use super::debug::DebugLibfunc;
 use super::drop::DropLibfunc;
 use super::duplicate::DupLibfunc;
@@ -51,6 +53,7 @@ define_type_hierarchy! {
         Array(ArrayType),
         Bitwise(BitwiseType),
         Box(BoxType),
+        Const(ConstType),
         EcOp(EcOpType),
         EcPoint(EcPointType),
         EcState(EcStateType),
@@ -95,6 +98,7 @@ define_libfunc_hierarchy! {
         Bool(BoolLibfunc),
         Box(BoxLibfunc),
         Cast(CastLibfunc),
+        Const(ConstLibfunc),
         Drop(DropLibfunc),
         Dup(DupLibfunc),
         Ec(EcLibfunc),
@@ -127,5 +131,7 @@ define_libfunc_hierarchy! {
         Debug(DebugLibfunc),
         SnapshotTake(SnapshotTakeLibfunc),
         Bytes31(Bytes31Libfunc),
+        // Must be last, as it accepts any id not matched by the core libfuncs.
+        Custom(CustomLibfunc),
     }, CoreConcreteLibfunc
 }
@@ -1,6 +1,9 @@
+use std::sync::Arc;
+
 use itertools::Itertools;
 
 use super::args_as_single_type;
+use super::custom::LibfuncExtension;
 use super::error::{ExtensionError, SpecializationError};
 use super::type_specialization_context::TypeSpecializationContext;
 use crate::ids::{ConcreteTypeId, FunctionId, GenericLibfuncId, GenericTypeId};
@@ -58,6 +61,23 @@ pub trait SignatureSpecializationContext: TypeSpecializationContext {
         self.get_concrete_type(id, &[GenericArg::Type(wrapped)])
     }
 
+    /// Returns the extension implementing the libfunc with the given id, if one was provided.
+    fn try_get_libfunc_extension(
+        &self,
+        _id: &GenericLibfuncId,
+    ) -> Option<Arc<dyn LibfuncExtension>> {
+        None
+    }
+
+    /// Wraps [Self::try_get_libfunc_extension] with a result object.
+    fn get_libfunc_extension(
+        &self,
+        id: &GenericLibfuncId,
+    ) -> Result<Arc<dyn LibfuncExtension>, SpecializationError> {
+        self.try_get_libfunc_extension(id)
+            .ok_or_else(|| SpecializationError::UnsupportedId(id.0.clone()))
+    }
+
     /// Upcasting to the [TypeSpecializationContext], since trait upcasting is still experimental.
     fn as_type_specialization_context(&self) -> &dyn TypeSpecializationContext;
 }
@@ -1,3 +1,4 @@
+use super::gas::GasBuiltinType;
 use super::range_check::RangeCheckType;
 use super::snapshot::snapshot_ty;
 use super::starknet::getter::boxed_ty;
@@ -11,7 +12,7 @@ use crate::extensions::types::{
     GenericTypeArgGenericType, GenericTypeArgGenericTypeWrapper, TypeInfo,
 };
 use crate::extensions::{
-    args_as_single_type, NamedType, OutputVarReferenceInfo, SpecializationError,
+    NamedType, OutputVarReferenceInfo, SpecializationError, args_as_single_type,
 };
 use crate::ids::{ConcreteTypeId, GenericTypeId};
 use crate::program::GenericArg;
@@ -55,6 +56,10 @@ define_libfunc_hierarchy! {
         Len(ArrayLenLibfunc),
         SnapshotPopFront(ArraySnapshotPopFrontLibfunc),
         SnapshotPopBack(ArraySnapshotPopBackLibfunc),
+        SnapshotMultiPopFront(ArraySnapshotMultiPopFrontLibfunc),
+        SnapshotMultiPopBack(ArraySnapshotMultiPopBackLibfunc),
+        AppendSpan(ArrayAppendSpanLibfunc),
+        Concat(ArrayConcatLibfunc),
     }, ArrayConcreteLibfunc
 }
 
@@ -407,3 +412,217 @@ impl SignatureAndTypeGenericLibfunc for ArraySnapshotPopBackLibfuncWrapped {
 }
 pub type ArraySnapshotPopBackLibfunc =
     WrapSignatureAndTypeGenericLibfunc<ArraySnapshotPopBackLibfuncWrapped>;
+
+/// Returns the signature of the libfuncs popping multiple values from an array snapshot.
+fn array_snapshot_multi_pop_signature(
+    context: &dyn SignatureSpecializationContext,
+    ty: ConcreteTypeId,
+) -> Result<LibfuncSignature, SpecializationError> {
+    let arr_snapshot_ty =
+        snapshot_ty(context, context.get_wrapped_concrete_type(ArrayType::id(), ty)?)?;
+    let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+    let index_type = context.get_concrete_type(ArrayIndexType::id(), &[])?;
+    let rc_output_info = OutputVarInfo::new_builtin(range_check_type.clone(), 0);
+    Ok(LibfuncSignature {
+        param_signatures: vec![
+            ParamSignature::new(range_check_type).with_allow_add_const(),
+            ParamSignature::new(arr_snapshot_ty.clone()),
+            // Count
+            ParamSignature::new(index_type),
+        ],
+        branch_signatures: vec![
+            // Success.
+            BranchSignature {
+                vars: vec![
+                    rc_output_info.clone(),
+                    // The remaining array snapshot.
+                    OutputVarInfo {
+                        ty: arr_snapshot_ty.clone(),
+                        ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                    },
+                    // The popped values, as an array snapshot.
+                    OutputVarInfo {
+                        ty: arr_snapshot_ty.clone(),
+                        ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Generic),
+                    },
+                ],
+                ap_change: SierraApChange::Known { new_vars_only: false },
+            },
+            // Failure - the array snapshot has less than `count` values.
+            BranchSignature {
+                vars: vec![
+                    rc_output_info,
+                    OutputVarInfo {
+                        ty: arr_snapshot_ty,
+                        ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 1 },
+                    },
+                ],
+                ap_change: SierraApChange::Known { new_vars_only: false },
+            },
+        ],
+        fallthrough: Some(0),
+    })
+}
+
+/// Libfunc for popping multiple values from the beginning of an array snapshot.
+#[derive(Default)]
+pub struct ArraySnapshotMultiPopFrontLibfuncWrapped {}
+impl SignatureAndTypeGenericLibfunc for ArraySnapshotMultiPopFrontLibfuncWrapped {
+    const STR_ID: &'static str = "array_snapshot_multi_pop_front";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        ty: ConcreteTypeId,
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        array_snapshot_multi_pop_signature(context, ty)
+    }
+}
+pub type ArraySnapshotMultiPopFrontLibfunc =
+    WrapSignatureAndTypeGenericLibfunc<ArraySnapshotMultiPopFrontLibfuncWrapped>;
+
+/// Libfunc for popping multiple values from the end of an array snapshot.
+#[derive(Default)]
+pub struct ArraySnapshotMultiPopBackLibfuncWrapped {}
+impl SignatureAndTypeGenericLibfunc for ArraySnapshotMultiPopBackLibfuncWrapped {
+    const STR_ID: &'static str = "array_snapshot_multi_pop_back";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        ty: ConcreteTypeId,
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        array_snapshot_multi_pop_signature(context, ty)
+    }
+}
+pub type ArraySnapshotMultiPopBackLibfunc =
+    WrapSignatureAndTypeGenericLibfunc<ArraySnapshotMultiPopBackLibfuncWrapped>;
+
+/// Libfunc for appending the elements of an array snapshot to the end of an array, copying them in
+/// a loop. The copy cost per element is withdrawn from the gas counter, and the failure branch is
+/// taken if there is not enough gas. Only supports duplicatable element types, as the elements are
+/// copied as is.
+#[derive(Default)]
+pub struct ArrayAppendSpanLibfuncWrapped {}
+impl SignatureAndTypeGenericLibfunc for ArrayAppendSpanLibfuncWrapped {
+    const STR_ID: &'static str = "array_append_span";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        ty: ConcreteTypeId,
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        if !context.get_type_info(ty.clone())?.duplicatable {
+            return Err(SpecializationError::UnsupportedGenericArg);
+        }
+        let arr_ty = context.get_wrapped_concrete_type(ArrayType::id(), ty)?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let gas_builtin_type = context.get_concrete_type(GasBuiltinType::id(), &[])?;
+        let param_signatures = vec![
+            ParamSignature::new(range_check_type.clone()).with_allow_add_const(),
+            ParamSignature::new(gas_builtin_type.clone()),
+            ParamSignature::new(arr_ty.clone()),
+            ParamSignature::new(snapshot_ty(context, arr_ty.clone())?),
+        ];
+        let branch_signatures = vec![
+            // Success.
+            BranchSignature {
+                vars: vec![
+                    OutputVarInfo {
+                        ty: range_check_type.clone(),
+                        ref_info: OutputVarReferenceInfo::NewTempVar { idx: 0 },
+                    },
+                    OutputVarInfo {
+                        ty: gas_builtin_type.clone(),
+                        ref_info: OutputVarReferenceInfo::NewTempVar { idx: 1 },
+                    },
+                    OutputVarInfo {
+                        ty: arr_ty.clone(),
+                        ref_info: OutputVarReferenceInfo::NewTempVar { idx: 2 },
+                    },
+                ],
+                ap_change: SierraApChange::Unknown,
+            },
+            // Failure - not enough gas, returns the array as is.
+            BranchSignature {
+                vars: vec![
+                    OutputVarInfo::new_builtin(range_check_type, 0),
+                    OutputVarInfo {
+                        ty: gas_builtin_type,
+                        ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 1 },
+                    },
+                    OutputVarInfo {
+                        ty: arr_ty,
+                        ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 2 },
+                    },
+                ],
+                ap_change: SierraApChange::Unknown,
+            },
+        ];
+        Ok(LibfuncSignature { param_signatures, branch_signatures, fallthrough: Some(0) })
+    }
+}
+pub type ArrayAppendSpanLibfunc = WrapSignatureAndTypeGenericLibfunc<ArrayAppendSpanLibfuncWrapped>;
+
+/// Libfunc for creating a new array from the elements of two array snapshots, copying them in a
+/// loop. The copy cost per element is withdrawn from the gas counter, and the failure branch is
+/// taken if there is not enough gas. Only supports duplicatable element types, as the elements are
+/// copied as is.
+#[derive(Default)]
+pub struct ArrayConcatLibfuncWrapped {}
+impl SignatureAndTypeGenericLibfunc for ArrayConcatLibfuncWrapped {
+    const STR_ID: &'static str = "array_concat";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        ty: ConcreteTypeId,
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        if !context.get_type_info(ty.clone())?.duplicatable {
+            return Err(SpecializationError::UnsupportedGenericArg);
+        }
+        let arr_ty = context.get_wrapped_concrete_type(ArrayType::id(), ty)?;
+        let arr_snapshot_ty = snapshot_ty(context, arr_ty.clone())?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let gas_builtin_type = context.get_concrete_type(GasBuiltinType::id(), &[])?;
+        let param_signatures = vec![
+            ParamSignature::new(range_check_type.clone()).with_allow_add_const(),
+            ParamSignature::new(gas_builtin_type.clone()),
+            ParamSignature::new(arr_snapshot_ty.clone()),
+            ParamSignature::new(arr_snapshot_ty),
+        ];
+        let branch_signatures = vec![
+            // Success.
+            BranchSignature {
+                vars: vec![
+                    OutputVarInfo {
+                        ty: range_check_type.clone(),
+                        ref_info: OutputVarReferenceInfo::NewTempVar { idx: 0 },
+                    },
+                    OutputVarInfo {
+                        ty: gas_builtin_type.clone(),
+                        ref_info: OutputVarReferenceInfo::NewTempVar { idx: 1 },
+                    },
+                    OutputVarInfo {
+                        ty: arr_ty,
+                        ref_info: OutputVarReferenceInfo::NewTempVar { idx: 2 },
+                    },
+                ],
+                ap_change: SierraApChange::Unknown,
+            },
+            // Failure - not enough gas.
+            BranchSignature {
+                vars: vec![
+                    OutputVarInfo::new_builtin(range_check_type, 0),
+                    OutputVarInfo {
+                        ty: gas_builtin_type,
+                        ref_info: OutputVarReferenceInfo::SameAsParam { param_idx: 1 },
+                    },
+                ],
+                ap_change: SierraApChange::Unknown,
+            },
+        ];
+        Ok(LibfuncSignature { param_signatures, branch_signatures, fallthrough: Some(0) })
+    }
+}
+pub type ArrayConcatLibfunc = WrapSignatureAndTypeGenericLibfunc<ArrayConcatLibfuncWrapped>;
@@ -0,0 +1,315 @@
+//! Sierra example:
+//! ```ignore
+//! type u128 = u128;
+//! type u256 = Struct<ut@core::integer::u256, u128, u128>;
+//! type Box<u256> = Box<u256>;
+//! type Const<u128, 5> = Const<u128, 5>;
+//! type Const<u128, 0> = Const<u128, 0>;
+//! type Const<u256, 5> = Const<u256, Const<u128, 5>, Const<u128, 0>>;
+//! libfunc const_as_immediate<Const<u256, 5>> = const_as_immediate<Const<u256, 5>>;
+//! libfunc const_as_box<Const<u256, 5>> = const_as_box<Const<u256, 5>>;
+//! ...
+//! const_as_immediate<Const<u256, 5>>() -> (value);
+//! const_as_box<Const<u256, 5>>() -> (boxed);
+//! ```
+
+use cairo_lang_utils::try_extract_matches;
+use num_traits::Signed;
+
+use super::boxing::BoxType;
+use super::bytes31::Bytes31Type;
+use super::enm::EnumType;
+use super::felt252::Felt252Type;
+use super::int::signed::{Sint16Type, Sint32Type, Sint64Type, Sint8Type};
+use super::int::signed128::Sint128Type;
+use super::int::unsigned::{Uint16Type, Uint32Type, Uint64Type, Uint8Type};
+use super::int::unsigned128::Uint128Type;
+use super::structure::StructType;
+use crate::define_libfunc_hierarchy;
+use crate::extensions::lib_func::{
+    DeferredOutputKind, LibfuncSignature, OutputVarInfo, SierraApChange,
+    SignatureSpecializationContext, SpecializationContext,
+};
+use crate::extensions::type_specialization_context::TypeSpecializationContext;
+use crate::extensions::types::TypeInfo;
+use crate::extensions::{
+    args_as_single_type, ConcreteType, NamedLibfunc, NamedType, OutputVarReferenceInfo,
+    SignatureBasedConcreteLibfunc, SpecializationError,
+};
+use crate::ids::{ConcreteTypeId, GenericTypeId};
+use crate::program::{ConcreteTypeLongId, GenericArg};
+
+/// Type representing a constant value of a type, known at compile time.
+///
+/// The first generic argument is the type of the value, and the rest are its data:
+/// * For felt252, bytes31 and integer types - the value itself.
+/// * For structs - a `Const` type per member, in order.
+/// * For enums - the index of the variant, and a `Const` type of the variant.
+#[derive(Default)]
+pub struct ConstType {}
+impl NamedType for ConstType {
+    type Concrete = ConstConcreteType;
+    const ID: GenericTypeId = GenericTypeId::new_inline("Const");
+
+    fn specialize(
+        &self,
+        context: &dyn TypeSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let (inner_ty, inner_data) = match args {
+            [GenericArg::Type(inner_ty), inner_data @ ..] => (inner_ty, inner_data),
+            _ => return Err(SpecializationError::UnsupportedGenericArg),
+        };
+        validate_const_data(context, inner_ty, inner_data)?;
+        Ok(ConstConcreteType {
+            info: TypeInfo {
+                long_id: ConcreteTypeLongId {
+                    generic_id: <Self as NamedType>::ID,
+                    generic_args: args.to_vec(),
+                },
+                storable: false,
+                droppable: false,
+                duplicatable: false,
+                zero_sized: false,
+            },
+            inner_ty: inner_ty.clone(),
+            inner_data: inner_data.to_vec(),
+        })
+    }
+}
+
+pub struct ConstConcreteType {
+    pub info: TypeInfo,
+    /// The type of the constant value.
+    pub inner_ty: ConcreteTypeId,
+    /// The data of the constant value, as described in [ConstType].
+    pub inner_data: Vec<GenericArg>,
+}
+impl ConcreteType for ConstConcreteType {
+    fn info(&self) -> &TypeInfo {
+        &self.info
+    }
+}
+
+/// Validates that `inner_data` is valid data for a constant value of type `inner_ty`.
+fn validate_const_data(
+    context: &dyn TypeSpecializationContext,
+    inner_ty: &ConcreteTypeId,
+    inner_data: &[GenericArg],
+) -> Result<(), SpecializationError> {
+    let inner_type_info = context.get_type_info(inner_ty.clone())?;
+    let generic_id = inner_type_info.long_id.generic_id;
+    if generic_id == StructType::ID {
+        return validate_const_struct_data(
+            context,
+            &inner_type_info.long_id.generic_args,
+            inner_data,
+        );
+    }
+    if generic_id == EnumType::ID {
+        return validate_const_enum_data(
+            context,
+            &inner_type_info.long_id.generic_args,
+            inner_data,
+        );
+    }
+    let [GenericArg::Value(value)] = inner_data else {
+        return Err(SpecializationError::WrongNumberOfGenericArgs);
+    };
+    let is_valid = if generic_id == Felt252Type::ID {
+        true
+    } else if generic_id == Bytes31Type::ID {
+        !value.is_negative() && value.bits() <= 248
+    } else if generic_id == Uint8Type::ID {
+        u8::try_from(value).is_ok()
+    } else if generic_id == Uint16Type::ID {
+        u16::try_from(value).is_ok()
+    } else if generic_id == Uint32Type::ID {
+        u32::try_from(value).is_ok()
+    } else if generic_id == Uint64Type::ID {
+        u64::try_from(value).is_ok()
+    } else if generic_id == Uint128Type::ID {
+        u128::try_from(value).is_ok()
+    } else if generic_id == Sint8Type::ID {
+        i8::try_from(value).is_ok()
+    } else if generic_id == Sint16Type::ID {
+        i16::try_from(value).is_ok()
+    } else if generic_id == Sint32Type::ID {
+        i32::try_from(value).is_ok()
+    } else if generic_id == Sint64Type::ID {
+        i64::try_from(value).is_ok()
+    } else if generic_id == Sint128Type::ID {
+        i128::try_from(value).is_ok()
+    } else {
+        return Err(SpecializationError::UnsupportedGenericArg);
+    };
+    if is_valid { Ok(()) } else { Err(SpecializationError::UnsupportedGenericArg) }
+}
+
+/// Validates that `inner_data` is a `Const` type per member of the struct with the given generic
+/// args.
+fn validate_const_struct_data(
+    context: &dyn TypeSpecializationContext,
+    struct_generic_args: &[GenericArg],
+    inner_data: &[GenericArg],
+) -> Result<(), SpecializationError> {
+    // The first generic arg of a struct is its user type id.
+    let members = &struct_generic_args[1..];
+    if members.len() != inner_data.len() {
+        return Err(SpecializationError::WrongNumberOfGenericArgs);
+    }
+    for (member, member_data) in members.iter().zip(inner_data) {
+        let member_ty = try_extract_matches!(member, GenericArg::Type)
+            .ok_or(SpecializationError::UnsupportedGenericArg)?;
+        validate_const_of_type(context, member_ty, member_data)?;
+    }
+    Ok(())
+}
+
+/// Validates that `inner_data` is a variant index and a `Const` type of that variant, of the enum
+/// with the given generic args.
+fn validate_const_enum_data(
+    context: &dyn TypeSpecializationContext,
+    enum_generic_args: &[GenericArg],
+    inner_data: &[GenericArg],
+) -> Result<(), SpecializationError> {
+    let [GenericArg::Value(variant_index), variant_data] = inner_data else {
+        return Err(SpecializationError::WrongNumberOfGenericArgs);
+    };
+    // The first generic arg of an enum is its user type id.
+    let variants = &enum_generic_args[1..];
+    let variant = usize::try_from(variant_index)
+        .ok()
+        .and_then(|index| variants.get(index))
+        .and_then(|variant| try_extract_matches!(variant, GenericArg::Type))
+        .ok_or(SpecializationError::IndexOutOfRange {
+            index: variant_index.clone(),
+            range_size: variants.len(),
+        })?;
+    validate_const_of_type(context, variant, variant_data)
+}
+
+/// Validates that `arg` is a `Const` type of a value of type `ty`.
+fn validate_const_of_type(
+    context: &dyn TypeSpecializationContext,
+    ty: &ConcreteTypeId,
+    arg: &GenericArg,
+) -> Result<(), SpecializationError> {
+    let const_ty = try_extract_matches!(arg, GenericArg::Type)
+        .ok_or(SpecializationError::UnsupportedGenericArg)?;
+    let long_id = context.get_type_info(const_ty.clone())?.long_id;
+    if long_id.generic_id != ConstType::ID
+        || long_id.generic_args.first() != Some(&GenericArg::Type(ty.clone()))
+    {
+        return Err(SpecializationError::UnsupportedGenericArg);
+    }
+    Ok(())
+}
+
+/// Returns the type of the value of the given `Const` type.
+fn const_inner_type(
+    context: &dyn SignatureSpecializationContext,
+    const_type: &ConcreteTypeId,
+) -> Result<ConcreteTypeId, SpecializationError> {
+    let long_id = context.get_type_info(const_type.clone())?.long_id;
+    if long_id.generic_id != ConstType::ID {
+        return Err(SpecializationError::UnsupportedGenericArg);
+    }
+    long_id
+        .generic_args
+        .first()
+        .and_then(|arg| try_extract_matches!(arg, GenericArg::Type))
+        .cloned()
+        .ok_or(SpecializationError::UnsupportedGenericArg)
+}
+
+define_libfunc_hierarchy! {
+    pub enum ConstLibfunc {
+        AsBox(ConstAsBoxLibfunc),
+        AsImmediate(ConstAsImmediateLibfunc),
+    }, ConstConcreteLibfunc
+}
+
+/// A concrete const libfunc, with the `Const` type it materializes.
+pub struct ConstAsConcreteLibfunc {
+    pub const_type: ConcreteTypeId,
+    pub signature: LibfuncSignature,
+}
+impl SignatureBasedConcreteLibfunc for ConstAsConcreteLibfunc {
+    fn signature(&self) -> &LibfuncSignature {
+        &self.signature
+    }
+}
+
+/// Libfunc for getting a constant value as a chain of immediate values.
+#[derive(Default)]
+pub struct ConstAsImmediateLibfunc {}
+impl NamedLibfunc for ConstAsImmediateLibfunc {
+    type Concrete = ConstAsConcreteLibfunc;
+    const STR_ID: &'static str = "const_as_immediate";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        let const_type = args_as_single_type(args)?;
+        Ok(LibfuncSignature::new_non_branch(
+            vec![],
+            vec![OutputVarInfo {
+                ty: const_inner_type(context, &const_type)?,
+                ref_info: OutputVarReferenceInfo::Deferred(DeferredOutputKind::Const),
+            }],
+            SierraApChange::Known { new_vars_only: true },
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(ConstAsConcreteLibfunc {
+            const_type: args_as_single_type(args)?,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
+
+/// Libfunc for getting a boxed constant value, pointing into the constants segment of the
+/// program.
+#[derive(Default)]
+pub struct ConstAsBoxLibfunc {}
+impl NamedLibfunc for ConstAsBoxLibfunc {
+    type Concrete = ConstAsConcreteLibfunc;
+    const STR_ID: &'static str = "const_as_box";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        let const_type = args_as_single_type(args)?;
+        let inner_ty = const_inner_type(context, &const_type)?;
+        Ok(LibfuncSignature::new_non_branch(
+            vec![],
+            vec![OutputVarInfo {
+                ty: context.get_wrapped_concrete_type(BoxType::id(), inner_ty)?,
+                ref_info: OutputVarReferenceInfo::NewTempVar { idx: 0 },
+            }],
+            SierraApChange::Known { new_vars_only: false },
+        ))
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        Ok(ConstAsConcreteLibfunc {
+            const_type: args_as_single_type(args)?,
+            signature: self.specialize_signature(context.upcast(), args)?,
+        })
+    }
+}
+
@@ -0,0 +1,148 @@
+//! Libfuncs defined outside of the core library, provided by downstream crates.
+//!
+//! A custom libfunc is registered in a [LibfuncExtensions] registry under a generic libfunc id.
+//! A program registry created with the extensions (see
+//! [crate::program_registry::ProgramRegistry::new_with_extensions]) then supports it just like any
+//! core libfunc. Compiling the libfunc to CASM requires providing its lowering as well - see
+//! `cairo_lang_sierra_to_casm::invocations::custom`.
+
+use std::sync::Arc;
+
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use thiserror::Error;
+
+use crate::extensions::core::CoreLibfunc;
+use crate::extensions::lib_func::{
+    LibfuncSignature, SignatureSpecializationContext, SpecializationContext,
+};
+use crate::extensions::{GenericLibfunc, SignatureBasedConcreteLibfunc, SpecializationError};
+use crate::ids::GenericLibfuncId;
+use crate::program::GenericArg;
+use crate::simulation::value::CoreValue;
+use crate::simulation::LibfuncSimulationError;
+
+#[cfg(test)]
+#[path = "custom_test.rs"]
+mod test;
+
+/// The constant cost and the ap change of a branch of a custom libfunc.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CustomBranchCost {
+    pub steps: i32,
+    pub holes: i32,
+    pub range_checks: i32,
+    pub ap_change: usize,
+}
+
+/// Trait for implementing a libfunc outside of the core library.
+pub trait LibfuncExtension: Send + Sync {
+    /// Creates the specialization of the libfunc's signature with the template arguments.
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibfuncSignature, SpecializationError>;
+
+    /// Returns the cost and ap change of each of the branches of the specialized libfunc.
+    fn branch_costs(&self, args: &[GenericArg]) -> Vec<CustomBranchCost>;
+
+    /// Simulates a run of the specialized libfunc. Returns the value representations of the
+    /// outputs, and the chosen branch given the inputs.
+    fn simulate(
+        &self,
+        args: &[GenericArg],
+        inputs: Vec<CoreValue>,
+    ) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError>;
+}
+
+/// Error occurring while registering a libfunc extension.
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum LibfuncExtensionError {
+    #[error("Libfunc id `{0}` is already in use.")]
+    IdInUse(GenericLibfuncId),
+}
+
+/// A registry of libfunc extensions, by their generic libfunc ids.
+#[derive(Clone, Default)]
+pub struct LibfuncExtensions {
+    extensions: OrderedHashMap<GenericLibfuncId, Arc<dyn LibfuncExtension>>,
+}
+impl LibfuncExtensions {
+    /// Registers a libfunc extension under the given id.
+    /// Fails if the id is already used by a core libfunc or by another extension.
+    pub fn register(
+        &mut self,
+        id: GenericLibfuncId,
+        extension: Arc<dyn LibfuncExtension>,
+    ) -> Result<(), LibfuncExtensionError> {
+        if !matches!(CoreLibfunc::by_id(&id), Some(CoreLibfunc::Custom(_)))
+            || self.extensions.contains_key(&id)
+        {
+            return Err(LibfuncExtensionError::IdInUse(id));
+        }
+        self.extensions.insert(id, extension);
+        Ok(())
+    }
+
+    /// Returns the extension registered under the given id.
+    pub fn get(&self, id: &GenericLibfuncId) -> Option<Arc<dyn LibfuncExtension>> {
+        self.extensions.get(id).cloned()
+    }
+}
+
+/// A libfunc not defined by the core library, implemented by the [LibfuncExtension] provided by
+/// the specialization context.
+pub struct CustomLibfunc {
+    pub id: GenericLibfuncId,
+}
+impl GenericLibfunc for CustomLibfunc {
+    type Concrete = CustomConcreteLibfunc;
+
+    fn supported_ids() -> Vec<GenericLibfuncId> {
+        // Any id may be provided by an extension, so no id is listed in advance.
+        vec![]
+    }
+
+    fn by_id(id: &GenericLibfuncId) -> Option<Self> {
+        // The matching extension is only looked up on specialization, as it is provided by the
+        // specialization context.
+        Some(Self { id: id.clone() })
+    }
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        context.get_libfunc_extension(&self.id)?.specialize_signature(context, args)
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        let extension = context.get_libfunc_extension(&self.id)?;
+        Ok(CustomConcreteLibfunc {
+            id: self.id.clone(),
+            generic_args: args.to_vec(),
+            signature: extension.specialize_signature(context.upcast(), args)?,
+            branch_costs: extension.branch_costs(args),
+            extension,
+        })
+    }
+}
+
+/// A specialization of a [CustomLibfunc].
+pub struct CustomConcreteLibfunc {
+    pub id: GenericLibfuncId,
+    pub extension: Arc<dyn LibfuncExtension>,
+    pub generic_args: Vec<GenericArg>,
+    pub signature: LibfuncSignature,
+    pub branch_costs: Vec<CustomBranchCost>,
+}
+impl SignatureBasedConcreteLibfunc for CustomConcreteLibfunc {
+    fn signature(&self) -> &LibfuncSignature {
+        &self.signature
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use indoc::indoc;
+use num_bigint::BigInt;
+
+use super::{CustomBranchCost, LibfuncExtension, LibfuncExtensionError, LibfuncExtensions};
+use crate::extensions::felt252::Felt252Type;
+use crate::extensions::lib_func::{
+    LibfuncSignature, OutputVarInfo, SierraApChange, SignatureSpecializationContext,
+};
+use crate::extensions::{NamedType, OutputVarReferenceInfo, SpecializationError};
+use crate::program::GenericArg;
+use crate::simulation::value::CoreValue;
+use crate::simulation::{run, run_with_extensions, LibfuncSimulationError, SimulationError};
+use crate::program_registry::ProgramRegistryError;
+use crate::ProgramParser;
+
+/// A libfunc extension doubling a felt252.
+struct Felt252Double;
+impl LibfuncExtension for Felt252Double {
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        _args: &[GenericArg],
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        let felt252_ty = context.get_concrete_type(Felt252Type::id(), &[])?;
+        Ok(LibfuncSignature::new_non_branch(
+            vec![felt252_ty.clone()],
+            vec![OutputVarInfo {
+                ty: felt252_ty,
+                ref_info: OutputVarReferenceInfo::NewTempVar { idx: 0 },
+            }],
+            SierraApChange::Known { new_vars_only: false },
+        ))
+    }
+
+    fn branch_costs(&self, _args: &[GenericArg]) -> Vec<CustomBranchCost> {
+        vec![CustomBranchCost { steps: 1, ap_change: 1, ..Default::default() }]
+    }
+
+    fn simulate(
+        &self,
+        _args: &[GenericArg],
+        inputs: Vec<CoreValue>,
+    ) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+        match &inputs[..] {
+            [CoreValue::Felt252(value)] => Ok((vec![CoreValue::Felt252(value * 2)], 0)),
+            [_] => Err(LibfuncSimulationError::WrongArgType),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        }
+    }
+}
+
+#[test]
+fn test_register_libfunc_extension() {
+    let mut extensions = LibfuncExtensions::default();
+    extensions.register("test_felt252_double".into(), Arc::new(Felt252Double)).unwrap();
+    assert_eq!(
+        extensions.register("test_felt252_double".into(), Arc::new(Felt252Double)),
+        Err(LibfuncExtensionError::IdInUse("test_felt252_double".into()))
+    );
+    assert_eq!(
+        extensions.register("felt252_add".into(), Arc::new(Felt252Double)),
+        Err(LibfuncExtensionError::IdInUse("felt252_add".into()))
+    );
+}
+
+#[test]
+fn test_run_libfunc_extension() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+
+            libfunc felt252_double = test_felt252_double;
+
+            felt252_double(a) -> (a);
+            return (a);
+
+            double@0(a: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let mut extensions = LibfuncExtensions::default();
+    extensions.register("test_felt252_double".into(), Arc::new(Felt252Double)).unwrap();
+    assert_eq!(
+        run_with_extensions(
+            &program,
+            &HashMap::new(),
+            &"double".into(),
+            vec![CoreValue::Felt252(BigInt::from(5))],
+            &extensions,
+        ),
+        Ok(vec![CoreValue::Felt252(BigInt::from(10))])
+    );
+    // Without the extension, the libfunc is unsupported.
+    assert!(matches!(
+        run(&program, &HashMap::new(), &"double".into(), vec![CoreValue::Felt252(BigInt::from(5))]),
+        Err(SimulationError::ProgramRegistryError(error))
+            if matches!(*error, ProgramRegistryError::LibfuncSpecialization { .. })
+    ));
+}
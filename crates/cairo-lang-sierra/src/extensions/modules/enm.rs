@@ -14,11 +14,21 @@
 //! match_option(some_id) {1000(some), 2000(none)};
 //! match_option(none_id) {1000(some), 2000(none)};
 //! ```
+//!
+//! The variants of an enum whose variants have no data (a C-like enum) may be converted to and from
+//! felt252 discriminants, given as the generic args following the enum type:
+//! ```ignore
+//! type Status = Enum<ut@Status, unit_ty, unit_ty>;
+//! libfunc status_to_felt252 = enum_to_felt252<Status, 200, 404>;
+//! libfunc status_try_from_felt252 = enum_try_from_felt252<Status, 200, 404>;
+//! ```
 
 use cairo_lang_utils::try_extract_matches;
-use num_bigint::ToBigInt;
+use itertools::Itertools;
+use num_bigint::{BigInt, ToBigInt};
 use num_traits::Signed;
 
+use super::felt252::Felt252Type;
 use super::snapshot::snapshot_ty;
 use crate::define_libfunc_hierarchy;
 use crate::extensions::lib_func::{
@@ -110,6 +120,8 @@ define_libfunc_hierarchy! {
         Init(EnumInitLibfunc),
         Match(EnumMatchLibfunc),
         SnapshotMatch(EnumSnapshotMatchLibfunc),
+        ToFelt252(EnumToFelt252Libfunc),
+        TryFromFelt252(EnumTryFromFelt252Libfunc),
     }, EnumConcreteLibfunc
 }
 
@@ -265,3 +277,159 @@ impl SignatureOnlyGenericLibfunc for EnumSnapshotMatchLibfunc {
         })
     }
 }
+
+/// Returns the enum type and the discriminants of its variants from the generic args of a libfunc
+/// converting between a C-like enum and felt252 - `<EnumType, discriminant0, discriminant1, ...>`.
+fn enum_discriminants_args(
+    context: &dyn SignatureSpecializationContext,
+    args: &[GenericArg],
+) -> Result<(ConcreteTypeId, Vec<BigInt>), SpecializationError> {
+    let (enum_type, discriminant_args) = match args {
+        [GenericArg::Type(enum_type), discriminant_args @ ..] => {
+            (enum_type.clone(), discriminant_args)
+        }
+        [_, ..] => return Err(SpecializationError::UnsupportedGenericArg),
+        [] => return Err(SpecializationError::WrongNumberOfGenericArgs),
+    };
+    let generic_args = context.get_type_info(enum_type.clone())?.long_id.generic_args;
+    let variant_types =
+        EnumConcreteType::new(context.as_type_specialization_context(), &generic_args)?.variants;
+    if variant_types.len() != discriminant_args.len() {
+        return Err(SpecializationError::WrongNumberOfGenericArgs);
+    }
+    // Only enums whose variants have no data have a conversion, and an empty enum has no values.
+    if variant_types.is_empty() {
+        return Err(SpecializationError::UnsupportedGenericArg);
+    }
+    for ty in variant_types {
+        if !context.get_type_info(ty)?.zero_sized {
+            return Err(SpecializationError::UnsupportedGenericArg);
+        }
+    }
+    let discriminants = discriminant_args
+        .iter()
+        .map(|arg| match arg {
+            GenericArg::Value(value) if !value.is_negative() => Ok(value.clone()),
+            _ => Err(SpecializationError::UnsupportedGenericArg),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if !discriminants.iter().all_unique() {
+        return Err(SpecializationError::UnsupportedGenericArg);
+    }
+    Ok((enum_type, discriminants))
+}
+
+pub struct EnumDiscriminantsConcreteLibfunc {
+    pub signature: LibfuncSignature,
+    /// The discriminants of the variants of the enum, by the index of the variant.
+    pub discriminants: Vec<BigInt>,
+}
+impl SignatureBasedConcreteLibfunc for EnumDiscriminantsConcreteLibfunc {
+    fn signature(&self) -> &LibfuncSignature {
+        &self.signature
+    }
+}
+
+/// Libfunc for converting a value of a C-like enum to the discriminant of its variant.
+#[derive(Default)]
+pub struct EnumToFelt252Libfunc {}
+impl EnumToFelt252Libfunc {
+    /// Creates the specialization of the enum-to-felt252 libfunc with the given template
+    /// arguments.
+    fn specialize_concrete_lib_func(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<EnumDiscriminantsConcreteLibfunc, SpecializationError> {
+        let (enum_type, discriminants) = enum_discriminants_args(context, args)?;
+        Ok(EnumDiscriminantsConcreteLibfunc {
+            signature: LibfuncSignature::new_non_branch(
+                vec![enum_type],
+                vec![OutputVarInfo {
+                    ty: context.get_concrete_type(Felt252Type::id(), &[])?,
+                    ref_info: OutputVarReferenceInfo::SimpleDerefs,
+                }],
+                SierraApChange::Known { new_vars_only: false },
+            ),
+            discriminants,
+        })
+    }
+}
+impl NamedLibfunc for EnumToFelt252Libfunc {
+    type Concrete = EnumDiscriminantsConcreteLibfunc;
+    const STR_ID: &'static str = "enum_to_felt252";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        Ok(self.specialize_concrete_lib_func(context, args)?.signature)
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        self.specialize_concrete_lib_func(context.upcast(), args)
+    }
+}
+
+/// Libfunc for converting a felt252 to the value of a C-like enum whose variant has it as its
+/// discriminant. Branches to the failure branch if no variant has it as its discriminant.
+#[derive(Default)]
+pub struct EnumTryFromFelt252Libfunc {}
+impl EnumTryFromFelt252Libfunc {
+    /// Creates the specialization of the enum-try-from-felt252 libfunc with the given template
+    /// arguments.
+    fn specialize_concrete_lib_func(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<EnumDiscriminantsConcreteLibfunc, SpecializationError> {
+        let (enum_type, discriminants) = enum_discriminants_args(context, args)?;
+        Ok(EnumDiscriminantsConcreteLibfunc {
+            signature: LibfuncSignature {
+                param_signatures: vec![ParamSignature::new(
+                    context.get_concrete_type(Felt252Type::id(), &[])?,
+                )],
+                branch_signatures: vec![
+                    BranchSignature {
+                        vars: vec![OutputVarInfo {
+                            ty: enum_type,
+                            ref_info: OutputVarReferenceInfo::SimpleDerefs,
+                        }],
+                        ap_change: SierraApChange::Known { new_vars_only: false },
+                    },
+                    BranchSignature {
+                        vars: vec![],
+                        ap_change: SierraApChange::Known { new_vars_only: false },
+                    },
+                ],
+                fallthrough: Some(0),
+            },
+            discriminants,
+        })
+    }
+}
+impl NamedLibfunc for EnumTryFromFelt252Libfunc {
+    type Concrete = EnumDiscriminantsConcreteLibfunc;
+    const STR_ID: &'static str = "enum_try_from_felt252";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        Ok(self.specialize_concrete_lib_func(context, args)?.signature)
+    }
+
+    fn specialize(
+        &self,
+        context: &dyn SpecializationContext,
+        args: &[GenericArg],
+    ) -> Result<Self::Concrete, SpecializationError> {
+        self.specialize_concrete_lib_func(context.upcast(), args)
+    }
+}
@@ -9,8 +9,8 @@ use crate::extensions::lib_func::{
     SignatureSpecializationContext, SpecializationContext,
 };
 use crate::extensions::{
-    GenericLibfunc, NamedLibfunc, NamedType, NoGenericArgsGenericType, OutputVarReferenceInfo,
-    SignatureBasedConcreteLibfunc, SpecializationError,
+    GenericLibfunc, NamedLibfunc, NamedType, NoGenericArgsGenericLibfunc, NoGenericArgsGenericType,
+    OutputVarReferenceInfo, SignatureBasedConcreteLibfunc, SpecializationError,
 };
 use crate::ids::{GenericLibfuncId, GenericTypeId};
 use crate::program::GenericArg;
@@ -32,6 +32,7 @@ define_libfunc_hierarchy! {
         BinaryOperation(Felt252BinaryOperationLibfunc),
         Const(Felt252ConstLibfunc),
         IsZero(Felt252JumpNotZeroLibfunc),
+        Inverse(Felt252InverseLibfunc),
     }, Felt252Concrete
 }
 
@@ -291,3 +292,22 @@ impl SignatureBasedConcreteLibfunc for Felt252ConstConcreteLibfunc {
         &self.signature
     }
 }
+
+/// Libfunc for computing the multiplicative inverse of a non-zero felt252.
+#[derive(Default)]
+pub struct Felt252InverseLibfunc {}
+impl NoGenericArgsGenericLibfunc for Felt252InverseLibfunc {
+    const STR_ID: &'static str = "felt252_inverse";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        let ty = nonzero_ty(context, &context.get_concrete_type(Felt252Type::id(), &[])?)?;
+        Ok(LibfuncSignature::new_non_branch(
+            vec![ty.clone()],
+            vec![OutputVarInfo { ty, ref_info: OutputVarReferenceInfo::SimpleDerefs }],
+            SierraApChange::Known { new_vars_only: false },
+        ))
+    }
+}
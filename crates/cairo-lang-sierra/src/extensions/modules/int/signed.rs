@@ -13,6 +13,7 @@ use crate::extensions::lib_func::{
     BranchSignature, DeferredOutputKind, LibfuncSignature, OutputVarInfo, ParamSignature,
     SierraApChange, SignatureSpecializationContext, SpecializationContext,
 };
+use crate::extensions::non_zero::nonzero_ty;
 use crate::extensions::range_check::RangeCheckType;
 use crate::extensions::{
     GenericLibfunc, NamedType, NoGenericArgsGenericLibfunc, OutputVarReferenceInfo,
@@ -34,8 +35,14 @@ pub trait SintTraits: IntTraits {
     const UNSIGNED_INT_TYPE: GenericTypeId;
 }
 
+/// Trait for implementing signed integers smaller than 128 bits.
+pub trait SmallSintTraits: SintTraits {
+    /// The generic libfunc id for division with remainder, rounding toward zero.
+    const DIV_REM: &'static str;
+}
+
 define_libfunc_hierarchy! {
-    pub enum SintLibfunc<TSintTraits: SintTraits + IntMulTraits + IsZeroTraits> {
+    pub enum SintLibfunc<TSintTraits: SmallSintTraits + IntMulTraits + IsZeroTraits> {
         Const(IntConstLibfunc<TSintTraits>),
         Equal(IntEqualLibfunc<TSintTraits>),
         ToFelt252(IntToFelt252Libfunc<TSintTraits>),
@@ -44,6 +51,7 @@ define_libfunc_hierarchy! {
         Diff(SintDiffLibfunc<TSintTraits>),
         IsZero(IsZeroLibfunc<TSintTraits>),
         WideMul(IntWideMulLibfunc<TSintTraits>),
+        DivRem(SintDivRemLibfunc<TSintTraits>),
     }, SintConcrete
 }
 
@@ -191,6 +199,57 @@ impl<TSintTraits: SintTraits> NoGenericArgsGenericLibfunc for SintDiffLibfunc<TS
     }
 }
 
+/// Libfunc for signed integer division with remainder, rounding toward zero - so that the
+/// remainder has the sign of the dividend.
+/// The quotient overflows only when dividing the minimal value by -1, in which case the second
+/// branch is taken.
+#[derive(Default)]
+pub struct SintDivRemLibfunc<TSintTraits: SmallSintTraits> {
+    _phantom: PhantomData<TSintTraits>,
+}
+impl<TSintTraits: SmallSintTraits> NoGenericArgsGenericLibfunc for SintDivRemLibfunc<TSintTraits> {
+    const STR_ID: &'static str = TSintTraits::DIV_REM;
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        let ty = context.get_concrete_type(TSintTraits::GENERIC_TYPE_ID, &[])?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let rc_output_info = OutputVarInfo::new_builtin(range_check_type.clone(), 0);
+        Ok(LibfuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(range_check_type).with_allow_add_const(),
+                ParamSignature::new(ty.clone()),
+                ParamSignature::new(nonzero_ty(context, &ty)?),
+            ],
+            branch_signatures: vec![
+                // In range.
+                BranchSignature {
+                    vars: vec![
+                        rc_output_info.clone(),
+                        OutputVarInfo {
+                            ty: ty.clone(),
+                            ref_info: OutputVarReferenceInfo::NewTempVar { idx: 0 },
+                        },
+                        OutputVarInfo {
+                            ty,
+                            ref_info: OutputVarReferenceInfo::NewTempVar { idx: 1 },
+                        },
+                    ],
+                    ap_change: SierraApChange::Known { new_vars_only: false },
+                },
+                // Overflow.
+                BranchSignature {
+                    vars: vec![rc_output_info],
+                    ap_change: SierraApChange::Known { new_vars_only: false },
+                },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+}
+
 #[derive(Default)]
 pub struct Sint8Traits;
 
@@ -201,6 +260,10 @@ impl SintTraits for Sint8Traits {
     const UNSIGNED_INT_TYPE: GenericTypeId = <Uint8Type as NamedType>::ID;
 }
 
+impl SmallSintTraits for Sint8Traits {
+    const DIV_REM: &'static str = "i8_div_rem";
+}
+
 impl IntTraits for Sint8Traits {
     type IntType = i8;
     const GENERIC_TYPE_ID: GenericTypeId = GenericTypeId::new_inline("i8");
@@ -236,6 +299,10 @@ impl SintTraits for Sint16Traits {
     const UNSIGNED_INT_TYPE: GenericTypeId = <Uint16Type as NamedType>::ID;
 }
 
+impl SmallSintTraits for Sint16Traits {
+    const DIV_REM: &'static str = "i16_div_rem";
+}
+
 impl IntTraits for Sint16Traits {
     type IntType = i16;
     const GENERIC_TYPE_ID: GenericTypeId = GenericTypeId::new_inline("i16");
@@ -271,6 +338,10 @@ impl SintTraits for Sint32Traits {
     const UNSIGNED_INT_TYPE: GenericTypeId = <Uint32Type as NamedType>::ID;
 }
 
+impl SmallSintTraits for Sint32Traits {
+    const DIV_REM: &'static str = "i32_div_rem";
+}
+
 impl IntTraits for Sint32Traits {
     type IntType = i32;
     const GENERIC_TYPE_ID: GenericTypeId = GenericTypeId::new_inline("i32");
@@ -306,6 +377,10 @@ impl SintTraits for Sint64Traits {
     const UNSIGNED_INT_TYPE: GenericTypeId = <Uint64Type as NamedType>::ID;
 }
 
+impl SmallSintTraits for Sint64Traits {
+    const DIV_REM: &'static str = "i64_div_rem";
+}
+
 impl IntTraits for Sint64Traits {
     type IntType = i64;
     const GENERIC_TYPE_ID: GenericTypeId = GenericTypeId::new_inline("i64");
@@ -38,6 +38,16 @@ pub trait UintTraits: IntTraits {
     const BITWISE: &'static str;
 }
 
+/// Trait for implementing the unsigned integers smaller than u128.
+pub trait SmallUintTraits: UintTraits {
+    /// The generic libfunc id for checking if an integer is less than another.
+    const LESS_THAN: &'static str;
+    /// The generic libfunc id for checking if an integer is less than or equal to another.
+    const LESS_THAN_OR_EQUAL: &'static str;
+    /// The generic libfunc id for multiplication, failing on overflow.
+    const CHECKED_MUL: &'static str;
+}
+
 /// Libfunc for integer operations.
 pub struct UintOperationLibfunc<TUintTraits: UintTraits> {
     pub operator: IntOperator,
@@ -233,8 +243,118 @@ impl<TUintTraits: UintTraits> NoGenericArgsGenericLibfunc for UintBitwiseLibfunc
     }
 }
 
+/// Returns the signature of a comparison of two uints - taking the range check and the two uints,
+/// and returning the range check on the false branch and on the true branch.
+fn uint_comparison_signature<TUintTraits: SmallUintTraits>(
+    context: &dyn SignatureSpecializationContext,
+) -> Result<LibfuncSignature, SpecializationError> {
+    let ty = context.get_concrete_type(TUintTraits::GENERIC_TYPE_ID, &[])?;
+    let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+    let ty_param = ParamSignature::new(ty);
+    Ok(LibfuncSignature {
+        param_signatures: vec![
+            ParamSignature::new(range_check_type.clone()).with_allow_add_const(),
+            ty_param.clone(),
+            ty_param,
+        ],
+        branch_signatures: vec![
+            // False.
+            BranchSignature {
+                vars: vec![OutputVarInfo::new_builtin(range_check_type.clone(), 0)],
+                ap_change: SierraApChange::Known { new_vars_only: false },
+            },
+            // True.
+            BranchSignature {
+                vars: vec![OutputVarInfo::new_builtin(range_check_type, 0)],
+                ap_change: SierraApChange::Known { new_vars_only: false },
+            },
+        ],
+        fallthrough: Some(0),
+    })
+}
+
+/// Libfunc for checking if a uint is less than another.
+#[derive(Default)]
+pub struct UintLessThanLibfunc<TUintTraits: SmallUintTraits> {
+    _phantom: PhantomData<TUintTraits>,
+}
+impl<TUintTraits: SmallUintTraits> NoGenericArgsGenericLibfunc
+    for UintLessThanLibfunc<TUintTraits>
+{
+    const STR_ID: &'static str = TUintTraits::LESS_THAN;
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        uint_comparison_signature::<TUintTraits>(context)
+    }
+}
+
+/// Libfunc for checking if a uint is less than or equal to another.
+#[derive(Default)]
+pub struct UintLessThanOrEqualLibfunc<TUintTraits: SmallUintTraits> {
+    _phantom: PhantomData<TUintTraits>,
+}
+impl<TUintTraits: SmallUintTraits> NoGenericArgsGenericLibfunc
+    for UintLessThanOrEqualLibfunc<TUintTraits>
+{
+    const STR_ID: &'static str = TUintTraits::LESS_THAN_OR_EQUAL;
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        uint_comparison_signature::<TUintTraits>(context)
+    }
+}
+
+/// Libfunc for uint multiplication, failing on overflow.
+/// Returns the product on the fallthrough branch, and nothing on the overflow branch.
+#[derive(Default)]
+pub struct UintCheckedMulLibfunc<TUintTraits: SmallUintTraits> {
+    _phantom: PhantomData<TUintTraits>,
+}
+impl<TUintTraits: SmallUintTraits> NoGenericArgsGenericLibfunc
+    for UintCheckedMulLibfunc<TUintTraits>
+{
+    const STR_ID: &'static str = TUintTraits::CHECKED_MUL;
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        let ty = context.get_concrete_type(TUintTraits::GENERIC_TYPE_ID, &[])?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let ty_param = ParamSignature::new(ty.clone());
+        Ok(LibfuncSignature {
+            param_signatures: vec![
+                ParamSignature::new(range_check_type.clone()).with_allow_add_const(),
+                ty_param.clone(),
+                ty_param,
+            ],
+            branch_signatures: vec![
+                // No overflow.
+                BranchSignature {
+                    vars: vec![
+                        OutputVarInfo::new_builtin(range_check_type.clone(), 0),
+                        OutputVarInfo { ty, ref_info: OutputVarReferenceInfo::SimpleDerefs },
+                    ],
+                    ap_change: SierraApChange::Known { new_vars_only: false },
+                },
+                // Overflow.
+                BranchSignature {
+                    vars: vec![OutputVarInfo::new_builtin(range_check_type, 0)],
+                    ap_change: SierraApChange::Known { new_vars_only: false },
+                },
+            ],
+            fallthrough: Some(0),
+        })
+    }
+}
+
 define_libfunc_hierarchy! {
-    pub enum UintLibfunc<TUintTraits: UintTraits + IntMulTraits + IsZeroTraits> {
+    pub enum UintLibfunc<TUintTraits: SmallUintTraits + IntMulTraits + IsZeroTraits> {
         Const(IntConstLibfunc<TUintTraits>),
         Operation(UintOperationLibfunc<TUintTraits>),
         SquareRoot(UintSquareRootLibfunc<TUintTraits>),
@@ -244,6 +364,9 @@ define_libfunc_hierarchy! {
         IsZero(IsZeroLibfunc<TUintTraits>),
         Divmod(UintDivmodLibfunc<TUintTraits>),
         WideMul(IntWideMulLibfunc<TUintTraits>),
+        CheckedMul(UintCheckedMulLibfunc<TUintTraits>),
+        LessThan(UintLessThanLibfunc<TUintTraits>),
+        LessThanOrEqual(UintLessThanOrEqualLibfunc<TUintTraits>),
         Bitwise(UintBitwiseLibfunc<TUintTraits>),
     }, UintConcrete
 }
@@ -270,6 +393,12 @@ impl UintTraits for Uint8Traits {
     const BITWISE: &'static str = "u8_bitwise";
 }
 
+impl SmallUintTraits for Uint8Traits {
+    const LESS_THAN: &'static str = "u8_lt";
+    const LESS_THAN_OR_EQUAL: &'static str = "u8_le";
+    const CHECKED_MUL: &'static str = "u8_checked_mul";
+}
+
 impl IntMulTraits for Uint8Traits {
     const WIDE_MUL: &'static str = "u8_wide_mul";
     const WIDE_MUL_RES_TYPE_ID: GenericTypeId = <Uint16Type as NamedType>::ID;
@@ -307,6 +436,12 @@ impl UintTraits for Uint16Traits {
     const BITWISE: &'static str = "u16_bitwise";
 }
 
+impl SmallUintTraits for Uint16Traits {
+    const LESS_THAN: &'static str = "u16_lt";
+    const LESS_THAN_OR_EQUAL: &'static str = "u16_le";
+    const CHECKED_MUL: &'static str = "u16_checked_mul";
+}
+
 impl IntMulTraits for Uint16Traits {
     const WIDE_MUL: &'static str = "u16_wide_mul";
     const WIDE_MUL_RES_TYPE_ID: GenericTypeId = <Uint32Type as NamedType>::ID;
@@ -344,6 +479,12 @@ impl UintTraits for Uint32Traits {
     const BITWISE: &'static str = "u32_bitwise";
 }
 
+impl SmallUintTraits for Uint32Traits {
+    const LESS_THAN: &'static str = "u32_lt";
+    const LESS_THAN_OR_EQUAL: &'static str = "u32_le";
+    const CHECKED_MUL: &'static str = "u32_checked_mul";
+}
+
 impl IntMulTraits for Uint32Traits {
     const WIDE_MUL: &'static str = "u32_wide_mul";
     const WIDE_MUL_RES_TYPE_ID: GenericTypeId = <Uint64Type as NamedType>::ID;
@@ -381,6 +522,12 @@ impl UintTraits for Uint64Traits {
     const BITWISE: &'static str = "u64_bitwise";
 }
 
+impl SmallUintTraits for Uint64Traits {
+    const LESS_THAN: &'static str = "u64_lt";
+    const LESS_THAN_OR_EQUAL: &'static str = "u64_le";
+    const CHECKED_MUL: &'static str = "u64_checked_mul";
+}
+
 impl IntMulTraits for Uint64Traits {
     const WIDE_MUL: &'static str = "u64_wide_mul";
     const WIDE_MUL_RES_TYPE_ID: GenericTypeId = <Uint128Type as NamedType>::ID;
@@ -1,4 +1,5 @@
 use super::unsigned128::{U128MulGuaranteeType, Uint128Type};
+use super::unsigned512::get_u512_type;
 use crate::define_libfunc_hierarchy;
 use crate::extensions::lib_func::{
     BranchSignature, LibfuncSignature, OutputVarInfo, ParamSignature, SierraApChange,
@@ -14,7 +15,10 @@ use crate::extensions::{
 define_libfunc_hierarchy! {
     pub enum Uint256Libfunc {
         IsZero(Uint256IsZeroLibfunc),
+        OverflowingAdd(Uint256OverflowingAddLibfunc),
+        OverflowingSub(Uint256OverflowingSubLibfunc),
         Divmod(Uint256DivmodLibfunc),
+        WideMul(Uint256WideMulLibfunc),
         SquareRoot(Uint256SquareRootLibfunc),
         InvModN(Uint256InvModNLibfunc),
     }, Uint256Concrete
@@ -53,6 +57,68 @@ impl NoGenericArgsGenericLibfunc for Uint256IsZeroLibfunc {
     }
 }
 
+/// Returns the signature of an overflowing u256 operation - taking the range check and two u256
+/// values, and returning the range check and the wrapped result, on the no-overflow branch and on
+/// the overflow branch.
+fn u256_overflowing_op_signature(
+    context: &dyn SignatureSpecializationContext,
+) -> Result<LibfuncSignature, SpecializationError> {
+    let u256_ty = get_u256_type(context)?;
+    let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+    let outputs = vec![
+        OutputVarInfo::new_builtin(range_check_type.clone(), 0),
+        OutputVarInfo { ty: u256_ty.clone(), ref_info: OutputVarReferenceInfo::SimpleDerefs },
+    ];
+    Ok(LibfuncSignature {
+        param_signatures: vec![
+            ParamSignature::new(range_check_type).with_allow_add_const(),
+            ParamSignature::new(u256_ty.clone()),
+            ParamSignature::new(u256_ty),
+        ],
+        branch_signatures: vec![
+            // No overflow.
+            BranchSignature {
+                vars: outputs.clone(),
+                ap_change: SierraApChange::Known { new_vars_only: false },
+            },
+            // Overflow.
+            BranchSignature {
+                vars: outputs,
+                ap_change: SierraApChange::Known { new_vars_only: false },
+            },
+        ],
+        fallthrough: Some(0),
+    })
+}
+
+// Overflowing add.
+#[derive(Default)]
+pub struct Uint256OverflowingAddLibfunc;
+impl NoGenericArgsGenericLibfunc for Uint256OverflowingAddLibfunc {
+    const STR_ID: &'static str = "u256_overflowing_add";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        u256_overflowing_op_signature(context)
+    }
+}
+
+// Overflowing sub.
+#[derive(Default)]
+pub struct Uint256OverflowingSubLibfunc;
+impl NoGenericArgsGenericLibfunc for Uint256OverflowingSubLibfunc {
+    const STR_ID: &'static str = "u256_overflow_sub";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        u256_overflowing_op_signature(context)
+    }
+}
+
 // Divmod.
 #[derive(Default)]
 pub struct Uint256DivmodLibfunc;
@@ -87,6 +153,46 @@ impl NoGenericArgsGenericLibfunc for Uint256DivmodLibfunc {
     }
 }
 
+// Wide mul.
+#[derive(Default)]
+pub struct Uint256WideMulLibfunc;
+impl NoGenericArgsGenericLibfunc for Uint256WideMulLibfunc {
+    const STR_ID: &'static str = "u256_wide_mul";
+
+    fn specialize_signature(
+        &self,
+        context: &dyn SignatureSpecializationContext,
+    ) -> Result<LibfuncSignature, SpecializationError> {
+        let u256_ty = get_u256_type(context)?;
+        let range_check_type = context.get_concrete_type(RangeCheckType::id(), &[])?;
+        let guarantee_output = OutputVarInfo {
+            ty: context.get_concrete_type(U128MulGuaranteeType::id(), &[])?,
+            ref_info: OutputVarReferenceInfo::SimpleDerefs,
+        };
+        Ok(LibfuncSignature::new_non_branch_ex(
+            vec![
+                ParamSignature::new(range_check_type.clone()).with_allow_add_const(),
+                ParamSignature::new(u256_ty.clone()),
+                ParamSignature::new(u256_ty),
+            ],
+            vec![
+                OutputVarInfo::new_builtin(range_check_type, 0),
+                OutputVarInfo {
+                    ty: get_u512_type(context)?,
+                    ref_info: OutputVarReferenceInfo::SimpleDerefs,
+                },
+                // The guarantees of the multiplications of the limbs: `a0 * b0`, `a0 * b1`,
+                // `a1 * b0` and `a1 * b1`.
+                guarantee_output.clone(),
+                guarantee_output.clone(),
+                guarantee_output.clone(),
+                guarantee_output,
+            ],
+            SierraApChange::Known { new_vars_only: false },
+        ))
+    }
+}
+
 // Square root.
 #[derive(Default)]
 pub struct Uint256SquareRootLibfunc;
@@ -57,7 +57,7 @@ impl NoGenericArgsGenericLibfunc for Uint512DivmodU256Libfunc {
 }
 
 /// Helper for u512 type def.
-fn get_u512_type(
+pub(super) fn get_u512_type(
     context: &dyn SignatureSpecializationContext,
 ) -> Result<ConcreteTypeId, SpecializationError> {
     let u128_ty = context.get_concrete_type(Uint128Type::id(), &[])?;
@@ -14,7 +14,9 @@ pub mod boxing;
 pub mod branch_align;
 pub mod bytes31;
 pub mod casts;
+pub mod const_type;
 pub mod consts;
+pub mod custom;
 pub mod debug;
 pub mod drop;
 pub mod duplicate;
@@ -48,6 +48,7 @@ impl TypeSpecializationContext for MockSpecializationContext {
             || id == "U128AndFelt252".into()
             || id == "StorageAddress".into()
             || id == "ContractAddress".into()
+            || id == "CLikeEnum".into()
         {
             Some(TypeInfo {
                 long_id: self.mapping.get_by_left(&id)?.clone(),
@@ -72,6 +73,14 @@ impl TypeSpecializationContext for MockSpecializationContext {
                 duplicatable: false,
                 zero_sized: true,
             })
+        } else if id == "Unit".into() {
+            Some(TypeInfo {
+                long_id: self.mapping.get_by_left(&id)?.clone(),
+                storable: true,
+                droppable: true,
+                duplicatable: true,
+                zero_sized: true,
+            })
         } else if id == "GasBuiltin".into()
             || id == "System".into()
             || id == "RangeCheck".into()
@@ -85,6 +94,14 @@ impl TypeSpecializationContext for MockSpecializationContext {
                 duplicatable: false,
                 zero_sized: false,
             })
+        } else if id == "ConstU128".into() || id == "ConstFelt252".into() {
+            Some(TypeInfo {
+                long_id: self.mapping.get_by_left(&id)?.clone(),
+                storable: false,
+                droppable: false,
+                duplicatable: false,
+                zero_sized: false,
+            })
         } else if id == "SnapshotRangeCheck".into() || id == "SnapshotArrayU128".into() {
             Some(TypeInfo {
                 long_id: self.mapping.get_by_left(&id)?.clone(),
@@ -186,6 +203,25 @@ impl SpecializationContext for MockSpecializationContext {
             "Struct<name, UninitializedFelt252>")]
 #[test_case("Struct", vec![type_arg("u128"), type_arg("felt252")] => Err(UnsupportedGenericArg);
             "Struct<u128, felt252>")]
+#[test_case("Const", vec![type_arg("u128"), value_arg(5)] => Ok(()); "Const<u128, 5>")]
+#[test_case("Const", vec![type_arg("u128"), value_arg(-1)] => Err(UnsupportedGenericArg);
+            "Const<u128, -1>")]
+#[test_case("Const", vec![type_arg("u128")] => Err(WrongNumberOfGenericArgs); "Const<u128>")]
+#[test_case("Const", vec![type_arg("ArrayU128"), value_arg(5)] => Err(UnsupportedGenericArg);
+            "Const<ArrayU128, 5>")]
+#[test_case("Const", vec![type_arg("U128AndFelt252"), type_arg("ConstU128"), type_arg("ConstFelt252")]
+            => Ok(()); "Const<U128AndFelt252, ConstU128, ConstFelt252>")]
+#[test_case("Const", vec![type_arg("U128AndFelt252"), type_arg("ConstFelt252"), type_arg("ConstU128")]
+            => Err(UnsupportedGenericArg); "Const<U128AndFelt252, ConstFelt252, ConstU128>")]
+#[test_case("Const", vec![type_arg("U128AndFelt252"), type_arg("ConstU128")]
+            => Err(WrongNumberOfGenericArgs); "Const<U128AndFelt252, ConstU128>")]
+#[test_case("Const", vec![type_arg("Option"), value_arg(0), type_arg("ConstFelt252")] => Ok(());
+            "Const<Option, 0, ConstFelt252>")]
+#[test_case("Const", vec![type_arg("Option"), value_arg(1), type_arg("ConstFelt252")]
+            => Err(UnsupportedGenericArg); "Const<Option, 1, ConstFelt252>")]
+#[test_case("Const", vec![type_arg("Option"), value_arg(2), type_arg("ConstFelt252")]
+            => Err(IndexOutOfRange { index: BigInt::from(2), range_size: 2 });
+            "Const<Option, 2, ConstFelt252>")]
 #[test_case("System", vec![] => Ok(()); "System")]
 #[test_case("StorageBaseAddress", vec![] => Ok(()); "StorageBaseAddress")]
 #[test_case("Snapshot", vec![type_arg("RangeCheck")] => Ok(()); "Snapshot<RangeCheck>")]
@@ -211,12 +247,22 @@ fn find_type_specialization(
 #[test_case("function_call", vec![GenericArg::UserFunc("RegisteredFunction".into())]
             => Ok(()); "function_call<&RegisteredFunction>")]
 #[test_case("function_call", vec![] => Err(UnsupportedGenericArg); "function_call")]
+#[test_case("const_as_immediate", vec![type_arg("ConstU128")] => Ok(());
+            "const_as_immediate<ConstU128>")]
+#[test_case("const_as_immediate", vec![type_arg("u128")] => Err(UnsupportedGenericArg);
+            "const_as_immediate<u128>")]
+#[test_case("const_as_box", vec![type_arg("ConstU128")] => Ok(()); "const_as_box<ConstU128>")]
+#[test_case("const_as_box", vec![] => Err(WrongNumberOfGenericArgs); "const_as_box")]
 #[test_case("array_new", vec![] => Err(WrongNumberOfGenericArgs); "array_new")]
 #[test_case("array_new", vec![type_arg("u128")] => Ok(()); "array_new<u128>")]
 #[test_case("array_append", vec![] => Err(WrongNumberOfGenericArgs); "array_append")]
 #[test_case("array_append", vec![type_arg("u128")] => Ok(()); "array_append<u128>")]
 #[test_case("array_get", vec![] => Err(WrongNumberOfGenericArgs); "array_get")]
 #[test_case("array_get", vec![type_arg("u128")] => Ok(()); "array_get<u128>")]
+#[test_case("array_snapshot_multi_pop_front", vec![type_arg("u128")] => Ok(());
+            "array_snapshot_multi_pop_front<u128>")]
+#[test_case("array_snapshot_multi_pop_back", vec![] => Err(WrongNumberOfGenericArgs);
+            "array_snapshot_multi_pop_back")]
 #[test_case("array_len", vec![] => Err(WrongNumberOfGenericArgs); "array_len")]
 #[test_case("array_len", vec![type_arg("u128")] => Ok(()); "array_len<u128>")]
 #[test_case("withdraw_gas", vec![value_arg(0)] => Err(WrongNumberOfGenericArgs); "withdraw_gas<0>")]
@@ -233,6 +279,7 @@ fn find_type_specialization(
 #[test_case("u128_overflowing_add", vec![] => Ok(()); "u128_overflowing_add")]
 #[test_case("u128_overflowing_sub", vec![] => Ok(()); "u128_overflowing_sub")]
 #[test_case("u128_safe_divmod", vec![] => Ok(()); "u128_safe_divmod")]
+#[test_case("u128_sqrt", vec![] => Ok(()); "u128_sqrt")]
 #[test_case("u128_const", vec![value_arg(8)] => Ok(()); "u128_const<8>")]
 #[test_case("u128_const", vec![] => Err(UnsupportedGenericArg); "u128_const")]
 #[test_case("storage_base_address_const", vec![value_arg(8)] => Ok(()); "storage_base_address_const<8>")]
@@ -293,6 +340,21 @@ Ok(());"enum_init<Option,1>")]
 #[test_case("enum_match", vec![] => Err(WrongNumberOfGenericArgs); "enum_match")]
 #[test_case("enum_snapshot_match", vec![type_arg("Option")] => Ok(()); "enum_snapshot_match<Option>")]
 #[test_case("enum_snapshot_match", vec![type_arg("NonDupEnum")] => Ok(()); "enum_snapshot_match<NonDupEnum>")]
+#[test_case("enum_to_felt252", vec![type_arg("CLikeEnum"), value_arg(200), value_arg(404)]
+            => Ok(()); "enum_to_felt252<CLikeEnum,200,404>")]
+#[test_case("enum_to_felt252", vec![type_arg("CLikeEnum"), value_arg(200)]
+            => Err(WrongNumberOfGenericArgs); "enum_to_felt252<CLikeEnum,200>")]
+#[test_case("enum_to_felt252", vec![type_arg("CLikeEnum"), value_arg(200), value_arg(200)]
+            => Err(UnsupportedGenericArg); "enum_to_felt252<CLikeEnum,200,200>")]
+#[test_case("enum_to_felt252", vec![type_arg("CLikeEnum"), value_arg(-1), value_arg(404)]
+            => Err(UnsupportedGenericArg); "enum_to_felt252<CLikeEnum,-1,404>")]
+#[test_case("enum_to_felt252", vec![type_arg("Option"), value_arg(0), value_arg(1)]
+            => Err(UnsupportedGenericArg); "enum_to_felt252<Option,0,1>")]
+#[test_case("enum_to_felt252", vec![] => Err(WrongNumberOfGenericArgs); "enum_to_felt252")]
+#[test_case("enum_try_from_felt252", vec![type_arg("CLikeEnum"), value_arg(200), value_arg(404)]
+            => Ok(()); "enum_try_from_felt252<CLikeEnum,200,404>")]
+#[test_case("enum_try_from_felt252", vec![type_arg("CLikeEnum"), type_arg("Unit"), value_arg(404)]
+            => Err(UnsupportedGenericArg); "enum_try_from_felt252<CLikeEnum,Unit,404>")]
 #[test_case("struct_construct", vec![type_arg("U128AndFelt252")] => Ok(());
             "struct_construct<U128AndFelt252>")]
 #[test_case("struct_construct", vec![value_arg(4)] => Err(UnsupportedGenericArg);
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fmt;
 
 use cairo_lang_utils::write_comma_separated;
@@ -33,6 +34,48 @@ impl fmt::Display for Program {
     }
 }
 
+/// Displays a program for review - with a comment heading each section of the program and the
+/// statements of each function, and a blank line between functions. The parser ignores the
+/// comments, so the displayed code parses back to the same program.
+pub struct PrettyProgram<'a>(pub &'a Program);
+impl fmt::Display for PrettyProgram<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let program = self.0;
+        writeln!(f, "// Types.")?;
+        for declaration in &program.type_declarations {
+            writeln!(f, "{declaration};")?;
+        }
+        writeln!(f)?;
+        writeln!(f, "// Libfuncs.")?;
+        for declaration in &program.libfunc_declarations {
+            writeln!(f, "{declaration};")?;
+        }
+        let mut entry_points = HashMap::<usize, Vec<&FunctionId>>::new();
+        for func in &program.funcs {
+            entry_points.entry(func.entry_point.0).or_default().push(&func.id);
+        }
+        writeln!(f)?;
+        writeln!(f, "// Statements.")?;
+        for (i, statement) in program.statements.iter().enumerate() {
+            if let Some(func_ids) = entry_points.get(&i) {
+                if i != 0 {
+                    writeln!(f)?;
+                }
+                for func_id in func_ids {
+                    writeln!(f, "// {func_id}")?;
+                }
+            }
+            writeln!(f, "{statement}; // {i}")?;
+        }
+        writeln!(f)?;
+        writeln!(f, "// Functions.")?;
+        for func in &program.funcs {
+            writeln!(f, "{func};")?;
+        }
+        Ok(())
+    }
+}
+
 impl fmt::Display for TypeDeclaration {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let TypeDeclaration { id, long_id, declared_type_info } = self;
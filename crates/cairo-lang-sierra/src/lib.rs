@@ -12,7 +12,11 @@
 use lalrpop_util::lalrpop_mod;
 
 pub mod algorithm;
+pub mod binary;
+pub mod canonical;
+pub mod dead_code;
 pub mod debug_info;
+pub mod dup_drop;
 pub mod edit_state;
 pub mod extensions;
 pub mod fmt;
@@ -23,6 +27,7 @@ pub mod program_registry;
 pub mod simulation;
 #[cfg(test)]
 mod test_utils;
+pub mod validation;
 
 lalrpop_mod!(
     #[allow(clippy::all, unused_extern_crates)]
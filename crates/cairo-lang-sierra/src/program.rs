@@ -10,6 +10,7 @@ use crate::extensions::gas::{
     BuiltinCostWithdrawGasLibfunc, RedepositGasLibfunc, WithdrawGasLibfunc,
 };
 use crate::extensions::NamedLibfunc;
+use crate::fmt::PrettyProgram;
 use crate::ids::{
     ConcreteLibfuncId, ConcreteTypeId, FunctionId, GenericLibfuncId, GenericTypeId, UserTypeId,
     VarId,
@@ -134,6 +135,11 @@ impl Program {
     pub fn into_artifact(self) -> VersionedProgram {
         ProgramArtifact::stripped(self).into()
     }
+
+    /// Returns a wrapper for displaying the program for review - see [PrettyProgram].
+    pub fn pretty(&self) -> PrettyProgram<'_> {
+        PrettyProgram(self)
+    }
 }
 
 /// Declaration of a concrete type.
@@ -1,10 +1,12 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 use itertools::chain;
 use thiserror::Error;
 
+use crate::extensions::custom::{LibfuncExtension, LibfuncExtensions};
 use crate::extensions::lib_func::{
     SierraApChange, SignatureSpecializationContext, SpecializationContext,
 };
@@ -13,7 +15,9 @@ use crate::extensions::types::TypeInfo;
 use crate::extensions::{
     ConcreteType, ExtensionError, GenericLibfunc, GenericLibfuncEx, GenericType, GenericTypeEx,
 };
-use crate::ids::{ConcreteLibfuncId, ConcreteTypeId, FunctionId, GenericTypeId};
+use crate::ids::{
+    ConcreteLibfuncId, ConcreteTypeId, FunctionId, GenericLibfuncId, GenericTypeId,
+};
 use crate::program::{
     DeclaredTypeInfo, Function, FunctionSignature, GenericArg, Program, TypeDeclaration,
 };
@@ -70,6 +74,15 @@ impl<TType: GenericType, TLibfunc: GenericLibfunc> ProgramRegistry<TType, TLibfu
     pub fn new_with_ap_change(
         program: &Program,
         function_ap_change: OrderedHashMap<FunctionId, usize>,
+    ) -> Result<ProgramRegistry<TType, TLibfunc>, Box<ProgramRegistryError>> {
+        Self::new_with_extensions(program, function_ap_change, &LibfuncExtensions::default())
+    }
+
+    /// Create a registry for the program, supporting the libfuncs of the given extensions.
+    pub fn new_with_extensions(
+        program: &Program,
+        function_ap_change: OrderedHashMap<FunctionId, usize>,
+        libfunc_extensions: &LibfuncExtensions,
     ) -> Result<ProgramRegistry<TType, TLibfunc>, Box<ProgramRegistryError>> {
         let functions = get_functions(program)?;
         let (concrete_types, concrete_type_ids) = get_concrete_types_maps::<TType>(program)?;
@@ -80,6 +93,7 @@ impl<TType: GenericType, TLibfunc: GenericLibfunc> ProgramRegistry<TType, TLibfu
                 concrete_type_ids: &concrete_type_ids,
                 concrete_types: &concrete_types,
                 function_ap_change,
+                libfunc_extensions,
             },
         )?;
         let registry = ProgramRegistry { functions, concrete_types, concrete_libfuncs };
@@ -242,6 +256,8 @@ pub struct SpecializationContextForRegistry<'a, TType: GenericType> {
     pub concrete_types: &'a TypeMap<TType::Concrete>,
     /// AP changes information for Sierra user functions.
     pub function_ap_change: OrderedHashMap<FunctionId, usize>,
+    /// The extensions implementing the libfuncs not defined by the core library.
+    pub libfunc_extensions: &'a LibfuncExtensions,
 }
 impl<TType: GenericType> TypeSpecializationContext for SpecializationContextForRegistry<'_, TType> {
     fn try_get_type_info(&self, id: ConcreteTypeId) -> Option<TypeInfo> {
@@ -274,6 +290,13 @@ impl<TType: GenericType> SignatureSpecializationContext
             SierraApChange::Unknown
         })
     }
+
+    fn try_get_libfunc_extension(
+        &self,
+        id: &GenericLibfuncId,
+    ) -> Option<Arc<dyn LibfuncExtension>> {
+        self.libfunc_extensions.get(id)
+    }
 }
 impl<TType: GenericType> SpecializationContext for SpecializationContextForRegistry<'_, TType> {
     fn try_get_function(&self, function_id: &FunctionId) -> Option<Function> {
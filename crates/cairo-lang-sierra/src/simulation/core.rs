@@ -15,7 +15,9 @@ use crate::extensions::core::CoreConcreteLibfunc::{
     UnconditionalJump, UnwrapNonZero,
 };
 use crate::extensions::ec::EcConcreteLibfunc;
-use crate::extensions::enm::{EnumConcreteLibfunc, EnumInitConcreteLibfunc};
+use crate::extensions::enm::{
+    EnumConcreteLibfunc, EnumDiscriminantsConcreteLibfunc, EnumInitConcreteLibfunc,
+};
 use crate::extensions::felt252::{
     Felt252BinaryOpConcreteLibfunc, Felt252BinaryOperationConcrete, Felt252BinaryOperator,
     Felt252Concrete, Felt252ConstConcreteLibfunc, Felt252OperationWithConstConcreteLibfunc,
@@ -43,6 +45,12 @@ fn get_beta() -> BigInt {
         .unwrap()
 }
 
+/// Returns the prime of the felt252 field.
+fn get_prime() -> BigInt {
+    BigInt::from_str("3618502788666131213697322783095070105623107215331596699973092056135872020481")
+        .unwrap()
+}
+
 // TODO(spapini): Proper errors when converting from bigint to u128.
 /// Simulates the run of a single libfunc. Returns the value representations of the outputs, and
 /// the chosen branch given the inputs.
@@ -218,6 +226,69 @@ pub fn simulate<
             [_] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
         },
+        // The simulation does not charge the per-element copy cost, as it depends on the type size.
+        Array(ArrayConcreteLibfunc::AppendSpan(_)) => match &inputs[..] {
+            [
+                CoreValue::RangeCheck,
+                CoreValue::GasBuiltin(_),
+                CoreValue::Array(_),
+                CoreValue::Array(_),
+            ] => {
+                let mut iter = inputs.into_iter();
+                iter.next(); // Ignore range check.
+                let gas_counter = iter.next().unwrap();
+                let mut arr = extract_matches!(iter.next().unwrap(), CoreValue::Array);
+                arr.extend(extract_matches!(iter.next().unwrap(), CoreValue::Array));
+                Ok((vec![CoreValue::RangeCheck, gas_counter, CoreValue::Array(arr)], 0))
+            }
+            [_, _, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Array(ArrayConcreteLibfunc::Concat(_)) => match &inputs[..] {
+            [
+                CoreValue::RangeCheck,
+                CoreValue::GasBuiltin(_),
+                CoreValue::Array(_),
+                CoreValue::Array(_),
+            ] => {
+                let mut iter = inputs.into_iter();
+                iter.next(); // Ignore range check.
+                let gas_counter = iter.next().unwrap();
+                let mut arr = extract_matches!(iter.next().unwrap(), CoreValue::Array);
+                arr.extend(extract_matches!(iter.next().unwrap(), CoreValue::Array));
+                Ok((vec![CoreValue::RangeCheck, gas_counter, CoreValue::Array(arr)], 0))
+            }
+            [_, _, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Array(
+            ArrayConcreteLibfunc::SnapshotMultiPopFront(_)
+            | ArrayConcreteLibfunc::SnapshotMultiPopBack(_),
+        ) => match &inputs[..] {
+            [CoreValue::RangeCheck, CoreValue::Array(_), CoreValue::Uint32(_)] => {
+                let mut iter = inputs.into_iter();
+                iter.next(); // Ignore range check.
+                let mut arr = extract_matches!(iter.next().unwrap(), CoreValue::Array);
+                let count = extract_matches!(iter.next().unwrap(), CoreValue::Uint32) as usize;
+                if count > arr.len() {
+                    return Ok((vec![CoreValue::RangeCheck, CoreValue::Array(arr)], 1));
+                }
+                let (rest, popped) =
+                    if matches!(libfunc, Array(ArrayConcreteLibfunc::SnapshotMultiPopFront(_))) {
+                        let rest = arr.split_off(count);
+                        (rest, arr)
+                    } else {
+                        let popped = arr.split_off(arr.len() - count);
+                        (arr, popped)
+                    };
+                Ok((
+                    vec![CoreValue::RangeCheck, CoreValue::Array(rest), CoreValue::Array(popped)],
+                    0,
+                ))
+            }
+            [_, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
         Array(ArrayConcreteLibfunc::SnapshotPopFront(_)) => todo!(),
         Array(ArrayConcreteLibfunc::SnapshotPopBack(_)) => todo!(),
         Uint8(libfunc) => simulate_u8_libfunc(libfunc, &inputs),
@@ -277,6 +348,32 @@ pub fn simulate<
                 _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
             }
         }
+        Enum(EnumConcreteLibfunc::ToFelt252(EnumDiscriminantsConcreteLibfunc {
+            discriminants,
+            ..
+        })) => match &inputs[..] {
+            [CoreValue::Enum { index, .. }] => {
+                Ok((vec![CoreValue::Felt252(discriminants[*index].clone())], 0))
+            }
+            [_] => Err(LibfuncSimulationError::WrongArgType),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Enum(EnumConcreteLibfunc::TryFromFelt252(EnumDiscriminantsConcreteLibfunc {
+            discriminants,
+            ..
+        })) => match &inputs[..] {
+            [CoreValue::Felt252(value)] => {
+                match discriminants.iter().position(|discriminant| discriminant == value) {
+                    Some(index) => Ok((
+                        vec![CoreValue::Enum { value: Box::new(CoreValue::Struct(vec![])), index }],
+                        0,
+                    )),
+                    None => Ok((vec![], 1)),
+                }
+            }
+            [_] => Err(LibfuncSimulationError::WrongArgType),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
         Struct(StructConcreteLibfunc::Construct(_)) => Ok((vec![CoreValue::Struct(inputs)], 0)),
         Struct(
             StructConcreteLibfunc::Deconstruct(_) | StructConcreteLibfunc::SnapshotDeconstruct(_),
@@ -320,6 +417,9 @@ pub fn simulate<
         CoreConcreteLibfunc::Nullable(_) => {
             unimplemented!("Simulation of nullable is not implemented yet.")
         }
+        CoreConcreteLibfunc::Const(_) => {
+            unimplemented!("Simulation of constants is not implemented yet.")
+        }
         CoreConcreteLibfunc::Debug(_) => {
             if inputs.len() == 1 {
                 let arr = extract_matches!(&inputs[0], CoreValue::Array);
@@ -350,6 +450,9 @@ pub fn simulate<
         CoreConcreteLibfunc::Uint256(_) => unimplemented!(),
         CoreConcreteLibfunc::Uint512(_) => unimplemented!(),
         CoreConcreteLibfunc::Bytes31(_) => unimplemented!(),
+        CoreConcreteLibfunc::Custom(libfunc) => {
+            libfunc.extension.simulate(&libfunc.generic_args, inputs)
+        }
     }
 }
 
@@ -505,7 +608,7 @@ fn simulate_u128_libfunc(
         Uint128Concrete::SquareRoot(_) => match inputs {
             [CoreValue::RangeCheck, CoreValue::Uint128(value)] => {
                 let root = BigInt::from(*value).sqrt();
-                Ok((vec![CoreValue::RangeCheck, CoreValue::Uint128(root.to_u128().unwrap())], 0))
+                Ok((vec![CoreValue::RangeCheck, CoreValue::Uint64(root.to_u64().unwrap())], 0))
             }
             [_, _] => Err(LibfuncSimulationError::WrongArgType),
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
@@ -594,6 +697,30 @@ fn simulate_u8_libfunc(
         Uint8Concrete::IsZero(_) => unimplemented!(),
         Uint8Concrete::Divmod(_) => unimplemented!(),
         Uint8Concrete::Bitwise(_) => unimplemented!(),
+        Uint8Concrete::CheckedMul(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint8(lhs), CoreValue::Uint8(rhs)] => {
+                Ok(match lhs.checked_mul(*rhs) {
+                    Some(value) => (vec![CoreValue::RangeCheck, CoreValue::Uint8(value)], 0),
+                    None => (vec![CoreValue::RangeCheck], 1),
+                })
+            }
+            [_, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint8Concrete::LessThan(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint8(a), CoreValue::Uint8(b)] => {
+                Ok((vec![CoreValue::RangeCheck], usize::from(a < b)))
+            }
+            [_, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint8Concrete::LessThanOrEqual(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint8(a), CoreValue::Uint8(b)] => {
+                Ok((vec![CoreValue::RangeCheck], usize::from(a <= b)))
+            }
+            [_, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
         Uint8Concrete::WideMul(_) => match inputs {
             [CoreValue::Uint8(lhs), CoreValue::Uint8(rhs)] => {
                 Ok((vec![CoreValue::Uint16(u16::from(*lhs) * u16::from(*rhs))], 0))
@@ -631,7 +758,7 @@ fn simulate_u16_libfunc(
         Uint16Concrete::SquareRoot(_) => match inputs {
             [CoreValue::RangeCheck, CoreValue::Uint16(value)] => {
                 let root = BigInt::from(*value).sqrt();
-                Ok((vec![CoreValue::RangeCheck, CoreValue::Uint16(root.to_u16().unwrap())], 0))
+                Ok((vec![CoreValue::RangeCheck, CoreValue::Uint8(root.to_u8().unwrap())], 0))
             }
             [_, _] => Err(LibfuncSimulationError::WrongArgType),
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
@@ -663,6 +790,30 @@ fn simulate_u16_libfunc(
         Uint16Concrete::IsZero(_) => unimplemented!(),
         Uint16Concrete::Divmod(_) => unimplemented!(),
         Uint16Concrete::Bitwise(_) => unimplemented!(),
+        Uint16Concrete::CheckedMul(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint16(lhs), CoreValue::Uint16(rhs)] => {
+                Ok(match lhs.checked_mul(*rhs) {
+                    Some(value) => (vec![CoreValue::RangeCheck, CoreValue::Uint16(value)], 0),
+                    None => (vec![CoreValue::RangeCheck], 1),
+                })
+            }
+            [_, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint16Concrete::LessThan(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint16(a), CoreValue::Uint16(b)] => {
+                Ok((vec![CoreValue::RangeCheck], usize::from(a < b)))
+            }
+            [_, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint16Concrete::LessThanOrEqual(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint16(a), CoreValue::Uint16(b)] => {
+                Ok((vec![CoreValue::RangeCheck], usize::from(a <= b)))
+            }
+            [_, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
         Uint16Concrete::WideMul(_) => match inputs {
             [CoreValue::Uint16(lhs), CoreValue::Uint16(rhs)] => {
                 Ok((vec![CoreValue::Uint32(u32::from(*lhs) * u32::from(*rhs))], 0))
@@ -700,7 +851,7 @@ fn simulate_u32_libfunc(
         Uint32Concrete::SquareRoot(_) => match inputs {
             [CoreValue::RangeCheck, CoreValue::Uint32(value)] => {
                 let root = BigInt::from(*value).sqrt();
-                Ok((vec![CoreValue::RangeCheck, CoreValue::Uint32(root.to_u32().unwrap())], 0))
+                Ok((vec![CoreValue::RangeCheck, CoreValue::Uint16(root.to_u16().unwrap())], 0))
             }
             [_, _] => Err(LibfuncSimulationError::WrongArgType),
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
@@ -732,6 +883,30 @@ fn simulate_u32_libfunc(
         Uint32Concrete::IsZero(_) => unimplemented!(),
         Uint32Concrete::Divmod(_) => unimplemented!(),
         Uint32Concrete::Bitwise(_) => unimplemented!(),
+        Uint32Concrete::CheckedMul(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint32(lhs), CoreValue::Uint32(rhs)] => {
+                Ok(match lhs.checked_mul(*rhs) {
+                    Some(value) => (vec![CoreValue::RangeCheck, CoreValue::Uint32(value)], 0),
+                    None => (vec![CoreValue::RangeCheck], 1),
+                })
+            }
+            [_, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint32Concrete::LessThan(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint32(a), CoreValue::Uint32(b)] => {
+                Ok((vec![CoreValue::RangeCheck], usize::from(a < b)))
+            }
+            [_, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint32Concrete::LessThanOrEqual(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint32(a), CoreValue::Uint32(b)] => {
+                Ok((vec![CoreValue::RangeCheck], usize::from(a <= b)))
+            }
+            [_, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
         Uint32Concrete::WideMul(_) => match inputs {
             [CoreValue::Uint32(lhs), CoreValue::Uint32(rhs)] => {
                 Ok((vec![CoreValue::Uint64(u64::from(*lhs) * u64::from(*rhs))], 0))
@@ -769,7 +944,7 @@ fn simulate_u64_libfunc(
         Uint64Concrete::SquareRoot(_) => match inputs {
             [CoreValue::RangeCheck, CoreValue::Uint64(value)] => {
                 let root = BigInt::from(*value).sqrt();
-                Ok((vec![CoreValue::RangeCheck, CoreValue::Uint64(root.to_u64().unwrap())], 0))
+                Ok((vec![CoreValue::RangeCheck, CoreValue::Uint32(root.to_u32().unwrap())], 0))
             }
             [_, _] => Err(LibfuncSimulationError::WrongArgType),
             _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
@@ -801,6 +976,30 @@ fn simulate_u64_libfunc(
         Uint64Concrete::IsZero(_) => unimplemented!(),
         Uint64Concrete::Divmod(_) => unimplemented!(),
         Uint64Concrete::Bitwise(_) => unimplemented!(),
+        Uint64Concrete::CheckedMul(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint64(lhs), CoreValue::Uint64(rhs)] => {
+                Ok(match lhs.checked_mul(*rhs) {
+                    Some(value) => (vec![CoreValue::RangeCheck, CoreValue::Uint64(value)], 0),
+                    None => (vec![CoreValue::RangeCheck], 1),
+                })
+            }
+            [_, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint64Concrete::LessThan(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint64(a), CoreValue::Uint64(b)] => {
+                Ok((vec![CoreValue::RangeCheck], usize::from(a < b)))
+            }
+            [_, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
+        Uint64Concrete::LessThanOrEqual(_) => match inputs {
+            [CoreValue::RangeCheck, CoreValue::Uint64(a), CoreValue::Uint64(b)] => {
+                Ok((vec![CoreValue::RangeCheck], usize::from(a <= b)))
+            }
+            [_, _, _] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
         Uint64Concrete::WideMul(_) => match inputs {
             [CoreValue::Uint64(lhs), CoreValue::Uint64(rhs)] => {
                 Ok((vec![CoreValue::Uint128(u128::from(*lhs) * u128::from(*rhs))], 0))
@@ -884,5 +1083,22 @@ fn simulate_felt252_libfunc(
                 _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
             }
         }
+        Felt252Concrete::Inverse(_) => match inputs {
+            [CoreValue::NonZero(non_zero)] => {
+                if let CoreValue::Felt252(value) = non_zero.as_ref() {
+                    let prime = get_prime();
+                    let value = ((value % &prime) + &prime) % &prime;
+                    if value.is_zero() {
+                        return Err(LibfuncSimulationError::DivisionByZero);
+                    }
+                    let inverse = value.modpow(&(&prime - 2), &prime);
+                    Ok((vec![CoreValue::NonZero(Box::new(CoreValue::Felt252(inverse)))], 0))
+                } else {
+                    Err(LibfuncSimulationError::MemoryLayoutMismatch)
+                }
+            }
+            [_] => Err(LibfuncSimulationError::MemoryLayoutMismatch),
+            _ => Err(LibfuncSimulationError::WrongNumberOfArgs),
+        },
     }
 }
@@ -6,6 +6,7 @@ use thiserror::Error;
 use self::value::CoreValue;
 use crate::edit_state::{put_results, take_args, EditStateError};
 use crate::extensions::core::{CoreConcreteLibfunc, CoreLibfunc, CoreType};
+use crate::extensions::custom::LibfuncExtensions;
 use crate::ids::{FunctionId, VarId};
 use crate::program::{Program, Statement, StatementIdx};
 use crate::program_registry::{ProgramRegistry, ProgramRegistryError};
@@ -24,6 +25,8 @@ pub enum LibfuncSimulationError {
     WrongArgType,
     #[error("Expected a different memory layout")]
     MemoryLayoutMismatch,
+    #[error("Division by zero")]
+    DivisionByZero,
     #[error("Could not resolve requested symbol value")]
     UnresolvedStatementGasInfo,
     #[error("Error occurred during user function call")]
@@ -53,11 +56,33 @@ pub fn run(
     statement_gas_info: &HashMap<StatementIdx, i64>,
     function_id: &FunctionId,
     inputs: Vec<CoreValue>,
+) -> Result<Vec<CoreValue>, SimulationError> {
+    run_with_extensions(
+        program,
+        statement_gas_info,
+        function_id,
+        inputs,
+        &LibfuncExtensions::default(),
+    )
+}
+
+/// Runs a function from the program with the given inputs, supporting the libfuncs of the given
+/// extensions.
+pub fn run_with_extensions(
+    program: &Program,
+    statement_gas_info: &HashMap<StatementIdx, i64>,
+    function_id: &FunctionId,
+    inputs: Vec<CoreValue>,
+    libfunc_extensions: &LibfuncExtensions,
 ) -> Result<Vec<CoreValue>, SimulationError> {
     let context = SimulationContext {
         program,
         statement_gas_info,
-        registry: &ProgramRegistry::new(program)?,
+        registry: &ProgramRegistry::new_with_extensions(
+            program,
+            Default::default(),
+            libfunc_extensions,
+        )?,
     };
     context.simulate_function(function_id, inputs)
 }
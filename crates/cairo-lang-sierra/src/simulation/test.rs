@@ -3,10 +3,10 @@ use num_bigint::BigInt;
 use test_case::test_case;
 
 use super::value::CoreValue::{
-    self, Array, GasBuiltin, NonZero, RangeCheck, Uint128, Uint64, Uninitialized,
+    self, Array, Felt252, GasBuiltin, NonZero, RangeCheck, Uint128, Uint32, Uint64, Uninitialized,
 };
 use super::LibfuncSimulationError::{
-    self, FunctionSimulationError, MemoryLayoutMismatch, WrongNumberOfArgs,
+    self, DivisionByZero, FunctionSimulationError, MemoryLayoutMismatch, WrongNumberOfArgs,
 };
 use super::{core, SimulationError};
 use crate::extensions::core::CoreLibfunc;
@@ -172,10 +172,20 @@ fn simulate_branch(
             Ok(vec![Array(vec![Uint128(4)])]); "array_append([], 4)")]
 #[test_case("array_get", vec![type_arg("u128")], vec![RangeCheck, Array(vec![Uint128(5)]), Uint64(0)]
              => Ok(vec![RangeCheck, Uint128(5)]); "array_get([5], 0)")]
+#[test_case("array_snapshot_multi_pop_front", vec![type_arg("u128")],
+             vec![RangeCheck, Array(vec![Uint128(5), Uint128(6), Uint128(7)]), Uint32(2)]
+             => Ok(vec![RangeCheck, Array(vec![Uint128(7)]), Array(vec![Uint128(5), Uint128(6)])]);
+             "array_snapshot_multi_pop_front([5, 6, 7], 2)")]
+#[test_case("array_snapshot_multi_pop_back", vec![type_arg("u128")],
+             vec![RangeCheck, Array(vec![Uint128(5), Uint128(6), Uint128(7)]), Uint32(2)]
+             => Ok(vec![RangeCheck, Array(vec![Uint128(5)]), Array(vec![Uint128(6), Uint128(7)])]);
+             "array_snapshot_multi_pop_back([5, 6, 7], 2)")]
 #[test_case("array_len", vec![type_arg("u128")], vec![Array(vec![])] =>
             Ok(vec![Uint64(0)]); "array_len([])")]
 #[test_case("u128_safe_divmod", vec![], vec![RangeCheck, Uint128(32), NonZero(Box::new(Uint128(5)))]
              => Ok(vec![RangeCheck, Uint128(6), Uint128(2)]); "u128_safe_divmod(32, 5)")]
+#[test_case("u128_sqrt", vec![], vec![RangeCheck, Uint128(1000)]
+             => Ok(vec![RangeCheck, Uint64(31)]); "u128_sqrt(1000)")]
 #[test_case("u128_const", vec![value_arg(3)], vec![] => Ok(vec![Uint128(3)]);
             "u128_const<3>()")]
 #[test_case("dup", vec![type_arg("u128")], vec![Uint128(24)]
@@ -194,6 +204,20 @@ fn simulate_branch(
              => Ok(vec![]); "function_call<drop_all_inputs>()")]
 #[test_case("function_call", vec![user_func_arg("identity")], vec![Uint128(3), Uint128(5)]
              => Ok(vec![Uint128(3), Uint128(5)]); "function_call<identity>()")]
+#[test_case("felt252_inverse", vec![], vec![NonZero(Box::new(Felt252(BigInt::from(1))))]
+             => Ok(vec![NonZero(Box::new(Felt252(BigInt::from(1))))]); "felt252_inverse(1)")]
+#[test_case("felt252_inverse", vec![], vec![NonZero(Box::new(Felt252(BigInt::from(2))))]
+             => Ok(vec![NonZero(Box::new(Felt252(
+                 "1809251394333065606848661391547535052811553607665798349986546028067936010241"
+                     .parse().unwrap()
+             )))]);
+            "felt252_inverse(2)")]
+#[test_case("felt252_inverse", vec![], vec![NonZero(Box::new(Felt252(BigInt::from(-1))))]
+             => Ok(vec![NonZero(Box::new(Felt252(
+                 "3618502788666131213697322783095070105623107215331596699973092056135872020480"
+                     .parse().unwrap()
+             )))]);
+            "felt252_inverse(minus 1)")]
 fn simulate_none_branch(
     id: &str,
     generic_args: Vec<GenericArg>,
@@ -231,6 +255,9 @@ fn simulate_none_branch(
 #[test_case("finalize_locals", vec![], vec![Uint128(4)] => WrongNumberOfArgs; "finalize_locals(4)")]
 #[test_case("rename", vec![type_arg("u128")], vec![] => WrongNumberOfArgs; "rename<u128>()")]
 #[test_case("jump", vec![], vec![Uint128(4)] => WrongNumberOfArgs; "jump(4)")]
+#[test_case("felt252_inverse", vec![], vec![] => WrongNumberOfArgs; "felt252_inverse()")]
+#[test_case("felt252_inverse", vec![], vec![NonZero(Box::new(Felt252(BigInt::from(0))))]
+             => DivisionByZero; "felt252_inverse(0)")]
 #[test_case("function_call", vec![user_func_arg("unimplemented")], vec![] =>
             FunctionSimulationError(
                 "unimplemented".into(),
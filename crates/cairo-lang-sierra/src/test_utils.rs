@@ -1,5 +1,6 @@
 use bimap::BiMap;
 use itertools::chain;
+use num_bigint::BigInt;
 
 use crate::ids::ConcreteTypeId;
 use crate::program::{ConcreteTypeLongId, GenericArg};
@@ -44,6 +45,11 @@ pub fn build_bijective_mapping() -> BiMap<ConcreteTypeId, ConcreteTypeLongId> {
         as_named_type_long_id("Enum", "NonDupEnum", &["felt252", "RangeCheck"]),
     );
     elements.insert("SnapshotNonDupEnum".into(), as_type_long_id("Snapshot", &["NonDupEnum"]));
+    elements.insert("Unit".into(), as_named_type_long_id("Struct", "Unit", &[]));
+    elements
+        .insert("CLikeEnum".into(), as_named_type_long_id("Enum", "CLikeEnum", &["Unit", "Unit"]));
+    elements.insert("ConstU128".into(), as_const_long_id("u128", 5));
+    elements.insert("ConstFelt252".into(), as_const_long_id("felt252", 7));
     elements
 }
 
@@ -64,3 +70,13 @@ fn as_named_type_long_id(genetic_name: &str, user_name: &str, args: &[&str]) ->
         .collect(),
     }
 }
+
+fn as_const_long_id(type_name: &str, value: i64) -> ConcreteTypeLongId {
+    ConcreteTypeLongId {
+        generic_id: "Const".into(),
+        generic_args: vec![
+            GenericArg::Type(ConcreteTypeId::from(type_name)),
+            GenericArg::Value(BigInt::from(value)),
+        ],
+    }
+}
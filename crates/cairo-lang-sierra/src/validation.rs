@@ -0,0 +1,199 @@
+//! A validation pass over Sierra programs, for tools ingesting Sierra code they did not generate.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use itertools::zip_eq;
+use thiserror::Error;
+
+use crate::extensions::core::{CoreLibfunc, CoreType};
+use crate::extensions::ConcreteLibfunc;
+use crate::ids::{ConcreteTypeId, FunctionId, VarId};
+use crate::program::{BranchTarget, Function, Invocation, Program, Statement, StatementIdx};
+use crate::program_registry::{ProgramRegistry, ProgramRegistryError};
+
+#[cfg(test)]
+#[path = "validation_test.rs"]
+mod test;
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum ValidationError {
+    #[error("{0}")]
+    ProgramRegistryError(Box<ProgramRegistryError>),
+    #[error("The parameters of function {0} do not match its signature")]
+    FunctionParamsMismatch(FunctionId),
+    #[error("The entry point of function {0} is out of bounds")]
+    FunctionEntryPointOutOfBounds(FunctionId),
+    #[error("#{statement_idx}: Branch target is out of bounds")]
+    BranchTargetOutOfBounds { statement_idx: StatementIdx },
+    #[error("#{statement_idx}: Invocation mismatched to libfunc")]
+    LibfuncInvocationMismatch { statement_idx: StatementIdx },
+    #[error("#{statement_idx}: Variable {var_id} is not defined")]
+    MissingVariable { statement_idx: StatementIdx, var_id: VarId },
+    #[error("#{statement_idx}: Variable {var_id} is already defined")]
+    VariableOverride { statement_idx: StatementIdx, var_id: VarId },
+    #[error("#{statement_idx}: Variable {var_id} is not of the expected type {expected}")]
+    VariableTypeMismatch { statement_idx: StatementIdx, var_id: VarId, expected: ConcreteTypeId },
+    #[error("#{statement_idx}: Reached with different variables from different statements")]
+    InconsistentVariables { statement_idx: StatementIdx },
+    #[error("#{statement_idx}: Return does not match the signature of function {func_id}")]
+    ReturnMismatch { statement_idx: StatementIdx, func_id: FunctionId },
+    #[error("#{statement_idx}: Variables are left unused on return")]
+    DanglingVariables { statement_idx: StatementIdx },
+}
+
+/// The types of the variables available at a statement.
+type Environment = HashMap<VarId, ConcreteTypeId>;
+
+/// Validates a Sierra program.
+///
+/// Checks that the declarations are consistent and specialize successfully, that all the ids used
+/// by the program resolve, and that every function's statements are well typed - from the
+/// parameters at its entry point to the returned values at each of its return statements.
+pub fn validate_program(program: &Program) -> Result<(), Box<ValidationError>> {
+    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(program)
+        .map_err(ValidationError::ProgramRegistryError)?;
+    for func in &program.funcs {
+        validate_function(program, &registry, func)?;
+    }
+    Ok(())
+}
+
+/// Validates the statements reachable from the entry point of a function.
+fn validate_function(
+    program: &Program,
+    registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+    func: &Function,
+) -> Result<(), Box<ValidationError>> {
+    if func.params.len() != func.signature.param_types.len()
+        || !zip_eq(&func.params, &func.signature.param_types).all(|(param, ty)| param.ty == *ty)
+    {
+        return Err(Box::new(ValidationError::FunctionParamsMismatch(func.id.clone())));
+    }
+    if func.entry_point.0 >= program.statements.len() {
+        return Err(Box::new(ValidationError::FunctionEntryPointOutOfBounds(func.id.clone())));
+    }
+    let mut entry_environment = Environment::new();
+    for param in &func.params {
+        add_var(&mut entry_environment, func.entry_point, &param.id, &param.ty)?;
+    }
+    let mut environments = HashMap::<StatementIdx, Environment>::new();
+    let mut pending = vec![(func.entry_point, entry_environment)];
+    while let Some((statement_idx, mut environment)) = pending.pop() {
+        match environments.entry(statement_idx) {
+            Entry::Occupied(entry) if *entry.get() != environment => {
+                return Err(Box::new(ValidationError::InconsistentVariables { statement_idx }));
+            }
+            Entry::Occupied(_) => continue,
+            Entry::Vacant(entry) => {
+                entry.insert(environment.clone());
+            }
+        }
+        match &program.statements[statement_idx.0] {
+            Statement::Return(vars) => {
+                if vars.len() != func.signature.ret_types.len() {
+                    return Err(Box::new(ValidationError::ReturnMismatch {
+                        statement_idx,
+                        func_id: func.id.clone(),
+                    }));
+                }
+                for (var_id, ty) in zip_eq(vars, &func.signature.ret_types) {
+                    take_var(&mut environment, statement_idx, var_id, ty)?;
+                }
+                if !environment.is_empty() {
+                    return Err(Box::new(ValidationError::DanglingVariables { statement_idx }));
+                }
+            }
+            Statement::Invocation(invocation) => {
+                for (target, branch_environment) in
+                    validate_invocation(registry, statement_idx, invocation, environment)?
+                {
+                    let target_idx = statement_idx.next(&target);
+                    if target_idx.0 >= program.statements.len() {
+                        return Err(Box::new(ValidationError::BranchTargetOutOfBounds {
+                            statement_idx,
+                        }));
+                    }
+                    pending.push((target_idx, branch_environment));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Validates an invocation given the environment before it, and returns the target and the
+/// environment after it of each of its branches.
+fn validate_invocation(
+    registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+    statement_idx: StatementIdx,
+    invocation: &Invocation,
+    mut environment: Environment,
+) -> Result<Vec<(BranchTarget, Environment)>, Box<ValidationError>> {
+    let libfunc = registry
+        .get_libfunc(&invocation.libfunc_id)
+        .map_err(ValidationError::ProgramRegistryError)?;
+    let output_types = libfunc.output_types();
+    if invocation.args.len() != libfunc.param_signatures().len()
+        || !itertools::equal(
+            invocation.branches.iter().map(|branch| branch.results.len()),
+            output_types.iter().map(|types| types.len()),
+        )
+        || libfunc.fallthrough().is_some_and(|fallthrough| {
+            invocation.branches[fallthrough].target != BranchTarget::Fallthrough
+        })
+    {
+        return Err(Box::new(ValidationError::LibfuncInvocationMismatch { statement_idx }));
+    }
+    for (var_id, param) in zip_eq(&invocation.args, libfunc.param_signatures()) {
+        take_var(&mut environment, statement_idx, var_id, &param.ty)?;
+    }
+    zip_eq(&invocation.branches, output_types)
+        .map(|(branch, types)| {
+            let mut branch_environment = environment.clone();
+            for (var_id, ty) in zip_eq(&branch.results, &types) {
+                add_var(&mut branch_environment, statement_idx, var_id, ty)?;
+            }
+            Ok((branch.target.clone(), branch_environment))
+        })
+        .collect()
+}
+
+/// Adds a variable of type `ty` to the environment.
+fn add_var(
+    environment: &mut Environment,
+    statement_idx: StatementIdx,
+    var_id: &VarId,
+    ty: &ConcreteTypeId,
+) -> Result<(), Box<ValidationError>> {
+    match environment.entry(var_id.clone()) {
+        Entry::Occupied(_) => Err(Box::new(ValidationError::VariableOverride {
+            statement_idx,
+            var_id: var_id.clone(),
+        })),
+        Entry::Vacant(entry) => {
+            entry.insert(ty.clone());
+            Ok(())
+        }
+    }
+}
+
+/// Removes a variable from the environment, checking that it is of type `ty`.
+fn take_var(
+    environment: &mut Environment,
+    statement_idx: StatementIdx,
+    var_id: &VarId,
+    ty: &ConcreteTypeId,
+) -> Result<(), Box<ValidationError>> {
+    let actual_ty = environment.remove(var_id).ok_or_else(|| {
+        Box::new(ValidationError::MissingVariable { statement_idx, var_id: var_id.clone() })
+    })?;
+    if actual_ty != *ty {
+        return Err(Box::new(ValidationError::VariableTypeMismatch {
+            statement_idx,
+            var_id: var_id.clone(),
+            expected: ty.clone(),
+        }));
+    }
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+use test_case::test_case;
+
+use super::validate_program;
+use crate::ProgramParser;
+
+/// The declarations used by the tested programs.
+const DECLARATIONS: &str = indoc! {"
+    type felt252 = felt252;
+    type NonZeroFelt252 = NonZero<felt252>;
+
+    libfunc branch_align = branch_align;
+    libfunc felt252_add = felt252_add;
+    libfunc felt252_is_zero = felt252_is_zero;
+    libfunc felt252_dup = dup<felt252>;
+    libfunc felt252_drop = drop<felt252>;
+    libfunc drop_nz_felt252 = drop<NonZeroFelt252>;
+    libfunc store_temp_felt252 = store_temp<felt252>;
+    libfunc jump = jump;
+"};
+
+/// Validates a program with [DECLARATIONS], and returns the error message if invalid.
+fn validate(code: &str) -> Result<(), String> {
+    let program = ProgramParser::new().parse(&format!("{DECLARATIONS}\n{code}")).unwrap();
+    validate_program(&program).map_err(|error| error.to_string())
+}
+
+#[test_case(indoc! {"
+                felt252_dup(a) -> (a, b);
+                felt252_add(a, b) -> (c);
+                store_temp_felt252(c) -> (c);
+                return(c);
+
+                double@0(a: felt252) -> (felt252);
+            "}, Ok(());
+            "valid")]
+#[test_case(indoc! {"
+                felt252_dup(a) -> (a, b);
+                felt252_is_zero(b) { fallthrough() 5(b) };
+                branch_align() -> ();
+                store_temp_felt252(a) -> (a);
+                return(a);
+                branch_align() -> ();
+                drop_nz_felt252(b) -> ();
+                store_temp_felt252(a) -> (a);
+                return(a);
+
+                identity@0(a: felt252) -> (felt252);
+            "}, Ok(());
+            "valid with branches")]
+#[test_case(indoc! {"
+                felt252_sub(a, a) -> (a);
+                return(a);
+
+                foo@0(a: felt252) -> (felt252);
+            "}, Err("Could not find the requested libfunc".into());
+            "unknown libfunc")]
+#[test_case(indoc! {"
+                return(a);
+
+                foo@0(a: NonZeroFelt252) -> (felt252);
+            "}, Err("#0: Variable a is not of the expected type felt252".into());
+            "wrong return type")]
+#[test_case(indoc! {"
+                return(a);
+
+                foo@1(a: felt252) -> (felt252);
+            "}, Err("The entry point of function foo is out of bounds".into());
+            "entry point out of bounds")]
+#[test_case(indoc! {"
+                jump() { 5() };
+
+                foo@0() -> ();
+            "}, Err("#0: Branch target is out of bounds".into());
+            "branch target out of bounds")]
+#[test_case(indoc! {"
+                felt252_drop(a) -> ();
+
+                foo@0(a: felt252) -> ();
+            "}, Err("#0: Branch target is out of bounds".into());
+            "fallthrough out of bounds")]
+#[test_case(indoc! {"
+                felt252_add(a) -> (a);
+                return(a);
+
+                foo@0(a: felt252) -> (felt252);
+            "}, Err("#0: Invocation mismatched to libfunc".into());
+            "wrong number of args")]
+#[test_case(indoc! {"
+                felt252_is_zero(a) { 1() fallthrough(b) };
+                return();
+
+                foo@0(a: felt252) -> ();
+            "}, Err("#0: Invocation mismatched to libfunc".into());
+            "misplaced fallthrough")]
+#[test_case(indoc! {"
+                store_temp_felt252(b) -> (b);
+                return(b);
+
+                foo@0(a: felt252) -> (felt252);
+            "}, Err("#0: Variable b is not defined".into());
+            "missing variable")]
+#[test_case(indoc! {"
+                felt252_dup(a) -> (a, b);
+                felt252_dup(a) -> (a, b);
+                return(a);
+
+                foo@0(a: felt252) -> (felt252);
+            "}, Err("#1: Variable b is already defined".into());
+            "variable override")]
+#[test_case(indoc! {"
+                felt252_is_zero(a) { fallthrough() 3(b) };
+                branch_align() -> ();
+                drop_nz_felt252(b) -> ();
+                branch_align() -> ();
+                drop_nz_felt252(b) -> ();
+                return();
+
+                foo@0(a: felt252) -> ();
+            "}, Err("#2: Variable b is not defined".into());
+            "variable of another branch")]
+#[test_case(indoc! {"
+                felt252_is_zero(a) { fallthrough() 2(b) };
+                jump() { 2() };
+                drop_nz_felt252(b) -> ();
+                return();
+
+                foo@0(a: felt252) -> ();
+            "}, Err("#2: Reached with different variables from different statements".into());
+            "inconsistent variables")]
+#[test_case(indoc! {"
+                return(a);
+
+                foo@0(a: felt252) -> ();
+            "}, Err("#0: Return does not match the signature of function foo".into());
+            "wrong number of returned values")]
+#[test_case(indoc! {"
+                return();
+
+                foo@0(a: felt252) -> ();
+            "}, Err("#0: Variables are left unused on return".into());
+            "dangling variables")]
+fn test_validate_program(code: &str, expected: Result<(), String>) {
+    assert_eq!(validate(code), expected);
+}
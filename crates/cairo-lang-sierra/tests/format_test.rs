@@ -150,3 +150,52 @@ fn labeled_program_display_test() {
         .to_string())
     );
 }
+
+// Testing the pretty display of a program, making sure it parses back to the same program.
+#[test]
+fn pretty_program_test() {
+    let parser = cairo_lang_sierra::ProgramParser::new();
+    let program = parser
+        .parse(indoc! {"
+            type felt252 = felt252;
+            libfunc store_temp_felt252 = store_temp<felt252>;
+            libfunc call_double = function_call<user@double>;
+            store_temp_felt252(a) -> (a);
+            call_double(a) -> (a);
+            return(a);
+            felt252_dup(a) -> (a, b);
+            felt252_add(a, b) -> (a);
+            return(a);
+            main@0(a: felt252) -> (felt252);
+            double@3(a: felt252) -> (felt252);
+        "})
+        .unwrap();
+    let pretty = program.pretty().to_string();
+    assert_eq!(
+        pretty,
+        indoc! {"
+            // Types.
+            type felt252 = felt252;
+
+            // Libfuncs.
+            libfunc store_temp_felt252 = store_temp<felt252>;
+            libfunc call_double = function_call<user@double>;
+
+            // Statements.
+            // main
+            store_temp_felt252(a) -> (a); // 0
+            call_double(a) -> (a); // 1
+            return(a); // 2
+
+            // double
+            felt252_dup(a) -> (a, b); // 3
+            felt252_add(a, b) -> (a); // 4
+            return(a); // 5
+
+            // Functions.
+            main@0(a: felt252) -> (felt252);
+            double@3(a: felt252) -> (felt252);
+        "}
+    );
+    assert_eq!(parser.parse(&pretty), Ok(program));
+}
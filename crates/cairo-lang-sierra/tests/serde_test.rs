@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 
+use cairo_lang_sierra::binary::{decode_program, encode_program};
 use cairo_lang_sierra::program::VersionedProgram;
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
@@ -54,3 +55,11 @@ fn serde_json_from_parser_test(example_name: &str) {
         "Could not serialize and deserialize VersionedProgram."
     );
 }
+
+// Encode the parsed code in the binary format and decode it, ensuring the program is retained.
+#[test_case("fib_jumps")]
+#[test_case("fib_no_gas")]
+fn binary_from_parser_test(example_name: &str) {
+    let program = get_test_program_from_sierra(example_name).into_v1().unwrap().program;
+    assert_eq!(decode_program(&encode_program(&program)), Ok(program));
+}
@@ -20,7 +20,7 @@ use cairo_lang_sierra::extensions::structure::StructType;
 use cairo_lang_sierra::extensions::NamedType;
 use cairo_lang_sierra::ids::{ConcreteTypeId, GenericTypeId};
 use cairo_lang_sierra::program::{ConcreteTypeLongId, GenericArg, TypeDeclaration};
-use cairo_lang_sierra_to_casm::compiler::CompilationError;
+use cairo_lang_sierra_to_casm::compiler::{AssembledCairoProgram, CompilationError};
 use cairo_lang_sierra_to_casm::metadata::{
     calc_metadata, MetadataComputationConfig, MetadataError,
 };
@@ -285,6 +285,7 @@ impl CasmContractClass {
                 .collect(),
             linear_gas_solver: false,
             linear_ap_change_solver: false,
+            libfunc_extensions: Default::default(),
         };
         let metadata = calc_metadata(&program, metadata_computation_config)?;
 
@@ -292,20 +293,17 @@ impl CasmContractClass {
         let cairo_program =
             cairo_lang_sierra_to_casm::compiler::compile(&program, &metadata, gas_usage_check)?;
 
-        let mut bytecode = vec![];
-        let mut hints = vec![];
-        for instruction in cairo_program.instructions {
-            if !instruction.hints.is_empty() {
-                hints.push((bytecode.len(), instruction.hints.clone()))
-            }
-            bytecode.extend(instruction.assemble().encode().iter().map(|big_int| {
+        let AssembledCairoProgram { bytecode, hints } = cairo_program.assemble();
+        let bytecode = bytecode
+            .iter()
+            .map(|big_int| {
                 let (_q, reminder) = big_int.magnitude().div_rem(&prime);
 
                 BigUintAsHex {
                     value: if big_int.is_negative() { &prime - reminder } else { reminder },
                 }
-            }))
-        }
+            })
+            .collect();
 
         let builtin_types = UnorderedHashSet::<GenericTypeId>::from_iter([
             RangeCheckType::id(),
@@ -8,6 +8,7 @@ use crate::casm_contract_class::{BigUintAsHex, CasmContractClass, StarknetSierra
 use crate::contract_class::ContractClass;
 use crate::test_utils::{
     get_contract_file_name_from_path, get_example_file_path, get_test_contract,
+    get_test_contract_ex,
 };
 
 #[test_case("test_contract::test_contract")]
@@ -59,3 +60,20 @@ fn test_casm_contract_from_contract_class_from_contracts_crate(example_contract_
         serde_json::to_string_pretty(&casm_contract).unwrap() + "\n",
     );
 }
+
+/// Tests that eliminating the redundant `dup`, `drop` and `rename` invocations of a contract in the
+/// contract_crate shrinks its Sierra code, which still compiles to casm.
+#[test_case("account::account")]
+#[test_case("erc20::erc_20")]
+#[test_case("token_bridge::token_bridge")]
+fn test_casm_contract_without_redundant_dup_drop(example_contract_path: &str) {
+    let path = format!("cairo_level_tests::contracts::{example_contract_path}");
+    let contract_class = get_test_contract(&path);
+    let optimized_contract_class = get_test_contract_ex(&path, true);
+    assert!(
+        optimized_contract_class.extract_sierra_program().unwrap().statements.len()
+            < contract_class.extract_sierra_program().unwrap().statements.len()
+    );
+    let add_pythonic_hints = false;
+    CasmContractClass::from_contract_class(optimized_contract_class, add_pythonic_hints).unwrap();
+}
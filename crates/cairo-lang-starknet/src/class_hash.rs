@@ -0,0 +1,106 @@
+//! Computation of the hashes identifying contract classes on Starknet, as defined in
+//! <https://docs.starknet.io/documentation/architecture_and_concepts/Smart_Contracts/class-hash/>.
+
+use num_bigint::BigUint;
+use starknet_crypto::{poseidon_hash_many, FieldElement};
+
+use crate::casm_contract_class::{CasmContractClass, CasmContractEntryPoint};
+use crate::contract::starknet_keccak;
+use crate::contract_class::{ContractClass, ContractEntryPoint};
+
+#[cfg(test)]
+#[path = "class_hash_test.rs"]
+mod test;
+
+/// The prefix of the version of a contract class, as encoded into its hash.
+const CONTRACT_CLASS_VERSION_PREFIX: &str = "CONTRACT_CLASS_V";
+/// The version of a compiled contract class, as encoded into its hash.
+const COMPILED_CLASS_VERSION: &str = "COMPILED_CLASS_V1";
+
+/// Returns the hash of a contract class - the class hash used for declaring it.
+///
+/// `abi` is the ABI string of the declare transaction, which is hashed as is. The `abi` field of
+/// the class is ignored, as serializing it again may not reproduce the declared string exactly.
+pub fn compute_class_hash(contract_class: &ContractClass, abi: &str) -> BigUint {
+    let version =
+        format!("{CONTRACT_CLASS_VERSION_PREFIX}{}", contract_class.contract_class_version);
+    let entry_points = &contract_class.entry_points_by_type;
+    let hash = poseidon_hash_many(&[
+        short_string(&version),
+        entry_points_hash(&entry_points.external),
+        entry_points_hash(&entry_points.l1_handler),
+        entry_points_hash(&entry_points.constructor),
+        to_field_element(&starknet_keccak(abi.as_bytes())),
+        poseidon_hash_many(
+            &contract_class
+                .sierra_program
+                .iter()
+                .map(|value| to_field_element(&value.value))
+                .collect::<Vec<_>>(),
+        ),
+    ]);
+    BigUint::from_bytes_be(&hash.to_bytes_be())
+}
+
+/// Returns the hash of a compiled contract class - the compiled class hash committed to when
+/// declaring the contract class it was compiled from.
+pub fn compute_compiled_class_hash(compiled_class: &CasmContractClass) -> BigUint {
+    let entry_points = &compiled_class.entry_points_by_type;
+    let hash = poseidon_hash_many(&[
+        short_string(COMPILED_CLASS_VERSION),
+        compiled_entry_points_hash(&entry_points.external),
+        compiled_entry_points_hash(&entry_points.l1_handler),
+        compiled_entry_points_hash(&entry_points.constructor),
+        poseidon_hash_many(
+            &compiled_class
+                .bytecode
+                .iter()
+                .map(|value| to_field_element(&value.value))
+                .collect::<Vec<_>>(),
+        ),
+    ]);
+    BigUint::from_bytes_be(&hash.to_bytes_be())
+}
+
+/// Returns the hash of the entry points of a contract class, of a single type.
+fn entry_points_hash(entry_points: &[ContractEntryPoint]) -> FieldElement {
+    poseidon_hash_many(
+        &entry_points
+            .iter()
+            .flat_map(|entry_point| {
+                [
+                    to_field_element(&entry_point.selector),
+                    FieldElement::from(entry_point.function_idx),
+                ]
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Returns the hash of the entry points of a compiled contract class, of a single type.
+fn compiled_entry_points_hash(entry_points: &[CasmContractEntryPoint]) -> FieldElement {
+    poseidon_hash_many(
+        &entry_points
+            .iter()
+            .flat_map(|entry_point| {
+                let builtins: Vec<_> =
+                    entry_point.builtins.iter().map(|builtin| short_string(builtin)).collect();
+                [
+                    to_field_element(&entry_point.selector),
+                    FieldElement::from(entry_point.offset),
+                    poseidon_hash_many(&builtins),
+                ]
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Converts a value known to be a valid felt252 to a field element.
+fn to_field_element(value: &BigUint) -> FieldElement {
+    FieldElement::from_byte_slice_be(&value.to_bytes_be()).unwrap()
+}
+
+/// Encodes an ASCII string of at most 31 characters as a field element.
+fn short_string(value: &str) -> FieldElement {
+    FieldElement::from_byte_slice_be(value.as_bytes()).unwrap()
+}
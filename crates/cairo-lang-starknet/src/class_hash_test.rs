@@ -0,0 +1,142 @@
+use cairo_lang_utils::bigint::BigUintAsHex;
+use num_bigint::BigUint;
+use starknet_crypto::{poseidon_hash_many, FieldElement};
+use test_case::test_case;
+
+use super::{compute_class_hash, compute_compiled_class_hash};
+use crate::casm_contract_class::{
+    CasmContractClass, CasmContractEntryPoint, CasmContractEntryPoints,
+};
+use crate::contract::starknet_keccak;
+use crate::contract_class::{ContractClass, ContractEntryPoint, ContractEntryPoints};
+use crate::test_utils::get_example_file_path;
+
+/// Reads the contract class and the compiled contract class of the given example from their JSON
+/// files.
+fn read_classes(example_file_name: &str) -> (ContractClass, CasmContractClass) {
+    let read = |suffix: &str| {
+        std::fs::read_to_string(get_example_file_path(&format!("{example_file_name}.{suffix}")))
+            .unwrap()
+    };
+    (
+        serde_json::from_str(&read("contract_class.json")).unwrap(),
+        serde_json::from_str(&read("compiled_contract_class.json")).unwrap(),
+    )
+}
+
+/// Formats a hash as a hex string.
+fn hex(hash: BigUint) -> String {
+    format!("{hash:#x}")
+}
+
+/// Converts a hex string to a field element.
+fn felt(value: &str) -> FieldElement {
+    FieldElement::from_hex_be(value).unwrap()
+}
+
+/// The selector of `__execute__`, as used on Starknet.
+const EXECUTE_SELECTOR: &str = "0x15d40a3d6ca2ac30f4031e42be28da9b056fef9bb7357ac5e85627ee876e5ad";
+
+#[test]
+fn test_class_hash_matches_spec() {
+    let abi = r#"[{"type": "function", "name": "__execute__", "inputs": [], "outputs": []}]"#;
+    let contract_class = ContractClass {
+        sierra_program: [1_u32, 2, 3].map(|value| BigUintAsHex { value: value.into() }).to_vec(),
+        sierra_program_debug_info: None,
+        contract_class_version: "0.1.0".into(),
+        entry_points_by_type: ContractEntryPoints {
+            external: vec![ContractEntryPoint {
+                selector: BigUint::from_bytes_be(&felt(EXECUTE_SELECTOR).to_bytes_be()),
+                function_idx: 2,
+            }],
+            l1_handler: vec![],
+            constructor: vec![],
+        },
+        // Ignored by the hash, which uses the given ABI string.
+        abi: None,
+    };
+    // The class hash, composed by the definition in the Starknet documentation.
+    let expected = poseidon_hash_many(&[
+        // "CONTRACT_CLASS_V0.1.0" as a short string.
+        felt("0x434f4e54524143545f434c4153535f56302e312e30"),
+        poseidon_hash_many(&[felt(EXECUTE_SELECTOR), felt("0x2")]),
+        poseidon_hash_many(&[]),
+        poseidon_hash_many(&[]),
+        felt(&hex(starknet_keccak(abi.as_bytes()))),
+        poseidon_hash_many(&[felt("0x1"), felt("0x2"), felt("0x3")]),
+    ]);
+    assert_eq!(
+        compute_class_hash(&contract_class, abi),
+        BigUint::from_bytes_be(&expected.to_bytes_be())
+    );
+}
+
+#[test]
+fn test_compiled_class_hash_matches_spec() {
+    let compiled_class = CasmContractClass {
+        prime: Default::default(),
+        compiler_version: Default::default(),
+        bytecode: [1_u32, 2].map(|value| BigUintAsHex { value: value.into() }).to_vec(),
+        hints: vec![],
+        pythonic_hints: None,
+        entry_points_by_type: CasmContractEntryPoints {
+            external: vec![CasmContractEntryPoint {
+                selector: BigUint::from_bytes_be(&felt(EXECUTE_SELECTOR).to_bytes_be()),
+                offset: 7,
+                builtins: vec!["range_check".into()],
+            }],
+            l1_handler: vec![],
+            constructor: vec![],
+        },
+    };
+    // The compiled class hash, composed by the definition in the Starknet documentation.
+    let expected = poseidon_hash_many(&[
+        // "COMPILED_CLASS_V1" as a short string.
+        felt("0x434f4d50494c45445f434c4153535f5631"),
+        poseidon_hash_many(&[
+            felt(EXECUTE_SELECTOR),
+            felt("0x7"),
+            // "range_check" as a short string.
+            poseidon_hash_many(&[felt("0x72616e67655f636865636b")]),
+        ]),
+        poseidon_hash_many(&[]),
+        poseidon_hash_many(&[]),
+        poseidon_hash_many(&[felt("0x1"), felt("0x2")]),
+    ]);
+    assert_eq!(
+        compute_compiled_class_hash(&compiled_class),
+        BigUint::from_bytes_be(&expected.to_bytes_be())
+    );
+}
+
+#[test_case(
+    "account__account",
+    "0x46565f343d2c29a97ae28d678625f33fcb654b1844be5d5264e3846da21aa52",
+    "0x31d7293bc78fc13b9d0d9a657169e3d80fe544b46e54613f4363854a1c5865f"
+)]
+#[test_case(
+    "minimal_contract__minimal_contract",
+    "0x2c083546be9af2b674b9875d1640b45d3f012972473b7975f4caa2de3875192",
+    "0x82367c58fdfdfe74f67d69d9664ae6d24cf16b73a309f723a634e70b23d4ad"
+)]
+fn test_class_hashes(
+    example_file_name: &str,
+    expected_class_hash: &str,
+    expected_compiled_class_hash: &str,
+) {
+    let (contract_class, compiled_class) = read_classes(example_file_name);
+    // Hashed with an empty ABI string, as the examples don't keep the declared one.
+    assert_eq!(hex(compute_class_hash(&contract_class, "")), expected_class_hash);
+    assert_eq!(hex(compute_compiled_class_hash(&compiled_class)), expected_compiled_class_hash);
+}
+
+#[test]
+fn test_class_hashes_depend_on_entry_points() {
+    let (mut contract_class, mut compiled_class) = read_classes("account__account");
+    let class_hash = compute_class_hash(&contract_class, "");
+    let compiled_class_hash = compute_compiled_class_hash(&compiled_class);
+    contract_class.entry_points_by_type.external.swap(0, 1);
+    compiled_class.entry_points_by_type.external[0].builtins.pop();
+    assert_ne!(compute_class_hash(&contract_class, ""), class_hash);
+    assert_ne!(compute_compiled_class_hash(&compiled_class), compiled_class_hash);
+}
@@ -6,15 +6,18 @@ use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_compiler::project::setup_project;
 use cairo_lang_compiler::CompilerConfig;
 use cairo_lang_defs::ids::TopLevelLanguageElementId;
-use cairo_lang_diagnostics::ToOption;
+use cairo_lang_diagnostics::{Maybe, ToOption};
 use cairo_lang_filesystem::ids::CrateId;
 use cairo_lang_lowering::db::LoweringGroup;
 use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
 use cairo_lang_sierra as sierra;
+use cairo_lang_sierra::dead_code::eliminate_dead_code;
+use cairo_lang_sierra::dup_drop::eliminate_redundant_dup_drop;
 use cairo_lang_sierra_generator::canonical_id_replacer::CanonicalReplacer;
 use cairo_lang_sierra_generator::db::SierraGenGroup;
 use cairo_lang_sierra_generator::replace_ids::{replace_sierra_ids_in_program, SierraIdReplacer};
 use cairo_lang_utils::bigint::{deserialize_big_uint, serialize_big_uint, BigUintAsHex};
+use cairo_lang_utils::Upcast;
 use itertools::{chain, Itertools};
 use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
@@ -193,12 +196,25 @@ fn compile_contract_with_prepared_and_checked_db(
 ) -> Result<ContractClass> {
     let SemanticEntryPoints { external, l1_handler, constructor } =
         extract_semantic_entrypoints(db, contract)?;
+    let entry_point_functions =
+        chain!(&external, &l1_handler, &constructor).map(|f| f.value).collect_vec();
     let mut sierra_program = db
-        .get_sierra_program_for_functions(
-            chain!(&external, &l1_handler, &constructor).map(|f| f.value).collect(),
-        )
+        .get_sierra_program_for_functions(entry_point_functions.clone())
         .to_option()
         .with_context(|| "Compilation failed without any diagnostics.")?;
+    let entry_point_sierra_ids = entry_point_functions
+        .into_iter()
+        .map(|f| f.function_id(db.upcast()).map(|id| db.intern_sierra_function(id)))
+        .collect::<Maybe<Vec<_>>>()
+        .to_option()
+        .with_context(|| "Compilation failed without any diagnostics.")?;
+    if compiler_config.eliminate_redundant_dup_drop {
+        sierra_program = Arc::new(eliminate_redundant_dup_drop(&sierra_program));
+    }
+    let mut sierra_program = Arc::new(
+        eliminate_dead_code(&sierra_program, &entry_point_sierra_ids)
+            .with_context(|| "Failed eliminating dead code from the Sierra program.")?,
+    );
 
     if compiler_config.replace_ids {
         sierra_program = Arc::new(replace_sierra_ids_in_program(db, &sierra_program));
@@ -12,13 +12,15 @@ mod aliased;
 pub mod allowed_libfuncs;
 mod analyzer;
 pub mod casm_contract_class;
+pub mod class_hash;
 pub mod compiler_version;
 pub mod contract;
 pub mod contract_class;
-mod felt252_serde;
+pub mod felt252_serde;
 mod felt252_vec_compression;
 pub mod inline_macros;
 pub mod plugin;
+pub mod unused_externals;
 
 /// Get the suite of plugins for compilation with StarkNet.
 pub fn starknet_plugin_suite() -> PluginSuite {
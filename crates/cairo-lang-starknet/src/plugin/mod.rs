@@ -75,4 +75,8 @@ impl MacroPlugin for StarkNetPlugin {
             SUBSTORAGE_ATTR.to_string(),
         ]
     }
+
+    fn declared_derives(&self) -> Vec<String> {
+        vec![EVENT_TRAIT.to_string(), STORE_TRAIT.to_string()]
+    }
 }
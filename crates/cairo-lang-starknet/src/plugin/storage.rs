@@ -381,6 +381,7 @@ fn handle_simple_storage_member(address: &str, starknet_module_kind: StarknetMod
                     )
                 )
             }}
+            #[storage_write]
             fn write(ref self: {member_state_name}, value: $type_path$) {{
                 // Only address_domain 0 is currently supported.
                 let address_domain = 0_u32;
@@ -54,6 +54,15 @@ pub static SHARED_DB_WITH_CONTRACTS: Lazy<Mutex<RootDatabase>> = Lazy::new(|| {
 
 /// Returns the compiled test contract from the contracts crate, with replaced ids.
 pub fn get_test_contract(example_file_name: &str) -> crate::contract_class::ContractClass {
+    get_test_contract_ex(example_file_name, false)
+}
+
+/// Returns the compiled test contract from the contracts crate, with replaced ids, and optionally
+/// without the redundant `dup`, `drop` and `rename` invocations.
+pub fn get_test_contract_ex(
+    example_file_name: &str,
+    eliminate_redundant_dup_drop: bool,
+) -> crate::contract_class::ContractClass {
     let locked_db = test_lock(&SHARED_DB_WITH_CONTRACTS);
     let db = locked_db.snapshot();
     drop(locked_db);
@@ -81,6 +90,7 @@ pub fn get_test_contract(example_file_name: &str) -> crate::contract_class::Cont
             replace_ids: true,
             allowed_libfuncs_list_name: Some(BUILTIN_ALL_LIBFUNCS_LIST.to_string()),
             diagnostics_reporter,
+            eliminate_redundant_dup_drop,
         },
     )
     .expect("compile_path failed")
@@ -0,0 +1,172 @@
+use cairo_lang_defs::ids::{FunctionWithBodyId, LanguageElementId, ModuleId};
+use cairo_lang_defs::plugin::PluginDiagnostic;
+use cairo_lang_filesystem::ids::CrateId;
+use cairo_lang_semantic::corelib::core_crate;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::items::attribute::SemanticQueryAttrs;
+use cairo_lang_semantic::plugin::AnalyzerPlugin;
+use cairo_lang_syntax::node::{Terminal, TypedSyntaxNode};
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use itertools::chain;
+
+use crate::contract::{module_contract, ContractDeclaration};
+use crate::plugin::consts::{
+    ABI_ATTR, ABI_ATTR_EMBED_V0_ARG, ABI_ATTR_PER_ITEM_ARG, EXTERNAL_ATTR, INTERFACE_ATTR,
+};
+
+#[cfg(test)]
+#[path = "unused_externals_test.rs"]
+mod test;
+
+/// The attribute marking test functions.
+const TEST_ATTR: &str = "test";
+
+/// Plugin to add warnings for external contract functions that are neither declared by an
+/// interface trait nor used by any test in the workspace.
+#[derive(Default, Debug)]
+pub struct UnusedExternalsAnalyzer;
+
+impl AnalyzerPlugin for UnusedExternalsAnalyzer {
+    fn diagnostics(&self, db: &dyn SemanticGroup, module_id: ModuleId) -> Vec<PluginDiagnostic> {
+        let Some(contract) = module_contract(db, module_id) else {
+            return vec![];
+        };
+        let syntax_db = db.upcast();
+        find_unused_external_functions(db, &[contract])
+            .into_iter()
+            .map(|function_id| {
+                let declaration = match function_id {
+                    FunctionWithBodyId::Free(free_function_id) => free_function_id
+                        .stable_ptr(db.upcast())
+                        .lookup(syntax_db)
+                        .declaration(syntax_db),
+                    FunctionWithBodyId::Impl(impl_function_id) => impl_function_id
+                        .stable_ptr(db.upcast())
+                        .lookup(syntax_db)
+                        .declaration(syntax_db),
+                    FunctionWithBodyId::Trait(trait_function_id) => trait_function_id
+                        .stable_ptr(db.upcast())
+                        .lookup(syntax_db)
+                        .declaration(syntax_db),
+                };
+                let name = declaration.name(syntax_db);
+                PluginDiagnostic::warning(
+                    name.stable_ptr().untyped(),
+                    format!(
+                        "External function `{}` is not declared by an interface and is not used \
+                         by any test.",
+                        name.text(syntax_db)
+                    ),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Returns the external functions of the given contracts that are neither declared by an interface
+/// trait nor reachable from any test function in the workspace.
+/// Functions only invoked through a raw `call_contract_syscall` with their selector are not
+/// recognized as used.
+pub fn find_unused_external_functions(
+    db: &dyn SemanticGroup,
+    contracts: &[ContractDeclaration],
+) -> Vec<FunctionWithBodyId> {
+    let contract_crates =
+        contracts.iter().map(|contract| contract.module_id().owning_crate(db.upcast()));
+    let used_functions = functions_reachable_from_tests(db, chain!(db.crates(), contract_crates));
+    contracts
+        .iter()
+        .flat_map(|contract| contract_non_interface_externals(db, contract))
+        .filter(|function_id| !used_functions.contains(function_id))
+        .collect()
+}
+
+/// Returns the external functions of a contract that do not implement an interface trait.
+fn contract_non_interface_externals(
+    db: &dyn SemanticGroup,
+    contract: &ContractDeclaration,
+) -> Vec<FunctionWithBodyId> {
+    let module_id = contract.module_id();
+    let mut externals = vec![];
+    for free_function_id in db.module_free_functions_ids(module_id).unwrap_or_default().iter() {
+        let function_id = FunctionWithBodyId::Free(*free_function_id);
+        if function_id.has_attr(db, EXTERNAL_ATTR).unwrap_or_default() {
+            externals.push(function_id);
+        }
+    }
+    for impl_def_id in db.module_impls_ids(module_id).unwrap_or_default().iter() {
+        let all_external = impl_def_id.has_attr(db, EXTERNAL_ATTR).unwrap_or_default()
+            || impl_def_id
+                .has_attr_with_arg(db, ABI_ATTR, ABI_ATTR_EMBED_V0_ARG)
+                .unwrap_or_default();
+        if !all_external
+            && !impl_def_id
+                .has_attr_with_arg(db, ABI_ATTR, ABI_ATTR_PER_ITEM_ARG)
+                .unwrap_or_default()
+        {
+            continue;
+        }
+        let Ok(concrete_trait_id) = db.impl_def_concrete_trait(*impl_def_id) else {
+            continue;
+        };
+        if concrete_trait_id.trait_id(db).has_attr(db, INTERFACE_ATTR).unwrap_or_default() {
+            continue;
+        }
+        for impl_function_id in db.impl_functions(*impl_def_id).unwrap_or_default().values() {
+            let function_id = FunctionWithBodyId::Impl(*impl_function_id);
+            if all_external || function_id.has_attr(db, EXTERNAL_ATTR).unwrap_or_default() {
+                externals.push(function_id);
+            }
+        }
+    }
+    externals
+}
+
+/// Returns the functions with a body that are transitively called by a test function of the given
+/// crates, including the test functions themselves. Functions of the core crate are ignored.
+fn functions_reachable_from_tests(
+    db: &dyn SemanticGroup,
+    crate_ids: impl Iterator<Item = CrateId>,
+) -> UnorderedHashSet<FunctionWithBodyId> {
+    let core = core_crate(db);
+    let mut stack = vec![];
+    for crate_id in crate_ids.collect::<OrderedHashSet<_>>() {
+        if crate_id == core {
+            continue;
+        }
+        for module_id in db.crate_modules(crate_id).iter() {
+            for free_function_id in
+                db.module_free_functions_ids(*module_id).unwrap_or_default().iter()
+            {
+                let function_id = FunctionWithBodyId::Free(*free_function_id);
+                if function_id.has_attr(db, TEST_ATTR).unwrap_or_default() {
+                    stack.push(function_id);
+                }
+            }
+        }
+    }
+
+    let mut visited = UnorderedHashSet::default();
+    while let Some(function_id) = stack.pop() {
+        if !visited.insert(function_id) {
+            continue;
+        }
+        let Ok(body) = db.function_body(function_id) else {
+            continue;
+        };
+        for (_, expr) in body.exprs.iter() {
+            let cairo_lang_semantic::Expr::FunctionCall(call) = expr else {
+                continue;
+            };
+            let Ok(Some(callee)) = call.function.get_concrete(db).body(db) else {
+                continue;
+            };
+            let callee = callee.function_with_body_id(db);
+            if callee.parent_module(db.upcast()).owning_crate(db.upcast()) != core {
+                stack.push(callee);
+            }
+        }
+    }
+    visited
+}
@@ -0,0 +1,121 @@
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_defs::ids::TopLevelLanguageElementId;
+use cairo_lang_filesystem::cfg::{Cfg, CfgSet};
+use cairo_lang_semantic::test_utils::{get_crate_semantic_diagnostics, setup_test_crate};
+use indoc::indoc;
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+
+use crate::contract::find_contracts;
+use crate::starknet_plugin_suite;
+use crate::unused_externals::{find_unused_external_functions, UnusedExternalsAnalyzer};
+
+#[test]
+fn test_find_unused_external_functions() {
+    let db = &mut RootDatabase::builder()
+        .detect_corelib()
+        .with_cfg(CfgSet::from_iter([Cfg::name("test")]))
+        .with_plugin_suite(starknet_plugin_suite())
+        .build()
+        .unwrap();
+    let crate_id = setup_test_crate(
+        db,
+        indoc! {"
+            #[starknet::interface]
+            trait IBalance<T> {
+                fn get_balance(self: @T) -> felt252;
+            }
+
+            #[starknet::contract]
+            mod balance {
+                #[storage]
+                struct Storage {
+                    value: felt252,
+                }
+
+                #[external(v0)]
+                impl BalanceImpl of super::IBalance<ContractState> {
+                    fn get_balance(self: @ContractState) -> felt252 {
+                        self.value.read()
+                    }
+                }
+
+                #[external(v0)]
+                fn set_balance(ref self: ContractState, value: felt252) {
+                    self.value.write(value);
+                }
+
+                #[external(v0)]
+                fn reset_balance(ref self: ContractState) {
+                    self.value.write(0);
+                }
+
+                #[abi(per_item)]
+                #[generate_trait]
+                impl HelpersImpl of HelpersTrait {
+                    #[external(v0)]
+                    fn increase_balance(ref self: ContractState) {
+                        self.value.write(self.value.read() + 1);
+                    }
+                    fn internal_helper(self: @ContractState) {}
+                }
+            }
+
+            #[cfg(test)]
+            mod tests {
+                use super::balance;
+
+                fn set_to_one(ref state: balance::ContractState) {
+                    balance::set_balance(ref state, 1);
+                }
+
+                #[test]
+                fn test_set_balance() {
+                    let mut state = balance::unsafe_new_contract_state();
+                    set_to_one(ref state);
+                }
+            }
+        "},
+    );
+
+    let contracts = find_contracts(db, &[crate_id]);
+    assert_eq!(
+        find_unused_external_functions(db, &contracts)
+            .into_iter()
+            .map(|function_id| function_id.full_path(db))
+            .collect_vec(),
+        vec!["test::balance::reset_balance", "test::balance::increase_balance"]
+    );
+}
+
+#[test]
+fn test_unused_externals_analyzer() {
+    let mut suite = starknet_plugin_suite();
+    suite.add_analyzer_plugin::<UnusedExternalsAnalyzer>();
+    let db =
+        &mut RootDatabase::builder().detect_corelib().with_plugin_suite(suite).build().unwrap();
+    let crate_id = setup_test_crate(
+        db,
+        indoc! {"
+            #[starknet::contract]
+            mod counter {
+                #[storage]
+                struct Storage {}
+
+                #[external(v0)]
+                fn increase(ref self: ContractState) {}
+            }
+        "},
+    );
+
+    assert_eq!(
+        get_crate_semantic_diagnostics(db, crate_id).format(db),
+        indoc! {"
+            warning: Plugin diagnostic: External function `increase` is not declared by an interface and is not used by any test.
+             --> lib.cairo:7:8
+                fn increase(ref self: ContractState) {}
+                   ^******^
+
+        "}
+    );
+}
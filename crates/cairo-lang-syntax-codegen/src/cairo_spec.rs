@@ -31,6 +31,8 @@ pub fn get_spec() -> Vec<Node> {
         .node("Match")
         .node("If")
         .node("Loop")
+        .node("While")
+        .node("For")
         .node("ErrorPropagate")
         .node("FieldInitShorthand")
         .node("Indexed")
@@ -115,6 +117,7 @@ pub fn get_spec() -> Vec<Node> {
         .node_with_explicit_kind("GE", "TerminalGE")
         .node_with_explicit_kind("LT", "TerminalLT")
         .node_with_explicit_kind("GT", "TerminalGT")
+        .node_with_explicit_kind("DotDot", "TerminalDotDot")
     )
     .add_struct(StructBuilder::new("ExprListParenthesized")
         .node("lparen", "TerminalLParen")
@@ -173,6 +176,18 @@ pub fn get_spec() -> Vec<Node> {
         .node("loop_kw", "TerminalLoop")
         .node("body", "ExprBlock")
     )
+    .add_struct(StructBuilder::new("ExprWhile")
+        .node("while_kw", "TerminalWhile")
+        .node("condition", "Expr")
+        .node("body", "ExprBlock")
+    )
+    .add_struct(StructBuilder::new("ExprFor")
+        .node("for_kw", "TerminalIdentifier")
+        .node("pattern", "Pattern")
+        .node("identifier", "TerminalIdentifier")
+        .node("expr", "Expr")
+        .node("body", "ExprBlock")
+    )
     .add_struct(StructBuilder::new("ElseClause")
         .node("else_kw", "TerminalElse")
         .node("else_block_or_if", "BlockOrIf")
@@ -238,12 +253,18 @@ pub fn get_spec() -> Vec<Node> {
         .node("Struct")
         .node("Tuple")
         .node("Enum")
+        .node("Binding")
         .node_with_explicit_kind("Path", "ExprPath")
     )
     .add_struct(StructBuilder::new("PatternIdentifier")
         .node("modifiers", "ModifierList")
         .key_node("name", "TerminalIdentifier")
     )
+    .add_struct(StructBuilder::new("PatternBinding")
+        .node("binding", "PatternIdentifier")
+        .node("at", "TerminalAt")
+        .node("pattern", "Pattern")
+    )
     .add_struct(StructBuilder::new("PatternStruct")
         // TODO(spapini): Use SimplePath instead - which is not an expr.
         .node("path", "ExprPath")
@@ -378,7 +399,13 @@ pub fn get_spec() -> Vec<Node> {
         .node("attributes" ,"AttributeList")
         .key_node("name", "TerminalIdentifier")
         .node("type_clause", "OptionTypeClause")
+        .node("discriminant", "OptionVariantDiscriminant")
+    )
+    .add_struct(StructBuilder::new("VariantDiscriminant")
+        .node("eq", "TerminalEq")
+        .node("value", "Expr")
     )
+    .add_option("VariantDiscriminant")
     .add_separated_list("VariantList", "Variant", "TerminalComma")
     // --- Items ---
     .add_enum(EnumBuilder::new("Item")
@@ -720,6 +747,7 @@ pub fn get_spec() -> Vec<Node> {
     .add_keyword_token_and_terminal("Function")
     .add_keyword_token_and_terminal("If")
     .add_keyword_token_and_terminal("Loop")
+    .add_keyword_token_and_terminal("While")
     .add_keyword_token_and_terminal("Impl")
     .add_keyword_token_and_terminal("Implicits")
     .add_keyword_token_and_terminal("Let")
@@ -13,6 +13,13 @@ pub const MUST_USE_ATTR: &str = "must_use";
 /// An attribute to define the order of implicit arguments.
 pub const IMPLICIT_PRECEDENCE_ATTR: &str = "implicit_precedence";
 
+/// An attribute to mark a function as a storage write: every call to it overwrites the same storage,
+/// and has no other observable effect. A call is redundant if another call to the same function
+/// follows it with no storage access in between.
+/// Only honored in the corelib and in code generated by plugins, on functions writing through the
+/// fixed size `Store` impls of the corelib.
+pub const STORAGE_WRITE_ATTR: &str = "storage_write";
+
 /// An attribute for the declaration of a starknet interface.
 /// It is used in the starkent crate, however it is defined here because it is currently used in the
 /// corelib.
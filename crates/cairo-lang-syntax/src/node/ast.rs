@@ -210,6 +210,8 @@ pub enum Expr {
     Match(ExprMatch),
     If(ExprIf),
     Loop(ExprLoop),
+    While(ExprWhile),
+    For(ExprFor),
     ErrorPropagate(ExprErrorPropagate),
     FieldInitShorthand(ExprFieldInitShorthand),
     Indexed(ExprIndexed),
@@ -306,6 +308,16 @@ impl From<ExprLoopPtr> for ExprPtr {
         Self(value.0)
     }
 }
+impl From<ExprWhilePtr> for ExprPtr {
+    fn from(value: ExprWhilePtr) -> Self {
+        Self(value.0)
+    }
+}
+impl From<ExprForPtr> for ExprPtr {
+    fn from(value: ExprForPtr) -> Self {
+        Self(value.0)
+    }
+}
 impl From<ExprErrorPropagatePtr> for ExprPtr {
     fn from(value: ExprErrorPropagatePtr) -> Self {
         Self(value.0)
@@ -411,6 +423,16 @@ impl From<ExprLoopGreen> for ExprGreen {
         Self(value.0)
     }
 }
+impl From<ExprWhileGreen> for ExprGreen {
+    fn from(value: ExprWhileGreen) -> Self {
+        Self(value.0)
+    }
+}
+impl From<ExprForGreen> for ExprGreen {
+    fn from(value: ExprForGreen) -> Self {
+        Self(value.0)
+    }
+}
 impl From<ExprErrorPropagateGreen> for ExprGreen {
     fn from(value: ExprErrorPropagateGreen) -> Self {
         Self(value.0)
@@ -476,6 +498,8 @@ impl TypedSyntaxNode for Expr {
             SyntaxKind::ExprMatch => Expr::Match(ExprMatch::from_syntax_node(db, node)),
             SyntaxKind::ExprIf => Expr::If(ExprIf::from_syntax_node(db, node)),
             SyntaxKind::ExprLoop => Expr::Loop(ExprLoop::from_syntax_node(db, node)),
+            SyntaxKind::ExprWhile => Expr::While(ExprWhile::from_syntax_node(db, node)),
+            SyntaxKind::ExprFor => Expr::For(ExprFor::from_syntax_node(db, node)),
             SyntaxKind::ExprErrorPropagate => {
                 Expr::ErrorPropagate(ExprErrorPropagate::from_syntax_node(db, node))
             }
@@ -508,6 +532,8 @@ impl TypedSyntaxNode for Expr {
             Expr::Match(x) => x.as_syntax_node(),
             Expr::If(x) => x.as_syntax_node(),
             Expr::Loop(x) => x.as_syntax_node(),
+            Expr::While(x) => x.as_syntax_node(),
+            Expr::For(x) => x.as_syntax_node(),
             Expr::ErrorPropagate(x) => x.as_syntax_node(),
             Expr::FieldInitShorthand(x) => x.as_syntax_node(),
             Expr::Indexed(x) => x.as_syntax_node(),
@@ -539,6 +565,8 @@ impl Expr {
             SyntaxKind::ExprMatch => true,
             SyntaxKind::ExprIf => true,
             SyntaxKind::ExprLoop => true,
+            SyntaxKind::ExprWhile => true,
+            SyntaxKind::ExprFor => true,
             SyntaxKind::ExprErrorPropagate => true,
             SyntaxKind::ExprFieldInitShorthand => true,
             SyntaxKind::ExprIndexed => true,
@@ -2037,6 +2065,7 @@ pub enum BinaryOperator {
     GE(TerminalGE),
     LT(TerminalLT),
     GT(TerminalGT),
+    DotDot(TerminalDotDot),
 }
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct BinaryOperatorPtr(pub SyntaxStablePtrId);
@@ -2168,6 +2197,11 @@ impl From<TerminalGTPtr> for BinaryOperatorPtr {
         Self(value.0)
     }
 }
+impl From<TerminalDotDotPtr> for BinaryOperatorPtr {
+    fn from(value: TerminalDotDotPtr) -> Self {
+        Self(value.0)
+    }
+}
 impl From<TerminalDotGreen> for BinaryOperatorGreen {
     fn from(value: TerminalDotGreen) -> Self {
         Self(value.0)
@@ -2288,6 +2322,11 @@ impl From<TerminalGTGreen> for BinaryOperatorGreen {
         Self(value.0)
     }
 }
+impl From<TerminalDotDotGreen> for BinaryOperatorGreen {
+    fn from(value: TerminalDotDotGreen) -> Self {
+        Self(value.0)
+    }
+}
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct BinaryOperatorGreen(pub GreenId);
 impl TypedSyntaxNode for BinaryOperator {
@@ -2344,6 +2383,9 @@ impl TypedSyntaxNode for BinaryOperator {
             SyntaxKind::TerminalGE => BinaryOperator::GE(TerminalGE::from_syntax_node(db, node)),
             SyntaxKind::TerminalLT => BinaryOperator::LT(TerminalLT::from_syntax_node(db, node)),
             SyntaxKind::TerminalGT => BinaryOperator::GT(TerminalGT::from_syntax_node(db, node)),
+            SyntaxKind::TerminalDotDot => {
+                BinaryOperator::DotDot(TerminalDotDot::from_syntax_node(db, node))
+            }
             _ => {
                 panic!("Unexpected syntax kind {:?} when constructing {}.", kind, "BinaryOperator")
             }
@@ -2375,6 +2417,7 @@ impl TypedSyntaxNode for BinaryOperator {
             BinaryOperator::GE(x) => x.as_syntax_node(),
             BinaryOperator::LT(x) => x.as_syntax_node(),
             BinaryOperator::GT(x) => x.as_syntax_node(),
+            BinaryOperator::DotDot(x) => x.as_syntax_node(),
         }
     }
     fn stable_ptr(&self) -> Self::StablePtr {
@@ -2409,6 +2452,7 @@ impl BinaryOperator {
             SyntaxKind::TerminalGE => true,
             SyntaxKind::TerminalLT => true,
             SyntaxKind::TerminalGT => true,
+            SyntaxKind::TerminalDotDot => true,
             _ => false,
         }
     }
@@ -3511,6 +3555,182 @@ impl TypedSyntaxNode for ExprLoop {
     }
 }
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ExprWhile {
+    node: SyntaxNode,
+    children: Arc<Vec<SyntaxNode>>,
+}
+impl ExprWhile {
+    pub const INDEX_WHILE_KW: usize = 0;
+    pub const INDEX_CONDITION: usize = 1;
+    pub const INDEX_BODY: usize = 2;
+    pub fn new_green(
+        db: &dyn SyntaxGroup,
+        while_kw: TerminalWhileGreen,
+        condition: ExprGreen,
+        body: ExprBlockGreen,
+    ) -> ExprWhileGreen {
+        let children: Vec<GreenId> = vec![while_kw.0, condition.0, body.0];
+        let width = children.iter().copied().map(|id| db.lookup_intern_green(id).width()).sum();
+        ExprWhileGreen(db.intern_green(Arc::new(GreenNode {
+            kind: SyntaxKind::ExprWhile,
+            details: GreenNodeDetails::Node { children, width },
+        })))
+    }
+}
+impl ExprWhile {
+    pub fn while_kw(&self, db: &dyn SyntaxGroup) -> TerminalWhile {
+        TerminalWhile::from_syntax_node(db, self.children[0].clone())
+    }
+    pub fn condition(&self, db: &dyn SyntaxGroup) -> Expr {
+        Expr::from_syntax_node(db, self.children[1].clone())
+    }
+    pub fn body(&self, db: &dyn SyntaxGroup) -> ExprBlock {
+        ExprBlock::from_syntax_node(db, self.children[2].clone())
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ExprWhilePtr(pub SyntaxStablePtrId);
+impl ExprWhilePtr {
+    pub fn untyped(&self) -> SyntaxStablePtrId {
+        self.0
+    }
+    pub fn lookup(&self, db: &dyn SyntaxGroup) -> ExprWhile {
+        ExprWhile::from_syntax_node(db, self.0.lookup(db))
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ExprWhileGreen(pub GreenId);
+impl TypedSyntaxNode for ExprWhile {
+    const OPTIONAL_KIND: Option<SyntaxKind> = Some(SyntaxKind::ExprWhile);
+    type StablePtr = ExprWhilePtr;
+    type Green = ExprWhileGreen;
+    fn missing(db: &dyn SyntaxGroup) -> Self::Green {
+        ExprWhileGreen(db.intern_green(Arc::new(GreenNode {
+            kind: SyntaxKind::ExprWhile,
+            details: GreenNodeDetails::Node {
+                children: vec![
+                    TerminalWhile::missing(db).0,
+                    Expr::missing(db).0,
+                    ExprBlock::missing(db).0,
+                ],
+                width: TextWidth::default(),
+            },
+        })))
+    }
+    fn from_syntax_node(db: &dyn SyntaxGroup, node: SyntaxNode) -> Self {
+        let kind = node.kind(db);
+        assert_eq!(
+            kind,
+            SyntaxKind::ExprWhile,
+            "Unexpected SyntaxKind {:?}. Expected {:?}.",
+            kind,
+            SyntaxKind::ExprWhile
+        );
+        let children = db.get_children(node.clone());
+        Self { node, children }
+    }
+    fn as_syntax_node(&self) -> SyntaxNode {
+        self.node.clone()
+    }
+    fn stable_ptr(&self) -> Self::StablePtr {
+        ExprWhilePtr(self.node.0.stable_ptr)
+    }
+}
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ExprFor {
+    node: SyntaxNode,
+    children: Arc<Vec<SyntaxNode>>,
+}
+impl ExprFor {
+    pub const INDEX_FOR_KW: usize = 0;
+    pub const INDEX_PATTERN: usize = 1;
+    pub const INDEX_IDENTIFIER: usize = 2;
+    pub const INDEX_EXPR: usize = 3;
+    pub const INDEX_BODY: usize = 4;
+    pub fn new_green(
+        db: &dyn SyntaxGroup,
+        for_kw: TerminalIdentifierGreen,
+        pattern: PatternGreen,
+        identifier: TerminalIdentifierGreen,
+        expr: ExprGreen,
+        body: ExprBlockGreen,
+    ) -> ExprForGreen {
+        let children: Vec<GreenId> = vec![for_kw.0, pattern.0, identifier.0, expr.0, body.0];
+        let width = children.iter().copied().map(|id| db.lookup_intern_green(id).width()).sum();
+        ExprForGreen(db.intern_green(Arc::new(GreenNode {
+            kind: SyntaxKind::ExprFor,
+            details: GreenNodeDetails::Node { children, width },
+        })))
+    }
+}
+impl ExprFor {
+    pub fn for_kw(&self, db: &dyn SyntaxGroup) -> TerminalIdentifier {
+        TerminalIdentifier::from_syntax_node(db, self.children[0].clone())
+    }
+    pub fn pattern(&self, db: &dyn SyntaxGroup) -> Pattern {
+        Pattern::from_syntax_node(db, self.children[1].clone())
+    }
+    pub fn identifier(&self, db: &dyn SyntaxGroup) -> TerminalIdentifier {
+        TerminalIdentifier::from_syntax_node(db, self.children[2].clone())
+    }
+    pub fn expr(&self, db: &dyn SyntaxGroup) -> Expr {
+        Expr::from_syntax_node(db, self.children[3].clone())
+    }
+    pub fn body(&self, db: &dyn SyntaxGroup) -> ExprBlock {
+        ExprBlock::from_syntax_node(db, self.children[4].clone())
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ExprForPtr(pub SyntaxStablePtrId);
+impl ExprForPtr {
+    pub fn untyped(&self) -> SyntaxStablePtrId {
+        self.0
+    }
+    pub fn lookup(&self, db: &dyn SyntaxGroup) -> ExprFor {
+        ExprFor::from_syntax_node(db, self.0.lookup(db))
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ExprForGreen(pub GreenId);
+impl TypedSyntaxNode for ExprFor {
+    const OPTIONAL_KIND: Option<SyntaxKind> = Some(SyntaxKind::ExprFor);
+    type StablePtr = ExprForPtr;
+    type Green = ExprForGreen;
+    fn missing(db: &dyn SyntaxGroup) -> Self::Green {
+        ExprForGreen(db.intern_green(Arc::new(GreenNode {
+            kind: SyntaxKind::ExprFor,
+            details: GreenNodeDetails::Node {
+                children: vec![
+                    TerminalIdentifier::missing(db).0,
+                    Pattern::missing(db).0,
+                    TerminalIdentifier::missing(db).0,
+                    Expr::missing(db).0,
+                    ExprBlock::missing(db).0,
+                ],
+                width: TextWidth::default(),
+            },
+        })))
+    }
+    fn from_syntax_node(db: &dyn SyntaxGroup, node: SyntaxNode) -> Self {
+        let kind = node.kind(db);
+        assert_eq!(
+            kind,
+            SyntaxKind::ExprFor,
+            "Unexpected SyntaxKind {:?}. Expected {:?}.",
+            kind,
+            SyntaxKind::ExprFor
+        );
+        let children = db.get_children(node.clone());
+        Self { node, children }
+    }
+    fn as_syntax_node(&self) -> SyntaxNode {
+        self.node.clone()
+    }
+    fn stable_ptr(&self) -> Self::StablePtr {
+        ExprForPtr(self.node.0.stable_ptr)
+    }
+}
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct ElseClause {
     node: SyntaxNode,
     children: Arc<Vec<SyntaxNode>>,
@@ -4823,6 +5043,7 @@ pub enum Pattern {
     Struct(PatternStruct),
     Tuple(PatternTuple),
     Enum(PatternEnum),
+    Binding(PatternBinding),
     Path(ExprPath),
 }
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
@@ -4875,6 +5096,11 @@ impl From<PatternEnumPtr> for PatternPtr {
         Self(value.0)
     }
 }
+impl From<PatternBindingPtr> for PatternPtr {
+    fn from(value: PatternBindingPtr) -> Self {
+        Self(value.0)
+    }
+}
 impl From<ExprPathPtr> for PatternPtr {
     fn from(value: ExprPathPtr) -> Self {
         Self(value.0)
@@ -4920,6 +5146,11 @@ impl From<PatternEnumGreen> for PatternGreen {
         Self(value.0)
     }
 }
+impl From<PatternBindingGreen> for PatternGreen {
+    fn from(value: PatternBindingGreen) -> Self {
+        Self(value.0)
+    }
+}
 impl From<ExprPathGreen> for PatternGreen {
     fn from(value: ExprPathGreen) -> Self {
         Self(value.0)
@@ -4955,6 +5186,9 @@ impl TypedSyntaxNode for Pattern {
             SyntaxKind::PatternStruct => Pattern::Struct(PatternStruct::from_syntax_node(db, node)),
             SyntaxKind::PatternTuple => Pattern::Tuple(PatternTuple::from_syntax_node(db, node)),
             SyntaxKind::PatternEnum => Pattern::Enum(PatternEnum::from_syntax_node(db, node)),
+            SyntaxKind::PatternBinding => {
+                Pattern::Binding(PatternBinding::from_syntax_node(db, node))
+            }
             SyntaxKind::ExprPath => Pattern::Path(ExprPath::from_syntax_node(db, node)),
             _ => panic!("Unexpected syntax kind {:?} when constructing {}.", kind, "Pattern"),
         }
@@ -4969,6 +5203,7 @@ impl TypedSyntaxNode for Pattern {
             Pattern::Struct(x) => x.as_syntax_node(),
             Pattern::Tuple(x) => x.as_syntax_node(),
             Pattern::Enum(x) => x.as_syntax_node(),
+            Pattern::Binding(x) => x.as_syntax_node(),
             Pattern::Path(x) => x.as_syntax_node(),
         }
     }
@@ -4988,6 +5223,7 @@ impl Pattern {
             SyntaxKind::PatternStruct => true,
             SyntaxKind::PatternTuple => true,
             SyntaxKind::PatternEnum => true,
+            SyntaxKind::PatternBinding => true,
             SyntaxKind::ExprPath => true,
             _ => false,
         }
@@ -5075,6 +5311,88 @@ impl TypedSyntaxNode for PatternIdentifier {
     }
 }
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PatternBinding {
+    node: SyntaxNode,
+    children: Arc<Vec<SyntaxNode>>,
+}
+impl PatternBinding {
+    pub const INDEX_BINDING: usize = 0;
+    pub const INDEX_AT: usize = 1;
+    pub const INDEX_PATTERN: usize = 2;
+    pub fn new_green(
+        db: &dyn SyntaxGroup,
+        binding: PatternIdentifierGreen,
+        at: TerminalAtGreen,
+        pattern: PatternGreen,
+    ) -> PatternBindingGreen {
+        let children: Vec<GreenId> = vec![binding.0, at.0, pattern.0];
+        let width = children.iter().copied().map(|id| db.lookup_intern_green(id).width()).sum();
+        PatternBindingGreen(db.intern_green(Arc::new(GreenNode {
+            kind: SyntaxKind::PatternBinding,
+            details: GreenNodeDetails::Node { children, width },
+        })))
+    }
+}
+impl PatternBinding {
+    pub fn binding(&self, db: &dyn SyntaxGroup) -> PatternIdentifier {
+        PatternIdentifier::from_syntax_node(db, self.children[0].clone())
+    }
+    pub fn at(&self, db: &dyn SyntaxGroup) -> TerminalAt {
+        TerminalAt::from_syntax_node(db, self.children[1].clone())
+    }
+    pub fn pattern(&self, db: &dyn SyntaxGroup) -> Pattern {
+        Pattern::from_syntax_node(db, self.children[2].clone())
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PatternBindingPtr(pub SyntaxStablePtrId);
+impl PatternBindingPtr {
+    pub fn untyped(&self) -> SyntaxStablePtrId {
+        self.0
+    }
+    pub fn lookup(&self, db: &dyn SyntaxGroup) -> PatternBinding {
+        PatternBinding::from_syntax_node(db, self.0.lookup(db))
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PatternBindingGreen(pub GreenId);
+impl TypedSyntaxNode for PatternBinding {
+    const OPTIONAL_KIND: Option<SyntaxKind> = Some(SyntaxKind::PatternBinding);
+    type StablePtr = PatternBindingPtr;
+    type Green = PatternBindingGreen;
+    fn missing(db: &dyn SyntaxGroup) -> Self::Green {
+        PatternBindingGreen(db.intern_green(Arc::new(GreenNode {
+            kind: SyntaxKind::PatternBinding,
+            details: GreenNodeDetails::Node {
+                children: vec![
+                    PatternIdentifier::missing(db).0,
+                    TerminalAt::missing(db).0,
+                    Pattern::missing(db).0,
+                ],
+                width: TextWidth::default(),
+            },
+        })))
+    }
+    fn from_syntax_node(db: &dyn SyntaxGroup, node: SyntaxNode) -> Self {
+        let kind = node.kind(db);
+        assert_eq!(
+            kind,
+            SyntaxKind::PatternBinding,
+            "Unexpected SyntaxKind {:?}. Expected {:?}.",
+            kind,
+            SyntaxKind::PatternBinding
+        );
+        let children = db.get_children(node.clone());
+        Self { node, children }
+    }
+    fn as_syntax_node(&self) -> SyntaxNode {
+        self.node.clone()
+    }
+    fn stable_ptr(&self) -> Self::StablePtr {
+        PatternBindingPtr(self.node.0.stable_ptr)
+    }
+}
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct PatternStruct {
     node: SyntaxNode,
     children: Arc<Vec<SyntaxNode>>,
@@ -8354,13 +8672,15 @@ impl Variant {
     pub const INDEX_ATTRIBUTES: usize = 0;
     pub const INDEX_NAME: usize = 1;
     pub const INDEX_TYPE_CLAUSE: usize = 2;
+    pub const INDEX_DISCRIMINANT: usize = 3;
     pub fn new_green(
         db: &dyn SyntaxGroup,
         attributes: AttributeListGreen,
         name: TerminalIdentifierGreen,
         type_clause: OptionTypeClauseGreen,
+        discriminant: OptionVariantDiscriminantGreen,
     ) -> VariantGreen {
-        let children: Vec<GreenId> = vec![attributes.0, name.0, type_clause.0];
+        let children: Vec<GreenId> = vec![attributes.0, name.0, type_clause.0, discriminant.0];
         let width = children.iter().copied().map(|id| db.lookup_intern_green(id).width()).sum();
         VariantGreen(db.intern_green(Arc::new(GreenNode {
             kind: SyntaxKind::Variant,
@@ -8378,6 +8698,9 @@ impl Variant {
     pub fn type_clause(&self, db: &dyn SyntaxGroup) -> OptionTypeClause {
         OptionTypeClause::from_syntax_node(db, self.children[2].clone())
     }
+    pub fn discriminant(&self, db: &dyn SyntaxGroup) -> OptionVariantDiscriminant {
+        OptionVariantDiscriminant::from_syntax_node(db, self.children[3].clone())
+    }
 }
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct VariantPtr(pub SyntaxStablePtrId);
@@ -8411,6 +8734,7 @@ impl TypedSyntaxNode for Variant {
                     AttributeList::missing(db).0,
                     TerminalIdentifier::missing(db).0,
                     OptionTypeClause::missing(db).0,
+                    OptionVariantDiscriminant::missing(db).0,
                 ],
                 width: TextWidth::default(),
             },
@@ -8436,6 +8760,215 @@ impl TypedSyntaxNode for Variant {
     }
 }
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct VariantDiscriminant {
+    node: SyntaxNode,
+    children: Arc<Vec<SyntaxNode>>,
+}
+impl VariantDiscriminant {
+    pub const INDEX_EQ: usize = 0;
+    pub const INDEX_VALUE: usize = 1;
+    pub fn new_green(
+        db: &dyn SyntaxGroup,
+        eq: TerminalEqGreen,
+        value: ExprGreen,
+    ) -> VariantDiscriminantGreen {
+        let children: Vec<GreenId> = vec![eq.0, value.0];
+        let width = children.iter().copied().map(|id| db.lookup_intern_green(id).width()).sum();
+        VariantDiscriminantGreen(db.intern_green(Arc::new(GreenNode {
+            kind: SyntaxKind::VariantDiscriminant,
+            details: GreenNodeDetails::Node { children, width },
+        })))
+    }
+}
+impl VariantDiscriminant {
+    pub fn eq(&self, db: &dyn SyntaxGroup) -> TerminalEq {
+        TerminalEq::from_syntax_node(db, self.children[0].clone())
+    }
+    pub fn value(&self, db: &dyn SyntaxGroup) -> Expr {
+        Expr::from_syntax_node(db, self.children[1].clone())
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct VariantDiscriminantPtr(pub SyntaxStablePtrId);
+impl VariantDiscriminantPtr {
+    pub fn untyped(&self) -> SyntaxStablePtrId {
+        self.0
+    }
+    pub fn lookup(&self, db: &dyn SyntaxGroup) -> VariantDiscriminant {
+        VariantDiscriminant::from_syntax_node(db, self.0.lookup(db))
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct VariantDiscriminantGreen(pub GreenId);
+impl TypedSyntaxNode for VariantDiscriminant {
+    const OPTIONAL_KIND: Option<SyntaxKind> = Some(SyntaxKind::VariantDiscriminant);
+    type StablePtr = VariantDiscriminantPtr;
+    type Green = VariantDiscriminantGreen;
+    fn missing(db: &dyn SyntaxGroup) -> Self::Green {
+        VariantDiscriminantGreen(db.intern_green(Arc::new(GreenNode {
+            kind: SyntaxKind::VariantDiscriminant,
+            details: GreenNodeDetails::Node {
+                children: vec![TerminalEq::missing(db).0, Expr::missing(db).0],
+                width: TextWidth::default(),
+            },
+        })))
+    }
+    fn from_syntax_node(db: &dyn SyntaxGroup, node: SyntaxNode) -> Self {
+        let kind = node.kind(db);
+        assert_eq!(
+            kind,
+            SyntaxKind::VariantDiscriminant,
+            "Unexpected SyntaxKind {:?}. Expected {:?}.",
+            kind,
+            SyntaxKind::VariantDiscriminant
+        );
+        let children = db.get_children(node.clone());
+        Self { node, children }
+    }
+    fn as_syntax_node(&self) -> SyntaxNode {
+        self.node.clone()
+    }
+    fn stable_ptr(&self) -> Self::StablePtr {
+        VariantDiscriminantPtr(self.node.0.stable_ptr)
+    }
+}
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum OptionVariantDiscriminant {
+    Empty(OptionVariantDiscriminantEmpty),
+    VariantDiscriminant(VariantDiscriminant),
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct OptionVariantDiscriminantPtr(pub SyntaxStablePtrId);
+impl OptionVariantDiscriminantPtr {
+    pub fn untyped(&self) -> SyntaxStablePtrId {
+        self.0
+    }
+    pub fn lookup(&self, db: &dyn SyntaxGroup) -> OptionVariantDiscriminant {
+        OptionVariantDiscriminant::from_syntax_node(db, self.0.lookup(db))
+    }
+}
+impl From<OptionVariantDiscriminantEmptyPtr> for OptionVariantDiscriminantPtr {
+    fn from(value: OptionVariantDiscriminantEmptyPtr) -> Self {
+        Self(value.0)
+    }
+}
+impl From<VariantDiscriminantPtr> for OptionVariantDiscriminantPtr {
+    fn from(value: VariantDiscriminantPtr) -> Self {
+        Self(value.0)
+    }
+}
+impl From<OptionVariantDiscriminantEmptyGreen> for OptionVariantDiscriminantGreen {
+    fn from(value: OptionVariantDiscriminantEmptyGreen) -> Self {
+        Self(value.0)
+    }
+}
+impl From<VariantDiscriminantGreen> for OptionVariantDiscriminantGreen {
+    fn from(value: VariantDiscriminantGreen) -> Self {
+        Self(value.0)
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct OptionVariantDiscriminantGreen(pub GreenId);
+impl TypedSyntaxNode for OptionVariantDiscriminant {
+    const OPTIONAL_KIND: Option<SyntaxKind> = None;
+    type StablePtr = OptionVariantDiscriminantPtr;
+    type Green = OptionVariantDiscriminantGreen;
+    fn missing(db: &dyn SyntaxGroup) -> Self::Green {
+        panic!("No missing variant.");
+    }
+    fn from_syntax_node(db: &dyn SyntaxGroup, node: SyntaxNode) -> Self {
+        let kind = node.kind(db);
+        match kind {
+            SyntaxKind::OptionVariantDiscriminantEmpty => OptionVariantDiscriminant::Empty(
+                OptionVariantDiscriminantEmpty::from_syntax_node(db, node),
+            ),
+            SyntaxKind::VariantDiscriminant => OptionVariantDiscriminant::VariantDiscriminant(
+                VariantDiscriminant::from_syntax_node(db, node),
+            ),
+            _ => panic!(
+                "Unexpected syntax kind {:?} when constructing {}.",
+                kind, "OptionVariantDiscriminant"
+            ),
+        }
+    }
+    fn as_syntax_node(&self) -> SyntaxNode {
+        match self {
+            OptionVariantDiscriminant::Empty(x) => x.as_syntax_node(),
+            OptionVariantDiscriminant::VariantDiscriminant(x) => x.as_syntax_node(),
+        }
+    }
+    fn stable_ptr(&self) -> Self::StablePtr {
+        OptionVariantDiscriminantPtr(self.as_syntax_node().0.stable_ptr)
+    }
+}
+impl OptionVariantDiscriminant {
+    #[allow(clippy::match_like_matches_macro)]
+    pub fn is_variant(kind: SyntaxKind) -> bool {
+        match kind {
+            SyntaxKind::OptionVariantDiscriminantEmpty => true,
+            SyntaxKind::VariantDiscriminant => true,
+            _ => false,
+        }
+    }
+}
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct OptionVariantDiscriminantEmpty {
+    node: SyntaxNode,
+    children: Arc<Vec<SyntaxNode>>,
+}
+impl OptionVariantDiscriminantEmpty {
+    pub fn new_green(db: &dyn SyntaxGroup) -> OptionVariantDiscriminantEmptyGreen {
+        let children: Vec<GreenId> = vec![];
+        let width = children.iter().copied().map(|id| db.lookup_intern_green(id).width()).sum();
+        OptionVariantDiscriminantEmptyGreen(db.intern_green(Arc::new(GreenNode {
+            kind: SyntaxKind::OptionVariantDiscriminantEmpty,
+            details: GreenNodeDetails::Node { children, width },
+        })))
+    }
+}
+impl OptionVariantDiscriminantEmpty {}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct OptionVariantDiscriminantEmptyPtr(pub SyntaxStablePtrId);
+impl OptionVariantDiscriminantEmptyPtr {
+    pub fn untyped(&self) -> SyntaxStablePtrId {
+        self.0
+    }
+    pub fn lookup(&self, db: &dyn SyntaxGroup) -> OptionVariantDiscriminantEmpty {
+        OptionVariantDiscriminantEmpty::from_syntax_node(db, self.0.lookup(db))
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct OptionVariantDiscriminantEmptyGreen(pub GreenId);
+impl TypedSyntaxNode for OptionVariantDiscriminantEmpty {
+    const OPTIONAL_KIND: Option<SyntaxKind> = Some(SyntaxKind::OptionVariantDiscriminantEmpty);
+    type StablePtr = OptionVariantDiscriminantEmptyPtr;
+    type Green = OptionVariantDiscriminantEmptyGreen;
+    fn missing(db: &dyn SyntaxGroup) -> Self::Green {
+        OptionVariantDiscriminantEmptyGreen(db.intern_green(Arc::new(GreenNode {
+            kind: SyntaxKind::OptionVariantDiscriminantEmpty,
+            details: GreenNodeDetails::Node { children: vec![], width: TextWidth::default() },
+        })))
+    }
+    fn from_syntax_node(db: &dyn SyntaxGroup, node: SyntaxNode) -> Self {
+        let kind = node.kind(db);
+        assert_eq!(
+            kind,
+            SyntaxKind::OptionVariantDiscriminantEmpty,
+            "Unexpected SyntaxKind {:?}. Expected {:?}.",
+            kind,
+            SyntaxKind::OptionVariantDiscriminantEmpty
+        );
+        let children = db.get_children(node.clone());
+        Self { node, children }
+    }
+    fn as_syntax_node(&self) -> SyntaxNode {
+        self.node.clone()
+    }
+    fn stable_ptr(&self) -> Self::StablePtr {
+        OptionVariantDiscriminantEmptyPtr(self.node.0.stable_ptr)
+    }
+}
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct VariantList(ElementList<Variant, 2>);
 impl Deref for VariantList {
     type Target = ElementList<Variant, 2>;
@@ -16241,6 +16774,151 @@ impl TypedSyntaxNode for TerminalLoop {
     }
 }
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TokenWhile {
+    node: SyntaxNode,
+}
+impl Token for TokenWhile {
+    fn new_green(db: &dyn SyntaxGroup, text: SmolStr) -> Self::Green {
+        TokenWhileGreen(db.intern_green(Arc::new(GreenNode {
+            kind: SyntaxKind::TokenWhile,
+            details: GreenNodeDetails::Token(text),
+        })))
+    }
+    fn text(&self, db: &dyn SyntaxGroup) -> SmolStr {
+        extract_matches!(
+            &db.lookup_intern_green(self.node.0.green).details,
+            GreenNodeDetails::Token
+        )
+        .clone()
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TokenWhilePtr(pub SyntaxStablePtrId);
+impl TokenWhilePtr {
+    pub fn untyped(&self) -> SyntaxStablePtrId {
+        self.0
+    }
+    pub fn lookup(&self, db: &dyn SyntaxGroup) -> TokenWhile {
+        TokenWhile::from_syntax_node(db, self.0.lookup(db))
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TokenWhileGreen(pub GreenId);
+impl TokenWhileGreen {
+    pub fn text(&self, db: &dyn SyntaxGroup) -> SmolStr {
+        extract_matches!(&db.lookup_intern_green(self.0).details, GreenNodeDetails::Token).clone()
+    }
+}
+impl TypedSyntaxNode for TokenWhile {
+    const OPTIONAL_KIND: Option<SyntaxKind> = Some(SyntaxKind::TokenWhile);
+    type StablePtr = TokenWhilePtr;
+    type Green = TokenWhileGreen;
+    fn missing(db: &dyn SyntaxGroup) -> Self::Green {
+        TokenWhileGreen(db.intern_green(Arc::new(GreenNode {
+            kind: SyntaxKind::TokenMissing,
+            details: GreenNodeDetails::Token("".into()),
+        })))
+    }
+    fn from_syntax_node(db: &dyn SyntaxGroup, node: SyntaxNode) -> Self {
+        match db.lookup_intern_green(node.0.green).details {
+            GreenNodeDetails::Token(_) => Self { node },
+            GreenNodeDetails::Node { .. } => {
+                panic!("Expected a token {:?}, not an internal node", SyntaxKind::TokenWhile)
+            }
+        }
+    }
+    fn as_syntax_node(&self) -> SyntaxNode {
+        self.node.clone()
+    }
+    fn stable_ptr(&self) -> Self::StablePtr {
+        TokenWhilePtr(self.node.0.stable_ptr)
+    }
+}
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TerminalWhile {
+    node: SyntaxNode,
+    children: Arc<Vec<SyntaxNode>>,
+}
+impl Terminal for TerminalWhile {
+    const KIND: SyntaxKind = SyntaxKind::TerminalWhile;
+    type TokenType = TokenWhile;
+    fn new_green(
+        db: &dyn SyntaxGroup,
+        leading_trivia: TriviaGreen,
+        token: <<TerminalWhile as Terminal>::TokenType as TypedSyntaxNode>::Green,
+        trailing_trivia: TriviaGreen,
+    ) -> Self::Green {
+        let children: Vec<GreenId> = vec![leading_trivia.0, token.0, trailing_trivia.0];
+        let width = children.iter().copied().map(|id| db.lookup_intern_green(id).width()).sum();
+        TerminalWhileGreen(db.intern_green(Arc::new(GreenNode {
+            kind: SyntaxKind::TerminalWhile,
+            details: GreenNodeDetails::Node { children, width },
+        })))
+    }
+    fn text(&self, db: &dyn SyntaxGroup) -> SmolStr {
+        self.token(db).text(db)
+    }
+}
+impl TerminalWhile {
+    pub fn leading_trivia(&self, db: &dyn SyntaxGroup) -> Trivia {
+        Trivia::from_syntax_node(db, self.children[0].clone())
+    }
+    pub fn token(&self, db: &dyn SyntaxGroup) -> TokenWhile {
+        TokenWhile::from_syntax_node(db, self.children[1].clone())
+    }
+    pub fn trailing_trivia(&self, db: &dyn SyntaxGroup) -> Trivia {
+        Trivia::from_syntax_node(db, self.children[2].clone())
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TerminalWhilePtr(pub SyntaxStablePtrId);
+impl TerminalWhilePtr {
+    pub fn untyped(&self) -> SyntaxStablePtrId {
+        self.0
+    }
+    pub fn lookup(&self, db: &dyn SyntaxGroup) -> TerminalWhile {
+        TerminalWhile::from_syntax_node(db, self.0.lookup(db))
+    }
+}
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TerminalWhileGreen(pub GreenId);
+impl TypedSyntaxNode for TerminalWhile {
+    const OPTIONAL_KIND: Option<SyntaxKind> = Some(SyntaxKind::TerminalWhile);
+    type StablePtr = TerminalWhilePtr;
+    type Green = TerminalWhileGreen;
+    fn missing(db: &dyn SyntaxGroup) -> Self::Green {
+        TerminalWhileGreen(db.intern_green(Arc::new(GreenNode {
+            kind: SyntaxKind::TerminalWhile,
+            details: GreenNodeDetails::Node {
+                children: vec![
+                    Trivia::missing(db).0,
+                    TokenWhile::missing(db).0,
+                    Trivia::missing(db).0,
+                ],
+                width: TextWidth::default(),
+            },
+        })))
+    }
+    fn from_syntax_node(db: &dyn SyntaxGroup, node: SyntaxNode) -> Self {
+        let kind = node.kind(db);
+        assert_eq!(
+            kind,
+            SyntaxKind::TerminalWhile,
+            "Unexpected SyntaxKind {:?}. Expected {:?}.",
+            kind,
+            SyntaxKind::TerminalWhile
+        );
+        let children = db.get_children(node.clone());
+        Self { node, children }
+    }
+    fn as_syntax_node(&self) -> SyntaxNode {
+        self.node.clone()
+    }
+    fn stable_ptr(&self) -> Self::StablePtr {
+        TerminalWhilePtr(self.node.0.stable_ptr)
+    }
+}
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct TokenImpl {
     node: SyntaxNode,
 }
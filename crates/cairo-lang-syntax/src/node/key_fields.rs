@@ -80,6 +80,12 @@ pub fn get_key_fields(kind: SyntaxKind, children: &[GreenId]) -> Vec<GreenId> {
         SyntaxKind::ExprLoop => {
             vec![]
         }
+        SyntaxKind::ExprWhile => {
+            vec![]
+        }
+        SyntaxKind::ExprFor => {
+            vec![]
+        }
         SyntaxKind::ElseClause => {
             vec![]
         }
@@ -120,6 +126,9 @@ pub fn get_key_fields(kind: SyntaxKind, children: &[GreenId]) -> Vec<GreenId> {
         SyntaxKind::PatternIdentifier => {
             vec![/* name */ children[1]]
         }
+        SyntaxKind::PatternBinding => {
+            vec![]
+        }
         SyntaxKind::PatternStruct => {
             vec![]
         }
@@ -205,6 +214,12 @@ pub fn get_key_fields(kind: SyntaxKind, children: &[GreenId]) -> Vec<GreenId> {
         SyntaxKind::Variant => {
             vec![/* name */ children[1]]
         }
+        SyntaxKind::VariantDiscriminant => {
+            vec![]
+        }
+        SyntaxKind::OptionVariantDiscriminantEmpty => {
+            vec![]
+        }
         SyntaxKind::VariantList => vec![],
         SyntaxKind::ItemList => vec![],
         SyntaxKind::ItemMissing => {
@@ -394,6 +409,10 @@ pub fn get_key_fields(kind: SyntaxKind, children: &[GreenId]) -> Vec<GreenId> {
         SyntaxKind::TerminalLoop => {
             vec![]
         }
+        SyntaxKind::TokenWhile => vec![],
+        SyntaxKind::TerminalWhile => {
+            vec![]
+        }
         SyntaxKind::TokenImpl => vec![],
         SyntaxKind::TerminalImpl => {
             vec![]
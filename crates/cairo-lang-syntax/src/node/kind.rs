@@ -30,6 +30,8 @@ pub enum SyntaxKind {
     MatchArm,
     ExprIf,
     ExprLoop,
+    ExprWhile,
+    ExprFor,
     ElseClause,
     OptionElseClauseEmpty,
     ExprErrorPropagate,
@@ -44,6 +46,7 @@ pub enum SyntaxKind {
     ArgListBracketed,
     WrappedArgListMissing,
     PatternIdentifier,
+    PatternBinding,
     PatternStruct,
     PatternStructParamList,
     PatternTuple,
@@ -77,6 +80,8 @@ pub enum SyntaxKind {
     Member,
     MemberList,
     Variant,
+    VariantDiscriminant,
+    OptionVariantDiscriminantEmpty,
     VariantList,
     ItemList,
     ItemMissing,
@@ -154,6 +159,8 @@ pub enum SyntaxKind {
     TerminalIf,
     TokenLoop,
     TerminalLoop,
+    TokenWhile,
+    TerminalWhile,
     TokenImpl,
     TerminalImpl,
     TokenImplicits,
@@ -302,6 +309,7 @@ impl SyntaxKind {
                 | SyntaxKind::TokenFunction
                 | SyntaxKind::TokenIf
                 | SyntaxKind::TokenLoop
+                | SyntaxKind::TokenWhile
                 | SyntaxKind::TokenImpl
                 | SyntaxKind::TokenImplicits
                 | SyntaxKind::TokenLet
@@ -387,6 +395,7 @@ impl SyntaxKind {
                 | SyntaxKind::TerminalFunction
                 | SyntaxKind::TerminalIf
                 | SyntaxKind::TerminalLoop
+                | SyntaxKind::TerminalWhile
                 | SyntaxKind::TerminalImpl
                 | SyntaxKind::TerminalImplicits
                 | SyntaxKind::TerminalLet
@@ -463,6 +472,7 @@ impl SyntaxKind {
                 | SyntaxKind::TokenFunction
                 | SyntaxKind::TokenIf
                 | SyntaxKind::TokenLoop
+                | SyntaxKind::TokenWhile
                 | SyntaxKind::TokenImpl
                 | SyntaxKind::TokenImplicits
                 | SyntaxKind::TokenLet
@@ -495,6 +505,7 @@ impl SyntaxKind {
                 | SyntaxKind::TerminalFunction
                 | SyntaxKind::TerminalIf
                 | SyntaxKind::TerminalLoop
+                | SyntaxKind::TerminalWhile
                 | SyntaxKind::TerminalImpl
                 | SyntaxKind::TerminalImplicits
                 | SyntaxKind::TerminalLet
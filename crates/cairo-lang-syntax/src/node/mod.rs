@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use cairo_lang_filesystem::ids::FileId;
 use cairo_lang_filesystem::span::{TextOffset, TextSpan, TextWidth};
+use cairo_lang_utils::ensure_sufficient_stack;
 use smol_str::SmolStr;
 
 use self::ast::TriviaGreen;
@@ -34,7 +35,7 @@ mod test_utils;
 /// SyntaxNode. Untyped view of the syntax tree. Adds parent() and offset() capabilities.
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct SyntaxNode(Arc<SyntaxNodeInner>);
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[derive(Clone, Debug)]
 struct SyntaxNodeInner {
     green: GreenId,
     /// Number of characters from the beginning of the file to the start of the span of this
@@ -43,6 +44,34 @@ struct SyntaxNodeInner {
     parent: Option<SyntaxNode>,
     stable_ptr: SyntaxStablePtrId,
 }
+// The parent is not compared nor hashed, as it is determined by the stable pointer (which leads to
+// the root of the file, including its green tree). This keeps these operations from recursing
+// over all the ancestors of the node.
+impl PartialEq for SyntaxNodeInner {
+    fn eq(&self, other: &Self) -> bool {
+        self.stable_ptr == other.stable_ptr
+            && self.green == other.green
+            && self.offset == other.offset
+    }
+}
+impl Eq for SyntaxNodeInner {}
+impl Hash for SyntaxNodeInner {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.green.hash(state);
+        self.offset.hash(state);
+        self.stable_ptr.hash(state);
+    }
+}
+// Releases the ancestors iteratively, as dropping the last reference to a deep node would
+// otherwise recursively drop all of its ancestors.
+impl Drop for SyntaxNodeInner {
+    fn drop(&mut self) {
+        let mut parent = self.parent.take();
+        while let Some(SyntaxNode(node)) = parent {
+            parent = Arc::into_inner(node).and_then(|mut node| node.parent.take());
+        }
+    }
+}
 impl SyntaxNode {
     pub fn new_root(db: &dyn SyntaxGroup, file_id: FileId, green: GreenId) -> Self {
         let inner = SyntaxNodeInner {
@@ -113,7 +142,7 @@ impl SyntaxNode {
                 if let Some(child) =
                     children.iter().find(|child| child.width(db) != TextWidth::default())
                 {
-                    child.span_start_without_trivia(db)
+                    ensure_sufficient_stack(|| child.span_start_without_trivia(db))
                 } else {
                     self.offset()
                 }
@@ -132,7 +161,7 @@ impl SyntaxNode {
                 if let Some(child) =
                     children.iter().filter(|child| child.width(db) != TextWidth::default()).last()
                 {
-                    child.span_end_without_trivia(db)
+                    ensure_sufficient_stack(|| child.span_end_without_trivia(db))
                 } else {
                     self.span(db).end
                 }
@@ -145,7 +174,7 @@ impl SyntaxNode {
     pub fn lookup_offset(&self, db: &dyn SyntaxGroup, offset: TextOffset) -> SyntaxNode {
         for child in db.get_children(self.clone()).iter() {
             if child.offset().add_width(child.width(db)) > offset {
-                return child.lookup_offset(db, offset);
+                return ensure_sufficient_stack(|| child.lookup_offset(db, offset));
             }
         }
         self.clone()
@@ -251,7 +280,9 @@ impl<'a> Display for NodeTextFormatter<'a> {
             green::GreenNodeDetails::Token(text) => write!(f, "{text}")?,
             green::GreenNodeDetails::Node { .. } => {
                 for child in self.db.get_children(self.node.clone()).iter() {
-                    write!(f, "{}", NodeTextFormatter { node: child, db: self.db })?;
+                    ensure_sufficient_stack(|| {
+                        write!(f, "{}", NodeTextFormatter { node: child, db: self.db })
+                    })?;
                 }
             }
         }
@@ -98,21 +98,7 @@ pub fn compile_test_prepared_db(
 
     let named_tests = all_tests
         .into_iter()
-        .map(|(func_id, test)| {
-            (
-                format!(
-                    "{:?}",
-                    FunctionLongId {
-                        function: ConcreteFunction {
-                            generic_function: GenericFunctionId::Free(func_id),
-                            generic_args: vec![]
-                        }
-                    }
-                    .debug(db)
-                ),
-                test,
-            )
-        })
+        .map(|(func_id, test)| (test_name(db, func_id), test))
         .collect_vec();
     let contracts_info = get_contracts_info(db, main_crate_ids.clone(), &replacer)?;
 
@@ -136,6 +122,20 @@ pub struct TestCompilation {
     pub sierra_program: Program,
 }
 
+/// Returns the name of a test function, by which it is found in the compiled tests.
+pub fn test_name(db: &(dyn SemanticGroup + 'static), func_id: FreeFunctionId) -> String {
+    format!(
+        "{:?}",
+        FunctionLongId {
+            function: ConcreteFunction {
+                generic_function: GenericFunctionId::Free(func_id),
+                generic_args: vec![]
+            }
+        }
+        .debug(db)
+    )
+}
+
 /// Finds the tests in the requested crates.
 fn find_all_tests(
     db: &dyn SemanticGroup,
@@ -1,4 +1,5 @@
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::vec::IntoIter;
 
@@ -10,7 +11,7 @@ use cairo_lang_compiler::project::setup_project;
 use cairo_lang_filesystem::cfg::{Cfg, CfgSet};
 use cairo_lang_filesystem::ids::CrateId;
 use cairo_lang_runner::casm_run::format_next_item;
-use cairo_lang_runner::{RunResultValue, SierraCasmRunner};
+use cairo_lang_runner::{FunctionResources, RunResultValue, SierraCasmRunner, StarknetState};
 use cairo_lang_sierra::extensions::gas::CostTokenType;
 use cairo_lang_sierra::ids::FunctionId;
 use cairo_lang_sierra::program::Program;
@@ -89,6 +90,7 @@ impl CompiledTestRunner {
             compiled.sierra_program,
             compiled.function_set_costs,
             compiled.contracts_info,
+            self.config.state_artifacts_dir.as_deref(),
         )?;
 
         if failed.is_empty() {
@@ -126,7 +128,7 @@ impl CompiledTestRunner {
 }
 
 /// Formats the given felts as a panic string.
-fn format_for_panic(mut felts: IntoIter<Felt252>) -> String {
+pub fn format_for_panic(mut felts: IntoIter<Felt252>) -> String {
     let mut items = Vec::new();
     while let Some(item) = format_next_item(&mut felts) {
         items.push(item.quote_if_string());
@@ -142,6 +144,8 @@ pub struct TestRunConfig {
     pub filter: String,
     pub include_ignored: bool,
     pub ignored: bool,
+    /// The directory to write the Starknet state artifact of each ran test to, if any.
+    pub state_artifacts_dir: Option<PathBuf>,
 }
 
 /// The test cases compiler.
@@ -232,17 +236,21 @@ pub fn filter_test_cases(
 }
 
 /// The status of a ran test.
-enum TestStatus {
+pub enum TestStatus {
     Success,
     Fail(RunResultValue),
 }
 
 /// The result of a ran test.
-struct TestResult {
+pub struct TestResult {
     /// The status of the run.
-    status: TestStatus,
+    pub status: TestStatus,
     /// The gas usage of the run if relevant.
-    gas_usage: Option<i64>,
+    pub gas_usage: Option<i64>,
+    /// The resources used by each function called in the run, if the run was profiled.
+    pub resources: Option<OrderedHashMap<String, FunctionResources>>,
+    /// The simulated Starknet state at the end of the run.
+    pub starknet_state: StarknetState,
 }
 
 /// Summary data of the ran tests.
@@ -259,17 +267,9 @@ pub fn run_tests(
     sierra_program: Program,
     function_set_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
     contracts_info: OrderedHashMap<Felt252, ContractInfo>,
+    state_artifacts_dir: Option<&Path>,
 ) -> Result<TestsSummary> {
-    let runner = SierraCasmRunner::new(
-        sierra_program,
-        Some(MetadataComputationConfig {
-            function_set_costs,
-            linear_gas_solver: true,
-            linear_ap_change_solver: true,
-        }),
-        contracts_info,
-    )
-    .with_context(|| "Failed setting up runner.")?;
+    let runner = create_test_runner(sierra_program, function_set_costs, contracts_info)?;
     println!("running {} tests", named_tests.len());
     let wrapped_summary = Mutex::new(Ok(TestsSummary {
         passed: vec![],
@@ -283,44 +283,11 @@ pub fn run_tests(
             if test.ignored {
                 return Ok((name, None));
             }
-            let func = runner.find_function(name.as_str())?;
-            let result = runner
-                .run_function_with_starknet_context(
-                    func,
-                    &[],
-                    test.available_gas,
-                    Default::default(),
-                )
-                .with_context(|| format!("Failed to run the function `{}`.", name.as_str()))?;
-            Ok((
-                name,
-                Some(TestResult {
-                    status: match &result.value {
-                        RunResultValue::Success(_) => match test.expectation {
-                            TestExpectation::Success => TestStatus::Success,
-                            TestExpectation::Panics(_) => TestStatus::Fail(result.value),
-                        },
-                        RunResultValue::Panic(value) => match test.expectation {
-                            TestExpectation::Success => TestStatus::Fail(result.value),
-                            TestExpectation::Panics(panic_expectation) => match panic_expectation {
-                                PanicExpectation::Exact(expected) if value != &expected => {
-                                    TestStatus::Fail(result.value)
-                                }
-                                _ => TestStatus::Success,
-                            },
-                        },
-                    },
-                    gas_usage: test
-                        .available_gas
-                        .zip(result.gas_counter)
-                        .map(|(before, after)| {
-                            before.into_or_panic::<i64>() - after.to_bigint().to_i64().unwrap()
-                        })
-                        .or_else(|| {
-                            runner.initial_required_gas(func).map(|gas| gas.into_or_panic::<i64>())
-                        }),
-                }),
-            ))
+            let result = run_single_test(&runner, &name, test, false)?;
+            if let Some(state_artifacts_dir) = state_artifacts_dir {
+                write_state_artifact(state_artifacts_dir, &name, &result.starknet_state)?;
+            }
+            Ok((name, Some(result)))
         })
         .for_each(|r| {
             let mut wrapped_summary = wrapped_summary.lock().unwrap();
@@ -336,10 +303,10 @@ pub fn run_tests(
             };
             let summary = wrapped_summary.as_mut().unwrap();
             let (res_type, status_str, gas_usage) = match status {
-                Some(TestResult { status: TestStatus::Success, gas_usage }) => {
+                Some(TestResult { status: TestStatus::Success, gas_usage, .. }) => {
                     (&mut summary.passed, "ok".bright_green(), gas_usage)
                 }
-                Some(TestResult { status: TestStatus::Fail(run_result), gas_usage }) => {
+                Some(TestResult { status: TestStatus::Fail(run_result), gas_usage, .. }) => {
                     summary.failed_run_results.push(run_result);
                     (&mut summary.failed, "fail".bright_red(), gas_usage)
                 }
@@ -354,3 +321,80 @@ pub fn run_tests(
         });
     wrapped_summary.into_inner().unwrap()
 }
+
+/// Sets up a runner for the compiled tests.
+pub fn create_test_runner(
+    sierra_program: Program,
+    function_set_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
+    contracts_info: OrderedHashMap<Felt252, ContractInfo>,
+) -> Result<SierraCasmRunner> {
+    SierraCasmRunner::new(
+        sierra_program,
+        Some(MetadataComputationConfig {
+            function_set_costs,
+            linear_gas_solver: true,
+            linear_ap_change_solver: true,
+            libfunc_extensions: Default::default(),
+        }),
+        contracts_info,
+    )
+    .with_context(|| "Failed setting up runner.")
+}
+
+/// Runs a single test that is not ignored, and checks its result against the test expectation.
+/// If `profile` is true, also collects the resources used by each function called in the run.
+pub fn run_single_test(
+    runner: &SierraCasmRunner,
+    name: &str,
+    test: TestConfig,
+    profile: bool,
+) -> Result<TestResult> {
+    let func = runner.find_function(name)?;
+    let (result, resources) = if profile {
+        let (result, resources) = runner
+            .run_function_with_resources(func, &[], test.available_gas, Default::default())
+            .with_context(|| format!("Failed to run the function `{name}`."))?;
+        (result, Some(resources))
+    } else {
+        let result = runner
+            .run_function_with_starknet_context(func, &[], test.available_gas, Default::default())
+            .with_context(|| format!("Failed to run the function `{name}`."))?;
+        (result, None)
+    };
+    Ok(TestResult {
+        status: match &result.value {
+            RunResultValue::Success(_) => match test.expectation {
+                TestExpectation::Success => TestStatus::Success,
+                TestExpectation::Panics(_) => TestStatus::Fail(result.value),
+            },
+            RunResultValue::Panic(value) => match test.expectation {
+                TestExpectation::Success => TestStatus::Fail(result.value),
+                TestExpectation::Panics(panic_expectation) => match panic_expectation {
+                    PanicExpectation::Exact(expected) if value != &expected => {
+                        TestStatus::Fail(result.value)
+                    }
+                    _ => TestStatus::Success,
+                },
+            },
+        },
+        gas_usage: test
+            .available_gas
+            .zip(result.gas_counter)
+            .map(|(before, after)| {
+                before.into_or_panic::<i64>() - after.to_bigint().to_i64().unwrap()
+            })
+            .or_else(|| runner.initial_required_gas(func).map(|gas| gas.into_or_panic::<i64>())),
+        resources,
+        starknet_state: result.starknet_state,
+    })
+}
+
+/// Writes the changes a test made to the simulated Starknet state, along with the events it
+/// emitted, as a JSON artifact named after the test.
+fn write_state_artifact(dir: &Path, name: &str, starknet_state: &StarknetState) -> Result<()> {
+    let path = dir.join(format!("{}.json", name.replace("::", "__")));
+    let artifact = serde_json::to_string_pretty(&starknet_state.artifact())
+        .with_context(|| "Serialization failed.")?;
+    fs::write(&path, artifact)
+        .with_context(|| format!("Failed to write the state artifact `{}`.", path.display()))
+}
@@ -37,6 +37,19 @@ pub fn write_comma_separated<Iter: IntoIterator<Item = V>, V: std::fmt::Display>
     Ok(())
 }
 
+/// The minimal stack space left before [ensure_sufficient_stack] grows the stack.
+const STACK_RED_ZONE: usize = 100 * 1024;
+/// The size of each stack segment allocated by [ensure_sufficient_stack].
+const STACK_SEGMENT_SIZE: usize = 1024 * 1024;
+
+/// Runs the given closure, first growing the stack if it is about to run out.
+/// Should wrap recursive calls whose depth depends on the input (e.g. the nesting of syntax trees),
+/// so that deep inputs don't overflow the stack.
+#[inline]
+pub fn ensure_sufficient_stack<R>(f: impl FnOnce() -> R) -> R {
+    stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, f)
+}
+
 /// Helper operations on `Option<T>`.
 pub trait OptionHelper {
     fn on_none<F: FnOnce()>(self, f: F) -> Self;
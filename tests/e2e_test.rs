@@ -64,6 +64,7 @@ cairo_lang_test_utils::test_file_test_with_runner!(
         nullable: "nullable",
         poseidon: "poseidon",
         snapshot: "snapshot",
+        struct_: "struct",
         u128: "u128",
         u16: "u16",
         bytes31: "bytes31",
@@ -192,6 +193,7 @@ fn run_e2e_test(
         function_set_costs: enforced_costs,
         linear_gas_solver: false,
         linear_ap_change_solver: false,
+        libfunc_extensions: Default::default(),
     };
     let metadata = calc_metadata(&sierra_program, metadata_config.clone()).unwrap();
 
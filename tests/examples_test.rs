@@ -11,6 +11,7 @@ use cairo_lang_filesystem::db::FilesGroupEx;
 use cairo_lang_filesystem::flag::Flag;
 use cairo_lang_filesystem::ids::{CrateId, FlagId};
 use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
+use cairo_lang_runner::value_format::user_type_member_names;
 use cairo_lang_runner::{token_gas_cost, Arg, RunResultValue, SierraCasmRunner};
 use cairo_lang_sierra::extensions::gas::CostTokenType;
 use cairo_lang_sierra_generator::db::SierraGenGroup;
@@ -337,3 +338,37 @@ fn run_fib_array_len(#[case] n: usize, #[case] last: usize, example_dir_data: &E
         [_, _, actual_last, actual_len] if actual_last == &Felt252::from(last) && actual_len == &Felt252::from(n)
     );
 }
+
+#[rstest]
+#[case::fib_struct("fib_struct", &[1, 1, 9], "FibResult { value: 55, index: 9, nothing: () }")]
+#[case::fib_u128_checked_pass("fib_u128_checked", &[1, 1, 10], "Option::Some(89)")]
+#[case::fib_u128_checked_fail("fib_u128_checked", &[1, 1, 200], "Option::None")]
+#[case::fib_array("fib_array", &[6], "([1, 1, 2, 3, 5, 8], 8, 6)")]
+fn format_return_value_test(
+    #[case] name: &str,
+    #[case] params: &[usize],
+    #[case] expected: &str,
+    example_dir_data: &ExampleDirData,
+) {
+    let runner = SierraCasmRunner::new(
+        checked_compile_to_sierra(name, example_dir_data, false),
+        None,
+        Default::default(),
+    )
+    .expect("Failed setting up runner.");
+    let function = runner.find_function("").expect("Failed finding the function.");
+    let result = runner
+        .run_function_with_starknet_context(
+            function,
+            &params.iter().map(|param| Arg::Value(Felt252::from(*param))).collect_vec(),
+            None,
+            Default::default(),
+        )
+        .expect("Failed running the function.");
+    let values = extract_matches!(result.value, RunResultValue::Success);
+    let member_names = user_type_member_names(&*example_dir_data.0.lock().unwrap());
+    assert_eq!(
+        runner.format_return_value(function, &values, &result.memory, &member_names).as_deref(),
+        Some(expected)
+    );
+}
@@ -0,0 +1,301 @@
+//! Randomized checks that the hints emitted by libfunc implementations are sound - i.e. that the
+//! values they assign always satisfy the constraints asserted by the surrounding CASM.
+//!
+//! Each case wraps a hint-heavy libfunc in a small Cairo function, and runs it on boundary values
+//! as well as on random values of the parameter types. An unsound hint makes the VM fail on an
+//! assertion, and a hint that leads to a provable but wrong branch is caught by comparing the
+//! result with a reference implementation.
+
+use cairo_felt::Felt252;
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
+use cairo_lang_runner::{Arg, RunResultValue, SierraCasmRunner};
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_sierra_generator::db::SierraGenGroup;
+use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
+use indoc::indoc;
+use itertools::Itertools;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rstest::{fixture, rstest};
+
+/// The number of random inputs each libfunc is checked on, in addition to the boundary values.
+const N_RANDOM_SAMPLES: usize = 100;
+
+/// Wrappers of the checked libfuncs. Results are flattened into felts, so they can be compared to
+/// the reference implementations regardless of the memory representation of enums.
+const WRAPPERS_CODE: &str = indoc! {"
+    use integer::{u512, U128sFromFelt252Result};
+
+    fn u128_overflowing_add(a: u128, b: u128) -> (felt252, u128) {
+        match integer::u128_overflowing_add(a, b) {
+            Result::Ok(v) => (0, v),
+            Result::Err(v) => (1, v),
+        }
+    }
+    fn u128_overflowing_sub(a: u128, b: u128) -> (felt252, u128) {
+        match integer::u128_overflowing_sub(a, b) {
+            Result::Ok(v) => (0, v),
+            Result::Err(v) => (1, v),
+        }
+    }
+    fn u128_lt(a: u128, b: u128) -> felt252 {
+        if a < b {
+            1
+        } else {
+            0
+        }
+    }
+    fn u64_overflowing_add(a: u64, b: u64) -> (felt252, u64) {
+        match integer::u64_overflowing_add(a, b) {
+            Result::Ok(v) => (0, v),
+            Result::Err(v) => (1, v),
+        }
+    }
+    fn u8_overflowing_sub(a: u8, b: u8) -> (felt252, u8) {
+        match integer::u8_overflowing_sub(a, b) {
+            Result::Ok(v) => (0, v),
+            Result::Err(v) => (1, v),
+        }
+    }
+    fn u128_wide_mul(a: u128, b: u128) -> (u128, u128) {
+        integer::u128_wide_mul(a, b)
+    }
+    fn u128_safe_divmod(a: u128, b: NonZero<u128>) -> (u128, u128) {
+        integer::u128_safe_divmod(a, b)
+    }
+    fn u128_sqrt(a: u128) -> u64 {
+        integer::u128_sqrt(a)
+    }
+    fn u128s_from_felt252(a: felt252) -> (felt252, u128, u128) {
+        match integer::u128s_from_felt252(a) {
+            U128sFromFelt252Result::Narrow(low) => (0, 0, low),
+            U128sFromFelt252Result::Wide((high, low)) => (1, high, low),
+        }
+    }
+    fn downcast_u128_u8(a: u128) -> (felt252, felt252) {
+        match integer::downcast::<u128, u8>(a) {
+            Option::Some(v) => (0, v.into()),
+            Option::None => (1, 0),
+        }
+    }
+    fn u256_safe_div_rem(a: u256, b: NonZero<u256>) -> (u256, u256) {
+        integer::u256_safe_div_rem(a, b)
+    }
+    fn u256_sqrt(a: u256) -> u128 {
+        integer::u256_sqrt(a)
+    }
+    fn u512_safe_div_rem_by_u256(a: u512, b: NonZero<u256>) -> (u512, u256) {
+        integer::u512_safe_div_rem_by_u256(a, b)
+    }
+    fn felt252_checked_inverse(a: felt252) -> (felt252, felt252) {
+        match core::felt252_checked_inverse(a) {
+            Option::Some(v) => (0, v.into()),
+            Option::None => (1, 0),
+        }
+    }
+"};
+
+/// The set of values a parameter of a checked libfunc may take.
+#[derive(Clone, Copy, Debug)]
+enum Domain {
+    /// An unsigned integer of the given bit width, split into 128-bit limbs when wider than 128.
+    Uint(u64),
+    /// A non-zero unsigned integer of the given bit width.
+    NonZeroUint(u64),
+    /// Any felt252 value.
+    Felt252,
+}
+impl Domain {
+    /// The exclusive upper bound of the domain.
+    fn bound(self) -> BigUint {
+        match self {
+            Domain::Uint(bits) | Domain::NonZeroUint(bits) => BigUint::one() << bits,
+            Domain::Felt252 => Felt252::prime(),
+        }
+    }
+
+    /// Values at the edges of the domain, where off-by-one hints tend to break.
+    fn boundary_values(self) -> Vec<BigUint> {
+        let bound = self.bound();
+        let mut values = vec![BigUint::zero(), BigUint::one(), &bound - 1u32, &bound >> 1];
+        if bound > BigUint::one() << 128 {
+            values.extend([(BigUint::one() << 128) - 1u32, BigUint::one() << 128]);
+        }
+        if matches!(self, Domain::NonZeroUint(_)) {
+            values.retain(|value| !value.is_zero());
+        }
+        values.into_iter().unique().collect()
+    }
+
+    /// A uniformly random value from the domain.
+    fn sample(self, rng: &mut StdRng) -> BigUint {
+        let bound = self.bound();
+        loop {
+            let bytes: Vec<u8> = (0..32 * 2).map(|_| rng.gen()).collect();
+            let value = BigUint::from_bytes_le(&bytes) % &bound;
+            if !(matches!(self, Domain::NonZeroUint(_)) && value.is_zero()) {
+                return value;
+            }
+        }
+    }
+
+    /// The felts representing the value when passed as an argument.
+    fn to_args(self, value: &BigUint) -> Vec<Arg> {
+        match self {
+            Domain::Uint(bits) | Domain::NonZeroUint(bits) if bits > 128 => {
+                split_u128_limbs(value, (bits / 128) as usize)
+                    .into_iter()
+                    .map(|limb| Arg::Value(limb.into()))
+                    .collect()
+            }
+            _ => vec![Arg::Value(value.into())],
+        }
+    }
+}
+
+/// Splits a value into `n_limbs` 128-bit limbs, least significant first.
+fn split_u128_limbs(value: &BigUint, n_limbs: usize) -> Vec<BigUint> {
+    let mask = (BigUint::one() << 128) - 1u32;
+    (0..n_limbs).map(|i| (value >> (128 * i)) & &mask).collect()
+}
+
+/// Computes the expected flattened result of a wrapper from its inputs.
+type Reference = fn(&[BigUint]) -> Vec<BigUint>;
+
+fn overflowing(value: BigUint, bits: u64) -> Vec<BigUint> {
+    let bound = BigUint::one() << bits;
+    vec![BigUint::from(u32::from(value >= bound)), value % bound]
+}
+fn overflowing_add_reference<const BITS: u64>(args: &[BigUint]) -> Vec<BigUint> {
+    overflowing(&args[0] + &args[1], BITS)
+}
+fn overflowing_sub_reference<const BITS: u64>(args: &[BigUint]) -> Vec<BigUint> {
+    let bound = BigUint::one() << BITS;
+    let [a, b] = args else { unreachable!() };
+    if a >= b { vec![BigUint::zero(), a - b] } else { vec![BigUint::one(), a + bound - b] }
+}
+fn lt_reference(args: &[BigUint]) -> Vec<BigUint> {
+    vec![BigUint::from(u32::from(args[0] < args[1]))]
+}
+fn wide_mul_reference(args: &[BigUint]) -> Vec<BigUint> {
+    split_u128_limbs(&(&args[0] * &args[1]), 2).into_iter().rev().collect()
+}
+fn divmod_reference(args: &[BigUint]) -> Vec<BigUint> {
+    vec![&args[0] / &args[1], &args[0] % &args[1]]
+}
+fn sqrt_reference(args: &[BigUint]) -> Vec<BigUint> {
+    vec![args[0].sqrt()]
+}
+fn u128s_from_felt252_reference(args: &[BigUint]) -> Vec<BigUint> {
+    let [low, high] = <[_; 2]>::try_from(split_u128_limbs(&args[0], 2)).unwrap();
+    vec![BigUint::from(u32::from(!high.is_zero())), high, low]
+}
+fn downcast_u8_reference(args: &[BigUint]) -> Vec<BigUint> {
+    if args[0] < BigUint::from(256u32) {
+        vec![BigUint::zero(), args[0].clone()]
+    } else {
+        vec![BigUint::one(), BigUint::zero()]
+    }
+}
+fn u256_div_rem_reference(args: &[BigUint]) -> Vec<BigUint> {
+    [&args[0] / &args[1], &args[0] % &args[1]]
+        .iter()
+        .flat_map(|value| split_u128_limbs(value, 2))
+        .collect()
+}
+fn u512_div_rem_reference(args: &[BigUint]) -> Vec<BigUint> {
+    let mut result = split_u128_limbs(&(&args[0] / &args[1]), 4);
+    result.extend(split_u128_limbs(&(&args[0] % &args[1]), 2));
+    result
+}
+fn felt252_inverse_reference(args: &[BigUint]) -> Vec<BigUint> {
+    if args[0].is_zero() {
+        return vec![BigUint::one(), BigUint::zero()];
+    }
+    let prime = Felt252::prime();
+    vec![BigUint::zero(), args[0].modpow(&(&prime - 2u32), &prime)]
+}
+
+/// Compiles the wrappers into a runner shared by all the cases.
+#[fixture]
+#[once]
+fn runner() -> SierraCasmRunner {
+    let db = &mut RootDatabase::builder().detect_corelib().build().unwrap();
+    let test_module = setup_test_module(db, WRAPPERS_CODE).unwrap();
+    DiagnosticsReporter::stderr().with_crates(&[test_module.crate_id]).ensure(db).unwrap();
+    let sierra_program = db.get_sierra_program(vec![test_module.crate_id]).unwrap();
+    SierraCasmRunner::new(
+        replace_sierra_ids_in_program(db, &sierra_program),
+        None,
+        Default::default(),
+    )
+    .expect("Failed setting up runner.")
+}
+
+#[rstest]
+#[case::u128_overflowing_add(
+    "u128_overflowing_add", &[Domain::Uint(128), Domain::Uint(128)], overflowing_add_reference::<128>
+)]
+#[case::u128_overflowing_sub(
+    "u128_overflowing_sub", &[Domain::Uint(128), Domain::Uint(128)], overflowing_sub_reference::<128>
+)]
+#[case::u128_lt("u128_lt", &[Domain::Uint(128), Domain::Uint(128)], lt_reference)]
+#[case::u64_overflowing_add(
+    "u64_overflowing_add", &[Domain::Uint(64), Domain::Uint(64)], overflowing_add_reference::<64>
+)]
+#[case::u8_overflowing_sub(
+    "u8_overflowing_sub", &[Domain::Uint(8), Domain::Uint(8)], overflowing_sub_reference::<8>
+)]
+#[case::u128_wide_mul("u128_wide_mul", &[Domain::Uint(128), Domain::Uint(128)], wide_mul_reference)]
+#[case::u128_safe_divmod(
+    "u128_safe_divmod", &[Domain::Uint(128), Domain::NonZeroUint(128)], divmod_reference
+)]
+#[case::u128_sqrt("u128_sqrt", &[Domain::Uint(128)], sqrt_reference)]
+#[case::u128s_from_felt252(
+    "u128s_from_felt252", &[Domain::Felt252], u128s_from_felt252_reference
+)]
+#[case::downcast_u128_u8("downcast_u128_u8", &[Domain::Uint(128)], downcast_u8_reference)]
+#[case::u256_safe_div_rem(
+    "u256_safe_div_rem", &[Domain::Uint(256), Domain::NonZeroUint(256)], u256_div_rem_reference
+)]
+#[case::u256_sqrt("u256_sqrt", &[Domain::Uint(256)], sqrt_reference)]
+#[case::u512_safe_div_rem_by_u256(
+    "u512_safe_div_rem_by_u256",
+    &[Domain::Uint(512), Domain::NonZeroUint(256)],
+    u512_div_rem_reference
+)]
+#[case::felt252_checked_inverse(
+    "felt252_checked_inverse", &[Domain::Felt252], felt252_inverse_reference
+)]
+fn hint_soundness_test(
+    #[case] name: &str,
+    #[case] domains: &[Domain],
+    #[case] reference: Reference,
+    runner: &SierraCasmRunner,
+) {
+    let function = runner.find_function(&format!("::{name}")).unwrap();
+    let mut rng = StdRng::seed_from_u64(0);
+    let boundary_inputs =
+        domains.iter().map(|domain| domain.boundary_values()).multi_cartesian_product();
+    let random_inputs = (0..N_RANDOM_SAMPLES)
+        .map(|_| domains.iter().map(|domain| domain.sample(&mut rng)).collect_vec())
+        .collect_vec();
+    for inputs in boundary_inputs.chain(random_inputs) {
+        let args = domains
+            .iter()
+            .zip(&inputs)
+            .flat_map(|(domain, value)| domain.to_args(value))
+            .collect_vec();
+        let result = runner
+            .run_function_with_starknet_context(function, &args, None, Default::default())
+            .unwrap_or_else(|err| panic!("`{name}` failed on inputs {inputs:?}: {err}"));
+        assert_eq!(
+            result.value,
+            RunResultValue::Success(reference(&inputs).iter().map(Felt252::from).collect()),
+            "`{name}` returned an unexpected result on inputs {inputs:?}."
+        );
+    }
+}